@@ -56,6 +56,10 @@ export interface OutboundMessageEvent {
 	agent_id: string;
 	channel_id: string;
 	text: string;
+	cost_usd: number | null;
+	prompt_tokens: number | null;
+	completion_tokens: number | null;
+	latency_ms: number | null;
 }
 
 export interface OutboundMessageDeltaEvent {
@@ -66,6 +70,13 @@ export interface OutboundMessageDeltaEvent {
 	aggregated_text: string;
 }
 
+export interface OutboundReasoningEvent {
+	type: "outbound_reasoning";
+	agent_id: string;
+	channel_id: string;
+	text: string;
+}
+
 export interface TypingStateEvent {
 	type: "typing_state";
 	agent_id: string;
@@ -141,6 +152,7 @@ export interface ToolCompletedEvent {
 	process_id: string;
 	tool_name: string;
 	result: string;
+	truncated_bytes: number | null;
 }
 
 // -- OpenCode live transcript part types --
@@ -179,10 +191,79 @@ export interface CortexChatMessageEvent {
 	tool_calls?: CortexChatToolCall[];
 }
 
+export interface ProviderStatusEvent {
+	type: "provider_status";
+	provider: string;
+	model: string;
+	status:
+		| "rate_limited"
+		| "probing"
+		| "recovered"
+		| "model_fallback"
+		| "default_model_fallback"
+		| "base_url_failover"
+		| "tool_call_repair";
+	cooldown_secs?: number;
+	from_model?: string;
+	attempt?: number;
+	from_base_url?: string;
+	to_base_url?: string;
+	tool_name?: string;
+	outcome?: "lenient" | "model" | "failed";
+}
+
+export interface BroadcastTargetResult {
+	adapter: string;
+	target: string | null;
+	success: boolean;
+	error: string | null;
+}
+
+export interface BroadcastResponse {
+	success: boolean;
+	skipped: string | null;
+	results: BroadcastTargetResult[];
+}
+
+export interface CompactionTriggeredEvent {
+	type: "compaction_triggered";
+	agent_id: string;
+	channel_id: string;
+	threshold_reached: number;
+	reason: "token_ratio" | "turn_count";
+}
+
+export interface CompactionProgressEvent {
+	type: "compaction_progress";
+	agent_id: string;
+	channel_id: string;
+	status: string;
+}
+
+export interface CompactionCompletedEvent {
+	type: "compaction_completed";
+	agent_id: string;
+	channel_id: string;
+	/** 0 when nothing was compacted. */
+	turns_compacted: number;
+	summary_tokens: number;
+	summary_max_tokens: number;
+	/** Estimated token size of the channel history afterwards. */
+	context_tokens: number;
+}
+
+export interface MemoriesConsolidatedEvent {
+	type: "memories_consolidated";
+	agent_id: string;
+	memory_id: string;
+	archived_memory_ids: string[];
+}
+
 export type ApiEvent =
 	| InboundMessageEvent
 	| OutboundMessageEvent
 	| OutboundMessageDeltaEvent
+	| OutboundReasoningEvent
 	| TypingStateEvent
 	| WorkerStartedEvent
 	| WorkerStatusEvent
@@ -194,7 +275,12 @@ export type ApiEvent =
 	| ToolCompletedEvent
 	| OpenCodePartUpdatedEvent
 	| WorkerTextEvent
-	| CortexChatMessageEvent;
+	| CortexChatMessageEvent
+	| ProviderStatusEvent
+	| CompactionTriggeredEvent
+	| CompactionProgressEvent
+	| CompactionCompletedEvent
+	| MemoriesConsolidatedEvent;
 
 async function fetchJson<T>(path: string): Promise<T> {
 	const response = await fetch(`${getApiBase()}${path}`);
@@ -240,6 +326,22 @@ export interface MessagesResponse {
 	has_more: boolean;
 }
 
+export interface ConversationSearchHit {
+	id: string;
+	channel_id: string;
+	channel_name: string | null;
+	role: string;
+	sender_name: string | null;
+	created_at: string;
+	/** Excerpt around the match, with matched terms wrapped in `**`. */
+	snippet: string;
+}
+
+export interface ConversationSearchResponse {
+	results: ConversationSearchHit[];
+	has_more: boolean;
+}
+
 export interface WorkerStatusInfo {
 	id: string;
 	task: string;
@@ -264,22 +366,51 @@ export interface CompletedItemInfo {
 	result_summary: string;
 }
 
+export interface CompactionStatusInfo {
+	started_at: string;
+	status: string;
+}
+
 export interface StatusBlockSnapshot {
 	active_workers: WorkerStatusInfo[];
 	active_branches: BranchStatusInfo[];
 	completed_items: CompletedItemInfo[];
+	compaction: CompactionStatusInfo | null;
 }
 
 /** channel_id -> StatusBlockSnapshot */
 export type ChannelStatusResponse = Record<string, StatusBlockSnapshot>;
 
+export interface RecalledMemory {
+	id: string;
+	memory_type: string;
+	content: string;
+	score: number;
+	updated_at: string;
+}
+
+/** Memories recalled into the channel prompt for the latest user message. */
+export interface MemoryRecallPreview {
+	count: number;
+	order: "relevance" | "recency";
+	memories: RecalledMemory[];
+}
+
 export interface PromptInspectResponse {
 	channel_id: string;
 	system_prompt: string;
 	total_chars: number;
+	estimated_tokens: number;
+	token_budget: number | null;
+	trimmed_skills: string[];
+	output_language: string | null;
+	model: string;
+	persona: string | null;
+	memory_recall: MemoryRecallPreview;
 	history_length: number;
 	history: unknown[];
 	capture_enabled: boolean;
+	show_reasoning: boolean;
 	/** Present when the channel is not active */
 	error?: string;
 	message?: string;
@@ -305,6 +436,16 @@ export interface PromptSnapshot {
 	system_prompt_chars: number;
 	history: unknown;
 	history_length: number;
+	model?: string | null;
+}
+
+export interface ReplayLastTurnResponse {
+	channel_id: string;
+	snapshot_timestamp_ms: number;
+	model: string;
+	user_message: string;
+	output: string;
+	tool_calls: { name: string; arguments: unknown }[];
 }
 
 export interface PromptCaptureResponse {
@@ -312,6 +453,45 @@ export interface PromptCaptureResponse {
 	capture_enabled: boolean;
 }
 
+export interface OutputLanguageResponse {
+	channel_id: string;
+	output_language: string | null;
+}
+
+export interface ChannelModelResponse {
+	channel_id: string;
+	model: string;
+	pinned: boolean;
+}
+
+export interface ShowReasoningResponse {
+	channel_id: string;
+	show_reasoning: boolean;
+}
+
+export interface PinnedContext {
+	id: string;
+	text: string;
+	pinned_at: string;
+}
+
+export interface PinsResponse {
+	channel_id: string;
+	pins: PinnedContext[];
+}
+
+export interface Note {
+	/** Null for the agent-wide note. */
+	channel_id: string | null;
+	content: string;
+	created_at: string;
+	updated_at: string;
+}
+
+export interface NoteResponse {
+	note: Note | null;
+}
+
 // --- Workers API types ---
 
 export type ActionContent =
@@ -342,6 +522,14 @@ export interface WorkerRunInfo {
 	project_name: string | null;
 }
 
+export interface WorkerLogUsageResponse {
+	total_bytes: number;
+	file_count: number;
+	oldest: string | null;
+	retention_days: number | null;
+	max_total_bytes: number | null;
+}
+
 export interface WorkerDetailResponse {
 	id: string;
 	task: string;
@@ -696,6 +884,7 @@ export interface RoutingSection {
 export interface TuningSection {
 	max_concurrent_branches: number;
 	max_concurrent_workers: number;
+	max_concurrent_processes: number;
 	max_turns: number;
 	branch_max_turns: number;
 	context_window: number;
@@ -706,6 +895,8 @@ export interface CompactionSection {
 	background_threshold: number;
 	aggressive_threshold: number;
 	emergency_threshold: number;
+	summary_max_tokens: number | null;
+	max_turns_before_compact: number | null;
 }
 
 export interface CortexSection {
@@ -795,6 +986,7 @@ export interface RoutingUpdate {
 export interface TuningUpdate {
 	max_concurrent_branches?: number;
 	max_concurrent_workers?: number;
+	max_concurrent_processes?: number;
 	max_turns?: number;
 	branch_max_turns?: number;
 	context_window?: number;
@@ -805,6 +997,8 @@ export interface CompactionUpdate {
 	background_threshold?: number;
 	aggressive_threshold?: number;
 	emergency_threshold?: number;
+	summary_max_tokens?: number;
+	max_turns_before_compact?: number;
 }
 
 export interface CortexUpdate {
@@ -884,12 +1078,21 @@ export interface CronJobWithStats {
 	interval_secs: number;
 	delivery_target: string;
 	enabled: boolean;
+	paused: boolean;
 	run_once: boolean;
 	active_hours: [number, number] | null;
 	timeout_secs: number | null;
+	next_run_at: string | null;
 	success_count: number;
 	failure_count: number;
 	last_executed_at: string | null;
+	last_run: CronExecutionEntry | null;
+}
+
+export interface CronDeliveryResult {
+	target: string;
+	success: boolean;
+	error: string | null;
 }
 
 export interface CronExecutionEntry {
@@ -897,6 +1100,8 @@ export interface CronExecutionEntry {
 	executed_at: string;
 	success: boolean;
 	result_summary: string | null;
+	delivery_results: CronDeliveryResult[];
+	delivery_suppressed: boolean;
 }
 
 export interface CronListResponse {
@@ -913,6 +1118,14 @@ export interface CronActionResponse {
 	message: string;
 }
 
+export interface CronPauseResponse {
+	success: boolean;
+	cron_id: string;
+	paused: boolean;
+	next_run_at: string | null;
+	message: string;
+}
+
 export interface CreateCronRequest {
 	id: string;
 	prompt: string;
@@ -1253,6 +1466,7 @@ export interface BindingInfo {
 	channel_ids: string[];
 	require_mention: boolean;
 	dm_allowed_users: string[];
+	welcome_message: string | null;
 }
 
 export interface BindingsListResponse {
@@ -1269,6 +1483,7 @@ export interface CreateBindingRequest {
 	channel_ids?: string[];
 	require_mention?: boolean;
 	dm_allowed_users?: string[];
+	welcome_message?: string;
 	platform_credentials?: {
 		discord_token?: string;
 		slack_bot_token?: string;
@@ -1314,6 +1529,8 @@ export interface UpdateBindingRequest {
 	channel_ids?: string[];
 	require_mention?: boolean;
 	dm_allowed_users?: string[];
+	/** Omit to keep the current welcome message; empty string removes it. */
+	welcome_message?: string;
 }
 
 export interface UpdateBindingResponse {
@@ -1367,6 +1584,8 @@ export interface GlobalSettingsResponse {
 	api_port: number;
 	api_bind: string;
 	worker_log_mode: string;
+	worker_log_retention_days: number;
+	worker_log_max_total_mb: number;
 	opencode: OpenCodeSettings;
 }
 
@@ -1376,6 +1595,8 @@ export interface GlobalSettingsUpdate {
 	api_port?: number;
 	api_bind?: string;
 	worker_log_mode?: string;
+	worker_log_retention_days?: number;
+	worker_log_max_total_mb?: number;
 	opencode?: OpenCodeSettingsUpdate;
 }
 
@@ -1440,6 +1661,7 @@ export interface TopologyHuman {
 	telegram_id?: string;
 	slack_id?: string;
 	email?: string;
+	handoff_target?: string;
 }
 
 export interface TopologyResponse {
@@ -1459,6 +1681,7 @@ export interface CreateHumanRequest {
 	telegram_id?: string;
 	slack_id?: string;
 	email?: string;
+	handoff_target?: string;
 }
 
 export interface UpdateHumanRequest {
@@ -1470,6 +1693,7 @@ export interface UpdateHumanRequest {
 	telegram_id?: string;
 	slack_id?: string;
 	email?: string;
+	handoff_target?: string;
 }
 
 export interface CreateGroupRequest {
@@ -1697,6 +1921,16 @@ export const api = {
 		if (before) params.set("before", before);
 		return fetchJson<MessagesResponse>(`/channels/messages?${params}`);
 	},
+	searchConversations: (agentId: string, query: string, limit = 20, offset = 0) => {
+		const params = new URLSearchParams({
+			q: query,
+			limit: String(limit),
+			offset: String(offset),
+		});
+		return fetchJson<ConversationSearchResponse>(
+			`/agents/${encodeURIComponent(agentId)}/conversations/search?${params}`,
+		);
+	},
 	channelStatus: () => fetchJson<ChannelStatusResponse>("/channels/status"),
 	inspectPrompt: (channelId: string) =>
 		fetchJson<PromptInspectResponse>(`/channels/inspect?channel_id=${encodeURIComponent(channelId)}`),
@@ -1709,6 +1943,77 @@ export const api = {
 		if (!response.ok) throw new Error(`API error: ${response.status}`);
 		return response.json() as Promise<PromptCaptureResponse>;
 	},
+	setOutputLanguage: async (agentId: string, channelId: string, language: string | null) => {
+		const response = await fetch(`${getApiBase()}/channels/output-language`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, language }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<OutputLanguageResponse>;
+	},
+	setShowReasoning: async (agentId: string, channelId: string, enabled: boolean) => {
+		const response = await fetch(`${getApiBase()}/channels/reasoning`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, enabled }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<ShowReasoningResponse>;
+	},
+	listPins: (agentId: string, channelId: string) =>
+		fetchJson<PinsResponse>(
+			`/channels/pins?agent_id=${encodeURIComponent(agentId)}&channel_id=${encodeURIComponent(channelId)}`,
+		),
+	pinContext: async (agentId: string, channelId: string, text: string) => {
+		const response = await fetch(`${getApiBase()}/channels/pins`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, text }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<{ success: boolean; channel_id: string; pin: PinnedContext }>;
+	},
+	unpinContext: async (agentId: string, channelId: string, pinId: string) => {
+		const response = await fetch(
+			`${getApiBase()}/channels/pins?agent_id=${encodeURIComponent(agentId)}&channel_id=${encodeURIComponent(channelId)}&pin_id=${encodeURIComponent(pinId)}`,
+			{ method: "DELETE" },
+		);
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<{ success: boolean }>;
+	},
+	getNote: (agentId: string, channelId?: string) => {
+		const params = new URLSearchParams({ agent_id: agentId });
+		if (channelId) params.set("channel_id", channelId);
+		return fetchJson<NoteResponse>(`/agents/notes?${params}`);
+	},
+	replaceNote: async (agentId: string, content: string, channelId?: string) => {
+		const response = await fetch(`${getApiBase()}/agents/notes`, {
+			method: "PUT",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId ?? null, content }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<NoteResponse>;
+	},
+	appendNote: async (agentId: string, content: string, channelId?: string) => {
+		const response = await fetch(`${getApiBase()}/agents/notes/append`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId ?? null, content }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<NoteResponse>;
+	},
+	clearNote: async (agentId: string, channelId?: string) => {
+		const params = new URLSearchParams({ agent_id: agentId });
+		if (channelId) params.set("channel_id", channelId);
+		const response = await fetch(`${getApiBase()}/agents/notes?${params}`, {
+			method: "DELETE",
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<{ success: boolean; removed: boolean }>;
+	},
 	listPromptSnapshots: (channelId: string, limit = 50) =>
 		fetchJson<PromptSnapshotListResponse>(
 			`/channels/inspect/snapshots?channel_id=${encodeURIComponent(channelId)}&limit=${limit}`,
@@ -1717,6 +2022,15 @@ export const api = {
 		fetchJson<PromptSnapshot>(
 			`/channels/inspect/snapshot?channel_id=${encodeURIComponent(channelId)}&timestamp_ms=${timestampMs}`,
 		),
+	replayLastTurn: async (agentId: string, channelId: string, model?: string) => {
+		const response = await fetch(`${getApiBase()}/channels/inspect/replay-last`, {
+			method: "POST",
+			headers: { "Content-Type": "application/json" },
+			body: JSON.stringify({ agent_id: agentId, channel_id: channelId, model: model ?? null }),
+		});
+		if (!response.ok) throw new Error(`API error: ${response.status}`);
+		return response.json() as Promise<ReplayLastTurnResponse>;
+	},
 	workersList: (agentId: string, params: { limit?: number; offset?: number; status?: string } = {}) => {
 		const search = new URLSearchParams({ agent_id: agentId });
 		if (params.limit) search.set("limit", String(params.limit));
@@ -1726,6 +2040,8 @@ export const api = {
 	},
 	workerDetail: (agentId: string, workerId: string) =>
 		fetchJson<WorkerDetailResponse>(`/agents/workers/detail?agent_id=${encodeURIComponent(agentId)}&worker_id=${encodeURIComponent(workerId)}`),
+	workerLogUsage: (agentId: string) =>
+		fetchJson<WorkerLogUsageResponse>(`/agents/workers/logs/usage?agent_id=${encodeURIComponent(agentId)}`),
 	agentMemories: (agentId: string, params: MemoriesListParams = {}) => {
 		const search = new URLSearchParams({ agent_id: agentId });
 		if (params.limit) search.set("limit", String(params.limit));
@@ -1839,6 +2155,18 @@ export const api = {
 		return response.json() as Promise<{ success: boolean; message: string }>;
 	},
 
+	/** Restart a single agent from the current config.toml. Resolves once it is back up. */
+	restartAgent: async (agentId: string) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/restart`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ success: boolean; agent_id?: string; message: string }>;
+	},
+
 	/** Get the avatar URL for an agent (returns the raw URL, not fetched). */
 	agentAvatarUrl: (agentId: string) => `${getApiBase()}/agents/avatar?agent_id=${encodeURIComponent(agentId)}`,
 
@@ -1928,6 +2256,28 @@ export const api = {
 		return response.json() as Promise<CronActionResponse>;
 	},
 
+	pauseCronJob: async (agentId: string, cronId: string) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/cron/${encodeURIComponent(cronId)}/pause`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<CronPauseResponse>;
+	},
+
+	resumeCronJob: async (agentId: string, cronId: string) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/cron/${encodeURIComponent(cronId)}/resume`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<CronPauseResponse>;
+	},
+
 	triggerCronJob: async (agentId: string, cronId: string) => {
 		const response = await fetch(`${getApiBase()}/agents/cron/trigger`, {
 			method: "POST",
@@ -1952,6 +2302,47 @@ export const api = {
 		return response.json() as Promise<{ success: boolean; message: string }>;
 	},
 
+	cancelTurn: async (agentId: string, channelId: string) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/channels/${encodeURIComponent(channelId)}/cancel`,
+			{ method: "POST" },
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<{ success: boolean; cancelled: boolean }>;
+	},
+
+	setChannelModel: async (agentId: string, channelId: string, model: string | null) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/channels/${encodeURIComponent(channelId)}/model`,
+			{
+				method: "PUT",
+				headers: { "Content-Type": "application/json" },
+				body: JSON.stringify({ model }),
+			},
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<ChannelModelResponse>;
+	},
+
+	broadcast: async (agentId: string, text: string, respectQuietHours = false) => {
+		const response = await fetch(
+			`${getApiBase()}/agents/${encodeURIComponent(agentId)}/broadcast`,
+			{
+				method: "POST",
+				headers: { "Content-Type": "application/json" },
+				body: JSON.stringify({ text, respect_quiet_hours: respectQuietHours }),
+			},
+		);
+		if (!response.ok) {
+			throw new Error(`API error: ${response.status}`);
+		}
+		return response.json() as Promise<BroadcastResponse>;
+	},
+
 	// Provider management
 	providers: () => fetchJson<ProvidersResponse>("/providers"),
 	updateProvider: async (provider: string, apiKey: string, model: string) => {
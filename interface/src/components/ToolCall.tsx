@@ -1004,6 +1004,7 @@ function formatToolName(name: string): string {
 		webfetch: "Web Fetch",
 		todowrite: "Todo",
 		read_skill: "Read Skill",
+		list_skills: "List Skills",
 		web_search: "Web Search",
 		spacebot_docs: "Docs",
 	};
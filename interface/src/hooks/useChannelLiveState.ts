@@ -4,6 +4,9 @@ import {
 	api,
 	type BranchCompletedEvent,
 	type BranchStartedEvent,
+	type CompactionProgressEvent,
+	type CompactionTriggeredEvent,
+	type CompactionCompletedEvent,
 	type InboundMessageEvent,
 	type OutboundMessageDeltaEvent,
 	type OutboundMessageEvent,
@@ -52,6 +55,8 @@ export interface ChannelLiveState {
 	timeline: TimelineItem[];
 	workers: Record<string, ActiveWorker>;
 	branches: Record<string, ActiveBranch>;
+	/** Current stage of a running context compaction, or null when idle. */
+	compaction: string | null;
 	streamingMessageId: string | null;
 	historyLoaded: boolean;
 	hasMore: boolean;
@@ -66,6 +71,7 @@ function emptyLiveState(): ChannelLiveState {
 		timeline: [],
 		workers: {},
 		branches: {},
+		compaction: null,
 		streamingMessageId: null,
 		historyLoaded: false,
 		hasMore: true,
@@ -185,7 +191,12 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 							toolCalls: existingBranch?.toolCalls ?? 0,
 						};
 					}
-					next[channelId] = { ...existing, workers, branches };
+					next[channelId] = {
+						...existing,
+						workers,
+						branches,
+						compaction: snapshot.compaction?.status ?? null,
+					};
 				}
 				return next;
 			});
@@ -369,6 +380,28 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 		});
 	}, []);
 
+	const setCompaction = useCallback((channelId: string, compaction: string | null) => {
+		setLiveStates((prev) => {
+			const existing = getOrCreate(prev, channelId);
+			return { ...prev, [channelId]: { ...existing, compaction } };
+		});
+	}, []);
+
+	const handleCompactionTriggered = useCallback((data: unknown) => {
+		const event = data as CompactionTriggeredEvent;
+		setCompaction(event.channel_id, "starting");
+	}, [setCompaction]);
+
+	const handleCompactionProgress = useCallback((data: unknown) => {
+		const event = data as CompactionProgressEvent;
+		setCompaction(event.channel_id, event.status);
+	}, [setCompaction]);
+
+	const handleCompactionCompleted = useCallback((data: unknown) => {
+		const event = data as CompactionCompletedEvent;
+		setCompaction(event.channel_id, null);
+	}, [setCompaction]);
+
 	const handleWorkerStarted = useCallback((data: unknown) => {
 		const event = data as WorkerStartedEvent;
 		if (!event.channel_id) return;
@@ -815,6 +848,9 @@ export function useChannelLiveState(channels: ChannelInfo[]) {
 		branch_completed: handleBranchCompleted,
 		tool_started: handleToolStarted,
 		tool_completed: handleToolCompleted,
+		compaction_triggered: handleCompactionTriggered,
+		compaction_progress: handleCompactionProgress,
+		compaction_completed: handleCompactionCompleted,
 	};
 
 	return { liveStates, handlers, syncStatusSnapshot, loadOlderMessages };
@@ -943,6 +943,14 @@ function ConfigSectionEditor({ sectionId, label, description, detail, config, on
 							min={1}
 							max={20}
 						/>
+						<NumberStepper
+							label="Max Concurrent Processes"
+							description="Channel turns, branches, workers, and cortex runs at once across the agent (0 = unlimited)"
+							value={localValues.max_concurrent_processes as number}
+							onChange={(v) => handleChange("max_concurrent_processes", v)}
+							min={0}
+							max={100}
+						/>
 						<NumberStepper
 							label="Max Turns"
 							description="Max LLM turns per channel message"
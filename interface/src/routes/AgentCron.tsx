@@ -737,6 +737,9 @@ function JobExecutions({ agentId, jobId }: { agentId: string; jobId: string }) {
 					<span className="text-tiny tabular-nums text-ink-faint">
 						{formatTimeAgo(execution.executed_at)}
 					</span>
+					{execution.delivery_suppressed && (
+						<span className="text-tiny text-ink-faint">skipped</span>
+					)}
 					{execution.result_summary && (
 						<span className="min-w-0 flex-1 truncate text-tiny text-ink-dull">
 							{execution.result_summary}
@@ -148,7 +148,7 @@ export function AgentIngest({agentId}: AgentIngestProps) {
 				)}
 				<div className="flex-1" />
 				<span className="text-xs text-ink-faint">
-					.pdf .txt .md .json .csv .yaml .toml .html .log +more
+					.pdf .docx .txt .md .json .csv .yaml .toml .html .log +more
 				</span>
 			</div>
 
@@ -196,7 +196,7 @@ export function AgentIngest({agentId}: AgentIngestProps) {
 					type="file"
 					multiple
 					className="hidden"
-					accept=".pdf,.txt,.md,.markdown,.json,.jsonl,.csv,.tsv,.log,.xml,.yaml,.yml,.toml,.rst,.org,.html,.htm"
+					accept=".pdf,.docx,.txt,.md,.markdown,.json,.jsonl,.csv,.tsv,.log,.xml,.yaml,.yml,.toml,.rst,.org,.html,.htm"
 					onChange={(e) => {
 						if (e.target.files) {
 							handleFiles(e.target.files);
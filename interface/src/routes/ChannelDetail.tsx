@@ -287,6 +287,7 @@ export function ChannelDetail({ agentId, channelId, channel, liveState, onLoadMo
 	const hasMore = liveState?.hasMore ?? false;
 	const loadingMore = liveState?.loadingMore ?? false;
 	const isTyping = liveState?.isTyping ?? false;
+	const compaction = liveState?.compaction ?? null;
 	const workers = liveState?.workers ?? {};
 	const branches = liveState?.branches ?? {};
 	const activeWorkerCount = Object.keys(workers).length;
@@ -382,6 +383,12 @@ export function ChannelDetail({ agentId, channelId, channel, liveState, onLoadMo
 								<span className="ml-1 text-tiny text-ink-faint">typing</span>
 							</div>
 						)}
+						{compaction && (
+							<div className="flex items-center gap-1" title={compaction}>
+								<span className="inline-block h-1.5 w-1.5 animate-pulse rounded-full bg-amber-400" />
+								<span className="text-tiny text-ink-faint">compacting…</span>
+							</div>
+						)}
 						<div className="flex overflow-hidden rounded-md border border-app-line bg-app-darkBox">
 							<Button
 								aria-label="Inspect prompt"
@@ -0,0 +1,3 @@
+-- Per-target delivery outcomes for each cron execution, stored as a JSON
+-- array of {target, success, error}. NULL when nothing was delivered.
+ALTER TABLE cron_executions ADD COLUMN delivery_results TEXT;
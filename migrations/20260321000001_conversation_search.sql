@@ -0,0 +1,30 @@
+-- Full-text index over conversation transcripts for cross-channel search.
+-- External-content table: rows live in conversation_messages and the index
+-- is kept in sync by the triggers below.
+CREATE VIRTUAL TABLE IF NOT EXISTS conversation_messages_fts USING fts5(
+    content,
+    content='conversation_messages',
+    content_rowid='rowid',
+    tokenize='porter unicode61'
+);
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_insert
+AFTER INSERT ON conversation_messages BEGIN
+    INSERT INTO conversation_messages_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_delete
+AFTER DELETE ON conversation_messages BEGIN
+    INSERT INTO conversation_messages_fts(conversation_messages_fts, rowid, content)
+    VALUES ('delete', old.rowid, old.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS conversation_messages_fts_update
+AFTER UPDATE OF content ON conversation_messages BEGIN
+    INSERT INTO conversation_messages_fts(conversation_messages_fts, rowid, content)
+    VALUES ('delete', old.rowid, old.content);
+    INSERT INTO conversation_messages_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
+
+-- Index messages logged before this migration.
+INSERT INTO conversation_messages_fts(conversation_messages_fts) VALUES ('rebuild');
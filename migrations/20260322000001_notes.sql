@@ -0,0 +1,9 @@
+-- Agent scratchpad notes: free-form text the agent reads back verbatim.
+-- One row per scope. The empty scope is the agent-wide note; any other
+-- value is the channel ID the note belongs to.
+CREATE TABLE IF NOT EXISTS notes (
+    scope TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+-- Runtime pause flag for cron jobs, set through the API. Kept apart from
+-- `enabled` so seeding jobs from config on startup doesn't clear it.
+ALTER TABLE cron_jobs ADD COLUMN paused INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Set when a cron run called the skip tool, so its result was recorded but
+-- not delivered.
+ALTER TABLE cron_executions ADD COLUMN delivery_suppressed INTEGER NOT NULL DEFAULT 0;
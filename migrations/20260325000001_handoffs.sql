@@ -0,0 +1,18 @@
+-- Human handoffs: conversations the agent escalated to a person. While a
+-- channel has an open handoff the agent records messages but does not reply.
+CREATE TABLE IF NOT EXISTS handoffs (
+    id TEXT PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    human_id TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    -- `adapter:target` the notification went to, NULL if it could not be sent.
+    notified_target TEXT,
+    status TEXT NOT NULL DEFAULT 'open',
+    resolution TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMP
+);
+
+-- At most one open handoff per channel.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_handoffs_open_channel
+    ON handoffs(channel_id) WHERE status = 'open';
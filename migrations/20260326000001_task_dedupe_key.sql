@@ -0,0 +1,7 @@
+-- Normalized title of an automatically extracted task. Concurrent extractions
+-- of the same action item race to create it; the index lets only one insert
+-- win while the task is open.
+ALTER TABLE tasks ADD COLUMN dedupe_key TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_open_dedupe_key
+    ON tasks(agent_id, dedupe_key) WHERE dedupe_key IS NOT NULL AND status != 'done';
@@ -3,19 +3,27 @@
 pub mod branch;
 pub mod channel;
 pub mod channel_attachments;
+pub mod channel_buttons;
+pub mod channel_cancel;
 pub mod channel_dispatch;
 pub mod channel_history;
 pub mod channel_prompt;
+pub mod channel_reset;
 pub mod compactor;
 pub mod cortex;
 pub mod cortex_chat;
+pub mod inbound_limit;
 pub mod ingestion;
 #[cfg(test)]
 mod invariant_harness;
+pub mod media_safety;
 pub mod process_control;
+pub mod process_limit;
 pub mod prompt_snapshot;
 pub mod status;
+pub mod transcription;
 pub mod worker;
+pub mod worker_logs;
 
 pub(crate) fn panic_payload_to_string(panic_payload: &(dyn std::any::Any + Send)) -> String {
     panic_payload
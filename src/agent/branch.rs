@@ -1,6 +1,7 @@
 //! Branch: Fork context for thinking and delegation.
 
 use crate::agent::compactor::estimate_history_tokens;
+use crate::agent::process_limit::ProcessPriority;
 use crate::error::Result;
 use crate::hooks::SpacebotHook;
 use crate::llm::SpacebotModel;
@@ -62,7 +63,16 @@ impl Branch {
             ProcessType::Branch,
             Some(channel_id.clone()),
             deps.event_tx.clone(),
-        );
+        )
+        .with_disabled_tools(deps.runtime_config.disabled_tools.load_full())
+        .with_output_token_cap(
+            deps.runtime_config
+                .routing
+                .load()
+                .max_output_tokens(ProcessType::Branch),
+        )
+        .with_tool_call_limits(**deps.runtime_config.tool_call_limits.load())
+        .with_tool_server(tool_server.clone());
         if let Some(contract_state) = &execution_config.memory_persistence_contract {
             hook = hook.with_memory_persistence_contract(contract_state.clone());
         }
@@ -92,6 +102,10 @@ impl Branch {
     /// be large, making them susceptible to overflow on the first LLM call.
     pub async fn run(mut self, prompt: impl Into<String>) -> Result<String> {
         let prompt = prompt.into();
+        let _permit = self
+            .deps
+            .acquire_process_permit(ProcessPriority::Foreground)
+            .await;
 
         tracing::info!(
             branch_id = %self.id,
@@ -108,7 +122,8 @@ impl Branch {
         let model_name = routing.resolve(ProcessType::Branch, None).to_string();
         let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "branch")
-            .with_routing((**routing).clone());
+            .with_routing((**routing).clone())
+            .with_turn_budget(self.hook.turn_budget());
 
         let agent = AgentBuilder::new(model)
             .preamble(&self.system_prompt)
@@ -2,19 +2,30 @@
 
 use crate::agent::channel_attachments;
 use crate::agent::channel_attachments::download_attachments;
-use crate::agent::channel_dispatch::spawn_memory_persistence_branch;
+use crate::agent::channel_buttons::{ButtonActionKind, parse_button_action};
+use crate::agent::channel_cancel::{TURN_CANCELLED_REASON, TurnCancel};
+use crate::agent::channel_dispatch::{spawn_memory_persistence_branch, spawn_worker_from_state};
 use crate::agent::channel_history::{
-    apply_history_after_turn, event_is_for_channel, extract_message_id,
-    extract_reply_from_tool_syntax, format_batched_user_message, format_user_message,
-    message_display_name, pop_retrigger_bridge_message,
+    HistoryTurn, apply_history_after_turn, event_is_for_channel, extract_message_id,
+    extract_reply_from_tool_syntax, format_batched_user_message, format_reply_context,
+    format_user_message, message_display_name, pop_retrigger_bridge_message, prune_history_before,
 };
 use crate::agent::channel_prompt::{
     MAX_RETRIGGERS_PER_TURN, RETRIGGER_DEBOUNCE_MS, RETRIGGER_MAX_TURNS, TemporalContext,
+    recall_memories, render_recalled_memories, render_within_budget, resolve_channel_model,
+    resolve_conversation_slots, resolve_notes_context, resolve_output_language, resolve_persona,
+    resolve_pinned_context,
 };
+use crate::agent::channel_reset::{self, RESET_CONFIRMATION, ResetSignal};
 use crate::agent::compactor::Compactor;
+use crate::agent::inbound_limit::{self, LimitedInbound};
+use crate::agent::media_safety;
 use crate::agent::process_control::ControlActionResult;
+use crate::agent::process_limit::ProcessPriority;
 use crate::agent::status::{StatusBlock, SystemInfo};
+use crate::agent::transcription;
 use crate::agent::worker::Worker;
+use crate::config::ChatCommand;
 use crate::conversation::{ChannelStore, ConversationLogger, ProcessRunLogger};
 use crate::error::{AgentError, Result};
 use crate::hooks::SpacebotHook;
@@ -30,6 +41,7 @@ use rig::one_or_many::OneOrMany;
 use rig::tool::server::ToolServer;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::sync::{Arc, Weak};
 use tokio::sync::broadcast;
 use tokio::sync::{RwLock, mpsc};
@@ -86,7 +98,12 @@ fn should_flush_coalesce_buffer_for_event(event: &ProcessEvent) -> bool {
 #[derive(Clone)]
 pub struct ChannelState {
     pub channel_id: ChannelId,
+    /// When this channel's event loop was created.
+    pub started_at: chrono::DateTime<chrono::Utc>,
     pub history: Arc<RwLock<Vec<rig::message::Message>>>,
+    /// Start times of the turns in `history`, recorded while
+    /// `history_max_age_days` is set so expired turns can be pruned.
+    pub(crate) history_turns: Arc<RwLock<VecDeque<HistoryTurn>>>,
     pub active_branches: Arc<RwLock<HashMap<BranchId, tokio::task::JoinHandle<()>>>>,
     pub active_workers: Arc<RwLock<HashMap<WorkerId, Worker>>>,
     /// Tokio task handles for running workers, used for cancellation via abort().
@@ -98,6 +115,9 @@ pub struct ChannelState {
     /// Used by the route tool to deliver addendum context to running workers
     /// without requiring the worker to be interactive.
     pub worker_injections: Arc<RwLock<HashMap<WorkerId, tokio::sync::mpsc::Sender<String>>>>,
+    /// Cancel signals for builtin workers, keyed by worker ID. Raised just
+    /// before a worker is aborted so it stops waiting on a retry backoff.
+    pub worker_cancels: Arc<RwLock<HashMap<WorkerId, tokio::sync::watch::Sender<bool>>>>,
     /// Task descriptions reserved for spawn. Prevents the TOCTOU race where
     /// two concurrent `spawn_worker` calls both pass `check_duplicate_task`
     /// before either registers in the status block. Reservations are
@@ -124,6 +144,12 @@ pub struct ChannelState {
     /// `ToolStarted`/`ToolCompleted` events as they flow through the system.
     /// Defaults to a standalone empty map when the API layer is not active.
     pub live_worker_transcripts: LiveWorkerTranscripts,
+    /// Resets requested by tools or the API, finished by the channel loop.
+    pub reset_signal: Arc<ResetSignal>,
+    /// Lets the API stop the turn in flight.
+    pub turn_cancel: Arc<TurnCancel>,
+    /// Whether the channel is handed off to a human.
+    pub handoff: Arc<crate::handoff::HandoffState>,
 }
 
 impl ChannelState {
@@ -155,6 +181,9 @@ impl ChannelState {
             .remove(&worker_id)
             .is_some();
         self.worker_injections.write().await.remove(&worker_id);
+        if let Some(cancel_tx) = self.worker_cancels.write().await.remove(&worker_id) {
+            cancel_tx.send_replace(true);
+        }
         let removed_status = self.status_block.write().await.remove_worker(worker_id);
         let should_emit = removed || handle.is_some();
 
@@ -383,6 +412,85 @@ impl WeakChannelControlHandle {
     }
 }
 
+/// Idle tracking and shutdown signal for a running channel.
+///
+/// The main loop polls `idle_for` and calls `shutdown` to tear down channels
+/// that have gone quiet. Dropping the message sender is not enough to stop a
+/// channel because it keeps its own `self_tx` for retriggers.
+#[derive(Clone)]
+pub struct ChannelIdleHandle {
+    last_activity: Arc<std::sync::Mutex<std::time::Instant>>,
+    shutdown: Arc<tokio::sync::Notify>,
+    /// Set while an inactivity follow-up is scheduled, so eviction waits
+    /// for it to go out.
+    followup_pending: Arc<std::sync::atomic::AtomicBool>,
+    /// Set while the event loop is handling a message, event, or timer, so a
+    /// channel is never evicted mid-turn.
+    turn_active: Arc<std::sync::atomic::AtomicBool>,
+    state: ChannelState,
+}
+
+/// Clears [`ChannelIdleHandle::turn_active`] when the handled message or
+/// event is done, including on early returns.
+struct TurnActiveGuard(Arc<std::sync::atomic::AtomicBool>);
+
+impl Drop for TurnActiveGuard {
+    fn drop(&mut self) {
+        self.0.store(false, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+impl ChannelIdleHandle {
+    fn new(state: ChannelState) -> Self {
+        Self {
+            last_activity: Arc::new(std::sync::Mutex::new(std::time::Instant::now())),
+            shutdown: Arc::new(tokio::sync::Notify::new()),
+            followup_pending: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            turn_active: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            state,
+        }
+    }
+
+    fn touch(&self) {
+        *self
+            .last_activity
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = std::time::Instant::now();
+    }
+
+    /// Mark a turn in progress until the returned guard drops.
+    fn begin_turn(&self) -> TurnActiveGuard {
+        self.turn_active
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        TurnActiveGuard(self.turn_active.clone())
+    }
+
+    /// Time since the channel last handled a message or event, or `None`
+    /// while a turn is running or it still has branches, workers, or a
+    /// follow-up pending.
+    pub async fn idle_for(&self) -> Option<std::time::Duration> {
+        if self.turn_active.load(std::sync::atomic::Ordering::Relaxed)
+            || self
+                .followup_pending
+                .load(std::sync::atomic::Ordering::Relaxed)
+            || !self.state.active_branches.read().await.is_empty()
+            || !self.state.active_workers.read().await.is_empty()
+        {
+            return None;
+        }
+        let last_activity = *self
+            .last_activity
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        Some(last_activity.elapsed())
+    }
+
+    /// Ask the channel to handle any queued messages and stop.
+    pub fn shutdown(&self) {
+        self.shutdown.notify_one();
+    }
+}
+
 impl std::fmt::Debug for ChannelState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ChannelState")
@@ -448,6 +556,9 @@ pub struct Channel {
     /// Injected into the system prompt (not into chat history) so the LLM
     /// treats it as read-only context rather than actionable user messages.
     backfill_transcript: Option<String>,
+    /// Binding welcome message, sent before the first turn if this channel
+    /// starts a new conversation. Taken on the first inbound message.
+    welcome_message: Option<String>,
     /// Channel-local reply mode toggle.
     /// When true, suppress unsolicited replies unless explicitly invoked.
     listen_only_mode: bool,
@@ -455,6 +566,11 @@ pub struct Channel {
     listen_only_session_override: Option<bool>,
     /// Handle exposed to the supervision control plane.
     control_handle: ChannelControlHandle,
+    /// Handle the main loop uses to evict the channel once it goes idle.
+    idle_handle: ChannelIdleHandle,
+    /// When to send the inactivity follow-up, armed after each reply and
+    /// cleared by the next inbound message.
+    followup_deadline: Option<tokio::time::Instant>,
 }
 
 /// RAII guard that records `message_handling_duration_seconds` when dropped,
@@ -501,7 +617,15 @@ impl Channel {
             ProcessType::Channel,
             Some(id.clone()),
             deps.event_tx.clone(),
-        );
+        )
+        .with_disabled_tools(deps.runtime_config.disabled_tools.load_full())
+        .with_output_token_cap(
+            deps.runtime_config
+                .routing
+                .load()
+                .max_output_tokens(ProcessType::Channel),
+        )
+        .with_tool_call_limits(**deps.runtime_config.tool_call_limits.load());
         let status_block = Arc::new(RwLock::new(StatusBlock::new()));
         let history = Arc::new(RwLock::new(Vec::new()));
         let active_branches = Arc::new(RwLock::new(HashMap::new()));
@@ -516,12 +640,15 @@ impl Channel {
 
         let state = ChannelState {
             channel_id: id.clone(),
+            started_at: chrono::Utc::now(),
             history: history.clone(),
+            history_turns: Arc::new(RwLock::new(VecDeque::new())),
             active_branches: active_branches.clone(),
             active_workers: active_workers.clone(),
             worker_handles: Arc::new(RwLock::new(HashMap::new())),
             worker_inputs: Arc::new(RwLock::new(HashMap::new())),
             worker_injections: Arc::new(RwLock::new(HashMap::new())),
+            worker_cancels: Arc::new(RwLock::new(HashMap::new())),
             reserved_tasks: Arc::new(RwLock::new(HashSet::new())),
             status_block: status_block.clone(),
             deps: deps.clone(),
@@ -534,11 +661,15 @@ impl Channel {
             prompt_snapshot_store,
             live_worker_transcripts: live_worker_transcripts
                 .unwrap_or_else(|| Arc::new(RwLock::new(HashMap::new()))),
+            reset_signal: Arc::new(ResetSignal::default()),
+            turn_cancel: Arc::new(TurnCancel::default()),
+            handoff: Arc::new(crate::handoff::HandoffState::default()),
         };
 
         // Each channel gets its own isolated tool server to avoid races between
         // concurrent channels sharing per-turn add/remove cycles.
         let tool_server = ToolServer::new().run();
+        hook.set_tool_server(tool_server.clone());
 
         // Construct the send_agent_message tool if this agent has links.
         let send_agent_message_tool = {
@@ -560,6 +691,7 @@ impl Channel {
         let self_tx = message_tx.clone();
         let resolved_listen_only_mode = deps.runtime_config.channel_config.load().listen_only_mode;
         let control_handle = ChannelControlHandle::new(state.clone());
+        let idle_handle = ChannelIdleHandle::new(state.clone());
         let channel = Self {
             id: id.clone(),
             title: None,
@@ -589,9 +721,12 @@ impl Channel {
             pending_results: Vec::new(),
             send_agent_message_tool,
             backfill_transcript: None,
+            welcome_message: None,
             listen_only_mode: resolved_listen_only_mode,
             listen_only_session_override: None,
             control_handle,
+            idle_handle,
+            followup_deadline: None,
         };
 
         (channel, message_tx)
@@ -602,6 +737,50 @@ impl Channel {
         self.backfill_transcript = Some(transcript);
     }
 
+    /// Set the welcome message to send if this channel starts a new conversation.
+    pub fn set_welcome_message(&mut self, message: String) {
+        self.welcome_message = Some(message);
+    }
+
+    /// Send the binding welcome message ahead of the first user message, but
+    /// only when the conversation has never been seen before. Channels
+    /// recreated after eviction or a restart find the conversation in the
+    /// channel store and stay quiet.
+    async fn send_welcome_if_new_conversation(&mut self, message: &InboundMessage) {
+        if message.source == "system" {
+            return;
+        }
+        let Some(template) = self.welcome_message.take() else {
+            return;
+        };
+
+        match self.state.channel_store.get(&message.conversation_id).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return,
+            Err(error) => {
+                tracing::warn!(%error, channel_id = %self.id, "failed to check channel store, skipping welcome message");
+                return;
+            }
+        }
+
+        let text = template.replace("{agent_name}", self.agent_display_name());
+        let routed = RoutedResponse {
+            response: OutboundResponse::Text(text.clone()),
+            target: message.clone(),
+            usage: None,
+        };
+        if let Err(error) = self.response_tx.send(routed).await {
+            tracing::error!(%error, channel_id = %self.id, "failed to send welcome message");
+            return;
+        }
+        self.state.conversation_logger.log_bot_message_with_name(
+            &self.state.channel_id,
+            &text,
+            Some(self.agent_display_name()),
+        );
+        tracing::info!(channel_id = %self.id, "sent binding welcome message");
+    }
+
     /// Get the agent's display name (falls back to agent ID).
     fn agent_display_name(&self) -> &str {
         self.deps
@@ -745,6 +924,10 @@ impl Channel {
         self.control_handle.clone()
     }
 
+    pub fn idle_handle(&self) -> ChannelIdleHandle {
+        self.idle_handle.clone()
+    }
+
     fn rewrite_tool_routed_command_prompt(&self, raw_text: &str) -> Option<String> {
         match raw_text.trim() {
             "/tasks" => Some(
@@ -791,11 +974,21 @@ impl Channel {
     async fn send_routed(
         &self,
         response: OutboundResponse,
+    ) -> std::result::Result<(), mpsc::error::SendError<RoutedResponse>> {
+        self.send_routed_with_usage(response, None).await
+    }
+
+    /// Send a routed response carrying the usage of the turn that produced it.
+    async fn send_routed_with_usage(
+        &self,
+        response: OutboundResponse,
+        usage: Option<crate::llm::usage::MessageUsage>,
     ) -> std::result::Result<(), mpsc::error::SendError<RoutedResponse>> {
         let routed = match &self.current_inbound {
             Some(target) => RoutedResponse {
                 response,
                 target: target.clone(),
+                usage,
             },
             None => {
                 tracing::warn!(
@@ -805,12 +998,28 @@ impl Channel {
                 RoutedResponse {
                     response,
                     target: InboundMessage::empty(),
+                    usage,
                 }
             }
         };
         self.response_tx.send(routed).await
     }
 
+    /// Tell the conversation an attachment was withheld by the media safety
+    /// check, if a `blocked_response` is configured.
+    async fn send_media_blocked_notice(&mut self) {
+        let response = self
+            .deps
+            .runtime_config
+            .media_safety
+            .load()
+            .blocked_response
+            .clone();
+        if let Some(response) = response {
+            self.send_builtin_text(response, "media-safety").await;
+        }
+    }
+
     async fn send_builtin_text(&mut self, text: String, log_label: &str) {
         match self.send_routed(OutboundResponse::Text(text.clone())).await {
             Ok(()) => {
@@ -842,6 +1051,172 @@ impl Channel {
         }
     }
 
+    /// Snapshot of the agent, channel, mode, and models for status commands.
+    fn status_text(&self) -> String {
+        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
+        let now_line = temporal_context.current_time_line();
+        let routing = self.deps.runtime_config.routing.load();
+        let channel_model = resolve_channel_model(&self.deps.runtime_config, &self.id);
+        let branch_model = routing.resolve(ProcessType::Branch, None).to_string();
+        let mode = if self.listen_only_mode {
+            "quiet"
+        } else {
+            "active"
+        };
+        let adapter = self.current_adapter().unwrap_or("unknown");
+        format!(
+            "status\n\
+             - agent: {}\n\
+             - channel: {}\n\
+             - adapter: {}\n\
+             - mode: {} (quiet => only command/@mention/reply-to-bot)\n\
+             - channel model: {}\n\
+             - branch model: {}\n\
+             - time: {}",
+            self.deps.agent_id, self.id, adapter, mode, channel_model, branch_model, now_line
+        )
+    }
+
+    /// Finish a conversation reset by clearing the state only the channel
+    /// loop owns. History is cleared again in case a turn that was running
+    /// when the reset was requested wrote back to it.
+    async fn finish_reset(&mut self, announce: bool) {
+        self.state.history.write().await.clear();
+        self.state.history_turns.write().await.clear();
+        self.coalesce_buffer.clear();
+        self.coalesce_deadline = None;
+        self.pending_retrigger = false;
+        self.pending_retrigger_metadata.clear();
+        self.retrigger_deadline = None;
+        self.pending_results.clear();
+        self.retrigger_count = 0;
+        self.memory_persistence_branches.clear();
+        self.branch_reply_targets.clear();
+        self.backfill_transcript = None;
+        self.set_followup_deadline(None);
+
+        if announce {
+            self.send_builtin_text(RESET_CONFIRMATION.to_string(), "reset")
+                .await;
+        }
+    }
+
+    /// Whether the channel was handed off to a human and is waiting for them
+    /// to resolve it. Messages are still recorded, but the agent stays quiet.
+    async fn awaiting_human(&self) -> bool {
+        if let Some(open) = self.state.handoff.get() {
+            return open;
+        }
+        let store = crate::handoff::HandoffStore::new(self.deps.sqlite_pool.clone());
+        match store.open_for_channel(&self.id).await {
+            Ok(handoff) => self.state.handoff.load(handoff.is_some()),
+            Err(error) => {
+                tracing::warn!(channel_id = %self.id, %error, "failed to check for open handoff");
+                false
+            }
+        }
+    }
+
+    /// Arm the inactivity follow-up after a turn that replied. Only one is
+    /// scheduled per idle period: the next inbound message clears it, and
+    /// sending it doesn't re-arm.
+    fn schedule_followup(&mut self, replied: bool) {
+        let followup_after_secs = self
+            .deps
+            .runtime_config
+            .channel_config
+            .load()
+            .followup_after_secs;
+        let deadline = (replied && followup_after_secs > 0).then(|| {
+            tokio::time::Instant::now() + std::time::Duration::from_secs(followup_after_secs)
+        });
+        self.set_followup_deadline(deadline);
+    }
+
+    fn set_followup_deadline(&mut self, deadline: Option<tokio::time::Instant>) {
+        self.followup_deadline = deadline;
+        self.idle_handle
+            .followup_pending
+            .store(deadline.is_some(), std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Send the inactivity follow-up unless the conversation is muted or it
+    /// is quiet hours. Either way it's spent for this idle period.
+    async fn send_followup(&mut self) {
+        let channel_config = self.deps.runtime_config.channel_config.load_full();
+        if channel_config.followup_after_secs == 0 {
+            return;
+        }
+        let gate = FollowupGate {
+            listen_only_mode: self.listen_only_mode,
+            paused: self.deps.runtime_config.is_paused(),
+            awaiting_human: self.awaiting_human().await,
+            local_hour: TemporalContext::from_runtime(self.deps.runtime_config.as_ref())
+                .local_hour(),
+            quiet_hours: channel_config.followup_quiet_hours,
+        };
+        if let Some(reason) = followup_blocked_reason(gate) {
+            tracing::debug!(channel_id = %self.id, reason, "skipping inactivity follow-up");
+            return;
+        }
+
+        tracing::info!(channel_id = %self.id, "sending inactivity follow-up");
+        self.send_builtin_text(channel_config.followup_message.clone(), "followup")
+            .await;
+    }
+
+    /// Handle `channel.command_prefix` commands (`!reset`, `!status`,
+    /// `!help`) on any adapter. Prefixed text that names no enabled command
+    /// falls through to the normal turn.
+    async fn try_handle_prefixed_command(
+        &mut self,
+        raw_text: &str,
+        message: &InboundMessage,
+    ) -> Result<bool> {
+        if message.source == "system" {
+            return Ok(false);
+        }
+
+        let channel_config = self.deps.runtime_config.channel_config.load_full();
+        let Some(command) = crate::messaging::commands::parse_prefixed_command(
+            raw_text,
+            &channel_config.command_prefix,
+            &channel_config.commands,
+        ) else {
+            return Ok(false);
+        };
+
+        tracing::info!(
+            channel_id = %self.id,
+            command = command.as_str(),
+            "handling prefixed chat command"
+        );
+
+        let body = match command {
+            ChatCommand::Reset => {
+                channel_reset::reset_conversation(&self.state, false).await;
+                self.finish_reset(false).await;
+                RESET_CONFIRMATION.to_string()
+            }
+            ChatCommand::Status => self.status_text(),
+            ChatCommand::Help => {
+                let prefix = &channel_config.command_prefix;
+                let mut lines = vec!["commands:".to_string()];
+                for command in &channel_config.commands {
+                    let description = match command {
+                        ChatCommand::Reset => "clear this channel's conversation history",
+                        ChatCommand::Status => "current mode, models, binding snapshot",
+                        ChatCommand::Help => "this list",
+                    };
+                    lines.push(format!("- {prefix}{}: {description}", command.as_str()));
+                }
+                lines.join("\n")
+            }
+        };
+        self.send_builtin_text(body, command.as_str()).await;
+        Ok(true)
+    }
+
     async fn try_handle_builtin_ops_commands(
         &mut self,
         raw_text: &str,
@@ -863,37 +1238,9 @@ impl Channel {
             return Ok(false);
         }
 
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
-        let now_line = temporal_context.current_time_line();
-
         match text {
             "/status" => {
-                let routing = self.deps.runtime_config.routing.load();
-                let channel_model = routing.resolve(ProcessType::Channel, None).to_string();
-                let branch_model = routing.resolve(ProcessType::Branch, None).to_string();
-                let mode = if self.listen_only_mode {
-                    "quiet"
-                } else {
-                    "active"
-                };
-                let adapter = self.current_adapter().unwrap_or("unknown");
-                let body = format!(
-                    "status\n\
-                     - agent: {}\n\
-                     - channel: {}\n\
-                     - adapter: {}\n\
-                     - mode: {} (quiet => only command/@mention/reply-to-bot)\n\
-                     - channel model: {}\n\
-                     - branch model: {}\n\
-                     - time: {}",
-                    self.deps.agent_id,
-                    self.id,
-                    adapter,
-                    mode,
-                    channel_model,
-                    branch_model,
-                    now_line
-                );
+                let body = self.status_text();
                 self.send_builtin_text(body, "status").await;
                 return Ok(true);
             }
@@ -946,13 +1293,19 @@ impl Channel {
         let mut last_lag_warning: Option<std::time::Instant> = None;
 
         loop {
-            // Compute next deadline from coalesce and retrigger timers
-            let next_deadline = match (self.coalesce_deadline, self.retrigger_deadline) {
-                (Some(a), Some(b)) => Some(a.min(b)),
-                (Some(a), None) => Some(a),
-                (None, Some(b)) => Some(b),
-                (None, None) => None,
-            };
+            if let Some(pending) = self.state.reset_signal.take() {
+                self.finish_reset(pending.announce).await;
+            }
+
+            // Compute next deadline from coalesce, retrigger, and follow-up timers
+            let next_deadline = [
+                self.coalesce_deadline,
+                self.retrigger_deadline,
+                self.followup_deadline,
+            ]
+            .into_iter()
+            .flatten()
+            .min();
             let sleep_duration = next_deadline
                 .map(|deadline| {
                     let now = tokio::time::Instant::now();
@@ -963,9 +1316,24 @@ impl Channel {
                     }
                 })
                 .unwrap_or(std::time::Duration::from_secs(3600)); // Default long timeout if no deadline
+            let reset_signal = self.state.reset_signal.clone();
 
             tokio::select! {
+                _ = reset_signal.notified() => continue,
                 Some(message) = self.message_rx.recv() => {
+                    let _turn = self.idle_handle.begin_turn();
+                    self.idle_handle.touch();
+                    // Retriggers are the agent's own turns, not the user
+                    // coming back, so they leave a scheduled follow-up alone.
+                    if message.source != "system" {
+                        self.set_followup_deadline(None);
+                    }
+                    let Some(message) = self.resolve_reaction_trigger(message) else {
+                        continue;
+                    };
+                    if self.dispatch_button_action(&message).await {
+                        continue;
+                    }
                     let config = self.deps.runtime_config.coalesce.load();
                     if self.should_coalesce(&message, &config) {
                         self.coalesce_buffer.push(message);
@@ -986,6 +1354,8 @@ impl Channel {
                             if !should_process_event_for_channel(&event, &self.id) {
                                 continue;
                             }
+                            let _turn = self.idle_handle.begin_turn();
+                            self.idle_handle.touch();
                             // Worker/branch lifecycle events bypass coalescing.
                             if should_flush_coalesce_buffer_for_event(&event)
                                 && let Err(error) = self.flush_coalesce_buffer().await
@@ -1029,6 +1399,7 @@ impl Channel {
                     }
                 }
                 _ = tokio::time::sleep(sleep_duration), if next_deadline.is_some() => {
+                    let _turn = self.idle_handle.begin_turn();
                     let now = tokio::time::Instant::now();
                     // Check coalesce deadline
                     if self.coalesce_deadline.is_some_and(|d| d <= now)
@@ -1040,6 +1411,15 @@ impl Channel {
                     if self.retrigger_deadline.is_some_and(|d| d <= now) {
                         self.flush_pending_retrigger().await;
                     }
+                    // Check follow-up deadline
+                    if self.followup_deadline.is_some_and(|d| d <= now) {
+                        self.set_followup_deadline(None);
+                        self.send_followup().await;
+                    }
+                }
+                _ = self.idle_handle.shutdown.notified() => {
+                    self.drain_before_shutdown().await;
+                    break;
                 }
                 else => break,
             }
@@ -1054,6 +1434,122 @@ impl Channel {
         Ok(())
     }
 
+    /// Stop accepting messages and handle whatever is already queued, so an
+    /// idle eviction never drops a message that raced with it.
+    async fn drain_before_shutdown(&mut self) {
+        if self.pending_retrigger {
+            self.flush_pending_retrigger().await;
+        }
+        self.message_rx.close();
+        while let Ok(message) = self.message_rx.try_recv() {
+            let Some(message) = self.resolve_reaction_trigger(message) else {
+                continue;
+            };
+            if self.dispatch_button_action(&message).await {
+                continue;
+            }
+            if let Err(error) = self.flush_coalesce_buffer().await {
+                tracing::error!(%error, channel_id = %self.id, "error flushing coalesce buffer");
+            }
+            if let Err(error) = self.handle_message(message).await {
+                tracing::error!(%error, channel_id = %self.id, "error handling message");
+            }
+        }
+    }
+
+    /// Attach the configured intent to a reaction trigger, or drop the
+    /// reaction when its emoji has no mapping in `reaction_triggers`.
+    fn resolve_reaction_trigger(&self, mut message: InboundMessage) -> Option<InboundMessage> {
+        let crate::MessageContent::Reaction { emoji, .. } = &message.content else {
+            return Some(message);
+        };
+        let triggers = self.deps.runtime_config.reaction_triggers.load();
+        let Some(intent) = triggers.intent_for(emoji) else {
+            tracing::debug!(
+                channel_id = %self.id,
+                emoji = %emoji,
+                "ignoring reaction without a configured trigger"
+            );
+            return None;
+        };
+        message
+            .metadata
+            .insert("reaction_intent".into(), intent.into());
+        Some(message)
+    }
+
+    /// Run the action behind a `run_worker:` or `run_cron:` button click and
+    /// report back in the conversation. Returns false for any other message,
+    /// leaving it to the normal interaction-as-message handling.
+    async fn dispatch_button_action(&mut self, message: &InboundMessage) -> bool {
+        let crate::MessageContent::Interaction { action_id, .. } = &message.content else {
+            return false;
+        };
+        let Some(action) = parse_button_action(action_id) else {
+            return false;
+        };
+
+        self.current_inbound = Some(message.clone());
+        self.persist_inbound_user_message(message, &message.content.to_string(), None);
+        tracing::info!(
+            channel_id = %self.id,
+            action_id = %action_id,
+            "dispatching button action"
+        );
+
+        let reply = match action.kind {
+            ButtonActionKind::RunWorker => self.run_worker_button(&action.target).await,
+            ButtonActionKind::RunCron => self.run_cron_button(&action.target).await,
+        };
+        self.send_builtin_text(reply, "button-action").await;
+        true
+    }
+
+    /// Spawn the worker task configured for a `run_worker:<name>` button.
+    async fn run_worker_button(&self, name: &str) -> String {
+        let task = self
+            .deps
+            .runtime_config
+            .worker_buttons
+            .load()
+            .task_for(name)
+            .map(str::to_string);
+        let Some(task) = task else {
+            return format!("No worker button named `{name}` is configured.");
+        };
+
+        match spawn_worker_from_state(&self.state, task, false, &[]).await {
+            Ok(worker_id) => {
+                tracing::info!(channel_id = %self.id, %worker_id, button = %name, "worker started from button");
+                format!("Started the `{name}` worker.")
+            }
+            Err(error) => {
+                tracing::warn!(channel_id = %self.id, %error, button = %name, "button failed to start worker");
+                format!("Couldn't start the `{name}` worker: {error}")
+            }
+        }
+    }
+
+    /// Run the cron job named by a `run_cron:<id>` button in the background.
+    async fn run_cron_button(&self, job_id: &str) -> String {
+        let Some(cron_tool) = &self.deps.cron_tool else {
+            return "Cron jobs are not available for this agent.".to_string();
+        };
+        let scheduler = cron_tool.scheduler().clone();
+        if !scheduler.is_registered(job_id).await {
+            return format!("No cron job with ID `{job_id}` is registered.");
+        }
+
+        // The job runs a full agent turn; don't hold up the channel for it.
+        let cron_id = job_id.to_string();
+        tokio::spawn(async move {
+            if let Err(error) = scheduler.trigger_now(&cron_id).await {
+                tracing::warn!(%error, cron_id = %cron_id, "button-triggered cron job failed");
+            }
+        });
+        format!("Running cron job `{job_id}`.")
+    }
+
     /// Determine if a message should be coalesced (batched with other messages).
     ///
     /// Returns false for:
@@ -1074,18 +1570,27 @@ impl Channel {
         if config.multi_user_only && self.is_dm() {
             return false;
         }
-        // Built-in slash commands should execute immediately and never be batched.
-        let looks_like_command = match &message.content {
-            crate::MessageContent::Text(text) => text.trim_start().starts_with('/'),
-            crate::MessageContent::Media { text, .. } => text
-                .as_deref()
-                .is_some_and(|value| value.trim_start().starts_with('/')),
-            crate::MessageContent::Interaction { .. } => false,
+        // Built-in slash and prefixed commands should execute immediately and
+        // never be batched, since batches skip command handling.
+        let text = match &message.content {
+            crate::MessageContent::Text(text) => Some(text.as_str()),
+            crate::MessageContent::Media { text, .. } => text.as_deref(),
+            crate::MessageContent::Interaction { .. } => None,
+            crate::MessageContent::Reaction { .. } => None,
         };
-        if looks_like_command {
+        let Some(text) = text else {
+            return true;
+        };
+        if text.trim_start().starts_with('/') {
             return false;
         }
-        true
+        let channel_config = self.deps.runtime_config.channel_config.load();
+        crate::messaging::commands::parse_prefixed_command(
+            text,
+            &channel_config.command_prefix,
+            &channel_config.commands,
+        )
+        .is_none()
     }
 
     /// Check if this is a DM (direct message) conversation based on conversation_id.
@@ -1168,10 +1673,16 @@ impl Channel {
     /// individually to conversation history, then presents them as one user turn
     /// with a coalesce hint telling the LLM this is a fast-moving conversation.
     #[tracing::instrument(skip(self, messages), fields(channel_id = %self.id, agent_id = %self.deps.agent_id, message_count = messages.len()))]
-    async fn handle_message_batch(&mut self, messages: Vec<InboundMessage>) -> Result<()> {
+    async fn handle_message_batch(&mut self, mut messages: Vec<InboundMessage>) -> Result<()> {
         // Apply runtime-config updates immediately without requiring a restart.
         self.sync_listen_only_mode_from_runtime();
 
+        // Turn voice notes into message text before anything reads it.
+        let mut voice = transcription::VoiceTranscripts::default();
+        for message in &mut messages {
+            transcription::transcribe_message(&self.deps, message, &mut voice).await;
+        }
+
         let message_count = messages.len();
         let batch_start_timestamp = messages
             .iter()
@@ -1260,6 +1771,14 @@ impl Channel {
             )?);
         }
 
+        if let Some(first_real) = messages
+            .iter()
+            .find(|message| message.source != "system")
+            .cloned()
+        {
+            self.send_welcome_if_new_conversation(&first_real).await;
+        }
+
         // Persist each message to conversation log (individual audit trail)
         let save_attachments_enabled = self
             .deps
@@ -1269,17 +1788,21 @@ impl Channel {
             .save_attachments;
         let saved_dir = self.deps.runtime_config.saved_dir();
 
-        // Entries: (formatted_text, attachments, optional saved bytes per attachment)
+        // Entries: (message index, raw text, attachments, bytes fetched during
+        // screening, optional saved bytes per attachment)
         let mut pending_batch_entries: Vec<(
+            usize,
             String,
             Vec<crate::Attachment>,
+            HashMap<String, Vec<u8>>,
             Option<Vec<channel_attachments::SavedAttachmentWithBytes>>,
         )> = Vec::new();
         let mut conversation_id = String::new();
         let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
         let mut batch_has_invoke = false;
+        let mut batch_media_blocked = false;
 
-        for message in &messages {
+        for (index, message) in messages.iter().enumerate() {
             if message.source != "system" {
                 let sender_name = message
                     .metadata
@@ -1296,8 +1819,17 @@ impl Channel {
                     crate::MessageContent::Interaction { .. } => {
                         (message.content.to_string(), Vec::new())
                     }
+                    crate::MessageContent::Reaction { .. } => {
+                        (render_reaction_trigger(message), Vec::new())
+                    }
                 };
 
+                // Screen inbound media before it is saved or shown to a vision model.
+                let mut screening = media_safety::screen_attachments(&self.deps, attachments).await;
+                batch_media_blocked |= screening.blocked();
+                let attachments = std::mem::take(&mut screening.allowed);
+                let downloaded = std::mem::take(&mut screening.downloaded);
+
                 if self.listen_only_mode {
                     let (invoked_by_command, invoked_by_mention, invoked_by_reply) =
                         self.compute_listen_mode_invocation(message, &raw_text);
@@ -1314,6 +1846,7 @@ impl Channel {
                             self.state.channel_id.as_ref(),
                             &saved_dir,
                             &attachments,
+                            &downloaded,
                         )
                         .await,
                     )
@@ -1322,7 +1855,7 @@ impl Channel {
                 };
 
                 // Enrich metadata with saved attachment info
-                let metadata = if let Some(ref data) = saved_data {
+                let mut metadata = if let Some(ref data) = saved_data {
                     let metas: Vec<_> = data.iter().map(|(meta, _)| meta.clone()).collect();
                     let mut enriched = message.metadata.clone();
                     if let Ok(json) = serde_json::to_value(&metas) {
@@ -1332,6 +1865,7 @@ impl Channel {
                 } else {
                     message.metadata.clone()
                 };
+                screening.annotate(&mut metadata);
 
                 self.state.conversation_logger.log_user_message(
                     &self.state.channel_id,
@@ -1346,31 +1880,37 @@ impl Channel {
 
                 conversation_id = message.conversation_id.clone();
 
-                // Include both absolute and relative time context.
-                let relative_secs = batch_tail_timestamp
-                    .signed_duration_since(message.timestamp)
-                    .num_seconds()
-                    .max(0);
-                let relative_text = if relative_secs < 1 {
-                    "just now".to_string()
-                } else if relative_secs < 60 {
-                    format!("{}s ago", relative_secs)
-                } else {
-                    format!("{}m ago", relative_secs / 60)
-                };
-                let absolute_timestamp = temporal_context.format_timestamp(message.timestamp);
+                pending_batch_entries.push((index, raw_text, attachments, downloaded, saved_data));
+            }
+        }
 
-                let display_name = message_display_name(message);
+        if self.deps.runtime_config.is_paused() {
+            tracing::debug!(
+                channel_id = %self.id,
+                message_count,
+                "agent paused by PAUSE file: recorded coalesced batch without replying"
+            );
+            return Ok(());
+        }
 
-                let formatted_text = format_batched_user_message(
-                    display_name,
-                    &absolute_timestamp,
-                    &relative_text,
-                    &raw_text,
-                );
+        if self.awaiting_human().await {
+            tracing::debug!(
+                channel_id = %self.id,
+                message_count,
+                "handoff open: recorded coalesced batch without replying"
+            );
+            return Ok(());
+        }
 
-                pending_batch_entries.push((formatted_text, attachments, saved_data));
-            }
+        // One extraction call for the whole batch rather than one per message.
+        if !pending_batch_entries.is_empty() {
+            let batch_text = pending_batch_entries
+                .iter()
+                .map(|(_, raw_text, _, _, _)| inbound_limit::capped_prefix(&self.deps, raw_text))
+                .filter(|text| !text.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            crate::tasks::extraction::spawn_extraction(&self.deps, &conversation_id, &batch_text);
         }
 
         if self.listen_only_mode && !batch_has_invoke {
@@ -1386,7 +1926,48 @@ impl Channel {
         }
 
         let mut user_contents: Vec<UserContent> = Vec::new();
-        for (formatted_text, attachments, saved_data) in pending_batch_entries {
+        let mut oversized_notice = None;
+        for (index, raw_text, attachments, downloaded, saved_data) in pending_batch_entries {
+            let message = &messages[index];
+
+            // Cap oversized text for the turn. The conversation log already
+            // has the original.
+            let turn_text =
+                match inbound_limit::limit_inbound_text(&self.deps, &self.id, &raw_text).await {
+                    LimitedInbound::Text(text) => text,
+                    LimitedInbound::Rejected(notice) => {
+                        oversized_notice = Some(notice);
+                        inbound_limit::REJECTED_PLACEHOLDER.to_string()
+                    }
+                };
+
+            // Include both absolute and relative time context.
+            let relative_secs = batch_tail_timestamp
+                .signed_duration_since(message.timestamp)
+                .num_seconds()
+                .max(0);
+            let relative_text = if relative_secs < 1 {
+                "just now".to_string()
+            } else if relative_secs < 60 {
+                format!("{}s ago", relative_secs)
+            } else {
+                format!("{}m ago", relative_secs / 60)
+            };
+            let absolute_timestamp = temporal_context.format_timestamp(message.timestamp);
+
+            let display_name = message_display_name(message);
+
+            let formatted_text = format_batched_user_message(
+                display_name,
+                &absolute_timestamp,
+                &relative_text,
+                &turn_text,
+            );
+            let formatted_text = match format_reply_context(message) {
+                Some(block) => format!("{block}\n{formatted_text}"),
+                None => formatted_text,
+            };
+
             if !attachments.is_empty() {
                 let attachment_content = if let Some(ref saved) = saved_data {
                     let mut content = Vec::new();
@@ -1404,12 +1985,19 @@ impl Channel {
                             unsaved.push(attachment.clone());
                         }
                     }
+                    let unsaved = voice.untranscribed(&unsaved);
                     if !unsaved.is_empty() {
-                        content.extend(download_attachments(&self.deps, &unsaved).await);
+                        content
+                            .extend(download_attachments(&self.deps, &unsaved, &downloaded).await);
                     }
                     content
                 } else {
-                    download_attachments(&self.deps, &attachments).await
+                    download_attachments(
+                        &self.deps,
+                        &voice.untranscribed(&attachments),
+                        &downloaded,
+                    )
+                    .await
                 };
                 for content in attachment_content {
                     user_contents.push(content);
@@ -1417,6 +2005,9 @@ impl Channel {
             }
             user_contents.push(UserContent::text(formatted_text));
         }
+        if let Some(notice) = oversized_notice {
+            self.send_builtin_text(notice, "oversized-inbound").await;
+        }
 
         // Separate text and non-text (image/audio) content
         let mut text_parts = Vec::new();
@@ -1428,15 +2019,20 @@ impl Channel {
             }
         }
 
+        let batch_text = text_parts.join("\n");
         let combined_text = format!(
             "[{} messages arrived rapidly in this channel]\n\n{}",
-            message_count,
-            text_parts.join("\n")
+            message_count, batch_text
         );
 
         // Build system prompt with coalesce hint
         let system_prompt = self
-            .build_system_prompt_with_coalesce(message_count, elapsed_secs, unique_sender_count)
+            .build_system_prompt_with_coalesce(
+                message_count,
+                elapsed_secs,
+                unique_sender_count,
+                &batch_text,
+            )
             .await?;
 
         // Extract adapter from messages (prefer explicit message.adapter, fall back to stored source_adapter)
@@ -1458,6 +2054,10 @@ impl Channel {
             self.current_inbound = Some(last_real.clone());
         }
 
+        if batch_media_blocked {
+            self.send_media_blocked_notice().await;
+        }
+
         // Run agent turn with any image/audio attachments preserved
         let (result, skip_flag, replied_flag, _) = self
             .run_agent_turn(
@@ -1472,6 +2072,7 @@ impl Channel {
 
         self.handle_agent_result(result, &skip_flag, &replied_flag, false)
             .await;
+        self.schedule_followup(replied_flag.load(std::sync::atomic::Ordering::Relaxed));
         // Check compaction
         if let Err(error) = self.compactor.check_and_compact().await {
             tracing::warn!(channel_id = %self.id, %error, "compaction check failed");
@@ -1485,11 +2086,14 @@ impl Channel {
     }
 
     /// Build system prompt with coalesce hint for batched messages.
+    ///
+    /// `recall_query` is the text memories are recalled against; empty skips recall.
     async fn build_system_prompt_with_coalesce(
         &self,
         message_count: usize,
         elapsed_secs: f64,
         unique_senders: usize,
+        recall_query: &str,
     ) -> Result<String> {
         let rc = &self.deps.runtime_config;
         let prompt_engine = rc.prompts.load();
@@ -1497,7 +2101,6 @@ impl Channel {
         let identity_context = rc.identity.load().render();
         let memory_bulletin = rc.memory_bulletin.load();
         let skills = rc.skills.load();
-        let skills_prompt = skills.render_channel_prompt(&prompt_engine)?;
 
         let browser_enabled = rc.browser_config.load().enabled;
         let web_search_enabled = rc.brave_search_key.load().is_some();
@@ -1578,23 +2181,54 @@ impl Channel {
             }
         };
 
-        prompt_engine.render_channel_prompt_with_links(
-            empty_to_none(identity_context),
-            empty_to_none(memory_bulletin.to_string()),
-            empty_to_none(skills_prompt),
-            worker_capabilities,
-            self.conversation_context.clone(),
-            empty_to_none(status_text),
-            coalesce_hint,
-            available_channels,
-            sandbox_enabled,
-            org_context,
-            adapter_prompt,
-            project_context,
-            self.backfill_transcript.clone(),
-            empty_to_none(working_memory),
-            empty_to_none(channel_activity_map),
-        )
+        let identity_context = empty_to_none(identity_context);
+        let memory_bulletin = empty_to_none(memory_bulletin.to_string());
+        let status_text = empty_to_none(status_text);
+        let working_memory = empty_to_none(working_memory);
+        let channel_activity_map = empty_to_none(channel_activity_map);
+        let budget = **rc.system_prompt_token_budget.load();
+        let output_language = resolve_output_language(rc, &self.id);
+        let pinned_context = resolve_pinned_context(rc, &self.id);
+        let conversation_slots = resolve_conversation_slots(rc, &self.id);
+        let notes_context =
+            resolve_notes_context(&self.deps.sqlite_pool, rc, self.id.as_ref()).await;
+        let recalled = recall_memories(&self.deps.memory_search, rc, recall_query).await;
+        self.deps.memory_search.record_recall(&recalled).await;
+        let recalled_memories = render_recalled_memories(&recalled);
+        let persona = resolve_persona(rc, &self.id);
+
+        let budgeted = render_within_budget(
+            self.id.as_ref(),
+            &skills,
+            &prompt_engine,
+            budget,
+            |skills_prompt| {
+                prompt_engine.render_channel_prompt_with_links(
+                    identity_context.clone(),
+                    memory_bulletin.clone(),
+                    skills_prompt,
+                    worker_capabilities.clone(),
+                    self.conversation_context.clone(),
+                    status_text.clone(),
+                    coalesce_hint.clone(),
+                    available_channels.clone(),
+                    sandbox_enabled,
+                    org_context.clone(),
+                    adapter_prompt.clone(),
+                    project_context.clone(),
+                    self.backfill_transcript.clone(),
+                    working_memory.clone(),
+                    channel_activity_map.clone(),
+                    output_language.clone(),
+                    pinned_context.clone(),
+                    conversation_slots.clone(),
+                    notes_context.clone(),
+                    recalled_memories.clone(),
+                    persona.clone(),
+                )
+            },
+        )?;
+        Ok(budgeted.prompt)
     }
 
     /// Handle an incoming message by running the channel's LLM agent loop.
@@ -1657,6 +2291,11 @@ impl Channel {
                 .or_else(|| Some(message.source.clone()));
         }
 
+        // Turn voice notes into message text before anything reads it.
+        let mut message = message;
+        let mut voice = transcription::VoiceTranscripts::default();
+        transcription::transcribe_message(&self.deps, &mut message, &mut voice).await;
+
         let (raw_text, attachments) = match &message.content {
             crate::MessageContent::Text(text) => (text.clone(), Vec::new()),
             crate::MessageContent::Media { text, attachments } => {
@@ -1664,8 +2303,18 @@ impl Channel {
             }
             // Render interactions as their Display form so the LLM sees plain text.
             crate::MessageContent::Interaction { .. } => (message.content.to_string(), Vec::new()),
+            crate::MessageContent::Reaction { .. } => {
+                (render_reaction_trigger(&message), Vec::new())
+            }
         };
 
+        // Screen inbound media before it is saved or shown to a vision model.
+        let screening = media_safety::screen_attachments(&self.deps, attachments).await;
+        screening.annotate(&mut message.metadata);
+        let media_blocked = screening.blocked();
+        let attachments = screening.allowed;
+        let downloaded = screening.downloaded;
+
         // Save attachments to disk when enabled, capturing bytes for LLM reuse
         let save_attachments_enabled = self
             .deps
@@ -1682,6 +2331,7 @@ impl Channel {
                     self.state.channel_id.as_ref(),
                     &saved_dir,
                     &attachments,
+                    &downloaded,
                 )
                 .await,
             )
@@ -1693,8 +2343,27 @@ impl Channel {
             .as_ref()
             .map(|data| data.iter().map(|(meta, _)| meta.clone()).collect());
 
+        self.send_welcome_if_new_conversation(&message).await;
         self.persist_inbound_user_message(&message, &raw_text, saved_metas.as_deref());
 
+        // Checked before any built-in reply so a handed-off channel stays
+        // silent, pings and commands included.
+        if self.awaiting_human().await {
+            tracing::debug!(
+                channel_id = %self.id,
+                source = %message.source,
+                "handoff open: recorded message without replying"
+            );
+            return Ok(());
+        }
+
+        if media_blocked {
+            self.send_media_blocked_notice().await;
+            if raw_text.trim().is_empty() && attachments.is_empty() {
+                return Ok(());
+            }
+        }
+
         // Deterministic built-in command: bypass model output drift for agent identity checks.
         if message.source != "system" && raw_text.trim() == "/agent-id" {
             self.send_builtin_text(self.deps.agent_id.to_string(), "agent-id")
@@ -1739,6 +2408,13 @@ impl Channel {
             )?);
         }
 
+        if self
+            .try_handle_prefixed_command(&raw_text, &message)
+            .await?
+        {
+            return Ok(());
+        }
+
         if self
             .try_handle_builtin_ops_commands(&raw_text, &message)
             .await?
@@ -1746,16 +2422,22 @@ impl Channel {
             return Ok(());
         }
 
-        let rewritten_text = if message.source == "system" {
-            raw_text.clone()
-        } else {
-            self.rewrite_tool_routed_command_prompt(&raw_text)
-                .unwrap_or_else(|| raw_text.clone())
-        };
+        if self.deps.runtime_config.is_paused() {
+            tracing::debug!(
+                channel_id = %self.id,
+                source = %message.source,
+                "agent paused by PAUSE file: recorded message without replying"
+            );
+            return Ok(());
+        }
 
-        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
-        let message_timestamp = temporal_context.format_timestamp(message.timestamp);
-        let user_text = format_user_message(&rewritten_text, &message, &message_timestamp);
+        if message.source != "system" {
+            crate::tasks::extraction::spawn_extraction(
+                &self.deps,
+                &message.conversation_id,
+                inbound_limit::capped_prefix(&self.deps, &raw_text),
+            );
+        }
 
         let mut invoked_by_command = false;
         let mut invoked_by_mention = false;
@@ -1782,7 +2464,38 @@ impl Channel {
             }
         }
 
-        let system_prompt = self.build_system_prompt().await?;
+        // Cap oversized text for the turn. The conversation log already has
+        // the original.
+        let turn_text = if message.source == "system" {
+            raw_text.clone()
+        } else {
+            match inbound_limit::limit_inbound_text(&self.deps, &self.id, &raw_text).await {
+                LimitedInbound::Text(text) => text,
+                LimitedInbound::Rejected(notice) => {
+                    self.send_builtin_text(notice, "oversized-inbound").await;
+                    return Ok(());
+                }
+            }
+        };
+
+        let rewritten_text = if message.source == "system" {
+            turn_text.clone()
+        } else {
+            self.rewrite_tool_routed_command_prompt(&turn_text)
+                .unwrap_or_else(|| turn_text.clone())
+        };
+
+        let temporal_context = TemporalContext::from_runtime(self.deps.runtime_config.as_ref());
+        let message_timestamp = temporal_context.format_timestamp(message.timestamp);
+        let user_text = format_user_message(&rewritten_text, &message, &message_timestamp);
+
+        // Retriggers carry internal relay text, not a user message to recall against.
+        let recall_query = if message.source == "system" {
+            ""
+        } else {
+            turn_text.as_str()
+        };
+        let system_prompt = self.build_system_prompt(recall_query).await?;
 
         {
             let mut reply_target = self.state.reply_target_message_id.write().await;
@@ -1812,13 +2525,16 @@ impl Channel {
                 }
 
                 // Process any attachments that weren't saved (or need transcription)
+                let unsaved_attachments = voice.untranscribed(&unsaved_attachments);
                 if !unsaved_attachments.is_empty() {
-                    let extra = download_attachments(&self.deps, &unsaved_attachments).await;
+                    let extra =
+                        download_attachments(&self.deps, &unsaved_attachments, &downloaded).await;
                     content.extend(extra);
                 }
                 content
             } else {
-                download_attachments(&self.deps, &attachments).await
+                download_attachments(&self.deps, &voice.untranscribed(&attachments), &downloaded)
+                    .await
             }
         } else {
             Vec::new()
@@ -1841,6 +2557,10 @@ impl Channel {
 
         self.handle_agent_result(result, &skip_flag, &replied_flag, is_retrigger)
             .await;
+        let replied = replied_flag.load(std::sync::atomic::Ordering::Relaxed);
+        if replied || !is_retrigger {
+            self.schedule_followup(replied);
+        }
 
         // Safety-net: in quiet mode, explicit mention/reply should never be dropped silently.
         if should_send_quiet_mode_fallback(
@@ -1875,7 +2595,6 @@ impl Channel {
         // reply content payload, this fallback preserves a compact background
         // result record for the next user turn.
         if is_retrigger {
-            let replied = replied_flag.load(std::sync::atomic::Ordering::Relaxed);
             if replied && retrigger_reply_preserved {
                 tracing::debug!(
                     channel_id = %self.id,
@@ -2188,14 +2907,15 @@ impl Channel {
     }
 
     /// Assemble the full system prompt using the PromptEngine.
-    async fn build_system_prompt(&self) -> crate::error::Result<String> {
+    ///
+    /// `recall_query` is the text memories are recalled against; empty skips recall.
+    async fn build_system_prompt(&self, recall_query: &str) -> crate::error::Result<String> {
         let rc = &self.deps.runtime_config;
         let prompt_engine = rc.prompts.load();
 
         let identity_context = rc.identity.load().render();
         let memory_bulletin = rc.memory_bulletin.load();
         let skills = rc.skills.load();
-        let skills_prompt = skills.render_channel_prompt(&prompt_engine)?;
 
         let browser_enabled = rc.browser_config.load().enabled;
         let web_search_enabled = rc.brave_search_key.load().is_some();
@@ -2270,23 +2990,70 @@ impl Channel {
 
         let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
 
-        prompt_engine.render_channel_prompt_with_links(
-            empty_to_none(identity_context),
-            empty_to_none(memory_bulletin.to_string()),
-            empty_to_none(skills_prompt),
-            worker_capabilities,
-            self.conversation_context.clone(),
-            empty_to_none(status_text),
-            None, // coalesce_hint - only set for batched messages
-            available_channels,
-            sandbox_enabled,
-            org_context,
-            adapter_prompt,
-            project_context,
-            self.backfill_transcript.clone(),
-            empty_to_none(working_memory),
-            empty_to_none(channel_activity_map),
-        )
+        let identity_context = empty_to_none(identity_context);
+        let memory_bulletin = empty_to_none(memory_bulletin.to_string());
+        let status_text = empty_to_none(status_text);
+        let working_memory = empty_to_none(working_memory);
+        let channel_activity_map = empty_to_none(channel_activity_map);
+        let budget = **rc.system_prompt_token_budget.load();
+        let output_language = resolve_output_language(rc, &self.id);
+        let pinned_context = resolve_pinned_context(rc, &self.id);
+        let conversation_slots = resolve_conversation_slots(rc, &self.id);
+        let notes_context =
+            resolve_notes_context(&self.deps.sqlite_pool, rc, self.id.as_ref()).await;
+        let recalled = recall_memories(&self.deps.memory_search, rc, recall_query).await;
+        self.deps.memory_search.record_recall(&recalled).await;
+        let recalled_memories = render_recalled_memories(&recalled);
+        let persona = resolve_persona(rc, &self.id);
+
+        let budgeted = render_within_budget(
+            self.id.as_ref(),
+            &skills,
+            &prompt_engine,
+            budget,
+            |skills_prompt| {
+                prompt_engine.render_channel_prompt_with_links(
+                    identity_context.clone(),
+                    memory_bulletin.clone(),
+                    skills_prompt,
+                    worker_capabilities.clone(),
+                    self.conversation_context.clone(),
+                    status_text.clone(),
+                    None, // coalesce_hint - only set for batched messages
+                    available_channels.clone(),
+                    sandbox_enabled,
+                    org_context.clone(),
+                    adapter_prompt.clone(),
+                    project_context.clone(),
+                    self.backfill_transcript.clone(),
+                    working_memory.clone(),
+                    channel_activity_map.clone(),
+                    output_language.clone(),
+                    pinned_context.clone(),
+                    conversation_slots.clone(),
+                    notes_context.clone(),
+                    recalled_memories.clone(),
+                    persona.clone(),
+                )
+            },
+        )?;
+        Ok(budgeted.prompt)
+    }
+
+    /// Drop history from turns that started before `cutoff`, the
+    /// `history_max_age_days` boundary.
+    async fn prune_expired_history(&self, cutoff: chrono::DateTime<chrono::Utc>) {
+        let mut history = self.state.history.write().await;
+        let mut turns = self.state.history_turns.write().await;
+        let removed = prune_history_before(&mut history, &mut turns, cutoff);
+        if removed > 0 {
+            tracing::info!(
+                channel_id = %self.id,
+                removed,
+                remaining = history.len(),
+                "pruned history past its max age"
+            );
+        }
     }
 
     /// Register per-turn tools, run the LLM agentic loop, and clean up.
@@ -2308,6 +3075,10 @@ impl Channel {
         crate::tools::RepliedFlag,
         bool,
     )> {
+        let _permit = self
+            .deps
+            .acquire_process_permit(ProcessPriority::Foreground)
+            .await;
         let skip_flag = crate::tools::new_skip_flag();
         let replied_flag = crate::tools::new_replied_flag();
         let allow_direct_reply = !self.suppress_plaintext_fallback();
@@ -2323,7 +3094,8 @@ impl Channel {
             .current_inbound
             .clone()
             .unwrap_or_else(InboundMessage::empty);
-        let routed_sender = RoutedSender::new(self.response_tx.clone(), current_inbound.clone());
+        let routed_sender = RoutedSender::new(self.response_tx.clone(), current_inbound.clone())
+            .with_usage(self.hook.turn_usage().clone());
 
         // Extract Slack thread_ts from the current inbound message so cron
         // delivery targets include the originating thread.
@@ -2333,6 +3105,17 @@ impl Channel {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        self.hook
+            .set_disabled_tools(self.deps.runtime_config.disabled_tools.load_full());
+        self.hook.set_output_token_cap(
+            self.deps
+                .runtime_config
+                .routing
+                .load()
+                .max_output_tokens(ProcessType::Channel),
+        );
+        self.hook
+            .set_tool_call_limits(**self.deps.runtime_config.tool_call_limits.load());
         if let Err(error) = crate::tools::add_channel_tools(
             &self.tool_server,
             self.state.clone(),
@@ -2359,10 +3142,12 @@ impl Channel {
         } else {
             **rc.max_turns.load()
         };
-        let model_name = routing.resolve(ProcessType::Channel, None);
-        let model = SpacebotModel::make(&self.deps.llm_manager, model_name)
+        let model_name = resolve_channel_model(rc, &self.id);
+        self.hook.turn_usage().start(&model_name);
+        let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "channel")
-            .with_routing((**routing).clone());
+            .with_routing((**routing).clone())
+            .with_turn_budget(self.hook.turn_budget());
 
         let agent = AgentBuilder::new(model)
             .preamble(system_prompt)
@@ -2374,6 +3159,12 @@ impl Channel {
             .await
             .ok();
 
+        let history_cutoff = rc.channel_config.load().history_cutoff(chrono::Utc::now());
+        if let Some(cutoff) = history_cutoff {
+            self.prune_expired_history(cutoff).await;
+        }
+        let mut leading_messages = 0;
+
         // Inject attachments as a user message before the text prompt
         if !attachment_content.is_empty() {
             let mut history = self.state.history.write().await;
@@ -2381,6 +3172,7 @@ impl Channel {
                 OneOrMany::one(UserContent::text("[attachment processing failed]"))
             });
             history.push(rig::message::Message::User { content });
+            leading_messages += 1;
             drop(history);
         }
 
@@ -2403,10 +3195,23 @@ impl Channel {
                         "[acknowledged — working on it in background]",
                     )),
                 });
+                leading_messages += 1;
             }
             drop(history);
         }
 
+        if history_cutoff.is_some() {
+            self.state
+                .history_turns
+                .write()
+                .await
+                .push_back(HistoryTurn {
+                    started_at: chrono::Utc::now(),
+                    prompt: user_text.to_string(),
+                    leading_messages,
+                });
+        }
+
         // Clone history out so the write lock is released before the agentic loop.
         // The branch tool needs a read lock on history to clone it for the branch,
         // and holding a write lock across the entire agentic loop would deadlock.
@@ -2417,9 +3222,26 @@ impl Channel {
         let history_len_before = history.len();
 
         // ── Prompt snapshot capture (fire-and-forget) ──
-        self.maybe_capture_snapshot(system_prompt, user_text, &history);
+        self.maybe_capture_snapshot(system_prompt, user_text, &history, &model_name);
+
+        self.hook.set_reasoning_sender(reasoning_sender_for(
+            self.deps.runtime_config.settings.load().as_deref(),
+            &self.id,
+            &self.response_tx,
+            &current_inbound,
+        ));
 
-        let mut result = self.hook.prompt_once(&agent, &mut history, user_text).await;
+        // Race the model call against an API cancel. Dropping the call stops
+        // generation and any running tool; what was already sent stays sent.
+        let mut cancel_rx = self.state.turn_cancel.begin();
+        let mut cancelled = false;
+        let mut result = tokio::select! {
+            result = self.hook.prompt_once(&agent, &mut history, user_text) => result,
+            Ok(()) = &mut cancel_rx => {
+                cancelled = true;
+                Ok(String::new())
+            }
+        };
 
         // If the LLM responded with text that looks like tool call syntax, it failed
         // to use the tool calling API. Inject a correction and retry a couple
@@ -2427,7 +3249,8 @@ impl Channel {
         const TOOL_SYNTAX_RECOVERY_MAX_ATTEMPTS: usize = 2;
         let mut recovery_attempts = 0;
         while let Ok(ref response) = result {
-            if !crate::tools::should_block_user_visible_text(response)
+            if cancelled
+                || !crate::tools::should_block_user_visible_text(response)
                 || recovery_attempts >= TOOL_SYNTAX_RECOVERY_MAX_ATTEMPTS
             {
                 break;
@@ -2442,11 +3265,22 @@ impl Channel {
 
             let prompt_engine = self.deps.runtime_config.prompts.load();
             let correction = prompt_engine.render_system_tool_syntax_correction()?;
-            result = self
-                .hook
-                .prompt_once(&agent, &mut history, &correction)
-                .await;
+            result = tokio::select! {
+                result = self.hook.prompt_once(&agent, &mut history, &correction) => result,
+                Ok(()) = &mut cancel_rx => {
+                    cancelled = true;
+                    Ok(String::new())
+                }
+            };
+        }
+        self.state.turn_cancel.finish();
+        if cancelled {
+            result = Err(rig::completion::PromptError::PromptCancelled {
+                chat_history: Box::new(history.clone()),
+                reason: TURN_CANCELLED_REASON.to_string(),
+            });
         }
+        self.hook.set_reasoning_sender(None);
 
         let retrigger_reply_preserved = {
             let mut guard = self.state.history.write().await;
@@ -2471,7 +3305,11 @@ impl Channel {
 
     /// Send outbound text and record send metrics.
     async fn send_outbound_text(&self, text: String, error_context: &str) {
-        match self.send_routed(OutboundResponse::Text(text)).await {
+        let usage = self.hook.turn_usage().take();
+        match self
+            .send_routed_with_usage(OutboundResponse::Text(text), usage)
+            .await
+        {
             Ok(()) => {
                 #[cfg(feature = "metrics")]
                 {
@@ -2905,6 +3743,7 @@ impl Channel {
                 self.state.active_workers.write().await.remove(worker_id);
                 self.state.worker_inputs.write().await.remove(worker_id);
                 self.state.worker_injections.write().await.remove(worker_id);
+                self.state.worker_cancels.write().await.remove(worker_id);
 
                 // Record worker completion in working memory.
                 let worker_summary = if result.len() > 200 {
@@ -3284,6 +4123,7 @@ impl Channel {
         system_prompt: &str,
         user_message: &str,
         history: &[rig::message::Message],
+        model_name: &str,
     ) {
         // 1. Check if we have a snapshot store.
         let snapshot_store = match self.state.prompt_snapshot_store.as_ref() {
@@ -3319,7 +4159,7 @@ impl Channel {
         let history_length = history.len();
         let system_prompt_chars = system_prompt.chars().count();
 
-        let snapshot = crate::agent::prompt_snapshot::PromptSnapshot {
+        let mut snapshot = crate::agent::prompt_snapshot::PromptSnapshot {
             channel_id: self.id.to_string(),
             timestamp_ms: chrono::Utc::now().timestamp_millis(),
             user_message: user_message.to_string(),
@@ -3327,11 +4167,23 @@ impl Channel {
             system_prompt_chars,
             history: history_json,
             history_length,
+            model: Some(model_name.to_string()),
+            tools: Vec::new(),
         };
 
-        // 5. Fire-and-forget save.
+        // 5. Fire-and-forget save, with the turn's tool definitions so a
+        // replay sends the same request.
         let channel_id = self.id.clone();
+        let tool_server = self.tool_server.clone();
         tokio::spawn(async move {
+            match tool_server.get_tool_defs(None).await {
+                Ok(tools) => snapshot.tools = tools,
+                Err(error) => tracing::warn!(
+                    channel_id = %channel_id,
+                    %error,
+                    "failed to list tools for prompt snapshot"
+                ),
+            }
             if let Err(error) = snapshot_store.save(&snapshot) {
                 tracing::warn!(
                     channel_id = %channel_id,
@@ -3343,6 +4195,33 @@ impl Channel {
     }
 }
 
+/// Where to surface model reasoning for a turn answering `target`: a sender
+/// back to the conversation when `channel_id` has reasoning visibility
+/// switched on, otherwise None.
+fn reasoning_sender_for(
+    settings: Option<&crate::settings::SettingsStore>,
+    channel_id: &str,
+    response_tx: &mpsc::Sender<RoutedResponse>,
+    target: &InboundMessage,
+) -> Option<RoutedSender> {
+    settings
+        .is_some_and(|settings| settings.show_reasoning_enabled(channel_id))
+        .then(|| RoutedSender::new(response_tx.clone(), target.clone()))
+}
+
+/// Render a reaction trigger for the LLM: the reaction itself plus the action
+/// mapped to its emoji.
+fn render_reaction_trigger(message: &InboundMessage) -> String {
+    match message
+        .metadata
+        .get("reaction_intent")
+        .and_then(|value| value.as_str())
+    {
+        Some(intent) => format!("{}\nRequested action: {intent}", message.content),
+        None => message.content.to_string(),
+    }
+}
+
 fn compute_listen_mode_invocation(message: &InboundMessage, raw_text: &str) -> (bool, bool, bool) {
     let text = raw_text.trim();
     let invoked_by_command = text.starts_with('/');
@@ -3480,10 +4359,37 @@ fn should_send_quiet_mode_fallback(
         )
 }
 
+#[derive(Debug, Clone, Copy)]
+struct FollowupGate {
+    listen_only_mode: bool,
+    paused: bool,
+    awaiting_human: bool,
+    local_hour: u8,
+    quiet_hours: Option<(u8, u8)>,
+}
+
+/// Why a due inactivity follow-up must not be sent, if anything.
+fn followup_blocked_reason(gate: FollowupGate) -> Option<&'static str> {
+    if gate.listen_only_mode {
+        Some("listen-only mode")
+    } else if gate.paused {
+        Some("agent paused")
+    } else if gate.awaiting_human {
+        Some("handed off to a human")
+    } else if gate.quiet_hours.is_some_and(|(start, end)| {
+        crate::cron::scheduler::hour_in_active_window(gate.local_hour, start, end)
+    }) {
+        Some("quiet hours")
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        QuietModeFallbackState, compute_listen_mode_invocation, recv_channel_event,
+        FollowupGate, QuietModeFallbackState, compute_listen_mode_invocation,
+        followup_blocked_reason, reasoning_sender_for, recv_channel_event,
         should_process_event_for_channel, should_send_discord_quiet_mode_ping_ack,
         should_send_quiet_mode_fallback,
     };
@@ -3580,6 +4486,7 @@ mod tests {
             agent_id: Arc::from("agent"),
             channel_id: Arc::from("channel-b"),
             threshold_reached: 0.85,
+            reason: crate::CompactionTrigger::TokenRatio,
         };
 
         assert!(!should_process_event_for_channel(&event, &channel_id));
@@ -3758,4 +4665,143 @@ mod tests {
             }
         ));
     }
+
+    #[test]
+    fn followup_respects_mute_pause_handoff_and_quiet_hours() {
+        let open = FollowupGate {
+            listen_only_mode: false,
+            paused: false,
+            awaiting_human: false,
+            local_hour: 14,
+            quiet_hours: Some((22, 7)),
+        };
+        assert_eq!(followup_blocked_reason(open), None);
+
+        for (gate, reason) in [
+            (
+                FollowupGate {
+                    listen_only_mode: true,
+                    ..open
+                },
+                "listen-only mode",
+            ),
+            (
+                FollowupGate {
+                    paused: true,
+                    ..open
+                },
+                "agent paused",
+            ),
+            (
+                FollowupGate {
+                    awaiting_human: true,
+                    ..open
+                },
+                "handed off to a human",
+            ),
+            (
+                FollowupGate {
+                    local_hour: 23,
+                    ..open
+                },
+                "quiet hours",
+            ),
+            (
+                FollowupGate {
+                    local_hour: 3,
+                    ..open
+                },
+                "quiet hours",
+            ),
+        ] {
+            assert_eq!(followup_blocked_reason(gate), Some(reason));
+        }
+
+        // The window ends at the end hour.
+        let morning = FollowupGate {
+            local_hour: 7,
+            ..open
+        };
+        assert_eq!(followup_blocked_reason(morning), None);
+    }
+
+    fn reasoning_response() -> rig::completion::CompletionResponse<crate::llm::model::RawResponse> {
+        rig::completion::CompletionResponse {
+            choice: rig::OneOrMany::many(vec![
+                rig::message::AssistantContent::reasoning("weighing the options"),
+                rig::message::AssistantContent::tool_call(
+                    "call_1",
+                    "reply",
+                    serde_json::json!({ "content": "hello" }),
+                ),
+            ])
+            .unwrap(),
+            message_id: None,
+            usage: rig::completion::Usage::default(),
+            raw_response: crate::llm::model::RawResponse {
+                body: serde_json::json!({}),
+            },
+        }
+    }
+
+    /// Run one completion through a channel hook set up the way a turn in
+    /// `channel_id` sets it up, and collect the reasoning it surfaced.
+    async fn surfaced_reasoning(
+        settings: &crate::settings::SettingsStore,
+        channel_id: &str,
+    ) -> Vec<String> {
+        let (event_tx, _event_rx) = tokio::sync::broadcast::channel(8);
+        let hook = crate::hooks::SpacebotHook::new(
+            AgentId::from("agent"),
+            ProcessId::Channel(ChannelId::from(channel_id)),
+            crate::ProcessType::Channel,
+            Some(ChannelId::from(channel_id)),
+            event_tx,
+        );
+        let (response_tx, mut response_rx) = tokio::sync::mpsc::channel(8);
+        let target = inbound_message("discord", &[], "why?");
+        hook.set_reasoning_sender(reasoning_sender_for(
+            Some(settings),
+            channel_id,
+            &response_tx,
+            &target,
+        ));
+
+        let _ = <crate::hooks::SpacebotHook as rig::agent::PromptHook<
+            crate::llm::SpacebotModel,
+        >>::on_completion_response(
+            &hook,
+            &rig::completion::Message::from("why?"),
+            &reasoning_response(),
+        )
+        .await;
+
+        let mut surfaced = Vec::new();
+        while let Ok(routed) = response_rx.try_recv() {
+            if let crate::OutboundResponse::Reasoning(text) = routed.response {
+                surfaced.push(text);
+            }
+        }
+        surfaced
+    }
+
+    #[tokio::test]
+    async fn reasoning_is_forwarded_only_when_the_channel_toggle_is_on() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings =
+            crate::settings::SettingsStore::new(&dir.path().join("settings.redb")).unwrap();
+
+        assert!(surfaced_reasoning(&settings, "discord:1").await.is_empty());
+
+        settings.set_show_reasoning("discord:1", true).unwrap();
+        assert_eq!(
+            surfaced_reasoning(&settings, "discord:1").await,
+            vec!["weighing the options".to_string()]
+        );
+        // The toggle is per channel.
+        assert!(surfaced_reasoning(&settings, "discord:2").await.is_empty());
+
+        settings.set_show_reasoning("discord:1", false).unwrap();
+        assert!(surfaced_reasoning(&settings, "discord:1").await.is_empty());
+    }
 }
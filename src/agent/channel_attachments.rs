@@ -12,10 +12,12 @@ use crate::AgentDeps;
 use crate::config::ApiType;
 use rig::message::{ImageMediaType, MimeType, UserContent};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Image MIME types we support for vision.
-const IMAGE_MIME_PREFIXES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
+pub(crate) const IMAGE_MIME_PREFIXES: &[&str] =
+    &["image/jpeg", "image/png", "image/gif", "image/webp"];
 
 /// Text-based MIME types where we inline the content.
 const TEXT_MIME_PREFIXES: &[&str] = &[
@@ -31,10 +33,12 @@ const TEXT_MIME_PREFIXES: &[&str] = &[
 /// Download attachments and convert them to LLM-ready UserContent parts.
 ///
 /// Images become `UserContent::Image` (base64). Text files get inlined.
-/// Other file types get a metadata-only description.
+/// Other file types get a metadata-only description. Images already in
+/// `downloaded` (keyed by URL) are not fetched again.
 pub(crate) async fn download_attachments(
     deps: &AgentDeps,
     attachments: &[crate::Attachment],
+    downloaded: &HashMap<String, Vec<u8>>,
 ) -> Vec<UserContent> {
     let http = deps.llm_manager.http_client();
     let mut parts = Vec::new();
@@ -48,7 +52,7 @@ pub(crate) async fn download_attachments(
             .any(|p| attachment.mime_type.starts_with(p));
 
         let content = if is_image {
-            download_image_attachment(http, attachment).await
+            download_image_attachment(http, attachment, downloaded).await
         } else if is_text {
             download_text_attachment(http, attachment).await
         } else if attachment.mime_type.starts_with("audio/") {
@@ -75,7 +79,7 @@ pub(crate) async fn download_attachments(
 /// When `auth_header` is set (Slack), uses a no-redirect client and manually
 /// follows redirects so the `Authorization` header isn't silently stripped on
 /// cross-origin redirects. For public URLs (Discord/Telegram), uses a plain GET.
-async fn download_attachment_bytes(
+pub(crate) async fn download_attachment_bytes(
     http: &reqwest::Client,
     attachment: &crate::Attachment,
 ) -> std::result::Result<Vec<u8>, String> {
@@ -157,12 +161,26 @@ async fn download_attachment_bytes_with_auth(
     Err("too many redirects".into())
 }
 
+/// The attachment's bytes from `downloaded` (keyed by URL), or a fresh
+/// download when it isn't there.
+async fn attachment_bytes(
+    http: &reqwest::Client,
+    attachment: &crate::Attachment,
+    downloaded: &HashMap<String, Vec<u8>>,
+) -> std::result::Result<Vec<u8>, String> {
+    match downloaded.get(&attachment.url) {
+        Some(bytes) => Ok(bytes.clone()),
+        None => download_attachment_bytes(http, attachment).await,
+    }
+}
+
 /// Download an image attachment and encode it as base64 for the LLM.
 async fn download_image_attachment(
     http: &reqwest::Client,
     attachment: &crate::Attachment,
+    downloaded: &HashMap<String, Vec<u8>>,
 ) -> UserContent {
-    let bytes = match download_attachment_bytes(http, attachment).await {
+    let bytes = match attachment_bytes(http, attachment, downloaded).await {
         Ok(b) => b,
         Err(error) => {
             tracing::warn!(%error, filename = %attachment.filename, "failed to download image");
@@ -341,7 +359,7 @@ async fn transcribe_audio_attachment(
     ))
 }
 
-fn audio_format_for_attachment(attachment: &crate::Attachment) -> &'static str {
+pub(crate) fn audio_format_for_attachment(attachment: &crate::Attachment) -> &'static str {
     let mime = attachment.mime_type.to_lowercase();
     if mime.contains("mpeg") || mime.contains("mp3") {
         return "mp3";
@@ -515,13 +533,15 @@ pub struct SavedAttachmentMeta {
 /// the caller can annotate the conversation message.
 ///
 /// Also returns the raw bytes keyed by index so the caller can reuse them for
-/// LLM processing without a second download.
+/// LLM processing without a second download. Attachments already in
+/// `downloaded` (keyed by URL) are not fetched again.
 pub(crate) async fn save_channel_attachments(
     pool: &sqlx::SqlitePool,
     http: &reqwest::Client,
     channel_id: &str,
     saved_dir: &Path,
     attachments: &[crate::Attachment],
+    downloaded: &HashMap<String, Vec<u8>>,
 ) -> Vec<(SavedAttachmentMeta, Vec<u8>)> {
     let mut results = Vec::with_capacity(attachments.len());
 
@@ -538,7 +558,7 @@ pub(crate) async fn save_channel_attachments(
             }
         };
 
-        let bytes = match download_attachment_bytes(http, attachment).await {
+        let bytes = match attachment_bytes(http, attachment, downloaded).await {
             Ok(bytes) => bytes,
             Err(error) => {
                 tracing::warn!(
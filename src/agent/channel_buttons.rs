@@ -0,0 +1,78 @@
+//! Button interactions that dispatch an action directly.
+//!
+//! A button whose `custom_id` starts with a registered prefix (for example
+//! `run_worker:research` or `run_cron:daily-digest`) runs the mapped action
+//! when clicked instead of reaching the LLM as an interaction message. Any
+//! other `custom_id` keeps the normal interaction-as-message handling.
+
+/// What a prefixed button does when clicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonActionKind {
+    /// Spawn the worker task configured under the name in `worker_buttons`.
+    RunWorker,
+    /// Run the cron job with this ID now, outside its schedule.
+    RunCron,
+}
+
+/// Registered `custom_id` prefixes and the action each one dispatches.
+pub const BUTTON_ACTION_PREFIXES: [(&str, ButtonActionKind); 2] = [
+    ("run_worker:", ButtonActionKind::RunWorker),
+    ("run_cron:", ButtonActionKind::RunCron),
+];
+
+/// A button click resolved against [`BUTTON_ACTION_PREFIXES`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonAction {
+    pub kind: ButtonActionKind,
+    /// The part of the `custom_id` after the prefix: a worker button name or
+    /// a cron job ID.
+    pub target: String,
+}
+
+/// Resolve a button `custom_id` to an action. Returns `None` for IDs without
+/// a registered prefix, or with nothing after it.
+pub fn parse_button_action(custom_id: &str) -> Option<ButtonAction> {
+    BUTTON_ACTION_PREFIXES
+        .iter()
+        .find_map(|(prefix, kind)| {
+            custom_id
+                .strip_prefix(prefix)
+                .map(|target| (*kind, target.trim()))
+        })
+        .filter(|(_, target)| !target.is_empty())
+        .map(|(kind, target)| ButtonAction {
+            kind,
+            target: target.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_prefixes_resolve_to_actions() {
+        assert_eq!(
+            parse_button_action("run_worker:research"),
+            Some(ButtonAction {
+                kind: ButtonActionKind::RunWorker,
+                target: "research".into(),
+            })
+        );
+        assert_eq!(
+            parse_button_action("run_cron:daily-digest"),
+            Some(ButtonAction {
+                kind: ButtonActionKind::RunCron,
+                target: "daily-digest".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn other_ids_fall_back_to_interaction_messages() {
+        assert_eq!(parse_button_action("approve"), None);
+        assert_eq!(parse_button_action("run_worker:"), None);
+        assert_eq!(parse_button_action("run_worker: "), None);
+        assert_eq!(parse_button_action("RUN_WORKER:research"), None);
+    }
+}
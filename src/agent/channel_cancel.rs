@@ -0,0 +1,71 @@
+//! Cancellation of a channel turn that is in flight.
+//!
+//! The channel registers each model call with [`TurnCancel`] and races it
+//! against the cancel signal. Cancelling drops the model call, which stops
+//! generation and any tool call that was running. Replies already sent stay
+//! sent; the turn ends as a `PromptCancelled` with [`TURN_CANCELLED_REASON`].
+
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+/// `PromptCancelled` reason for a turn stopped through [`TurnCancel`].
+pub const TURN_CANCELLED_REASON: &str = "turn cancelled";
+
+/// Cancel handle for the channel's running turn, if any.
+#[derive(Debug, Default)]
+pub struct TurnCancel {
+    current: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl TurnCancel {
+    /// Mark a turn as running. The receiver resolves if it is cancelled.
+    pub fn begin(&self) -> oneshot::Receiver<()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        *self.lock() = Some(cancel_tx);
+        cancel_rx
+    }
+
+    /// Mark the running turn as finished.
+    pub fn finish(&self) {
+        self.lock().take();
+    }
+
+    /// Cancel the running turn. Returns false, doing nothing, when no turn
+    /// is running.
+    pub fn cancel(&self) -> bool {
+        self.lock()
+            .take()
+            .is_some_and(|cancel_tx| cancel_tx.send(()).is_ok())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<oneshot::Sender<()>>> {
+        self.current
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_without_running_turn_is_a_no_op() {
+        let cancel = TurnCancel::default();
+        assert!(!cancel.cancel());
+
+        let _cancel_rx = cancel.begin();
+        cancel.finish();
+        assert!(!cancel.cancel());
+    }
+
+    #[tokio::test]
+    async fn cancel_signals_running_turn_once() {
+        let cancel = TurnCancel::default();
+        let cancel_rx = cancel.begin();
+
+        assert!(cancel.cancel());
+        assert!(!cancel.cancel());
+        assert_eq!(cancel_rx.await, Ok(()));
+    }
+}
@@ -368,6 +368,16 @@ async fn spawn_branch(
     Ok(branch_id)
 }
 
+/// Refuse worker dispatch while a PAUSE kill switch file is present.
+fn check_not_paused(state: &ChannelState) -> std::result::Result<(), AgentError> {
+    if state.deps.runtime_config.is_paused() {
+        return Err(AgentError::Paused {
+            agent_id: state.deps.agent_id.to_string(),
+        });
+    }
+    Ok(())
+}
+
 /// Check whether the channel has capacity for another worker.
 ///
 /// Uses `worker_handles` as the source of truth for active workers, since
@@ -434,6 +444,7 @@ pub async fn spawn_worker_from_state(
     interactive: bool,
     suggested_skills: &[&str],
 ) -> std::result::Result<WorkerId, AgentError> {
+    check_not_paused(state)?;
     check_worker_limit(state).await?;
     let task = task.into();
     reserve_task_if_unique(state, &task).await?;
@@ -546,6 +557,13 @@ async fn spawn_worker_inner(
     };
 
     let worker_id = worker.id;
+    let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+    state
+        .worker_cancels
+        .write()
+        .await
+        .insert(worker_id, cancel_tx);
+    let worker = worker.with_cancel_signal(cancel_rx);
 
     let worker_span = tracing::info_span!(
         "worker.run",
@@ -617,6 +635,7 @@ pub async fn spawn_opencode_worker_from_state(
         )));
     }
 
+    check_not_paused(state)?;
     check_worker_limit(state).await?;
     let task = task.into();
     reserve_task_if_unique(state, &task).await?;
@@ -691,7 +710,9 @@ async fn spawn_opencode_worker_inner(
             Some(store) => worker.with_secrets_store(store.clone()),
             None => worker,
         };
-        worker.with_sqlite_pool(state.deps.sqlite_pool.clone())
+        worker
+            .with_sqlite_pool(state.deps.sqlite_pool.clone())
+            .with_process_limit(state.deps.clone())
     } else {
         let worker = crate::opencode::OpenCodeWorker::new(
             Some(state.channel_id.clone()),
@@ -709,7 +730,9 @@ async fn spawn_opencode_worker_inner(
             Some(store) => worker.with_secrets_store(store.clone()),
             None => worker,
         };
-        worker.with_sqlite_pool(state.deps.sqlite_pool.clone())
+        worker
+            .with_sqlite_pool(state.deps.sqlite_pool.clone())
+            .with_process_limit(state.deps.clone())
     };
 
     let worker_id = worker.id;
@@ -970,7 +993,9 @@ pub async fn resume_idle_worker_into_state(
             if let Some(store) = &oc_secrets_store {
                 worker = worker.with_secrets_store(store.clone());
             }
-            worker = worker.with_sqlite_pool(state.deps.sqlite_pool.clone());
+            worker = worker
+                .with_sqlite_pool(state.deps.sqlite_pool.clone())
+                .with_process_limit(state.deps.clone());
 
             state
                 .worker_inputs
@@ -1110,6 +1135,13 @@ pub async fn resume_idle_worker_into_state(
                 .write()
                 .await
                 .insert(worker_id, inject_tx);
+            let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+            state
+                .worker_cancels
+                .write()
+                .await
+                .insert(worker_id, cancel_tx);
+            let worker = worker.with_cancel_signal(cancel_rx);
 
             let worker_span = tracing::info_span!(
                 "worker.resume",
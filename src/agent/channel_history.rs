@@ -1,11 +1,14 @@
 //! History management and message formatting for channels.
 //!
 //! Pure functions that operate on `rig::message::Message` vectors —
-//! history reconciliation after LLM turns, user message formatting,
-//! reply extraction from cancelled turns, and event filtering.
+//! history reconciliation after LLM turns, age-based pruning, user message
+//! formatting, reply extraction from cancelled turns, and event filtering.
 
 use crate::{ChannelId, InboundMessage, ProcessEvent};
 
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
 /// Write history back after the agentic loop completes.
 ///
 /// On success or `MaxTurnsError`, the history Rig built is consistent and safe
@@ -183,7 +186,7 @@ fn extract_reply_content_from_cancelled_history(
 
 /// Returns true if a message is a User message containing only text content
 /// (i.e., an actual user prompt, not a tool result).
-fn is_user_text_message(message: &rig::message::Message) -> bool {
+pub(crate) fn is_user_text_message(message: &rig::message::Message) -> bool {
     match message {
         rig::message::Message::User { content } => content
             .iter()
@@ -192,6 +195,65 @@ fn is_user_text_message(message: &rig::message::Message) -> bool {
     }
 }
 
+/// A channel turn as recorded for `history_max_age_days`. History messages
+/// carry no timestamps, so a turn is found again by its prompt text.
+#[derive(Debug, Clone)]
+pub(crate) struct HistoryTurn {
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) prompt: String,
+    /// Messages the turn pushed ahead of its prompt (attachments, the
+    /// retrigger bridge).
+    pub(crate) leading_messages: usize,
+}
+
+/// Drop history from turns that started before `cutoff`.
+///
+/// Everything ahead of the oldest turn still inside the window is removed,
+/// along with the records of expired turns. When every recorded turn has
+/// expired the whole history goes. When the surviving turns were compacted
+/// away, history is left alone, since the summary also covers recent turns.
+/// Pinned context lives in the system prompt and is never touched. Returns
+/// how many messages were removed.
+pub(crate) fn prune_history_before(
+    history: &mut Vec<rig::message::Message>,
+    turns: &mut VecDeque<HistoryTurn>,
+    cutoff: DateTime<Utc>,
+) -> usize {
+    let recorded = turns.len();
+    while turns.front().is_some_and(|turn| turn.started_at < cutoff) {
+        turns.pop_front();
+    }
+    if turns.len() == recorded {
+        return 0;
+    }
+    if turns.is_empty() {
+        let removed = history.len();
+        history.clear();
+        return removed;
+    }
+
+    let Some((turn_index, prompt_index)) = turns.iter().enumerate().find_map(|(index, turn)| {
+        history
+            .iter()
+            .position(|message| is_user_prompt(message, &turn.prompt))
+            .map(|position| (index, position.saturating_sub(turn.leading_messages)))
+    }) else {
+        return 0;
+    };
+    turns.drain(..turn_index);
+    history.drain(..prompt_index);
+    prompt_index
+}
+
+fn is_user_prompt(message: &rig::message::Message, prompt: &str) -> bool {
+    match message {
+        rig::message::Message::User { content } => content
+            .iter()
+            .any(|c| matches!(c, rig::message::UserContent::Text(text) if text.text == prompt)),
+        _ => false,
+    }
+}
+
 /// Some models emit tool call syntax as plain text instead of making actual tool calls.
 /// When the text starts with a tool-like prefix (e.g. `[reply]`, `(reply)`), try to
 /// extract the reply content so we can send it cleanly instead of showing raw JSON.
@@ -293,20 +355,9 @@ pub(crate) fn format_user_message(
 
     let reply_context = message
         .metadata
-        .get("reply_to_author")
+        .get(crate::metadata_keys::REPLY_TO_AUTHOR)
         .and_then(|v| v.as_str())
-        .map(|author| {
-            let content_preview = message
-                .metadata
-                .get(crate::metadata_keys::REPLY_TO_TEXT)
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            if content_preview.is_empty() {
-                format!(" (replying to {author})")
-            } else {
-                format!(" (replying to {author}: \"{content_preview}\")")
-            }
-        })
+        .map(|author| format!(" (replying to {author})"))
         .unwrap_or_default();
 
     // If raw_text is empty or just whitespace, use a placeholder to avoid
@@ -325,9 +376,33 @@ pub(crate) fn format_user_message(
         .map(|s| format!(" {s}"))
         .unwrap_or_default();
 
-    format!(
+    let line = format!(
         "{display_name}{bot_tag}{reply_context}{sender_context} [{timestamp_text}]: {text_content}"
-    )
+    );
+    match format_reply_context(message) {
+        Some(block) => format!("{block}\n{line}"),
+        None => line,
+    }
+}
+
+/// Context block quoting the message this one replies to, placed above the
+/// message so the model sees what is being answered even when the original
+/// is outside the history window. None when the adapter captured no text.
+pub(crate) fn format_reply_context(message: &InboundMessage) -> Option<String> {
+    let quoted = message
+        .metadata
+        .get(crate::metadata_keys::REPLY_TO_TEXT)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())?;
+    let author = message
+        .metadata
+        .get(crate::metadata_keys::REPLY_TO_AUTHOR)
+        .and_then(|v| v.as_str());
+    Some(match author {
+        Some(author) => format!("(in reply to {author}: \"{quoted}\")"),
+        None => format!("(in reply to: \"{quoted}\")"),
+    })
 }
 
 pub(crate) fn format_batched_user_message(
@@ -394,6 +469,10 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
             channel_id: event_channel,
             ..
         }
+        | ProcessEvent::MemoryDeleted {
+            channel_id: event_channel,
+            ..
+        }
         | ProcessEvent::WorkerPermission {
             channel_id: event_channel,
             ..
@@ -406,6 +485,14 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
             channel_id: event_channel,
             ..
         }
+        | ProcessEvent::CompactionProgress {
+            channel_id: event_channel,
+            ..
+        }
+        | ProcessEvent::CompactionCompleted {
+            channel_id: event_channel,
+            ..
+        }
         | ProcessEvent::AgentMessageSent {
             channel_id: event_channel,
             ..
@@ -433,6 +520,7 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
         ProcessEvent::OpenCodePartUpdated { .. }
         | ProcessEvent::StatusUpdate { .. }
         | ProcessEvent::TaskUpdated { .. }
+        | ProcessEvent::MemoriesConsolidated { .. }
         | ProcessEvent::WorkerText { .. }
         | ProcessEvent::CortexChatUpdate { .. } => false,
     }
@@ -440,7 +528,9 @@ pub(crate) fn event_is_for_channel(event: &ProcessEvent, channel_id: &ChannelId)
 
 #[cfg(test)]
 mod tests {
-    use super::{apply_history_after_turn, event_is_for_channel};
+    use super::{
+        HistoryTurn, apply_history_after_turn, event_is_for_channel, prune_history_before,
+    };
     use crate::{ChannelId, ProcessEvent, ProcessId};
     use rig::completion::{CompletionError, PromptError};
     use rig::message::Message;
@@ -868,6 +958,90 @@ mod tests {
         );
     }
 
+    fn turn(day: u32, prompt: &str, leading_messages: usize) -> HistoryTurn {
+        HistoryTurn {
+            started_at: chrono::NaiveDate::from_ymd_opt(2026, 3, day)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .expect("valid date")
+                .and_utc(),
+            prompt: prompt.to_string(),
+            leading_messages,
+        }
+    }
+
+    #[test]
+    fn prune_drops_turns_before_the_cutoff() {
+        let mut history = vec![
+            user_msg("old question"),
+            assistant_msg("old answer"),
+            user_msg("[attachment]"),
+            user_msg("new question"),
+            assistant_msg("new answer"),
+        ];
+        let mut turns = std::collections::VecDeque::from([
+            turn(1, "old question", 0),
+            turn(9, "new question", 1),
+        ]);
+        let cutoff = turn(5, "", 0).started_at;
+
+        assert_eq!(prune_history_before(&mut history, &mut turns, cutoff), 2);
+        assert_eq!(
+            history,
+            vec![
+                user_msg("[attachment]"),
+                user_msg("new question"),
+                assistant_msg("new answer"),
+            ]
+        );
+        assert_eq!(turns.len(), 1);
+
+        // Nothing else has expired.
+        assert_eq!(prune_history_before(&mut history, &mut turns, cutoff), 0);
+        assert_eq!(history.len(), 3);
+    }
+
+    #[test]
+    fn prune_clears_history_once_every_turn_expired() {
+        let mut history = make_history(&["summary of earlier turns", "ok", "question", "answer"]);
+        let mut turns = std::collections::VecDeque::from([turn(1, "question", 0)]);
+        let cutoff = turn(5, "", 0).started_at;
+
+        assert_eq!(prune_history_before(&mut history, &mut turns, cutoff), 4);
+        assert!(history.is_empty());
+        assert!(turns.is_empty());
+    }
+
+    #[test]
+    fn format_user_message_prepends_reply_context() {
+        use super::format_user_message;
+        use crate::InboundMessage;
+
+        let mut message = InboundMessage::empty();
+        message.source = "discord".to_string();
+        message.formatted_author = Some("Alice".to_string());
+        message
+            .metadata
+            .insert(crate::metadata_keys::REPLY_TO_AUTHOR.into(), "Bob".into());
+        message.metadata.insert(
+            crate::metadata_keys::REPLY_TO_TEXT.into(),
+            "the deploy is at 5".into(),
+        );
+
+        let formatted = format_user_message("moved to 6?", &message, "2026-02-26 12:00:00 UTC");
+        assert_eq!(
+            formatted,
+            "(in reply to Bob: \"the deploy is at 5\")\n\
+             Alice (replying to Bob) [2026-02-26 12:00:00 UTC]: moved to 6?"
+        );
+
+        message.metadata.remove(crate::metadata_keys::REPLY_TO_TEXT);
+        let formatted = format_user_message("hi", &message, "2026-02-26 12:00:00 UTC");
+        assert_eq!(
+            formatted,
+            "Alice (replying to Bob) [2026-02-26 12:00:00 UTC]: hi"
+        );
+    }
+
     #[test]
     fn format_user_message_handles_empty_text() {
         use super::format_user_message;
@@ -4,7 +4,7 @@
 //! all the prompt-building methods that assemble the channel's
 //! system prompt from identity, memory bulletin, skills, status, etc.
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, Timelike, Utc};
 use chrono_tz::Tz;
 
 /// Debounce window for retriggers: coalesce rapid branch/worker completions
@@ -114,6 +114,15 @@ impl TemporalContext {
         }
     }
 
+    /// Hour of the day (0-23) in the resolved timezone.
+    pub(crate) fn local_hour(&self) -> u8 {
+        let hour = match &self.timezone {
+            TemporalTimezone::Named { timezone, .. } => self.now_utc.with_timezone(timezone).hour(),
+            TemporalTimezone::SystemLocal => self.now_utc.with_timezone(&Local).hour(),
+        };
+        hour as u8
+    }
+
     pub(crate) fn current_time_line(&self) -> String {
         format!(
             "{}; UTC {}",
@@ -122,3 +131,586 @@ impl TemporalContext {
         )
     }
 }
+
+/// A rendered channel system prompt after the token budget has been applied.
+#[derive(Debug)]
+pub(crate) struct BudgetedSystemPrompt {
+    pub(crate) prompt: String,
+    pub(crate) estimated_tokens: usize,
+    /// Skills left out of the prompt to fit the budget, in the order dropped.
+    pub(crate) trimmed_skills: Vec<String>,
+}
+
+/// Rough token estimate for rendered prompt text, using the same ~4 chars
+/// per token heuristic as the compactor.
+pub(crate) fn estimate_prompt_tokens(text: &str) -> usize {
+    text.chars().count() / 4
+}
+
+/// Render the system prompt, dropping skills from the summary until the
+/// estimated size fits within `budget`.
+///
+/// `render` receives the skills section (or None when empty) and returns the
+/// full prompt. Skills are the only section trimmed — identity, bulletin, and
+/// status are always kept, so the result can still exceed the budget.
+pub(crate) fn render_within_budget(
+    channel_id: &str,
+    skills: &crate::skills::SkillSet,
+    prompt_engine: &crate::prompts::PromptEngine,
+    budget: Option<usize>,
+    render: impl Fn(Option<String>) -> crate::error::Result<String>,
+) -> crate::error::Result<BudgetedSystemPrompt> {
+    let render_excluding = |excluded: &[String]| {
+        let skills_prompt = skills.render_channel_prompt_excluding(prompt_engine, excluded)?;
+        render((!skills_prompt.is_empty()).then_some(skills_prompt))
+    };
+
+    let mut trimmed_skills = Vec::new();
+    let mut prompt = render_excluding(&trimmed_skills)?;
+    let mut estimated_tokens = estimate_prompt_tokens(&prompt);
+
+    let Some(budget) = budget else {
+        return Ok(BudgetedSystemPrompt {
+            prompt,
+            estimated_tokens,
+            trimmed_skills,
+        });
+    };
+
+    let untrimmed_tokens = estimated_tokens;
+    for skill_name in skills.trim_order() {
+        if estimated_tokens <= budget {
+            break;
+        }
+        trimmed_skills.push(skill_name);
+        prompt = render_excluding(&trimmed_skills)?;
+        estimated_tokens = estimate_prompt_tokens(&prompt);
+    }
+
+    if !trimmed_skills.is_empty() {
+        tracing::info!(
+            channel_id,
+            budget,
+            untrimmed_tokens,
+            estimated_tokens,
+            trimmed = %trimmed_skills.join(", "),
+            "trimmed skills from system prompt to fit token budget"
+        );
+    }
+    if estimated_tokens > budget {
+        tracing::warn!(
+            channel_id,
+            budget,
+            estimated_tokens,
+            "system prompt exceeds token budget with no skills left to trim"
+        );
+    }
+
+    Ok(BudgetedSystemPrompt {
+        prompt,
+        estimated_tokens,
+        trimmed_skills,
+    })
+}
+
+/// Reply language for a channel: the per-channel settings override when one
+/// is set, otherwise the agent's configured `output_language`.
+pub(crate) fn resolve_output_language(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> Option<String> {
+    let settings = runtime_config.settings.load();
+    settings
+        .as_ref()
+        .as_ref()
+        .and_then(|settings| settings.output_language_for(channel_id))
+        .or_else(|| runtime_config.output_language.load().as_ref().clone())
+        .map(|language| language.trim().to_string())
+        .filter(|language| !language.is_empty())
+}
+
+/// The model a channel runs on: its pinned model if one is set, otherwise the
+/// agent's channel routing.
+pub(crate) fn resolve_channel_model(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> String {
+    let settings = runtime_config.settings.load();
+    settings
+        .as_ref()
+        .as_ref()
+        .and_then(|settings| settings.channel_model_for(channel_id))
+        .unwrap_or_else(|| {
+            runtime_config
+                .routing
+                .load()
+                .resolve(crate::ProcessType::Channel, None)
+                .to_string()
+        })
+}
+
+/// The active persona for a channel, or None when no persona is set or the
+/// stored name is no longer configured.
+pub(crate) fn resolve_persona(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> Option<crate::prompts::engine::ActivePersona> {
+    let settings = runtime_config.settings.load();
+    let name = settings
+        .as_ref()
+        .as_ref()
+        .and_then(|settings| settings.active_persona_for(channel_id))?;
+    let personas = runtime_config.personas.load();
+    let Some(prompt) = personas.prompt_for(&name) else {
+        tracing::debug!(%channel_id, persona = %name, "active persona is no longer configured");
+        return None;
+    };
+    Some(crate::prompts::engine::ActivePersona {
+        name,
+        prompt: prompt.to_string(),
+    })
+}
+
+/// Pinned snippets for a channel rendered as a prompt list, or None when
+/// nothing is pinned.
+pub(crate) fn resolve_pinned_context(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> Option<String> {
+    let settings = runtime_config.settings.load();
+    let pins = settings
+        .as_ref()
+        .as_ref()
+        .map(|settings| settings.pinned_context_for(channel_id))
+        .unwrap_or_default();
+    render_pinned_context(&pins)
+}
+
+fn render_pinned_context(pins: &[crate::settings::PinnedContext]) -> Option<String> {
+    if pins.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = pins
+        .iter()
+        .map(|pin| {
+            // Indent continuation lines so multi-line pins stay one list item.
+            let text = pin.text.lines().collect::<Vec<_>>().join("\n  ");
+            format!("- [{}] {}", pin.id, text)
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// The conversation's slots rendered as a prompt list, or None when none are
+/// set or `slots_in_prompt` is off.
+pub(crate) fn resolve_conversation_slots(
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> Option<String> {
+    if !runtime_config.channel_config.load().slots_in_prompt {
+        return None;
+    }
+    let settings = runtime_config.settings.load();
+    let slots = settings
+        .as_ref()
+        .as_ref()
+        .map(|settings| settings.conversation_slots_for(channel_id))
+        .unwrap_or_default();
+    render_conversation_slots(&slots)
+}
+
+fn render_conversation_slots(
+    slots: &std::collections::BTreeMap<String, crate::settings::ConversationSlot>,
+) -> Option<String> {
+    if slots.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = slots
+        .iter()
+        .map(|(key, slot)| {
+            // Indent continuation lines so multi-line values stay one list item.
+            let value = slot.value.lines().collect::<Vec<_>>().join("\n  ");
+            format!("- {key}: {value}")
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// Memories recalled for `query` under the channel's recall settings, in
+/// prompt order. Empty when recall is disabled or the query is blank. Turns
+/// that put these in the prompt record them with
+/// [`MemorySearch::record_recall`](crate::memory::MemorySearch::record_recall).
+pub(crate) async fn recall_memories(
+    memory_search: &crate::memory::MemorySearch,
+    runtime_config: &crate::config::RuntimeConfig,
+    query: &str,
+) -> Vec<crate::memory::types::MemorySearchResult> {
+    let channel_config = runtime_config.channel_config.load();
+    let count = channel_config.memory_recall_count;
+    let query = query.trim();
+    if count == 0 || query.is_empty() {
+        return Vec::new();
+    }
+    let config = crate::memory::SearchConfig {
+        max_results: count,
+        max_results_per_source: count * 2,
+        ..Default::default()
+    };
+    let mut results = match memory_search.search(query, &config).await {
+        Ok(results) => results,
+        Err(error) => {
+            tracing::warn!(%error, "failed to recall memories for prompt");
+            return Vec::new();
+        }
+    };
+    results.truncate(count);
+    order_recalled_memories(&mut results, channel_config.memory_recall_order);
+    results
+}
+
+fn order_recalled_memories(
+    results: &mut [crate::memory::types::MemorySearchResult],
+    order: crate::config::MemoryRecallOrder,
+) {
+    // Search results already come most relevant first.
+    if order == crate::config::MemoryRecallOrder::Recency {
+        results.sort_by(|a, b| b.memory.updated_at.cmp(&a.memory.updated_at));
+    }
+}
+
+/// Recalled memories rendered as a prompt list, or None when there are none.
+pub(crate) fn render_recalled_memories(
+    results: &[crate::memory::types::MemorySearchResult],
+) -> Option<String> {
+    if results.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let content = result
+                .memory
+                .content
+                .lines()
+                .collect::<Vec<_>>()
+                .join("\n  ");
+            format!(
+                "- [{}, {}] {}",
+                result.memory.memory_type,
+                result.memory.updated_at.format("%Y-%m-%d"),
+                content
+            )
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// The agent-wide note and this channel's note rendered for the prompt, or
+/// None when both are empty or injection is disabled.
+pub(crate) async fn resolve_notes_context(
+    pool: &sqlx::SqlitePool,
+    runtime_config: &crate::config::RuntimeConfig,
+    channel_id: &str,
+) -> Option<String> {
+    let max_chars = runtime_config.channel_config.load().notes_prompt_max_chars;
+    if max_chars == 0 {
+        return None;
+    }
+    let store = crate::notes::NotesStore::new(pool.clone());
+    let (agent_note, channel_note) =
+        match tokio::try_join!(store.read(None), store.read(Some(channel_id))) {
+            Ok(notes) => notes,
+            Err(error) => {
+                tracing::warn!(%error, %channel_id, "failed to load notes for prompt");
+                return None;
+            }
+        };
+    render_notes_context(agent_note.as_ref(), channel_note.as_ref(), max_chars)
+}
+
+fn render_notes_context(
+    agent_note: Option<&crate::notes::Note>,
+    channel_note: Option<&crate::notes::Note>,
+    max_chars: usize,
+) -> Option<String> {
+    let sections: Vec<String> = [
+        ("Agent notes", agent_note),
+        ("Notes for this conversation", channel_note),
+    ]
+    .into_iter()
+    .filter_map(|(heading, note)| {
+        let note = note.filter(|note| !note.content.trim().is_empty())?;
+        let length = note.content.chars().count();
+        let body = if length <= max_chars {
+            note.content.trim_end().to_string()
+        } else {
+            // Too long to inline; point at the tool instead of truncating.
+            format!("({length} characters. Read it with the `notes` tool.)")
+        };
+        Some(format!("### {heading}\n\n{body}"))
+    })
+    .collect();
+
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+/// Best-effort check that a reply is written in `language`.
+///
+/// Only languages with a distinctive script can be checked; returns None for
+/// anything else (including Latin-script languages) and for replies too short
+/// to judge.
+pub(crate) fn reply_matches_language(text: &str, language: &str) -> Option<bool> {
+    let language = language.trim().to_ascii_lowercase();
+    let base = language.split(['-', '_']).next().unwrap_or_default();
+    let in_script: fn(char) -> bool = match base {
+        "japanese" | "ja" => |c| matches!(c, '\u{3040}'..='\u{30ff}' | '\u{4e00}'..='\u{9fff}'),
+        "chinese" | "zh" | "mandarin" | "cantonese" => {
+            |c| matches!(c, '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}')
+        }
+        "korean" | "ko" => {
+            |c| matches!(c, '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}' | '\u{ac00}'..='\u{d7af}')
+        }
+        "russian" | "ru" | "ukrainian" | "uk" | "bulgarian" | "bg" => {
+            |c| matches!(c, '\u{0400}'..='\u{04ff}')
+        }
+        "greek" | "el" => |c| matches!(c, '\u{0370}'..='\u{03ff}'),
+        "arabic" | "ar" | "persian" | "farsi" | "fa" | "urdu" | "ur" => {
+            |c| matches!(c, '\u{0600}'..='\u{06ff}')
+        }
+        "hebrew" | "he" => |c| matches!(c, '\u{0590}'..='\u{05ff}'),
+        "hindi" | "hi" => |c| matches!(c, '\u{0900}'..='\u{097f}'),
+        "thai" | "th" => |c| matches!(c, '\u{0e00}'..='\u{0e7f}'),
+        _ => return None,
+    };
+
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() < 8 {
+        return None;
+    }
+    // Code, names, and URLs stay in Latin script, so only require that a
+    // meaningful share of the letters are in the expected script.
+    let matching = letters.iter().filter(|c| in_script(**c)).count();
+    Some(matching * 10 >= letters.len() * 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        order_recalled_memories, render_conversation_slots, render_notes_context,
+        render_pinned_context, render_recalled_memories, reply_matches_language,
+        resolve_channel_model,
+    };
+    use crate::config::MemoryRecallOrder;
+    use crate::memory::types::{Memory, MemorySearchResult, MemoryType};
+    use crate::settings::{ConversationSlot, PinnedContext};
+
+    #[test]
+    fn pinned_channel_model_overrides_routing_until_unpinned() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let config =
+            crate::config::Config::load_from_env(dir.path()).expect("failed to build config");
+        let resolved = config
+            .resolve_agents()
+            .into_iter()
+            .next()
+            .expect("missing resolved agent config");
+        let runtime_config = crate::config::RuntimeConfig::new(
+            dir.path(),
+            &resolved,
+            &config.defaults,
+            crate::prompts::PromptEngine::new("en").unwrap(),
+            crate::identity::Identity::default(),
+            crate::skills::SkillSet::default(),
+        );
+        let routed = runtime_config
+            .routing
+            .load()
+            .resolve(crate::ProcessType::Channel, None)
+            .to_string();
+        let settings = std::sync::Arc::new(
+            crate::settings::SettingsStore::new(&dir.path().join("settings.redb"))
+                .expect("failed to open settings store"),
+        );
+        runtime_config.set_settings(settings.clone(), None);
+
+        assert_eq!(resolve_channel_model(&runtime_config, "discord:1"), routed);
+
+        settings
+            .set_channel_model_for("discord:1", Some("openai/gpt-4.1-mini"))
+            .unwrap();
+        assert_eq!(
+            resolve_channel_model(&runtime_config, "discord:1"),
+            "openai/gpt-4.1-mini"
+        );
+        // Pins are per channel.
+        assert_eq!(resolve_channel_model(&runtime_config, "discord:2"), routed);
+
+        settings.set_channel_model_for("discord:1", None).unwrap();
+        assert_eq!(resolve_channel_model(&runtime_config, "discord:1"), routed);
+    }
+
+    #[test]
+    fn stored_pins_reach_the_channel_prompt_after_compaction() {
+        // Compaction only rewrites the conversation history. Pins live in the
+        // settings store and are rendered into the system prompt every turn.
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let settings = crate::settings::SettingsStore::new(&dir.path().join("settings.redb"))
+            .expect("failed to open settings store");
+        let pin = settings
+            .pin_context("discord:1", "The customer's order number is A-123.")
+            .unwrap();
+
+        let pinned_context = render_pinned_context(&settings.pinned_context_for("discord:1"));
+        let prompt = crate::prompts::PromptEngine::new("en")
+            .unwrap()
+            .render_channel_prompt_with_links(
+                None,
+                None,
+                None,
+                String::new(),
+                None,
+                None,
+                None,
+                None,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                pinned_context,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert!(prompt.contains("## Pinned Context"));
+        assert!(prompt.contains(&format!(
+            "- [{}] The customer's order number is A-123.",
+            pin.id
+        )));
+    }
+
+    #[test]
+    fn pinned_context_renders_one_item_per_pin() {
+        assert_eq!(render_pinned_context(&[]), None);
+
+        let pin = |id: &str, text: &str| PinnedContext {
+            id: id.into(),
+            text: text.into(),
+            pinned_at: chrono::Utc::now(),
+        };
+        let rendered = render_pinned_context(&[
+            pin("a1b2c3d4", "Deploys go out on Thursdays only."),
+            pin("e5f6a7b8", "Staging DB: db-staging-2\nProd DB: db-prod-1"),
+        ])
+        .unwrap();
+        assert_eq!(
+            rendered,
+            "- [a1b2c3d4] Deploys go out on Thursdays only.\n\
+             - [e5f6a7b8] Staging DB: db-staging-2\n  Prod DB: db-prod-1"
+        );
+    }
+
+    #[test]
+    fn conversation_slots_render_one_item_per_key() {
+        assert_eq!(render_conversation_slots(&Default::default()), None);
+
+        let slot = |value: &str| ConversationSlot {
+            value: value.into(),
+            updated_at: chrono::Utc::now(),
+        };
+        let slots = [
+            ("user_name".to_string(), slot("Ada")),
+            ("address".to_string(), slot("1 Main St\nSpringfield")),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            render_conversation_slots(&slots).unwrap(),
+            "- address: 1 Main St\n  Springfield\n- user_name: Ada"
+        );
+    }
+
+    #[test]
+    fn recalled_memories_follow_the_configured_order() {
+        let recalled = |content: &str, updated: &str, score: f32| {
+            let mut memory = Memory::new(content, MemoryType::Fact);
+            memory.updated_at = format!("{updated}T12:00:00Z").parse().unwrap();
+            MemorySearchResult {
+                memory,
+                score,
+                rank: 0,
+            }
+        };
+        let results = || {
+            vec![
+                recalled("Deploys go out on Thursdays.", "2026-01-10", 0.9),
+                recalled("Staging DB is db-staging-2.", "2026-03-02", 0.5),
+            ]
+        };
+
+        let mut by_relevance = results();
+        order_recalled_memories(&mut by_relevance, MemoryRecallOrder::Relevance);
+        assert_eq!(
+            render_recalled_memories(&by_relevance).unwrap(),
+            "- [fact, 2026-01-10] Deploys go out on Thursdays.\n\
+             - [fact, 2026-03-02] Staging DB is db-staging-2."
+        );
+
+        let mut by_recency = results();
+        order_recalled_memories(&mut by_recency, MemoryRecallOrder::Recency);
+        assert_eq!(by_recency[0].memory.content, "Staging DB is db-staging-2.");
+        assert_eq!(render_recalled_memories(&[]), None);
+    }
+
+    #[test]
+    fn notes_context_inlines_short_notes_and_points_at_long_ones() {
+        assert_eq!(render_notes_context(None, None, 2000), None);
+
+        let note = |content: &str| crate::notes::Note {
+            channel_id: None,
+            content: content.into(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let agent_note = note("1. migrate db\n2. update docs\n");
+        let channel_note = note(&"x".repeat(50));
+        let rendered = render_notes_context(Some(&agent_note), Some(&channel_note), 40).unwrap();
+        assert_eq!(
+            rendered,
+            "### Agent notes\n\n1. migrate db\n2. update docs\n\n\
+             ### Notes for this conversation\n\n\
+             (50 characters. Read it with the `notes` tool.)"
+        );
+    }
+
+    #[test]
+    fn reply_language_check_detects_script_mismatch() {
+        assert_eq!(
+            reply_matches_language("今日はいい天気ですね。散歩に行きましょう。", "Japanese"),
+            Some(true)
+        );
+        assert_eq!(
+            reply_matches_language("The weather is nice today, let's go for a walk.", "ja-JP"),
+            Some(false)
+        );
+        assert_eq!(
+            reply_matches_language("Привет! Как у тебя дела сегодня?", "russian"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn reply_language_check_skips_unknown_or_short_input() {
+        assert_eq!(
+            reply_matches_language("Bonjour à tous, ça va ?", "French"),
+            None
+        );
+        assert_eq!(reply_matches_language("ok", "Japanese"), None);
+    }
+}
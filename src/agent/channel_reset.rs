@@ -0,0 +1,160 @@
+//! Full conversation reset for a channel.
+//!
+//! Unlike compaction, which summarizes old turns to make room, a reset drops
+//! the channel's context entirely: history, running branches and workers, and
+//! optionally the memories saved from the channel. The conversation log keeps
+//! every message and records a boundary, so a recreated channel backfills
+//! only what came after the reset.
+//!
+//! The reset can be requested from inside a turn (the `reset_conversation`
+//! tool) or from the API while a turn is running. Shared state is cleared
+//! immediately; state owned by the channel loop (coalesce buffer, pending
+//! retriggers, backfill) is cleared by the loop when it picks up the
+//! [`ResetSignal`], after the in-flight turn has written its history.
+
+use crate::agent::channel::ChannelState;
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Confirmation posted to the conversation after a reset.
+pub const RESET_CONFIRMATION: &str =
+    "conversation reset. i've cleared this channel's history and will start fresh.";
+
+/// What a reset cleared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConversationReset {
+    pub messages_cleared: usize,
+    pub workers_cancelled: usize,
+    pub branches_cancelled: usize,
+    pub memories_forgotten: u64,
+}
+
+/// A reset waiting for the channel loop to finish it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingReset {
+    /// Post [`RESET_CONFIRMATION`] once the reset is finished. Off for the
+    /// tool, where the model confirms in its own reply.
+    pub announce: bool,
+}
+
+/// Hand-off from a reset made outside the channel loop to the loop itself.
+#[derive(Debug, Default)]
+pub struct ResetSignal {
+    pending: Mutex<Option<PendingReset>>,
+    notify: Notify,
+}
+
+impl ResetSignal {
+    /// Ask the channel loop to finish a reset. Repeated requests before the
+    /// loop gets to them collapse into one, announced if any asked for it.
+    pub fn request(&self, announce: bool) {
+        {
+            let mut pending = self
+                .pending
+                .lock()
+                .unwrap_or_else(|error| error.into_inner());
+            let announce = announce || pending.is_some_and(|pending| pending.announce);
+            *pending = Some(PendingReset { announce });
+        }
+        self.notify.notify_one();
+    }
+
+    /// Take the pending reset, if any.
+    pub fn take(&self) -> Option<PendingReset> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .take()
+    }
+
+    /// Wait until a reset is requested.
+    pub async fn notified(&self) {
+        self.notify.notified().await;
+    }
+}
+
+/// Clear the shared parts of a channel's conversation: cancel its branches
+/// and workers, empty its history, optionally forget memories saved from
+/// it, and log the reset boundary.
+pub async fn reset_conversation(state: &ChannelState, forget_memories: bool) -> ConversationReset {
+    let mut reset = ConversationReset::default();
+
+    let worker_ids: Vec<_> = state.worker_handles.read().await.keys().copied().collect();
+    for worker_id in worker_ids {
+        if state
+            .cancel_worker_with_reason(worker_id, "conversation reset")
+            .await
+            .is_ok()
+        {
+            reset.workers_cancelled += 1;
+        }
+    }
+
+    let branch_ids: Vec<_> = state.active_branches.read().await.keys().copied().collect();
+    for branch_id in branch_ids {
+        if state
+            .cancel_branch_with_reason(branch_id, "conversation reset")
+            .await
+            .is_ok()
+        {
+            reset.branches_cancelled += 1;
+        }
+    }
+
+    {
+        let mut history = state.history.write().await;
+        reset.messages_cleared = history.len();
+        history.clear();
+    }
+
+    if forget_memories {
+        match state
+            .deps
+            .memory_search
+            .store()
+            .forget_channel(&state.channel_id)
+            .await
+        {
+            Ok(count) => reset.memories_forgotten = count,
+            Err(error) => tracing::warn!(
+                channel_id = %state.channel_id,
+                %error,
+                "failed to forget channel memories during reset"
+            ),
+        }
+    }
+
+    state.conversation_logger.log_reset(&state.channel_id);
+
+    tracing::info!(
+        channel_id = %state.channel_id,
+        messages_cleared = reset.messages_cleared,
+        workers_cancelled = reset.workers_cancelled,
+        branches_cancelled = reset.branches_cancelled,
+        memories_forgotten = reset.memories_forgotten,
+        "conversation reset"
+    );
+
+    reset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_requests_collapse_and_keep_announce() {
+        let signal = ResetSignal::default();
+        assert_eq!(signal.take(), None);
+
+        signal.request(true);
+        signal.request(false);
+        assert_eq!(signal.take(), Some(PendingReset { announce: true }));
+        assert_eq!(signal.take(), None);
+
+        signal.request(false);
+        assert_eq!(signal.take(), Some(PendingReset { announce: false }));
+    }
+}
@@ -4,10 +4,12 @@
 //! spawns compaction workers when thresholds are crossed. The LLM work (summarization
 //! + memory extraction) happens in the spawned worker, not here.
 
+use crate::agent::channel_history::is_user_text_message;
+use crate::agent::process_limit::ProcessPriority;
 use crate::error::Result;
 use crate::hooks::SpacebotHook;
 use crate::llm::SpacebotModel;
-use crate::{AgentDeps, ChannelId, ProcessId, ProcessType};
+use crate::{AgentDeps, ChannelId, CompactionTrigger, ProcessId, ProcessType};
 use rig::agent::AgentBuilder;
 use rig::completion::CompletionModel;
 use rig::message::{AssistantContent, Message, UserContent};
@@ -49,13 +51,16 @@ impl Compactor {
         let context_window = **rc.context_window.load();
         let compaction_config = **rc.compaction.load();
 
-        let usage = {
+        let (usage, turns) = {
             let history = self.history.read().await;
             let estimated_tokens = estimate_history_tokens(&history);
-            estimated_tokens as f32 / context_window as f32
+            (
+                estimated_tokens as f32 / context_window as f32,
+                count_history_turns(&history),
+            )
         };
 
-        let action = if usage >= compaction_config.emergency_threshold {
+        let threshold_action = if usage >= compaction_config.emergency_threshold {
             Some(CompactionAction::EmergencyTruncate)
         } else if usage >= compaction_config.aggressive_threshold {
             Some(CompactionAction::Aggressive)
@@ -64,12 +69,27 @@ impl Compactor {
         } else {
             None
         };
+        let turn_limit_exceeded = compaction_config
+            .max_turns_before_compact
+            .is_some_and(|max_turns| turns > max_turns);
+
+        // Token thresholds win when both fire, since they may call for a
+        // harsher action than the background pass the turn limit asks for.
+        let trigger = match threshold_action {
+            Some(action) => Some((action, CompactionTrigger::TokenRatio)),
+            None if turn_limit_exceeded => {
+                Some((CompactionAction::Background, CompactionTrigger::TurnCount))
+            }
+            None => None,
+        };
 
-        if let Some(action) = action {
+        if let Some((action, reason)) = trigger {
             tracing::info!(
                 channel_id = %self.channel_id,
                 usage = %format!("{:.1}%", usage * 100.0),
+                turns,
                 ?action,
+                ?reason,
                 "compaction triggered"
             );
             if let Err(error) = self
@@ -79,6 +99,7 @@ impl Compactor {
                     agent_id: self.deps.agent_id.clone(),
                     channel_id: self.channel_id.clone(),
                     threshold_reached: usage,
+                    reason,
                 })
             {
                 tracing::debug!(
@@ -91,7 +112,16 @@ impl Compactor {
             match action {
                 CompactionAction::EmergencyTruncate => {
                     // Emergency is synchronous — fast, no LLM
-                    self.emergency_truncate().await?;
+                    let removed = self.emergency_truncate().await?;
+                    let context_tokens = estimate_history_tokens(&self.history.read().await);
+                    emit_compaction_completed(
+                        &self.deps,
+                        &self.channel_id,
+                        removed,
+                        0,
+                        0,
+                        context_tokens,
+                    );
                 }
                 CompactionAction::Background | CompactionAction::Aggressive => {
                     // Background/aggressive spawn a worker
@@ -124,11 +154,25 @@ impl Compactor {
         let is_compacting = self.is_compacting.clone();
         let channel_id = self.channel_id.clone();
         let deps = self.deps.clone();
-        let prompt_engine = deps.runtime_config.prompts.load();
-        let compactor_prompt = match prompt_engine.render_static("compactor") {
+        let rc = &deps.runtime_config;
+        let summary_max_tokens = rc
+            .compaction
+            .load()
+            .summary_token_budget(**rc.context_window.load());
+        let prompt_engine = rc.prompts.load();
+        let compactor_prompt = match prompt_engine.render_compactor_prompt(summary_max_tokens) {
             Ok(p) => p,
             Err(error) => {
                 tracing::error!(%error, "failed to render compactor prompt");
+                let context_tokens = estimate_history_tokens(&history.read().await);
+                emit_compaction_completed(
+                    &deps,
+                    &channel_id,
+                    0,
+                    0,
+                    summary_max_tokens,
+                    context_tokens,
+                );
                 let mut flag = is_compacting.write().await;
                 *flag = false;
                 return;
@@ -136,25 +180,50 @@ impl Compactor {
         };
 
         tokio::spawn(async move {
-            let result =
-                run_compaction(&deps, &compactor_prompt, &history, &channel_id, fraction).await;
-
-            match result {
-                Ok(turns_compacted) => {
+            let permit = deps
+                .acquire_process_permit(ProcessPriority::Foreground)
+                .await;
+            let result = run_compaction(
+                &deps,
+                &compactor_prompt,
+                &history,
+                &channel_id,
+                fraction,
+                summary_max_tokens,
+            )
+            .await;
+            drop(permit);
+
+            let (turns_compacted, summary_tokens) = match result {
+                Ok(Some(outcome)) => {
                     tracing::info!(
                         channel_id = %channel_id,
-                        turns_compacted,
+                        turns_compacted = outcome.turns_compacted,
+                        summary_tokens = outcome.summary_tokens,
+                        summary_max_tokens,
                         "compaction completed"
                     );
+                    (outcome.turns_compacted, outcome.summary_tokens)
                 }
+                Ok(None) => (0, 0),
                 Err(error) => {
                     tracing::error!(
                         channel_id = %channel_id,
                         %error,
                         "compaction failed"
                     );
+                    (0, 0)
                 }
-            }
+            };
+            let context_tokens = estimate_history_tokens(&history.read().await);
+            emit_compaction_completed(
+                &deps,
+                &channel_id,
+                turns_compacted,
+                summary_tokens,
+                summary_max_tokens,
+                context_tokens,
+            );
 
             let mut flag = is_compacting.write().await;
             *flag = false;
@@ -164,12 +233,12 @@ impl Compactor {
     /// Emergency truncation: drop oldest messages without LLM summarization.
     ///
     /// Only fires at 95%+ context usage. Removes the oldest half of messages and
-    /// inserts a marker. Fast and synchronous.
-    async fn emergency_truncate(&self) -> Result<()> {
+    /// inserts a marker. Fast and synchronous. Returns how many were removed.
+    async fn emergency_truncate(&self) -> Result<usize> {
         let mut history = self.history.write().await;
         let total = history.len();
         if total <= 2 {
-            return Ok(());
+            return Ok(0);
         }
 
         let remove_count = total / 2;
@@ -189,11 +258,64 @@ impl Compactor {
             "emergency truncation performed"
         );
 
-        Ok(())
+        Ok(remove_count)
+    }
+}
+
+/// Report a compaction stage to the channel's status block and the UI.
+fn emit_compaction_progress(deps: &AgentDeps, channel_id: &ChannelId, status: impl Into<String>) {
+    if let Err(error) = deps.event_tx.send(crate::ProcessEvent::CompactionProgress {
+        agent_id: deps.agent_id.clone(),
+        channel_id: channel_id.clone(),
+        status: status.into(),
+    }) {
+        tracing::debug!(
+            channel_id = %channel_id,
+            %error,
+            "failed to emit compaction-progress event"
+        );
     }
 }
 
+/// Report the end of a compaction run, successful or not, so the status block
+/// stops showing it as running.
+fn emit_compaction_completed(
+    deps: &AgentDeps,
+    channel_id: &ChannelId,
+    turns_compacted: usize,
+    summary_tokens: usize,
+    summary_max_tokens: usize,
+    context_tokens: usize,
+) {
+    if let Err(error) = deps
+        .event_tx
+        .send(crate::ProcessEvent::CompactionCompleted {
+            agent_id: deps.agent_id.clone(),
+            channel_id: channel_id.clone(),
+            turns_compacted,
+            summary_tokens,
+            summary_max_tokens,
+            context_tokens,
+        })
+    {
+        tracing::debug!(
+            channel_id = %channel_id,
+            %error,
+            "failed to emit compaction-completed event"
+        );
+    }
+}
+
+/// Result of a compaction run that replaced history with a summary.
+struct CompactionOutcome {
+    turns_compacted: usize,
+    /// Estimated token size of the inserted summary.
+    summary_tokens: usize,
+}
+
 /// Run the actual compaction: summarize via LLM, extract memories, swap summary into history.
+///
+/// Returns `None` when the history was too short to compact.
 #[tracing::instrument(skip(deps, compactor_prompt, history), fields(agent_id = %deps.agent_id))]
 async fn run_compaction(
     deps: &AgentDeps,
@@ -201,7 +323,8 @@ async fn run_compaction(
     history: &Arc<RwLock<Vec<Message>>>,
     channel_id: &ChannelId,
     fraction: f32,
-) -> Result<usize> {
+    summary_max_tokens: usize,
+) -> Result<Option<CompactionOutcome>> {
     // 1. Read and remove the oldest messages from history
     let (removed_messages, remove_count) = {
         let mut hist = history.write().await;
@@ -210,21 +333,37 @@ async fn run_compaction(
             .max(1)
             .min(total.saturating_sub(2));
         if remove_count == 0 {
-            return Ok(0);
+            return Ok(None);
         }
         let removed: Vec<Message> = hist.drain(..remove_count).collect();
         (removed, remove_count)
     };
 
+    emit_compaction_progress(
+        deps,
+        channel_id,
+        format!("summarizing {remove_count} older messages"),
+    );
+
     // 2. Build the transcript text for the LLM
     let transcript = render_messages_as_transcript(&removed_messages);
 
     // 3. Run the compaction LLM to produce summary + extracted memories
     let routing = deps.runtime_config.routing.load();
     let model_name = routing.resolve(ProcessType::Compactor, None).to_string();
+    let hook = SpacebotHook::new(
+        deps.agent_id.clone(),
+        ProcessId::Worker(Uuid::new_v4()),
+        ProcessType::Compactor,
+        Some(channel_id.clone()),
+        deps.event_tx.clone(),
+    )
+    .with_output_token_cap(routing.max_output_tokens(ProcessType::Compactor));
+
     let model = SpacebotModel::make(&deps.llm_manager, &model_name)
         .with_context(&*deps.agent_id, "compactor")
-        .with_routing((**routing).clone());
+        .with_routing((**routing).clone())
+        .with_turn_budget(hook.turn_budget());
 
     // Give the compaction worker memory_save so it can directly persist memories
     // No tool server — the compactor's sole job is producing a summary.
@@ -234,20 +373,12 @@ async fn run_compaction(
         .default_max_turns(1)
         .build();
 
-    let hook = SpacebotHook::new(
-        deps.agent_id.clone(),
-        ProcessId::Worker(Uuid::new_v4()),
-        ProcessType::Compactor,
-        Some(channel_id.clone()),
-        deps.event_tx.clone(),
-    );
-
     let mut compaction_history = Vec::new();
     let response = hook
         .prompt_once(&agent, &mut compaction_history, &transcript)
         .await;
 
-    let summary = match response {
+    let mut summary = match response {
         Ok(text) => extract_summary_section(&text),
         Err(error) => {
             tracing::warn!(%error, "compaction LLM failed, using fallback summary");
@@ -255,14 +386,84 @@ async fn run_compaction(
         }
     };
 
-    // 4. Insert the summary at the beginning of the channel's history
+    // 4. Hold the summary to its budget: ask once for a shorter rewrite, then
+    // hard-truncate if the model still overshoots.
+    if estimate_text_tokens(&summary) > summary_max_tokens {
+        tracing::debug!(
+            summary_tokens = estimate_text_tokens(&summary),
+            summary_max_tokens,
+            "compaction summary over budget, requesting shorter rewrite"
+        );
+        emit_compaction_progress(deps, channel_id, "shortening the summary");
+        let rewrite_request = format!(
+            "That summary is too long. Rewrite it to fit within {summary_max_tokens} tokens \
+             (roughly {} words), keeping the most important context. Reply with only the \
+             rewritten summary.",
+            summary_max_tokens * 3 / 4
+        );
+        match hook
+            .prompt_once(&agent, &mut compaction_history, &rewrite_request)
+            .await
+        {
+            Ok(text) => {
+                let rewritten = extract_summary_section(&text);
+                if !rewritten.is_empty() {
+                    summary = rewritten;
+                }
+            }
+            Err(error) => {
+                tracing::warn!(%error, "compaction summary rewrite failed, truncating");
+            }
+        }
+        summary = truncate_to_token_budget(&summary, summary_max_tokens);
+    }
+    let summary_tokens = estimate_text_tokens(&summary);
+
+    // 5. Insert the summary at the beginning of the channel's history
     {
         let mut hist = history.write().await;
         let summary_message = format!("[Compaction Summary]: {summary}");
         hist.insert(0, Message::from(summary_message));
     }
 
-    Ok(remove_count)
+    Ok(Some(CompactionOutcome {
+        turns_compacted: remove_count,
+        summary_tokens,
+    }))
+}
+
+/// Count conversational turns in a history, one per user prompt. Tool results
+/// ride along with the turn that made the call.
+fn count_history_turns(history: &[Message]) -> usize {
+    history
+        .iter()
+        .filter(|message| is_user_text_message(message))
+        .count()
+}
+
+/// Estimate token count for a single piece of text using the chars/4 heuristic.
+fn estimate_text_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Cut text down to fit a token budget, breaking at whitespace where possible.
+fn truncate_to_token_budget(text: &str, max_tokens: usize) -> String {
+    if estimate_text_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+
+    // Leave room for the trailing ellipsis.
+    let max_bytes = (max_tokens * 4).saturating_sub('…'.len_utf8());
+    let mut end = max_bytes.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &text[..end];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(index) if index > 0 => &head[..index],
+        _ => head,
+    };
+    format!("{}…", head.trim_end())
 }
 
 /// Estimate token count for a history using chars/4 heuristic.
@@ -311,6 +512,18 @@ fn estimate_user_content_chars(content: &UserContent) -> usize {
     }
 }
 
+/// Estimate the output tokens of a completion using the chars/4 heuristic,
+/// for providers that don't report usage.
+pub(crate) fn estimate_completion_tokens<'a>(
+    content: impl IntoIterator<Item = &'a AssistantContent>,
+) -> u64 {
+    let chars: usize = content
+        .into_iter()
+        .map(estimate_assistant_content_chars)
+        .sum();
+    chars.div_ceil(4) as u64
+}
+
 fn estimate_assistant_content_chars(content: &AssistantContent) -> usize {
     match content {
         AssistantContent::Text(t) => t.text.len(),
@@ -410,3 +623,43 @@ pub enum CompactionAction {
     /// Emergency truncation (no LLM, drop oldest 50%).
     EmergencyTruncate,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_to_token_budget_respects_budget() {
+        let text = "alpha beta gamma delta epsilon zeta eta theta iota kappa";
+        let truncated = truncate_to_token_budget(text, 5);
+        assert!(estimate_text_tokens(&truncated) <= 5);
+        assert!(truncated.ends_with('…'));
+        assert!(text.starts_with(truncated.trim_end_matches('…')));
+    }
+
+    #[test]
+    fn count_history_turns_skips_replies_and_tool_results() {
+        let history = vec![
+            Message::from("first question"),
+            Message::assistant("first answer"),
+            Message::User {
+                content: rig::OneOrMany::one(UserContent::ToolResult(rig::message::ToolResult {
+                    id: "call_1".to_string(),
+                    call_id: None,
+                    content: rig::OneOrMany::one(rig::message::ToolResultContent::text("ok")),
+                })),
+            },
+            Message::from("second question"),
+        ];
+
+        assert_eq!(count_history_turns(&history), 2);
+    }
+
+    #[test]
+    fn truncate_to_token_budget_keeps_short_text() {
+        assert_eq!(
+            truncate_to_token_budget("short summary", 100),
+            "short summary"
+        );
+    }
+}
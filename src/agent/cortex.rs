@@ -13,13 +13,15 @@ use crate::agent::channel_dispatch::{WorkerCompletionError, map_worker_completio
 use crate::agent::process_control::{
     ControlActionResult, DetachedWorkerControl, ProcessControlRegistry,
 };
+use crate::agent::process_limit::ProcessPriority;
 use crate::agent::worker::Worker;
 use crate::error::Result;
 use crate::hooks::CortexHook;
 use crate::llm::SpacebotModel;
+use crate::memory::consolidation as memory_consolidation;
 use crate::memory::maintenance as memory_maintenance;
 use crate::memory::search::{SearchConfig, SearchMode, SearchSort};
-use crate::memory::types::{Association, MemoryType, RelationType};
+use crate::memory::types::{Association, Memory, MemoryType, RelationType};
 use crate::tasks::{TaskStatus, UpdateTaskInput};
 use crate::{
     AgentDeps, AgentId, BranchId, ChannelId, ProcessEvent, ProcessId, ProcessType, WorkerId,
@@ -61,6 +63,13 @@ fn should_execute_warmup(warmup_config: crate::config::WarmupConfig, force: bool
     warmup_config.enabled || force
 }
 
+/// Model preloads run on startup, manual, and dispatch-triggered passes. The
+/// periodic refresh skips them so an idle agent doesn't keep paying for
+/// throwaway completions.
+fn should_preload_models(warmup_config: crate::config::WarmupConfig, reason: &str) -> bool {
+    warmup_config.preload_models && reason != "scheduled"
+}
+
 fn should_generate_bulletin_from_bulletin_loop(
     warmup_config: crate::config::WarmupConfig,
     status: &crate::config::WarmupStatus,
@@ -93,6 +102,7 @@ const MAINTENANCE_TASK_TIMEOUT_MIN_SECS: u64 = 300;
 const MAINTENANCE_TASK_TIMEOUT_MAX_SECS: u64 = 3_600;
 const MAINTENANCE_TASK_TIMEOUT_MULTIPLIER: u64 = 6;
 const MAINTENANCE_TASK_CANCEL_GRACE_SECS: u64 = 30;
+const MODEL_PRELOAD_TIMEOUT_SECS: u64 = 30;
 
 fn bulletin_refresh_failure_backoff(consecutive_failures: u32) -> Duration {
     let exponent = consecutive_failures.saturating_sub(1).min(5);
@@ -712,6 +722,7 @@ pub enum Signal {
     CompactionTriggered {
         channel_id: ChannelId,
         threshold_reached: f32,
+        reason: crate::CompactionTrigger,
     },
     /// Generic status update.
     StatusUpdate {
@@ -1261,10 +1272,12 @@ fn signal_from_event(event: ProcessEvent) -> Option<Signal> {
         ProcessEvent::CompactionTriggered {
             channel_id,
             threshold_reached,
+            reason,
             ..
         } => Signal::CompactionTriggered {
             channel_id,
             threshold_reached,
+            reason,
         },
         ProcessEvent::StatusUpdate {
             process_id, status, ..
@@ -1346,7 +1359,11 @@ fn signal_from_event(event: ProcessEvent) -> Option<Signal> {
             status: "idle".to_string(),
         },
         // UI-only events — no cortex signal needed.
-        ProcessEvent::OpenCodeSessionCreated { .. }
+        ProcessEvent::MemoryDeleted { .. }
+        | ProcessEvent::MemoriesConsolidated { .. }
+        | ProcessEvent::CompactionProgress { .. }
+        | ProcessEvent::CompactionCompleted { .. }
+        | ProcessEvent::OpenCodeSessionCreated { .. }
         | ProcessEvent::OpenCodePartUpdated { .. }
         | ProcessEvent::WorkerInitialResult { .. }
         | ProcessEvent::WorkerText { .. }
@@ -1663,6 +1680,65 @@ pub async fn run_warmup_once(deps: &AgentDeps, logger: &CortexLogger, reason: &s
             })),
         );
     }
+
+    // Preloading never gates readiness, so it runs after the pass has
+    // settled and outside the warmup lock.
+    if should_preload_models(warmup_config, reason) {
+        let deps = deps.clone();
+        let logger = logger.clone();
+        let reason = reason.to_string();
+        tokio::spawn(async move {
+            let model_preloads = preload_routed_models(&deps).await;
+            logger.log(
+                "model_preload_completed",
+                "Routed model preload completed",
+                Some(serde_json::json!({
+                    "reason": reason,
+                    "model_preloads": model_preloads,
+                })),
+            );
+        });
+    }
+}
+
+/// Send a tiny throwaway completion to each routed model, concurrently, so
+/// provider connections and caches are warm before the first real message.
+///
+/// Each request is bounded by [`MODEL_PRELOAD_TIMEOUT_SECS`]. Failures and
+/// timeouts are logged and reported in the returned timings but never fail
+/// the warmup pass; the model will just be cold on first use.
+async fn preload_routed_models(deps: &AgentDeps) -> Vec<serde_json::Value> {
+    let routing = deps.runtime_config.routing.load();
+    let preloads = routing
+        .routed_models()
+        .into_iter()
+        .map(|model_name| async move {
+            let started = Instant::now();
+            let model = SpacebotModel::make(&deps.llm_manager, model_name)
+                .with_context(&*deps.agent_id, "warmup");
+            let result = tokio::time::timeout(
+                Duration::from_secs(MODEL_PRELOAD_TIMEOUT_SECS),
+                model.completion_request("ping").max_tokens(16).send(),
+            )
+            .await
+            .map_err(|_| format!("timed out after {MODEL_PRELOAD_TIMEOUT_SECS}s"))
+            .and_then(|result| result.map_err(|error| error.to_string()));
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+
+            match &result {
+                Ok(_) => tracing::info!(model = model_name, elapsed_ms, "model preloaded"),
+                Err(error) => {
+                    tracing::warn!(model = model_name, elapsed_ms, %error, "model preload failed")
+                }
+            }
+            serde_json::json!({
+                "model": model_name,
+                "elapsed_ms": elapsed_ms,
+                "error": result.err(),
+            })
+        });
+
+    futures::future::join_all(preloads).await
 }
 
 /// Trigger a forced warmup pass in the background from a dispatch path.
@@ -2136,6 +2212,8 @@ async fn run_cortex_loop(
                                 memory_search.store(),
                                 memory_search.embedding_table(),
                                 memory_search.embedding_model_arc(),
+                                memory_search.embedding_config(),
+                                Some(&memory_search),
                                 &maintenance_config,
                                 maintenance_cancel_rx,
                             )
@@ -2171,6 +2249,26 @@ async fn run_cortex_loop(
                     tracing::warn!(%error, "working memory event pruning failed");
                 }
 
+                // Conversation log: drop messages past `history_max_age_days` (opt-in).
+                let history_cutoff = cortex
+                    .deps
+                    .runtime_config
+                    .channel_config
+                    .load()
+                    .history_cutoff(chrono::Utc::now());
+                if let Some(cutoff) = history_cutoff {
+                    let conversation_logger = crate::conversation::history::ConversationLogger::new(
+                        cortex.deps.sqlite_pool.clone(),
+                    );
+                    match conversation_logger.prune_older_than(cutoff).await {
+                        Ok(0) => {}
+                        Ok(removed) => {
+                            tracing::info!(removed, %cutoff, "pruned conversation log past its max age");
+                        }
+                        Err(error) => tracing::warn!(%error, "conversation log pruning failed"),
+                    }
+                }
+
                 let updated_tick_interval_secs = cortex_config.tick_interval_secs.max(1);
                 if updated_tick_interval_secs != tick_interval_secs {
                     tick_interval_secs = updated_tick_interval_secs;
@@ -2354,6 +2452,79 @@ async fn gather_active_tasks(deps: &AgentDeps) -> anyhow::Result<String> {
     Ok(output)
 }
 
+/// Language cortex output is written in: `cortex_language`, else the agent's
+/// `output_language`. None leaves cortex output in English.
+fn resolve_cortex_language(runtime_config: &crate::config::RuntimeConfig) -> Option<String> {
+    let cortex_language = runtime_config.cortex_language.load();
+    let output_language = runtime_config.output_language.load();
+    [cortex_language.as_deref(), output_language.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|language| !language.is_empty())
+        .map(str::to_string)
+}
+
+/// Append the cortex output language instruction to a cortex preamble.
+fn with_cortex_language(deps: &AgentDeps, preamble: &str) -> String {
+    let Some(language) = resolve_cortex_language(&deps.runtime_config) else {
+        return preamble.to_string();
+    };
+    match deps
+        .runtime_config
+        .prompts
+        .load()
+        .render_system_cortex_language(&language)
+    {
+        Ok(instruction) => format!("{}\n\n{}", preamble.trim_end(), instruction.trim()),
+        Err(error) => {
+            tracing::warn!(%error, "failed to render cortex language instruction");
+            preamble.to_string()
+        }
+    }
+}
+
+/// Whether a manual bulletin is pinned until it is cleared, so the cortex
+/// shouldn't generate one.
+fn bulletin_pinned(settings: Option<&crate::settings::SettingsStore>) -> bool {
+    settings
+        .and_then(|settings| settings.memory_bulletin_override())
+        .is_some_and(|bulletin_override| bulletin_override.permanent)
+}
+
+/// Store a cortex-generated bulletin unless one was pinned permanently by
+/// hand. A temporary manual bulletin ends at this refresh.
+fn store_cortex_bulletin(deps: &AgentDeps, bulletin: String) {
+    let settings = deps.runtime_config.settings.load();
+    store_generated_bulletin(
+        settings.as_deref(),
+        &deps.runtime_config.memory_bulletin,
+        bulletin,
+    );
+}
+
+fn store_generated_bulletin(
+    settings: Option<&crate::settings::SettingsStore>,
+    memory_bulletin: &arc_swap::ArcSwap<String>,
+    bulletin: String,
+) {
+    if let Some(settings) = settings {
+        match settings.memory_bulletin_override() {
+            Some(bulletin_override) if bulletin_override.permanent => {
+                tracing::debug!("memory bulletin is pinned, keeping the manual bulletin");
+                return;
+            }
+            Some(_) => {
+                if let Err(error) = settings.set_memory_bulletin_override(None) {
+                    tracing::warn!(%error, "failed to clear memory bulletin override");
+                }
+            }
+            None => {}
+        }
+    }
+    memory_bulletin.store(Arc::new(bulletin));
+}
+
 /// Generate a memory bulletin and store it in RuntimeConfig.
 ///
 /// Programmatically queries the memory store across multiple dimensions
@@ -2362,10 +2533,18 @@ async fn gather_active_tasks(deps: &AgentDeps) -> anyhow::Result<String> {
 /// concise briefing.
 ///
 /// On failure, the previous bulletin is preserved (not blanked out).
-/// Returns `true` if the bulletin was successfully generated.
+/// Generation is skipped while a manual bulletin is pinned permanently.
+/// Returns `true` if the bulletin was successfully generated or skipped.
 #[tracing::instrument(skip(deps, logger), fields(agent_id = %deps.agent_id))]
 pub async fn generate_bulletin(deps: &AgentDeps, logger: &CortexLogger) -> bool {
+    if bulletin_pinned(deps.runtime_config.settings.load().as_deref()) {
+        tracing::debug!("memory bulletin is pinned, skipping bulletin generation");
+        return true;
+    }
     tracing::info!("cortex generating memory bulletin");
+    let _permit = deps
+        .acquire_process_permit(ProcessPriority::Background)
+        .await;
     let started = Instant::now();
 
     // Phase 1: Programmatically gather raw memory sections (no LLM needed)
@@ -2374,9 +2553,7 @@ pub async fn generate_bulletin(deps: &AgentDeps, logger: &CortexLogger) -> bool
 
     if raw_sections.is_empty() {
         tracing::info!("no memories found, skipping bulletin synthesis");
-        deps.runtime_config
-            .memory_bulletin
-            .store(Arc::new(String::new()));
+        store_cortex_bulletin(deps, String::new());
         logger.log(
             "bulletin_generated",
             "Bulletin skipped: no memories in graph",
@@ -2411,7 +2588,7 @@ pub async fn generate_bulletin(deps: &AgentDeps, logger: &CortexLogger) -> bool
     // Attach CortexHook so observation/termination semantics stay consistent
     // with other process types.
     let agent = AgentBuilder::new(model)
-        .preamble(&bulletin_prompt)
+        .preamble(&with_cortex_language(deps, &bulletin_prompt))
         .hook(CortexHook::new())
         .build();
 
@@ -2430,9 +2607,7 @@ pub async fn generate_bulletin(deps: &AgentDeps, logger: &CortexLogger) -> bool
             let word_count = bulletin.split_whitespace().count();
             let duration_ms = started.elapsed().as_millis() as u64;
             tracing::info!(words = word_count, "cortex bulletin generated");
-            deps.runtime_config
-                .memory_bulletin
-                .store(Arc::new(bulletin));
+            store_cortex_bulletin(deps, bulletin);
             let refresh_ms = chrono::Utc::now().timestamp_millis();
             update_warmup_status(deps, |status| {
                 status.last_refresh_unix_ms = Some(refresh_ms);
@@ -2530,6 +2705,9 @@ const KNOWLEDGE_SYNTHESIS_SECTIONS: &[BulletinSection] = &[
 #[tracing::instrument(skip(deps, logger), fields(agent_id = %deps.agent_id))]
 pub async fn generate_knowledge_synthesis(deps: &AgentDeps, logger: &CortexLogger) -> bool {
     tracing::info!("cortex generating knowledge synthesis");
+    let _permit = deps
+        .acquire_process_permit(ProcessPriority::Background)
+        .await;
     let started = Instant::now();
 
     // Gather narrower sections (no identity, no events, no recent).
@@ -2542,9 +2720,7 @@ pub async fn generate_knowledge_synthesis(deps: &AgentDeps, logger: &CortexLogge
             .knowledge_synthesis
             .store(Arc::new(String::new()));
         // Keep bulletin in sync during transition.
-        deps.runtime_config
-            .memory_bulletin
-            .store(Arc::new(String::new()));
+        store_cortex_bulletin(deps, String::new());
         return true;
     }
 
@@ -2574,7 +2750,7 @@ pub async fn generate_knowledge_synthesis(deps: &AgentDeps, logger: &CortexLogge
         .with_routing((**routing).clone());
 
     let agent = AgentBuilder::new(model)
-        .preamble(&synthesis_preamble)
+        .preamble(&with_cortex_language(deps, &synthesis_preamble))
         .hook(CortexHook::new())
         .build();
 
@@ -2601,9 +2777,7 @@ pub async fn generate_knowledge_synthesis(deps: &AgentDeps, logger: &CortexLogge
                 .knowledge_synthesis
                 .store(Arc::new(synthesis.clone()));
             // Keep bulletin in sync during transition so unconverted consumers work.
-            deps.runtime_config
-                .memory_bulletin
-                .store(Arc::new(synthesis));
+            store_cortex_bulletin(deps, synthesis);
             // Mark this version as synthesized.
             let current = deps
                 .runtime_config
@@ -2824,7 +2998,10 @@ pub async fn maybe_synthesize_intraday_batch(
         .with_routing((**routing).clone());
 
     let agent = AgentBuilder::new(model)
-        .preamble("You are a concise narrative summarizer. Output only the summary paragraph, nothing else.")
+        .preamble(&with_cortex_language(
+            deps,
+            "You are a concise narrative summarizer. Output only the summary paragraph, nothing else.",
+        ))
         .hook(CortexHook::new())
         .build();
 
@@ -2966,7 +3143,10 @@ pub async fn maybe_synthesize_daily_summary(
         .with_routing((**routing).clone());
 
     let agent = AgentBuilder::new(model)
-        .preamble("You are a daily activity summarizer. Output only the summary, nothing else.")
+        .preamble(&with_cortex_language(
+            deps,
+            "You are a daily activity summarizer. Output only the summary, nothing else.",
+        ))
         .hook(CortexHook::new())
         .build();
 
@@ -3071,6 +3251,9 @@ struct ProfileLlmResponse {
 #[tracing::instrument(skip(deps, logger), fields(agent_id = %deps.agent_id))]
 async fn generate_profile(deps: &AgentDeps, logger: &CortexLogger) {
     tracing::info!("cortex generating agent profile");
+    let _permit = deps
+        .acquire_process_permit(ProcessPriority::Background)
+        .await;
     let started = Instant::now();
 
     let prompt_engine = deps.runtime_config.prompts.load();
@@ -3117,7 +3300,7 @@ async fn generate_profile(deps: &AgentDeps, logger: &CortexLogger) {
         .with_routing((**routing).clone());
 
     let agent = AgentBuilder::new(model)
-        .preamble(&profile_prompt)
+        .preamble(&with_cortex_language(deps, &profile_prompt))
         .hook(CortexHook::new())
         .build();
 
@@ -3235,6 +3418,10 @@ async fn run_ready_task_loop(deps: &AgentDeps, logger: &CortexLogger) -> anyhow:
 }
 
 async fn pickup_one_ready_task(deps: &AgentDeps, logger: &CortexLogger) -> anyhow::Result<()> {
+    if deps.runtime_config.is_paused() {
+        return Ok(());
+    }
+
     let Some(task) = deps.task_store.claim_next_ready(&deps.agent_id).await? else {
         return Ok(());
     };
@@ -4109,20 +4296,238 @@ async fn fetch_memories_for_association(
     Ok(rows.iter().map(|row| row.get("id")).collect())
 }
 
+// -- Consolidation loop --
+
+/// Upper bound on clusters consolidated in one pass, so a large backlog is
+/// worked through gradually instead of in one burst of LLM calls.
+const MAX_CONSOLIDATIONS_PER_PASS: usize = 5;
+
+/// How often a disabled consolidation loop re-checks its config.
+const CONSOLIDATION_DISABLED_POLL_SECS: u64 = 60;
+
+/// Spawn the memory consolidation loop for an agent.
+///
+/// Periodically folds clusters of associated memories into a single
+/// higher-level memory and marks the originals forgotten. Disabled while
+/// `consolidation_interval_secs` is 0.
+pub fn spawn_consolidation_loop(
+    deps: AgentDeps,
+    logger: CortexLogger,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        run_consolidation_loop(&deps, &logger).await;
+    })
+}
+
+async fn run_consolidation_loop(deps: &AgentDeps, logger: &CortexLogger) {
+    tracing::info!("cortex consolidation loop started");
+
+    loop {
+        let cortex_config = **deps.runtime_config.cortex.load();
+        let interval = cortex_config.consolidation_interval_secs;
+        if interval == 0 {
+            tokio::time::sleep(Duration::from_secs(CONSOLIDATION_DISABLED_POLL_SECS)).await;
+            continue;
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+
+        let count = run_consolidation_pass(deps, logger).await;
+        if count > 0 {
+            tracing::info!(clusters_consolidated = count, "consolidation pass complete");
+        }
+    }
+}
+
+/// Run a single consolidation pass. Returns the number of clusters consolidated.
+async fn run_consolidation_pass(deps: &AgentDeps, logger: &CortexLogger) -> usize {
+    let cortex_config = **deps.runtime_config.cortex.load();
+    let store = deps.memory_search.store();
+
+    let edges = match store
+        .get_consolidation_edges(cortex_config.consolidation_min_importance)
+        .await
+    {
+        Ok(edges) => edges,
+        Err(error) => {
+            tracing::warn!(%error, "failed to fetch edges for consolidation pass");
+            return 0;
+        }
+    };
+
+    let clusters =
+        memory_consolidation::find_clusters(&edges, cortex_config.consolidation_min_cluster_size);
+
+    let mut consolidated = 0_usize;
+    for cluster in clusters.into_iter().take(MAX_CONSOLIDATIONS_PER_PASS) {
+        match consolidate_cluster(deps, logger, &cluster).await {
+            Ok(true) => consolidated += 1,
+            Ok(false) => {}
+            Err(error) => {
+                tracing::warn!(%error, cluster_size = cluster.len(), "memory consolidation failed");
+            }
+        }
+    }
+
+    consolidated
+}
+
+/// Consolidate one cluster of memory IDs. Returns false if the cluster no
+/// longer qualifies (members forgotten or below threshold since the edges
+/// were read).
+async fn consolidate_cluster(
+    deps: &AgentDeps,
+    logger: &CortexLogger,
+    cluster: &[String],
+) -> anyhow::Result<bool> {
+    let cortex_config = **deps.runtime_config.cortex.load();
+    let store = deps.memory_search.store();
+
+    let mut originals = Vec::with_capacity(cluster.len());
+    for memory_id in cluster {
+        if let Some(memory) = store.load(memory_id).await?
+            && !memory.forgotten
+            && memory.importance >= cortex_config.consolidation_min_importance
+        {
+            originals.push(memory);
+        }
+    }
+    if originals.len() < cortex_config.consolidation_min_cluster_size.max(2) {
+        return Ok(false);
+    }
+    originals.sort_by_key(|memory| memory.created_at);
+
+    let memory_type = originals[0].memory_type;
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let preamble = prompt_engine.render_static("cortex_memory_consolidation")?;
+    let user_prompt =
+        prompt_engine.render_system_memory_consolidation(&memory_type.to_string(), &originals)?;
+
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Cortex, None).to_string();
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "cortex")
+        .with_routing((**routing).clone());
+
+    let agent = AgentBuilder::new(model)
+        .preamble(&with_cortex_language(deps, &preamble))
+        .hook(CortexHook::new())
+        .build();
+
+    let content = agent.prompt(&user_prompt).await?.trim().to_string();
+    if content.is_empty() {
+        anyhow::bail!("consolidation produced an empty memory");
+    }
+
+    let importance = originals
+        .iter()
+        .map(|memory| memory.importance)
+        .fold(0.0_f32, f32::max);
+    let content = deps.memory_search.scrub_content(&content);
+    let mut memory = Memory::new(content, memory_type)
+        .with_importance(importance)
+        .with_source("cortex:consolidation");
+    // Keep the channel only when every original came from the same one.
+    if let Some(channel_id) = &originals[0].channel_id
+        && originals
+            .iter()
+            .all(|original| original.channel_id.as_ref() == Some(channel_id))
+    {
+        memory = memory.with_channel_id(channel_id.clone());
+    }
+
+    // Embed before writing anything so a failed embedding leaves the
+    // originals untouched.
+    let embedding = deps.memory_search.embed_passage(&memory.content).await?;
+
+    store
+        .consolidate_memories_atomic(&memory, &originals)
+        .await?;
+
+    let embedding_table = deps.memory_search.embedding_table();
+    embedding_table
+        .store(&memory.id, &memory.content, &embedding)
+        .await?;
+    if let Err(error) = deps
+        .memory_search
+        .store_routed_embedding(&memory.id, &memory.content)
+        .await
+    {
+        tracing::warn!(memory_id = %memory.id, %error, "failed to store routed embedding for consolidated memory");
+    }
+    for original in &originals {
+        if let Err(error) = embedding_table.delete(&original.id).await {
+            tracing::warn!(memory_id = %original.id, %error, "failed to drop consolidated memory embedding");
+        }
+        deps.memory_search
+            .delete_routed_embeddings(&original.id)
+            .await;
+    }
+    if let Err(error) = embedding_table.ensure_fts_index().await {
+        tracing::warn!(%error, "failed to ensure FTS index after memory consolidation");
+    }
+
+    let archived_memory_ids: Vec<String> = originals
+        .iter()
+        .map(|original| original.id.clone())
+        .collect();
+
+    deps.memory_event_tx
+        .send(ProcessEvent::MemorySaved {
+            agent_id: deps.agent_id.clone(),
+            memory_id: memory.id.clone(),
+            channel_id: memory.channel_id.clone(),
+            memory_type: memory.memory_type,
+            importance: memory.importance,
+            content_summary: summarize_signal_text(&memory.content),
+        })
+        .ok();
+    deps.event_tx
+        .send(ProcessEvent::MemoriesConsolidated {
+            agent_id: deps.agent_id.clone(),
+            memory_id: memory.id.clone(),
+            archived_memory_ids: archived_memory_ids.clone(),
+        })
+        .ok();
+
+    tracing::info!(
+        memory_id = %memory.id,
+        archived = archived_memory_ids.len(),
+        "consolidated memory cluster"
+    );
+    logger.log(
+        "memory_consolidated",
+        &format!(
+            "Consolidated {} {memory_type} memories into {}",
+            archived_memory_ids.len(),
+            memory.id
+        ),
+        Some(serde_json::json!({
+            "memory_id": memory.id,
+            "memory_type": memory_type.to_string(),
+            "archived_memory_ids": archived_memory_ids,
+            "importance": memory.importance,
+            "model": model_name,
+        })),
+    );
+
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
         BULLETIN_REFRESH_CIRCUIT_OPEN_SECS, BULLETIN_REFRESH_CIRCUIT_OPEN_THRESHOLD, BranchTracker,
         BulletinRefreshOutcome, CortexReceiverOutcome, HealthRuntimeState,
         MAINTENANCE_TASK_CANCEL_GRACE_SECS, MaintenanceTimeoutAction, ReceiverClosedBehavior,
-        Signal, WorkerTracker, apply_cancelled_warmup_status, build_kill_targets,
+        Signal, WorkerTracker, apply_cancelled_warmup_status, build_kill_targets, bulletin_pinned,
         claim_detached_completion, detached_timeout_transition, handle_cortex_receiver_result,
         has_completed_initial_warmup, is_cancelled_control_result, is_terminal_control_result,
         maintenance_task_timeout, maintenance_timeout_action, maybe_close_bulletin_refresh_circuit,
         maybe_generate_bulletin_under_lock, parse_structured_success_flag, push_signal_into_buffer,
         record_bulletin_refresh_failure, should_execute_warmup,
-        should_generate_bulletin_from_bulletin_loop, signal_from_event, summarize_signal_text,
-        take_lagged_control_flag,
+        should_generate_bulletin_from_bulletin_loop, signal_from_event, store_generated_bulletin,
+        summarize_signal_text, take_lagged_control_flag,
     };
     use crate::ProcessEvent;
     use crate::agent::process_control::ControlActionResult;
@@ -4167,6 +4572,23 @@ mod tests {
         assert!(should_execute_warmup(warmup_config, false));
     }
 
+    #[test]
+    fn model_preload_skips_scheduled_passes_and_respects_config() {
+        let warmup_config = crate::config::WarmupConfig {
+            preload_models: true,
+            ..Default::default()
+        };
+
+        assert!(should_preload_models(warmup_config, "startup"));
+        assert!(should_preload_models(warmup_config, "startup_pre_adapter"));
+        assert!(should_preload_models(warmup_config, "dispatch_worker"));
+        assert!(!should_preload_models(warmup_config, "scheduled"));
+        assert!(!should_preload_models(
+            crate::config::WarmupConfig::default(),
+            "startup"
+        ));
+    }
+
     #[test]
     fn initial_warmup_completion_detected_when_status_has_refresh_timestamp() {
         let status = crate::config::WarmupStatus {
@@ -4430,6 +4852,7 @@ mod tests {
                 channel_id: Some(channel_id.clone()),
                 tool_name: "shell".to_string(),
                 result: "done".to_string(),
+                truncated_bytes: None,
             },
             ProcessEvent::MemorySaved {
                 agent_id: agent_id.clone(),
@@ -4443,6 +4866,7 @@ mod tests {
                 agent_id: agent_id.clone(),
                 channel_id: channel_id.clone(),
                 threshold_reached: 0.86,
+                reason: crate::CompactionTrigger::TokenRatio,
             },
             ProcessEvent::StatusUpdate {
                 agent_id: agent_id.clone(),
@@ -4810,6 +5234,7 @@ mod tests {
                 created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 completed_at TIMESTAMP,
+                dedupe_key TEXT,
                 UNIQUE(agent_id, task_number)
             )",
         )
@@ -4892,6 +5317,7 @@ mod tests {
                 created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 completed_at TIMESTAMP,
+                dedupe_key TEXT,
                 UNIQUE(agent_id, task_number)
             )",
         )
@@ -5187,4 +5613,60 @@ mod tests {
             MAX_DROPPED_EVENTS_BUDGET
         );
     }
+
+    fn bulletin_settings() -> (tempfile::TempDir, crate::settings::SettingsStore) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let settings = crate::settings::SettingsStore::new(&dir.path().join("settings.redb"))
+            .expect("failed to open settings store");
+        (dir, settings)
+    }
+
+    fn set_bulletin_override(settings: &crate::settings::SettingsStore, permanent: bool) {
+        settings
+            .set_memory_bulletin_override(Some(&crate::settings::BulletinOverride {
+                bulletin: "hand-written bulletin".into(),
+                permanent,
+                set_at: chrono::Utc::now(),
+            }))
+            .expect("failed to store override");
+    }
+
+    #[test]
+    fn permanent_bulletin_override_survives_a_refresh() {
+        let (_dir, settings) = bulletin_settings();
+        set_bulletin_override(&settings, true);
+        let memory_bulletin = arc_swap::ArcSwap::from_pointee("hand-written bulletin".to_string());
+
+        assert!(bulletin_pinned(Some(&settings)));
+        store_generated_bulletin(Some(&settings), &memory_bulletin, "generated".into());
+
+        assert_eq!(memory_bulletin.load().as_str(), "hand-written bulletin");
+        assert!(settings.memory_bulletin_override().is_some());
+    }
+
+    #[test]
+    fn temporary_bulletin_override_ends_at_the_next_refresh() {
+        let (_dir, settings) = bulletin_settings();
+        set_bulletin_override(&settings, false);
+        let memory_bulletin = arc_swap::ArcSwap::from_pointee("hand-written bulletin".to_string());
+
+        assert!(!bulletin_pinned(Some(&settings)));
+        store_generated_bulletin(Some(&settings), &memory_bulletin, "generated".into());
+
+        assert_eq!(memory_bulletin.load().as_str(), "generated");
+        assert!(settings.memory_bulletin_override().is_none());
+    }
+
+    #[test]
+    fn bulletin_is_stored_without_an_override() {
+        let (_dir, settings) = bulletin_settings();
+        let memory_bulletin = arc_swap::ArcSwap::from_pointee(String::new());
+
+        assert!(!bulletin_pinned(Some(&settings)));
+        assert!(!bulletin_pinned(None));
+        store_generated_bulletin(Some(&settings), &memory_bulletin, "generated".into());
+        store_generated_bulletin(None, &memory_bulletin, "again".into());
+
+        assert_eq!(memory_bulletin.load().as_str(), "again");
+    }
 }
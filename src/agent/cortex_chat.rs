@@ -205,8 +205,11 @@ impl<M: CompletionModel> PromptHook<M> for CortexChatHook {
             return guard_action;
         }
         let preview = crate::tools::truncate_utf8_ellipsis(result, 200);
-        self.spacebot_hook
-            .emit_tool_completed_event_from_capped(tool_name, preview.clone());
+        self.spacebot_hook.emit_tool_completed_event_from_capped(
+            tool_name,
+            preview.clone(),
+            crate::tools::result_limit::truncated_bytes(result),
+        );
         self.spacebot_hook
             .record_tool_result_metrics(tool_name, internal_call_id);
 
@@ -677,9 +680,22 @@ impl CortexChatSession {
         // Resolve model and build agent
         let routing = self.deps.runtime_config.routing.load();
         let model_name = routing.resolve(ProcessType::Cortex, None).to_string();
+        let (event_tx, event_rx) = mpsc::channel(256);
+        let spacebot_hook = SpacebotHook::new(
+            self.deps.agent_id.clone(),
+            ProcessId::Worker(uuid::Uuid::new_v4()),
+            ProcessType::Cortex,
+            channel_context_id.map(std::sync::Arc::<str>::from),
+            self.deps.event_tx.clone(),
+        )
+        .with_disabled_tools(self.deps.runtime_config.disabled_tools.load_full())
+        .with_output_token_cap(routing.max_output_tokens(ProcessType::Cortex))
+        .with_tool_call_limits(**self.deps.runtime_config.tool_call_limits.load())
+        .with_tool_server(self.tool_server.clone());
         let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(self.deps.agent_id.as_ref(), "cortex")
-            .with_routing(routing.as_ref().clone());
+            .with_routing(routing.as_ref().clone())
+            .with_turn_budget(spacebot_hook.turn_budget());
 
         let agent = AgentBuilder::new(model)
             .preamble(&system_prompt)
@@ -687,14 +703,6 @@ impl CortexChatSession {
             .tool_server_handle(self.tool_server.clone())
             .build();
 
-        let (event_tx, event_rx) = mpsc::channel(256);
-        let spacebot_hook = SpacebotHook::new(
-            self.deps.agent_id.clone(),
-            ProcessId::Worker(uuid::Uuid::new_v4()),
-            ProcessType::Cortex,
-            channel_context_id.map(std::sync::Arc::<str>::from),
-            self.deps.event_tx.clone(),
-        );
         let tool_calls = Arc::new(Mutex::new(Vec::new()));
         let hook = CortexChatHook::new(event_tx.clone(), spacebot_hook, tool_calls.clone());
 
@@ -0,0 +1,150 @@
+//! Size cap on inbound message text.
+//!
+//! When `channel.max_inbound_chars` is set, message text longer than the cap
+//! is truncated, summarized, or rejected (per `channel.oversized_inbound`)
+//! before it is formatted into a turn. The conversation log is written from
+//! the original text first, so the full message is never lost.
+//!
+//! Summarization fails open to truncation: if the model call fails, the turn
+//! still runs on the first `max_inbound_chars` characters.
+
+use crate::config::OversizedInboundPolicy;
+use crate::hooks::SpacebotHook;
+use crate::llm::SpacebotModel;
+use crate::tools::char_prefix;
+use crate::{AgentDeps, ChannelId, ProcessId, ProcessType};
+
+use rig::agent::AgentBuilder;
+use uuid::Uuid;
+
+/// Most characters of an oversized message handed to the summarizer. Keeps a
+/// pasted multi-megabyte blob from overflowing the summarizer's own context.
+const SUMMARY_INPUT_MAX_CHARS: usize = 200_000;
+
+/// Text standing in for a rejected message in a coalesced batch.
+pub const REJECTED_PLACEHOLDER: &str = "[message over the size limit was not read]";
+
+/// The text a turn should see for one inbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitedInbound {
+    /// Use this text for the turn. Unchanged when within the limit.
+    Text(String),
+    /// Skip the message and reply with this notice.
+    Rejected(String),
+}
+
+/// Apply the agent's inbound size cap to `text`.
+pub async fn limit_inbound_text(
+    deps: &AgentDeps,
+    channel_id: &ChannelId,
+    text: &str,
+) -> LimitedInbound {
+    let channel_config = deps.runtime_config.channel_config.load();
+    let max_chars = channel_config.max_inbound_chars;
+    let total_chars = text.chars().count();
+    if max_chars == 0 || total_chars <= max_chars {
+        return LimitedInbound::Text(text.to_string());
+    }
+
+    tracing::info!(
+        channel_id = %channel_id,
+        total_chars,
+        max_chars,
+        policy = channel_config.oversized_inbound.as_str(),
+        "inbound message over size limit"
+    );
+
+    match channel_config.oversized_inbound {
+        OversizedInboundPolicy::Truncate => {
+            LimitedInbound::Text(truncate_with_notice(text, max_chars, total_chars))
+        }
+        OversizedInboundPolicy::Reject => {
+            LimitedInbound::Rejected(channel_config.oversized_message.clone())
+        }
+        OversizedInboundPolicy::Summarize => match summarize(deps, channel_id, text).await {
+            Ok(summary) => LimitedInbound::Text(format!(
+                "[This message was {total_chars} characters, over the {max_chars} character \
+                 limit. You are seeing a summary; the full text is kept in the conversation \
+                 log.]\n\n{summary}"
+            )),
+            Err(error) => {
+                tracing::warn!(
+                    channel_id = %channel_id,
+                    %error,
+                    "inbound summarization failed, truncating instead"
+                );
+                LimitedInbound::Text(truncate_with_notice(text, max_chars, total_chars))
+            }
+        },
+    }
+}
+
+/// The part of `text` within the size cap, for background passes (like task
+/// extraction) that should not pay for an oversized paste.
+pub fn capped_prefix<'a>(deps: &AgentDeps, text: &'a str) -> &'a str {
+    match deps.runtime_config.channel_config.load().max_inbound_chars {
+        0 => text,
+        max_chars => char_prefix(text, max_chars),
+    }
+}
+
+/// Keep the first `max_chars` characters of `text` and say what was cut.
+fn truncate_with_notice(text: &str, max_chars: usize, total_chars: usize) -> String {
+    format!(
+        "{}\n\n[Message truncated: showing the first {max_chars} of {total_chars} characters. \
+         The full text is kept in the conversation log.]",
+        char_prefix(text, max_chars)
+    )
+}
+
+/// Ask the compactor model for a summary of an oversized message.
+async fn summarize(deps: &AgentDeps, channel_id: &ChannelId, text: &str) -> anyhow::Result<String> {
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let preamble = prompt_engine.render_static("inbound_summary")?;
+
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Compactor, None).to_string();
+    let hook = SpacebotHook::new(
+        deps.agent_id.clone(),
+        ProcessId::Worker(Uuid::new_v4()),
+        ProcessType::Compactor,
+        Some(channel_id.clone()),
+        deps.event_tx.clone(),
+    )
+    .with_output_token_cap(routing.max_output_tokens(ProcessType::Compactor));
+
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "compactor")
+        .with_routing((**routing).clone())
+        .with_turn_budget(hook.turn_budget());
+
+    let agent = AgentBuilder::new(model)
+        .preamble(&preamble)
+        .default_max_turns(1)
+        .build();
+
+    let input = char_prefix(text, SUMMARY_INPUT_MAX_CHARS);
+    let mut history = Vec::new();
+    let summary = hook
+        .prompt_once(&agent, &mut history, input)
+        .await
+        .map_err(|error| anyhow::anyhow!("inbound summary failed: {error}"))?;
+
+    let summary = summary.trim();
+    if summary.is_empty() {
+        anyhow::bail!("inbound summary was empty");
+    }
+    Ok(summary.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_keeps_prefix_and_reports_lengths() {
+        let truncated = truncate_with_notice("héllo wörld", 5, 11);
+        assert!(truncated.starts_with("héllo\n\n[Message truncated"));
+        assert!(truncated.contains("first 5 of 11 characters"));
+    }
+}
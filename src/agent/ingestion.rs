@@ -2,11 +2,21 @@
 //!
 //! Polls a directory in the agent workspace for supported files, extracts text,
 //! chunks it, and processes each chunk through the memory recall + save flow.
-//! Files are deleted after all chunks are successfully ingested.
+//! Files are deleted after all chunks are successfully ingested. Files whose
+//! extension isn't in `[ingestion] extensions`, or whose text can't be
+//! extracted, are moved to a `failed/` subdirectory for the user to inspect.
+//!
+//! Files that fail mid-ingestion (e.g. a provider error on some chunk) are
+//! moved to a hidden `.retry/` queue and re-attempted on later poll cycles.
+//! After `[ingestion] max_retries` failed retries they are moved to `failed/`
+//! with a `<name>.error.json` sidecar describing the last error.
 //!
 //! Progress is tracked per-chunk in SQLite using a SHA-256 hash of the file
 //! content. If the server restarts mid-file, already-completed chunks are
 //! skipped on the next run.
+//!
+//! With `concurrency > 1`, memory saves from chunks processed in parallel are
+//! coalesced into shared embedding batches by [`crate::memory::EmbeddingModel`].
 
 use crate::AgentDeps;
 use crate::ProcessId;
@@ -17,18 +27,49 @@ use crate::llm::SpacebotModel;
 use crate::tools::MemoryPersistenceContractState;
 
 use anyhow::Context as _;
+use futures::StreamExt as _;
 use rig::agent::AgentBuilder;
 use rig::completion::{CompletionModel, PromptError};
 use rig::tool::server::ToolServerHandle;
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
+use tokio::sync::Semaphore;
 
+use regex::Regex;
 use std::collections::HashSet;
+use std::future::Future;
+use std::io::Read as _;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Subdirectory of the ingest directory that unsupported and unreadable files
+/// are moved to.
+const FAILED_DIR_NAME: &str = "failed";
+
+/// Hidden subdirectory of the ingest directory holding files that failed and
+/// are waiting to be retried on a later poll cycle.
+const RETRY_DIR_NAME: &str = ".retry";
+
+/// Suffix of the sidecar written next to a file in `failed/`.
+const ERROR_SIDECAR_SUFFIX: &str = ".error.json";
+
+/// Attempt counter kept in a hidden sidecar next to a queued retry file.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct RetryState {
+    attempts: u32,
+    last_error: String,
+}
+
+/// Contents of the error sidecar written next to a file in `failed/`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct FailureRecord {
+    error: String,
+    attempts: u32,
+    failed_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Spawn the ingestion polling loop for an agent.
 ///
 /// Runs until the returned JoinHandle is dropped or aborted. Scans the ingest
@@ -46,42 +87,66 @@ async fn run_ingestion_loop(ingest_dir: &Path, deps: &AgentDeps) -> anyhow::Resu
     tracing::info!(path = %ingest_dir.display(), "ingestion loop started");
 
     loop {
-        let config = **deps.runtime_config.ingestion.load();
+        let config = deps.runtime_config.ingestion.load_full();
 
         if !config.enabled {
             tokio::time::sleep(Duration::from_secs(config.poll_interval_secs)).await;
             continue;
         }
 
-        // Scan for files
-        match scan_ingest_dir(ingest_dir).await {
-            Ok(files) if !files.is_empty() => {
-                for file_path in files {
-                    if let Err(error) = process_file(&file_path, deps, &config).await {
-                        tracing::error!(
-                            path = %file_path.display(),
-                            %error,
-                            "failed to ingest file"
-                        );
-                    }
-                }
-            }
+        // Scan for new files, then pick up anything waiting in the retry queue.
+        let mut files = match scan_ingest_dir(ingest_dir, &config.extensions).await {
+            Ok(files) => files,
             Err(error) => {
                 // Directory might not exist yet — that's fine
                 tracing::debug!(%error, "failed to scan ingest directory");
+                Vec::new()
             }
-            _ => {}
+        };
+        files.extend(
+            scan_ingest_dir(&ingest_dir.join(RETRY_DIR_NAME), &config.extensions)
+                .await
+                .unwrap_or_default(),
+        );
+
+        if !files.is_empty() {
+            // One semaphore per poll cycle bounds in-flight chunk LLM calls
+            // across every file, so concurrent files can't multiply the
+            // load on the embedder or recall path.
+            let concurrency = config.concurrency.max(1);
+            let chunk_permits = Arc::new(Semaphore::new(concurrency));
+            let config = &*config;
+            futures::stream::iter(files)
+                .for_each_concurrent(concurrency, |file_path| {
+                    let chunk_permits = chunk_permits.clone();
+                    async move {
+                        if let Err(error) =
+                            process_file(&file_path, deps, config, chunk_permits).await
+                        {
+                            tracing::error!(
+                                path = %file_path.display(),
+                                %error,
+                                "failed to ingest file"
+                            );
+                        }
+                    }
+                })
+                .await;
         }
 
+        #[cfg(feature = "metrics")]
+        record_queue_sizes(ingest_dir, &deps.agent_id).await;
+
         tokio::time::sleep(Duration::from_secs(config.poll_interval_secs)).await;
     }
 }
 
-/// Scan the ingest directory for supported ingestion files.
+/// Scan the ingest directory for supported ingestion files, moving files with
+/// other extensions to `failed/`.
 ///
 /// Returns files sorted by modification time (oldest first) so ingestion
 /// order is predictable.
-async fn scan_ingest_dir(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+async fn scan_ingest_dir(dir: &Path, extensions: &[String]) -> anyhow::Result<Vec<PathBuf>> {
     let mut entries = tokio::fs::read_dir(dir)
         .await
         .with_context(|| format!("failed to read ingest directory: {}", dir.display()))?;
@@ -102,13 +167,21 @@ async fn scan_ingest_dir(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
         }
 
         // Only process files that look ingestible.
-        if is_supported_ingest_file(&path) {
+        if is_supported_ingest_file(&path, extensions) {
             files.push(path);
         } else {
-            tracing::warn!(
-                path = %path.display(),
-                "skipping unsupported file in ingest directory"
-            );
+            match move_to_failed(&path, "unsupported file extension", 0).await {
+                Ok(moved) => tracing::warn!(
+                    path = %path.display(),
+                    moved_to = %moved.display(),
+                    "moved unsupported file out of ingest directory"
+                ),
+                Err(error) => tracing::warn!(
+                    path = %path.display(),
+                    %error,
+                    "failed to move unsupported file out of ingest directory"
+                ),
+            }
         }
     }
 
@@ -122,33 +195,182 @@ async fn scan_ingest_dir(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-/// Check if a file extension suggests ingestible content.
-fn is_supported_ingest_file(path: &Path) -> bool {
+/// Check if a file's extension is in the configured allowlist.
+fn is_supported_ingest_file(path: &Path, extensions: &[String]) -> bool {
     let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
         // No extension — try to read as text
         return true;
     };
 
-    matches!(
-        ext.to_lowercase().as_str(),
-        "txt"
-            | "md"
-            | "markdown"
-            | "json"
-            | "jsonl"
-            | "csv"
-            | "tsv"
-            | "log"
-            | "xml"
-            | "yaml"
-            | "yml"
-            | "toml"
-            | "rst"
-            | "org"
-            | "html"
-            | "htm"
-            | "pdf"
-    )
+    let ext = ext.to_lowercase();
+    extensions.iter().any(|allowed| *allowed == ext)
+}
+
+/// The ingest directory a file belongs to, whether it sits directly in it or
+/// in its retry queue.
+fn ingest_root(path: &Path) -> anyhow::Result<&Path> {
+    let parent = path
+        .parent()
+        .context("ingest file has no parent directory")?;
+    if parent
+        .file_name()
+        .is_some_and(|name| name == RETRY_DIR_NAME)
+    {
+        return parent
+            .parent()
+            .context("retry queue has no parent directory");
+    }
+    Ok(parent)
+}
+
+/// Move a file into `dir`, keeping its name unless a file with that name is
+/// already there.
+async fn move_into(path: &Path, dir: &Path) -> anyhow::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let filename = path
+        .file_name()
+        .context("ingest file has no name")?
+        .to_string_lossy();
+    let mut target = dir.join(filename.as_ref());
+    if tokio::fs::try_exists(&target).await.unwrap_or(false) {
+        let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f");
+        target = dir.join(format!("{timestamp}-{filename}"));
+    }
+
+    tokio::fs::rename(path, &target)
+        .await
+        .with_context(|| format!("failed to move {} to {}", path.display(), target.display()))?;
+    Ok(target)
+}
+
+/// Move a file into the ingest directory's `failed/` subdirectory and write a
+/// `<name>.error.json` sidecar next to it. Clears any retry state the file had.
+async fn move_to_failed(path: &Path, error: &str, attempts: u32) -> anyhow::Result<PathBuf> {
+    let failed_dir = ingest_root(path)?.join(FAILED_DIR_NAME);
+    let target = move_into(path, &failed_dir).await?;
+    clear_retry_state(path).await;
+
+    let record = FailureRecord {
+        error: error.to_string(),
+        attempts,
+        failed_at: chrono::Utc::now(),
+    };
+    let mut sidecar = target.clone().into_os_string();
+    sidecar.push(ERROR_SIDECAR_SUFFIX);
+    tokio::fs::write(&sidecar, serde_json::to_vec_pretty(&record)?)
+        .await
+        .with_context(|| format!("failed to write {}", Path::new(&sidecar).display()))?;
+    Ok(target)
+}
+
+/// Hidden sidecar holding the attempt counter for a file in the retry queue.
+fn retry_state_path(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy();
+    let retry_dir = path.parent()?;
+    (retry_dir.file_name()? == RETRY_DIR_NAME)
+        .then(|| retry_dir.join(format!(".{name}.attempts.json")))
+}
+
+async fn read_retry_state(path: &Path) -> RetryState {
+    let Some(state_path) = retry_state_path(path) else {
+        return RetryState::default();
+    };
+    match tokio::fs::read(&state_path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+        Err(_) => RetryState::default(),
+    }
+}
+
+async fn clear_retry_state(path: &Path) {
+    if let Some(state_path) = retry_state_path(path) {
+        let _ = tokio::fs::remove_file(state_path).await;
+    }
+}
+
+/// Record a failed ingestion attempt: queue the file for another attempt on
+/// the next poll cycle, or move it to `failed/` once `max_retries` retries
+/// have been used up.
+async fn record_failure(
+    path: &Path,
+    error: &anyhow::Error,
+    max_retries: u32,
+) -> anyhow::Result<PathBuf> {
+    let error = format!("{error:#}");
+    let attempts = read_retry_state(path).await.attempts + 1;
+
+    if attempts > max_retries {
+        let moved = move_to_failed(path, &error, attempts).await?;
+        tracing::warn!(
+            path = %path.display(),
+            moved_to = %moved.display(),
+            attempts,
+            "ingestion retries exhausted, moved file out of ingest directory"
+        );
+        return Ok(moved);
+    }
+
+    let queued = if retry_state_path(path).is_some() {
+        path.to_path_buf()
+    } else {
+        move_into(path, &ingest_root(path)?.join(RETRY_DIR_NAME)).await?
+    };
+    let state = RetryState {
+        attempts,
+        last_error: error,
+    };
+    let state_path = retry_state_path(&queued).context("retry queue file has no name")?;
+    tokio::fs::write(&state_path, serde_json::to_vec_pretty(&state)?)
+        .await
+        .with_context(|| format!("failed to write {}", state_path.display()))?;
+
+    tracing::warn!(
+        path = %path.display(),
+        queued_at = %queued.display(),
+        attempts,
+        max_retries,
+        "file ingestion failed, queued for retry"
+    );
+    Ok(queued)
+}
+
+/// Publish how many files are pending, waiting to be retried, and failed.
+#[cfg(feature = "metrics")]
+async fn record_queue_sizes(ingest_dir: &Path, agent_id: &str) {
+    let metrics = crate::telemetry::Metrics::global();
+    for (state, dir) in [
+        ("pending", ingest_dir.to_path_buf()),
+        ("retrying", ingest_dir.join(RETRY_DIR_NAME)),
+        ("failed", ingest_dir.join(FAILED_DIR_NAME)),
+    ] {
+        let count = count_queued_files(&dir).await;
+        metrics
+            .ingestion_files_queued
+            .with_label_values(&[agent_id, state])
+            .set(count as i64);
+    }
+}
+
+/// Count the visible files in a directory, ignoring error sidecars.
+#[cfg(feature = "metrics")]
+async fn count_queued_files(dir: &Path) -> usize {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return 0;
+    };
+    let mut count = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name.ends_with(ERROR_SIDECAR_SUFFIX) {
+            continue;
+        }
+        if entry.file_type().await.is_ok_and(|kind| kind.is_file()) {
+            count += 1;
+        }
+    }
+    count
 }
 
 /// SHA-256 hex digest of file content, used as a stable identifier for
@@ -162,13 +384,80 @@ pub fn content_hash(content: &str) -> String {
 /// Process a single file: read, chunk, process each chunk, then delete.
 ///
 /// Checks the ingestion_progress table to skip chunks that were already
-/// completed in a previous run (e.g. before a server restart).
-#[tracing::instrument(skip(deps, config), fields(agent_id = %deps.agent_id, path = %path.display()))]
+/// completed in a previous run (e.g. before a server restart). A failed file
+/// is queued for retry or moved to `failed/`.
+#[tracing::instrument(skip(deps, config, chunk_permits), fields(agent_id = %deps.agent_id, path = %path.display()))]
 async fn process_file(
     path: &Path,
     deps: &AgentDeps,
     config: &IngestionConfig,
+    chunk_permits: Arc<Semaphore>,
 ) -> anyhow::Result<()> {
+    ingest_file_with(
+        path,
+        &deps.sqlite_pool,
+        &deps.agent_id,
+        config,
+        chunk_permits,
+        |chunk, filename, chunk_number, total_chunks| async move {
+            process_chunk(&chunk, &filename, chunk_number, total_chunks, deps).await
+        },
+    )
+    .await
+}
+
+/// [`process_file_with`] plus retry bookkeeping: on failure the file is queued
+/// in `.retry/` or, once its retries are used up, moved to `failed/`. The
+/// original error is still returned so the caller can log it.
+async fn ingest_file_with<F, Fut>(
+    path: &Path,
+    pool: &SqlitePool,
+    agent_id: &str,
+    config: &IngestionConfig,
+    chunk_permits: Arc<Semaphore>,
+    process: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(String, String, usize, usize) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    match process_file_with(path, pool, agent_id, config, chunk_permits, process).await {
+        Ok(()) => {
+            clear_retry_state(path).await;
+            Ok(())
+        }
+        Err(error) => {
+            if let Err(move_error) = record_failure(path, &error, config.max_retries).await {
+                tracing::error!(
+                    path = %path.display(),
+                    error = %move_error,
+                    "failed to queue file for retry"
+                );
+            }
+            Err(error)
+        }
+    }
+}
+
+/// File ingestion with the chunk processor injected, so the progress and
+/// delete-on-success bookkeeping can be exercised without an LLM.
+///
+/// Pending chunks run concurrently, each holding a permit from
+/// `chunk_permits` while it processes. The source file is only deleted once
+/// every chunk has succeeded; otherwise an error is returned and the file and
+/// its progress rows are left in place.
+async fn process_file_with<F, Fut>(
+    path: &Path,
+    pool: &SqlitePool,
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))] agent_id: &str,
+    config: &IngestionConfig,
+    chunk_permits: Arc<Semaphore>,
+    process: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(String, String, usize, usize) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
     let filename = path
         .file_name()
         .and_then(|n| n.to_str())
@@ -176,7 +465,20 @@ async fn process_file(
 
     tracing::info!(file = %filename, "starting file ingestion");
 
-    let content = read_ingest_content(path).await?;
+    let content = match read_ingest_content(path).await {
+        Ok(content) => content,
+        Err(error) => {
+            let attempts = read_retry_state(path).await.attempts + 1;
+            let moved = move_to_failed(path, &format!("{error:#}"), attempts).await?;
+            tracing::warn!(
+                file = %filename,
+                moved_to = %moved.display(),
+                error = format!("{error:#}"),
+                "could not extract text, moved file out of ingest directory"
+            );
+            return Ok(());
+        }
+    };
 
     if content.trim().is_empty() {
         tracing::info!(file = %filename, "skipping empty file");
@@ -189,18 +491,11 @@ async fn process_file(
     let chunks = chunk_text(&content, config.chunk_size);
     let total_chunks = chunks.len();
 
-    let completed = load_completed_chunks(&deps.sqlite_pool, &hash).await?;
+    let completed = load_completed_chunks(pool, &hash).await?;
     let remaining = total_chunks - completed.len();
 
     // Record file-level tracking (idempotent — skips if already exists from a previous run)
-    upsert_ingestion_file(
-        &deps.sqlite_pool,
-        &hash,
-        filename,
-        file_size,
-        total_chunks as i64,
-    )
-    .await?;
+    upsert_ingestion_file(pool, &hash, filename, file_size, total_chunks as i64).await?;
 
     if !completed.is_empty() {
         tracing::info!(
@@ -219,60 +514,79 @@ async fn process_file(
         );
     }
 
-    let mut had_failure = false;
-
-    for (index, chunk) in chunks.iter().enumerate() {
-        let chunk_number = index + 1;
-
-        if completed.contains(&(index as i64)) {
+    let pending_chunks = chunks.into_iter().enumerate().filter(|(index, _)| {
+        let already_done = completed.contains(&(*index as i64));
+        if already_done {
             tracing::debug!(
                 file = %filename,
-                chunk = %format!("{chunk_number}/{total_chunks}"),
+                chunk = %format!("{}/{total_chunks}", index + 1),
                 "chunk already ingested, skipping"
             );
-            continue;
         }
+        !already_done
+    });
 
-        tracing::info!(
-            file = %filename,
-            chunk = %format!("{chunk_number}/{total_chunks}"),
-            chars = chunk.len(),
-            "processing chunk"
-        );
+    let chunk_results = futures::future::join_all(pending_chunks.map(|(index, chunk)| {
+        let chunk_permits = chunk_permits.clone();
+        let process = &process;
+        async move {
+            let chunk_number = index + 1;
+            let _permit = chunk_permits
+                .acquire_owned()
+                .await
+                .context("ingestion semaphore closed")?;
 
-        match process_chunk(chunk, filename, chunk_number, total_chunks, deps).await {
-            Ok(()) => {
-                record_chunk_completed(
-                    &deps.sqlite_pool,
-                    &hash,
-                    index as i64,
-                    total_chunks as i64,
-                    filename,
-                )
-                .await?;
-            }
-            Err(error) => {
-                tracing::error!(
-                    file = %filename,
-                    chunk = %format!("{chunk_number}/{total_chunks}"),
-                    %error,
-                    "failed to process chunk"
-                );
-                had_failure = true;
+            tracing::info!(
+                file = %filename,
+                chunk = %format!("{chunk_number}/{total_chunks}"),
+                chars = chunk.len(),
+                "processing chunk"
+            );
+
+            match process(chunk, filename.to_string(), chunk_number, total_chunks).await {
+                Ok(()) => {
+                    record_chunk_completed(
+                        pool,
+                        &hash,
+                        index as i64,
+                        total_chunks as i64,
+                        filename,
+                    )
+                    .await?;
+                    Ok(None)
+                }
+                Err(error) => {
+                    tracing::error!(
+                        file = %filename,
+                        chunk = %format!("{chunk_number}/{total_chunks}"),
+                        %error,
+                        "failed to process chunk"
+                    );
+                    Ok::<_, anyhow::Error>(Some(format!("{error:#}")))
+                }
             }
         }
+    }))
+    .await;
+
+    let mut chunk_errors = Vec::new();
+    for result in chunk_results {
+        if let Some(error) = result? {
+            chunk_errors.push(error);
+        }
     }
+    let had_failure = !chunk_errors.is_empty();
 
     // Mark file as completed (or failed if any chunk errored)
     let final_status = if had_failure { "failed" } else { "completed" };
-    complete_ingestion_file(&deps.sqlite_pool, &hash, final_status).await?;
+    complete_ingestion_file(pool, &hash, final_status).await?;
 
     #[cfg(feature = "metrics")]
     {
         let result = if had_failure { "failure" } else { "success" };
         crate::telemetry::Metrics::global()
             .ingestion_files_processed_total
-            .with_label_values(&[&deps.agent_id, result])
+            .with_label_values(&[agent_id, result])
             .inc();
     }
 
@@ -285,11 +599,15 @@ async fn process_file(
             chunks = total_chunks,
             "file ingestion had failures — keeping file and progress for retry"
         );
-        return Ok(());
+        anyhow::bail!(
+            "{} of {total_chunks} chunks failed: {}",
+            chunk_errors.len(),
+            chunk_errors[0]
+        );
     }
 
     // Full success: clean up progress rows and remove the source file.
-    delete_progress(&deps.sqlite_pool, &hash).await?;
+    delete_progress(pool, &hash).await?;
 
     tokio::fs::remove_file(path)
         .await
@@ -302,28 +620,120 @@ async fn process_file(
 
 /// Read an ingest file and return extracted text content.
 ///
-/// Plaintext-like files are read directly as UTF-8. PDFs are read as bytes and
-/// converted to text through the PDF extractor.
+/// Plaintext-like files are read directly as UTF-8. PDFs and DOCX documents are
+/// read as bytes and converted to text on a blocking thread. HTML has its
+/// markup stripped so chunks carry only the readable text.
 async fn read_ingest_content(path: &Path) -> anyhow::Result<String> {
-    let extension = path.extension().and_then(|extension| extension.to_str());
-
-    if extension.is_some_and(|ext| ext.eq_ignore_ascii_case("pdf")) {
-        let bytes = tokio::fs::read(path)
-            .await
-            .with_context(|| format!("failed to read pdf file: {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+
+    match extension.as_deref() {
+        Some("pdf") => {
+            let bytes = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("failed to read pdf file: {}", path.display()))?;
 
-        let extracted =
             tokio::task::spawn_blocking(move || pdf_extract::extract_text_from_mem(&bytes))
                 .await
                 .context("pdf extraction task failed")?
-                .with_context(|| format!("failed to extract text from pdf: {}", path.display()))?;
+                .with_context(|| format!("failed to extract text from pdf: {}", path.display()))
+        }
+        Some("docx") => {
+            let bytes = tokio::fs::read(path)
+                .await
+                .with_context(|| format!("failed to read docx file: {}", path.display()))?;
 
-        return Ok(extracted);
+            tokio::task::spawn_blocking(move || extract_docx_text(&bytes))
+                .await
+                .context("docx extraction task failed")?
+                .with_context(|| format!("failed to extract text from docx: {}", path.display()))
+        }
+        Some("html" | "htm") => {
+            let html = tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("failed to read html file: {}", path.display()))?;
+            Ok(html_to_text(&html))
+        }
+        _ => tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read file: {}", path.display())),
     }
+}
 
-    tokio::fs::read_to_string(path)
-        .await
-        .with_context(|| format!("failed to read file: {}", path.display()))
+/// Extract paragraph text from a DOCX document's `word/document.xml`.
+fn extract_docx_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .context("file is not a docx (zip) archive")?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .context("docx archive has no word/document.xml")?
+        .read_to_string(&mut xml)
+        .context("failed to read word/document.xml")?;
+    Ok(docx_xml_to_text(&xml))
+}
+
+/// Flatten WordprocessingML to text: one line per paragraph, with tabs and
+/// manual line breaks kept.
+fn docx_xml_to_text(xml: &str) -> String {
+    static BREAK: OnceLock<Regex> = OnceLock::new();
+    static TAB: OnceLock<Regex> = OnceLock::new();
+    let line_breaks = BREAK
+        .get_or_init(|| Regex::new(r"</w:p>|<w:(?:br|cr)\b[^>]*/>").expect("valid break regex"));
+    let tabs = TAB.get_or_init(|| Regex::new(r"<w:tab\b[^>]*/>").expect("valid tab regex"));
+
+    let text = line_breaks.replace_all(xml, "\n");
+    let text = tabs.replace_all(&text, "\t");
+    let text = markup_tag_regex().replace_all(&text, "");
+    decode_entities(&text).trim().to_string()
+}
+
+/// Strip HTML down to its readable text, dropping scripts, styles, and
+/// comments and keeping block elements on separate lines.
+fn html_to_text(html: &str) -> String {
+    static HIDDEN: OnceLock<Regex> = OnceLock::new();
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    let hidden = HIDDEN.get_or_init(|| {
+        Regex::new(r"(?is)<script\b.*?</script>|<style\b.*?</style>|<!--.*?-->")
+            .expect("valid hidden html regex")
+    });
+    let block = BLOCK.get_or_init(|| {
+        Regex::new(
+            r"(?i)<br\s*/?>|</(?:p|div|li|h[1-6]|tr|table|section|article|blockquote|pre|ul|ol)>",
+        )
+        .expect("valid block html regex")
+    });
+
+    let text = hidden.replace_all(html, "");
+    let text = block.replace_all(&text, "\n");
+    let text = markup_tag_regex().replace_all(&text, "");
+    let text = decode_entities(&text.replace("&nbsp;", " "));
+
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !line.is_empty() || lines.last().is_some_and(|last: &String| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn markup_tag_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"(?s)<[^>]+>").expect("valid markup tag regex"))
+}
+
+/// Decode the predefined XML entities, plus the numeric apostrophe HTML uses.
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
 }
 
 // -- Progress tracking queries --------------------------------------------------
@@ -396,7 +806,7 @@ async fn upsert_ingestion_file(
         ON CONFLICT(content_hash) DO UPDATE SET
             total_chunks = excluded.total_chunks,
             status = 'processing'
-        WHERE status = 'queued' OR status = 'processing'
+        WHERE status IN ('queued', 'processing', 'failed')
         "#,
     )
     .bind(hash)
@@ -478,11 +888,6 @@ async fn process_chunk(
 
     let routing = deps.runtime_config.routing.load();
     let model_name = routing.resolve(ProcessType::Branch, None).to_string();
-    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
-        .with_context(&*deps.agent_id, "branch")
-        .with_worker_type("ingestion")
-        .with_routing((**routing).clone());
-
     let conversation_logger =
         crate::conversation::history::ConversationLogger::new(deps.sqlite_pool.clone());
     let channel_store = crate::conversation::ChannelStore::new(deps.sqlite_pool.clone());
@@ -504,19 +909,29 @@ async fn process_chunk(
         },
     );
 
-    let agent = AgentBuilder::new(model)
-        .preamble(&ingestion_prompt)
-        .default_max_turns(10)
-        .tool_server_handle(tool_server)
-        .build();
-
     let hook = SpacebotHook::new(
         deps.agent_id.clone(),
         ProcessId::Branch(Uuid::new_v4()),
         ProcessType::Branch,
         None,
         deps.event_tx.clone(),
-    );
+    )
+    .with_disabled_tools(deps.runtime_config.disabled_tools.load_full())
+    .with_output_token_cap(routing.max_output_tokens(ProcessType::Branch))
+    .with_tool_call_limits(**deps.runtime_config.tool_call_limits.load())
+    .with_tool_server(tool_server.clone());
+
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "branch")
+        .with_worker_type("ingestion")
+        .with_routing((**routing).clone())
+        .with_turn_budget(hook.turn_budget());
+
+    let agent = AgentBuilder::new(model)
+        .preamble(&ingestion_prompt)
+        .default_max_turns(10)
+        .tool_server_handle(tool_server)
+        .build();
 
     let user_prompt =
         prompt_engine.render_system_ingestion_chunk(filename, chunk_number, total_chunks, chunk)?;
@@ -608,13 +1023,64 @@ mod tests {
 
     #[test]
     fn test_is_supported_ingest_file() {
-        assert!(is_supported_ingest_file(Path::new("notes.txt")));
-        assert!(is_supported_ingest_file(Path::new("data.json")));
-        assert!(is_supported_ingest_file(Path::new("readme.md")));
-        assert!(is_supported_ingest_file(Path::new("manual.pdf")));
-        assert!(is_supported_ingest_file(Path::new("no_extension")));
-        assert!(!is_supported_ingest_file(Path::new("image.png")));
-        assert!(!is_supported_ingest_file(Path::new("binary.exe")));
+        let extensions = IngestionConfig::default().extensions;
+        let supported = |name: &str| is_supported_ingest_file(Path::new(name), &extensions);
+        assert!(supported("notes.txt"));
+        assert!(supported("data.json"));
+        assert!(supported("readme.md"));
+        assert!(supported("manual.pdf"));
+        assert!(supported("Report.DOCX"));
+        assert!(supported("no_extension"));
+        assert!(!supported("image.png"));
+        assert!(!supported("binary.exe"));
+
+        let only_pdf = vec!["pdf".to_string()];
+        assert!(is_supported_ingest_file(Path::new("manual.pdf"), &only_pdf));
+        assert!(!is_supported_ingest_file(Path::new("notes.txt"), &only_pdf));
+    }
+
+    #[test]
+    fn test_docx_xml_to_text_keeps_paragraphs_and_tabs() {
+        let xml = concat!(
+            r#"<w:document><w:body>"#,
+            r#"<w:p><w:r><w:t>Quarterly</w:t></w:r><w:r><w:t xml:space="preserve"> plan</w:t></w:r></w:p>"#,
+            r#"<w:p><w:r><w:t>Owner</w:t><w:tab/><w:t>Ops &amp; Infra</w:t></w:r></w:p>"#,
+            r#"</w:body></w:document>"#,
+        );
+
+        assert_eq!(docx_xml_to_text(xml), "Quarterly plan\nOwner\tOps & Infra");
+    }
+
+    #[test]
+    fn test_extract_docx_text_reads_document_xml() {
+        use std::io::Write as _;
+
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        let mut writer = zip::ZipWriter::new(&mut buffer);
+        writer
+            .start_file(
+                "word/document.xml",
+                zip::write::SimpleFileOptions::default(),
+            )
+            .unwrap();
+        writer
+            .write_all(br#"<w:document><w:body><w:p><w:r><w:t>hello</w:t></w:r></w:p></w:body></w:document>"#)
+            .unwrap();
+        writer.finish().unwrap();
+
+        assert_eq!(extract_docx_text(buffer.get_ref()).unwrap(), "hello");
+        assert!(extract_docx_text(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_html_to_text_drops_markup_and_scripts() {
+        let html = r#"<html><head><style>p { color: red; }</style><script>alert("x")</script></head>
+<body><h1>Runbook</h1><p>Restart the <b>worker</b> &amp; check logs.</p><!-- draft --><ul><li>one</li><li>two</li></ul></body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Runbook\nRestart the worker & check logs.\none\ntwo"
+        );
     }
 
     #[test]
@@ -670,6 +1136,244 @@ mod tests {
         );
     }
 
+    async fn setup_test_pool() -> SqlitePool {
+        let options = sqlx::sqlite::SqliteConnectOptions::new()
+            .in_memory(true)
+            .create_if_missing(true);
+        let pool = sqlx::pool::PoolOptions::<sqlx::Sqlite>::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("in-memory SQLite");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        pool
+    }
+
+    fn write_multi_chunk_file(dir: &Path) -> PathBuf {
+        let path = dir.join("notes.txt");
+        let content = (0..8)
+            .map(|index| format!("line number {index} with some padding"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn small_chunk_config() -> IngestionConfig {
+        IngestionConfig {
+            chunk_size: 40,
+            concurrency: 4,
+            ..IngestionConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn unreadable_file_is_moved_to_failed_dir() {
+        let pool = setup_test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.docx");
+        std::fs::write(&path, b"not a zip").unwrap();
+        let config = small_chunk_config();
+
+        process_file_with(
+            &path,
+            &pool,
+            "agent",
+            &config,
+            Arc::new(Semaphore::new(config.concurrency)),
+            |_chunk, _filename, _chunk_number, _total_chunks| async move {
+                Err::<(), _>(anyhow::anyhow!("no chunks should be processed"))
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!path.exists());
+        let failed_dir = dir.path().join(FAILED_DIR_NAME);
+        assert!(failed_dir.join("broken.docx").exists());
+        let record: FailureRecord = serde_json::from_slice(
+            &std::fs::read(failed_dir.join("broken.docx.error.json")).unwrap(),
+        )
+        .unwrap();
+        assert!(record.error.contains("docx"));
+    }
+
+    #[tokio::test]
+    async fn concurrent_ingestion_keeps_file_when_a_chunk_fails() {
+        let pool = setup_test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_multi_chunk_file(dir.path());
+        let config = small_chunk_config();
+
+        process_file_with(
+            &path,
+            &pool,
+            "agent",
+            &config,
+            Arc::new(Semaphore::new(config.concurrency)),
+            |_chunk, _filename, chunk_number, _total_chunks| async move {
+                if chunk_number == 2 {
+                    anyhow::bail!("provider error");
+                }
+                Ok(())
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            path.exists(),
+            "file with a failed chunk must not be deleted"
+        );
+
+        let hash = content_hash(&std::fs::read_to_string(&path).unwrap());
+        let completed = load_completed_chunks(&pool, &hash).await.unwrap();
+        assert!(!completed.contains(&1), "failed chunk must not be recorded");
+        assert!(completed.len() > 1, "successful chunks must be recorded");
+    }
+
+    #[tokio::test]
+    async fn concurrent_ingestion_deletes_file_when_all_chunks_succeed() {
+        let pool = setup_test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_multi_chunk_file(dir.path());
+        let config = small_chunk_config();
+
+        let in_flight = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        process_file_with(
+            &path,
+            &pool,
+            "agent",
+            &config,
+            Arc::new(Semaphore::new(2)),
+            |_chunk, _filename, _chunk_number, _total_chunks| {
+                let in_flight = in_flight.clone();
+                let peak = peak.clone();
+                async move {
+                    use std::sync::atomic::Ordering;
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(!path.exists(), "fully ingested file must be deleted");
+        assert!(
+            peak.load(std::sync::atomic::Ordering::SeqCst) <= 2,
+            "chunk processing must respect the semaphore limit"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_file_is_retried_then_moved_to_failed_dir() {
+        let pool = setup_test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_multi_chunk_file(dir.path());
+        let config = IngestionConfig {
+            max_retries: 1,
+            ..small_chunk_config()
+        };
+        let failing = |_chunk: String,
+                       _filename: String,
+                       _chunk_number: usize,
+                       _total_chunks: usize| async move {
+            Err::<(), _>(anyhow::anyhow!("provider error"))
+        };
+
+        let permits = Arc::new(Semaphore::new(config.concurrency));
+        ingest_file_with(&path, &pool, "agent", &config, permits.clone(), failing)
+            .await
+            .unwrap_err();
+
+        let queued = dir.path().join(RETRY_DIR_NAME).join("notes.txt");
+        assert!(!path.exists());
+        assert!(queued.exists(), "failed file must be queued for retry");
+        assert_eq!(read_retry_state(&queued).await.attempts, 1);
+        assert_eq!(
+            scan_ingest_dir(&dir.path().join(RETRY_DIR_NAME), &config.extensions)
+                .await
+                .unwrap(),
+            vec![queued.clone()],
+            "retry sidecar must not be picked up as an ingest file"
+        );
+
+        ingest_file_with(&queued, &pool, "agent", &config, permits, failing)
+            .await
+            .unwrap_err();
+
+        let failed = dir.path().join(FAILED_DIR_NAME).join("notes.txt");
+        assert!(!queued.exists());
+        assert!(failed.exists(), "exhausted file must be moved to failed/");
+        assert!(retry_state_path(&queued).is_some_and(|state| !state.exists()));
+        let record: FailureRecord = serde_json::from_slice(
+            &std::fs::read(
+                dir.path()
+                    .join(FAILED_DIR_NAME)
+                    .join("notes.txt.error.json"),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(record.attempts, 2);
+        assert!(record.error.contains("provider error"));
+    }
+
+    #[tokio::test]
+    async fn successful_retry_deletes_file_and_retry_state() {
+        let pool = setup_test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_multi_chunk_file(dir.path());
+        let config = small_chunk_config();
+        let permits = Arc::new(Semaphore::new(config.concurrency));
+
+        ingest_file_with(
+            &path,
+            &pool,
+            "agent",
+            &config,
+            permits.clone(),
+            |_chunk, _filename, chunk_number, _total_chunks| async move {
+                if chunk_number == 1 {
+                    anyhow::bail!("provider error");
+                }
+                Ok(())
+            },
+        )
+        .await
+        .unwrap_err();
+
+        let queued = dir.path().join(RETRY_DIR_NAME).join("notes.txt");
+        let state_path = retry_state_path(&queued).unwrap();
+        assert!(state_path.exists());
+
+        ingest_file_with(
+            &queued,
+            &pool,
+            "agent",
+            &config,
+            permits,
+            |_chunk, _filename, _chunk_number, _total_chunks| async move { Ok(()) },
+        )
+        .await
+        .unwrap();
+
+        assert!(!queued.exists(), "retried file must be deleted on success");
+        assert!(
+            !state_path.exists(),
+            "retry state must be cleared on success"
+        );
+        assert!(!dir.path().join(FAILED_DIR_NAME).exists());
+    }
+
     #[test]
     fn test_max_turns_classified_as_chunk_failure() {
         let result = classify_chunk_prompt_result(
@@ -0,0 +1,374 @@
+//! Content-safety screening for inbound media.
+//!
+//! When `media_safety.enabled` is set, image attachments are classified
+//! before they reach a vision model. Each classified attachment gets a report
+//! in the message metadata (under `media_safety`), and attachments scoring at
+//! or above `block_threshold` are withheld from the model.
+//!
+//! The built-in classifier calls an OpenAI-compatible moderation endpoint.
+//! Supply a custom one with [`RuntimeConfig::set_media_classifier`].
+//!
+//! Screening fails open: if an attachment can't be downloaded or classified,
+//! it is passed through unchanged and the error is logged.
+//!
+//! Images that pass keep their downloaded bytes in [`MediaScreening`], so
+//! saving them and showing them to the model doesn't fetch them again.
+//!
+//! [`RuntimeConfig::set_media_classifier`]: crate::config::RuntimeConfig::set_media_classifier
+
+use crate::AgentDeps;
+use crate::agent::channel_attachments::{IMAGE_MIME_PREFIXES, download_attachment_bytes};
+use crate::config::MediaSafetyConfig;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Classifier output for a single attachment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaSafetyVerdict {
+    /// Highest category score, 0.0 (safe) to 1.0 (clearly disallowed).
+    pub score: f32,
+    /// Categories the classifier flagged, if it reports them.
+    pub categories: Vec<String>,
+}
+
+/// Static trait for media classifiers.
+pub trait MediaClassifier: Send + Sync + 'static {
+    /// Unique name for this classifier, used in logs and reports.
+    fn name(&self) -> &str;
+
+    /// Classify a downloaded attachment.
+    fn classify(
+        &self,
+        attachment: &crate::Attachment,
+        bytes: &[u8],
+    ) -> impl Future<Output = anyhow::Result<MediaSafetyVerdict>> + Send;
+}
+
+/// Dynamic trait for runtime polymorphism.
+/// Use this when you need `Arc<dyn MediaClassifierDyn>` for storing different classifiers.
+pub trait MediaClassifierDyn: Send + Sync + 'static {
+    fn name(&self) -> &str;
+
+    fn classify<'a>(
+        &'a self,
+        attachment: &'a crate::Attachment,
+        bytes: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<MediaSafetyVerdict>> + Send + 'a>>;
+}
+
+/// Blanket implementation: any type implementing MediaClassifier automatically implements MediaClassifierDyn.
+impl<T: MediaClassifier> MediaClassifierDyn for T {
+    fn name(&self) -> &str {
+        MediaClassifier::name(self)
+    }
+
+    fn classify<'a>(
+        &'a self,
+        attachment: &'a crate::Attachment,
+        bytes: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<MediaSafetyVerdict>> + Send + 'a>> {
+        Box::pin(MediaClassifier::classify(self, attachment, bytes))
+    }
+}
+
+/// Built-in classifier backed by an OpenAI-compatible moderation endpoint.
+pub struct ModerationClassifier {
+    http: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl ModerationClassifier {
+    pub fn new(http: reqwest::Client, config: &MediaSafetyConfig) -> Self {
+        Self {
+            http,
+            endpoint: config.endpoint.clone(),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+}
+
+impl MediaClassifier for ModerationClassifier {
+    fn name(&self) -> &str {
+        "moderation"
+    }
+
+    async fn classify(
+        &self,
+        attachment: &crate::Attachment,
+        bytes: &[u8],
+    ) -> anyhow::Result<MediaSafetyVerdict> {
+        use base64::Engine as _;
+        let data_url = format!(
+            "data:{};base64,{}",
+            attachment.mime_type,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        );
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": [{ "type": "image_url", "image_url": { "url": data_url } }],
+        });
+
+        let mut request = self.http.post(&self.endpoint).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            anyhow::bail!("moderation endpoint returned {status}: {text}");
+        }
+
+        let body: ModerationResponse = response.json().await?;
+        verdict_from_moderation(body)
+            .ok_or_else(|| anyhow::anyhow!("moderation response contained no results"))
+    }
+}
+
+#[derive(Deserialize)]
+struct ModerationResponse {
+    #[serde(default)]
+    results: Vec<ModerationResult>,
+}
+
+#[derive(Deserialize)]
+struct ModerationResult {
+    #[serde(default)]
+    categories: HashMap<String, bool>,
+    #[serde(default)]
+    category_scores: HashMap<String, f32>,
+}
+
+/// Collapse a moderation response into a single verdict: the highest
+/// category score and every flagged category, sorted for stable output.
+fn verdict_from_moderation(response: ModerationResponse) -> Option<MediaSafetyVerdict> {
+    let result = response.results.into_iter().next()?;
+    let score = result
+        .category_scores
+        .values()
+        .copied()
+        .fold(0.0_f32, f32::max);
+    let mut categories: Vec<String> = result
+        .categories
+        .into_iter()
+        .filter_map(|(category, flagged)| flagged.then_some(category))
+        .collect();
+    categories.sort();
+    Some(MediaSafetyVerdict { score, categories })
+}
+
+/// Safety report for one screened attachment, stored in message metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaSafetyReport {
+    pub filename: String,
+    pub classifier: String,
+    pub score: f32,
+    pub categories: Vec<String>,
+    pub blocked: bool,
+}
+
+/// Result of screening a message's attachments.
+#[derive(Debug, Default)]
+pub struct MediaScreening {
+    /// Attachments that may be passed on to the model.
+    pub allowed: Vec<crate::Attachment>,
+    /// One report per classified attachment, in input order.
+    pub reports: Vec<MediaSafetyReport>,
+    /// Bytes of allowed attachments that were downloaded for screening,
+    /// keyed by attachment URL.
+    pub downloaded: HashMap<String, Vec<u8>>,
+}
+
+impl MediaScreening {
+    fn pass_through(attachments: Vec<crate::Attachment>) -> Self {
+        Self {
+            allowed: attachments,
+            ..Self::default()
+        }
+    }
+
+    fn allow(&mut self, attachment: crate::Attachment, bytes: Option<Vec<u8>>) {
+        if let Some(bytes) = bytes {
+            self.downloaded.insert(attachment.url.clone(), bytes);
+        }
+        self.allowed.push(attachment);
+    }
+
+    /// Whether any attachment was blocked.
+    pub fn blocked(&self) -> bool {
+        self.reports.iter().any(|report| report.blocked)
+    }
+
+    /// Record the reports under `media_safety` in message metadata.
+    pub fn annotate(&self, metadata: &mut HashMap<String, serde_json::Value>) {
+        if self.reports.is_empty() {
+            return;
+        }
+        if let Ok(reports) = serde_json::to_value(&self.reports) {
+            metadata.insert(crate::metadata_keys::MEDIA_SAFETY.to_string(), reports);
+        }
+    }
+}
+
+/// Classify image attachments and drop the ones that should be blocked.
+///
+/// Returns the attachments unchanged when screening is disabled or the
+/// message has no images.
+pub(crate) async fn screen_attachments(
+    deps: &AgentDeps,
+    attachments: Vec<crate::Attachment>,
+) -> MediaScreening {
+    let config = deps.runtime_config.media_safety.load_full();
+    if !config.enabled || !attachments.iter().any(is_image) {
+        return MediaScreening::pass_through(attachments);
+    }
+
+    let http = deps.llm_manager.http_client();
+    let custom_classifier = deps.runtime_config.media_classifier.load_full();
+    let classifier: Arc<dyn MediaClassifierDyn> = match custom_classifier.as_ref() {
+        Some(custom) => custom.clone(),
+        None => Arc::new(ModerationClassifier::new(http.clone(), &config)),
+    };
+
+    let mut screening = MediaScreening::default();
+    for attachment in attachments {
+        if !is_image(&attachment) {
+            screening.allowed.push(attachment);
+            continue;
+        }
+
+        let bytes = download_attachment_bytes(http, &attachment).await;
+        let verdict = match &bytes {
+            Ok(bytes) => classifier
+                .classify(&attachment, bytes)
+                .await
+                .map_err(|error| error.to_string()),
+            Err(error) => Err(format!("download failed: {error}")),
+        };
+        let verdict = match verdict {
+            Ok(verdict) => verdict,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    filename = %attachment.filename,
+                    classifier = classifier.name(),
+                    "media safety check failed, passing attachment through"
+                );
+                screening.allow(attachment, bytes.ok());
+                continue;
+            }
+        };
+
+        let blocked = verdict.score >= config.block_threshold;
+        if blocked {
+            tracing::info!(
+                filename = %attachment.filename,
+                score = verdict.score,
+                categories = ?verdict.categories,
+                "blocked inbound attachment by media safety check"
+            );
+        }
+        screening.reports.push(MediaSafetyReport {
+            filename: attachment.filename.clone(),
+            classifier: classifier.name().to_string(),
+            score: verdict.score,
+            categories: verdict.categories,
+            blocked,
+        });
+        if !blocked {
+            screening.allow(attachment, bytes.ok());
+        }
+    }
+
+    screening
+}
+
+fn is_image(attachment: &crate::Attachment) -> bool {
+    IMAGE_MIME_PREFIXES
+        .iter()
+        .any(|prefix| attachment.mime_type.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdict_takes_max_score_and_flagged_categories() {
+        let response: ModerationResponse = serde_json::from_value(serde_json::json!({
+            "results": [{
+                "flagged": true,
+                "categories": { "violence": true, "sexual": false, "self-harm": true },
+                "category_scores": { "violence": 0.97, "sexual": 0.01, "self-harm": 0.4 }
+            }]
+        }))
+        .unwrap();
+
+        let verdict = verdict_from_moderation(response).unwrap();
+        assert_eq!(verdict.score, 0.97);
+        assert_eq!(verdict.categories, vec!["self-harm", "violence"]);
+    }
+
+    #[test]
+    fn verdict_requires_a_result() {
+        let response: ModerationResponse =
+            serde_json::from_value(serde_json::json!({ "results": [] })).unwrap();
+        assert!(verdict_from_moderation(response).is_none());
+    }
+
+    #[test]
+    fn allowed_attachments_keep_the_bytes_screening_downloaded() {
+        let attachment = |name: &str| crate::Attachment {
+            filename: name.into(),
+            mime_type: "image/png".into(),
+            url: format!("https://cdn.example.com/{name}"),
+            size_bytes: None,
+            auth_header: None,
+        };
+        let mut screening = MediaScreening::default();
+        screening.allow(attachment("cat.png"), Some(vec![1, 2, 3]));
+        screening.allow(attachment("dog.png"), None);
+
+        assert_eq!(screening.allowed.len(), 2);
+        assert_eq!(
+            screening.downloaded.get("https://cdn.example.com/cat.png"),
+            Some(&vec![1, 2, 3])
+        );
+        assert!(
+            !screening
+                .downloaded
+                .contains_key("https://cdn.example.com/dog.png")
+        );
+    }
+
+    #[test]
+    fn annotate_skips_empty_reports() {
+        let mut metadata = HashMap::new();
+        MediaScreening::default().annotate(&mut metadata);
+        assert!(metadata.is_empty());
+
+        let screening = MediaScreening {
+            reports: vec![MediaSafetyReport {
+                filename: "cat.png".into(),
+                classifier: "moderation".into(),
+                score: 0.95,
+                categories: vec!["violence".into()],
+                blocked: true,
+            }],
+            ..MediaScreening::default()
+        };
+        assert!(screening.blocked());
+        screening.annotate(&mut metadata);
+        assert_eq!(
+            metadata[crate::metadata_keys::MEDIA_SAFETY][0]["blocked"],
+            serde_json::json!(true)
+        );
+    }
+}
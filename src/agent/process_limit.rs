@@ -0,0 +1,275 @@
+//! Per-agent cap on concurrently running processes.
+//!
+//! Every channel turn, branch, worker, compaction, and cortex run acquires a
+//! permit before it starts calling the model and holds it until it finishes.
+//! The cap is read from `max_concurrent_processes` on every check, so a config
+//! reload takes effect for waiting processes as soon as a slot is released.
+//! `0` means unlimited.
+//!
+//! When the agent is saturated, background work (cortex bulletins, profile and
+//! knowledge synthesis) does not take a freed slot while user-facing work is
+//! waiting for one.
+
+use crate::AgentId;
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Scheduling class of a process competing for a slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessPriority {
+    /// Channel turns and the branches, workers, and compactions they spawn.
+    Foreground,
+    /// Cortex maintenance runs. Yields to waiting foreground processes.
+    Background,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    active: usize,
+    foreground_waiting: usize,
+}
+
+/// Shared counter of running processes for one agent.
+#[derive(Debug)]
+pub struct ProcessLimiter {
+    agent_id: AgentId,
+    state: Mutex<LimiterState>,
+    released: Notify,
+}
+
+impl ProcessLimiter {
+    pub fn new(agent_id: AgentId) -> Self {
+        Self {
+            agent_id,
+            state: Mutex::new(LimiterState::default()),
+            released: Notify::new(),
+        }
+    }
+
+    /// Number of processes currently holding a permit.
+    pub fn active(&self) -> usize {
+        self.lock_state().active
+    }
+
+    /// Wait until a slot is free under the cap returned by `limit`.
+    pub async fn acquire(
+        self: &Arc<Self>,
+        limit: impl Fn() -> usize,
+        priority: ProcessPriority,
+    ) -> ProcessPermit {
+        let mut waiting: Option<ForegroundWaiting> = None;
+        let mut logged = false;
+
+        loop {
+            let released = self.released.notified();
+            tokio::pin!(released);
+            // Register before checking so a release between the check and the
+            // await is not missed.
+            released.as_mut().enable();
+
+            {
+                let mut state = self.lock_state();
+                let limit = limit();
+                let has_slot = limit == 0 || state.active < limit;
+                let yields =
+                    priority == ProcessPriority::Background && state.foreground_waiting > 0;
+
+                if has_slot && !yields {
+                    state.active += 1;
+                    let active = state.active;
+                    if let Some(mut waiting) = waiting.take() {
+                        waiting.armed = false;
+                        state.foreground_waiting -= 1;
+                        if state.foreground_waiting == 0 {
+                            self.released.notify_waiters();
+                        }
+                    }
+                    drop(state);
+                    self.record_active(active);
+                    return ProcessPermit {
+                        limiter: self.clone(),
+                    };
+                }
+
+                if priority == ProcessPriority::Foreground && waiting.is_none() {
+                    state.foreground_waiting += 1;
+                    waiting = Some(ForegroundWaiting {
+                        limiter: self.clone(),
+                        armed: true,
+                    });
+                }
+
+                if !logged {
+                    tracing::debug!(
+                        agent_id = %self.agent_id,
+                        active = state.active,
+                        limit,
+                        ?priority,
+                        "process waiting for a concurrency slot"
+                    );
+                    logged = true;
+                }
+            }
+
+            released.await;
+        }
+    }
+
+    fn release(&self) {
+        let active = {
+            let mut state = self.lock_state();
+            state.active = state.active.saturating_sub(1);
+            state.active
+        };
+        self.record_active(active);
+        self.released.notify_waiters();
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record_active(&self, active: usize) {
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Metrics::global()
+            .active_processes
+            .with_label_values(&[&*self.agent_id])
+            .set(active as i64);
+    }
+}
+
+/// A held slot. Released when dropped.
+#[derive(Debug)]
+pub struct ProcessPermit {
+    limiter: Arc<ProcessLimiter>,
+}
+
+impl Drop for ProcessPermit {
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+/// Keeps a foreground waiter counted until it gets a slot or is cancelled.
+struct ForegroundWaiting {
+    limiter: Arc<ProcessLimiter>,
+    armed: bool,
+}
+
+impl Drop for ForegroundWaiting {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let none_waiting = {
+            let mut state = self.limiter.lock_state();
+            state.foreground_waiting = state.foreground_waiting.saturating_sub(1);
+            state.foreground_waiting == 0
+        };
+        if none_waiting {
+            self.limiter.released.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn limiter() -> Arc<ProcessLimiter> {
+        Arc::new(ProcessLimiter::new(Arc::from("agent")))
+    }
+
+    #[tokio::test]
+    async fn zero_limit_is_unlimited() {
+        let limiter = limiter();
+        let mut permits = Vec::new();
+        for _ in 0..5 {
+            permits.push(limiter.acquire(|| 0, ProcessPriority::Background).await);
+        }
+        assert_eq!(limiter.active(), 5);
+        drop(permits);
+        assert_eq!(limiter.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn waits_for_a_released_slot() {
+        let limiter = limiter();
+        let held = limiter.acquire(|| 1, ProcessPriority::Foreground).await;
+
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                let _permit = limiter.acquire(|| 1, ProcessPriority::Foreground).await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(held);
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should get the released slot")
+            .expect("waiter task should not panic");
+        assert_eq!(limiter.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn background_yields_to_waiting_foreground() {
+        let limiter = limiter();
+        let held = limiter.acquire(|| 1, ProcessPriority::Foreground).await;
+
+        let background = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(|| 1, ProcessPriority::Background).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let foreground = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(|| 1, ProcessPriority::Foreground).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        drop(held);
+        let foreground_permit = tokio::time::timeout(Duration::from_secs(1), foreground)
+            .await
+            .expect("foreground should take the freed slot")
+            .expect("foreground task should not panic");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!background.is_finished());
+
+        drop(foreground_permit);
+        let _background_permit = tokio::time::timeout(Duration::from_secs(1), background)
+            .await
+            .expect("background should run once foreground is done")
+            .expect("background task should not panic");
+        assert_eq!(limiter.active(), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_foreground_waiter_unblocks_background() {
+        let limiter = limiter();
+        let held = limiter.acquire(|| 1, ProcessPriority::Foreground).await;
+
+        let foreground = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(|| 1, ProcessPriority::Foreground).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        foreground.abort();
+        let _ = foreground.await;
+
+        drop(held);
+        let _permit = tokio::time::timeout(
+            Duration::from_secs(1),
+            limiter.acquire(|| 1, ProcessPriority::Background),
+        )
+        .await
+        .expect("background should not wait on a cancelled foreground waiter");
+    }
+}
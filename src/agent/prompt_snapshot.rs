@@ -30,6 +30,14 @@ pub struct PromptSnapshot {
     pub history: serde_json::Value,
     /// Number of messages in the history.
     pub history_length: usize,
+    /// Model the turn was routed to. Absent on snapshots captured before
+    /// this field existed.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Tool definitions sent with the turn's request. Empty on snapshots
+    /// captured before this field existed.
+    #[serde(default)]
+    pub tools: Vec<rig::completion::ToolDefinition>,
 }
 
 /// Summary of a snapshot for listing (without the full content).
@@ -176,6 +184,14 @@ impl PromptSnapshotStore {
         Ok(summaries)
     }
 
+    /// Retrieve the most recent snapshot for a channel.
+    pub fn latest(&self, channel_id: &str) -> crate::error::Result<Option<PromptSnapshot>> {
+        let Some(summary) = self.list(channel_id, 1)?.into_iter().next() else {
+            return Ok(None);
+        };
+        self.get(channel_id, summary.timestamp_ms)
+    }
+
     /// Retrieve a specific snapshot.
     pub fn get(
         &self,
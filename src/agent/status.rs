@@ -138,6 +138,8 @@ pub struct StatusBlock {
     pub completed_items: Vec<CompletedItem>,
     /// Active link conversations with other agents.
     pub active_link_conversations: Vec<LinkConversationStatus>,
+    /// Context compaction currently running on this channel, if any.
+    pub compaction: Option<CompactionStatus>,
 }
 
 /// Status of an active branch.
@@ -183,6 +185,13 @@ pub struct LinkConversationStatus {
     pub turn_count: u32,
 }
 
+/// Status of a running context compaction.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CompactionStatus {
+    pub started_at: DateTime<Utc>,
+    pub status: String,
+}
+
 /// Type of completed item.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub enum CompletedItemType {
@@ -263,6 +272,20 @@ impl StatusBlock {
             ProcessEvent::AgentMessageSent { to_agent_id, .. } => {
                 self.track_link_conversation(to_agent_id.as_ref());
             }
+            ProcessEvent::CompactionTriggered { .. } => {
+                self.compaction = Some(CompactionStatus {
+                    started_at: Utc::now(),
+                    status: "starting".to_string(),
+                });
+            }
+            ProcessEvent::CompactionProgress { status, .. } => {
+                if let Some(compaction) = &mut self.compaction {
+                    compaction.status.clone_from(status);
+                }
+            }
+            ProcessEvent::CompactionCompleted { .. } => {
+                self.compaction = None;
+            }
             _ => {}
         }
 
@@ -411,6 +434,15 @@ impl StatusBlock {
             output.push('\n');
         }
 
+        if let Some(compaction) = &self.compaction {
+            output.push_str("## Context Compaction\n");
+            output.push_str(&format!(
+                "- Compacting older history (started {}): {}\n\n",
+                compaction.started_at.format("%H:%M:%S"),
+                compaction.status
+            ));
+        }
+
         // Active link conversations
         if !self.active_link_conversations.is_empty() {
             output.push_str("## Active Link Conversations\n");
@@ -630,6 +662,37 @@ mod tests {
         assert!(rendered.contains("Current date/time: 2026-02-26 12:00:00 UTC"));
     }
 
+    #[test]
+    fn compaction_events_show_and_clear_progress() {
+        let mut status = StatusBlock::new();
+        let agent_id: crate::AgentId = std::sync::Arc::from("agent");
+        let channel_id: crate::ChannelId = std::sync::Arc::from("channel");
+
+        status.update(&crate::ProcessEvent::CompactionTriggered {
+            agent_id: agent_id.clone(),
+            channel_id: channel_id.clone(),
+            threshold_reached: 0.82,
+            reason: crate::CompactionTrigger::TurnCount,
+        });
+        status.update(&crate::ProcessEvent::CompactionProgress {
+            agent_id: agent_id.clone(),
+            channel_id: channel_id.clone(),
+            status: "summarizing 40 messages".to_string(),
+        });
+        assert!(status.render().contains("summarizing 40 messages"));
+
+        status.update(&crate::ProcessEvent::CompactionCompleted {
+            agent_id,
+            channel_id,
+            turns_compacted: 40,
+            summary_tokens: 900,
+            summary_max_tokens: 1_000,
+            context_tokens: 30_000,
+        });
+        assert!(status.compaction.is_none());
+        assert!(!status.render().contains("Context Compaction"));
+    }
+
     #[test]
     fn remove_branch_removes_existing_branch() {
         let mut status = StatusBlock::new();
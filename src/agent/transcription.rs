@@ -0,0 +1,217 @@
+//! Speech-to-text for inbound voice notes.
+//!
+//! When `transcription.enabled` is set, audio attachments are uploaded to an
+//! OpenAI-compatible `/v1/audio/transcriptions` endpoint as soon as the channel
+//! picks up the message. The transcript is written into the message text, so
+//! history, listen-only checks, and the model all see what was said. The audio
+//! stays attached to the message but is not sent to the `voice` routing model
+//! a second time.
+//!
+//! Transcription fails open: if an attachment can't be downloaded or
+//! transcribed, it is left for the `voice` routing fallback and the error is
+//! logged.
+
+use crate::AgentDeps;
+use crate::agent::channel_attachments::{audio_format_for_attachment, download_attachment_bytes};
+use crate::config::TranscriptionConfig;
+
+use serde::Deserialize;
+use std::collections::HashSet;
+
+/// Upload limit enforced by OpenAI's transcription endpoint.
+const MAX_AUDIO_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Audio attachments whose transcript is already in the message text.
+#[derive(Debug, Default)]
+pub struct VoiceTranscripts {
+    urls: HashSet<String>,
+}
+
+impl VoiceTranscripts {
+    /// Whether `attachment` was transcribed.
+    pub fn covers(&self, attachment: &crate::Attachment) -> bool {
+        self.urls.contains(&attachment.url)
+    }
+
+    /// The attachments that still need to be shown to the model.
+    pub fn untranscribed(&self, attachments: &[crate::Attachment]) -> Vec<crate::Attachment> {
+        attachments
+            .iter()
+            .filter(|attachment| !self.covers(attachment))
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    #[serde(default)]
+    text: String,
+}
+
+/// Transcribe the audio attachments of `message` into its text.
+///
+/// Does nothing when transcription is disabled or the message carries no
+/// audio. Transcribed attachments are recorded in `transcripts`.
+pub(crate) async fn transcribe_message(
+    deps: &AgentDeps,
+    message: &mut crate::InboundMessage,
+    transcripts: &mut VoiceTranscripts,
+) {
+    let config = deps.runtime_config.transcription.load_full();
+    if !config.enabled {
+        return;
+    }
+    let crate::MessageContent::Media { text, attachments } = &mut message.content else {
+        return;
+    };
+    if !attachments.iter().any(is_audio) {
+        return;
+    }
+
+    let http = deps.llm_manager.http_client();
+    let mut spoken = Vec::new();
+    for attachment in attachments.iter().filter(|attachment| is_audio(attachment)) {
+        match transcribe_attachment(http, &config, attachment).await {
+            Ok(transcript) => {
+                transcripts.urls.insert(attachment.url.clone());
+                let transcript = transcript.trim();
+                if !transcript.is_empty() {
+                    spoken.push(transcript.to_string());
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    filename = %attachment.filename,
+                    "voice transcription failed, falling back to the voice model"
+                );
+            }
+        }
+    }
+
+    if !spoken.is_empty() {
+        *text = Some(merge_transcripts(text.as_deref(), &spoken));
+    }
+}
+
+async fn transcribe_attachment(
+    http: &reqwest::Client,
+    config: &TranscriptionConfig,
+    attachment: &crate::Attachment,
+) -> anyhow::Result<String> {
+    if attachment
+        .size_bytes
+        .is_some_and(|size| size > MAX_AUDIO_BYTES)
+    {
+        anyhow::bail!("audio exceeds the 25 MB upload limit");
+    }
+
+    let bytes = download_attachment_bytes(http, attachment)
+        .await
+        .map_err(|error| anyhow::anyhow!("download failed: {error}"))?;
+    let file = reqwest::multipart::Part::bytes(bytes)
+        .file_name(upload_filename(attachment))
+        .mime_str(&attachment.mime_type)?;
+    let mut form = reqwest::multipart::Form::new()
+        .text("model", config.model.clone())
+        .text("response_format", "json")
+        .part("file", file);
+    if let Some(language) = &config.language {
+        form = form.text("language", language.clone());
+    }
+
+    let mut request = http.post(&config.endpoint).multipart(form);
+    if let Some(api_key) = &config.api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        anyhow::bail!("transcription endpoint returned {status}: {text}");
+    }
+
+    let body: TranscriptionResponse = response.json().await?;
+    Ok(body.text)
+}
+
+/// Providers infer the audio format from the file extension, and voice notes
+/// often arrive without one (Telegram sends bare `voice` files).
+fn upload_filename(attachment: &crate::Attachment) -> String {
+    if attachment.filename.contains('.') {
+        attachment.filename.clone()
+    } else {
+        format!(
+            "{}.{}",
+            attachment.filename,
+            audio_format_for_attachment(attachment)
+        )
+    }
+}
+
+/// Append transcripts to any text the sender typed alongside the audio.
+fn merge_transcripts(text: Option<&str>, spoken: &[String]) -> String {
+    let transcript = spoken.join("\n\n");
+    match text.map(str::trim) {
+        Some(text) if !text.is_empty() => format!("{text}\n\n{transcript}"),
+        _ => transcript,
+    }
+}
+
+fn is_audio(attachment: &crate::Attachment) -> bool {
+    attachment.mime_type.starts_with("audio/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(filename: &str, mime_type: &str) -> crate::Attachment {
+        crate::Attachment {
+            filename: filename.into(),
+            mime_type: mime_type.into(),
+            url: format!("https://example.com/{filename}"),
+            size_bytes: None,
+            auth_header: None,
+        }
+    }
+
+    #[test]
+    fn upload_filename_adds_missing_extension() {
+        assert_eq!(
+            upload_filename(&attachment("voice", "audio/ogg")),
+            "voice.ogg"
+        );
+        assert_eq!(
+            upload_filename(&attachment("memo.m4a", "audio/mp4")),
+            "memo.m4a"
+        );
+    }
+
+    #[test]
+    fn transcripts_follow_typed_text() {
+        let spoken = vec!["first note".to_string(), "second note".to_string()];
+        assert_eq!(
+            merge_transcripts(None, &spoken),
+            "first note\n\nsecond note"
+        );
+        assert_eq!(merge_transcripts(Some("  "), &spoken[..1]), "first note");
+        assert_eq!(
+            merge_transcripts(Some("listen to this"), &spoken[..1]),
+            "listen to this\n\nfirst note"
+        );
+    }
+
+    #[test]
+    fn untranscribed_keeps_other_attachments() {
+        let voice = attachment("voice.ogg", "audio/ogg");
+        let image = attachment("cat.png", "image/png");
+        let mut transcripts = VoiceTranscripts::default();
+        transcripts.urls.insert(voice.url.clone());
+
+        let remaining = transcripts.untranscribed(&[voice, image]);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].filename, "cat.png");
+    }
+}
@@ -1,6 +1,7 @@
 //! Worker: Independent task execution process.
 
 use crate::agent::compactor::estimate_history_tokens;
+use crate::agent::process_limit::ProcessPriority;
 use crate::config::BrowserConfig;
 use crate::error::Result;
 use crate::hooks::SpacebotHook;
@@ -90,6 +91,9 @@ pub struct Worker {
     pub status_rx: watch::Receiver<String>,
     /// Prior conversation history for resumed workers (set by `resume_interactive`).
     pub prior_history: Option<Vec<rig::message::Message>>,
+    /// Flipped to true when the worker is cancelled, so a retry backoff ends
+    /// at once instead of sleeping it out.
+    pub cancel_rx: Option<watch::Receiver<bool>>,
 }
 
 impl Worker {
@@ -113,7 +117,15 @@ impl Worker {
             ProcessType::Worker,
             channel_id.clone(),
             deps.event_tx.clone(),
-        );
+        )
+        .with_disabled_tools(deps.runtime_config.disabled_tools.load_full())
+        .with_output_token_cap(
+            deps.runtime_config
+                .routing
+                .load()
+                .max_output_tokens(ProcessType::Worker),
+        )
+        .with_tool_call_limits(**deps.runtime_config.tool_call_limits.load());
         let (status_tx, status_rx) = watch::channel("starting".to_string());
         let (inject_tx, inject_rx) = mpsc::channel(8);
 
@@ -135,6 +147,7 @@ impl Worker {
                 status_tx,
                 status_rx,
                 prior_history: None,
+                cancel_rx: None,
             },
             inject_tx,
         )
@@ -169,6 +182,12 @@ impl Worker {
         )
     }
 
+    /// Watch `cancel_rx` for cancellation while waiting to retry.
+    pub fn with_cancel_signal(mut self, cancel_rx: watch::Receiver<bool>) -> Self {
+        self.cancel_rx = Some(cancel_rx);
+        self
+    }
+
     /// Create a new interactive worker.
     ///
     /// Returns `(worker, input_tx, inject_tx)`. The `input_tx` drives the
@@ -242,7 +261,17 @@ impl Worker {
             ProcessType::Worker,
             worker.channel_id.clone(),
             worker.deps.event_tx.clone(),
-        );
+        )
+        .with_disabled_tools(worker.deps.runtime_config.disabled_tools.load_full())
+        .with_output_token_cap(
+            worker
+                .deps
+                .runtime_config
+                .routing
+                .load()
+                .max_output_tokens(ProcessType::Worker),
+        )
+        .with_tool_call_limits(**worker.deps.runtime_config.tool_call_limits.load());
         worker.state = WorkerState::WaitingForInput;
         // Stash the prior history so `run_follow_up_loop()` can pick it up.
         worker.prior_history = Some(prior_history);
@@ -290,6 +319,20 @@ impl Worker {
             self.hook = self.hook.clone().with_inject_rx(inject_rx);
         }
 
+        // A process slot is held while the initial task runs. Interactive
+        // workers give it up while waiting for input and take one again for
+        // each follow-up, so idle workers don't count against the limit.
+        let resuming = self.prior_history.is_some();
+        let permit = if resuming {
+            None
+        } else {
+            Some(
+                self.deps
+                    .acquire_process_permit(ProcessPriority::Foreground)
+                    .await,
+            )
+        };
+
         self.status_tx.send_modify(|s| *s = "running".to_string());
         self.hook.send_status("running");
 
@@ -312,13 +355,15 @@ impl Worker {
             mcp_tools,
             self.deps.runtime_config.clone(),
         );
+        self.hook.set_tool_server(worker_tool_server.clone());
 
         let routing = self.deps.runtime_config.routing.load();
         let model_name = routing.resolve(ProcessType::Worker, None).to_string();
         let model = SpacebotModel::make(&self.deps.llm_manager, &model_name)
             .with_context(&*self.deps.agent_id, "worker")
             .with_worker_type("builtin")
-            .with_routing((**routing).clone());
+            .with_routing((**routing).clone())
+            .with_turn_budget(self.hook.turn_budget());
 
         let agent = AgentBuilder::new(model)
             .preamble(&self.system_prompt)
@@ -329,7 +374,6 @@ impl Worker {
         // If this is a resumed worker, load the prior history into `history`
         // (not `compacted_history`) so the LLM sees it as conversation context
         // on the next follow-up call.
-        let resuming = self.prior_history.is_some();
         let mut history = self.prior_history.take().unwrap_or_default();
         let mut compacted_history = Vec::new();
 
@@ -349,6 +393,7 @@ impl Worker {
         let mut segments_run = 0;
         let mut overflow_retries = 0;
         let mut transient_retries = 0;
+        let mut worker_retries = 0;
 
         let mut result = if resuming {
             // For resumed workers, synthesize a "result" from the task
@@ -448,6 +493,21 @@ impl Worker {
                     Err(error) if is_retriable_error(&error.to_string()) => {
                         transient_retries += 1;
                         if transient_retries > MAX_TRANSIENT_RETRIES {
+                            if let Some(retry_prompt) = self
+                                .prepare_retry(
+                                    &error.to_string(),
+                                    &mut worker_retries,
+                                    &mut compacted_history,
+                                    &mut history,
+                                )
+                                .await?
+                            {
+                                prompt = retry_prompt;
+                                overflow_retries = 0;
+                                transient_retries = 0;
+                                continue;
+                            }
+
                             self.state = WorkerState::Failed;
                             self.hook.send_status("failed");
                             self.write_failure_log(&history, &format!(
@@ -483,6 +543,21 @@ impl Worker {
                         // nothing to "continue" from.
                     }
                     Err(error) => {
+                        if let Some(retry_prompt) = self
+                            .prepare_retry(
+                                &error.to_string(),
+                                &mut worker_retries,
+                                &mut compacted_history,
+                                &mut history,
+                            )
+                            .await?
+                        {
+                            prompt = retry_prompt;
+                            overflow_retries = 0;
+                            transient_retries = 0;
+                            continue;
+                        }
+
                         self.state = WorkerState::Failed;
                         self.hook.send_status("failed");
                         self.write_failure_log(&history, &error.to_string());
@@ -522,6 +597,8 @@ impl Worker {
             }
         }
 
+        drop(permit);
+
         // For interactive workers, enter a follow-up loop
         let mut follow_up_failure: Option<String> = None;
         if let Some(mut input_rx) = self.input_rx.take() {
@@ -535,6 +612,10 @@ impl Worker {
             }
 
             while let Some(follow_up) = input_rx.recv().await {
+                let _permit = self
+                    .deps
+                    .acquire_process_permit(ProcessPriority::Foreground)
+                    .await;
                 self.state = WorkerState::Running;
                 self.hook.send_status("processing follow-up");
 
@@ -688,6 +769,74 @@ impl Worker {
         Ok(result)
     }
 
+    /// Apply the agent's worker retry policy to a failed initial run.
+    ///
+    /// Returns the prompt for the next attempt, or `None` when the failure
+    /// isn't retryable or the retries are used up. Before returning a prompt
+    /// this reports the attempt, waits out the backoff, and clears the
+    /// history unless the policy preserves context. Errors with
+    /// [`AgentError::Cancelled`] if the worker is cancelled during the backoff.
+    ///
+    /// [`AgentError::Cancelled`]: crate::error::AgentError::Cancelled
+    async fn prepare_retry(
+        &mut self,
+        error: &str,
+        attempts: &mut u32,
+        compacted_history: &mut Vec<rig::message::Message>,
+        history: &mut Vec<rig::message::Message>,
+    ) -> Result<Option<String>> {
+        let policy = **self.deps.runtime_config.worker_retry.load();
+        if *attempts >= policy.max_retries || !is_retryable_worker_failure(error) {
+            return Ok(None);
+        }
+        *attempts += 1;
+
+        let delay = policy.backoff(*attempts);
+        tracing::warn!(
+            worker_id = %self.id,
+            attempt = *attempts,
+            max_retries = policy.max_retries,
+            delay_secs = delay.as_secs(),
+            preserve_context = policy.preserve_context,
+            %error,
+            "worker failed with a retryable error, retrying"
+        );
+        self.hook
+            .send_status(format!("retrying ({}/{})", attempts, policy.max_retries));
+
+        let cancelled = async {
+            match self.cancel_rx.as_mut() {
+                Some(cancel_rx) if cancel_rx.wait_for(|cancelled| *cancelled).await.is_ok() => {}
+                // No signal, or the sender is gone without cancelling.
+                _ => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = cancelled => {
+                let reason = "cancelled while waiting to retry".to_string();
+                self.state = WorkerState::Failed;
+                self.hook.send_status("cancelled");
+                self.write_failure_log(history, &reason);
+                self.persist_transcript(compacted_history, history).await;
+                tracing::info!(worker_id = %self.id, "worker cancelled during retry backoff");
+                return Err(crate::error::AgentError::Cancelled { reason }.into());
+            }
+        }
+
+        if policy.preserve_context {
+            Ok(Some(
+                "Continue where you left off. Your previous attempt failed with a \
+                 transient error, so retry the step that failed. Do not repeat completed work."
+                    .into(),
+            ))
+        } else {
+            compacted_history.clear();
+            history.clear();
+            Ok(Some(self.task.clone()))
+        }
+    }
+
     /// Check context usage and compact history if approaching the limit.
     ///
     /// Workers don't have a full Compactor instance — they do inline compaction
@@ -1011,6 +1160,53 @@ impl Worker {
 /// always has the latest snapshot but doesn't waste context on stale ones.
 const DEDUP_TOOL_RESULTS: &[&str] = &["browser_snapshot", "browser_tab_list"];
 
+/// Whether a worker failure is worth re-running the worker for.
+///
+/// Provider errors are judged by their HTTP status: rate limits, timeouts,
+/// and server errors are transient, while any other 4xx (bad auth, missing
+/// permissions, invalid requests) would fail the same way again. Failures
+/// without a status are transient when the provider couldn't be reached or
+/// the connection broke, and permanent when access was denied locally.
+fn is_retryable_worker_failure(error: &str) -> bool {
+    if let Some(status) = provider_error_status(error) {
+        return status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::REQUEST_TIMEOUT
+            || status.is_server_error();
+    }
+
+    let lower = error.to_lowercase();
+    let permanent = ["permission denied", "not permitted", "access denied"];
+    if permanent.iter().any(|marker| lower.contains(marker)) {
+        return false;
+    }
+
+    let transient = [
+        "connection",
+        "error sending request",
+        "network",
+        "timed out",
+        "timeout",
+        "dns",
+        "broken pipe",
+        "reset by peer",
+        "temporarily unavailable",
+        "unreachable",
+        "overloaded",
+        "empty response",
+        "failed to read response body",
+        "error decoding response body",
+    ];
+    transient.iter().any(|marker| lower.contains(marker))
+}
+
+/// The HTTP status of a provider error. Providers report failed requests as
+/// `... API error (<status> <reason>...`.
+fn provider_error_status(error: &str) -> Option<reqwest::StatusCode> {
+    let (_, rest) = error.split_once("API error (")?;
+    let code = rest.get(..3)?;
+    reqwest::StatusCode::from_bytes(code.as_bytes()).ok()
+}
+
 /// Replace all but the most recent result for each tool in `DEDUP_TOOL_RESULTS`
 /// with a short placeholder. This dramatically reduces context usage for
 /// browser-heavy workflows where `browser_snapshot` returns large ARIA trees
@@ -1139,3 +1335,53 @@ fn build_worker_recap(messages: &[rig::message::Message]) -> String {
         recap
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_worker_failures_are_retryable() {
+        assert!(is_retryable_worker_failure(
+            "error sending request: connection reset by peer"
+        ));
+        assert!(is_retryable_worker_failure(
+            "dns error: failed to lookup host"
+        ));
+        assert!(is_retryable_worker_failure("operation timed out"));
+        assert!(is_retryable_worker_failure(
+            "CompletionError: ProviderError: Anthropic API error (503 Service Unavailable): busy"
+        ));
+        assert!(is_retryable_worker_failure(
+            "OpenAI API error (429 Too Many Requests: slow down) [retry-after=5s]"
+        ));
+        // Numbers in the message text don't decide anything.
+        assert!(is_retryable_worker_failure(
+            "connection reset while fetching issue #401"
+        ));
+    }
+
+    #[test]
+    fn permission_and_auth_failures_are_not_retryable() {
+        assert!(!is_retryable_worker_failure(
+            "shell: permission denied (os error 13)"
+        ));
+        assert!(!is_retryable_worker_failure(
+            "Anthropic API error (401 Unauthorized): invalid x-api-key"
+        ));
+        assert!(!is_retryable_worker_failure(
+            "OpenRouter API error (403 Forbidden: connection to this model is not allowed)"
+        ));
+        assert!(!is_retryable_worker_failure(
+            "OpenAI API error (400 Bad Request: the request timed out upstream)"
+        ));
+    }
+
+    #[test]
+    fn task_errors_are_not_retryable() {
+        assert!(!is_retryable_worker_failure("file not found: src/main.rs"));
+        assert!(!is_retryable_worker_failure(
+            "worker exceeded its max turns without finishing"
+        ));
+    }
+}
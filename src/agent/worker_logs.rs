@@ -0,0 +1,242 @@
+//! Worker log retention.
+//!
+//! Workers write `worker_<id>_<timestamp>.log` files according to
+//! `worker_log_mode`. Retention is opt-in: when `worker_log_retention_days` is
+//! set, a background loop per agent prunes logs older than that, and when
+//! `worker_log_max_total_mb` is set, it deletes the oldest logs until the
+//! total fits under the cap. Only worker log files are ever touched.
+
+use crate::AgentDeps;
+use crate::config::WorkerLogRetention;
+
+use serde::Serialize;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often the cleanup loop runs.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Disk usage of an agent's worker logs.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkerLogUsage {
+    pub total_bytes: u64,
+    pub file_count: usize,
+    pub oldest: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// What a cleanup pass removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CleanupReport {
+    pub removed_files: usize,
+    pub removed_bytes: u64,
+}
+
+struct LogFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Directories an agent's workers write logs to: the agent data directory
+/// (channel and cron workers) and the workspace (cortex and detached workers).
+pub fn worker_log_dirs(data_dir: &Path, workspace: &Path) -> Vec<PathBuf> {
+    vec![
+        data_dir.join("logs"),
+        workspace.join(".spacebot").join("logs"),
+    ]
+}
+
+/// Total size, count, and age of the worker logs in `dirs`.
+pub fn usage(dirs: &[PathBuf]) -> WorkerLogUsage {
+    let files = collect_log_files(dirs);
+    WorkerLogUsage {
+        total_bytes: files.iter().map(|file| file.size).sum(),
+        file_count: files.len(),
+        oldest: files
+            .iter()
+            .map(|file| file.modified)
+            .min()
+            .map(chrono::DateTime::<chrono::Utc>::from),
+    }
+}
+
+/// Delete worker logs that violate the retention policy, oldest first.
+pub fn prune(dirs: &[PathBuf], retention: &WorkerLogRetention, now: SystemTime) -> CleanupReport {
+    let mut files = collect_log_files(dirs);
+    files.sort_by_key(|file| file.modified);
+
+    let cutoff = retention
+        .max_age_days
+        .and_then(|days| now.checked_sub(Duration::from_secs(days.saturating_mul(86_400))));
+    let mut total_bytes: u64 = files.iter().map(|file| file.size).sum();
+    let mut report = CleanupReport::default();
+
+    for file in files {
+        let expired = cutoff.is_some_and(|cutoff| file.modified < cutoff);
+        let over_cap = retention
+            .max_total_bytes
+            .is_some_and(|max_bytes| total_bytes > max_bytes);
+        // Files are sorted oldest first, so nothing after this one qualifies.
+        if !expired && !over_cap {
+            break;
+        }
+        match std::fs::remove_file(&file.path) {
+            Ok(()) => {
+                total_bytes -= file.size;
+                report.removed_files += 1;
+                report.removed_bytes += file.size;
+            }
+            Err(error) => {
+                tracing::warn!(%error, path = %file.path.display(), "failed to remove worker log");
+            }
+        }
+    }
+
+    report
+}
+
+/// Spawn the periodic cleanup loop for an agent's worker logs.
+pub fn spawn_worker_log_cleanup_loop(
+    log_dirs: Vec<PathBuf>,
+    deps: AgentDeps,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let retention = **deps.runtime_config.worker_log_retention.load();
+            if retention.is_enabled() {
+                let dirs = log_dirs.clone();
+                match tokio::task::spawn_blocking(move || {
+                    prune(&dirs, &retention, SystemTime::now())
+                })
+                .await
+                {
+                    Ok(report) if report.removed_files > 0 => {
+                        tracing::info!(
+                            agent_id = %deps.agent_id,
+                            removed_files = report.removed_files,
+                            removed_bytes = report.removed_bytes,
+                            "pruned worker logs"
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        tracing::warn!(%error, agent_id = %deps.agent_id, "worker log cleanup failed");
+                    }
+                }
+            }
+            tokio::time::sleep(CLEANUP_INTERVAL).await;
+        }
+    })
+}
+
+/// Collect worker log files under `dirs`, including the `successful/` and
+/// `failed/` subdirectories used by `all_separate` mode.
+fn collect_log_files(dirs: &[PathBuf]) -> Vec<LogFile> {
+    let mut files = Vec::new();
+    let mut pending: Vec<PathBuf> = dirs.to_vec();
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let path = entry.path();
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if metadata.is_file() && is_worker_log(&path) {
+                files.push(LogFile {
+                    path,
+                    size: metadata.len(),
+                    modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+    }
+    files
+}
+
+fn is_worker_log(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("worker_") && name.ends_with(".log"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_logs_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "spacebot-worker-logs-{name}-{}",
+            uuid::Uuid::new_v4()
+        ))
+    }
+
+    fn write_log(dir: &Path, name: &str, bytes: usize, age: Duration, now: SystemTime) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, vec![b'x'; bytes]).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(now - age).unwrap();
+        path
+    }
+
+    #[test]
+    fn prune_removes_expired_logs_and_leaves_other_files() {
+        let root = temp_logs_dir("age");
+        let now = SystemTime::now();
+        let day = Duration::from_secs(86_400);
+        let old = write_log(&root.join("failed"), "worker_a_1.log", 10, day * 40, now);
+        let recent = write_log(&root, "worker_b_2.log", 10, day, now);
+        let unrelated = write_log(&root, "api_access.jsonl", 10, day * 40, now);
+
+        let retention = WorkerLogRetention {
+            max_age_days: Some(30),
+            max_total_bytes: None,
+        };
+        let report = prune(std::slice::from_ref(&root), &retention, now);
+
+        assert_eq!(
+            report,
+            CleanupReport {
+                removed_files: 1,
+                removed_bytes: 10
+            }
+        );
+        assert!(!old.exists());
+        assert!(recent.exists());
+        assert!(unrelated.exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn prune_enforces_total_size_oldest_first() {
+        let root = temp_logs_dir("size");
+        let now = SystemTime::now();
+        let hour = Duration::from_secs(3600);
+        let oldest = write_log(&root, "worker_a_1.log", 100, hour * 3, now);
+        let middle = write_log(&root, "worker_b_2.log", 100, hour * 2, now);
+        let newest = write_log(&root, "worker_c_3.log", 100, hour, now);
+
+        let retention = WorkerLogRetention {
+            max_age_days: None,
+            max_total_bytes: Some(250),
+        };
+        let report = prune(std::slice::from_ref(&root), &retention, now);
+
+        assert_eq!(report.removed_files, 1);
+        assert!(!oldest.exists());
+        assert!(middle.exists());
+        assert!(newest.exists());
+
+        let usage = usage(std::slice::from_ref(&root));
+        assert_eq!(usage.total_bytes, 200);
+        assert_eq!(usage.file_count, 2);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
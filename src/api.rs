@@ -4,20 +4,26 @@
 //! managing agents, viewing status, and interacting with the system.
 //! Includes an SSE endpoint for realtime event streaming.
 
+pub mod access_log;
 pub mod agents;
 mod bindings;
+mod broadcast;
+mod bundles;
 mod channels;
 mod config;
 mod cortex;
 mod cron;
 mod factory;
+mod handoffs;
 mod ingest;
 mod links;
 mod mcp;
 mod memories;
 mod messaging;
 mod models;
+mod notes;
 mod opencode_proxy;
+mod processes;
 mod projects;
 mod providers;
 mod secrets;
@@ -28,6 +34,7 @@ pub(crate) mod ssh;
 mod state;
 mod system;
 mod tasks;
+mod toml_edits;
 mod tools;
 mod webchat;
 mod workers;
@@ -0,0 +1,214 @@
+//! JSONL access log for the HTTP API.
+//!
+//! When `api.access_log` is enabled, every API request is appended as one
+//! JSON line to `logs/api_access.jsonl` in the instance directory. The log is
+//! separate from tracing output so it can be grepped or shipped on its own.
+//! Files rotate by size, keeping `access_log_max_files` old files as
+//! `api_access.jsonl.1` (newest) through `.N` (oldest).
+//!
+//! Bearer tokens are never written. A request is recorded as authenticated
+//! or not, and authenticated requests carry a short SHA-256 fingerprint of
+//! the token as their subject.
+
+use serde::Serialize;
+use sha2::{Digest as _, Sha256};
+use tokio::sync::mpsc;
+
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Lines buffered between request handlers and the writer. When the writer
+/// falls behind, new lines are dropped rather than slowing requests down.
+const ACCESS_LOG_BUFFER: usize = 4096;
+
+/// One access log line.
+#[derive(Debug, Serialize)]
+pub struct AccessLogEntry {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub latency_ms: f64,
+    pub authenticated: bool,
+    /// Fingerprint of the bearer token that authenticated the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+}
+
+/// Handle for appending entries to the access log.
+#[derive(Debug, Clone)]
+pub struct AccessLog {
+    tx: mpsc::Sender<String>,
+}
+
+impl AccessLog {
+    /// Start the writer for a log file at `path`.
+    pub fn spawn(path: PathBuf, max_bytes: u64, max_files: usize) -> std::io::Result<Self> {
+        let mut writer = RotatingWriter::open(path, max_bytes, max_files)?;
+        let (tx, mut rx) = mpsc::channel::<String>(ACCESS_LOG_BUFFER);
+        std::thread::Builder::new()
+            .name("api-access-log".into())
+            .spawn(move || {
+                while let Some(line) = rx.blocking_recv() {
+                    if let Err(error) = writer.write_line(&line) {
+                        tracing::warn!(%error, path = %writer.path.display(), "failed to write API access log");
+                    }
+                }
+            })?;
+        Ok(Self { tx })
+    }
+
+    /// Queue an entry for writing. Never blocks the request.
+    pub fn record(&self, entry: &AccessLogEntry) {
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(error) => {
+                tracing::warn!(%error, "failed to serialize API access log entry");
+                return;
+            }
+        };
+        if self.tx.try_send(line).is_err() {
+            tracing::debug!("API access log writer is behind, dropping entry");
+        }
+    }
+}
+
+/// Short, stable fingerprint of a bearer token. Identifies which token was
+/// used without the log revealing it.
+pub fn token_subject(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    format!("token:{}", &hex::encode(digest)[..12])
+}
+
+struct RotatingWriter {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl RotatingWriter {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_bytes,
+            max_files,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let length = line.len() as u64 + 1;
+        if self.size > 0 && self.size + length > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.size += length;
+        Ok(())
+    }
+
+    /// Shift `path.N-1` to `path.N` down to `path` -> `path.1`, dropping the
+    /// oldest file, then start a fresh log.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..self.max_files).rev() {
+                let from = rotated_path(&self.path, index);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "spacebot-access-log-{name}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        dir.join("api_access.jsonl")
+    }
+
+    #[test]
+    fn token_subject_does_not_contain_token() {
+        let token = "super-secret-token-value";
+        let subject = token_subject(token);
+        assert!(!subject.contains(token));
+        assert_eq!(subject, token_subject(token));
+        assert_ne!(subject, token_subject("another-token"));
+        assert_eq!(subject.len(), "token:".len() + 12);
+    }
+
+    #[test]
+    fn writer_rotates_by_size_and_keeps_max_files() {
+        let path = temp_log_path("rotate");
+        let mut writer = RotatingWriter::open(path.clone(), 20, 2).unwrap();
+        for line in ["aaaaaaaaaa", "bbbbbbbbbb", "cccccccccc", "dddddddddd"] {
+            writer.write_line(line).unwrap();
+        }
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "dddddddddd\n");
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "cccccccccc\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "bbbbbbbbbb\n"
+        );
+        assert!(!rotated_path(&path, 3).exists());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn entry_omits_missing_subject_and_address() {
+        let entry = AccessLogEntry {
+            timestamp: chrono::Utc::now(),
+            method: "GET".into(),
+            path: "/api/status".into(),
+            status: 401,
+            latency_ms: 0.4,
+            authenticated: false,
+            subject: None,
+            remote_addr: None,
+        };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["status"], 401);
+        assert_eq!(json["authenticated"], false);
+        assert!(json.get("subject").is_none());
+        assert!(json.get("remote_addr").is_none());
+    }
+}
@@ -4,7 +4,7 @@ use crate::agent::cortex::CortexLogger;
 use crate::conversation::channels::ChannelStore;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use sqlx::Row as _;
@@ -159,7 +159,7 @@ pub(super) struct AgentMcpQuery {
 }
 
 #[derive(Deserialize)]
-pub(super) struct ReconnectMcpRequest {
+pub(super) struct AgentMcpServerRequest {
     agent_id: String,
     server_name: String,
 }
@@ -208,7 +208,10 @@ fn hydrate_warmup_status(
     status
 }
 
-fn compute_bulletin_age_secs(last_refresh_unix_ms: Option<i64>, now_unix_ms: i64) -> Option<u64> {
+pub(super) fn compute_bulletin_age_secs(
+    last_refresh_unix_ms: Option<i64>,
+    now_unix_ms: i64,
+) -> Option<u64> {
     last_refresh_unix_ms.map(|refresh_ms| {
         if now_unix_ms > refresh_ms {
             ((now_unix_ms - refresh_ms) / 1000) as u64
@@ -294,9 +297,12 @@ pub(super) async fn list_agent_mcp(
 }
 
 /// Force reconnect for a single MCP server on an agent.
+///
+/// Tools are re-listed as part of the connect handshake, so the next worker
+/// spawn and channel turn pick up the refreshed tool set.
 pub(super) async fn reconnect_agent_mcp(
     State(state): State<Arc<ApiState>>,
-    Json(request): Json<ReconnectMcpRequest>,
+    Json(request): Json<AgentMcpServerRequest>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let managers = state.mcp_managers.load();
     let manager = managers
@@ -317,6 +323,46 @@ pub(super) async fn reconnect_agent_mcp(
             StatusCode::BAD_REQUEST
         })?;
 
+    let tool_count = manager
+        .statuses()
+        .await
+        .into_iter()
+        .find(|status| status.name == request.server_name)
+        .map(|status| status.tool_count)
+        .unwrap_or_default();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "agent_id": request.agent_id,
+        "server_name": request.server_name,
+        "tool_count": tool_count
+    })))
+}
+
+/// Disconnect a single MCP server on an agent without removing its config.
+pub(super) async fn disconnect_agent_mcp(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<AgentMcpServerRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let managers = state.mcp_managers.load();
+    let manager = managers
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    manager
+        .disconnect(&request.server_name)
+        .await
+        .map_err(|error| {
+            tracing::warn!(
+                %error,
+                agent_id = %request.agent_id,
+                server_name = %request.server_name,
+                "failed to disconnect mcp server"
+            );
+            StatusCode::NOT_FOUND
+        })?;
+
     Ok(Json(serde_json::json!({
         "success": true,
         "agent_id": request.agent_id,
@@ -447,6 +493,9 @@ pub(super) async fn trigger_warmup(
                 process_control_registry: Arc::new(
                     crate::agent::process_control::ProcessControlRegistry::new(),
                 ),
+                process_limiter: Arc::new(crate::agent::process_limit::ProcessLimiter::new(
+                    Arc::from(agent_id.as_str()),
+                )),
                 injection_tx,
                 working_memory,
             };
@@ -500,6 +549,89 @@ pub async fn create_agent_internal(
     state: &Arc<ApiState>,
     request: CreateAgentRequest,
 ) -> Result<CreateAgentResult, String> {
+    let agent_id = validate_new_agent_id(state, &request.agent_id)?;
+    let instance_dir = (**state.instance_dir.load()).clone();
+
+    let mut new_table = toml_edit::Table::new();
+    new_table["id"] = toml_edit::value(&agent_id);
+    if let Some(display_name) = &request.display_name
+        && !display_name.is_empty()
+    {
+        new_table["display_name"] = toml_edit::value(display_name.as_str());
+    }
+    if let Some(role) = &request.role
+        && !role.is_empty()
+    {
+        new_table["role"] = toml_edit::value(role.as_str());
+    }
+    let disk_defaults = append_agent_table(state, new_table)
+        .await?
+        .map(|fresh_config| fresh_config.defaults);
+
+    let raw_config = crate::config::AgentConfig {
+        id: agent_id.clone(),
+        default: false,
+        display_name: request.display_name.clone().filter(|s| !s.is_empty()),
+        role: request.role.clone().filter(|s| !s.is_empty()),
+        gradient_start: None,
+        gradient_end: None,
+        workspace: None,
+        routing: None,
+        max_concurrent_branches: None,
+        max_concurrent_workers: None,
+        max_concurrent_processes: None,
+        max_turns: None,
+        branch_max_turns: None,
+        context_window: None,
+        system_prompt_token_budget: None,
+        output_language: None,
+        cortex_language: None,
+        response_footer: None,
+        compaction: None,
+        memory_persistence: None,
+        coalesce: None,
+        ingestion: None,
+        cortex: None,
+        warmup: None,
+        browser: None,
+        channel: None,
+        media_safety: None,
+        task_extraction: None,
+        transcription: None,
+        embedding: None,
+        worker_retry: None,
+        memory_decay: None,
+        memory_scrub: None,
+        tool_call_limits: None,
+        stream_pacing: None,
+        reaction_triggers: None,
+        personas: None,
+        prompt_overrides: None,
+        worker_buttons: None,
+        disabled_tools: None,
+        mcp: None,
+        brave_search_key: None,
+        cron_timezone: None,
+        user_timezone: None,
+        sandbox: None,
+        projects: None,
+        cron: Vec::new(),
+    };
+
+    initialize_agent(state, raw_config, &instance_dir, disk_defaults).await?;
+
+    tracing::info!(agent_id = %agent_id, "agent created and initialized via API");
+
+    Ok(CreateAgentResult {
+        success: true,
+        agent_id: agent_id.clone(),
+        message: format!("Agent '{agent_id}' created and running"),
+    })
+}
+
+/// Check that an agent with this ID can be created on this instance.
+/// Returns the trimmed ID.
+pub(super) fn validate_new_agent_id(state: &ApiState, agent_id: &str) -> Result<String, String> {
     if let Some(limit) = hosted_agent_limit() {
         let existing = state.agent_configs.load();
         if existing.len() >= limit {
@@ -510,20 +642,31 @@ pub async fn create_agent_internal(
         }
     }
 
-    let agent_id = request.agent_id.trim().to_string();
+    let agent_id = agent_id.trim().to_string();
     if agent_id.is_empty() {
         return Err("Agent ID cannot be empty".into());
     }
 
-    {
-        let existing = state.agent_configs.load();
-        if existing.iter().any(|a| a.id == agent_id) {
-            return Err(format!("Agent '{agent_id}' already exists"));
-        }
+    let existing = state.agent_configs.load();
+    if existing.iter().any(|a| a.id == agent_id) {
+        return Err(format!("Agent '{agent_id}' already exists"));
     }
 
+    Ok(agent_id)
+}
+
+/// Append an `[[agents]]` table to config.toml, then reload the config from
+/// disk.
+///
+/// Returns the freshly loaded config, or None if it could not be reloaded, in
+/// which case callers fall back to the cached defaults. Reading defaults from
+/// disk rather than the cache avoids stale values (e.g. if a provider was
+/// configured but the in-memory cache wasn't refreshed yet).
+pub(super) async fn append_agent_table(
+    state: &Arc<ApiState>,
+    table: toml_edit::Table,
+) -> Result<Option<crate::config::Config>, String> {
     let config_path = state.config_path.read().await.clone();
-    let instance_dir = (**state.instance_dir.load()).clone();
 
     // Acquire the config write mutex to prevent concurrent read-modify-write races.
     let _config_guard = state.config_write_mutex.lock().await;
@@ -549,20 +692,7 @@ pub async fn create_agent_internal(
     let agents_array = doc["agents"]
         .as_array_of_tables_mut()
         .ok_or_else(|| "agents is not an array of tables in config.toml".to_string())?;
-
-    let mut new_table = toml_edit::Table::new();
-    new_table["id"] = toml_edit::value(&agent_id);
-    if let Some(display_name) = &request.display_name
-        && !display_name.is_empty()
-    {
-        new_table["display_name"] = toml_edit::value(display_name.as_str());
-    }
-    if let Some(role) = &request.role
-        && !role.is_empty()
-    {
-        new_table["role"] = toml_edit::value(role.as_str());
-    }
-    agents_array.push(new_table);
+    agents_array.push(table);
 
     tokio::fs::write(&config_path, doc.to_string())
         .await
@@ -574,26 +704,40 @@ pub async fn create_agent_internal(
     // Release the config write mutex — remaining work doesn't touch config.toml.
     drop(_config_guard);
 
-    // Read defaults directly from the config we just wrote to disk rather than
-    // relying on the cached `defaults_config` which may be stale (e.g. if a
-    // provider was configured but the in-memory cache wasn't refreshed yet).
-    let disk_defaults = match crate::config::Config::load_from_path(&config_path) {
+    match crate::config::Config::load_from_path(&config_path) {
         Ok(fresh_config) => {
             // Also update the in-memory cache so subsequent operations
             // (e.g. creating another agent) don't hit stale defaults.
             state
                 .set_defaults_config(fresh_config.defaults.clone())
                 .await;
-            Some(fresh_config.defaults)
+            Ok(Some(fresh_config))
         }
         Err(error) => {
             tracing::warn!(
                 %error,
                 "failed to reload config.toml for defaults; falling back to cached defaults"
             );
-            None
+            Ok(None)
         }
-    };
+    }
+}
+
+/// Bring an agent up from its raw config: databases, runtime config, MCP,
+/// sandbox, cron, cortex loops and cortex chat. Registers the agent with the
+/// main loop and every API state map.
+///
+/// Shared by agent creation and restart. `disk_defaults` should be freshly
+/// loaded from config.toml when available; otherwise the cached defaults are
+/// used.
+pub(super) async fn initialize_agent(
+    state: &Arc<ApiState>,
+    raw_config: crate::config::AgentConfig,
+    instance_dir: &std::path::Path,
+    disk_defaults: Option<crate::config::DefaultsConfig>,
+) -> Result<(), String> {
+    let agent_id = raw_config.id.clone();
+
     let cached_defaults;
     let defaults = if let Some(ref d) = disk_defaults {
         d
@@ -605,37 +749,7 @@ pub async fn create_agent_internal(
         })?
     };
 
-    let raw_config = crate::config::AgentConfig {
-        id: agent_id.clone(),
-        default: false,
-        display_name: request.display_name.clone().filter(|s| !s.is_empty()),
-        role: request.role.clone().filter(|s| !s.is_empty()),
-        gradient_start: None,
-        gradient_end: None,
-        workspace: None,
-        routing: None,
-        max_concurrent_branches: None,
-        max_concurrent_workers: None,
-        max_turns: None,
-        branch_max_turns: None,
-        context_window: None,
-        compaction: None,
-        memory_persistence: None,
-        coalesce: None,
-        ingestion: None,
-        cortex: None,
-        warmup: None,
-        browser: None,
-        channel: None,
-        mcp: None,
-        brave_search_key: None,
-        cron_timezone: None,
-        user_timezone: None,
-        sandbox: None,
-        projects: None,
-        cron: Vec::new(),
-    };
-    let agent_config = raw_config.resolve(&instance_dir, defaults);
+    let agent_config = raw_config.resolve(instance_dir, defaults);
 
     for dir in [
         &agent_config.workspace,
@@ -650,7 +764,7 @@ pub async fn create_agent_internal(
         })?;
     }
 
-    let db = crate::db::Db::connect(&agent_config.data_dir)
+    let db = crate::db::Db::connect(&agent_config.data_dir, &state.db_config.load())
         .await
         .map_err(|error| {
             tracing::error!(%error, agent_id = %agent_id, "failed to connect agent databases");
@@ -688,11 +802,60 @@ pub async fn create_agent_internal(
         tracing::warn!(%error, agent_id = %agent_id, "failed to create FTS index");
     }
 
-    let memory_search = std::sync::Arc::new(crate::memory::MemorySearch::new(
-        memory_store,
-        embedding_table,
-        embedding_model,
-    ));
+    let routed_embedders = match crate::memory::routing::open_routed_embedders(
+        &agent_config.embedding.routes,
+        &db.lance,
+        &instance_dir.join("embedding_cache"),
+    )
+    .await
+    {
+        Ok(routed_embedders) => routed_embedders,
+        Err(error) => {
+            tracing::warn!(
+                %error,
+                agent_id = %agent_id,
+                "failed to load routed embedding models, using the default model only"
+            );
+            Vec::new()
+        }
+    };
+
+    let memory_search = std::sync::Arc::new(
+        crate::memory::MemorySearch::new(memory_store, embedding_table, embedding_model)
+            .with_agent_id(&agent_config.id)
+            .with_embedding_config(agent_config.embedding.clone())
+            .with_routed_embedders(routed_embedders)
+            .with_decay_config(agent_config.memory_decay)
+            .with_scrub_config(&agent_config.memory_scrub),
+    );
+    match crate::memory::seed::seed_memories_once(
+        &memory_search,
+        &agent_config.workspace,
+        &agent_config.data_dir,
+    )
+    .await
+    {
+        Ok(0) => {}
+        Ok(count) => tracing::info!(agent_id = %agent_id, count, "seeded memories from workspace"),
+        Err(error) => {
+            tracing::warn!(%error, agent_id = %agent_id, "failed to seed memories, will retry on next start");
+        }
+    }
+    {
+        let memory_search = memory_search.clone();
+        let agent_id = agent_id.clone();
+        tokio::spawn(async move {
+            match memory_search.backfill_routed_embeddings().await {
+                Ok(0) => {}
+                Ok(count) => {
+                    tracing::info!(agent_id = %agent_id, count, "backfilled routed embeddings")
+                }
+                Err(error) => {
+                    tracing::warn!(%error, agent_id = %agent_id, "failed to backfill routed embeddings")
+                }
+            }
+        });
+    }
     let task_store = std::sync::Arc::new(crate::tasks::TaskStore::new(db.sqlite.clone()));
 
     let (event_tx, memory_event_tx) = crate::create_process_event_buses();
@@ -718,7 +881,11 @@ pub async fn create_agent_internal(
                 tracing::error!("prompt engine not available");
                 "prompt engine not available".to_string()
             })?
-            .clone()
+            .with_overrides(&agent_config.prompt_overrides)
+            .map_err(|error| {
+                tracing::error!(%error, "failed to load prompt overrides");
+                format!("failed to load prompt overrides: {error}")
+            })?
     };
 
     let defaults_for_runtime = if let Some(d) = disk_defaults {
@@ -735,7 +902,7 @@ pub async fn create_agent_internal(
     };
 
     let runtime_config = std::sync::Arc::new(crate::config::RuntimeConfig::new(
-        &instance_dir,
+        instance_dir,
         &agent_config,
         &defaults_for_runtime,
         prompt_engine,
@@ -763,7 +930,7 @@ pub async fn create_agent_internal(
         crate::sandbox::Sandbox::new(
             runtime_config.sandbox.clone(),
             agent_config.workspace.clone(),
-            &instance_dir,
+            instance_dir,
             agent_config.data_dir.clone(),
         )
         .await,
@@ -798,6 +965,9 @@ pub async fn create_agent_internal(
         process_control_registry: Arc::new(
             crate::agent::process_control::ProcessControlRegistry::new(),
         ),
+        process_limiter: Arc::new(crate::agent::process_limit::ProcessLimiter::new(
+            arc_agent_id.clone(),
+        )),
         injection_tx: state.injection_tx.clone(),
         agent_names: {
             let configs = state.agent_configs.load();
@@ -811,10 +981,9 @@ pub async fn create_agent_internal(
                 })
                 .collect();
             names.entry(agent_id.clone()).or_insert_with(|| {
-                request
+                agent_config
                     .display_name
                     .clone()
-                    .filter(|s| !s.is_empty())
                     .unwrap_or_else(|| agent_id.clone())
             });
             Arc::new(names)
@@ -833,6 +1002,17 @@ pub async fn create_agent_internal(
         },
     };
 
+    if let Some(messaging_manager) = &deps.messaging_manager {
+        messaging_manager.register_agent_footer(
+            agent_id.clone(),
+            runtime_config.clone(),
+            deps.agent_names
+                .get(&agent_id)
+                .cloned()
+                .unwrap_or_else(|| agent_id.clone()),
+        );
+    }
+
     let event_rx = event_tx.subscribe();
     state.register_agent_events(agent_id.clone(), event_rx);
 
@@ -853,6 +1033,45 @@ pub async fn create_agent_internal(
     let scheduler = std::sync::Arc::new(crate::cron::Scheduler::new(cron_context));
     runtime_config.set_cron(cron_store.clone(), scheduler.clone());
 
+    // Seed cron jobs from config and register everything stored for this agent.
+    // A freshly created agent has none; a restarted one picks its jobs back up.
+    for cron_def in &agent_config.cron {
+        let cron_config = crate::cron::CronConfig {
+            id: cron_def.id.clone(),
+            prompt: cron_def.prompt.clone(),
+            cron_expr: cron_def.cron_expr.clone(),
+            interval_secs: cron_def.interval_secs,
+            delivery_target: cron_def.delivery_target.clone(),
+            active_hours: cron_def.active_hours,
+            enabled: cron_def.enabled,
+            run_once: cron_def.run_once,
+            timeout_secs: cron_def.timeout_secs,
+        };
+        if let Err(error) = cron_store.save(&cron_config).await {
+            tracing::warn!(agent_id = %agent_id, cron_id = %cron_def.id, %error, "failed to seed cron config");
+        }
+    }
+    match cron_store.load_all().await {
+        Ok(configs) => {
+            let last_times = cron_store
+                .last_execution_times()
+                .await
+                .unwrap_or_else(|error| {
+                    tracing::warn!(agent_id = %agent_id, %error, "failed to load cron last execution times");
+                    std::collections::HashMap::new()
+                });
+            for cron_config in configs {
+                let anchor = last_times.get(&cron_config.id).map(String::as_str);
+                if let Err(error) = scheduler.register_with_anchor(cron_config, anchor).await {
+                    tracing::warn!(agent_id = %agent_id, %error, "failed to register cron job");
+                }
+            }
+        }
+        Err(error) => {
+            tracing::warn!(agent_id = %agent_id, %error, "failed to load cron jobs from database");
+        }
+    }
+
     let cron_tool = crate::tools::CronTool::new(cron_store.clone(), scheduler.clone());
 
     let browser_config = (**runtime_config.browser_config.load()).clone();
@@ -898,19 +1117,34 @@ pub async fn create_agent_internal(
     .with_factory(true);
 
     let cortex_logger = crate::agent::cortex::CortexLogger::new(db.sqlite.clone());
-    let _warmup_loop = crate::agent::cortex::spawn_warmup_loop(deps.clone(), cortex_logger.clone());
-    let _cortex_loop = crate::agent::cortex::spawn_cortex_loop(deps.clone(), cortex_logger.clone());
-    let _association_loop =
-        crate::agent::cortex::spawn_association_loop(deps.clone(), cortex_logger);
-    crate::agent::cortex::spawn_ready_task_loop(
-        deps.clone(),
-        crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
-    );
+    let mut loops = vec![
+        crate::agent::cortex::spawn_warmup_loop(deps.clone(), cortex_logger.clone()),
+        crate::agent::cortex::spawn_cortex_loop(deps.clone(), cortex_logger.clone()),
+        crate::agent::cortex::spawn_association_loop(deps.clone(), cortex_logger.clone()),
+        crate::agent::cortex::spawn_consolidation_loop(deps.clone(), cortex_logger),
+        crate::agent::cortex::spawn_ready_task_loop(
+            deps.clone(),
+            crate::agent::cortex::CortexLogger::new(db.sqlite.clone()),
+        ),
+        crate::agent::worker_logs::spawn_worker_log_cleanup_loop(
+            crate::agent::worker_logs::worker_log_dirs(
+                &agent_config.data_dir,
+                &agent_config.workspace,
+            ),
+            deps.clone(),
+        ),
+    ];
 
     let ingestion_config = **runtime_config.ingestion.load();
     if ingestion_config.enabled {
-        crate::agent::ingestion::spawn_ingestion_loop(agent_config.ingest_dir(), deps.clone());
+        loops.push(crate::agent::ingestion::spawn_ingestion_loop(
+            agent_config.ingest_dir(),
+            deps.clone(),
+        ));
     }
+    state
+        .register_agent_tasks(&agent_id, loops.iter().map(|handle| handle.abort_handle()))
+        .await;
 
     let sqlite_pool = db.sqlite.clone();
     let mut deps_with_cron = deps.clone();
@@ -979,7 +1213,7 @@ pub async fn create_agent_internal(
             .store(std::sync::Arc::new(project_stores_map));
 
         let mut agent_infos = (**state.agent_configs.load()).clone();
-        agent_infos.push(AgentInfo {
+        let info = AgentInfo {
             id: agent_config.id.clone(),
             display_name: agent_config.display_name.clone(),
             role: agent_config.role.clone(),
@@ -990,7 +1224,15 @@ pub async fn create_agent_internal(
             max_turns: agent_config.max_turns,
             max_concurrent_branches: agent_config.max_concurrent_branches,
             max_concurrent_workers: agent_config.max_concurrent_workers,
-        });
+        };
+        // A restarted agent keeps its position in the agent list.
+        match agent_infos
+            .iter_mut()
+            .find(|existing| existing.id == info.id)
+        {
+            Some(existing) => *existing = info,
+            None => agent_infos.push(info),
+        }
         state.agent_configs.store(std::sync::Arc::new(agent_infos));
 
         let mut cron_stores = (**state.cron_stores.load()).clone();
@@ -1012,13 +1254,7 @@ pub async fn create_agent_internal(
             .store(std::sync::Arc::new(sessions));
     }
 
-    tracing::info!(agent_id = %agent_id, "agent created and initialized via API");
-
-    Ok(CreateAgentResult {
-        success: true,
-        agent_id: agent_id.clone(),
-        message: format!("Agent '{agent_id}' created and running"),
-    })
+    Ok(())
 }
 
 /// Update an agent's display_name and role in config.toml.
@@ -1214,10 +1450,91 @@ pub(super) async fn delete_agent(
             })?;
     }
 
+    teardown_agent(&state, &agent_id).await;
+
+    let mut agent_infos = (**state.agent_configs.load()).clone();
+    agent_infos.retain(|a| a.id != agent_id);
+    state.agent_configs.store(std::sync::Arc::new(agent_infos));
+
+    // Signal the main event loop to remove the agent
+    if let Err(error) = state.agent_remove_tx.send(agent_id.clone()).await {
+        tracing::error!(%error, "failed to send agent removal to main loop");
+    }
+
+    tracing::info!(agent_id = %agent_id, "agent deleted via API");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": format!("Agent '{agent_id}' deleted")
+    })))
+}
+
+/// Restart a single agent: stop its loops, cron timers, MCP connections and
+/// database, then bring it back up from the current config.toml. Other agents
+/// keep running. Responds once the new instance is registered.
+pub(super) async fn restart_agent(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    {
+        let existing = state.agent_configs.load();
+        if !existing.iter().any(|a| a.id == agent_id) {
+            return Err(StatusCode::NOT_FOUND);
+        }
+    }
+
+    let config_path = state.config_path.read().await.clone();
+    let config = crate::config::Config::load_from_path(&config_path).map_err(|error| {
+        tracing::warn!(%error, agent_id = %agent_id, "failed to load config.toml for restart");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let Some(raw_config) = config.agents.iter().find(|a| a.id == agent_id).cloned() else {
+        return Ok(Json(serde_json::json!({
+            "success": false,
+            "message": format!("Agent '{agent_id}' is not defined in config.toml")
+        })));
+    };
+    state.set_defaults_config(config.defaults.clone()).await;
+    let instance_dir = (**state.instance_dir.load()).clone();
+
+    tracing::info!(agent_id = %agent_id, "restarting agent via API");
+    teardown_agent(&state, &agent_id).await;
+
+    if let Err(error) =
+        initialize_agent(&state, raw_config, &instance_dir, Some(config.defaults)).await
+    {
+        tracing::error!(%error, agent_id = %agent_id, "agent failed to come back up after restart");
+        return Ok(Json(serde_json::json!({
+            "success": false,
+            "agent_id": agent_id,
+            "message": format!("Agent '{agent_id}' was stopped but failed to restart: {error}")
+        })));
+    }
+
+    tracing::info!(agent_id = %agent_id, "agent restarted via API");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "agent_id": agent_id,
+        "message": format!("Agent '{agent_id}' restarted")
+    })))
+}
+
+/// Stop an agent's runtime without touching config.toml or its files:
+/// background loops, cron timers, MCP connections and the SQLite pool. The
+/// agent is dropped from the API state maps but stays in `agent_configs`.
+async fn teardown_agent(state: &Arc<ApiState>, agent_id: &str) {
+    state.abort_agent_tasks(agent_id).await;
+
+    let scheduler = state.cron_schedulers.load().get(agent_id).cloned();
+    if let Some(scheduler) = scheduler {
+        scheduler.shutdown().await;
+    }
+
     // Close the SQLite pool before removing state
     {
         let pools = state.agent_pools.load();
-        if let Some(pool) = pools.get(&agent_id) {
+        if let Some(pool) = pools.get(agent_id) {
             pool.close().await;
         }
     }
@@ -1225,81 +1542,65 @@ pub(super) async fn delete_agent(
     // Remove from all API state maps
     {
         let mut mcp_managers = (**state.mcp_managers.load()).clone();
-        if let Some(mcp_manager) = mcp_managers.remove(&agent_id) {
+        if let Some(mcp_manager) = mcp_managers.remove(agent_id) {
             mcp_manager.disconnect_all().await;
         }
         state.mcp_managers.store(std::sync::Arc::new(mcp_managers));
 
         let mut pools = (**state.agent_pools.load()).clone();
-        pools.remove(&agent_id);
+        pools.remove(agent_id);
         state.agent_pools.store(std::sync::Arc::new(pools));
 
         let mut searches = (**state.memory_searches.load()).clone();
-        searches.remove(&agent_id);
+        searches.remove(agent_id);
         state.memory_searches.store(std::sync::Arc::new(searches));
 
         let mut workspaces = (**state.agent_workspaces.load()).clone();
-        workspaces.remove(&agent_id);
+        workspaces.remove(agent_id);
         state
             .agent_workspaces
             .store(std::sync::Arc::new(workspaces));
 
         let mut identity_dirs = (**state.agent_identity_dirs.load()).clone();
-        identity_dirs.remove(&agent_id);
+        identity_dirs.remove(agent_id);
         state
             .agent_identity_dirs
             .store(std::sync::Arc::new(identity_dirs));
 
         let mut data_dirs = (**state.agent_data_dirs.load()).clone();
-        data_dirs.remove(&agent_id);
+        data_dirs.remove(agent_id);
         state.agent_data_dirs.store(std::sync::Arc::new(data_dirs));
 
         let mut configs = (**state.runtime_configs.load()).clone();
-        configs.remove(&agent_id);
+        configs.remove(agent_id);
         state.runtime_configs.store(std::sync::Arc::new(configs));
 
         let mut sandboxes = (**state.sandboxes.load()).clone();
-        sandboxes.remove(&agent_id);
+        sandboxes.remove(agent_id);
         state.sandboxes.store(std::sync::Arc::new(sandboxes));
 
-        let mut agent_infos = (**state.agent_configs.load()).clone();
-        agent_infos.retain(|a| a.id != agent_id);
-        state.agent_configs.store(std::sync::Arc::new(agent_infos));
-
         let mut cron_stores = (**state.cron_stores.load()).clone();
-        cron_stores.remove(&agent_id);
+        cron_stores.remove(agent_id);
         state.cron_stores.store(std::sync::Arc::new(cron_stores));
 
         let mut cron_schedulers = (**state.cron_schedulers.load()).clone();
-        cron_schedulers.remove(&agent_id);
+        cron_schedulers.remove(agent_id);
         state
             .cron_schedulers
             .store(std::sync::Arc::new(cron_schedulers));
 
         let mut sessions = (**state.cortex_chat_sessions.load()).clone();
-        sessions.remove(&agent_id);
+        sessions.remove(agent_id);
         state
             .cortex_chat_sessions
             .store(std::sync::Arc::new(sessions));
 
         let mut project_stores_map = (**state.project_stores.load()).clone();
-        project_stores_map.remove(&agent_id);
+        project_stores_map.remove(agent_id);
         state
             .project_stores
             .store(std::sync::Arc::new(project_stores_map));
     }
-
-    // Signal the main event loop to remove the agent
-    if let Err(error) = state.agent_remove_tx.send(agent_id.clone()).await {
-        tracing::error!(%error, "failed to send agent removal to main loop");
-    }
-
-    tracing::info!(agent_id = %agent_id, "agent deleted via API");
-
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "message": format!("Agent '{agent_id}' deleted")
-    })))
 }
 
 /// Get overview stats for an agent: memory breakdown, channels, cron, cortex.
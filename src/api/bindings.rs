@@ -1,4 +1,5 @@
 use super::state::ApiState;
+use super::toml_edits::{set_or_remove, set_value, string_array};
 
 use axum::Json;
 use axum::extract::{Query, State};
@@ -18,6 +19,7 @@ pub(super) struct BindingResponse {
     channel_ids: Vec<String>,
     require_mention: bool,
     dm_allowed_users: Vec<String>,
+    welcome_message: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -51,6 +53,8 @@ pub(super) struct CreateBindingRequest {
     require_mention: bool,
     #[serde(default)]
     dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    welcome_message: Option<String>,
     /// Optional: set platform credentials if not yet configured.
     #[serde(default)]
     platform_credentials: Option<PlatformCredentials>,
@@ -161,6 +165,8 @@ pub(super) struct UpdateBindingRequest {
     require_mention: bool,
     #[serde(default)]
     dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    welcome_message: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -186,7 +192,8 @@ pub(super) async fn list_bindings(
 
     let filtered: Vec<BindingResponse> = bindings
         .into_iter()
-        .filter(|b| query.agent_id.as_ref().is_none_or(|id| &b.agent_id == id))
+        // Scheduled bindings are listed for every agent they can route to.
+        .filter(|b| query.agent_id.as_ref().is_none_or(|id| b.routes_to(id)))
         .map(|b| BindingResponse {
             agent_id: b.agent_id,
             channel: b.channel,
@@ -198,6 +205,7 @@ pub(super) async fn list_bindings(
             channel_ids: b.channel_ids,
             require_mention: b.require_mention,
             dm_allowed_users: b.dm_allowed_users,
+            welcome_message: b.welcome_message,
         })
         .collect();
 
@@ -252,8 +260,8 @@ pub(super) async fn create_binding(
             let discord = messaging["discord"]
                 .as_table_mut()
                 .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-            discord["enabled"] = toml_edit::value(true);
-            discord["token"] = toml_edit::value(token.as_str());
+            set_value(discord, "enabled", true);
+            set_value(discord, "token", token.as_str());
             new_discord_token = Some(token.clone());
         }
         if let Some(bot_token) = &credentials.slack_bot_token {
@@ -271,9 +279,9 @@ pub(super) async fn create_binding(
                 let slack = messaging["slack"]
                     .as_table_mut()
                     .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-                slack["enabled"] = toml_edit::value(true);
-                slack["bot_token"] = toml_edit::value(bot_token.as_str());
-                slack["app_token"] = toml_edit::value(app_token);
+                set_value(slack, "enabled", true);
+                set_value(slack, "bot_token", bot_token.as_str());
+                set_value(slack, "app_token", app_token);
                 new_slack_tokens = Some((bot_token.clone(), app_token.to_string()));
             }
         }
@@ -292,8 +300,8 @@ pub(super) async fn create_binding(
             let telegram = messaging["telegram"]
                 .as_table_mut()
                 .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-            telegram["enabled"] = toml_edit::value(true);
-            telegram["token"] = toml_edit::value(token.as_str());
+            set_value(telegram, "enabled", true);
+            set_value(telegram, "token", token.as_str());
             new_telegram_token = Some(token.clone());
         }
 
@@ -358,23 +366,29 @@ pub(super) async fn create_binding(
             let email = messaging["email"]
                 .as_table_mut()
                 .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-            email["enabled"] = toml_edit::value(true);
-            email["imap_host"] = toml_edit::value(email_imap_host);
-            email["imap_port"] =
-                toml_edit::value(i64::from(credentials.email_imap_port.unwrap_or(993)));
-            email["imap_username"] = toml_edit::value(email_imap_username);
-            email["imap_password"] = toml_edit::value(email_imap_password);
-            email["smtp_host"] = toml_edit::value(email_smtp_host);
-            email["smtp_port"] =
-                toml_edit::value(i64::from(credentials.email_smtp_port.unwrap_or(587)));
-            email["smtp_username"] = toml_edit::value(email_smtp_username);
-            email["smtp_password"] = toml_edit::value(email_smtp_password);
-            email["from_address"] = toml_edit::value(email_from_address);
+            set_value(email, "enabled", true);
+            set_value(email, "imap_host", email_imap_host);
+            set_value(
+                email,
+                "imap_port",
+                i64::from(credentials.email_imap_port.unwrap_or(993)),
+            );
+            set_value(email, "imap_username", email_imap_username);
+            set_value(email, "imap_password", email_imap_password);
+            set_value(email, "smtp_host", email_smtp_host);
+            set_value(
+                email,
+                "smtp_port",
+                i64::from(credentials.email_smtp_port.unwrap_or(587)),
+            );
+            set_value(email, "smtp_username", email_smtp_username);
+            set_value(email, "smtp_password", email_smtp_password);
+            set_value(email, "from_address", email_from_address);
 
             if let Some(from_name) = &credentials.email_from_name {
                 let from_name = from_name.trim();
                 if !from_name.is_empty() {
-                    email["from_name"] = toml_edit::value(from_name);
+                    set_value(email, "from_name", from_name);
                 }
             }
 
@@ -399,23 +413,24 @@ pub(super) async fn create_binding(
                 let twitch = messaging["twitch"]
                     .as_table_mut()
                     .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-                twitch["enabled"] = toml_edit::value(true);
-                twitch["username"] = toml_edit::value(username.as_str());
-                twitch["oauth_token"] = toml_edit::value(oauth_token);
+                set_value(twitch, "enabled", true);
+                set_value(twitch, "username", username.as_str());
+                set_value(twitch, "oauth_token", oauth_token);
                 if !client_id.is_empty() {
-                    twitch["client_id"] = toml_edit::value(client_id);
+                    set_value(twitch, "client_id", client_id);
                 }
                 if !client_secret.is_empty() {
-                    twitch["client_secret"] = toml_edit::value(client_secret);
+                    set_value(twitch, "client_secret", client_secret);
                 }
                 if !refresh_token.is_empty() {
-                    twitch["refresh_token"] = toml_edit::value(refresh_token);
+                    set_value(twitch, "refresh_token", refresh_token);
                 }
                 new_twitch_creds = Some((username.clone(), oauth_token.to_string()));
             }
         }
     }
 
+    let require_mention_default = platform_require_mention_default(&doc, &request.channel);
     if doc.get("bindings").is_none() {
         doc["bindings"] = toml_edit::Item::ArrayOfTables(toml_edit::ArrayOfTables::new());
     }
@@ -458,8 +473,10 @@ pub(super) async fn create_binding(
         }
         binding_table["channel_ids"] = toml_edit::value(arr);
     }
-    if request.require_mention {
-        binding_table["require_mention"] = toml_edit::value(true);
+    // Only write an explicit value where it matters: `true`, or `false`
+    // overriding a platform that requires mentions by default.
+    if request.require_mention || require_mention_default {
+        binding_table["require_mention"] = toml_edit::value(request.require_mention);
     }
     if !request.dm_allowed_users.is_empty() {
         let mut arr = toml_edit::Array::new();
@@ -468,6 +485,13 @@ pub(super) async fn create_binding(
         }
         binding_table["dm_allowed_users"] = toml_edit::value(arr);
     }
+    if let Some(welcome_message) = request
+        .welcome_message
+        .as_deref()
+        .filter(|message| !message.trim().is_empty())
+    {
+        binding_table["welcome_message"] = toml_edit::value(welcome_message);
+    }
     bindings_array.push(binding_table);
 
     tokio::fs::write(&config_path, doc.to_string())
@@ -536,11 +560,17 @@ pub(super) async fn create_binding(
                         }
                     }
                 };
+                let stream_edit_interval = new_config
+                    .messaging
+                    .discord
+                    .as_ref()
+                    .and_then(|discord_config| discord_config.stream_edit_interval());
                 let adapter = crate::messaging::discord::DiscordAdapter::new(
                     "discord",
                     &token,
                     discord_perms,
-                );
+                )
+                .with_stream_edits(stream_edit_interval);
                 if let Err(error) = manager.register_and_start(adapter).await {
                     tracing::error!(%error, "failed to hot-start discord adapter");
                 }
@@ -695,6 +725,7 @@ pub(super) async fn update_binding(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    let require_mention_default = platform_require_mention_default(&doc, &request.channel);
     let bindings_array = doc
         .get_mut("bindings")
         .and_then(|b| b.as_array_of_tables_mut())
@@ -774,72 +805,57 @@ pub(super) async fn update_binding(
         .get_mut(idx)
         .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    binding["agent_id"] = toml_edit::value(&request.agent_id);
-    binding["channel"] = toml_edit::value(&request.channel);
-
-    binding.remove("adapter");
-    binding.remove("guild_id");
-    binding.remove("workspace_id");
-    binding.remove("chat_id");
-    binding.remove("team_id");
+    set_value(binding, "agent_id", &request.agent_id);
+    set_value(binding, "channel", &request.channel);
 
-    if let Some(adapter) = request
-        .adapter
-        .as_deref()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-    {
-        binding["adapter"] = toml_edit::value(adapter);
-    }
-
-    if let Some(ref guild_id) = request.guild_id
-        && !guild_id.is_empty()
-    {
-        binding["guild_id"] = toml_edit::value(guild_id);
-    }
-    if let Some(ref workspace_id) = request.workspace_id
-        && !workspace_id.is_empty()
-    {
-        binding["workspace_id"] = toml_edit::value(workspace_id);
-    }
-    if let Some(ref chat_id) = request.chat_id
-        && !chat_id.is_empty()
-    {
-        binding["chat_id"] = toml_edit::value(chat_id);
-    }
-    if let Some(team_id) = request
-        .team_id
-        .as_deref()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-    {
-        binding["team_id"] = toml_edit::value(team_id);
+    // Update in place rather than removing and re-adding keys, so comments
+    // and key order in the user's config.toml survive the edit.
+    fn non_empty(value: Option<&str>) -> Option<&str> {
+        value.filter(|s| !s.is_empty())
     }
+    set_or_remove(
+        binding,
+        "adapter",
+        non_empty(request.adapter.as_deref().map(str::trim)),
+    );
+    set_or_remove(binding, "guild_id", non_empty(request.guild_id.as_deref()));
+    set_or_remove(
+        binding,
+        "workspace_id",
+        non_empty(request.workspace_id.as_deref()),
+    );
+    set_or_remove(binding, "chat_id", non_empty(request.chat_id.as_deref()));
+    set_or_remove(
+        binding,
+        "team_id",
+        non_empty(request.team_id.as_deref().map(str::trim)),
+    );
+    set_or_remove(
+        binding,
+        "channel_ids",
+        (!request.channel_ids.is_empty()).then(|| string_array(&request.channel_ids)),
+    );
+    set_or_remove(
+        binding,
+        "require_mention",
+        (request.require_mention || require_mention_default).then_some(request.require_mention),
+    );
+    set_or_remove(
+        binding,
+        "dm_allowed_users",
+        (!request.dm_allowed_users.is_empty()).then(|| string_array(&request.dm_allowed_users)),
+    );
 
-    if !request.channel_ids.is_empty() {
-        let mut arr = toml_edit::Array::new();
-        for id in &request.channel_ids {
-            arr.push(id.as_str());
+    // Absent leaves the welcome message as is, so clients that predate the
+    // field don't wipe it; an empty string removes it.
+    match request.welcome_message.as_deref() {
+        Some(message) if message.trim().is_empty() => {
+            binding.remove("welcome_message");
         }
-        binding["channel_ids"] = toml_edit::value(arr);
-    } else {
-        binding.remove("channel_ids");
-    }
-
-    if request.require_mention {
-        binding["require_mention"] = toml_edit::value(true);
-    } else {
-        binding.remove("require_mention");
-    }
-
-    if !request.dm_allowed_users.is_empty() {
-        let mut arr = toml_edit::Array::new();
-        for id in &request.dm_allowed_users {
-            arr.push(id.as_str());
+        Some(message) => {
+            set_value(binding, "welcome_message", message);
         }
-        binding["dm_allowed_users"] = toml_edit::value(arr);
-    } else {
-        binding.remove("dm_allowed_users");
+        None => {}
     }
 
     tokio::fs::write(&config_path, doc.to_string())
@@ -889,6 +905,15 @@ pub(super) async fn update_binding(
     }))
 }
 
+/// The `require_mention_default` set under `[messaging.<platform>]`.
+fn platform_require_mention_default(doc: &toml_edit::DocumentMut, platform: &str) -> bool {
+    doc.get("messaging")
+        .and_then(|messaging| messaging.get(platform))
+        .and_then(|section| section.get("require_mention_default"))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
 /// Delete a binding by matching agent_id + channel + platform-specific identifiers.
 pub(super) async fn delete_binding(
     State(state): State<Arc<ApiState>>,
@@ -0,0 +1,161 @@
+use super::state::ApiState;
+use crate::OutboundResponse;
+use crate::agent::channel_prompt::TemporalContext;
+use crate::config::RuntimeConfig;
+use crate::cron::scheduler::hour_in_active_window;
+use crate::messaging::target::resolve_binding_targets;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct BroadcastRequest {
+    text: String,
+    /// Send nothing while the agent is paused, in listen-only mode, or inside
+    /// its follow-up quiet hours.
+    #[serde(default)]
+    respect_quiet_hours: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct BroadcastTargetResult {
+    /// Runtime adapter key of the binding, e.g. `discord` or `slack:ops`.
+    adapter: String,
+    /// `None` when the binding couldn't be resolved to a concrete channel.
+    target: Option<String>,
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct BroadcastResponse {
+    /// True when every binding resolved and every send succeeded.
+    success: bool,
+    /// Why nothing was sent, when `respect_quiet_hours` held the broadcast.
+    skipped: Option<String>,
+    results: Vec<BroadcastTargetResult>,
+}
+
+/// Why a broadcast that respects quiet hours must not go out now, if anything.
+fn quiet_reason(runtime_config: &RuntimeConfig) -> Option<&'static str> {
+    let channel_config = runtime_config.channel_config.load();
+    if runtime_config.is_paused() {
+        Some("agent paused")
+    } else if channel_config.listen_only_mode {
+        Some("listen-only mode")
+    } else if channel_config
+        .followup_quiet_hours
+        .is_some_and(|(start, end)| {
+            let local_hour = TemporalContext::from_runtime(runtime_config).local_hour();
+            hour_in_active_window(local_hour, start, end)
+        })
+    {
+        Some("quiet hours")
+    } else {
+        None
+    }
+}
+
+/// Send a message to every channel the agent is bound to, right away.
+/// Scheduled bindings count when their current window routes to the agent.
+/// Bindings that don't name a concrete channel are reported, not sent to.
+pub(super) async fn broadcast(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    Json(request): Json<BroadcastRequest>,
+) -> Result<Json<BroadcastResponse>, StatusCode> {
+    let text = request.text.trim();
+    if text.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(&agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if request.respect_quiet_hours
+        && let Some(reason) = quiet_reason(&runtime_config)
+    {
+        tracing::info!(%agent_id, reason, "broadcast held");
+        return Ok(Json(BroadcastResponse {
+            success: true,
+            skipped: Some(reason.to_string()),
+            results: Vec::new(),
+        }));
+    }
+
+    let messaging_manager = state
+        .messaging_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let bindings = match state.bindings.read().await.as_ref() {
+        Some(bindings) => bindings.load_full(),
+        None => Arc::new(Vec::new()),
+    };
+
+    let now = chrono::Utc::now();
+    let mut results = Vec::new();
+    let mut sent_to = HashSet::new();
+    for binding in bindings
+        .iter()
+        .filter(|binding| binding.agent_id_at(now) == agent_id)
+    {
+        let targets = match resolve_binding_targets(binding) {
+            Ok(targets) => targets,
+            Err(error) => {
+                results.push(BroadcastTargetResult {
+                    adapter: binding.runtime_adapter_key(),
+                    target: None,
+                    success: false,
+                    error: Some(error),
+                });
+                continue;
+            }
+        };
+
+        // Overlapping bindings can name the same channel.
+        for target in targets {
+            if !sent_to.insert(target.to_string()) {
+                continue;
+            }
+            let delivery = messaging_manager
+                .broadcast(
+                    &agent_id,
+                    &target.adapter,
+                    &target.target,
+                    OutboundResponse::Text(text.to_string()),
+                )
+                .await;
+            if let Err(error) = &delivery {
+                tracing::warn!(%agent_id, %target, %error, "broadcast delivery failed");
+            }
+            results.push(BroadcastTargetResult {
+                adapter: target.adapter,
+                target: Some(target.target),
+                success: delivery.is_ok(),
+                error: delivery.err().map(|error| error.to_string()),
+            });
+        }
+    }
+
+    tracing::info!(
+        %agent_id,
+        delivered = results.iter().filter(|result| result.success).count(),
+        total = results.len(),
+        "broadcast sent"
+    );
+
+    Ok(Json(BroadcastResponse {
+        success: results.iter().all(|result| result.success),
+        skipped: None,
+        results,
+    }))
+}
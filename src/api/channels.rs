@@ -1,11 +1,12 @@
 use super::state::ApiState;
 
 use crate::conversation::channels::ChannelStore;
-use crate::conversation::history::ProcessRunLogger;
+use crate::conversation::history::{ConversationLogger, ProcessRunLogger};
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use rig::completion::CompletionModel as _;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -88,6 +89,25 @@ pub(super) struct CancelProcessResponse {
     message: String,
 }
 
+#[derive(Deserialize)]
+pub(super) struct ResetChannelRequest {
+    agent_id: String,
+    channel_id: String,
+    /// Also forget the memories saved from the channel.
+    #[serde(default)]
+    forget_memories: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct ResetChannelResponse {
+    success: bool,
+    /// False when the channel was not running. Only the reset boundary and
+    /// memory forgetting apply then; there is no live context to clear.
+    live: bool,
+    #[serde(flatten)]
+    reset: crate::agent::channel_reset::ConversationReset,
+}
+
 /// List channels across agents, with optional activity and agent filters.
 pub(super) async fn list_channels(
     State(state): State<Arc<ApiState>>,
@@ -173,6 +193,47 @@ pub(super) async fn channel_messages(
     })
 }
 
+#[derive(Deserialize)]
+pub(super) struct ConversationSearchQuery {
+    q: String,
+    #[serde(default = "default_message_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+#[derive(Serialize)]
+pub(super) struct ConversationSearchResponse {
+    results: Vec<crate::conversation::history::ConversationSearchHit>,
+    has_more: bool,
+}
+
+/// Full-text search over the raw transcripts of all of an agent's channels.
+pub(super) async fn search_conversations(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    Query(query): Query<ConversationSearchQuery>,
+) -> Result<Json<ConversationSearchResponse>, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(&agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+
+    let logger = ConversationLogger::new(pool.clone());
+    let mut results = logger
+        .search(&query.q, limit + 1, offset)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %agent_id, query = %query.q, "conversation search failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let has_more = results.len() as i64 > limit;
+    results.truncate(limit as usize);
+
+    Ok(Json(ConversationSearchResponse { results, has_more }))
+}
+
 /// Get live status (active workers, branches, completed items) for all channels.
 pub(super) async fn channel_status(
     State(state): State<Arc<ApiState>>,
@@ -323,32 +384,14 @@ pub(super) async fn cancel_process(
                 }
             }
 
-            // Fallback for detached workers (for example after restart): no live
-            // channel state exists, but the DB row is still marked running.
-            let pools = state.agent_pools.load();
-            for (_agent_id, pool) in pools.iter() {
-                let logger = ProcessRunLogger::new(pool.clone());
-                match logger.cancel_running_detached_worker(worker_id).await {
-                    Ok(true) => {
-                        return Ok(Json(CancelProcessResponse {
-                            success: true,
-                            message: format!(
-                                "Worker {} cancelled (detached run reconciled)",
-                                request.process_id
-                            ),
-                        }));
-                    }
-                    Ok(false) => {}
-                    Err(error) => {
-                        tracing::warn!(
-                            %error,
-                            channel_id = %request.channel_id,
-                            process_id = %request.process_id,
-                            "failed to cancel detached worker run"
-                        );
-                        return Err(StatusCode::INTERNAL_SERVER_ERROR);
-                    }
-                }
+            if cancel_detached_worker(&state, worker_id).await? {
+                return Ok(Json(CancelProcessResponse {
+                    success: true,
+                    message: format!(
+                        "Worker {} cancelled (detached run reconciled)",
+                        request.process_id
+                    ),
+                }));
             }
 
             Err(StatusCode::NOT_FOUND)
@@ -377,6 +420,219 @@ pub(super) async fn cancel_process(
     }
 }
 
+/// Fallback for detached workers (for example after restart): no live
+/// channel state exists, but the DB row is still marked running. Returns
+/// whether any agent had such a run.
+pub(super) async fn cancel_detached_worker(
+    state: &ApiState,
+    worker_id: crate::WorkerId,
+) -> Result<bool, StatusCode> {
+    let pools = state.agent_pools.load();
+    for (_agent_id, pool) in pools.iter() {
+        let logger = ProcessRunLogger::new(pool.clone());
+        match logger.cancel_running_detached_worker(worker_id).await {
+            Ok(true) => return Ok(true),
+            Ok(false) => {}
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    worker_id = %worker_id,
+                    "failed to cancel detached worker run"
+                );
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[derive(Serialize)]
+pub(super) struct CancelTurnResponse {
+    success: bool,
+    /// False when no turn was running; the call was a no-op.
+    cancelled: bool,
+}
+
+/// Stop the channel's in-flight turn. Replies already sent are kept. Calling
+/// it while no turn is running does nothing.
+pub(super) async fn cancel_turn(
+    State(state): State<Arc<ApiState>>,
+    Path((agent_id, channel_id)): Path<(String, String)>,
+) -> Result<Json<CancelTurnResponse>, StatusCode> {
+    if !state.agent_pools.load().contains_key(&agent_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let channel_state = {
+        let states = state.channel_states.read().await;
+        states
+            .get(&channel_id)
+            .filter(|channel_state| *channel_state.deps.agent_id == agent_id)
+            .cloned()
+    };
+
+    let cancelled = channel_state.is_some_and(|channel_state| channel_state.turn_cancel.cancel());
+    if cancelled {
+        tracing::info!(%agent_id, %channel_id, "channel turn cancelled via API");
+    }
+
+    Ok(Json(CancelTurnResponse {
+        success: true,
+        cancelled,
+    }))
+}
+
+#[derive(Deserialize)]
+pub(super) struct ChannelModelBody {
+    /// Model for this channel, e.g. `openai/gpt-4.1-mini`. Null or empty
+    /// unpins it.
+    #[serde(default)]
+    model: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ChannelModelResponse {
+    channel_id: String,
+    /// Model the channel runs on from its next turn.
+    model: String,
+    /// False when the model comes from the agent's routing.
+    pinned: bool,
+}
+
+/// Pin a model to a channel, overriding the agent's routing for that channel
+/// only, or unpin it. Takes effect from the channel's next turn.
+pub(super) async fn set_channel_model(
+    State(state): State<Arc<ApiState>>,
+    Path((agent_id, channel_id)): Path<(String, String)>,
+    Json(body): Json<ChannelModelBody>,
+) -> Result<Json<ChannelModelResponse>, StatusCode> {
+    let model = body
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty());
+    if let Some(model) = model {
+        let Some((provider, _)) = model
+            .split_once('/')
+            .filter(|(provider, name)| !provider.is_empty() && !name.is_empty())
+        else {
+            return Err(StatusCode::BAD_REQUEST);
+        };
+        // A pin to a provider with no credentials would fail every turn.
+        let llm_manager = state
+            .llm_manager
+            .read()
+            .await
+            .clone()
+            .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+        if !llm_manager.has_provider(provider).await {
+            tracing::info!(%agent_id, %channel_id, %model, "rejected channel model pin for unconfigured provider");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let runtime_configs = state.runtime_configs.load();
+    let rc = runtime_configs
+        .get(&agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let settings = rc.settings.load();
+    let settings = settings.as_ref().as_ref().ok_or_else(|| {
+        tracing::warn!("no settings store available for channel model pin");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    settings
+        .set_channel_model_for(&channel_id, model)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to set channel model");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        %agent_id,
+        %channel_id,
+        model = ?model,
+        "channel model pin updated via API"
+    );
+
+    Ok(Json(ChannelModelResponse {
+        model: crate::agent::channel_prompt::resolve_channel_model(rc, &channel_id),
+        pinned: model.is_some(),
+        channel_id,
+    }))
+}
+
+/// Fully reset a channel's conversation: clear its history, cancel its
+/// workers and branches, and log a reset boundary. A running channel posts a
+/// confirmation to the conversation.
+pub(super) async fn reset_channel(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<ResetChannelRequest>,
+) -> Result<Json<ResetChannelResponse>, StatusCode> {
+    let channel_state = {
+        let states = state.channel_states.read().await;
+        states
+            .get(&request.channel_id)
+            .filter(|channel_state| *channel_state.deps.agent_id == request.agent_id)
+            .cloned()
+    };
+
+    if let Some(channel_state) = channel_state {
+        let reset = crate::agent::channel_reset::reset_conversation(
+            &channel_state,
+            request.forget_memories,
+        )
+        .await;
+        channel_state.reset_signal.request(true);
+        tracing::info!(
+            agent_id = %request.agent_id,
+            channel_id = %request.channel_id,
+            "channel reset via API"
+        );
+        return Ok(Json(ResetChannelResponse {
+            success: true,
+            live: true,
+            reset,
+        }));
+    }
+
+    let pool = state
+        .agent_pools
+        .load()
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut reset = crate::agent::channel_reset::ConversationReset::default();
+    if request.forget_memories {
+        let memory_search = state
+            .memory_searches
+            .load()
+            .get(&request.agent_id)
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND)?;
+        reset.memories_forgotten = memory_search
+            .store()
+            .forget_channel(&request.channel_id)
+            .await
+            .map_err(|error| {
+                tracing::warn!(%error, channel_id = %request.channel_id, "failed to forget channel memories");
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+    ConversationLogger::new(pool).log_reset(&request.channel_id);
+    tracing::info!(
+        agent_id = %request.agent_id,
+        channel_id = %request.channel_id,
+        "inactive channel reset via API"
+    );
+
+    Ok(Json(ResetChannelResponse {
+        success: true,
+        live: false,
+        reset,
+    }))
+}
+
 // ── Prompt Inspect ──────────────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -413,9 +669,6 @@ pub(super) async fn inspect_prompt(
     let identity_context = rc.identity.load().render();
     let memory_bulletin = rc.memory_bulletin.load();
     let skills = rc.skills.load();
-    let skills_prompt = skills
-        .render_channel_prompt(&prompt_engine)
-        .unwrap_or_default();
 
     let browser_enabled = rc.browser_config.load().enabled;
     let web_search_enabled = rc.brave_search_key.load().is_some();
@@ -649,27 +902,78 @@ pub(super) async fn inspect_prompt(
         }
     };
 
-    // ── Render the full system prompt ──
+    // ── Render the full system prompt (with the skill token budget applied) ──
     let empty_to_none = |s: String| if s.is_empty() { None } else { Some(s) };
-    let system_prompt = prompt_engine
-        .render_channel_prompt_with_links(
-            empty_to_none(identity_context),
-            empty_to_none(memory_bulletin.to_string()),
-            empty_to_none(skills_prompt),
-            worker_capabilities,
-            conversation_context,
-            empty_to_none(status_text),
-            None, // coalesce_hint — only set during batched message handling
-            available_channels,
-            sandbox_enabled,
-            org_context,
-            adapter_prompt,
-            project_context,
-            None, // backfill_transcript — only set during channel initialization
-            empty_to_none(working_memory),
-            empty_to_none(channel_activity_map),
-        )
-        .unwrap_or_default();
+    let identity_context = empty_to_none(identity_context);
+    let memory_bulletin = empty_to_none(memory_bulletin.to_string());
+    let status_text = empty_to_none(status_text);
+    let working_memory = empty_to_none(working_memory);
+    let channel_activity_map = empty_to_none(channel_activity_map);
+    let token_budget = **rc.system_prompt_token_budget.load();
+    let output_language =
+        crate::agent::channel_prompt::resolve_output_language(rc, &query.channel_id);
+    let pinned_context =
+        crate::agent::channel_prompt::resolve_pinned_context(rc, &query.channel_id);
+    let conversation_slots =
+        crate::agent::channel_prompt::resolve_conversation_slots(rc, &query.channel_id);
+    let notes_context = crate::agent::channel_prompt::resolve_notes_context(
+        &channel_state.deps.sqlite_pool,
+        rc,
+        &query.channel_id,
+    )
+    .await;
+    let persona = crate::agent::channel_prompt::resolve_persona(rc, &query.channel_id);
+    // Recall against the latest user message to show what the turn pulled in.
+    let recall_query = last_user_text(&channel_state.history.read().await).unwrap_or_default();
+    let recalled = crate::agent::channel_prompt::recall_memories(
+        &channel_state.deps.memory_search,
+        rc,
+        &recall_query,
+    )
+    .await;
+    let recalled_memories = crate::agent::channel_prompt::render_recalled_memories(&recalled);
+    let budgeted = crate::agent::channel_prompt::render_within_budget(
+        &query.channel_id,
+        &skills,
+        &prompt_engine,
+        token_budget,
+        |skills_prompt| {
+            prompt_engine.render_channel_prompt_with_links(
+                identity_context.clone(),
+                memory_bulletin.clone(),
+                skills_prompt,
+                worker_capabilities.clone(),
+                conversation_context.clone(),
+                status_text.clone(),
+                None, // coalesce_hint — only set during batched message handling
+                available_channels.clone(),
+                sandbox_enabled,
+                org_context.clone(),
+                adapter_prompt.clone(),
+                project_context.clone(),
+                None, // backfill_transcript — only set during channel initialization
+                working_memory.clone(),
+                channel_activity_map.clone(),
+                output_language.clone(),
+                pinned_context.clone(),
+                conversation_slots.clone(),
+                notes_context.clone(),
+                recalled_memories.clone(),
+                persona.clone(),
+            )
+        },
+    );
+    let (system_prompt, estimated_tokens, trimmed_skills) = match budgeted {
+        Ok(budgeted) => (
+            budgeted.prompt,
+            budgeted.estimated_tokens,
+            budgeted.trimmed_skills,
+        ),
+        Err(error) => {
+            tracing::warn!(%error, "failed to render system prompt for inspect");
+            (String::new(), 0, Vec::new())
+        }
+    };
 
     let total_chars = system_prompt.chars().count();
 
@@ -689,19 +993,70 @@ pub(super) async fn inspect_prompt(
         .map(|s| s.prompt_capture_enabled(&query.channel_id))
         .unwrap_or(false);
 
+    let show_reasoning = rc
+        .settings
+        .load()
+        .as_ref()
+        .as_ref()
+        .is_some_and(|s| s.show_reasoning_enabled(&query.channel_id));
+
+    let channel_config = rc.channel_config.load();
+    let memory_recall = serde_json::json!({
+        "count": channel_config.memory_recall_count,
+        "order": channel_config.memory_recall_order.as_str(),
+        "memories": recalled
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "id": result.memory.id,
+                    "memory_type": result.memory.memory_type.to_string(),
+                    "content": result.memory.content,
+                    "score": result.score,
+                    "updated_at": result.memory.updated_at.to_rfc3339(),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+
     // ── Build response ──
     let response = serde_json::json!({
         "channel_id": query.channel_id,
         "system_prompt": system_prompt,
         "total_chars": total_chars,
+        "estimated_tokens": estimated_tokens,
+        "token_budget": token_budget,
+        "trimmed_skills": trimmed_skills,
+        "output_language": output_language,
+        "model": crate::agent::channel_prompt::resolve_channel_model(rc, &query.channel_id),
+        "persona": persona.map(|persona| persona.name),
+        "memory_recall": memory_recall,
         "history_length": history.len(),
         "history": history_json,
         "capture_enabled": capture_enabled,
+        "show_reasoning": show_reasoning,
     });
 
     Ok(Json(response))
 }
 
+/// Text of the most recent user message in `history`, skipping tool results.
+fn last_user_text(history: &[rig::message::Message]) -> Option<String> {
+    history.iter().rev().find_map(|message| {
+        let rig::message::Message::User { content } = message else {
+            return None;
+        };
+        let text = content
+            .iter()
+            .filter_map(|content| match content {
+                rig::message::UserContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!text.is_empty()).then_some(text)
+    })
+}
+
 // ── Prompt Capture Toggle ──────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -748,6 +1103,310 @@ pub(super) async fn set_prompt_capture(
     })))
 }
 
+// ── Output Language Override ───────────────────────────────────────
+
+#[derive(Deserialize)]
+pub(super) struct OutputLanguageBody {
+    agent_id: String,
+    channel_id: String,
+    /// Reply language for this channel. Null or empty clears the override.
+    #[serde(default)]
+    language: Option<String>,
+}
+
+/// Set or clear the reply language override for a specific channel.
+pub(super) async fn set_output_language(
+    State(state): State<Arc<ApiState>>,
+    Json(body): Json<OutputLanguageBody>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let rc = runtime_configs
+        .get(&body.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let settings = rc.settings.load();
+    let settings = settings.as_ref().as_ref().ok_or_else(|| {
+        tracing::warn!("no settings store available for output language override");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let language = body
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty());
+    settings
+        .set_output_language_for(&body.channel_id, language)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to set output language override");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        agent_id = %body.agent_id,
+        channel_id = %body.channel_id,
+        language = ?language,
+        "channel output language override updated via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "channel_id": body.channel_id,
+        "output_language": crate::agent::channel_prompt::resolve_output_language(rc, &body.channel_id),
+    })))
+}
+
+// ── Reasoning Visibility ───────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub(super) struct ShowReasoningBody {
+    agent_id: String,
+    channel_id: String,
+    enabled: bool,
+}
+
+/// Show or hide model reasoning in a specific channel.
+pub(super) async fn set_show_reasoning(
+    State(state): State<Arc<ApiState>>,
+    Json(body): Json<ShowReasoningBody>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let rc = runtime_configs
+        .get(&body.agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let settings = rc.settings.load();
+    let settings = settings.as_ref().as_ref().ok_or_else(|| {
+        tracing::warn!("no settings store available for reasoning visibility toggle");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    settings
+        .set_show_reasoning(&body.channel_id, body.enabled)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to set reasoning visibility");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    tracing::info!(
+        agent_id = %body.agent_id,
+        channel_id = %body.channel_id,
+        enabled = body.enabled,
+        "channel reasoning visibility updated via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "channel_id": body.channel_id,
+        "show_reasoning": body.enabled,
+    })))
+}
+
+// ── Pinned Context ─────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub(super) struct PinsQuery {
+    agent_id: String,
+    channel_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct PinContextBody {
+    agent_id: String,
+    channel_id: String,
+    text: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct UnpinContextQuery {
+    agent_id: String,
+    channel_id: String,
+    pin_id: String,
+}
+
+fn settings_for_agent(
+    state: &ApiState,
+    agent_id: &str,
+) -> Result<Arc<crate::settings::SettingsStore>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let rc = runtime_configs.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    rc.settings.load().as_ref().clone().ok_or_else(|| {
+        tracing::warn!(%agent_id, "no settings store available");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// List the snippets pinned to a channel.
+pub(super) async fn list_pins(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<PinsQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &query.agent_id)?;
+    Ok(Json(serde_json::json!({
+        "channel_id": query.channel_id,
+        "pins": settings.pinned_context_for(&query.channel_id),
+    })))
+}
+
+/// Pin a snippet to a channel. It is rendered into the channel prompt from
+/// the next turn on.
+pub(super) async fn pin_context(
+    State(state): State<Arc<ApiState>>,
+    Json(body): Json<PinContextBody>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &body.agent_id)?;
+    let pin = settings
+        .pin_context(&body.channel_id, &body.text)
+        .map_err(|error| match error {
+            crate::error::Error::Settings(ref settings_error)
+                if matches!(
+                    **settings_error,
+                    crate::error::SettingsError::InvalidValue { .. }
+                ) =>
+            {
+                StatusCode::BAD_REQUEST
+            }
+            error => {
+                tracing::warn!(%error, "failed to pin context");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    tracing::info!(
+        agent_id = %body.agent_id,
+        channel_id = %body.channel_id,
+        pin_id = %pin.id,
+        "context pinned via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "channel_id": body.channel_id,
+        "pin": pin,
+    })))
+}
+
+/// Remove a pinned snippet from a channel.
+pub(super) async fn unpin_context(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<UnpinContextQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &query.agent_id)?;
+    let removed = settings
+        .unpin_context(&query.channel_id, &query.pin_id)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to unpin context");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !removed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(
+        agent_id = %query.agent_id,
+        channel_id = %query.channel_id,
+        pin_id = %query.pin_id,
+        "context unpinned via API"
+    );
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// ── Conversation Slots ─────────────────────────────────────────────
+
+#[derive(Deserialize)]
+pub(super) struct SlotsQuery {
+    agent_id: String,
+    channel_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetSlotBody {
+    agent_id: String,
+    channel_id: String,
+    key: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ClearSlotQuery {
+    agent_id: String,
+    channel_id: String,
+    key: String,
+}
+
+/// List the slots set in a channel.
+pub(super) async fn list_slots(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<SlotsQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &query.agent_id)?;
+    Ok(Json(serde_json::json!({
+        "channel_id": query.channel_id,
+        "slots": settings.conversation_slots_for(&query.channel_id),
+    })))
+}
+
+/// Set a slot in a channel, replacing any previous value.
+pub(super) async fn set_slot(
+    State(state): State<Arc<ApiState>>,
+    Json(body): Json<SetSlotBody>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &body.agent_id)?;
+    let slot = settings
+        .set_conversation_slot(&body.channel_id, &body.key, &body.value)
+        .map_err(|error| match error {
+            crate::error::Error::Settings(ref settings_error)
+                if matches!(
+                    **settings_error,
+                    crate::error::SettingsError::InvalidValue { .. }
+                ) =>
+            {
+                StatusCode::BAD_REQUEST
+            }
+            error => {
+                tracing::warn!(%error, "failed to set conversation slot");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+
+    tracing::info!(
+        agent_id = %body.agent_id,
+        channel_id = %body.channel_id,
+        slot = %body.key.trim(),
+        "conversation slot set via API"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "channel_id": body.channel_id,
+        "key": body.key.trim(),
+        "slot": slot,
+    })))
+}
+
+/// Clear a slot from a channel.
+pub(super) async fn clear_slot(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ClearSlotQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let settings = settings_for_agent(&state, &query.agent_id)?;
+    let removed = settings
+        .clear_conversation_slot(&query.channel_id, &query.key)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to clear conversation slot");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !removed {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    tracing::info!(
+        agent_id = %query.agent_id,
+        channel_id = %query.channel_id,
+        slot = %query.key,
+        "conversation slot cleared via API"
+    );
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
 // ── Prompt Snapshot History ────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -807,6 +1466,124 @@ pub(super) async fn get_prompt_snapshot(
     }
 }
 
+#[derive(Deserialize)]
+pub(super) struct ReplayLastTurnRequest {
+    agent_id: String,
+    channel_id: String,
+    /// Run the replay against this model instead of the one recorded in the
+    /// snapshot.
+    #[serde(default)]
+    model: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ReplayToolCall {
+    name: String,
+    arguments: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub(super) struct ReplayLastTurnResponse {
+    channel_id: String,
+    snapshot_timestamp_ms: i64,
+    model: String,
+    user_message: String,
+    output: String,
+    /// Tool calls the model made in response. They are not executed.
+    tool_calls: Vec<ReplayToolCall>,
+}
+
+/// Re-run the most recent captured turn for a channel and return the model
+/// output. The response is never posted to the messaging platform and the
+/// channel's live history is untouched.
+///
+/// Only turns with prompt capture enabled can be replayed. The request carries
+/// the tool definitions captured with the turn, so the model sees the same
+/// request it did live. Tool calls it makes are returned, not executed, so a
+/// replay never re-runs side effects.
+pub(super) async fn replay_last_turn(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<ReplayLastTurnRequest>,
+) -> Result<Json<ReplayLastTurnResponse>, StatusCode> {
+    let runtime_config = state
+        .runtime_configs
+        .load()
+        .get(&request.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let llm_manager = state
+        .llm_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let snapshot_store = find_snapshot_store(&state, &request.channel_id).await?;
+    let snapshot = snapshot_store
+        .latest(&request.channel_id)
+        .map_err(|error| {
+            tracing::warn!(%error, "failed to load latest prompt snapshot");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let history: Vec<rig::message::Message> =
+        serde_json::from_value(snapshot.history).map_err(|error| {
+            tracing::warn!(%error, "failed to deserialize snapshot history");
+            StatusCode::UNPROCESSABLE_ENTITY
+        })?;
+
+    let routing = runtime_config.routing.load();
+    let model_name = request
+        .model
+        .filter(|model| !model.trim().is_empty())
+        .or(snapshot.model)
+        .unwrap_or_else(|| {
+            routing
+                .resolve(crate::ProcessType::Channel, None)
+                .to_string()
+        });
+
+    let model = crate::llm::SpacebotModel::make(&llm_manager, &model_name)
+        .with_context(&request.agent_id, "channel_replay")
+        .with_routing((**routing).clone());
+    let response = model
+        .completion_request(snapshot.user_message.as_str())
+        .preamble(snapshot.system_prompt)
+        .messages(history)
+        .tools(snapshot.tools)
+        .send()
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, channel_id = %request.channel_id, "turn replay failed");
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let mut output = Vec::new();
+    let mut tool_calls = Vec::new();
+    for content in response.choice.iter() {
+        match content {
+            rig::message::AssistantContent::Text(text) => output.push(text.text.clone()),
+            rig::message::AssistantContent::ToolCall(tool_call) => {
+                tool_calls.push(ReplayToolCall {
+                    name: tool_call.function.name.clone(),
+                    arguments: tool_call.function.arguments.clone(),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Json(ReplayLastTurnResponse {
+        channel_id: request.channel_id,
+        snapshot_timestamp_ms: snapshot.timestamp_ms,
+        model: model_name,
+        user_message: snapshot.user_message,
+        output: output.join("\n"),
+        tool_calls,
+    }))
+}
+
 /// Find the prompt snapshot store for a channel.
 async fn find_snapshot_store(
     state: &ApiState,
@@ -22,6 +22,7 @@ pub(super) struct RoutingSection {
 pub(super) struct TuningSection {
     max_concurrent_branches: usize,
     max_concurrent_workers: usize,
+    max_concurrent_processes: usize,
     max_turns: usize,
     branch_max_turns: usize,
     context_window: usize,
@@ -33,6 +34,8 @@ pub(super) struct CompactionSection {
     background_threshold: f32,
     aggressive_threshold: f32,
     emergency_threshold: f32,
+    summary_max_tokens: Option<usize>,
+    max_turns_before_compact: Option<usize>,
 }
 
 #[derive(Serialize, Debug)]
@@ -57,6 +60,7 @@ pub(super) struct CortexSection {
 pub(super) struct WarmupSection {
     enabled: bool,
     eager_embedding_load: bool,
+    preload_models: bool,
     refresh_secs: u64,
     startup_delay_secs: u64,
 }
@@ -178,6 +182,7 @@ pub(super) struct RoutingUpdate {
 pub(super) struct TuningUpdate {
     max_concurrent_branches: Option<usize>,
     max_concurrent_workers: Option<usize>,
+    max_concurrent_processes: Option<usize>,
     max_turns: Option<usize>,
     branch_max_turns: Option<usize>,
     context_window: Option<usize>,
@@ -189,6 +194,8 @@ pub(super) struct CompactionUpdate {
     background_threshold: Option<f32>,
     aggressive_threshold: Option<f32>,
     emergency_threshold: Option<f32>,
+    summary_max_tokens: Option<usize>,
+    max_turns_before_compact: Option<usize>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -213,6 +220,7 @@ pub(super) struct CortexUpdate {
 pub(super) struct WarmupUpdate {
     enabled: Option<bool>,
     eager_embedding_load: Option<bool>,
+    preload_models: Option<bool>,
     refresh_secs: Option<u64>,
     startup_delay_secs: Option<u64>,
 }
@@ -303,6 +311,7 @@ pub(super) async fn get_agent_config(
         tuning: TuningSection {
             max_concurrent_branches: **rc.max_concurrent_branches.load(),
             max_concurrent_workers: **rc.max_concurrent_workers.load(),
+            max_concurrent_processes: **rc.max_concurrent_processes.load(),
             max_turns: **rc.max_turns.load(),
             branch_max_turns: **rc.branch_max_turns.load(),
             context_window: **rc.context_window.load(),
@@ -312,6 +321,8 @@ pub(super) async fn get_agent_config(
             background_threshold: compaction.background_threshold,
             aggressive_threshold: compaction.aggressive_threshold,
             emergency_threshold: compaction.emergency_threshold,
+            summary_max_tokens: compaction.summary_max_tokens,
+            max_turns_before_compact: compaction.max_turns_before_compact,
         },
         cortex: CortexSection {
             tick_interval_secs: cortex.tick_interval_secs,
@@ -332,6 +343,7 @@ pub(super) async fn get_agent_config(
         warmup: WarmupSection {
             enabled: warmup.enabled,
             eager_embedding_load: warmup.eager_embedding_load,
+            preload_models: warmup.preload_models,
             refresh_secs: warmup.refresh_secs,
             startup_delay_secs: warmup.startup_delay_secs,
         },
@@ -622,6 +634,9 @@ fn update_tuning_table(
     if let Some(v) = tuning.max_concurrent_workers {
         agent["max_concurrent_workers"] = toml_edit::value(v as i64);
     }
+    if let Some(v) = tuning.max_concurrent_processes {
+        agent["max_concurrent_processes"] = toml_edit::value(v as i64);
+    }
     if let Some(v) = tuning.max_turns {
         agent["max_turns"] = toml_edit::value(v as i64);
     }
@@ -653,6 +668,12 @@ fn update_compaction_table(
     if let Some(v) = compaction.emergency_threshold {
         table["emergency_threshold"] = toml_edit::value(v as f64);
     }
+    if let Some(v) = compaction.summary_max_tokens {
+        table["summary_max_tokens"] = toml_edit::value(v as i64);
+    }
+    if let Some(v) = compaction.max_turns_before_compact {
+        table["max_turns_before_compact"] = toml_edit::value(v as i64);
+    }
     Ok(())
 }
 
@@ -789,6 +810,9 @@ fn update_warmup_table(
     if let Some(v) = warmup.eager_embedding_load {
         table["eager_embedding_load"] = toml_edit::value(v);
     }
+    if let Some(v) = warmup.preload_models {
+        table["preload_models"] = toml_edit::value(v);
+    }
     if let Some(v) = warmup.refresh_secs {
         table["refresh_secs"] =
             toml_edit::value(i64::try_from(v).map_err(|_| StatusCode::BAD_REQUEST)?);
@@ -950,6 +974,7 @@ id = "main"
         let update = WarmupUpdate {
             enabled: Some(false),
             eager_embedding_load: Some(false),
+            preload_models: Some(true),
             refresh_secs: Some(300),
             startup_delay_secs: Some(7),
         };
@@ -968,6 +993,7 @@ id = "main"
 
         assert_eq!(warmup["enabled"].as_bool(), Some(false));
         assert_eq!(warmup["eager_embedding_load"].as_bool(), Some(false));
+        assert_eq!(warmup["preload_models"].as_bool(), Some(true));
         assert_eq!(warmup["refresh_secs"].as_integer(), Some(300));
         assert_eq!(warmup["startup_delay_secs"].as_integer(), Some(7));
     }
@@ -986,6 +1012,7 @@ id = "main"
         let update = WarmupUpdate {
             enabled: Some(true),
             eager_embedding_load: None,
+            preload_models: None,
             refresh_secs: None,
             startup_delay_secs: None,
         };
@@ -1004,6 +1031,7 @@ id = "main"
 
         assert_eq!(warmup["enabled"].as_bool(), Some(true));
         assert!(warmup.get("eager_embedding_load").is_none());
+        assert!(warmup.get("preload_models").is_none());
         assert!(warmup.get("refresh_secs").is_none());
         assert!(warmup.get("startup_delay_secs").is_none());
     }
@@ -1022,6 +1050,7 @@ id = "main"
         let update = WarmupUpdate {
             enabled: None,
             eager_embedding_load: None,
+            preload_models: None,
             refresh_secs: Some(u64::MAX),
             startup_delay_secs: None,
         };
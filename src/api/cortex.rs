@@ -4,9 +4,10 @@ use crate::agent::cortex::{CortexEvent, CortexLogger};
 use crate::agent::cortex_chat::{
     CortexChatEvent, CortexChatMessage, CortexChatSendError, CortexChatStore, CortexChatThread,
 };
+use crate::settings::BulletinOverride;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::Sse;
 use futures::stream::Stream;
@@ -62,6 +63,24 @@ fn default_cortex_events_limit() -> i64 {
     50
 }
 
+#[derive(Serialize)]
+pub(super) struct BulletinResponse {
+    agent_id: String,
+    bulletin: String,
+    /// Seconds since the bulletin was generated or set by hand.
+    age_secs: Option<u64>,
+    /// The manual bulletin in effect, if one was set.
+    bulletin_override: Option<BulletinOverride>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct SetBulletinRequest {
+    bulletin: String,
+    /// Keep the bulletin across cortex refreshes until it is cleared.
+    #[serde(default)]
+    permanent: bool,
+}
+
 fn map_cortex_chat_send_error(error: &CortexChatSendError) -> StatusCode {
     match error {
         CortexChatSendError::Busy => StatusCode::CONFLICT,
@@ -246,6 +265,115 @@ pub(super) async fn cortex_events(
     Ok(Json(CortexEventsResponse { events, total }))
 }
 
+fn bulletin_response(
+    agent_id: String,
+    runtime_config: &crate::config::RuntimeConfig,
+    bulletin_override: Option<BulletinOverride>,
+) -> BulletinResponse {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let refreshed_ms = match &bulletin_override {
+        Some(bulletin_override) => Some(bulletin_override.set_at.timestamp_millis()),
+        None => runtime_config.warmup_status.load().last_refresh_unix_ms,
+    };
+    BulletinResponse {
+        agent_id,
+        bulletin: runtime_config.memory_bulletin.load().to_string(),
+        age_secs: super::agents::compute_bulletin_age_secs(refreshed_ms, now_ms),
+        bulletin_override,
+    }
+}
+
+fn settings_for_bulletin(
+    runtime_config: &crate::config::RuntimeConfig,
+) -> Result<Arc<crate::settings::SettingsStore>, StatusCode> {
+    runtime_config
+        .settings
+        .load()
+        .as_ref()
+        .clone()
+        .ok_or_else(|| {
+            tracing::warn!("no settings store available for memory bulletin override");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+/// Get the memory bulletin currently injected into the agent's prompts.
+pub(super) async fn get_bulletin(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<BulletinResponse>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let runtime_config = runtime_configs
+        .get(&agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let bulletin_override = settings_for_bulletin(runtime_config)?.memory_bulletin_override();
+    Ok(Json(bulletin_response(
+        agent_id,
+        runtime_config,
+        bulletin_override,
+    )))
+}
+
+/// Replace the memory bulletin by hand. The override is persisted and holds
+/// until the next cortex refresh, or until cleared when `permanent` is set.
+pub(super) async fn set_bulletin(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    Json(request): Json<SetBulletinRequest>,
+) -> Result<Json<BulletinResponse>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let runtime_config = runtime_configs
+        .get(&agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let settings = settings_for_bulletin(runtime_config)?;
+
+    let bulletin_override = BulletinOverride {
+        bulletin: request.bulletin.trim().to_string(),
+        permanent: request.permanent,
+        set_at: chrono::Utc::now(),
+    };
+    settings
+        .set_memory_bulletin_override(Some(&bulletin_override))
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %agent_id, "failed to persist memory bulletin override");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    runtime_config
+        .memory_bulletin
+        .store(Arc::new(bulletin_override.bulletin.clone()));
+    tracing::info!(
+        agent_id = %agent_id,
+        permanent = bulletin_override.permanent,
+        "memory bulletin overridden via API"
+    );
+
+    Ok(Json(bulletin_response(
+        agent_id,
+        runtime_config,
+        Some(bulletin_override),
+    )))
+}
+
+/// Drop a manual bulletin. The current text stays in place until the next
+/// cortex refresh replaces it.
+pub(super) async fn clear_bulletin_override(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<BulletinResponse>, StatusCode> {
+    let runtime_configs = state.runtime_configs.load();
+    let runtime_config = runtime_configs
+        .get(&agent_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    settings_for_bulletin(runtime_config)?
+        .set_memory_bulletin_override(None)
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %agent_id, "failed to clear memory bulletin override");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(bulletin_response(agent_id, runtime_config, None)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::map_cortex_chat_send_error;
@@ -1,7 +1,7 @@
 use super::state::ApiState;
 
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -82,12 +82,18 @@ struct CronJobWithStats {
     interval_secs: u64,
     delivery_target: String,
     enabled: bool,
+    /// Paused through the API. Independent of `enabled`.
+    paused: bool,
     run_once: bool,
     active_hours: Option<(u8, u8)>,
     timeout_secs: Option<u64>,
+    /// Next scheduled run (RFC 3339), if the job's timer is running.
+    next_run_at: Option<String>,
     success_count: u64,
     failure_count: u64,
     last_executed_at: Option<String>,
+    /// Most recent run, including per-target delivery outcomes.
+    last_run: Option<crate::cron::CronExecutionEntry>,
 }
 
 #[derive(Serialize)]
@@ -107,6 +113,16 @@ pub(super) struct CronActionResponse {
     message: String,
 }
 
+#[derive(Serialize)]
+pub(super) struct CronPauseResponse {
+    success: bool,
+    cron_id: String,
+    paused: bool,
+    /// Next scheduled run (RFC 3339). `None` while paused or disabled.
+    next_run_at: Option<String>,
+    message: String,
+}
+
 /// List all cron jobs for an agent with execution statistics.
 pub(super) async fn list_cron_jobs(
     State(state): State<Arc<ApiState>>,
@@ -123,6 +139,10 @@ pub(super) async fn list_cron_jobs(
         tracing::warn!(%error, agent_id = %query.agent_id, "failed to load cron jobs");
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    let paused_ids = store.paused_ids().await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, "failed to load paused cron jobs");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     let mut jobs = Vec::new();
     for config in configs {
@@ -130,6 +150,14 @@ pub(super) async fn list_cron_jobs(
             .get_execution_stats(&config.id)
             .await
             .unwrap_or_default();
+        let last_run = store.last_run(&config.id).await.unwrap_or_else(|error| {
+            tracing::warn!(%error, cron_id = %config.id, "failed to load last cron run");
+            None
+        });
+        let next_run_at = scheduler
+            .next_run_at(&config.id)
+            .await
+            .map(|next_run| next_run.to_rfc3339());
 
         jobs.push(CronJobWithStats {
             id: config.id,
@@ -137,13 +165,16 @@ pub(super) async fn list_cron_jobs(
             cron_expr: config.cron_expr,
             interval_secs: config.interval_secs,
             delivery_target: config.delivery_target,
+            paused: paused_ids.contains(&config.id),
             enabled: config.enabled,
             run_once: config.run_once,
             active_hours: config.active_hours,
             timeout_secs: config.timeout_secs,
+            next_run_at,
             success_count: stats.success_count,
             failure_count: stats.failure_count,
             last_executed_at: stats.last_executed_at,
+            last_run,
         });
     }
 
@@ -340,6 +371,23 @@ pub(super) async fn create_or_update_cron(
         cron_err(StatusCode::INTERNAL_SERVER_ERROR, format!("failed to save: {error}"))
     })?;
 
+    // Saving doesn't clear a pause, so keep a paused job's timer stopped.
+    let paused = store
+        .paused_ids()
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to load paused cron jobs");
+            cron_err(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to save: {error}"),
+            )
+        })?
+        .contains(&config.id);
+    let config = crate::cron::CronConfig {
+        enabled: config.enabled && !paused,
+        ..config
+    };
+
     scheduler.register(config).await.map_err(|error| {
         tracing::warn!(%error, agent_id = %request.agent_id, cron_id = %request.id, "failed to register cron job");
         cron_err(StatusCode::INTERNAL_SERVER_ERROR, format!("failed to register: {error}"))
@@ -416,6 +464,14 @@ pub(super) async fn toggle_cron(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    // Enabling a job also lifts any pause so it actually starts running.
+    if request.enabled {
+        store.set_paused(&request.cron_id, false).await.map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, cron_id = %request.cron_id, "failed to clear cron job paused state");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+
     scheduler.set_enabled(&request.cron_id, request.enabled).await.map_err(|error| {
         tracing::warn!(%error, agent_id = %request.agent_id, cron_id = %request.cron_id, "failed to update scheduler enabled state");
         StatusCode::INTERNAL_SERVER_ERROR
@@ -431,3 +487,77 @@ pub(super) async fn toggle_cron(
         message: format!("Cron job '{}' {}", request.cron_id, status),
     }))
 }
+
+/// Pause a cron job. The pause is persisted and survives restarts.
+pub(super) async fn pause_cron(
+    State(state): State<Arc<ApiState>>,
+    Path((agent_id, cron_id)): Path<(String, String)>,
+) -> Result<Json<CronPauseResponse>, StatusCode> {
+    set_cron_paused(&state, &agent_id, &cron_id, true).await
+}
+
+/// Resume a paused cron job, scheduling its next run from now.
+pub(super) async fn resume_cron(
+    State(state): State<Arc<ApiState>>,
+    Path((agent_id, cron_id)): Path<(String, String)>,
+) -> Result<Json<CronPauseResponse>, StatusCode> {
+    set_cron_paused(&state, &agent_id, &cron_id, false).await
+}
+
+async fn set_cron_paused(
+    state: &ApiState,
+    agent_id: &str,
+    cron_id: &str,
+    paused: bool,
+) -> Result<Json<CronPauseResponse>, StatusCode> {
+    let stores = state.cron_stores.load();
+    let store = stores.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let schedulers = state.cron_schedulers.load();
+    let scheduler = schedulers.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let configs = store.load_all_unfiltered().await.map_err(|error| {
+        tracing::warn!(%error, %agent_id, %cron_id, "failed to load cron jobs");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let config = configs
+        .into_iter()
+        .find(|config| config.id == cron_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    store.set_paused(cron_id, paused).await.map_err(|error| {
+        tracing::warn!(%error, %agent_id, %cron_id, "failed to update cron job paused state");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // A disabled job stays stopped on resume; only the pause is lifted.
+    scheduler
+        .set_enabled(cron_id, !paused && config.enabled)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, %cron_id, "failed to update scheduler paused state");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let next_run_at = scheduler
+        .next_run_at(cron_id)
+        .await
+        .map(|next_run| next_run.to_rfc3339());
+    let message = if paused {
+        format!("Cron job '{cron_id}' paused")
+    } else if config.enabled {
+        format!("Cron job '{cron_id}' resumed")
+    } else {
+        format!("Cron job '{cron_id}' resumed but is disabled, so it will not run until enabled")
+    };
+
+    tracing::info!(%agent_id, %cron_id, paused, "cron job pause state changed");
+
+    Ok(Json(CronPauseResponse {
+        success: true,
+        cron_id: cron_id.to_string(),
+        paused,
+        next_run_at,
+        message,
+    }))
+}
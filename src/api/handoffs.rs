@@ -0,0 +1,99 @@
+use super::state::ApiState;
+use crate::handoff::{Handoff, HandoffStore};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct HandoffQuery {
+    agent_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct ResolveHandoffRequest {
+    agent_id: String,
+    channel_id: String,
+    /// What the human did, kept with the handoff for later review.
+    #[serde(default)]
+    resolution: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct HandoffListResponse {
+    handoffs: Vec<Handoff>,
+}
+
+#[derive(Serialize)]
+pub(super) struct HandoffResponse {
+    handoff: Handoff,
+}
+
+fn handoff_store(state: &ApiState, agent_id: &str) -> Result<HandoffStore, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(HandoffStore::new(pool.clone()))
+}
+
+/// List the agent's open handoffs, oldest first.
+pub(super) async fn list_handoffs(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<HandoffQuery>,
+) -> Result<Json<HandoffListResponse>, StatusCode> {
+    let store = handoff_store(&state, &query.agent_id)?;
+    let handoffs = store.list_open().await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, "failed to list handoffs");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(HandoffListResponse { handoffs }))
+}
+
+/// Resolve a channel's open handoff so the agent replies there again.
+pub(super) async fn resolve_handoff(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<ResolveHandoffRequest>,
+) -> Result<Json<HandoffResponse>, StatusCode> {
+    let store = handoff_store(&state, &request.agent_id)?;
+    let resolution = request
+        .resolution
+        .as_deref()
+        .map(str::trim)
+        .filter(|resolution| !resolution.is_empty());
+    let handoff = store
+        .resolve(&request.channel_id, resolution)
+        .await
+        .map_err(|error| {
+            tracing::warn!(
+                %error,
+                agent_id = %request.agent_id,
+                channel_id = %request.channel_id,
+                "failed to resolve handoff"
+            );
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // A running channel caches its handoff state; let it reply again. Only
+    // the agent whose handoff was resolved owns that channel state.
+    if let Some(channel_state) = state
+        .channel_states
+        .read()
+        .await
+        .get(&request.channel_id)
+        .filter(|channel_state| *channel_state.deps.agent_id == request.agent_id)
+    {
+        channel_state.handoff.set(false);
+    }
+
+    tracing::info!(
+        agent_id = %request.agent_id,
+        channel_id = %request.channel_id,
+        handoff_id = %handoff.id,
+        "handoff resolved via API"
+    );
+
+    Ok(Json(HandoffResponse { handoff }))
+}
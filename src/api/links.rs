@@ -650,6 +650,7 @@ pub struct CreateHumanRequest {
     pub telegram_id: Option<String>,
     pub slack_id: Option<String>,
     pub email: Option<String>,
+    pub handoff_target: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -662,6 +663,7 @@ pub struct UpdateHumanRequest {
     pub telegram_id: Option<String>,
     pub slack_id: Option<String>,
     pub email: Option<String>,
+    pub handoff_target: Option<String>,
 }
 
 /// List all humans.
@@ -711,6 +713,9 @@ fn ensure_humans_in_doc(doc: &mut toml_edit::DocumentMut, humans: &[crate::confi
         if let Some(ref email) = human.email {
             table["email"] = toml_edit::value(email.as_str());
         }
+        if let Some(ref handoff_target) = human.handoff_target {
+            table["handoff_target"] = toml_edit::value(handoff_target.as_str());
+        }
         humans_array.push(table);
     }
     doc["humans"] = toml_edit::Item::ArrayOfTables(humans_array);
@@ -795,6 +800,11 @@ pub async fn create_human(
     {
         table["email"] = toml_edit::value(email.as_str());
     }
+    if let Some(handoff_target) = &request.handoff_target
+        && !handoff_target.is_empty()
+    {
+        table["handoff_target"] = toml_edit::value(handoff_target.as_str());
+    }
     humans_array.push(table);
 
     tokio::fs::write(&config_path, doc.to_string())
@@ -834,6 +844,7 @@ pub async fn create_human(
         telegram_id: request.telegram_id.clone().filter(|s| !s.is_empty()),
         slack_id: request.slack_id.clone().filter(|s| !s.is_empty()),
         email: request.email.clone().filter(|s| !s.is_empty()),
+        handoff_target: request.handoff_target.clone().filter(|s| !s.is_empty()),
     };
     let mut humans = (**existing).clone();
     humans.push(new_human.clone());
@@ -916,6 +927,13 @@ pub async fn update_human(
             Some(email.clone())
         };
     }
+    if let Some(handoff_target) = &request.handoff_target {
+        updated.handoff_target = if handoff_target.is_empty() {
+            None
+        } else {
+            Some(handoff_target.clone())
+        };
+    }
 
     let config_path = state.config_path.read().await.clone();
     let content = tokio::fs::read_to_string(&config_path)
@@ -973,6 +991,11 @@ pub async fn update_human(
                 } else if request.email.is_some() {
                     table.remove("email");
                 }
+                if let Some(handoff_target) = &updated.handoff_target {
+                    table["handoff_target"] = toml_edit::value(handoff_target.as_str());
+                } else if request.handoff_target.is_some() {
+                    table.remove("handoff_target");
+                }
                 break;
             }
         }
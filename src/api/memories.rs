@@ -1,14 +1,21 @@
 use super::state::ApiState;
 
 use crate::memory::search::{SearchConfig, SearchMode};
+use crate::memory::snapshot::{self, RestoreSummary};
 use crate::memory::types::{Association, Memory, MemorySearchResult, MemoryType};
 
 use axum::Json;
-use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Upload limit for memory snapshots. Vectors make them far larger than the
+/// API's default body limit.
+pub(super) const MAX_MEMORY_SNAPSHOT_UPLOAD_BYTES: usize = 256 * 1024 * 1024;
+
 #[derive(Serialize)]
 pub(super) struct MemoriesListResponse {
     memories: Vec<Memory>,
@@ -33,6 +40,12 @@ pub(super) struct MemoryGraphNeighborsResponse {
     edges: Vec<Association>,
 }
 
+#[derive(Serialize)]
+pub(super) struct MemoryDeleteResponse {
+    memory_id: String,
+    deleted: bool,
+}
+
 #[derive(Deserialize)]
 pub(super) struct MemoriesListQuery {
     agent_id: String,
@@ -122,6 +135,26 @@ fn default_neighbor_depth() -> u32 {
     1
 }
 
+#[derive(Deserialize)]
+pub(super) struct MemoryDeleteQuery {
+    agent_id: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct MemoryImportQuery {
+    /// Wipe the agent's existing memories before restoring.
+    #[serde(default)]
+    replace: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct MemoryImportResponse {
+    agent_id: String,
+    replaced: bool,
+    #[serde(flatten)]
+    summary: RestoreSummary,
+}
+
 /// List memories for an agent with sorting, filtering, and pagination.
 pub(super) async fn list_memories(
     State(state): State<Arc<ApiState>>,
@@ -245,3 +278,159 @@ pub(super) async fn memory_graph_neighbors(
 
     Ok(Json(MemoryGraphNeighborsResponse { nodes, edges }))
 }
+
+/// Permanently delete a memory along with its graph edges and embedding.
+pub(super) async fn delete_memory(
+    State(state): State<Arc<ApiState>>,
+    Path(memory_id): Path<String>,
+    Query(query): Query<MemoryDeleteQuery>,
+) -> Result<Json<MemoryDeleteResponse>, StatusCode> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&query.agent_id).ok_or(StatusCode::NOT_FOUND)?;
+
+    let deleted = memory_search.purge(&memory_id).await.map_err(|error| {
+        tracing::warn!(%error, agent_id = %query.agent_id, %memory_id, "failed to delete memory");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !deleted {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    if let Some(runtime_config) = state.runtime_configs.load().get(&query.agent_id) {
+        runtime_config.bump_knowledge_synthesis_version();
+    }
+
+    state.send_event(super::state::ApiEvent::MemoryDeleted {
+        agent_id: query.agent_id,
+        memory_id: memory_id.clone(),
+    });
+
+    Ok(Json(MemoryDeleteResponse { memory_id, deleted }))
+}
+
+/// Export an agent's entire memory store (memories, graph edges, and vectors)
+/// as a gzipped JSON snapshot.
+pub(super) async fn export_memory_snapshot(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let searches = state.memory_searches.load();
+    let memory_search = searches.get(&agent_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("agent '{agent_id}' not found"),
+        )
+    })?;
+
+    let memory_snapshot = snapshot::export_snapshot(memory_search, &agent_id)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, "failed to export memory snapshot");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("memory export failed: {error}"),
+            )
+        })?;
+    let memory_count = memory_snapshot.memories.len();
+    let snapshot_bytes =
+        tokio::task::spawn_blocking(move || snapshot::encode_snapshot(&memory_snapshot))
+            .await
+            .map_err(|error| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("export task failed: {error}"),
+                )
+            })?
+            .map_err(|error| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("memory export failed: {error}"),
+                )
+            })?;
+
+    tracing::info!(%agent_id, memory_count, bytes = snapshot_bytes.len(), "memory snapshot exported");
+
+    let disposition = format!(
+        "attachment; filename={}-memory.json.gz",
+        super::bundles::sanitize_file_stem(&agent_id)
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        snapshot_bytes,
+    ))
+}
+
+/// Restore a memory snapshot into an agent. Vectors are reused when the
+/// snapshot was embedded the same way as this agent, and re-embedded otherwise.
+pub(super) async fn import_memory_snapshot(
+    State(state): State<Arc<ApiState>>,
+    Path(agent_id): Path<String>,
+    Query(query): Query<MemoryImportQuery>,
+    body: Bytes,
+) -> Result<Json<MemoryImportResponse>, (StatusCode, String)> {
+    let memory_search = state
+        .memory_searches
+        .load()
+        .get(&agent_id)
+        .cloned()
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("agent '{agent_id}' not found"),
+            )
+        })?;
+    if body.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "snapshot payload is empty".to_string(),
+        ));
+    }
+
+    let memory_snapshot = tokio::task::spawn_blocking(move || snapshot::decode_snapshot(&body))
+        .await
+        .map_err(|error| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("import task failed: {error}"),
+            )
+        })?
+        .map_err(|error| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("invalid snapshot: {error:#}"),
+            )
+        })?;
+    let source_agent_id = memory_snapshot.source_agent_id.clone();
+
+    let summary = snapshot::restore_snapshot(&memory_search, memory_snapshot, query.replace)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, %agent_id, "failed to restore memory snapshot");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("memory import failed: {error}"),
+            )
+        })?;
+
+    if let Some(runtime_config) = state.runtime_configs.load().get(&agent_id) {
+        runtime_config.bump_knowledge_synthesis_version();
+    }
+
+    tracing::info!(
+        %agent_id,
+        %source_agent_id,
+        replaced = query.replace,
+        memories = summary.memories,
+        reembedded = summary.reembedded,
+        "memory snapshot imported"
+    );
+
+    Ok(Json(MemoryImportResponse {
+        agent_id,
+        replaced: query.replace,
+        summary,
+    }))
+}
@@ -895,7 +895,8 @@ pub(super) async fn toggle_platform(
                                 "discord",
                                 &discord_config.token,
                                 perms,
-                            );
+                            )
+                            .with_stream_edits(discord_config.stream_edit_interval());
                             if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, "failed to start discord adapter on toggle");
                             }
@@ -920,7 +921,8 @@ pub(super) async fn toggle_platform(
                                 runtime_key,
                                 &instance.token,
                                 perms,
-                            );
+                            )
+                            .with_stream_edits(discord_config.stream_edit_interval());
                             if let Err(error) = manager.register_and_start(adapter).await {
                                 tracing::error!(%error, adapter = %instance.name, "failed to start named discord adapter on toggle");
                             }
@@ -1066,6 +1068,10 @@ pub(super) async fn toggle_platform(
                             webhook_config.port,
                             &webhook_config.bind,
                             webhook_config.auth_token.clone(),
+                        )
+                        .with_signing_secret(
+                            webhook_config.signing_secret.clone(),
+                            &webhook_config.signature_header,
                         );
                         if let Err(error) = manager.register_and_start(adapter).await {
                             tracing::error!(%error, "failed to start webhook adapter on toggle");
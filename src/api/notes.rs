@@ -0,0 +1,126 @@
+use super::state::ApiState;
+use crate::notes::{MAX_NOTE_CHARS, Note, NotesStore};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub(super) struct NoteQuery {
+    agent_id: String,
+    /// Omit for the agent-wide note.
+    #[serde(default)]
+    channel_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct WriteNoteRequest {
+    agent_id: String,
+    #[serde(default)]
+    channel_id: Option<String>,
+    content: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct NoteResponse {
+    note: Option<Note>,
+}
+
+fn notes_store(state: &ApiState, agent_id: &str) -> Result<NotesStore, StatusCode> {
+    let pools = state.agent_pools.load();
+    let pool = pools.get(agent_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(NotesStore::new(pool.clone()))
+}
+
+/// Read the agent-wide note, or a channel's note when `channel_id` is set.
+pub(super) async fn get_note(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<NoteQuery>,
+) -> Result<Json<NoteResponse>, StatusCode> {
+    let store = notes_store(&state, &query.agent_id)?;
+    let note = store
+        .read(query.channel_id.as_deref())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to read note");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(NoteResponse { note }))
+}
+
+/// Overwrite a note.
+pub(super) async fn replace_note(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<WriteNoteRequest>,
+) -> Result<Json<NoteResponse>, StatusCode> {
+    if request.content.chars().count() > MAX_NOTE_CHARS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let store = notes_store(&state, &request.agent_id)?;
+    let note = store
+        .replace(request.channel_id.as_deref(), &request.content)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to replace note");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(NoteResponse { note: Some(note) }))
+}
+
+/// Append a line to a note, creating it if needed.
+pub(super) async fn append_note(
+    State(state): State<Arc<ApiState>>,
+    Json(request): Json<WriteNoteRequest>,
+) -> Result<Json<NoteResponse>, StatusCode> {
+    if request.content.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let store = notes_store(&state, &request.agent_id)?;
+    let channel_id = request.channel_id.as_deref();
+
+    // The store rejects appends past the limit; check first so that case is
+    // a client error rather than a server one.
+    let current_chars = store
+        .read(channel_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|note| note.content.chars().count() + 1)
+        .unwrap_or(0);
+    if current_chars + request.content.chars().count() > MAX_NOTE_CHARS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let note = store
+        .append(channel_id, &request.content)
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %request.agent_id, "failed to append note");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(NoteResponse { note: Some(note) }))
+}
+
+/// Clear a note.
+pub(super) async fn clear_note(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<NoteQuery>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let store = notes_store(&state, &query.agent_id)?;
+    let removed = store
+        .clear(query.channel_id.as_deref())
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, agent_id = %query.agent_id, "failed to clear note");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(
+        serde_json::json!({ "success": true, "removed": removed }),
+    ))
+}
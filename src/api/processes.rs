@@ -0,0 +1,324 @@
+//! Live process listing and cancellation across all agents.
+//!
+//! Aggregates the per-channel registries (workers, branches, compaction) and
+//! the per-agent background loops into one list, and routes cancellation to
+//! the subsystem that owns the target process.
+
+use super::channels::cancel_detached_worker;
+use super::state::ApiState;
+
+use crate::agent::channel::ChannelState;
+use crate::{ProcessId, ProcessType};
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const CANCEL_REASON: &str = "cancelled via API";
+
+#[derive(Serialize)]
+pub(super) struct ProcessInfo {
+    /// `ProcessId` display form for channels, workers, and branches;
+    /// `compactor:<channel_id>` and `cortex:<agent_id>` otherwise.
+    id: String,
+    process_type: ProcessType,
+    agent_id: String,
+    channel_id: Option<String>,
+    description: Option<String>,
+    status: Option<String>,
+    started_at: DateTime<Utc>,
+    elapsed_secs: i64,
+    /// Whether `POST /processes/{id}/cancel` accepts this process.
+    cancellable: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct ProcessesResponse {
+    processes: Vec<ProcessInfo>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct ListProcessesQuery {
+    agent_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct CancelProcessResponse {
+    success: bool,
+    process_id: String,
+    message: String,
+}
+
+/// List every running channel, branch, worker, compaction, and cortex,
+/// oldest first.
+pub(super) async fn list_processes(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<ListProcessesQuery>,
+) -> Json<ProcessesResponse> {
+    let now = Utc::now();
+    let wanted = |agent_id: &str| {
+        query
+            .agent_id
+            .as_deref()
+            .is_none_or(|wanted| wanted == agent_id)
+    };
+
+    let mut processes = Vec::new();
+    for channel_state in live_channel_states(&state).await {
+        let agent_id = channel_state.deps.agent_id.to_string();
+        if !wanted(&agent_id) {
+            continue;
+        }
+        let channel_id = channel_state.channel_id.to_string();
+
+        processes.push(ProcessInfo {
+            id: ProcessId::Channel(channel_state.channel_id.clone()).to_string(),
+            process_type: ProcessType::Channel,
+            agent_id: agent_id.clone(),
+            channel_id: Some(channel_id.clone()),
+            description: None,
+            status: None,
+            started_at: channel_state.started_at,
+            elapsed_secs: elapsed_secs(channel_state.started_at, now),
+            cancellable: true,
+        });
+
+        let status_block = channel_state.status_block.read().await;
+        for branch in &status_block.active_branches {
+            processes.push(ProcessInfo {
+                id: ProcessId::Branch(branch.id).to_string(),
+                process_type: ProcessType::Branch,
+                agent_id: agent_id.clone(),
+                channel_id: Some(channel_id.clone()),
+                description: Some(branch.description.clone()),
+                status: None,
+                started_at: branch.started_at,
+                elapsed_secs: elapsed_secs(branch.started_at, now),
+                cancellable: true,
+            });
+        }
+        for worker in &status_block.active_workers {
+            processes.push(ProcessInfo {
+                id: ProcessId::Worker(worker.id).to_string(),
+                process_type: ProcessType::Worker,
+                agent_id: agent_id.clone(),
+                channel_id: Some(channel_id.clone()),
+                description: Some(worker.task.clone()),
+                status: Some(worker.status.clone()),
+                started_at: worker.started_at,
+                elapsed_secs: elapsed_secs(worker.started_at, now),
+                cancellable: true,
+            });
+        }
+        if let Some(compaction) = &status_block.compaction {
+            processes.push(ProcessInfo {
+                id: format!("compactor:{channel_id}"),
+                process_type: ProcessType::Compactor,
+                agent_id: agent_id.clone(),
+                channel_id: Some(channel_id.clone()),
+                description: None,
+                status: Some(compaction.status.clone()),
+                started_at: compaction.started_at,
+                elapsed_secs: elapsed_secs(compaction.started_at, now),
+                cancellable: false,
+            });
+        }
+    }
+
+    let cortex_started_at: Vec<_> = state
+        .agent_tasks_started_at
+        .read()
+        .await
+        .iter()
+        .map(|(agent_id, started_at)| (agent_id.clone(), *started_at))
+        .collect();
+    for (agent_id, started_at) in cortex_started_at {
+        if !wanted(&agent_id) {
+            continue;
+        }
+        processes.push(ProcessInfo {
+            id: format!("cortex:{agent_id}"),
+            process_type: ProcessType::Cortex,
+            agent_id,
+            channel_id: None,
+            description: Some("cortex and background loops".into()),
+            status: None,
+            started_at,
+            elapsed_secs: elapsed_secs(started_at, now),
+            cancellable: false,
+        });
+    }
+
+    processes.sort_by(|left, right| left.started_at.cmp(&right.started_at));
+    Json(ProcessesResponse { processes })
+}
+
+/// Cancel a process by its `ProcessId` display form. A worker or branch is
+/// cancelled in the channel that owns it. A channel has all of its workers
+/// and branches cancelled and keeps running. Compactions and the cortex
+/// can't be cancelled here; restart the agent instead.
+pub(super) async fn cancel_process(
+    State(state): State<Arc<ApiState>>,
+    Path(raw_process_id): Path<String>,
+) -> Result<Json<CancelProcessResponse>, StatusCode> {
+    let process_id: ProcessId = raw_process_id.parse().map_err(|error| {
+        tracing::debug!(%error, process_id = %raw_process_id, "rejected process cancel");
+        StatusCode::BAD_REQUEST
+    })?;
+    let channel_states = live_channel_states(&state).await;
+
+    let message = match &process_id {
+        ProcessId::Worker(worker_id) => {
+            let mut owner = None;
+            for channel_state in &channel_states {
+                if channel_state
+                    .worker_handles
+                    .read()
+                    .await
+                    .contains_key(worker_id)
+                    || channel_state
+                        .active_workers
+                        .read()
+                        .await
+                        .contains_key(worker_id)
+                {
+                    owner = Some(channel_state);
+                    break;
+                }
+            }
+
+            if let Some(channel_state) = owner {
+                channel_state
+                    .cancel_worker_with_reason(*worker_id, CANCEL_REASON)
+                    .await
+                    .map_err(|error| {
+                        tracing::warn!(%error, worker_id = %worker_id, "failed to cancel worker");
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+                format!("Worker {worker_id} cancelled")
+            } else if cancel_detached_worker(&state, *worker_id).await? {
+                format!("Worker {worker_id} cancelled (detached run reconciled)")
+            } else {
+                return Err(StatusCode::NOT_FOUND);
+            }
+        }
+        ProcessId::Branch(branch_id) => {
+            let mut owner = None;
+            for channel_state in &channel_states {
+                if channel_state
+                    .active_branches
+                    .read()
+                    .await
+                    .contains_key(branch_id)
+                {
+                    owner = Some(channel_state);
+                    break;
+                }
+            }
+            let channel_state = owner.ok_or(StatusCode::NOT_FOUND)?;
+
+            channel_state
+                .cancel_branch_with_reason(*branch_id, CANCEL_REASON)
+                .await
+                .map_err(|_| StatusCode::NOT_FOUND)?;
+            format!("Branch {branch_id} cancelled")
+        }
+        ProcessId::Channel(channel_id) => {
+            let channel_state = channel_states
+                .iter()
+                .find(|channel_state| channel_state.channel_id == *channel_id)
+                .ok_or(StatusCode::NOT_FOUND)?;
+            let (workers, branches) = cancel_channel_children(channel_state).await;
+            format!("Channel {channel_id}: cancelled {workers} workers and {branches} branches")
+        }
+    };
+
+    tracing::info!(process_id = %process_id, "process cancelled via API");
+    Ok(Json(CancelProcessResponse {
+        success: true,
+        process_id: process_id.to_string(),
+        message,
+    }))
+}
+
+/// Cancel every worker and branch a channel is running. Returns how many of
+/// each were cancelled; ones that finish mid-sweep are skipped.
+async fn cancel_channel_children(channel_state: &ChannelState) -> (usize, usize) {
+    let mut worker_ids: Vec<_> = channel_state
+        .worker_handles
+        .read()
+        .await
+        .keys()
+        .copied()
+        .collect();
+    for worker_id in channel_state.active_workers.read().await.keys() {
+        if !worker_ids.contains(worker_id) {
+            worker_ids.push(*worker_id);
+        }
+    }
+    let branch_ids: Vec<_> = channel_state
+        .active_branches
+        .read()
+        .await
+        .keys()
+        .copied()
+        .collect();
+
+    let mut workers = 0;
+    for worker_id in worker_ids {
+        if channel_state
+            .cancel_worker_with_reason(worker_id, CANCEL_REASON)
+            .await
+            .is_ok()
+        {
+            workers += 1;
+        }
+    }
+    let mut branches = 0;
+    for branch_id in branch_ids {
+        if channel_state
+            .cancel_branch_with_reason(branch_id, CANCEL_REASON)
+            .await
+            .is_ok()
+        {
+            branches += 1;
+        }
+    }
+    (workers, branches)
+}
+
+async fn live_channel_states(state: &ApiState) -> Vec<ChannelState> {
+    state
+        .channel_states
+        .read()
+        .await
+        .values()
+        .cloned()
+        .collect()
+}
+
+fn elapsed_secs(started_at: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (now - started_at).num_seconds().max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_ids_round_trip_through_display() {
+        let worker = ProcessId::Worker(uuid::Uuid::new_v4());
+        assert_eq!(worker.to_string().parse::<ProcessId>(), Ok(worker));
+
+        let channel: ProcessId = "channel:discord:123:456".parse().unwrap();
+        assert_eq!(channel, ProcessId::Channel("discord:123:456".into()));
+
+        assert!("compactor:discord:123".parse::<ProcessId>().is_err());
+        assert!("branch:not-a-uuid".parse::<ProcessId>().is_err());
+        assert!("channel:".parse::<ProcessId>().is_err());
+        assert!("worker".parse::<ProcessId>().is_err());
+    }
+}
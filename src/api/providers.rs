@@ -1,4 +1,5 @@
 use super::state::ApiState;
+use super::toml_edits::set_value;
 use crate::openai_auth::DeviceTokenPollResult;
 
 use anyhow::Context as _;
@@ -125,6 +126,13 @@ pub(super) struct OpenAiOAuthBrowserStatusResponse {
     message: Option<String>,
 }
 
+#[derive(Serialize)]
+pub(super) struct ProviderHealthResponse {
+    failure_threshold: u32,
+    probe_interval_secs: u64,
+    providers: Vec<crate::llm::health::ProviderHealthStatus>,
+}
+
 fn provider_toml_key(provider: &str) -> Option<&'static str> {
     match provider {
         "anthropic" => Some("anthropic_key"),
@@ -218,6 +226,10 @@ fn build_test_llm_config(provider: &str, credential: &str) -> crate::config::Llm
         zai_coding_plan_key: (provider == "zai-coding-plan").then(|| credential.to_string()),
         github_copilot_key: (provider == "github-copilot").then(|| credential.to_string()),
         providers,
+        health: crate::config::ProviderHealthConfig::default(),
+        validate_models: false,
+        default_model: None,
+        attribution: crate::config::AppAttribution::default(),
     }
 }
 
@@ -233,11 +245,9 @@ fn apply_model_routing(doc: &mut toml_edit::DocumentMut, model: &str) {
             .get_mut("routing")
             .and_then(|item| item.as_table_mut())
         {
-            routing_table["channel"] = toml_edit::value(model);
-            routing_table["branch"] = toml_edit::value(model);
-            routing_table["worker"] = toml_edit::value(model);
-            routing_table["compactor"] = toml_edit::value(model);
-            routing_table["cortex"] = toml_edit::value(model);
+            for process in ["channel", "branch", "worker", "compactor", "cortex"] {
+                set_value(routing_table, process, model);
+            }
         }
     }
 
@@ -258,11 +268,9 @@ fn apply_model_routing(doc: &mut toml_edit::DocumentMut, model: &str) {
             .get_mut("routing")
             .and_then(|routing_item| routing_item.as_table_mut())
         {
-            routing_table["channel"] = toml_edit::value(model);
-            routing_table["branch"] = toml_edit::value(model);
-            routing_table["worker"] = toml_edit::value(model);
-            routing_table["compactor"] = toml_edit::value(model);
-            routing_table["cortex"] = toml_edit::value(model);
+            for process in ["channel", "branch", "worker", "compactor", "cortex"] {
+                set_value(routing_table, process, model);
+            }
         }
     }
 }
@@ -817,7 +825,10 @@ pub(super) async fn update_provider(
         doc["llm"] = toml_edit::Item::Table(toml_edit::Table::new());
     }
 
-    doc["llm"][key_name] = toml_edit::value(request.api_key);
+    let llm = doc["llm"]
+        .as_table_mut()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    set_value(llm, key_name, request.api_key);
     apply_model_routing(&mut doc, normalized_model);
 
     tokio::fs::write(&config_path, doc.to_string())
@@ -926,6 +937,26 @@ pub(super) async fn test_provider_model(
     }
 }
 
+/// Health of providers that have seen auth or server failures. Providers that
+/// have never failed are omitted.
+pub(super) async fn provider_health(
+    State(state): State<Arc<ApiState>>,
+) -> Result<Json<ProviderHealthResponse>, StatusCode> {
+    let llm_manager = state
+        .llm_manager
+        .read()
+        .await
+        .clone()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let config = llm_manager.health_config();
+
+    Ok(Json(ProviderHealthResponse {
+        failure_threshold: config.failure_threshold,
+        probe_interval_secs: config.probe_interval_secs,
+        providers: llm_manager.provider_health().await,
+    }))
+}
+
 pub(super) async fn delete_provider(
     State(state): State<Arc<ApiState>>,
     axum::extract::Path(provider): axum::extract::Path<String>,
@@ -1,16 +1,17 @@
 //! HTTP server setup: router, static file serving, and API route wiring.
 
+use super::access_log::AccessLogEntry;
 use super::state::ApiState;
 use super::{
-    agents, bindings, channels, config, cortex, cron, factory, ingest, links, mcp, memories,
-    messaging, models, opencode_proxy, projects, providers, secrets, settings, skills, ssh, system,
-    tasks, tools, webchat, workers,
+    agents, bindings, broadcast, bundles, channels, config, cortex, cron, factory, handoffs,
+    ingest, links, mcp, memories, messaging, models, notes, opencode_proxy, processes, projects,
+    providers, secrets, settings, skills, ssh, system, tasks, tools, webchat, workers,
 };
 
 use axum::Json;
 
 use axum::Router;
-use axum::extract::{DefaultBodyLimit, Request, State};
+use axum::extract::{ConnectInfo, DefaultBodyLimit, OriginalUri, Request, State};
 use axum::http::{StatusCode, Uri, header};
 use axum::middleware::{self, Next};
 use axum::response::{Html, IntoResponse, Response};
@@ -68,8 +69,32 @@ pub async fn start_http_server(
                 .put(agents::update_agent)
                 .delete(agents::delete_agent),
         )
+        .route("/agents/{id}/restart", post(agents::restart_agent))
+        .route("/agents/{id}/export", post(bundles::export_agent))
+        .route("/agents/import", post(bundles::import_agent))
+        .route(
+            "/agents/{id}/memory/export",
+            post(memories::export_memory_snapshot),
+        )
+        .route(
+            "/agents/{id}/memory/import",
+            post(memories::import_memory_snapshot).layer(DefaultBodyLimit::max(
+                memories::MAX_MEMORY_SNAPSHOT_UPLOAD_BYTES,
+            )),
+        )
+        .route(
+            "/agents/{id}/bulletin",
+            get(cortex::get_bulletin)
+                .put(cortex::set_bulletin)
+                .delete(cortex::clear_bulletin_override),
+        )
+        .route(
+            "/agents/{id}/conversations/search",
+            get(channels::search_conversations),
+        )
         .route("/agents/mcp", get(agents::list_agent_mcp))
         .route("/agents/mcp/reconnect", post(agents::reconnect_agent_mcp))
+        .route("/agents/mcp/disconnect", post(agents::disconnect_agent_mcp))
         .route(
             "/agents/warmup",
             get(agents::get_warmup_status).post(agents::trigger_warmup),
@@ -92,6 +117,23 @@ pub async fn start_http_server(
             get(channels::list_channels).delete(channels::delete_channel),
         )
         .route("/channels/archive", put(channels::set_channel_archive))
+        .route(
+            "/channels/output-language",
+            put(channels::set_output_language),
+        )
+        .route("/channels/reasoning", put(channels::set_show_reasoning))
+        .route(
+            "/channels/pins",
+            get(channels::list_pins)
+                .post(channels::pin_context)
+                .delete(channels::unpin_context),
+        )
+        .route(
+            "/channels/slots",
+            get(channels::list_slots)
+                .put(channels::set_slot)
+                .delete(channels::clear_slot),
+        )
         .route("/channels/messages", get(channels::channel_messages))
         .route("/channels/status", get(channels::channel_status))
         .route("/channels/inspect", get(channels::inspect_prompt))
@@ -107,8 +149,13 @@ pub async fn start_http_server(
             "/channels/inspect/snapshot",
             get(channels::get_prompt_snapshot),
         )
+        .route(
+            "/channels/inspect/replay-last",
+            post(channels::replay_last_turn),
+        )
         .route("/agents/workers", get(workers::list_workers))
         .route("/agents/workers/detail", get(workers::worker_detail))
+        .route("/agents/workers/logs/usage", get(workers::worker_log_usage))
         .route(
             "/opencode/{port}/{*path}",
             any(opencode_proxy::opencode_proxy),
@@ -117,6 +164,10 @@ pub async fn start_http_server(
         .route("/opencode/{port}/", any(opencode_proxy::opencode_proxy))
         .route("/agents/memories", get(memories::list_memories))
         .route("/agents/memories/search", get(memories::search_memories))
+        .route(
+            "/agents/memories/{memory_id}",
+            delete(memories::delete_memory),
+        )
         .route("/agents/memories/graph", get(memories::memory_graph))
         .route(
             "/agents/memories/graph/neighbors",
@@ -154,6 +205,8 @@ pub async fn start_http_server(
         .route("/agents/cron/executions", get(cron::cron_executions))
         .route("/agents/cron/trigger", post(cron::trigger_cron))
         .route("/agents/cron/toggle", put(cron::toggle_cron))
+        .route("/agents/{id}/cron/{job_id}/pause", post(cron::pause_cron))
+        .route("/agents/{id}/cron/{job_id}/resume", post(cron::resume_cron))
         .route(
             "/agents/tasks",
             get(tasks::list_tasks).post(tasks::create_task),
@@ -166,6 +219,15 @@ pub async fn start_http_server(
         )
         .route("/agents/tasks/{number}/approve", post(tasks::approve_task))
         .route("/agents/tasks/{number}/execute", post(tasks::execute_task))
+        .route(
+            "/agents/notes",
+            get(notes::get_note)
+                .put(notes::replace_note)
+                .delete(notes::clear_note),
+        )
+        .route("/agents/notes/append", post(notes::append_note))
+        .route("/agents/handoffs", get(handoffs::list_handoffs))
+        .route("/agents/handoffs/resolve", post(handoffs::resolve_handoff))
         .route(
             "/agents/projects",
             get(projects::list_projects).post(projects::create_project),
@@ -195,6 +257,21 @@ pub async fn start_http_server(
             delete(projects::delete_worktree),
         )
         .route("/channels/cancel", post(channels::cancel_process))
+        .route(
+            "/agents/{id}/channels/{channel_id}/cancel",
+            post(channels::cancel_turn),
+        )
+        .route(
+            "/agents/{id}/channels/{channel_id}/model",
+            put(channels::set_channel_model),
+        )
+        .route("/channels/reset", post(channels::reset_channel))
+        .route("/agents/{id}/broadcast", post(broadcast::broadcast))
+        .route("/processes", get(processes::list_processes))
+        .route(
+            "/processes/{process_id}/cancel",
+            post(processes::cancel_process),
+        )
         .route(
             "/agents/ingest/files",
             get(ingest::list_ingest_files).delete(ingest::delete_ingest_file),
@@ -240,6 +317,7 @@ pub async fn start_http_server(
             get(providers::openai_browser_oauth_status),
         )
         .route("/providers/test", post(providers::test_provider_model))
+        .route("/providers/health", get(providers::provider_health))
         .route("/providers/{provider}", delete(providers::delete_provider))
         .route("/models", get(models::get_models))
         .route("/models/refresh", post(models::refresh_models))
@@ -302,6 +380,10 @@ pub async fn start_http_server(
         .layer(middleware::from_fn_with_state(
             state.clone(),
             api_auth_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            access_log_middleware,
         ));
 
     #[cfg(feature = "metrics")]
@@ -319,11 +401,14 @@ pub async fn start_http_server(
 
     let handle = tokio::spawn(async move {
         let mut shutdown = shutdown_rx;
-        if let Err(error) = axum::serve(listener, app)
-            .with_graceful_shutdown(async move {
-                let _ = shutdown.wait_for(|v| *v).await;
-            })
-            .await
+        if let Err(error) = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.wait_for(|v| *v).await;
+        })
+        .await
         {
             tracing::error!(%error, "HTTP server exited with error");
         }
@@ -364,6 +449,56 @@ async fn api_auth_middleware(
     }
 }
 
+/// Append a line to the access log for every API request, including ones
+/// rejected by auth. Only whether the request authenticated and a token
+/// fingerprint are logged, never the token itself.
+async fn access_log_middleware(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(access_log) = state.access_log.as_ref() else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let remote_addr = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0.to_string());
+    let subject = state.auth_token.as_deref().and_then(|expected_token| {
+        request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|token| *token == expected_token)
+            .map(super::access_log::token_subject)
+    });
+
+    let start = std::time::Instant::now();
+    let response = next.run(request).await;
+    let status = response.status();
+
+    access_log.record(&AccessLogEntry {
+        timestamp: chrono::Utc::now(),
+        method,
+        path,
+        status: status.as_u16(),
+        latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+        authenticated: subject.is_some(),
+        subject,
+        remote_addr,
+    });
+
+    response
+}
+
 #[cfg(feature = "metrics")]
 async fn metrics_middleware(request: Request, next: Next) -> Response {
     let method = request.method().to_string();
@@ -13,6 +13,10 @@ pub(super) struct GlobalSettingsResponse {
     api_port: u16,
     api_bind: String,
     worker_log_mode: String,
+    /// Days worker logs are kept. 0 keeps them regardless of age.
+    worker_log_retention_days: u64,
+    /// Cap on total worker log size in MiB. 0 disables the cap.
+    worker_log_max_total_mb: u64,
     opencode: OpenCodeSettingsResponse,
     ssh_enabled: bool,
 }
@@ -41,6 +45,8 @@ pub(super) struct GlobalSettingsUpdate {
     api_port: Option<u16>,
     api_bind: Option<String>,
     worker_log_mode: Option<String>,
+    worker_log_retention_days: Option<u64>,
+    worker_log_max_total_mb: Option<u64>,
     opencode: Option<OpenCodeSettingsUpdate>,
     ssh_enabled: Option<bool>,
 }
@@ -90,7 +96,7 @@ pub(super) async fn get_global_settings(
 ) -> Result<Json<GlobalSettingsResponse>, StatusCode> {
     let config_path = state.config_path.read().await.clone();
 
-    let (brave_search_key, api_enabled, api_port, api_bind, worker_log_mode, opencode, ssh_enabled) =
+    let (brave_search_key, api_enabled, api_port, api_bind, worker_logs, opencode, ssh_enabled) =
         if config_path.exists() {
             let content = tokio::fs::read_to_string(&config_path)
                 .await
@@ -138,6 +144,20 @@ pub(super) async fn get_global_settings(
                 .unwrap_or("errors_only")
                 .to_string();
 
+            let worker_log_retention_days = doc
+                .get("defaults")
+                .and_then(|d| d.get("worker_log_retention_days"))
+                .and_then(|v| v.as_integer())
+                .and_then(|i| u64::try_from(i).ok())
+                .unwrap_or(0);
+
+            let worker_log_max_total_mb = doc
+                .get("defaults")
+                .and_then(|d| d.get("worker_log_max_total_mb"))
+                .and_then(|v| v.as_integer())
+                .and_then(|i| u64::try_from(i).ok())
+                .unwrap_or(0);
+
             let opencode_table = doc.get("defaults").and_then(|d| d.get("opencode"));
             let opencode_perms = opencode_table.and_then(|o| o.get("permissions"));
             let opencode = OpenCodeSettingsResponse {
@@ -195,7 +215,11 @@ pub(super) async fn get_global_settings(
                 api_enabled,
                 api_port,
                 api_bind,
-                worker_log_mode,
+                (
+                    worker_log_mode,
+                    worker_log_retention_days,
+                    worker_log_max_total_mb,
+                ),
                 opencode,
                 ssh_enabled,
             )
@@ -205,7 +229,7 @@ pub(super) async fn get_global_settings(
                 true,
                 19898,
                 "127.0.0.1".to_string(),
-                "errors_only".to_string(),
+                ("errors_only".to_string(), 30, 0),
                 OpenCodeSettingsResponse {
                     enabled: false,
                     path: "opencode".to_string(),
@@ -222,12 +246,16 @@ pub(super) async fn get_global_settings(
             )
         };
 
+    let (worker_log_mode, worker_log_retention_days, worker_log_max_total_mb) = worker_logs;
+
     Ok(Json(GlobalSettingsResponse {
         brave_search_key,
         api_enabled,
         api_port,
         api_bind,
         worker_log_mode,
+        worker_log_retention_days,
+        worker_log_max_total_mb,
         opencode,
         ssh_enabled,
     }))
@@ -299,6 +327,18 @@ pub(super) async fn update_global_settings(
         doc["defaults"]["worker_log_mode"] = toml_edit::value(mode);
     }
 
+    if request.worker_log_retention_days.is_some() || request.worker_log_max_total_mb.is_some() {
+        if doc.get("defaults").is_none() {
+            doc["defaults"] = toml_edit::Item::Table(toml_edit::Table::new());
+        }
+        if let Some(days) = request.worker_log_retention_days {
+            doc["defaults"]["worker_log_retention_days"] = toml_edit::value(days as i64);
+        }
+        if let Some(max_mb) = request.worker_log_max_total_mb {
+            doc["defaults"]["worker_log_max_total_mb"] = toml_edit::value(max_mb as i64);
+        }
+    }
+
     if let Some(opencode) = request.opencode {
         if doc.get("defaults").is_none() {
             doc["defaults"] = toml_edit::Item::Table(toml_edit::Table::new());
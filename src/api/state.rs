@@ -3,7 +3,9 @@
 use crate::agent::channel::ChannelState;
 use crate::agent::cortex_chat::CortexChatSession;
 use crate::agent::status::StatusBlock;
-use crate::config::{Binding, DefaultsConfig, DiscordPermissions, RuntimeConfig, SlackPermissions};
+use crate::config::{
+    Binding, DbConfig, DefaultsConfig, DiscordPermissions, RuntimeConfig, SlackPermissions,
+};
 use crate::conversation::worker_transcript::{ActionContent, TranscriptStep};
 use crate::cron::{CronStore, Scheduler};
 use crate::llm::LlmManager;
@@ -15,7 +17,7 @@ use crate::projects::ProjectStore;
 use crate::prompts::PromptEngine;
 use crate::tasks::TaskStore;
 use crate::update::SharedUpdateStatus;
-use crate::{ProcessEvent, ProcessId};
+use crate::{CompactionTrigger, ProcessEvent, ProcessId};
 
 use arc_swap::ArcSwap;
 use serde::Serialize;
@@ -49,6 +51,8 @@ pub struct AgentInfo {
 pub struct ApiState {
     pub started_at: Instant,
     pub auth_token: Option<String>,
+    /// JSONL request log, present when `api.access_log` is enabled.
+    pub access_log: Option<super::access_log::AccessLog>,
     /// Aggregated event stream from all agents. SSE clients subscribe here.
     pub event_tx: broadcast::Sender<ApiEvent>,
     /// Per-agent SQLite pools for querying channel/conversation data.
@@ -92,6 +96,9 @@ pub struct ApiState {
     pub sandboxes: ArcSwap<HashMap<String, Arc<crate::sandbox::Sandbox>>>,
     /// Instance-level secrets store (shared across all agents).
     pub secrets_store: ArcSwap<Option<Arc<crate::secrets::store::SecretsStore>>>,
+    /// Outbound event sink, when `[event_sink]` is configured. Agents
+    /// registered for events are attached to it as well.
+    pub event_sink: ArcSwap<Option<crate::event_sink::EventSink>>,
     /// Shared reference to the Discord permissions ArcSwap (same instance used by the adapter and file watcher).
     pub discord_permissions: RwLock<Option<Arc<ArcSwap<DiscordPermissions>>>>,
     /// Shared reference to the Slack permissions ArcSwap (same instance used by the adapter and file watcher).
@@ -114,6 +121,8 @@ pub struct ApiState {
     pub prompt_engine: RwLock<Option<PromptEngine>>,
     /// Instance-level defaults for resolving new agent configs.
     pub defaults_config: RwLock<Option<DefaultsConfig>>,
+    /// SQLite pool settings for databases opened by runtime agent creation.
+    pub db_config: ArcSwap<DbConfig>,
     /// Sender to register newly created agents with the main event loop.
     pub agent_tx: mpsc::Sender<crate::Agent>,
     /// Sender to remove agents from the main event loop.
@@ -139,6 +148,13 @@ pub struct ApiState {
     /// Serializes SSH daemon enable/disable transitions to prevent races
     /// between overlapping toggle requests.
     pub ssh_mutex: tokio::sync::Mutex<()>,
+    /// Background loops (cortex, warmup, association, ready-task, ingestion)
+    /// per agent, aborted when the agent is deleted or restarted.
+    pub agent_tasks: RwLock<HashMap<String, Vec<tokio::task::AbortHandle>>>,
+    /// When each agent's background loops were first registered.
+    pub agent_tasks_started_at: RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>,
+    /// Inbound message hooks run by the main loop before binding resolution.
+    pub message_hooks: Arc<crate::hooks::MessageHookChain>,
 }
 
 /// Events sent to SSE clients. Wraps ProcessEvents with agent context.
@@ -153,11 +169,22 @@ pub enum ApiEvent {
         sender_id: String,
         text: String,
     },
-    /// An outbound message sent by the bot.
+    /// An outbound message sent by the bot. Usage fields describe the turn
+    /// that produced it and are `None` when the provider reported no usage.
     OutboundMessage {
         agent_id: String,
         channel_id: String,
         text: String,
+        cost_usd: Option<f64>,
+        prompt_tokens: Option<u64>,
+        completion_tokens: Option<u64>,
+        latency_ms: Option<u64>,
+    },
+    /// Model reasoning surfaced ahead of a reply (reasoning visibility on).
+    OutboundReasoning {
+        agent_id: String,
+        channel_id: String,
+        text: String,
     },
     /// Typing indicator state change.
     TypingState {
@@ -233,6 +260,7 @@ pub enum ApiEvent {
         process_id: String,
         tool_name: String,
         result: String,
+        truncated_bytes: Option<usize>,
     },
     /// Configuration was reloaded (skills, identity, etc.).
     ConfigReloaded,
@@ -258,6 +286,36 @@ pub enum ApiEvent {
         /// "created", "updated", or "deleted".
         action: String,
     },
+    /// A memory was permanently deleted.
+    MemoryDeleted { agent_id: String, memory_id: String },
+    /// The cortex consolidated related memories into a new one.
+    MemoriesConsolidated {
+        agent_id: String,
+        memory_id: String,
+        archived_memory_ids: Vec<String>,
+    },
+    /// A channel's history crossed a compaction threshold.
+    CompactionTriggered {
+        agent_id: String,
+        channel_id: String,
+        threshold_reached: f32,
+        reason: CompactionTrigger,
+    },
+    /// A running compaction moved on to a new stage.
+    CompactionProgress {
+        agent_id: String,
+        channel_id: String,
+        status: String,
+    },
+    /// A compaction finished. `turns_compacted` is 0 when nothing was removed.
+    CompactionCompleted {
+        agent_id: String,
+        channel_id: String,
+        turns_compacted: usize,
+        summary_tokens: usize,
+        summary_max_tokens: usize,
+        context_tokens: usize,
+    },
     /// A finalized content part from an OpenCode worker session.
     OpenCodePartUpdated {
         agent_id: String,
@@ -278,6 +336,11 @@ pub enum ApiEvent {
         content: String,
         tool_calls: Option<Vec<crate::agent::cortex_chat::CortexChatToolCall>>,
     },
+    /// An LLM provider entered or left rate-limit cooldown.
+    ProviderStatus {
+        #[serde(flatten)]
+        event: crate::llm::rate_limit::ProviderStatusEvent,
+    },
 }
 
 impl ApiState {
@@ -294,6 +357,7 @@ impl ApiState {
         Self {
             started_at: Instant::now(),
             auth_token: None,
+            access_log: None,
             event_tx,
             agent_pools: arc_swap::ArcSwap::from_pointee(HashMap::new()),
             agent_configs: arc_swap::ArcSwap::from_pointee(Vec::new()),
@@ -314,6 +378,7 @@ impl ApiState {
             mcp_managers: ArcSwap::from_pointee(HashMap::new()),
             sandboxes: ArcSwap::from_pointee(HashMap::new()),
             secrets_store: ArcSwap::from_pointee(None),
+            event_sink: ArcSwap::from_pointee(None),
             discord_permissions: RwLock::new(None),
             slack_permissions: RwLock::new(None),
             bindings: RwLock::new(None),
@@ -325,6 +390,7 @@ impl ApiState {
             embedding_model: RwLock::new(None),
             prompt_engine: RwLock::new(None),
             defaults_config: RwLock::new(None),
+            db_config: ArcSwap::from_pointee(DbConfig::default()),
             agent_tx,
             agent_remove_tx,
             task_store_registry,
@@ -335,6 +401,9 @@ impl ApiState {
             agent_humans: ArcSwap::from_pointee(Vec::new()),
             live_worker_transcripts: Arc::new(RwLock::new(HashMap::new())),
             ssh_mutex: tokio::sync::Mutex::new(()),
+            agent_tasks: RwLock::new(HashMap::new()),
+            agent_tasks_started_at: RwLock::new(HashMap::new()),
+            message_hooks: Arc::new(crate::hooks::MessageHookChain::new()),
         }
     }
 
@@ -350,9 +419,21 @@ impl ApiState {
             .insert(channel_id, status_block);
     }
 
-    /// Remove a channel's status block when it's dropped.
-    pub async fn unregister_channel_status(&self, channel_id: &str) {
-        self.channel_status_blocks.write().await.remove(channel_id);
+    /// Remove a channel's status block when it's dropped. Only removes the
+    /// entry if it is still this channel's block, so a channel finishing
+    /// after its replacement registered leaves the replacement in place.
+    pub async fn unregister_channel_status(
+        &self,
+        channel_id: &str,
+        status_block: &Arc<tokio::sync::RwLock<StatusBlock>>,
+    ) {
+        let mut status_blocks = self.channel_status_blocks.write().await;
+        if status_blocks
+            .get(channel_id)
+            .is_some_and(|registered| Arc::ptr_eq(registered, status_block))
+        {
+            status_blocks.remove(channel_id);
+        }
     }
 
     /// Register a channel's state for API-driven cancellation.
@@ -360,9 +441,16 @@ impl ApiState {
         self.channel_states.write().await.insert(channel_id, state);
     }
 
-    /// Remove a channel's state when it's dropped.
-    pub async fn unregister_channel_state(&self, channel_id: &str) {
-        self.channel_states.write().await.remove(channel_id);
+    /// Remove a channel's state when it's dropped, unless a newer channel for
+    /// the same conversation has already replaced it.
+    pub async fn unregister_channel_state(&self, channel_id: &str, state: &ChannelState) {
+        let mut states = self.channel_states.write().await;
+        if states
+            .get(channel_id)
+            .is_some_and(|registered| Arc::ptr_eq(&registered.status_block, &state.status_block))
+        {
+            states.remove(channel_id);
+        }
     }
 
     /// Retrieve the live transcript cache for a running worker.
@@ -382,6 +470,9 @@ impl ApiState {
         agent_id: String,
         mut agent_event_rx: broadcast::Receiver<ProcessEvent>,
     ) {
+        if let Some(sink) = self.event_sink.load().as_ref() {
+            sink.attach(agent_id.clone(), agent_event_rx.resubscribe());
+        }
         let api_tx = self.event_tx.clone();
         let live_transcripts = self.live_worker_transcripts.clone();
         tokio::spawn(async move {
@@ -531,6 +622,7 @@ impl ApiState {
                                 channel_id,
                                 tool_name,
                                 result,
+                                truncated_bytes,
                                 ..
                             } => {
                                 let (process_type, id_str) = process_id_info(process_id);
@@ -554,6 +646,7 @@ impl ApiState {
                                         process_id: id_str,
                                         tool_name: tool_name.clone(),
                                         result: result.clone(),
+                                        truncated_bytes: *truncated_bytes,
                                     })
                                     .ok();
                             }
@@ -604,6 +697,72 @@ impl ApiState {
                                     })
                                     .ok();
                             }
+                            ProcessEvent::MemoryDeleted { memory_id, .. } => {
+                                api_tx
+                                    .send(ApiEvent::MemoryDeleted {
+                                        agent_id: agent_id.clone(),
+                                        memory_id: memory_id.clone(),
+                                    })
+                                    .ok();
+                            }
+                            ProcessEvent::MemoriesConsolidated {
+                                memory_id,
+                                archived_memory_ids,
+                                ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::MemoriesConsolidated {
+                                        agent_id: agent_id.clone(),
+                                        memory_id: memory_id.clone(),
+                                        archived_memory_ids: archived_memory_ids.clone(),
+                                    })
+                                    .ok();
+                            }
+                            ProcessEvent::CompactionTriggered {
+                                channel_id,
+                                threshold_reached,
+                                reason,
+                                ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::CompactionTriggered {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.to_string(),
+                                        threshold_reached: *threshold_reached,
+                                        reason: *reason,
+                                    })
+                                    .ok();
+                            }
+                            ProcessEvent::CompactionProgress {
+                                channel_id, status, ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::CompactionProgress {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.to_string(),
+                                        status: status.clone(),
+                                    })
+                                    .ok();
+                            }
+                            ProcessEvent::CompactionCompleted {
+                                channel_id,
+                                turns_compacted,
+                                summary_tokens,
+                                summary_max_tokens,
+                                context_tokens,
+                                ..
+                            } => {
+                                api_tx
+                                    .send(ApiEvent::CompactionCompleted {
+                                        agent_id: agent_id.clone(),
+                                        channel_id: channel_id.to_string(),
+                                        turns_compacted: *turns_compacted,
+                                        summary_tokens: *summary_tokens,
+                                        summary_max_tokens: *summary_max_tokens,
+                                        context_tokens: *context_tokens,
+                                    })
+                                    .ok();
+                            }
                             ProcessEvent::TextDelta {
                                 channel_id: Some(channel_id),
                                 text_delta,
@@ -771,6 +930,12 @@ impl ApiState {
         self.sandboxes.store(Arc::new(sandboxes));
     }
 
+    /// Set the outbound event sink. Only agents registered afterwards are
+    /// attached to it.
+    pub fn set_event_sink(&self, sink: crate::event_sink::EventSink) {
+        self.event_sink.store(Arc::new(Some(sink)));
+    }
+
     /// Set the instance-level secrets store.
     pub fn set_secrets_store(&self, store: Arc<crate::secrets::store::SecretsStore>) {
         self.secrets_store.store(Arc::new(Some(store)));
@@ -803,6 +968,21 @@ impl ApiState {
 
     /// Set the shared LLM manager for runtime agent creation.
     pub async fn set_llm_manager(&self, manager: Arc<LlmManager>) {
+        let mut status_rx = manager.subscribe_status();
+        let api_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match status_rx.recv().await {
+                    Ok(event) => {
+                        api_tx.send(ApiEvent::ProviderStatus { event }).ok();
+                    }
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        tracing::debug!(count, "provider status forwarder lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
         *self.llm_manager.write().await = Some(manager);
     }
 
@@ -821,6 +1001,40 @@ impl ApiState {
         *self.defaults_config.write().await = Some(defaults);
     }
 
+    /// Set the SQLite pool settings for runtime agent creation.
+    pub fn set_db_config(&self, config: DbConfig) {
+        self.db_config.store(Arc::new(config));
+    }
+
+    /// Track an agent's background loops so they can be stopped on teardown.
+    pub async fn register_agent_tasks(
+        &self,
+        agent_id: &str,
+        handles: impl IntoIterator<Item = tokio::task::AbortHandle>,
+    ) {
+        self.agent_tasks
+            .write()
+            .await
+            .entry(agent_id.to_string())
+            .or_default()
+            .extend(handles);
+        self.agent_tasks_started_at
+            .write()
+            .await
+            .entry(agent_id.to_string())
+            .or_insert_with(chrono::Utc::now);
+    }
+
+    /// Abort every background loop registered for an agent.
+    pub async fn abort_agent_tasks(&self, agent_id: &str) {
+        self.agent_tasks_started_at.write().await.remove(agent_id);
+        if let Some(handles) = self.agent_tasks.write().await.remove(agent_id) {
+            for handle in handles {
+                handle.abort();
+            }
+        }
+    }
+
     /// Set the shared webchat adapter for API handlers.
     pub fn set_webchat_adapter(&self, adapter: Arc<WebChatAdapter>) {
         self.webchat_adapter.store(Arc::new(Some(adapter)));
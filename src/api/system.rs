@@ -85,6 +85,7 @@ pub(super) async fn events_sse(
                             ApiEvent::InboundMessage { .. } => "inbound_message",
                             ApiEvent::OutboundMessage { .. } => "outbound_message",
                             ApiEvent::OutboundMessageDelta { .. } => "outbound_message_delta",
+                            ApiEvent::OutboundReasoning { .. } => "outbound_reasoning",
                             ApiEvent::TypingState { .. } => "typing_state",
                             ApiEvent::WorkerStarted { .. } => "worker_started",
                             ApiEvent::WorkerStatusUpdate { .. } => "worker_status",
@@ -98,9 +99,15 @@ pub(super) async fn events_sse(
                             ApiEvent::AgentMessageSent { .. } => "agent_message_sent",
                             ApiEvent::AgentMessageReceived { .. } => "agent_message_received",
                             ApiEvent::TaskUpdated { .. } => "task_updated",
+                            ApiEvent::MemoryDeleted { .. } => "memory_deleted",
+                            ApiEvent::MemoriesConsolidated { .. } => "memories_consolidated",
+                            ApiEvent::CompactionTriggered { .. } => "compaction_triggered",
+                            ApiEvent::CompactionProgress { .. } => "compaction_progress",
+                            ApiEvent::CompactionCompleted { .. } => "compaction_completed",
                             ApiEvent::OpenCodePartUpdated { .. } => "opencode_part_updated",
                             ApiEvent::WorkerText { .. } => "worker_text",
                             ApiEvent::CortexChatMessage { .. } => "cortex_chat_message",
+                            ApiEvent::ProviderStatus { .. } => "provider_status",
                         };
                         yield Ok(axum::response::sse::Event::default()
                             .event(event_type)
@@ -0,0 +1,122 @@
+//! Targeted edits to config.toml that preserve the user's formatting.
+//!
+//! Plain `table["key"] = toml_edit::value(..)` assignment replaces the whole
+//! value, dropping any trailing comment and the original quoting style even
+//! when the value itself hasn't changed. These helpers only touch keys whose
+//! value actually differs, and carry the existing decor over when they do.
+
+/// Set `key` in `table`, leaving the existing entry untouched if it already
+/// holds an equal value and keeping its surrounding comments if it doesn't.
+pub(super) fn set_value(
+    table: &mut toml_edit::Table,
+    key: &str,
+    value: impl Into<toml_edit::Value>,
+) {
+    let mut value = value.into();
+
+    if let Some(existing) = table.get_mut(key).and_then(|item| item.as_value_mut()) {
+        if values_equal(existing, &value) {
+            return;
+        }
+        *value.decor_mut() = existing.decor().clone();
+        *existing = value;
+        return;
+    }
+
+    table.insert(key, toml_edit::Item::Value(value));
+}
+
+/// Set `key` when `value` is present, otherwise remove it.
+pub(super) fn set_or_remove<V: Into<toml_edit::Value>>(
+    table: &mut toml_edit::Table,
+    key: &str,
+    value: Option<V>,
+) {
+    match value {
+        Some(value) => set_value(table, key, value),
+        None => {
+            table.remove(key);
+        }
+    }
+}
+
+/// Build a string array value from a list of ids.
+pub(super) fn string_array<S: AsRef<str>>(items: &[S]) -> toml_edit::Value {
+    let mut array = toml_edit::Array::new();
+    for item in items {
+        array.push(item.as_ref());
+    }
+    toml_edit::Value::Array(array)
+}
+
+/// Compare two values ignoring formatting (quoting, whitespace, comments).
+fn values_equal(left: &toml_edit::Value, right: &toml_edit::Value) -> bool {
+    use toml_edit::Value;
+
+    match (left, right) {
+        (Value::String(left), Value::String(right)) => left.value() == right.value(),
+        (Value::Integer(left), Value::Integer(right)) => left.value() == right.value(),
+        (Value::Float(left), Value::Float(right)) => left.value() == right.value(),
+        (Value::Boolean(left), Value::Boolean(right)) => left.value() == right.value(),
+        (Value::Datetime(left), Value::Datetime(right)) => left.value() == right.value(),
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| values_equal(left, right))
+        }
+        (Value::InlineTable(left), Value::InlineTable(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, left)| {
+                    right
+                        .get(key)
+                        .is_some_and(|right| values_equal(left, right))
+                })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"# Spacebot config
+[llm]
+# production key
+anthropic_key = 'sk-old'  # rotated monthly
+openai_key = "sk-openai"
+"#;
+
+    fn llm_table(doc: &mut toml_edit::DocumentMut) -> &mut toml_edit::Table {
+        doc["llm"].as_table_mut().expect("llm table")
+    }
+
+    #[test]
+    fn unchanged_values_keep_their_formatting() {
+        let mut doc: toml_edit::DocumentMut = SOURCE.parse().unwrap();
+        set_value(llm_table(&mut doc), "anthropic_key", "sk-old");
+        assert_eq!(doc.to_string(), SOURCE);
+    }
+
+    #[test]
+    fn changed_values_keep_surrounding_comments() {
+        let mut doc: toml_edit::DocumentMut = SOURCE.parse().unwrap();
+        set_value(llm_table(&mut doc), "anthropic_key", "sk-new");
+        set_value(llm_table(&mut doc), "groq_key", "gsk-new");
+
+        let rendered = doc.to_string();
+        assert!(rendered.starts_with("# Spacebot config\n[llm]\n# production key\n"));
+        assert!(rendered.contains("anthropic_key = \"sk-new\"  # rotated monthly\n"));
+        assert!(rendered.contains("openai_key = \"sk-openai\"\n"));
+        assert!(rendered.ends_with("groq_key = \"gsk-new\"\n"));
+    }
+
+    #[test]
+    fn set_or_remove_drops_absent_keys() {
+        let mut doc: toml_edit::DocumentMut = SOURCE.parse().unwrap();
+        set_or_remove(llm_table(&mut doc), "openai_key", None::<&str>);
+        assert!(!doc.to_string().contains("openai_key"));
+    }
+}
@@ -213,3 +213,57 @@ pub(super) async fn worker_detail(
         directory: detail.directory,
     }))
 }
+
+#[derive(Deserialize)]
+pub(super) struct WorkerLogUsageQuery {
+    agent_id: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct WorkerLogUsageResponse {
+    #[serde(flatten)]
+    usage: crate::agent::worker_logs::WorkerLogUsage,
+    /// Configured age limit. None keeps logs regardless of age.
+    retention_days: Option<u64>,
+    /// Configured size cap in bytes. None disables the cap.
+    max_total_bytes: Option<u64>,
+}
+
+/// Disk usage of an agent's worker logs and the retention policy applied to them.
+pub(super) async fn worker_log_usage(
+    State(state): State<Arc<ApiState>>,
+    Query(query): Query<WorkerLogUsageQuery>,
+) -> Result<Json<WorkerLogUsageResponse>, StatusCode> {
+    let data_dir = state
+        .agent_data_dirs
+        .load()
+        .get(&query.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let workspace = state
+        .agent_workspaces
+        .load()
+        .get(&query.agent_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let retention = state
+        .runtime_configs
+        .load()
+        .get(&query.agent_id)
+        .map(|runtime_config| **runtime_config.worker_log_retention.load())
+        .unwrap_or_default();
+
+    let dirs = crate::agent::worker_logs::worker_log_dirs(&data_dir, &workspace);
+    let usage = tokio::task::spawn_blocking(move || crate::agent::worker_logs::usage(&dirs))
+        .await
+        .map_err(|error| {
+            tracing::warn!(%error, "worker log usage task failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(WorkerLogUsageResponse {
+        usage,
+        retention_days: retention.max_age_days,
+        max_total_bytes: retention.max_total_bytes,
+    }))
+}
@@ -173,27 +173,112 @@ pub async fn exchange_code(code_with_state: &str, verifier: &str) -> Result<OAut
     })
 }
 
+/// Label of the account stored in `anthropic_oauth.json`.
+pub const DEFAULT_ACCOUNT_LABEL: &str = "default";
+
 /// Path to the Anthropic OAuth credentials file within the instance directory.
 pub fn credentials_path(instance_dir: &Path) -> PathBuf {
     instance_dir.join("anthropic_oauth.json")
 }
 
+/// Path to the credentials file for a labeled account. The default account
+/// keeps using `anthropic_oauth.json`; other labels are stored as
+/// `auth.<label>.json`.
+pub fn account_credentials_path(instance_dir: &Path, label: &str) -> PathBuf {
+    if label == DEFAULT_ACCOUNT_LABEL {
+        credentials_path(instance_dir)
+    } else {
+        instance_dir.join(format!("auth.{label}.json"))
+    }
+}
+
+/// Check that an account label is safe to use in a file name.
+pub fn validate_account_label(label: &str) -> Result<()> {
+    let valid = !label.is_empty()
+        && label.len() <= 64
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!("invalid account label '{label}': use 1-64 letters, digits, '-' or '_'");
+    }
+    Ok(())
+}
+
+/// Whether any Anthropic OAuth account is stored.
+pub fn has_credentials(instance_dir: &Path) -> bool {
+    credentials_path(instance_dir).exists() || !account_labels(instance_dir).is_empty()
+}
+
+/// Labels of the accounts stored as `auth.<label>.json`, sorted.
+fn account_labels(instance_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(instance_dir) else {
+        return Vec::new();
+    };
+    let mut labels: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let label = name.strip_prefix("auth.")?.strip_suffix(".json")?;
+            validate_account_label(label).ok()?;
+            (label != DEFAULT_ACCOUNT_LABEL).then(|| label.to_string())
+        })
+        .collect();
+    labels.sort();
+    labels
+}
+
 /// Load stored credentials from disk.
 pub fn load_credentials(instance_dir: &Path) -> Result<Option<OAuthCredentials>> {
-    let path = credentials_path(instance_dir);
+    load_account_credentials(instance_dir, DEFAULT_ACCOUNT_LABEL)
+}
+
+/// Load the credentials for a labeled account.
+pub fn load_account_credentials(
+    instance_dir: &Path,
+    label: &str,
+) -> Result<Option<OAuthCredentials>> {
+    let path = account_credentials_path(instance_dir, label);
     if !path.exists() {
         return Ok(None);
     }
     let data = std::fs::read_to_string(&path)
         .with_context(|| format!("failed to read {}", path.display()))?;
-    let creds: OAuthCredentials =
-        serde_json::from_str(&data).context("failed to parse auth.json")?;
+    let creds: OAuthCredentials = serde_json::from_str(&data)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
     Ok(Some(creds))
 }
 
+/// Load every stored account as `(label, credentials)`, the default account
+/// first. Unreadable files are skipped with a warning.
+pub fn load_all_credentials(instance_dir: &Path) -> Vec<(String, OAuthCredentials)> {
+    std::iter::once(DEFAULT_ACCOUNT_LABEL.to_string())
+        .chain(account_labels(instance_dir))
+        .filter_map(
+            |label| match load_account_credentials(instance_dir, &label) {
+                Ok(creds) => creds.map(|creds| (label, creds)),
+                Err(error) => {
+                    tracing::warn!(%error, %label, "failed to load Anthropic OAuth account");
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
 /// Save credentials to disk with restricted permissions (0600).
 pub fn save_credentials(instance_dir: &Path, creds: &OAuthCredentials) -> Result<()> {
-    let path = credentials_path(instance_dir);
+    save_account_credentials(instance_dir, DEFAULT_ACCOUNT_LABEL, creds)
+}
+
+/// Save the credentials for a labeled account with restricted permissions (0600).
+pub fn save_account_credentials(
+    instance_dir: &Path,
+    label: &str,
+    creds: &OAuthCredentials,
+) -> Result<()> {
+    validate_account_label(label)?;
+    let path = account_credentials_path(instance_dir, label);
     let data = serde_json::to_string_pretty(creds).context("failed to serialize credentials")?;
 
     std::fs::write(&path, &data).with_context(|| format!("failed to write {}", path.display()))?;
@@ -209,7 +294,16 @@ pub fn save_credentials(instance_dir: &Path, creds: &OAuthCredentials) -> Result
 }
 
 /// Run the interactive OAuth login flow. Prints URL, prompts for code, exchanges tokens.
-pub async fn login_interactive(instance_dir: &Path, mode: AuthMode) -> Result<OAuthCredentials> {
+///
+/// Credentials are stored under `label`, or as the default account when no
+/// label is given.
+pub async fn login_interactive(
+    instance_dir: &Path,
+    mode: AuthMode,
+    label: Option<&str>,
+) -> Result<OAuthCredentials> {
+    let label = label.unwrap_or(DEFAULT_ACCOUNT_LABEL);
+    validate_account_label(label)?;
     let (url, verifier) = authorize_url(mode);
 
     eprintln!("Open this URL in your browser:\n");
@@ -237,12 +331,55 @@ pub async fn login_interactive(instance_dir: &Path, mode: AuthMode) -> Result<OA
         .await
         .context("failed to exchange authorization code")?;
 
-    save_credentials(instance_dir, &creds).context("failed to save credentials")?;
+    save_account_credentials(instance_dir, label, &creds).context("failed to save credentials")?;
 
     eprintln!(
         "Login successful. Credentials saved to {}",
-        credentials_path(instance_dir).display()
+        account_credentials_path(instance_dir, label).display()
     );
 
     Ok(creds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(token: &str) -> OAuthCredentials {
+        OAuthCredentials {
+            access_token: token.to_string(),
+            refresh_token: format!("{token}-refresh"),
+            expires_at: chrono::Utc::now().timestamp_millis() + 3_600_000,
+        }
+    }
+
+    #[test]
+    fn account_labels_must_be_file_name_safe() {
+        assert!(validate_account_label("work").is_ok());
+        assert!(validate_account_label("personal_2-max").is_ok());
+        assert!(validate_account_label("").is_err());
+        assert!(validate_account_label("../escape").is_err());
+        assert!(validate_account_label("has space").is_err());
+    }
+
+    #[test]
+    fn load_all_credentials_lists_default_then_labels() {
+        let instance_dir =
+            std::env::temp_dir().join(format!("spacebot-auth-accounts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&instance_dir).unwrap();
+        assert!(!has_credentials(&instance_dir));
+
+        save_account_credentials(&instance_dir, "work", &credentials("work")).unwrap();
+        save_credentials(&instance_dir, &credentials("main")).unwrap();
+        save_account_credentials(&instance_dir, "alt", &credentials("alt")).unwrap();
+
+        let accounts = load_all_credentials(&instance_dir);
+        let labels: Vec<&str> = accounts.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["default", "alt", "work"]);
+        assert_eq!(accounts[2].1.access_token, "work");
+        assert!(instance_dir.join("auth.work.json").exists());
+        assert!(has_credentials(&instance_dir));
+
+        std::fs::remove_dir_all(&instance_dir).ok();
+    }
+}
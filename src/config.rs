@@ -2,6 +2,7 @@
 
 mod load;
 mod onboarding;
+mod overlay;
 mod permissions;
 mod providers;
 mod runtime;
@@ -19,7 +20,7 @@ pub use permissions::{
     TelegramPermissions, TwitchPermissions,
 };
 pub(crate) use providers::default_provider_config;
-pub use runtime::RuntimeConfig;
+pub use runtime::{PAUSE_FILE_NAME, RuntimeConfig};
 pub use types::*;
 pub use watcher::spawn_file_watcher;
 
@@ -28,7 +29,11 @@ pub use watcher::spawn_file_watcher;
 
 // Make toml_schema types and internal helpers visible to tests in this module.
 #[cfg(test)]
-use load::warn_unknown_config_keys;
+use load::{
+    parse_binding_schedule, read_config_toml, unknown_routing_providers, warn_unknown_config_keys,
+};
+#[cfg(test)]
+use overlay::{merge_toml, overlay_path_for};
 #[cfg(test)]
 use providers::ANTHROPIC_PROVIDER_BASE_URL;
 #[cfg(test)]
@@ -340,6 +345,50 @@ openrouter_key = "legacy-openrouter-key"
         );
     }
 
+    #[test]
+    fn test_attribution_overrides_reach_openrouter_headers() {
+        let toml = r#"
+[llm]
+openrouter_key = "openrouter-key"
+
+[llm.attribution]
+referer = "https://example.com/my-app"
+title = ""
+user_agent = "my-app/1.0"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        assert_eq!(config.llm.attribution.user_agent, "my-app/1.0");
+        let openrouter_provider = config
+            .llm
+            .providers
+            .get("openrouter")
+            .expect("openrouter provider missing");
+        let header_names: Vec<&str> = openrouter_provider
+            .extra_headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        // An empty title drops both title headers.
+        assert_eq!(header_names, ["HTTP-Referer", "X-OpenRouter-Categories"]);
+        assert_eq!(
+            openrouter_provider.extra_headers[0].1,
+            "https://example.com/my-app"
+        );
+
+        let mut headers = vec![("user-agent".to_string(), "custom".to_string())];
+        config.llm.attribution.add_user_agent(&mut headers);
+        assert_eq!(headers.len(), 1);
+        let mut headers = Vec::new();
+        config.llm.attribution.add_user_agent(&mut headers);
+        assert_eq!(
+            headers,
+            [("User-Agent".to_string(), "my-app/1.0".to_string())]
+        );
+    }
+
     #[test]
     fn test_explicit_provider_config_takes_priority_over_legacy_key_migration() {
         let toml = r#"
@@ -409,6 +458,81 @@ name = "My OpenRouter"
         );
     }
 
+    #[test]
+    fn test_provider_toml_headers_extend_and_override_defaults() {
+        let toml = r#"
+[llm.provider.openrouter]
+api_type = "openai_completions"
+base_url = "https://openrouter.ai/api/v1"
+api_key = "explicit-openrouter-key"
+
+[llm.provider.openrouter.headers]
+"x-title" = "My Gateway"
+"X-Org-Id" = "org-123"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let openrouter_provider = config
+            .llm
+            .providers
+            .get("openrouter")
+            .expect("openrouter provider missing");
+        let titles: Vec<&str> = openrouter_provider
+            .extra_headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("x-title"))
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(titles, vec!["My Gateway"]);
+        assert!(
+            openrouter_provider
+                .extra_headers
+                .iter()
+                .any(|(key, value)| key == "X-Org-Id" && value == "org-123")
+        );
+        assert!(
+            !format!("{openrouter_provider:?}").contains("org-123"),
+            "header values should be redacted from debug output"
+        );
+    }
+
+    #[test]
+    fn test_provider_toml_fallback_base_urls() {
+        let toml = r#"
+[llm.provider.gateway]
+api_type = "openai_chat_completions"
+base_url = "https://primary.example.com/v1"
+api_key = "gateway-key"
+fallback_base_urls = ["https://secondary.example.com/v1", " "]
+
+[llm.provider.plain]
+api_type = "openai_chat_completions"
+base_url = "https://plain.example.com/v1"
+api_key = "plain-key"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        let gateway = config
+            .llm
+            .providers
+            .get("gateway")
+            .expect("gateway provider missing");
+        assert_eq!(
+            gateway.fallback_base_urls,
+            vec!["https://secondary.example.com/v1".to_string()]
+        );
+        let plain = config
+            .llm
+            .providers
+            .get("plain")
+            .expect("plain provider missing");
+        assert!(plain.fallback_base_urls.is_empty());
+    }
+
     #[test]
     fn test_needs_onboarding_without_config_or_env() {
         let _lock = env_test_lock().lock();
@@ -556,6 +680,96 @@ bind = "127.0.0.1"
         assert_eq!(config.api.bind, "0.0.0.0");
     }
 
+    #[test]
+    fn test_db_section_overrides_pool_defaults() {
+        let toml = r#"
+[db]
+max_connections = 0
+busy_timeout_secs = 20
+statement_timeout_secs = 5
+wal = false
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        assert_eq!(config.db.max_connections, 1);
+        assert_eq!(config.db.busy_timeout_secs, 20);
+        assert_eq!(
+            config.db.acquire_timeout_secs,
+            DbConfig::default().acquire_timeout_secs
+        );
+        assert_eq!(config.db.statement_timeout_secs, 5);
+        assert!(!config.db.wal);
+    }
+
+    #[test]
+    fn test_routing_models_with_unconfigured_providers_are_reported() {
+        let toml = r#"
+[llm.provider.myproxy]
+api_type = "openai_chat_completions"
+base_url = "https://proxy.example.com/v1"
+api_key = "static-provider-key"
+
+[defaults.routing]
+channel = "myproxy/fast-model"
+branch = "anthropic/claude-sonnet-4"
+worker = "opnai/gpt-5"
+
+[[agents]]
+id = "main"
+
+[agents.routing]
+cortex = "myproxy/slow-model"
+compactor = "opnai/gpt-5"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        // Reported once, under the first key that uses it.
+        assert_eq!(
+            unknown_routing_providers(&config),
+            vec![(
+                "defaults.routing.worker".to_string(),
+                "opnai/gpt-5".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_default_model_is_checked_like_routing_models() {
+        let toml = r#"
+[llm]
+default_model = "opnai/gpt-5"
+
+[llm.provider.myproxy]
+api_type = "openai_chat_completions"
+base_url = "https://proxy.example.com/v1"
+api_key = "static-provider-key"
+
+[defaults.routing]
+channel = "myproxy/fast-model"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        assert_eq!(config.llm.default_model.as_deref(), Some("opnai/gpt-5"));
+        assert_eq!(
+            unknown_routing_providers(&config),
+            vec![(
+                "llm.default_model".to_string(),
+                "opnai/gpt-5".to_string()
+            )]
+        );
+
+        let blank: TomlConfig =
+            toml::from_str("[llm]\ndefault_model = \"  \"\n").expect("failed to parse test TOML");
+        let config = Config::from_toml(blank, PathBuf::from(".")).expect("failed to build Config");
+        assert_eq!(config.llm.default_model, None);
+    }
+
     /// Helper to build a minimal `SlackConfig` for permission tests.
     fn slack_config_with_dm_users(dm_allowed_users: Vec<String>) -> SlackConfig {
         SlackConfig {
@@ -565,6 +779,7 @@ bind = "127.0.0.1"
             instances: vec![],
             dm_allowed_users,
             commands: vec![],
+            require_mention_default: false,
         }
     }
 
@@ -581,6 +796,8 @@ bind = "127.0.0.1"
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users,
+            welcome_message: None,
+            schedule: None,
         }
     }
 
@@ -1034,6 +1251,7 @@ branch_timeout_secs = 77
 supervisor_kill_budget_per_tick = 3
 association_max_per_pass = 55
 maintenance_decay_rate = 0.33
+consolidation_min_cluster_size = 6
 "#;
         let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
         let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
@@ -1061,6 +1279,8 @@ maintenance_decay_rate = 0.33
         assert_eq!(resolved.cortex.maintenance_min_age_days, 17);
         assert_eq!(resolved.cortex.maintenance_merge_similarity_threshold, 0.95);
         assert_eq!(resolved.cortex.association_max_per_pass, 55);
+        assert_eq!(resolved.cortex.consolidation_min_cluster_size, 6);
+        assert_eq!(resolved.cortex.consolidation_interval_secs, 0);
     }
 
     #[test]
@@ -1438,6 +1658,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         assert_eq!(binding.runtime_adapter_key(), "telegram:sales");
     }
@@ -1455,6 +1677,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         assert!(binding.uses_default_adapter());
     }
@@ -1474,6 +1698,120 @@ maintenance_merge_similarity_threshold = 1.1
         }
     }
 
+    #[test]
+    fn binding_schedule_routes_across_window_boundary() {
+        use chrono::TimeZone as _;
+
+        let bindings = vec![Binding {
+            agent_id: "business-hours".into(),
+            channel: "telegram".into(),
+            adapter: None,
+            guild_id: None,
+            workspace_id: None,
+            chat_id: None,
+            team_id: None,
+            channel_ids: vec![],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: Some(BindingSchedule {
+                timezone: Some(chrono_tz::America::New_York),
+                windows: vec![
+                    BindingTimeWindow {
+                        agent_id: "weekend".into(),
+                        start_minute: 0,
+                        end_minute: 0,
+                        days: vec![chrono::Weekday::Sat, chrono::Weekday::Sun],
+                    },
+                    BindingTimeWindow {
+                        agent_id: "after-hours".into(),
+                        start_minute: 18 * 60,
+                        end_minute: 9 * 60,
+                        days: vec![],
+                    },
+                ],
+            }),
+        }];
+        let message = test_inbound_message("telegram", None);
+        // Wednesday 2026-03-04 in New York, UTC-5.
+        let at = |hour, minute| {
+            let local = chrono_tz::America::New_York
+                .with_ymd_and_hms(2026, 3, 4, hour, minute, 0)
+                .unwrap();
+            resolve_agent_for_message_at(
+                &bindings,
+                &message,
+                "main",
+                local.with_timezone(&chrono::Utc),
+            )
+        };
+
+        assert_eq!(at(8, 59).as_deref(), Some("after-hours"));
+        assert_eq!(at(9, 0).as_deref(), Some("business-hours"));
+        assert_eq!(at(17, 59).as_deref(), Some("business-hours"));
+        assert_eq!(at(18, 0).as_deref(), Some("after-hours"));
+        assert_eq!(at(23, 30).as_deref(), Some("after-hours"));
+
+        let saturday_noon = chrono_tz::America::New_York
+            .with_ymd_and_hms(2026, 3, 7, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            resolve_agent_for_message_at(&bindings, &message, "main", saturday_noon).as_deref(),
+            Some("weekend")
+        );
+        assert!(bindings[0].routes_to("after-hours"));
+        assert!(!bindings[0].routes_to("main"));
+    }
+
+    #[test]
+    fn binding_schedule_parses_from_toml() {
+        let parsed: TomlConfig = toml::from_str(
+            r#"
+[[bindings]]
+agent_id = "support"
+channel = "telegram"
+timezone = "Europe/Berlin"
+
+[[bindings.schedule]]
+agent_id = "night"
+start = "18:30"
+end = "08:00"
+days = ["mon", "Tuesday"]
+"#,
+        )
+        .expect("failed to parse test TOML");
+        let binding = parsed.bindings.into_iter().next().expect("one binding");
+
+        let schedule = parse_binding_schedule(&binding.agent_id, binding.timezone, binding.schedule)
+            .expect("valid schedule")
+            .expect("schedule present");
+        assert_eq!(schedule.timezone, Some(chrono_tz::Europe::Berlin));
+        assert_eq!(
+            schedule.windows,
+            vec![BindingTimeWindow {
+                agent_id: "night".into(),
+                start_minute: 18 * 60 + 30,
+                end_minute: 8 * 60,
+                days: vec![chrono::Weekday::Mon, chrono::Weekday::Tue],
+            }]
+        );
+
+        let invalid = |start: &str, timezone: &str| {
+            let window = TomlBindingWindow {
+                agent_id: "night".into(),
+                start: start.into(),
+                end: "08:00".into(),
+                days: vec![],
+            };
+            parse_binding_schedule("support", Some(timezone.into()), vec![window]).is_err()
+        };
+        assert!(invalid("25:00", "UTC"));
+        assert!(invalid("6pm", "UTC"));
+        assert!(invalid("18:00", "Mars/Olympus"));
+        assert!(!invalid("18:00", "UTC"));
+    }
+
     #[test]
     fn adapter_matches_default_binding_default_message() {
         let binding = Binding {
@@ -1487,6 +1825,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         let message = test_inbound_message("telegram", None);
         assert!(binding_adapter_matches(&binding, &message));
@@ -1505,6 +1845,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(binding_adapter_matches(&binding, &message));
@@ -1523,6 +1865,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         let message = test_inbound_message("telegram", None);
         assert!(!binding_adapter_matches(&binding, &message));
@@ -1541,6 +1885,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         let message = test_inbound_message("telegram", Some("telegram:support"));
         assert!(!binding_adapter_matches(&binding, &message));
@@ -1559,11 +1905,91 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         };
         let message = test_inbound_message("telegram", Some("telegram:sales"));
         assert!(!binding_adapter_matches(&binding, &message));
     }
 
+    #[test]
+    fn discord_forum_binding_covers_forum_posts() {
+        let bindings = vec![Binding {
+            agent_id: "forum-agent".into(),
+            channel: "discord".into(),
+            adapter: None,
+            guild_id: Some("100".into()),
+            workspace_id: None,
+            chat_id: None,
+            team_id: None,
+            channel_ids: vec!["200".into()],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
+        }];
+
+        // A post in the forum is a thread whose parent is the forum channel.
+        let mut message = test_inbound_message("discord", None);
+        message
+            .metadata
+            .insert("discord_guild_id".into(), 100u64.into());
+        message
+            .metadata
+            .insert("discord_channel_id".into(), 300u64.into());
+        message
+            .metadata
+            .insert("discord_parent_channel_id".into(), 200u64.into());
+        message
+            .metadata
+            .insert("discord_is_forum_post".into(), true.into());
+
+        let agent_id = resolve_agent_for_message(&bindings, &message, "main");
+        assert_eq!(agent_id.as_deref(), Some("forum-agent"));
+
+        message.metadata.remove("discord_parent_channel_id");
+        let agent_id = resolve_agent_for_message(&bindings, &message, "main");
+        assert_eq!(agent_id.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn telegram_binding_channel_ids_match_forum_topics() {
+        let bindings = vec![Binding {
+            agent_id: "topic-agent".into(),
+            channel: "telegram".into(),
+            adapter: None,
+            guild_id: None,
+            workspace_id: None,
+            chat_id: Some("-1001234".into()),
+            team_id: None,
+            channel_ids: vec!["42".into()],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
+        }];
+
+        let mut message = test_inbound_message("telegram", None);
+        message
+            .metadata
+            .insert("telegram_chat_id".into(), (-1001234i64).into());
+        message
+            .metadata
+            .insert("telegram_topic_id".into(), 42i64.into());
+        let agent_id = resolve_agent_for_message(&bindings, &message, "main");
+        assert_eq!(agent_id.as_deref(), Some("topic-agent"));
+
+        message
+            .metadata
+            .insert("telegram_topic_id".into(), 7i64.into());
+        let agent_id = resolve_agent_for_message(&bindings, &message, "main");
+        assert_eq!(agent_id.as_deref(), Some("main"));
+
+        message.metadata.remove("telegram_topic_id");
+        let agent_id = resolve_agent_for_message(&bindings, &message, "main");
+        assert_eq!(agent_id.as_deref(), Some("main"));
+    }
+
     #[test]
     fn validate_named_adapters_valid_config() {
         let messaging = MessagingConfig {
@@ -1579,6 +2005,7 @@ maintenance_merge_similarity_threshold = 1.1
                     dm_allowed_users: vec![],
                 }],
                 dm_allowed_users: vec![],
+                require_mention_default: false,
             }),
             email: None,
             webhook: None,
@@ -1598,6 +2025,8 @@ maintenance_merge_similarity_threshold = 1.1
                 channel_ids: vec![],
                 require_mention: false,
                 dm_allowed_users: vec![],
+                welcome_message: None,
+                schedule: None,
             },
             Binding {
                 agent_id: "support-agent".into(),
@@ -1610,6 +2039,8 @@ maintenance_merge_similarity_threshold = 1.1
                 channel_ids: vec![],
                 require_mention: false,
                 dm_allowed_users: vec![],
+                welcome_message: None,
+                schedule: None,
             },
         ];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_ok());
@@ -1625,6 +2056,7 @@ maintenance_merge_similarity_threshold = 1.1
                 token: "tok".into(),
                 instances: vec![],
                 dm_allowed_users: vec![],
+                require_mention_default: false,
             }),
             email: None,
             webhook: None,
@@ -1643,6 +2075,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -1708,6 +2142,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -1727,6 +2163,7 @@ maintenance_merge_similarity_threshold = 1.1
                     dm_allowed_users: vec![],
                 }],
                 dm_allowed_users: vec![],
+                require_mention_default: false,
             }),
             email: None,
             webhook: None,
@@ -1746,6 +2183,8 @@ maintenance_merge_similarity_threshold = 1.1
             channel_ids: vec![],
             require_mention: false,
             dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
         }];
         assert!(validate_named_messaging_adapters(&messaging, &bindings).is_err());
     }
@@ -1850,6 +2289,1062 @@ guild_id = "123456"
         assert_eq!(normalize_adapter(Some("ops".into())), Some("ops".into()));
     }
 
+    #[test]
+    fn reaction_triggers_match_unicode_and_shortcodes() {
+        let mut triggers = ReactionTriggers::new(std::collections::HashMap::from([
+            ("📌".to_string(), "Save it to memory.".to_string()),
+            (":eyes:".to_string(), "Summarize it.".to_string()),
+            ("PartyParrot".to_string(), "Celebrate.".to_string()),
+            ("👍".to_string(), "   ".to_string()),
+        ]));
+
+        // Discord reports unicode, Slack reports short-codes.
+        assert_eq!(triggers.intent_for("📌"), Some("Save it to memory."));
+        assert_eq!(triggers.intent_for("pushpin"), Some("Save it to memory."));
+        assert_eq!(triggers.intent_for("👀"), Some("Summarize it."));
+        assert_eq!(triggers.intent_for("partyparrot"), Some("Celebrate."));
+        // Empty intents are not triggers.
+        assert_eq!(triggers.intent_for("+1"), None);
+        assert_eq!(triggers.intent_for("fire"), None);
+
+        triggers.extend([("pushpin".to_string(), "Pin it.".to_string())]);
+        assert_eq!(triggers.intent_for("📌"), Some("Pin it."));
+    }
+
+    #[test]
+    fn adapters_see_reaction_triggers_of_every_agent() {
+        let triggers = ReactionTriggers::new(std::collections::HashMap::from([(
+            "🐢".to_string(),
+            "File a bug.".to_string(),
+        )]));
+        assert!(!ReactionTriggers::any_agent_triggers("turtle"));
+
+        ReactionTriggers::register("reaction-trigger-test", &triggers);
+        assert!(ReactionTriggers::any_agent_triggers("turtle"));
+        assert!(ReactionTriggers::any_agent_triggers("🐢"));
+
+        ReactionTriggers::register("reaction-trigger-test", &ReactionTriggers::default());
+        assert!(!ReactionTriggers::any_agent_triggers("turtle"));
+
+        ReactionTriggers::register("reaction-trigger-test", &triggers);
+        ReactionTriggers::unregister("reaction-trigger-test");
+        assert!(!ReactionTriggers::any_agent_triggers("turtle"));
+    }
+
+    #[test]
+    fn personas_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.personas]
+formal = "Write in complete sentences. No slang or emoji."
+Casual = "Keep it short and relaxed."
+
+[[agents]]
+id = "main"
+
+[agents.personas]
+casual = "Loose and friendly, emoji welcome."
+pirate = "   "
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            resolved.personas.prompt_for(" FORMAL "),
+            Some("Write in complete sentences. No slang or emoji.")
+        );
+        assert_eq!(
+            resolved.personas.prompt_for("casual"),
+            Some("Loose and friendly, emoji welcome.")
+        );
+        // Empty prompts are not personas.
+        assert_eq!(resolved.personas.prompt_for("pirate"), None);
+        let names: Vec<&str> = resolved.personas.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["casual", "formal"]);
+    }
+
+    #[test]
+    fn worker_buttons_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.worker_buttons]
+research = "Research the topic of the message the button was attached to."
+digest = "Summarize today's activity."
+
+[[agents]]
+id = "main"
+
+[agents.worker_buttons]
+Digest = "Summarize this week's activity."
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            resolved.worker_buttons.task_for("research"),
+            Some("Research the topic of the message the button was attached to.")
+        );
+        assert_eq!(
+            resolved.worker_buttons.task_for("DIGEST"),
+            Some("Summarize this week's activity.")
+        );
+        assert_eq!(resolved.worker_buttons.task_for("deploy"), None);
+    }
+
+    #[test]
+    fn channel_limit_settings_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.channel]
+max_concurrent_channels = 50
+overflow_policy = "reject"
+max_queued_conversations = 20
+busy_message = "Busy, try again soon."
+
+[[agents]]
+id = "main"
+
+[agents.channel]
+max_concurrent_channels = 5
+overflow_policy = "sideways"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(config.defaults.channel.max_concurrent_channels, 50);
+        assert_eq!(resolved.channel.max_concurrent_channels, 5);
+        // An unknown policy falls back to the defaults' policy.
+        assert_eq!(
+            resolved.channel.overflow_policy,
+            ChannelOverflowPolicy::Reject
+        );
+        assert_eq!(resolved.channel.busy_message, "Busy, try again soon.");
+        assert_eq!(resolved.channel.max_queued_conversations, 20);
+        assert_eq!(ChannelConfig::default().max_queued_conversations, 100);
+        assert_eq!(
+            ChannelConfig::default().overflow_policy,
+            ChannelOverflowPolicy::Queue
+        );
+    }
+
+    #[test]
+    fn event_sink_is_off_without_url_and_resolves_settings() {
+        let parsed: TomlConfig = toml::from_str("").expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        assert!(config.event_sink.url.is_none());
+
+        let toml = r#"
+[event_sink]
+url = "https://events.example.com/ingest"
+auth_token = "literal-token"
+event_types = ["worker_started", "worker_complete"]
+batch_size = 0
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let sink = &config.event_sink;
+
+        assert_eq!(
+            sink.url.as_deref(),
+            Some("https://events.example.com/ingest")
+        );
+        assert_eq!(sink.auth_token.as_deref(), Some("literal-token"));
+        assert_eq!(sink.batch_size, 1);
+        assert_eq!(sink.max_retries, EventSinkConfig::default().max_retries);
+        assert!(sink.forwards("worker_started"));
+        assert!(!sink.forwards("status_update"));
+        assert!(!format!("{sink:?}").contains("literal-token"));
+    }
+
+    #[test]
+    fn inbound_size_limit_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.channel]
+max_inbound_chars = 20000
+oversized_inbound = "summarize"
+
+[[agents]]
+id = "main"
+
+[agents.channel]
+oversized_inbound = "reject"
+oversized_message = "Too long."
+
+[[agents]]
+id = "other"
+
+[agents.channel]
+oversized_inbound = "shrink"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.channel.max_inbound_chars, 20000);
+        assert_eq!(main.channel.oversized_inbound, OversizedInboundPolicy::Reject);
+        assert_eq!(main.channel.oversized_message, "Too long.");
+        // An unknown policy falls back to the defaults' policy.
+        assert_eq!(
+            other.channel.oversized_inbound,
+            OversizedInboundPolicy::Summarize
+        );
+        assert_eq!(ChannelConfig::default().max_inbound_chars, 0);
+        assert_eq!(
+            ChannelConfig::default().oversized_inbound,
+            OversizedInboundPolicy::Truncate
+        );
+    }
+
+    #[test]
+    fn chat_commands_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.channel]
+command_prefix = "?"
+
+[[agents]]
+id = "main"
+
+[agents.channel]
+commands = ["Reset", "help", "dance", "help"]
+
+[[agents]]
+id = "other"
+
+[agents.channel]
+command_prefix = ""
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.channel.command_prefix, "?");
+        // Unknown and repeated names are dropped.
+        assert_eq!(
+            main.channel.commands,
+            vec![ChatCommand::Reset, ChatCommand::Help]
+        );
+        assert_eq!(other.channel.command_prefix, "");
+        assert!(other.channel.commands.is_empty());
+        assert_eq!(ChannelConfig::default().command_prefix, "!");
+        assert!(ChannelConfig::default().commands.is_empty());
+    }
+
+    #[test]
+    fn response_footer_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults]
+response_footer = "— {{agent_name}} (AI)"
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "support"
+response_footer = "— Support (automated)"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let support = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            main.response_footer.as_deref(),
+            Some("— {{agent_name}} (AI)")
+        );
+        assert_eq!(
+            support.response_footer.as_deref(),
+            Some("— Support (automated)")
+        );
+    }
+
+    #[test]
+    fn followup_settings_parse_with_quiet_hours() {
+        let toml = r#"
+[defaults.channel]
+followup_after_secs = 900
+followup_quiet_start_hour = 22
+followup_quiet_end_hour = 8
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "other"
+
+[agents.channel]
+followup_message = "Anything else?"
+followup_quiet_start_hour = 9
+followup_quiet_end_hour = 9
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.channel.followup_after_secs, 900);
+        assert_eq!(main.channel.followup_quiet_hours, Some((22, 8)));
+        assert_eq!(
+            main.channel.followup_message,
+            ChannelConfig::default().followup_message
+        );
+        assert_eq!(other.channel.followup_after_secs, 900);
+        assert_eq!(other.channel.followup_message, "Anything else?");
+        // Equal start and end hours mean no quiet window.
+        assert_eq!(other.channel.followup_quiet_hours, None);
+        assert_eq!(ChannelConfig::default().followup_after_secs, 0);
+    }
+
+    #[test]
+    fn history_max_age_sets_the_cutoff() {
+        let toml = r#"
+[defaults.channel]
+history_max_age_days = 30
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "other"
+
+[agents.channel]
+history_max_age_days = 0
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        let now = chrono::Utc::now();
+        assert_eq!(main.channel.history_max_age_days, 30);
+        assert_eq!(
+            main.channel.history_cutoff(now),
+            Some(now - chrono::Duration::days(30))
+        );
+        // 0 keeps history forever.
+        assert_eq!(other.channel.history_cutoff(now), None);
+        assert_eq!(ChannelConfig::default().history_cutoff(now), None);
+    }
+
+    #[test]
+    fn merge_toml_merges_tables_and_replaces_everything_else() {
+        let mut base: toml::Value = toml::from_str(
+            r#"
+[defaults]
+max_concurrent_branches = 2
+output_language = "English"
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "support"
+"#,
+        )
+        .expect("failed to parse base");
+        let overlay: toml::Value = toml::from_str(
+            r#"
+[defaults]
+max_concurrent_branches = 8
+
+[[agents]]
+id = "staging"
+"#,
+        )
+        .expect("failed to parse overlay");
+
+        merge_toml(&mut base, overlay);
+
+        assert_eq!(base["defaults"]["max_concurrent_branches"].as_integer(), Some(8));
+        assert_eq!(base["defaults"]["output_language"].as_str(), Some("English"));
+        let agents = base["agents"].as_array().expect("agents should be an array");
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0]["id"].as_str(), Some("staging"));
+    }
+
+    #[test]
+    fn overlay_path_for_names_file_after_environment() {
+        let base = PathBuf::from("/srv/spacebot/config.toml");
+
+        assert_eq!(
+            overlay_path_for(&base, "staging"),
+            Some(PathBuf::from("/srv/spacebot/config.staging.toml"))
+        );
+        assert_eq!(
+            overlay_path_for(&base, "eu_prod-2"),
+            Some(PathBuf::from("/srv/spacebot/config.eu_prod-2.toml"))
+        );
+        assert_eq!(overlay_path_for(&base, "../secrets"), None);
+        assert_eq!(overlay_path_for(&base, "a/b"), None);
+        assert_eq!(overlay_path_for(&base, ""), None);
+    }
+
+    #[test]
+    fn read_config_toml_applies_overlay_before_validation() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let base_path = dir.path().join("config.toml");
+        let overlay_path = dir.path().join("config.staging.toml");
+        std::fs::write(
+            &base_path,
+            r#"
+[defaults]
+max_concurrent_branches = 2
+response_footer = "— base"
+
+[[agents]]
+id = "main"
+"#,
+        )
+        .expect("failed to write base config");
+        std::fs::write(
+            &overlay_path,
+            r#"
+[defaults]
+response_footer = "— staging"
+"#,
+        )
+        .expect("failed to write overlay");
+
+        let parsed = read_config_toml(&base_path, Some(&overlay_path)).expect("failed to merge");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        assert_eq!(main.max_concurrent_branches, 2);
+        assert_eq!(main.response_footer.as_deref(), Some("— staging"));
+
+        // A missing overlay falls back to the base config alone.
+        let missing = dir.path().join("config.prod.toml");
+        let parsed = read_config_toml(&base_path, Some(&missing)).expect("failed to read base");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        assert_eq!(main.response_footer.as_deref(), Some("— base"));
+
+        // The merged document is still type-checked.
+        std::fs::write(&overlay_path, "[defaults]\nmax_concurrent_branches = \"many\"\n")
+            .expect("failed to rewrite overlay");
+        assert!(read_config_toml(&base_path, Some(&overlay_path)).is_err());
+    }
+
+    #[test]
+    fn memory_recall_settings_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.channel]
+memory_recall_count = 8
+memory_recall_order = "recency"
+
+[[agents]]
+id = "main"
+
+[agents.channel]
+memory_recall_count = 3
+
+[[agents]]
+id = "other"
+
+[agents.channel]
+memory_recall_order = "newest"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.channel.memory_recall_count, 3);
+        assert_eq!(main.channel.memory_recall_order, MemoryRecallOrder::Recency);
+        // An unknown order falls back to the defaults' order.
+        assert_eq!(other.channel.memory_recall_count, 8);
+        assert_eq!(other.channel.memory_recall_order, MemoryRecallOrder::Recency);
+        assert_eq!(ChannelConfig::default().memory_recall_count, 0);
+        assert_eq!(
+            ChannelConfig::default().memory_recall_order,
+            MemoryRecallOrder::Relevance
+        );
+    }
+
+    #[test]
+    fn memory_decay_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.memory_decay]
+half_life_days = 30.0
+min_weight = 0.2
+
+[[agents]]
+id = "main"
+
+[agents.memory_decay]
+half_life_days = 7
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(config.defaults.memory_decay.half_life_days, 30.0);
+        assert_eq!(resolved.memory_decay.half_life_days, 7.0);
+        assert_eq!(resolved.memory_decay.min_weight, 0.2);
+
+        let week = chrono::Duration::days(7);
+        assert!((resolved.memory_decay.weight(week) - 0.5).abs() < 1e-9);
+        assert_eq!(
+            resolved.memory_decay.weight(chrono::Duration::days(700)),
+            0.2
+        );
+        assert_eq!(MemoryDecayConfig::default().weight(week), 1.0);
+    }
+
+    #[test]
+    fn memory_scrub_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.memory_scrub]
+patterns = ["ACCT-\\d+"]
+
+[[agents]]
+id = "main"
+
+[agents.memory_scrub]
+enabled = true
+redact_phone_numbers = false
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert!(!config.defaults.memory_scrub.enabled);
+        assert!(resolved.memory_scrub.enabled);
+        assert!(resolved.memory_scrub.redact_emails);
+        assert!(!resolved.memory_scrub.redact_phone_numbers);
+        assert_eq!(resolved.memory_scrub.patterns, vec![r"ACCT-\d+".to_string()]);
+    }
+
+    #[test]
+    fn memory_scrub_rejects_invalid_patterns() {
+        let toml = r#"
+[defaults.memory_scrub]
+enabled = true
+patterns = ["(unclosed"]
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let error = Config::from_toml(parsed, PathBuf::from("."))
+            .expect_err("invalid pattern should be rejected");
+        assert!(error.to_string().contains("memory_scrub pattern"));
+    }
+
+    #[test]
+    fn embedding_routes_parse_and_inherit() {
+        let toml = r#"
+[[defaults.embedding.routes]]
+content_type = "code"
+model = "jinaai/jina-embeddings-v2-base-code"
+passage_prefix = "code: "
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "prose"
+
+[agents.embedding]
+routes = []
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let prose = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.embedding.routes.len(), 1);
+        let route = &main.embedding.routes[0];
+        assert_eq!(route.content_type, EmbeddingContentType::Code);
+        assert_eq!(route.model, "jinaai/jina-embeddings-v2-base-code");
+        assert_eq!(route.passage_text("fn main() {}"), "code: fn main() {}");
+        assert_eq!(route.query_text("fn main"), "fn main");
+        assert!(prose.embedding.routes.is_empty());
+    }
+
+    #[test]
+    fn embedding_routes_reject_invalid_entries() {
+        for (routes, expected) in [
+            (
+                r#"{ content_type = "image", model = "jinaai/jina-embeddings-v2-base-code" }"#,
+                "content_type 'image'",
+            ),
+            (
+                r#"{ content_type = "code", model = "not/a-model" }"#,
+                "embedding route model",
+            ),
+            (
+                r#"{ content_type = "code", model = "jinaai/jina-embeddings-v2-base-code" }, { content_type = "Code", model = "Xenova/all-MiniLM-L6-v2" }"#,
+                "more than once",
+            ),
+        ] {
+            let toml = format!("[defaults.embedding]\nroutes = [{routes}]\n");
+            let parsed: TomlConfig = toml::from_str(&toml).expect("failed to parse test TOML");
+            let error = Config::from_toml(parsed, PathBuf::from("."))
+                .expect_err("invalid embedding route should be rejected");
+            assert!(
+                error.to_string().contains(expected),
+                "unexpected error: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn transcription_keys_are_not_sent_to_other_endpoints() {
+        let toml = r#"
+[defaults.transcription]
+endpoint = "https://api.groq.com/openai/v1/audio/transcriptions"
+api_key = "groq-key"
+
+[[agents]]
+id = "main"
+
+[agents.transcription]
+model = "whisper-large-v3"
+
+[[agents]]
+id = "local"
+
+[agents.transcription]
+endpoint = "http://localhost:9000/v1/audio/transcriptions"
+
+[[agents]]
+id = "groq"
+
+[agents.transcription]
+endpoint = "https://api.groq.com/openai/v1/audio/transcriptions"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        assert_eq!(
+            config.defaults.transcription.api_key.as_deref(),
+            Some("groq-key")
+        );
+
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        assert_eq!(main.transcription.api_key.as_deref(), Some("groq-key"));
+
+        // Neither the inherited key nor OPENAI_API_KEY follows a new endpoint.
+        let local = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+        assert_eq!(local.transcription.api_key, None);
+
+        let groq = config.agents[2].resolve(&config.instance_dir, &config.defaults);
+        assert_eq!(groq.transcription.api_key.as_deref(), Some("groq-key"));
+    }
+
+    #[test]
+    fn tool_call_limits_layer_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.tool_call_limits]
+max_tool_calls_per_turn = 40
+max_repeat_same_tool = 15
+
+[[agents]]
+id = "main"
+
+[agents.tool_call_limits]
+max_repeat_same_tool = 5
+
+[[agents]]
+id = "other"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            main.tool_call_limits,
+            ToolCallLimits {
+                max_tool_calls_per_turn: 40,
+                max_repeat_same_tool: 5,
+            }
+        );
+        assert_eq!(other.tool_call_limits, config.defaults.tool_call_limits);
+        assert_eq!(ToolCallLimits::default().max_tool_calls_per_turn, 0);
+    }
+
+    #[test]
+    fn stream_pacing_defaults_off_and_layers_agent_overrides() {
+        let toml = r#"
+[defaults.stream_pacing]
+chars_per_second = 60
+
+[[agents]]
+id = "main"
+
+[agents.stream_pacing]
+chars_per_second = 25
+
+[[agents]]
+id = "other"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.stream_pacing.chars_per_second, 25);
+        assert_eq!(other.stream_pacing.chars_per_second, 60);
+        assert_eq!(StreamPacingConfig::default().chars_per_second, 0);
+    }
+
+    #[test]
+    fn ingestion_extensions_normalize_and_layer_over_defaults() {
+        let toml = r#"
+[defaults.ingestion]
+extensions = [".PDF", " md ", ""]
+
+[[agents]]
+id = "main"
+
+[agents.ingestion]
+chunk_size = 2000
+max_retries = 0
+
+[[agents]]
+id = "other"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.ingestion.extensions, vec!["pdf", "md"]);
+        assert_eq!(main.ingestion.chunk_size, 2000);
+        assert_eq!(other.ingestion.extensions, vec!["pdf", "md"]);
+        assert_eq!(main.ingestion.max_retries, 0);
+        assert_eq!(other.ingestion.max_retries, 3);
+        assert!(
+            IngestionConfig::default()
+                .extensions
+                .contains(&"docx".to_string())
+        );
+    }
+
+    #[test]
+    fn max_concurrent_processes_defaults_unlimited_and_layers_agent_overrides() {
+        let toml = r#"
+[defaults]
+max_concurrent_processes = 4
+
+[[agents]]
+id = "main"
+max_concurrent_processes = 2
+
+[[agents]]
+id = "other"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.max_concurrent_processes, 2);
+        assert_eq!(other.max_concurrent_processes, 4);
+        assert_eq!(DefaultsConfig::default().max_concurrent_processes, 0);
+    }
+
+    #[test]
+    fn cortex_language_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults]
+cortex_language = "Japanese"
+
+[[agents]]
+id = "main"
+cortex_language = "German"
+
+[[agents]]
+id = "other"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let other = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.cortex_language.as_deref(), Some("German"));
+        assert_eq!(other.cortex_language.as_deref(), Some("Japanese"));
+    }
+
+    #[test]
+    fn bindings_inherit_platform_require_mention_default() {
+        let toml = r#"
+[messaging.discord]
+enabled = true
+token = "my-discord-token"
+require_mention_default = true
+
+[messaging.slack]
+enabled = true
+bot_token = "xoxb-test"
+app_token = "xapp-test"
+
+[[bindings]]
+agent_id = "main"
+channel = "discord"
+guild_id = "1"
+
+[[bindings]]
+agent_id = "main"
+channel = "discord"
+guild_id = "2"
+require_mention = false
+
+[[bindings]]
+agent_id = "main"
+channel = "slack"
+workspace_id = "T1"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+
+        assert!(config.bindings[0].require_mention);
+        assert!(!config.bindings[1].require_mention);
+        assert!(!config.bindings[2].require_mention);
+    }
+
+    #[test]
+    fn discord_stream_edits_are_opt_in_with_default_interval() {
+        let parse = |toml: &str| {
+            let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+            let config =
+                Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+            config.messaging.discord.expect("discord config")
+        };
+
+        let off = parse(
+            r#"
+[messaging.discord]
+enabled = true
+token = "my-discord-token"
+"#,
+        );
+        assert!(!off.stream_edits);
+        assert_eq!(off.stream_edit_interval(), None);
+
+        let on = parse(
+            r#"
+[messaging.discord]
+enabled = true
+token = "my-discord-token"
+stream_edits = true
+"#,
+        );
+        assert_eq!(
+            on.stream_edit_interval(),
+            Some(std::time::Duration::from_millis(
+                DEFAULT_DISCORD_STREAM_EDIT_INTERVAL_MS
+            ))
+        );
+
+        let tuned = parse(
+            r#"
+[messaging.discord]
+enabled = true
+token = "my-discord-token"
+stream_edits = true
+stream_edit_interval_ms = 2500
+"#,
+        );
+        assert_eq!(
+            tuned.stream_edit_interval(),
+            Some(std::time::Duration::from_millis(2500))
+        );
+    }
+
+    #[test]
+    fn scoped_default_mcp_servers_only_resolve_for_listed_agents() {
+        let toml = r#"
+[[defaults.mcp]]
+name = "filesystem"
+transport = "stdio"
+command = "npx"
+
+[[defaults.mcp]]
+name = "postgres"
+transport = "stdio"
+command = "npx"
+agents = ["main"]
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "other"
+
+[[agents]]
+id = "ops"
+
+[[agents.mcp]]
+name = "postgres"
+transport = "http"
+url = "https://db.example.com/mcp"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let server_names = |index: usize| -> Vec<String> {
+            config.agents[index]
+                .resolve(&config.instance_dir, &config.defaults)
+                .mcp
+                .into_iter()
+                .map(|server| server.name)
+                .collect()
+        };
+
+        assert_eq!(server_names(0), vec!["filesystem", "postgres"]);
+        assert_eq!(server_names(1), vec!["filesystem"]);
+        // An agent's own entry still applies when the default excludes it.
+        assert_eq!(server_names(2), vec!["filesystem", "postgres"]);
+        assert_eq!(config.defaults.mcp[1].agents, vec!["main"]);
+    }
+
+    #[test]
+    fn prompt_overrides_resolve_against_instance_dir() {
+        let instance_dir = tempfile::tempdir().expect("failed to create temp dir");
+        std::fs::create_dir(instance_dir.path().join("prompts")).unwrap();
+        std::fs::write(instance_dir.path().join("prompts/compactor.md"), "short").unwrap();
+        std::fs::write(instance_dir.path().join("prompts/worker.md"), "work").unwrap();
+        let toml = r#"
+[defaults.prompt_overrides]
+compactor = "prompts/compactor.md"
+
+[[agents]]
+id = "main"
+
+[agents.prompt_overrides]
+worker = "prompts/worker.md"
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, instance_dir.path().to_path_buf())
+            .expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        let overrides: Vec<(String, PathBuf)> = resolved
+            .prompt_overrides
+            .iter()
+            .map(|(name, path)| (name.to_string(), path.to_path_buf()))
+            .collect();
+        assert_eq!(
+            overrides,
+            vec![
+                (
+                    "compactor".to_string(),
+                    instance_dir.path().join("prompts/compactor.md")
+                ),
+                (
+                    "worker".to_string(),
+                    instance_dir.path().join("prompts/worker.md")
+                ),
+            ]
+        );
+
+        let prompts = crate::prompts::PromptEngine::new("en")
+            .unwrap()
+            .with_overrides(&resolved.prompt_overrides)
+            .expect("overrides should load");
+        assert_eq!(prompts.overridden_templates(), vec!["compactor", "worker"]);
+        assert_eq!(prompts.render_static("worker").unwrap(), "work");
+    }
+
+    #[test]
+    fn prompt_overrides_reject_unknown_templates_and_missing_files() {
+        let unknown = r#"
+[defaults.prompt_overrides]
+fragments = "prompts/fragments.md"
+"#;
+        let parsed: TomlConfig = toml::from_str(unknown).expect("failed to parse test TOML");
+        let error = Config::from_toml(parsed, PathBuf::from("."))
+            .expect_err("unknown template should be rejected");
+        assert!(error.to_string().contains("unknown prompt override 'fragments'"));
+
+        let missing = r#"
+[defaults.prompt_overrides]
+worker = "prompts/does-not-exist.md"
+"#;
+        let parsed: TomlConfig = toml::from_str(missing).expect("failed to parse test TOML");
+        let error = Config::from_toml(parsed, PathBuf::from("."))
+            .expect_err("missing file should be rejected");
+        assert!(error.to_string().contains("not a file"));
+    }
+
+    #[test]
+    fn worker_retry_layers_agent_overrides_over_defaults() {
+        let toml = r#"
+[defaults.worker_retry]
+max_retries = 3
+backoff_secs = 5
+
+[[agents]]
+id = "main"
+
+[agents.worker_retry]
+preserve_context = false
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let resolved = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(
+            resolved.worker_retry,
+            WorkerRetryConfig {
+                max_retries: 3,
+                backoff_secs: 5,
+                preserve_context: false,
+            }
+        );
+        assert_eq!(resolved.worker_retry.backoff(1).as_secs(), 5);
+        assert_eq!(resolved.worker_retry.backoff(3).as_secs(), 20);
+        assert_eq!(WorkerRetryConfig::default().max_retries, 0);
+    }
+
+    #[test]
+    fn compaction_turn_limit_inherits_and_zero_disables() {
+        let toml = r#"
+[defaults.compaction]
+max_turns_before_compact = 40
+
+[[agents]]
+id = "main"
+
+[[agents]]
+id = "quiet"
+
+[agents.compaction]
+max_turns_before_compact = 0
+"#;
+
+        let parsed: TomlConfig = toml::from_str(toml).expect("failed to parse test TOML");
+        let config = Config::from_toml(parsed, PathBuf::from(".")).expect("failed to build Config");
+        let main = config.agents[0].resolve(&config.instance_dir, &config.defaults);
+        let quiet = config.agents[1].resolve(&config.instance_dir, &config.defaults);
+
+        assert_eq!(main.compaction.max_turns_before_compact, Some(40));
+        assert_eq!(quiet.compaction.max_turns_before_compact, None);
+        assert_eq!(CompactionConfig::default().max_turns_before_compact, None);
+    }
+
     #[test]
     fn warn_unknown_config_keys_no_panic() {
         // Smoke test: the function should not panic for any input shape.
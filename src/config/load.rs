@@ -1,3 +1,4 @@
+use super::overlay;
 use super::providers::{
     ANTHROPIC_PROVIDER_BASE_URL, DEEPSEEK_PROVIDER_BASE_URL, FIREWORKS_PROVIDER_BASE_URL,
     GEMINI_PROVIDER_BASE_URL, GITHUB_COPILOT_DEFAULT_BASE_URL, GROQ_PROVIDER_BASE_URL,
@@ -11,15 +12,21 @@ use super::providers::{
 };
 use super::toml_schema::*;
 use super::{
-    AgentConfig, ApiConfig, ApiType, Binding, BrowserConfig, ChannelConfig, ClosePolicy,
-    CoalesceConfig, CompactionConfig, Config, CortexConfig, CronDef, DefaultsConfig, DiscordConfig,
-    DiscordInstanceConfig, EmailConfig, EmailInstanceConfig, GroupDef, HumanDef, IngestionConfig,
-    LinkDef, LlmConfig, MattermostConfig, MattermostInstanceConfig, McpServerConfig, McpTransport,
-    MemoryPersistenceConfig, MessagingConfig, MetricsConfig, OpenCodeConfig, ProjectsConfig,
-    ProviderConfig, SignalConfig, SignalInstanceConfig, SlackCommandConfig, SlackConfig,
-    SlackInstanceConfig, TelegramConfig, TelegramInstanceConfig, TelemetryConfig, TwitchConfig,
-    TwitchInstanceConfig, WarmupConfig, WebhookConfig, normalize_adapter,
-    validate_named_messaging_adapters,
+    AgentConfig, ApiConfig, ApiType, AppAttribution, Binding, BindingSchedule, BindingTimeWindow,
+    BrowserConfig, ChannelConfig, ChannelOverflowPolicy, ChatCommand, ClosePolicy, CoalesceConfig,
+    CompactionConfig, Config, CortexConfig, CronDef, DEFAULT_DISCORD_STREAM_EDIT_INTERVAL_MS,
+    DbConfig, DefaultsConfig, DiscordConfig, DiscordInstanceConfig, EmailConfig,
+    EmailInstanceConfig, EmbeddingConfig, EmbeddingContentType, EmbeddingRoute, EventSinkConfig,
+    GroupDef, HumanDef, IngestionConfig, LinkDef, LlmConfig, MattermostConfig,
+    MattermostInstanceConfig, McpServerConfig, McpTransport, MediaSafetyConfig, MemoryDecayConfig,
+    MemoryPersistenceConfig, MemoryRecallOrder, MemoryScrubConfig, MessagingConfig, MetricsConfig,
+    OPENAI_TRANSCRIPTION_ENDPOINT, OpenCodeConfig, OversizedInboundPolicy, Personas,
+    ProjectsConfig, PromptOverrides, ProviderConfig, ProviderHealthConfig, ReactionTriggers,
+    SignalConfig, SignalInstanceConfig, SlackCommandConfig, SlackConfig, SlackInstanceConfig,
+    StreamPacingConfig, TaskExtractionConfig, TelegramConfig, TelegramInstanceConfig,
+    TelemetryConfig, ToolCallLimits, ToolResultLimits, TranscriptionConfig, TwitchConfig,
+    TwitchInstanceConfig, WarmupConfig, WebhookConfig, WorkerButtons, WorkerLogRetention,
+    WorkerRetryConfig, normalize_adapter, validate_named_messaging_adapters,
 };
 use crate::error::{ConfigError, Result};
 
@@ -80,8 +87,54 @@ const KNOWN_TOP_LEVEL_KEYS: &[&str] = &[
     "api",
     "metrics",
     "telemetry",
+    "db",
+    "event_sink",
 ];
 
+/// Read and parse a config file, deep-merging `overlay_path` over it when
+/// that file exists. Both files are checked for unknown keys; the merged
+/// document is what gets parsed and validated.
+pub(super) fn read_config_toml(path: &Path, overlay_path: Option<&Path>) -> Result<TomlConfig> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config from {}", path.display()))?;
+    warn_unknown_config_keys(&content);
+
+    let overlay_path = overlay_path.filter(|overlay_path| {
+        let exists = overlay_path.exists();
+        if !exists {
+            tracing::warn!(
+                path = %overlay_path.display(),
+                "{} is set but its config overlay does not exist, using the base config only",
+                overlay::ENV_VAR
+            );
+        }
+        exists
+    });
+    let Some(overlay_path) = overlay_path else {
+        return Ok(toml::from_str(&content)
+            .with_context(|| format!("failed to parse config from {}", path.display()))?);
+    };
+
+    let overlay_content = std::fs::read_to_string(overlay_path)
+        .with_context(|| format!("failed to read config overlay {}", overlay_path.display()))?;
+    warn_unknown_config_keys(&overlay_content);
+
+    let mut merged: toml::Value = toml::from_str(&content)
+        .with_context(|| format!("failed to parse config from {}", path.display()))?;
+    let overlay: toml::Value = toml::from_str(&overlay_content)
+        .with_context(|| format!("failed to parse config overlay {}", overlay_path.display()))?;
+    overlay::merge_toml(&mut merged, overlay);
+    tracing::info!(path = %overlay_path.display(), "applied config overlay");
+
+    Ok(merged.try_into::<TomlConfig>().with_context(|| {
+        format!(
+            "invalid config after merging {} over {}",
+            overlay_path.display(),
+            path.display()
+        )
+    })?)
+}
+
 /// Pre-parse check that warns about unrecognised top-level keys in a config
 /// file.  Serde's default behaviour silently drops unknown fields, which leads
 /// to confusing "my setting does nothing" bugs (see issue #221).
@@ -128,6 +181,190 @@ fn parse_close_policy(value: Option<&str>) -> Option<ClosePolicy> {
     }
 }
 
+fn parse_channel_overflow_policy(value: Option<&str>) -> Option<ChannelOverflowPolicy> {
+    match value? {
+        "queue" => Some(ChannelOverflowPolicy::Queue),
+        "reject" => Some(ChannelOverflowPolicy::Reject),
+        other => {
+            tracing::warn!(
+                value = other,
+                "unknown overflow_policy value, expected one of: queue, reject"
+            );
+            None
+        }
+    }
+}
+
+fn parse_oversized_inbound_policy(value: Option<&str>) -> Option<OversizedInboundPolicy> {
+    match value? {
+        "truncate" => Some(OversizedInboundPolicy::Truncate),
+        "summarize" => Some(OversizedInboundPolicy::Summarize),
+        "reject" => Some(OversizedInboundPolicy::Reject),
+        other => {
+            tracing::warn!(
+                value = other,
+                "unknown oversized_inbound value, expected one of: truncate, summarize, reject"
+            );
+            None
+        }
+    }
+}
+
+fn parse_chat_commands(names: &[String]) -> Vec<ChatCommand> {
+    let mut commands = Vec::new();
+    for name in names {
+        match ChatCommand::parse(name.trim()) {
+            Some(command) if !commands.contains(&command) => commands.push(command),
+            Some(_) => {}
+            None => tracing::warn!(
+                value = %name,
+                "unknown chat command, expected one of: reset, status, help"
+            ),
+        }
+    }
+    commands
+}
+
+fn parse_memory_recall_order(value: Option<&str>) -> Option<MemoryRecallOrder> {
+    match value? {
+        "relevance" => Some(MemoryRecallOrder::Relevance),
+        "recency" => Some(MemoryRecallOrder::Recency),
+        other => {
+            tracing::warn!(
+                value = other,
+                "unknown memory_recall_order value, expected one of: relevance, recency"
+            );
+            None
+        }
+    }
+}
+
+/// Merge a `[channel]` table over a base config.
+fn resolve_channel(toml: TomlChannelConfig, base: &ChannelConfig) -> ChannelConfig {
+    ChannelConfig {
+        listen_only_mode: toml.listen_only_mode.unwrap_or(base.listen_only_mode),
+        save_attachments: toml.save_attachments.unwrap_or(base.save_attachments),
+        idle_timeout_secs: toml.idle_timeout_secs.unwrap_or(base.idle_timeout_secs),
+        notes_prompt_max_chars: toml
+            .notes_prompt_max_chars
+            .unwrap_or(base.notes_prompt_max_chars),
+        memory_recall_count: toml.memory_recall_count.unwrap_or(base.memory_recall_count),
+        memory_recall_order: parse_memory_recall_order(toml.memory_recall_order.as_deref())
+            .unwrap_or(base.memory_recall_order),
+        max_concurrent_channels: toml
+            .max_concurrent_channels
+            .unwrap_or(base.max_concurrent_channels),
+        overflow_policy: parse_channel_overflow_policy(toml.overflow_policy.as_deref())
+            .unwrap_or(base.overflow_policy),
+        max_queued_conversations: toml
+            .max_queued_conversations
+            .unwrap_or(base.max_queued_conversations),
+        busy_message: toml
+            .busy_message
+            .unwrap_or_else(|| base.busy_message.clone()),
+        max_inbound_chars: toml.max_inbound_chars.unwrap_or(base.max_inbound_chars),
+        oversized_inbound: parse_oversized_inbound_policy(toml.oversized_inbound.as_deref())
+            .unwrap_or(base.oversized_inbound),
+        oversized_message: toml
+            .oversized_message
+            .unwrap_or_else(|| base.oversized_message.clone()),
+        command_prefix: toml
+            .command_prefix
+            .map(|prefix| prefix.trim().to_string())
+            .unwrap_or_else(|| base.command_prefix.clone()),
+        commands: toml
+            .commands
+            .map(|names| parse_chat_commands(&names))
+            .unwrap_or_else(|| base.commands.clone()),
+        followup_after_secs: toml.followup_after_secs.unwrap_or(base.followup_after_secs),
+        followup_message: toml
+            .followup_message
+            .unwrap_or_else(|| base.followup_message.clone()),
+        followup_quiet_hours: match (toml.followup_quiet_start_hour, toml.followup_quiet_end_hour) {
+            (Some(start), Some(end)) => parse_quiet_hours(start, end),
+            (None, None) => base.followup_quiet_hours,
+            _ => {
+                tracing::warn!(
+                    "followup_quiet_start_hour and followup_quiet_end_hour must be set together, ignoring"
+                );
+                base.followup_quiet_hours
+            }
+        },
+        history_max_age_days: toml
+            .history_max_age_days
+            .unwrap_or(base.history_max_age_days),
+        slots_in_prompt: toml.slots_in_prompt.unwrap_or(base.slots_in_prompt),
+    }
+}
+
+/// Parse a binding's time-of-day windows. A binding without windows has no
+/// schedule, whatever its timezone.
+pub(super) fn parse_binding_schedule(
+    agent_id: &str,
+    timezone: Option<String>,
+    windows: Vec<TomlBindingWindow>,
+) -> Result<Option<BindingSchedule>> {
+    if windows.is_empty() {
+        return Ok(None);
+    }
+    let invalid = |detail: String| -> crate::error::Error {
+        ConfigError::Invalid(format!(
+            "binding for agent '{agent_id}' has an invalid schedule: {detail}"
+        ))
+        .into()
+    };
+
+    let timezone = timezone
+        .map(|name| {
+            name.trim()
+                .parse::<chrono_tz::Tz>()
+                .map_err(|_| invalid(format!("unknown timezone '{name}'")))
+        })
+        .transpose()?;
+
+    let windows = windows
+        .into_iter()
+        .map(|window| {
+            let days = window
+                .days
+                .iter()
+                .map(|day| {
+                    day.trim()
+                        .parse::<chrono::Weekday>()
+                        .map_err(|_| invalid(format!("unknown day '{day}'")))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(BindingTimeWindow {
+                start_minute: parse_minute_of_day(&window.start)
+                    .ok_or_else(|| invalid(format!("start '{}' is not HH:MM", window.start)))?,
+                end_minute: parse_minute_of_day(&window.end)
+                    .ok_or_else(|| invalid(format!("end '{}' is not HH:MM", window.end)))?,
+                agent_id: window.agent_id,
+                days,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(BindingSchedule { timezone, windows }))
+}
+
+/// `"HH:MM"` (24-hour) as minutes after midnight.
+fn parse_minute_of_day(value: &str) -> Option<u16> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    let hour: u16 = hour.parse().ok()?;
+    let minute: u16 = minute.parse().ok()?;
+    (hour < 24 && minute < 60).then_some(hour * 60 + minute)
+}
+
+/// Validate a quiet-hours window. Equal hours mean no window.
+fn parse_quiet_hours(start: u8, end: u8) -> Option<(u8, u8)> {
+    if start > 23 || end > 23 {
+        tracing::warn!(start, end, "followup quiet hours must be 0-23, ignoring");
+        return None;
+    }
+    (start != end).then_some((start, end))
+}
+
 /// Resolve the effective close policy. When `persist_session` is enabled and no
 /// explicit `close_policy` was provided, default to `Detach` so browser tabs and
 /// cookies survive across workers.
@@ -143,6 +380,286 @@ fn resolve_close_policy(
     })
 }
 
+/// Merge a `[media_safety]` table over a base config. The API key falls back
+/// to the base key, then to `OPENAI_API_KEY`.
+fn resolve_media_safety(
+    toml: TomlMediaSafetyConfig,
+    base: &MediaSafetyConfig,
+) -> MediaSafetyConfig {
+    MediaSafetyConfig {
+        enabled: toml.enabled.unwrap_or(base.enabled),
+        endpoint: toml.endpoint.unwrap_or_else(|| base.endpoint.clone()),
+        model: toml.model.unwrap_or_else(|| base.model.clone()),
+        api_key: toml
+            .api_key
+            .as_deref()
+            .and_then(resolve_env_value)
+            .or_else(|| base.api_key.clone())
+            .or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+        block_threshold: toml
+            .block_threshold
+            .unwrap_or(base.block_threshold)
+            .clamp(0.0, 1.0),
+        blocked_response: match toml.blocked_response {
+            Some(response) if response.trim().is_empty() => None,
+            Some(response) => Some(response),
+            None => base.blocked_response.clone(),
+        },
+    }
+}
+
+/// Merge a `[task_extraction]` table over a base config.
+fn resolve_task_extraction(
+    toml: TomlTaskExtractionConfig,
+    base: TaskExtractionConfig,
+) -> TaskExtractionConfig {
+    TaskExtractionConfig {
+        enabled: toml.enabled.unwrap_or(base.enabled),
+        min_confidence: toml
+            .min_confidence
+            .unwrap_or(base.min_confidence)
+            .clamp(0.0, 1.0),
+    }
+}
+
+/// Resolve the `[db]` table. The pool needs at least one connection and a
+/// nonzero busy timeout to be usable, so those are floored at 1.
+fn resolve_db(toml: TomlDbConfig) -> DbConfig {
+    let base = DbConfig::default();
+    DbConfig {
+        max_connections: toml.max_connections.unwrap_or(base.max_connections).max(1),
+        busy_timeout_secs: toml
+            .busy_timeout_secs
+            .unwrap_or(base.busy_timeout_secs)
+            .max(1),
+        acquire_timeout_secs: toml
+            .acquire_timeout_secs
+            .unwrap_or(base.acquire_timeout_secs)
+            .max(1),
+        statement_timeout_secs: toml
+            .statement_timeout_secs
+            .unwrap_or(base.statement_timeout_secs),
+        wal: toml.wal.unwrap_or(base.wal),
+    }
+}
+
+/// Resolve the `[event_sink]` table. The URL and token accept `env:` and
+/// `secret:` references, and a blank URL leaves the sink off. Batches and the
+/// queue need room for at least one event.
+fn resolve_event_sink(toml: TomlEventSinkConfig) -> EventSinkConfig {
+    let base = EventSinkConfig::default();
+    EventSinkConfig {
+        url: toml
+            .url
+            .as_deref()
+            .and_then(resolve_env_value)
+            .filter(|url| !url.trim().is_empty()),
+        auth_token: toml.auth_token.as_deref().and_then(resolve_env_value),
+        headers: toml
+            .headers
+            .into_iter()
+            .filter_map(|(name, value)| resolve_env_value(&value).map(|value| (name, value)))
+            .collect(),
+        event_types: toml.event_types,
+        batch_size: toml.batch_size.unwrap_or(base.batch_size).max(1),
+        flush_interval_ms: toml.flush_interval_ms.unwrap_or(base.flush_interval_ms),
+        max_retries: toml.max_retries.unwrap_or(base.max_retries),
+        queue_capacity: toml.queue_capacity.unwrap_or(base.queue_capacity).max(1),
+    }
+}
+
+/// Merge a `[transcription]` table over a base config. The API key falls back
+/// to the base key when the endpoint is unchanged, then to `OPENAI_API_KEY`
+/// when the endpoint is OpenAI's, so neither key is sent to another provider.
+fn resolve_transcription(
+    toml: TomlTranscriptionConfig,
+    base: &TranscriptionConfig,
+) -> TranscriptionConfig {
+    let endpoint = toml.endpoint.unwrap_or_else(|| base.endpoint.clone());
+    let api_key = toml
+        .api_key
+        .as_deref()
+        .and_then(resolve_env_value)
+        .or_else(|| {
+            (endpoint == base.endpoint)
+                .then(|| base.api_key.clone())
+                .flatten()
+        })
+        .or_else(|| {
+            (endpoint == OPENAI_TRANSCRIPTION_ENDPOINT)
+                .then(|| std::env::var("OPENAI_API_KEY").ok())
+                .flatten()
+        });
+    TranscriptionConfig {
+        enabled: toml.enabled.unwrap_or(base.enabled),
+        endpoint,
+        model: toml.model.unwrap_or_else(|| base.model.clone()),
+        api_key,
+        language: match toml.language {
+            Some(language) if language.trim().is_empty() => None,
+            Some(language) => Some(language),
+            None => base.language.clone(),
+        },
+    }
+}
+
+/// Merge an `[embedding]` table over a base config, rejecting routes with an
+/// unknown content type or model.
+fn resolve_embedding(toml: TomlEmbeddingConfig, base: &EmbeddingConfig) -> Result<EmbeddingConfig> {
+    let routes = match toml.routes {
+        Some(routes) => routes
+            .into_iter()
+            .map(resolve_embedding_route)
+            .collect::<Result<Vec<_>>>()?,
+        None => base.routes.clone(),
+    };
+    for (index, route) in routes.iter().enumerate() {
+        if routes[..index]
+            .iter()
+            .any(|other| other.content_type == route.content_type)
+        {
+            return Err(ConfigError::Invalid(format!(
+                "embedding routes list content_type '{}' more than once",
+                route.content_type.as_str()
+            ))
+            .into());
+        }
+    }
+
+    Ok(EmbeddingConfig {
+        query_prefix: toml
+            .query_prefix
+            .unwrap_or_else(|| base.query_prefix.clone()),
+        passage_prefix: toml
+            .passage_prefix
+            .unwrap_or_else(|| base.passage_prefix.clone()),
+        routes,
+    })
+}
+
+fn resolve_embedding_route(toml: TomlEmbeddingRoute) -> Result<EmbeddingRoute> {
+    let content_type = EmbeddingContentType::parse(&toml.content_type).ok_or_else(|| {
+        ConfigError::Invalid(format!(
+            "unknown embedding route content_type '{}', expected \"text\" or \"code\"",
+            toml.content_type
+        ))
+    })?;
+    if let Err(error) = toml.model.parse::<fastembed::EmbeddingModel>() {
+        return Err(ConfigError::Invalid(format!("invalid embedding route model: {error}")).into());
+    }
+
+    Ok(EmbeddingRoute {
+        content_type,
+        model: toml.model,
+        query_prefix: toml.query_prefix,
+        passage_prefix: toml.passage_prefix,
+    })
+}
+
+/// Merge a `[memory_decay]` table over a base config.
+fn resolve_memory_decay(toml: TomlMemoryDecayConfig, base: MemoryDecayConfig) -> MemoryDecayConfig {
+    MemoryDecayConfig {
+        half_life_days: toml.half_life_days.unwrap_or(base.half_life_days).max(0.0),
+        min_weight: toml.min_weight.unwrap_or(base.min_weight).clamp(0.0, 1.0),
+    }
+}
+
+/// Merge a `[memory_scrub]` table over a base config, rejecting patterns that
+/// don't compile.
+fn resolve_memory_scrub(
+    toml: TomlMemoryScrubConfig,
+    base: &MemoryScrubConfig,
+) -> Result<MemoryScrubConfig> {
+    let patterns = toml.patterns.unwrap_or_else(|| base.patterns.clone());
+    for pattern in &patterns {
+        if let Err(error) = regex::Regex::new(pattern) {
+            return Err(ConfigError::Invalid(format!(
+                "invalid memory_scrub pattern '{pattern}': {error}"
+            ))
+            .into());
+        }
+    }
+
+    Ok(MemoryScrubConfig {
+        enabled: toml.enabled.unwrap_or(base.enabled),
+        redact_emails: toml.redact_emails.unwrap_or(base.redact_emails),
+        redact_phone_numbers: toml
+            .redact_phone_numbers
+            .unwrap_or(base.redact_phone_numbers),
+        patterns,
+    })
+}
+
+/// Lowercase ingestion extensions and strip leading dots, so `".PDF"` and
+/// `"pdf"` configure the same thing.
+fn normalize_ingest_extensions(extensions: Vec<String>) -> Vec<String> {
+    extensions
+        .into_iter()
+        .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .collect()
+}
+
+/// Merge a `[stream_pacing]` table over a base config.
+fn resolve_stream_pacing(
+    toml: TomlStreamPacingConfig,
+    base: StreamPacingConfig,
+) -> StreamPacingConfig {
+    StreamPacingConfig {
+        chars_per_second: toml.chars_per_second.unwrap_or(base.chars_per_second),
+    }
+}
+
+/// Merge a `[tool_call_limits]` table over a base config.
+fn resolve_tool_call_limits(toml: TomlToolCallLimits, base: ToolCallLimits) -> ToolCallLimits {
+    ToolCallLimits {
+        max_tool_calls_per_turn: toml
+            .max_tool_calls_per_turn
+            .unwrap_or(base.max_tool_calls_per_turn),
+        max_repeat_same_tool: toml
+            .max_repeat_same_tool
+            .unwrap_or(base.max_repeat_same_tool),
+    }
+}
+
+/// Merge a `[worker_retry]` table over a base config.
+fn resolve_worker_retry(toml: TomlWorkerRetryConfig, base: WorkerRetryConfig) -> WorkerRetryConfig {
+    WorkerRetryConfig {
+        max_retries: toml.max_retries.unwrap_or(base.max_retries),
+        backoff_secs: toml.backoff_secs.unwrap_or(base.backoff_secs),
+        preserve_context: toml.preserve_context.unwrap_or(base.preserve_context),
+    }
+}
+
+/// Validate a `[prompt_overrides]` table. Names must be overridable process
+/// templates and paths, relative to the instance dir, must point at files.
+fn resolve_prompt_overrides(
+    toml: HashMap<String, String>,
+    instance_dir: &Path,
+) -> Result<PromptOverrides> {
+    let templates = crate::prompts::engine::OVERRIDABLE_TEMPLATES;
+    let mut overrides = PromptOverrides::default();
+    for (name, path) in toml {
+        if !templates.contains(&name.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "unknown prompt override '{name}', expected one of: {}",
+                templates.join(", ")
+            ))
+            .into());
+        }
+        let path = instance_dir.join(path);
+        if !path.is_file() {
+            return Err(ConfigError::Invalid(format!(
+                "prompt override '{name}' points at {}, which is not a file",
+                path.display()
+            ))
+            .into());
+        }
+        overrides.extend([(name, path)]);
+    }
+    Ok(overrides)
+}
+
 impl CortexConfig {
     fn resolve(overrides: TomlCortexConfig, defaults: CortexConfig) -> Result<CortexConfig> {
         let maintenance_interval_secs = overrides
@@ -213,6 +730,15 @@ impl CortexConfig {
             knowledge_synthesis_debounce_secs: overrides
                 .knowledge_synthesis_debounce_secs
                 .unwrap_or(defaults.knowledge_synthesis_debounce_secs),
+            consolidation_interval_secs: overrides
+                .consolidation_interval_secs
+                .unwrap_or(defaults.consolidation_interval_secs),
+            consolidation_min_cluster_size: overrides
+                .consolidation_min_cluster_size
+                .unwrap_or(defaults.consolidation_min_cluster_size),
+            consolidation_min_importance: overrides
+                .consolidation_min_importance
+                .unwrap_or(defaults.consolidation_min_importance),
         };
         config.validate_maintenance_bounds()?;
         Ok(config)
@@ -253,6 +779,18 @@ fn parse_otlp_headers(value: Option<String>) -> Result<HashMap<String, String>>
     Ok(headers)
 }
 
+/// Trim tool names from `disabled_tools` and drop blanks and duplicates.
+fn normalize_tool_names(names: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        let name = name.trim();
+        if !name.is_empty() && !normalized.iter().any(|existing| existing == name) {
+            normalized.push(name.to_string());
+        }
+    }
+    normalized
+}
+
 fn parse_mcp_server_config(raw: TomlMcpServerConfig) -> Result<McpServerConfig> {
     if raw.name.trim().is_empty() {
         return Err(ConfigError::Invalid("mcp server name cannot be empty".into()).into());
@@ -297,6 +835,7 @@ fn parse_mcp_server_config(raw: TomlMcpServerConfig) -> Result<McpServerConfig>
         name: raw.name,
         transport,
         enabled: raw.enabled,
+        agents: raw.agents,
     })
 }
 
@@ -321,7 +860,7 @@ impl Config {
         }
 
         // OAuth credentials count as configured
-        if crate::auth::credentials_path(&instance_dir).exists()
+        if crate::auth::has_credentials(&instance_dir)
             || crate::openai_auth::credentials_path(&instance_dir).exists()
         {
             return false;
@@ -399,13 +938,8 @@ impl Config {
             .map(|p| p.to_path_buf())
             .unwrap_or_else(|| PathBuf::from("."));
 
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("failed to read config from {}", path.display()))?;
-
-        warn_unknown_config_keys(&content);
-
-        let toml_config: TomlConfig = toml::from_str(&content)
-            .with_context(|| format!("failed to parse config from {}", path.display()))?;
+        let overlay_path = overlay::overlay_path(path);
+        let toml_config = read_config_toml(path, overlay_path.as_deref())?;
 
         Self::from_toml(toml_config, instance_dir)
     }
@@ -440,6 +974,10 @@ impl Config {
             zai_coding_plan_key: std::env::var("ZAI_CODING_PLAN_API_KEY").ok(),
             github_copilot_key: std::env::var("GITHUB_COPILOT_API_KEY").ok(),
             providers: HashMap::new(),
+            health: ProviderHealthConfig::default(),
+            validate_models: false,
+            default_model: None,
+            attribution: AppAttribution::default(),
         };
 
         // Populate providers from env vars (same as from_toml does)
@@ -455,6 +993,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: anthropic_from_auth_token,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -467,7 +1006,8 @@ impl Config {
                     api_key: openrouter_key,
                     name: None,
                     use_bearer_auth: false,
-                    extra_headers: openrouter_extra_headers(),
+                    extra_headers: openrouter_extra_headers(&llm.attribution),
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -539,6 +1079,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -552,6 +1093,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -565,6 +1107,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -577,7 +1120,8 @@ impl Config {
                     api_key: openrouter_key,
                     name: None,
                     use_bearer_auth: false,
-                    extra_headers: openrouter_extra_headers(),
+                    extra_headers: openrouter_extra_headers(&llm.attribution),
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -619,6 +1163,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -632,6 +1177,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -645,6 +1191,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -658,6 +1205,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -671,6 +1219,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -684,6 +1233,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -697,6 +1247,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -710,6 +1261,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -723,6 +1275,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -736,6 +1289,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -749,6 +1303,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -762,6 +1317,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -775,6 +1331,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -791,6 +1348,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -842,9 +1400,14 @@ impl Config {
             routing: Some(routing),
             max_concurrent_branches: None,
             max_concurrent_workers: None,
+            max_concurrent_processes: None,
             max_turns: None,
             branch_max_turns: None,
             context_window: None,
+            system_prompt_token_budget: None,
+            output_language: None,
+            cortex_language: None,
+            response_footer: None,
             compaction: None,
             memory_persistence: None,
             coalesce: None,
@@ -853,6 +1416,20 @@ impl Config {
             warmup: None,
             browser: None,
             channel: None,
+            media_safety: None,
+            task_extraction: None,
+            transcription: None,
+            embedding: None,
+            worker_retry: None,
+            memory_decay: None,
+            memory_scrub: None,
+            tool_call_limits: None,
+            stream_pacing: None,
+            reaction_triggers: None,
+            personas: None,
+            prompt_overrides: None,
+            worker_buttons: None,
+            disabled_tools: None,
             mcp: None,
             brave_search_key: None,
             cron_timezone: None,
@@ -890,11 +1467,14 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                handoff_target: None,
             }],
             messaging: MessagingConfig::default(),
             bindings: Vec::new(),
             api,
             metrics: MetricsConfig::default(),
+            db: DbConfig::default(),
+            event_sink: EventSinkConfig::default(),
             telemetry: TelemetryConfig {
                 otlp_endpoint: std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok(),
                 otlp_headers: parse_otlp_headers(std::env::var("OTEL_EXPORTER_OTLP_HEADERS").ok())?,
@@ -918,6 +1498,33 @@ impl Config {
         Ok(())
     }
 
+    /// Routing models from `[defaults.routing]`, every agent's routing, and
+    /// `[llm] default_model`, each paired with the config key it is set under.
+    pub fn routing_models(&self) -> Vec<(String, String)> {
+        let mut models: Vec<(String, String)> = self
+            .defaults
+            .routing
+            .model_refs()
+            .into_iter()
+            .map(|(key, model)| (format!("defaults.routing.{key}"), model.to_string()))
+            .collect();
+        for agent in &self.agents {
+            let Some(routing) = &agent.routing else {
+                continue;
+            };
+            models.extend(routing.model_refs().into_iter().map(|(key, model)| {
+                (
+                    format!("agents.{}.routing.{key}", agent.id),
+                    model.to_string(),
+                )
+            }));
+        }
+        if let Some(default_model) = &self.llm.default_model {
+            models.push(("llm.default_model".into(), default_model.clone()));
+        }
+        models
+    }
+
     pub(super) fn from_toml(toml: TomlConfig, instance_dir: PathBuf) -> Result<Self> {
         // Validate providers before processing
         for (provider_id, config) in &toml.llm.providers {
@@ -954,6 +1561,23 @@ impl Config {
             .and_then(resolve_env_value)
             .is_none();
 
+        let attribution = toml
+            .llm
+            .attribution
+            .as_ref()
+            .map(|attribution| {
+                let defaults = AppAttribution::default();
+                AppAttribution {
+                    referer: attribution.referer.clone().unwrap_or(defaults.referer),
+                    title: attribution.title.clone().unwrap_or(defaults.title),
+                    user_agent: attribution
+                        .user_agent
+                        .clone()
+                        .unwrap_or(defaults.user_agent),
+                }
+            })
+            .unwrap_or_default();
+
         let mut llm = LlmConfig {
             anthropic_key: toml
                 .llm
@@ -1085,6 +1709,30 @@ impl Config {
                 .as_deref()
                 .and_then(resolve_env_value)
                 .or_else(|| std::env::var("GITHUB_COPILOT_API_KEY").ok()),
+            health: toml
+                .llm
+                .health
+                .as_ref()
+                .map(|health| {
+                    let defaults = ProviderHealthConfig::default();
+                    ProviderHealthConfig {
+                        failure_threshold: health
+                            .failure_threshold
+                            .unwrap_or(defaults.failure_threshold),
+                        probe_interval_secs: health
+                            .probe_interval_secs
+                            .unwrap_or(defaults.probe_interval_secs)
+                            .max(1),
+                    }
+                })
+                .unwrap_or_default(),
+            validate_models: toml.llm.validate_models.unwrap_or(false),
+            default_model: toml
+                .llm
+                .default_model
+                .clone()
+                .filter(|model| !model.trim().is_empty()),
+            attribution: attribution.clone(),
             providers: toml
                 .llm
                 .providers
@@ -1094,11 +1742,33 @@ impl Config {
                         anyhow::anyhow!("failed to resolve API key for provider '{}'", provider_id)
                     })?;
                     let normalized_id = provider_id.to_lowercase();
-                    let extra_headers = if normalized_id == "openrouter" {
-                        openrouter_extra_headers()
+                    let mut extra_headers = if normalized_id == "openrouter" {
+                        openrouter_extra_headers(&attribution)
                     } else {
                         vec![]
                     };
+                    let mut custom_headers = config
+                        .headers
+                        .iter()
+                        .map(|(name, value)| {
+                            let value = resolve_env_value(value).ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "failed to resolve header '{}' for provider '{}'",
+                                    name,
+                                    provider_id
+                                )
+                            })?;
+                            Ok((name.clone(), value))
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    custom_headers.sort();
+                    // Configured headers replace built-in ones with the same name.
+                    extra_headers.retain(|(name, _)| {
+                        !custom_headers
+                            .iter()
+                            .any(|(custom, _)| custom.eq_ignore_ascii_case(name))
+                    });
+                    extra_headers.extend(custom_headers);
                     Ok((
                         normalized_id,
                         ProviderConfig {
@@ -1108,6 +1778,12 @@ impl Config {
                             name: config.name,
                             use_bearer_auth: false,
                             extra_headers,
+                            fallback_base_urls: config
+                                .fallback_base_urls
+                                .into_iter()
+                                .map(|url| url.trim().to_string())
+                                .filter(|url| !url.is_empty())
+                                .collect(),
                         },
                     ))
                 })
@@ -1134,6 +1810,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: anthropic_from_auth_token,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1147,6 +1824,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1159,7 +1837,8 @@ impl Config {
                     api_key: openrouter_key,
                     name: None,
                     use_bearer_auth: false,
-                    extra_headers: openrouter_extra_headers(),
+                    extra_headers: openrouter_extra_headers(&llm.attribution),
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1231,6 +1910,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1244,6 +1924,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1257,6 +1938,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1270,6 +1952,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1283,6 +1966,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1296,6 +1980,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1309,6 +1994,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1322,6 +2008,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1335,6 +2022,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1348,6 +2036,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1361,6 +2050,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1377,6 +2067,7 @@ impl Config {
                     name: None,
                     use_bearer_auth: false,
                     extra_headers: vec![],
+                    fallback_base_urls: Vec::new(),
                 });
         }
 
@@ -1402,7 +2093,7 @@ impl Config {
             base_defaults.routing.clone()
         };
         let defaults = DefaultsConfig {
-            routing: resolve_routing(toml.defaults.routing, &base_routing),
+            routing: resolve_routing(toml.defaults.routing, &base_routing)?,
             max_concurrent_branches: toml
                 .defaults
                 .max_concurrent_branches
@@ -1411,6 +2102,10 @@ impl Config {
                 .defaults
                 .max_concurrent_workers
                 .unwrap_or(base_defaults.max_concurrent_workers),
+            max_concurrent_processes: toml
+                .defaults
+                .max_concurrent_processes
+                .unwrap_or(base_defaults.max_concurrent_processes),
             max_turns: toml.defaults.max_turns.unwrap_or(base_defaults.max_turns),
             branch_max_turns: toml
                 .defaults
@@ -1420,6 +2115,25 @@ impl Config {
                 .defaults
                 .context_window
                 .unwrap_or(base_defaults.context_window),
+            system_prompt_token_budget: toml
+                .defaults
+                .system_prompt_token_budget
+                .or(base_defaults.system_prompt_token_budget),
+            output_language: toml
+                .defaults
+                .output_language
+                .clone()
+                .or_else(|| base_defaults.output_language.clone()),
+            cortex_language: toml
+                .defaults
+                .cortex_language
+                .clone()
+                .or_else(|| base_defaults.cortex_language.clone()),
+            response_footer: toml
+                .defaults
+                .response_footer
+                .clone()
+                .or_else(|| base_defaults.response_footer.clone()),
             compaction: toml
                 .defaults
                 .compaction
@@ -1433,6 +2147,13 @@ impl Config {
                     emergency_threshold: c
                         .emergency_threshold
                         .unwrap_or(base_defaults.compaction.emergency_threshold),
+                    summary_max_tokens: c
+                        .summary_max_tokens
+                        .or(base_defaults.compaction.summary_max_tokens),
+                    max_turns_before_compact: c
+                        .max_turns_before_compact
+                        .or(base_defaults.compaction.max_turns_before_compact)
+                        .filter(|max_turns| *max_turns > 0),
                 })
                 .unwrap_or(base_defaults.compaction),
             memory_persistence: toml
@@ -1471,8 +2192,19 @@ impl Config {
                         .poll_interval_secs
                         .unwrap_or(base_defaults.ingestion.poll_interval_secs),
                     chunk_size: ig.chunk_size.unwrap_or(base_defaults.ingestion.chunk_size),
+                    concurrency: ig
+                        .concurrency
+                        .unwrap_or(base_defaults.ingestion.concurrency)
+                        .max(1),
+                    extensions: ig
+                        .extensions
+                        .map(normalize_ingest_extensions)
+                        .unwrap_or_else(|| base_defaults.ingestion.extensions.clone()),
+                    max_retries: ig
+                        .max_retries
+                        .unwrap_or(base_defaults.ingestion.max_retries),
                 })
-                .unwrap_or(base_defaults.ingestion),
+                .unwrap_or_else(|| base_defaults.ingestion.clone()),
             cortex: toml
                 .defaults
                 .cortex
@@ -1487,6 +2219,9 @@ impl Config {
                     eager_embedding_load: w
                         .eager_embedding_load
                         .unwrap_or(base_defaults.warmup.eager_embedding_load),
+                    preload_models: w
+                        .preload_models
+                        .unwrap_or(base_defaults.warmup.preload_models),
                     refresh_secs: w.refresh_secs.unwrap_or(base_defaults.warmup.refresh_secs),
                     startup_delay_secs: w
                         .startup_delay_secs
@@ -1527,15 +2262,81 @@ impl Config {
             channel: toml
                 .defaults
                 .channel
-                .map(|channel_config| ChannelConfig {
-                    listen_only_mode: channel_config
-                        .listen_only_mode
-                        .unwrap_or(base_defaults.channel.listen_only_mode),
-                    save_attachments: channel_config
-                        .save_attachments
-                        .unwrap_or(base_defaults.channel.save_attachments),
-                })
-                .unwrap_or(base_defaults.channel),
+                .map(|c| resolve_channel(c, &base_defaults.channel))
+                .unwrap_or_else(|| base_defaults.channel.clone()),
+            media_safety: toml
+                .defaults
+                .media_safety
+                .map(|m| resolve_media_safety(m, &base_defaults.media_safety))
+                .unwrap_or_else(|| MediaSafetyConfig {
+                    api_key: std::env::var("OPENAI_API_KEY").ok(),
+                    ..base_defaults.media_safety.clone()
+                }),
+            task_extraction: toml
+                .defaults
+                .task_extraction
+                .map(|t| resolve_task_extraction(t, base_defaults.task_extraction))
+                .unwrap_or(base_defaults.task_extraction),
+            transcription: toml
+                .defaults
+                .transcription
+                .map(|t| resolve_transcription(t, &base_defaults.transcription))
+                .unwrap_or_else(|| TranscriptionConfig {
+                    api_key: std::env::var("OPENAI_API_KEY").ok(),
+                    ..base_defaults.transcription.clone()
+                }),
+            embedding: toml
+                .defaults
+                .embedding
+                .map(|e| resolve_embedding(e, &base_defaults.embedding))
+                .transpose()?
+                .unwrap_or_else(|| base_defaults.embedding.clone()),
+            worker_retry: toml
+                .defaults
+                .worker_retry
+                .map(|w| resolve_worker_retry(w, base_defaults.worker_retry))
+                .unwrap_or(base_defaults.worker_retry),
+            memory_decay: toml
+                .defaults
+                .memory_decay
+                .map(|d| resolve_memory_decay(d, base_defaults.memory_decay))
+                .unwrap_or(base_defaults.memory_decay),
+            memory_scrub: toml
+                .defaults
+                .memory_scrub
+                .map(|s| resolve_memory_scrub(s, &base_defaults.memory_scrub))
+                .transpose()?
+                .unwrap_or_else(|| base_defaults.memory_scrub.clone()),
+            tool_call_limits: toml
+                .defaults
+                .tool_call_limits
+                .map(|l| resolve_tool_call_limits(l, base_defaults.tool_call_limits))
+                .unwrap_or(base_defaults.tool_call_limits),
+            stream_pacing: toml
+                .defaults
+                .stream_pacing
+                .map(|p| resolve_stream_pacing(p, base_defaults.stream_pacing))
+                .unwrap_or(base_defaults.stream_pacing),
+            reaction_triggers: {
+                let mut triggers = base_defaults.reaction_triggers.clone();
+                triggers.extend(toml.defaults.reaction_triggers);
+                triggers
+            },
+            personas: {
+                let mut personas = base_defaults.personas.clone();
+                personas.extend(toml.defaults.personas);
+                personas
+            },
+            prompt_overrides: resolve_prompt_overrides(
+                toml.defaults.prompt_overrides,
+                &instance_dir,
+            )?,
+            worker_buttons: {
+                let mut buttons = base_defaults.worker_buttons.clone();
+                buttons.extend(toml.defaults.worker_buttons);
+                buttons
+            },
+            disabled_tools: normalize_tool_names(toml.defaults.disabled_tools),
             mcp: default_mcp,
             brave_search_key: toml
                 .defaults
@@ -1592,6 +2393,30 @@ impl Config {
                 .as_deref()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(base_defaults.worker_log_mode),
+            // 0 disables the corresponding limit.
+            worker_log_retention: WorkerLogRetention {
+                max_age_days: match toml.defaults.worker_log_retention_days {
+                    Some(0) => None,
+                    Some(days) => Some(days),
+                    None => base_defaults.worker_log_retention.max_age_days,
+                },
+                max_total_bytes: match toml.defaults.worker_log_max_total_mb {
+                    Some(0) => None,
+                    Some(mb) => Some(mb.saturating_mul(1024 * 1024)),
+                    None => base_defaults.worker_log_retention.max_total_bytes,
+                },
+            },
+            tool_results: toml
+                .defaults
+                .tool_results
+                .map(|t| ToolResultLimits {
+                    max_bytes: t.max_bytes.unwrap_or(base_defaults.tool_results.max_bytes),
+                    per_tool: t.per_tool,
+                    save_full_results: t
+                        .save_full_results
+                        .unwrap_or(base_defaults.tool_results.save_full_results),
+                })
+                .unwrap_or_else(|| base_defaults.tool_results.clone()),
             projects: toml
                 .defaults
                 .projects
@@ -1626,7 +2451,8 @@ impl Config {
                 // Per-agent routing resolves against instance defaults
                 let agent_routing = a
                     .routing
-                    .map(|r| resolve_routing(Some(r), &defaults.routing));
+                    .map(|r| resolve_routing(Some(r), &defaults.routing))
+                    .transpose()?;
 
                 let cron = a
                     .cron
@@ -1658,9 +2484,14 @@ impl Config {
                     routing: agent_routing,
                     max_concurrent_branches: a.max_concurrent_branches,
                     max_concurrent_workers: a.max_concurrent_workers,
+                    max_concurrent_processes: a.max_concurrent_processes,
                     max_turns: a.max_turns,
                     branch_max_turns: a.branch_max_turns,
                     context_window: a.context_window,
+                    system_prompt_token_budget: a.system_prompt_token_budget,
+                    output_language: a.output_language.clone(),
+                    cortex_language: a.cortex_language.clone(),
+                    response_footer: a.response_footer.clone(),
                     compaction: a.compaction.map(|c| CompactionConfig {
                         background_threshold: c
                             .background_threshold
@@ -1671,6 +2502,13 @@ impl Config {
                         emergency_threshold: c
                             .emergency_threshold
                             .unwrap_or(defaults.compaction.emergency_threshold),
+                        summary_max_tokens: c
+                            .summary_max_tokens
+                            .or(defaults.compaction.summary_max_tokens),
+                        max_turns_before_compact: c
+                            .max_turns_before_compact
+                            .or(defaults.compaction.max_turns_before_compact)
+                            .filter(|max_turns| *max_turns > 0),
                     }),
                     memory_persistence: a.memory_persistence.map(|mp| MemoryPersistenceConfig {
                         enabled: mp.enabled.unwrap_or(defaults.memory_persistence.enabled),
@@ -1693,6 +2531,15 @@ impl Config {
                             .poll_interval_secs
                             .unwrap_or(defaults.ingestion.poll_interval_secs),
                         chunk_size: ig.chunk_size.unwrap_or(defaults.ingestion.chunk_size),
+                        concurrency: ig
+                            .concurrency
+                            .unwrap_or(defaults.ingestion.concurrency)
+                            .max(1),
+                        extensions: ig
+                            .extensions
+                            .map(normalize_ingest_extensions)
+                            .unwrap_or_else(|| defaults.ingestion.extensions.clone()),
+                        max_retries: ig.max_retries.unwrap_or(defaults.ingestion.max_retries),
                     }),
                     cortex: a
                         .cortex
@@ -1703,6 +2550,7 @@ impl Config {
                         eager_embedding_load: w
                             .eager_embedding_load
                             .unwrap_or(defaults.warmup.eager_embedding_load),
+                        preload_models: w.preload_models.unwrap_or(defaults.warmup.preload_models),
                         refresh_secs: w.refresh_secs.unwrap_or(defaults.warmup.refresh_secs),
                         startup_delay_secs: w
                             .startup_delay_secs
@@ -1732,14 +2580,44 @@ impl Config {
                         ),
                         chrome_cache_dir: defaults.browser.chrome_cache_dir.clone(),
                     }),
-                    channel: a.channel.map(|channel_config| ChannelConfig {
-                        listen_only_mode: channel_config
-                            .listen_only_mode
-                            .unwrap_or(defaults.channel.listen_only_mode),
-                        save_attachments: channel_config
-                            .save_attachments
-                            .unwrap_or(defaults.channel.save_attachments),
-                    }),
+                    channel: a.channel.map(|c| resolve_channel(c, &defaults.channel)),
+                    media_safety: a
+                        .media_safety
+                        .map(|m| resolve_media_safety(m, &defaults.media_safety)),
+                    task_extraction: a
+                        .task_extraction
+                        .map(|t| resolve_task_extraction(t, defaults.task_extraction)),
+                    transcription: a
+                        .transcription
+                        .map(|t| resolve_transcription(t, &defaults.transcription)),
+                    embedding: a
+                        .embedding
+                        .map(|e| resolve_embedding(e, &defaults.embedding))
+                        .transpose()?,
+                    worker_retry: a
+                        .worker_retry
+                        .map(|w| resolve_worker_retry(w, defaults.worker_retry)),
+                    memory_decay: a
+                        .memory_decay
+                        .map(|d| resolve_memory_decay(d, defaults.memory_decay)),
+                    memory_scrub: a
+                        .memory_scrub
+                        .map(|s| resolve_memory_scrub(s, &defaults.memory_scrub))
+                        .transpose()?,
+                    tool_call_limits: a
+                        .tool_call_limits
+                        .map(|l| resolve_tool_call_limits(l, defaults.tool_call_limits)),
+                    stream_pacing: a
+                        .stream_pacing
+                        .map(|p| resolve_stream_pacing(p, defaults.stream_pacing)),
+                    reaction_triggers: a.reaction_triggers.map(ReactionTriggers::new),
+                    personas: a.personas.map(Personas::new),
+                    prompt_overrides: a
+                        .prompt_overrides
+                        .map(|overrides| resolve_prompt_overrides(overrides, &instance_dir))
+                        .transpose()?,
+                    worker_buttons: a.worker_buttons.map(WorkerButtons::new),
+                    disabled_tools: a.disabled_tools.map(normalize_tool_names),
                     mcp: match a.mcp {
                         Some(mcp_servers) => Some(
                             mcp_servers
@@ -1791,9 +2669,14 @@ impl Config {
                 routing: None,
                 max_concurrent_branches: None,
                 max_concurrent_workers: None,
+                max_concurrent_processes: None,
                 max_turns: None,
                 branch_max_turns: None,
                 context_window: None,
+                system_prompt_token_budget: None,
+                output_language: None,
+                cortex_language: None,
+                response_footer: None,
                 compaction: None,
                 memory_persistence: None,
                 coalesce: None,
@@ -1802,6 +2685,20 @@ impl Config {
                 warmup: None,
                 browser: None,
                 channel: None,
+                media_safety: None,
+                task_extraction: None,
+                transcription: None,
+                embedding: None,
+                worker_retry: None,
+                memory_decay: None,
+                memory_scrub: None,
+                tool_call_limits: None,
+                stream_pacing: None,
+                reaction_triggers: None,
+                personas: None,
+                prompt_overrides: None,
+                worker_buttons: None,
+                disabled_tools: None,
                 mcp: None,
                 brave_search_key: None,
                 cron_timezone: None,
@@ -1855,6 +2752,11 @@ impl Config {
                     instances,
                     dm_allowed_users: d.dm_allowed_users,
                     allow_bot_messages: d.allow_bot_messages,
+                    require_mention_default: d.require_mention_default,
+                    stream_edits: d.stream_edits,
+                    stream_edit_interval_ms: d
+                        .stream_edit_interval_ms
+                        .unwrap_or(DEFAULT_DISCORD_STREAM_EDIT_INTERVAL_MS),
                 })
             }),
             slack: toml.messaging.slack.and_then(|s| {
@@ -1918,6 +2820,7 @@ impl Config {
                             description: c.description,
                         })
                         .collect(),
+                    require_mention_default: s.require_mention_default,
                 })
             }),
             telegram: toml.messaging.telegram.and_then(|t| {
@@ -1954,6 +2857,7 @@ impl Config {
                     token: token.unwrap_or_default(),
                     instances,
                     dm_allowed_users: t.dm_allowed_users,
+                    require_mention_default: t.require_mention_default,
                 })
             }),
             email: toml.messaging.email.and_then(|email| {
@@ -2104,6 +3008,14 @@ impl Config {
                 port: w.port,
                 bind: w.bind,
                 auth_token: w.auth_token.as_deref().and_then(resolve_env_value),
+                signing_secret: w.signing_secret.as_deref().and_then(resolve_env_value),
+                signature_header: w
+                    .signature_header
+                    .map(|header| header.trim().to_ascii_lowercase())
+                    .filter(|header| !header.is_empty())
+                    .unwrap_or_else(|| {
+                        crate::messaging::webhook::DEFAULT_SIGNATURE_HEADER.to_string()
+                    }),
             }),
             twitch: toml.messaging.twitch.and_then(|t| {
                 let instances = t
@@ -2181,6 +3093,7 @@ impl Config {
                     instances,
                     channels: t.channels,
                     trigger_prefix: t.trigger_prefix,
+                    require_mention_default: t.require_mention_default,
                 })
             }),
             signal: toml.messaging.signal.and_then(|s| {
@@ -2280,6 +3193,7 @@ impl Config {
                     instances,
                     dm_allowed_users: mm.dm_allowed_users,
                     max_attachment_bytes: mm.max_attachment_bytes,
+                    require_mention_default: mm.require_mention_default,
                 })
             }),
         };
@@ -2287,27 +3201,47 @@ impl Config {
         let bindings: Vec<Binding> = toml
             .bindings
             .into_iter()
-            .map(|b| Binding {
-                agent_id: b.agent_id,
-                channel: b.channel,
-                adapter: normalize_adapter(b.adapter),
-                guild_id: b.guild_id,
-                workspace_id: b.workspace_id,
-                chat_id: b.chat_id,
-                team_id: b.team_id,
-                channel_ids: b.channel_ids,
-                require_mention: b.require_mention,
-                dm_allowed_users: b.dm_allowed_users,
+            .map(|b| {
+                let require_mention = b
+                    .require_mention
+                    .unwrap_or_else(|| messaging.require_mention_default(&b.channel));
+                Ok(Binding {
+                    schedule: parse_binding_schedule(&b.agent_id, b.timezone, b.schedule)?,
+                    agent_id: b.agent_id,
+                    channel: b.channel,
+                    adapter: normalize_adapter(b.adapter),
+                    guild_id: b.guild_id,
+                    workspace_id: b.workspace_id,
+                    chat_id: b.chat_id,
+                    team_id: b.team_id,
+                    channel_ids: b.channel_ids,
+                    require_mention,
+                    dm_allowed_users: b.dm_allowed_users,
+                    welcome_message: b
+                        .welcome_message
+                        .filter(|message| !message.trim().is_empty()),
+                })
             })
-            .collect();
+            .collect::<Result<_>>()?;
 
         validate_named_messaging_adapters(&messaging, &bindings)?;
 
+        let api_defaults = ApiConfig::default();
         let api = ApiConfig {
             enabled: toml.api.enabled,
             port: toml.api.port,
             bind: hosted_api_bind(toml.api.bind),
             auth_token: toml.api.auth_token.as_deref().and_then(resolve_env_value),
+            access_log: toml.api.access_log,
+            access_log_max_bytes: toml
+                .api
+                .access_log_max_size_mb
+                .map(|mb| mb.max(1) * 1024 * 1024)
+                .unwrap_or(api_defaults.access_log_max_bytes),
+            access_log_max_files: toml
+                .api
+                .access_log_max_files
+                .unwrap_or(api_defaults.access_log_max_files),
         };
 
         let metrics = MetricsConfig {
@@ -2384,6 +3318,7 @@ impl Config {
                     telegram_id: h.telegram_id,
                     slack_id: h.slack_id,
                     email: h.email,
+                    handoff_target: h.handoff_target,
                 }
             })
             .collect();
@@ -2401,6 +3336,7 @@ impl Config {
                 telegram_id: None,
                 slack_id: None,
                 email: None,
+                handoff_target: None,
             });
 
             // Link the default admin to the default agent so the agent sees
@@ -2417,7 +3353,7 @@ impl Config {
             }
         }
 
-        Ok(Config {
+        let config = Config {
             instance_dir,
             llm,
             defaults,
@@ -2430,10 +3366,43 @@ impl Config {
             api,
             metrics,
             telemetry,
-        })
+            db: resolve_db(toml.db),
+            event_sink: resolve_event_sink(toml.event_sink),
+        };
+
+        for (key, model) in unknown_routing_providers(&config) {
+            tracing::warn!(
+                %key,
+                %model,
+                "routing model uses provider `{}`, which is not configured",
+                crate::llm::routing::provider_from_model(&model)
+            );
+        }
+
+        Ok(config)
     }
 }
 
+/// Providers whose credentials come from OAuth or a token exchange at runtime,
+/// so they can be missing from `[llm]` and still work.
+const RUNTIME_CREDENTIAL_PROVIDERS: [&str; 3] = ["anthropic", "openai-chatgpt", "github-copilot"];
+
+/// Routing models whose provider prefix matches no configured provider, each
+/// reported once under the first key that sets it.
+pub(super) fn unknown_routing_providers(config: &Config) -> Vec<(String, String)> {
+    let mut seen = std::collections::HashSet::new();
+    config
+        .routing_models()
+        .into_iter()
+        .filter(|(_, model)| seen.insert(model.clone()))
+        .filter(|(_, model)| {
+            let provider = crate::llm::routing::provider_from_model(model).to_lowercase();
+            !config.llm.providers.contains_key(&provider)
+                && !RUNTIME_CREDENTIAL_PROVIDERS.contains(&provider.as_str())
+        })
+        .collect()
+}
+
 /// Load `HUMAN.md` from a human's directory, returning `None` if the file
 /// doesn't exist or is empty/whitespace.
 fn load_human_md(human_dir: &std::path::Path) -> Option<String> {
@@ -99,7 +99,7 @@ pub fn run_onboarding() -> anyhow::Result<Option<PathBuf>> {
                 .build()
                 .with_context(|| "failed to build tokio runtime")?;
 
-            runtime.block_on(crate::auth::login_interactive(&instance_dir, mode))?;
+            runtime.block_on(crate::auth::login_interactive(&instance_dir, mode, None))?;
             Some(true)
         } else {
             None
@@ -0,0 +1,62 @@
+//! Environment-specific config overlays.
+//!
+//! With `SPACEBOT_ENV=staging`, `config.staging.toml` is deep-merged over
+//! `config.toml` before the result is parsed, so an environment's file holds
+//! only what differs from the base. Tables merge key by key; anything else in
+//! the overlay, arrays included, replaces the base value whole.
+
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the overlay to apply.
+pub(super) const ENV_VAR: &str = "SPACEBOT_ENV";
+
+/// The overlay for the environment named by [`ENV_VAR`], next to `base`.
+/// `None` when the variable is unset or blank.
+pub(super) fn overlay_path(base: &Path) -> Option<PathBuf> {
+    let env = std::env::var(ENV_VAR).ok()?;
+    let env = env.trim();
+    if env.is_empty() {
+        return None;
+    }
+    let path = overlay_path_for(base, env);
+    if path.is_none() {
+        tracing::warn!(
+            env,
+            "{ENV_VAR} may only contain letters, digits, '-' and '_'; ignoring it"
+        );
+    }
+    path
+}
+
+/// `config.toml` + `staging` → `config.staging.toml`. Names that could
+/// escape the config directory are rejected.
+pub(super) fn overlay_path_for(base: &Path, env: &str) -> Option<PathBuf> {
+    let valid = !env.is_empty()
+        && env
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return None;
+    }
+    let stem = base.file_stem()?.to_str()?;
+    Some(base.with_file_name(format!("{stem}.{env}.toml")))
+}
+
+/// Deep-merge `overlay` into `base`. Tables merge recursively and the overlay
+/// wins everywhere else, so arrays (including `[[agents]]`) are replaced,
+/// never concatenated.
+pub(super) fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
@@ -1,6 +1,7 @@
 use super::toml_schema::TomlRoutingConfig;
-use super::{ApiType, ProviderConfig};
-use crate::llm::routing::RoutingConfig;
+use super::{ApiType, AppAttribution, ProviderConfig};
+use crate::error::{ConfigError, Result};
+use crate::llm::routing::{GeminiThinkingBudgets, RoutingConfig};
 
 use std::collections::HashMap;
 
@@ -34,16 +35,16 @@ pub(super) const GITHUB_COPILOT_DEFAULT_BASE_URL: &str = "https://api.individual
 /// We send both legacy (`X-Title`) and new (`X-OpenRouter-Title`) header names
 /// because (as of 2026-03-01) OpenRouter's backend still keys on the legacy names for populating
 /// the app listing (title, etc.).
-pub(super) fn openrouter_extra_headers() -> Vec<(String, String)> {
-    vec![
-        ("HTTP-Referer".into(), "https://spacebot.sh/".into()),
-        ("X-Title".into(), "Spacebot".into()),
-        ("X-OpenRouter-Title".into(), "Spacebot".into()),
-        (
-            "X-OpenRouter-Categories".into(),
-            "cloud-agent,cli-agent".into(),
-        ),
-    ]
+pub(super) fn openrouter_extra_headers(attribution: &AppAttribution) -> Vec<(String, String)> {
+    let mut headers = attribution.referer_headers();
+    if !attribution.title.is_empty() {
+        headers.push(("X-OpenRouter-Title".into(), attribution.title.clone()));
+    }
+    headers.push((
+        "X-OpenRouter-Categories".into(),
+        "cloud-agent,cli-agent".into(),
+    ));
+    headers
 }
 
 /// Returns the default ProviderConfig for a provider ID and API key.
@@ -61,6 +62,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "openai" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -69,6 +71,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "openrouter" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -76,7 +79,8 @@ pub(crate) fn default_provider_config(
             api_key,
             name: None,
             use_bearer_auth: false,
-            extra_headers: openrouter_extra_headers(),
+            extra_headers: openrouter_extra_headers(&AppAttribution::default()),
+            fallback_base_urls: Vec::new(),
         },
         "kilo" => ProviderConfig {
             api_type: ApiType::KiloGateway,
@@ -85,6 +89,7 @@ pub(crate) fn default_provider_config(
             name: Some("Kilo Gateway".to_string()),
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "zhipu" => ProviderConfig {
             api_type: ApiType::OpenAiChatCompletions,
@@ -93,6 +98,7 @@ pub(crate) fn default_provider_config(
             name: Some("Z.AI (GLM)".to_string()),
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "groq" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -101,6 +107,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "together" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -109,6 +116,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "fireworks" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -117,6 +125,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "deepseek" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -125,6 +134,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "xai" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -133,6 +143,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "mistral" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -141,6 +152,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "gemini" => ProviderConfig {
             api_type: ApiType::Gemini,
@@ -149,6 +161,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "ollama" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -157,6 +170,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "opencode-zen" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -165,6 +179,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "opencode-go" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -173,6 +188,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "nvidia" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -181,6 +197,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "minimax" => ProviderConfig {
             api_type: ApiType::Anthropic,
@@ -189,6 +206,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "minimax-cn" => ProviderConfig {
             api_type: ApiType::Anthropic,
@@ -197,6 +215,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "moonshot" => ProviderConfig {
             api_type: ApiType::OpenAiCompletions,
@@ -205,6 +224,7 @@ pub(crate) fn default_provider_config(
             name: None,
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         "zai-coding-plan" => ProviderConfig {
             api_type: ApiType::OpenAiChatCompletions,
@@ -213,6 +233,7 @@ pub(crate) fn default_provider_config(
             name: Some("Z.AI Coding Plan".to_string()),
             use_bearer_auth: false,
             extra_headers: vec![],
+            fallback_base_urls: Vec::new(),
         },
         // GitHub Copilot requires token exchange and dynamic base URL derivation.
         // The test path should use LlmManager::get_github_copilot_provider() instead.
@@ -240,6 +261,7 @@ pub(super) fn add_shorthand_provider(
                 name: name.map(str::to_string),
                 use_bearer_auth,
                 extra_headers: vec![],
+                fallback_base_urls: Vec::new(),
             });
     }
 }
@@ -298,8 +320,10 @@ pub(super) fn infer_routing_from_providers(
 pub(super) fn resolve_routing(
     toml: Option<TomlRoutingConfig>,
     base: &RoutingConfig,
-) -> RoutingConfig {
-    let Some(t) = toml else { return base.clone() };
+) -> Result<RoutingConfig> {
+    let Some(t) = toml else {
+        return Ok(base.clone());
+    };
 
     let mut task_overrides = base.task_overrides.clone();
     task_overrides.extend(t.task_overrides);
@@ -309,7 +333,21 @@ pub(super) fn resolve_routing(
         None => base.fallbacks.clone(),
     };
 
-    RoutingConfig {
+    let gemini_thinking_budgets = match t.gemini_thinking_budgets {
+        Some(budgets) => {
+            let base_budgets = base.gemini_thinking_budgets;
+            let resolved = GeminiThinkingBudgets {
+                low: budgets.low.unwrap_or(base_budgets.low),
+                medium: budgets.medium.unwrap_or(base_budgets.medium),
+                high: budgets.high.unwrap_or(base_budgets.high),
+            };
+            resolved.validate().map_err(ConfigError::Invalid)?;
+            resolved
+        }
+        None => base.gemini_thinking_budgets,
+    };
+
+    Ok(RoutingConfig {
         channel: t.channel.unwrap_or_else(|| base.channel.clone()),
         branch: t.branch.unwrap_or_else(|| base.branch.clone()),
         worker: t.worker.unwrap_or_else(|| base.worker.clone()),
@@ -321,6 +359,9 @@ pub(super) fn resolve_routing(
         rate_limit_cooldown_secs: t
             .rate_limit_cooldown_secs
             .unwrap_or(base.rate_limit_cooldown_secs),
+        tool_call_repair_attempts: t
+            .tool_call_repair_attempts
+            .unwrap_or(base.tool_call_repair_attempts),
         channel_thinking_effort: t
             .channel_thinking_effort
             .unwrap_or_else(|| base.channel_thinking_effort.clone()),
@@ -336,5 +377,35 @@ pub(super) fn resolve_routing(
         cortex_thinking_effort: t
             .cortex_thinking_effort
             .unwrap_or_else(|| base.cortex_thinking_effort.clone()),
+        gemini_thinking_budgets,
+        channel_max_output_tokens: resolve_output_cap(
+            t.channel_max_output_tokens,
+            base.channel_max_output_tokens,
+        ),
+        branch_max_output_tokens: resolve_output_cap(
+            t.branch_max_output_tokens,
+            base.branch_max_output_tokens,
+        ),
+        worker_max_output_tokens: resolve_output_cap(
+            t.worker_max_output_tokens,
+            base.worker_max_output_tokens,
+        ),
+        compactor_max_output_tokens: resolve_output_cap(
+            t.compactor_max_output_tokens,
+            base.compactor_max_output_tokens,
+        ),
+        cortex_max_output_tokens: resolve_output_cap(
+            t.cortex_max_output_tokens,
+            base.cortex_max_output_tokens,
+        ),
+    })
+}
+
+/// An explicit `0` removes an inherited output cap.
+fn resolve_output_cap(value: Option<u64>, base: Option<u64>) -> Option<u64> {
+    match value {
+        Some(0) => None,
+        Some(cap) => Some(cap),
+        None => base,
     }
 }
@@ -5,12 +5,19 @@ use arc_swap::ArcSwap;
 
 use super::{
     BrowserConfig, ChannelConfig, CoalesceConfig, CompactionConfig, Config, CortexConfig,
-    DefaultsConfig, IngestionConfig, McpServerConfig, MemoryPersistenceConfig, OpenCodeConfig,
-    ResolvedAgentConfig, WarmupConfig, WarmupStatus, WorkReadiness, evaluate_work_readiness,
+    DefaultsConfig, IngestionConfig, McpServerConfig, MediaSafetyConfig, MemoryPersistenceConfig,
+    OpenCodeConfig, Personas, PromptOverrides, ReactionTriggers, ResolvedAgentConfig,
+    StreamPacingConfig, TaskExtractionConfig, ToolCallLimits, ToolResultLimits,
+    TranscriptionConfig, WarmupConfig, WarmupStatus, WorkReadiness, WorkerButtons,
+    WorkerLogRetention, WorkerRetryConfig, evaluate_work_readiness,
 };
 use crate::llm::routing::RoutingConfig;
 use crate::tools::browser::SharedBrowserHandle;
 
+/// Kill switch file name. `instance_dir/PAUSE` pauses every agent and
+/// `agents/{id}/PAUSE` pauses one agent, until the file is removed.
+pub const PAUSE_FILE_NAME: &str = "PAUSE";
+
 /// Live configuration that can be hot-reloaded without restarting.
 ///
 /// All fields use ArcSwap for lock-free reads. Consumers call `.load()` on
@@ -31,11 +38,34 @@ pub struct RuntimeConfig {
     pub coalesce: ArcSwap<CoalesceConfig>,
     pub ingestion: ArcSwap<IngestionConfig>,
     pub channel_config: ArcSwap<ChannelConfig>,
+    pub media_safety: ArcSwap<MediaSafetyConfig>,
+    /// Custom classifier for inbound media. None uses the moderation endpoint
+    /// from `media_safety`.
+    pub media_classifier: ArcSwap<Option<Arc<dyn crate::agent::media_safety::MediaClassifierDyn>>>,
+    pub task_extraction: ArcSwap<TaskExtractionConfig>,
+    pub transcription: ArcSwap<TranscriptionConfig>,
+    pub worker_retry: ArcSwap<WorkerRetryConfig>,
+    pub tool_call_limits: ArcSwap<ToolCallLimits>,
+    pub stream_pacing: ArcSwap<StreamPacingConfig>,
+    pub reaction_triggers: ArcSwap<ReactionTriggers>,
+    pub personas: ArcSwap<Personas>,
+    pub worker_buttons: ArcSwap<WorkerButtons>,
+    /// Native tools left out of this agent's tool servers.
+    pub disabled_tools: ArcSwap<Vec<String>>,
     pub max_turns: ArcSwap<usize>,
     pub branch_max_turns: ArcSwap<usize>,
     pub context_window: ArcSwap<usize>,
+    /// Estimated token cap for the channel system prompt. None means no cap.
+    pub system_prompt_token_budget: ArcSwap<Option<usize>>,
+    /// Configured reply language. Channels may override it via the settings store.
+    pub output_language: ArcSwap<Option<String>>,
+    /// Configured cortex output language, before the `output_language` fallback.
+    pub cortex_language: ArcSwap<Option<String>>,
+    /// Footer appended to text replies, before `{{agent_name}}` expansion.
+    pub response_footer: ArcSwap<Option<String>>,
     pub max_concurrent_branches: ArcSwap<usize>,
     pub max_concurrent_workers: ArcSwap<usize>,
+    pub max_concurrent_processes: ArcSwap<usize>,
     pub browser_config: ArcSwap<BrowserConfig>,
     pub mcp: ArcSwap<Vec<McpServerConfig>>,
     pub history_backfill_count: ArcSwap<usize>,
@@ -62,10 +92,19 @@ pub struct RuntimeConfig {
     pub knowledge_synthesis_last_version: Arc<std::sync::atomic::AtomicU64>,
     /// Timestamp of the last knowledge_synthesis_version bump, for debouncing.
     pub knowledge_synthesis_last_change: Arc<std::sync::atomic::AtomicI64>,
+    /// Set while `instance_dir/PAUSE` exists. Maintained by the file watcher.
+    pub instance_paused: std::sync::atomic::AtomicBool,
+    /// Set while `agents/{id}/PAUSE` exists. Maintained by the file watcher.
+    pub agent_paused: std::sync::atomic::AtomicBool,
     pub prompts: ArcSwap<crate::prompts::PromptEngine>,
+    /// Override files the prompt engine was built from. Maintained by the
+    /// file watcher, which rebuilds the engine when one of them changes.
+    pub prompt_overrides: ArcSwap<PromptOverrides>,
     pub identity: ArcSwap<crate::identity::Identity>,
     pub skills: ArcSwap<crate::skills::SkillSet>,
     pub opencode: ArcSwap<OpenCodeConfig>,
+    pub worker_log_retention: ArcSwap<WorkerLogRetention>,
+    pub tool_results: ArcSwap<ToolResultLimits>,
     /// Shared pool of OpenCode server processes. Lazily initialized on first use.
     pub opencode_server_pool: ArcSwap<crate::opencode::OpenCodeServerPool>,
     /// Cron store, set after agent initialization.
@@ -123,13 +162,34 @@ impl RuntimeConfig {
             compaction: ArcSwap::from_pointee(agent_config.compaction),
             memory_persistence: ArcSwap::from_pointee(agent_config.memory_persistence),
             coalesce: ArcSwap::from_pointee(agent_config.coalesce),
-            ingestion: ArcSwap::from_pointee(agent_config.ingestion),
-            channel_config: ArcSwap::from_pointee(agent_config.channel),
+            ingestion: ArcSwap::from_pointee(agent_config.ingestion.clone()),
+            channel_config: ArcSwap::from_pointee(agent_config.channel.clone()),
+            media_safety: ArcSwap::from_pointee(agent_config.media_safety.clone()),
+            task_extraction: ArcSwap::from_pointee(agent_config.task_extraction),
+            media_classifier: ArcSwap::from_pointee(None),
+            transcription: ArcSwap::from_pointee(agent_config.transcription.clone()),
+            worker_retry: ArcSwap::from_pointee(agent_config.worker_retry),
+            tool_call_limits: ArcSwap::from_pointee(agent_config.tool_call_limits),
+            stream_pacing: ArcSwap::from_pointee(agent_config.stream_pacing),
+            reaction_triggers: {
+                ReactionTriggers::register(&agent_config.id, &agent_config.reaction_triggers);
+                ArcSwap::from_pointee(agent_config.reaction_triggers.clone())
+            },
+            personas: ArcSwap::from_pointee(agent_config.personas.clone()),
+            worker_buttons: ArcSwap::from_pointee(agent_config.worker_buttons.clone()),
+            disabled_tools: ArcSwap::from_pointee(agent_config.disabled_tools.clone()),
             max_turns: ArcSwap::from_pointee(agent_config.max_turns),
             branch_max_turns: ArcSwap::from_pointee(agent_config.branch_max_turns),
             context_window: ArcSwap::from_pointee(agent_config.context_window),
+            system_prompt_token_budget: ArcSwap::from_pointee(
+                agent_config.system_prompt_token_budget,
+            ),
+            output_language: ArcSwap::from_pointee(agent_config.output_language.clone()),
+            cortex_language: ArcSwap::from_pointee(agent_config.cortex_language.clone()),
+            response_footer: ArcSwap::from_pointee(agent_config.response_footer.clone()),
             max_concurrent_branches: ArcSwap::from_pointee(agent_config.max_concurrent_branches),
             max_concurrent_workers: ArcSwap::from_pointee(agent_config.max_concurrent_workers),
+            max_concurrent_processes: ArcSwap::from_pointee(agent_config.max_concurrent_processes),
             browser_config: ArcSwap::from_pointee(agent_config.browser.clone()),
             mcp: ArcSwap::from_pointee(agent_config.mcp.clone()),
             history_backfill_count: ArcSwap::from_pointee(agent_config.history_backfill_count),
@@ -145,10 +205,15 @@ impl RuntimeConfig {
             knowledge_synthesis_version: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             knowledge_synthesis_last_version: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             knowledge_synthesis_last_change: Arc::new(std::sync::atomic::AtomicI64::new(0)),
+            instance_paused: std::sync::atomic::AtomicBool::new(false),
+            agent_paused: std::sync::atomic::AtomicBool::new(false),
             prompts: ArcSwap::from_pointee(prompts),
+            prompt_overrides: ArcSwap::from_pointee(agent_config.prompt_overrides.clone()),
             identity: ArcSwap::from_pointee(identity),
             skills: ArcSwap::from_pointee(skills),
             opencode: ArcSwap::from_pointee(defaults.opencode.clone()),
+            worker_log_retention: ArcSwap::from_pointee(defaults.worker_log_retention),
+            tool_results: ArcSwap::from_pointee(defaults.tool_results.clone()),
             opencode_server_pool: ArcSwap::from_pointee(server_pool),
             cron_store: ArcSwap::from_pointee(None),
             cron_scheduler: ArcSwap::from_pointee(None),
@@ -169,6 +234,14 @@ impl RuntimeConfig {
         }
     }
 
+    /// Replace the built-in moderation classifier for inbound media.
+    pub fn set_media_classifier(
+        &self,
+        classifier: Arc<dyn crate::agent::media_safety::MediaClassifierDyn>,
+    ) {
+        self.media_classifier.store(Arc::new(Some(classifier)));
+    }
+
     /// Set the cron store and scheduler after initialization.
     pub fn set_cron(
         &self,
@@ -192,7 +265,7 @@ impl RuntimeConfig {
             match settings.channel_listen_only_mode() {
                 Ok(Some(enabled)) => {
                     self.channel_config.rcu(move |current| {
-                        let mut next = **current;
+                        let mut next = current.as_ref().clone();
                         next.listen_only_mode = enabled;
                         Arc::new(next)
                     });
@@ -203,6 +276,10 @@ impl RuntimeConfig {
                 }
             }
         }
+        if let Some(bulletin_override) = settings.memory_bulletin_override() {
+            self.memory_bulletin
+                .store(Arc::new(bulletin_override.bulletin));
+        }
     }
 
     /// Set the secrets store after initialization.
@@ -267,8 +344,11 @@ impl RuntimeConfig {
             .store(Arc::new(resolved.memory_persistence));
         self.coalesce.store(Arc::new(resolved.coalesce));
         self.ingestion.store(Arc::new(resolved.ingestion));
-        let resolved_channel = resolved.channel;
-        let configured_listen_only = agent.channel.map(|channel| channel.listen_only_mode);
+        let resolved_channel = resolved.channel.clone();
+        let configured_listen_only = agent
+            .channel
+            .as_ref()
+            .map(|channel| channel.listen_only_mode);
         self.channel_listen_only_explicit
             .store(Arc::new(configured_listen_only));
         let persisted_listen_only = self.settings.load().as_ref().as_ref().and_then(|settings| {
@@ -284,21 +364,49 @@ impl RuntimeConfig {
             }
         });
         self.channel_config.rcu(move |current| {
-            let mut next = resolved_channel;
+            let mut next = resolved_channel.clone();
             next.listen_only_mode = configured_listen_only
                 .or(persisted_listen_only)
                 .unwrap_or(current.as_ref().listen_only_mode);
             // save_attachments has no persisted override — config is authoritative
             Arc::new(next)
         });
+        self.media_safety
+            .store(Arc::new(resolved.media_safety.clone()));
+        self.task_extraction
+            .store(Arc::new(resolved.task_extraction));
+        self.transcription
+            .store(Arc::new(resolved.transcription.clone()));
+        self.worker_retry.store(Arc::new(resolved.worker_retry));
+        self.tool_call_limits
+            .store(Arc::new(resolved.tool_call_limits));
+        self.stream_pacing.store(Arc::new(resolved.stream_pacing));
+        ReactionTriggers::register(agent_id, &resolved.reaction_triggers);
+        self.reaction_triggers
+            .store(Arc::new(resolved.reaction_triggers.clone()));
+        self.personas.store(Arc::new(resolved.personas.clone()));
+        self.worker_buttons
+            .store(Arc::new(resolved.worker_buttons.clone()));
+        self.disabled_tools
+            .store(Arc::new(resolved.disabled_tools.clone()));
         self.max_turns.store(Arc::new(resolved.max_turns));
         self.branch_max_turns
             .store(Arc::new(resolved.branch_max_turns));
         self.context_window.store(Arc::new(resolved.context_window));
+        self.system_prompt_token_budget
+            .store(Arc::new(resolved.system_prompt_token_budget));
+        self.output_language
+            .store(Arc::new(resolved.output_language.clone()));
+        self.cortex_language
+            .store(Arc::new(resolved.cortex_language.clone()));
+        self.response_footer
+            .store(Arc::new(resolved.response_footer.clone()));
         self.max_concurrent_branches
             .store(Arc::new(resolved.max_concurrent_branches));
         self.max_concurrent_workers
             .store(Arc::new(resolved.max_concurrent_workers));
+        self.max_concurrent_processes
+            .store(Arc::new(resolved.max_concurrent_processes));
         let old_persist = self.browser_config.load().persist_session;
         let new_persist = resolved.browser.persist_session;
         if old_persist != new_persist {
@@ -326,6 +434,16 @@ impl RuntimeConfig {
         new_sandbox.project_paths = existing_project_paths;
         self.sandbox.store(Arc::new(new_sandbox));
         self.projects.store(Arc::new(resolved.projects.clone()));
+        if **self.prompt_overrides.load() != resolved.prompt_overrides {
+            self.prompt_overrides
+                .store(Arc::new(resolved.prompt_overrides.clone()));
+            self.reload_prompts();
+        }
+
+        self.worker_log_retention
+            .store(Arc::new(config.defaults.worker_log_retention));
+        self.tool_results
+            .store(Arc::new(config.defaults.tool_results.clone()));
 
         let old_opencode = self.opencode.load().as_ref().clone();
         let new_opencode = config.defaults.opencode.clone();
@@ -358,11 +476,67 @@ impl RuntimeConfig {
         tracing::info!("identity reloaded");
     }
 
+    /// Rebuild the prompt engine from the current override files. Keeps the
+    /// previous templates if an override fails to load.
+    pub fn reload_prompts(&self) {
+        let overrides = self.prompt_overrides.load();
+        match self.prompts.load().with_overrides(&overrides) {
+            Ok(prompts) => {
+                tracing::info!(
+                    overridden = ?prompts.overridden_templates(),
+                    "prompt templates reloaded"
+                );
+                self.prompts.store(Arc::new(prompts));
+            }
+            Err(error) => {
+                tracing::error!(%error, "failed to reload prompt overrides, keeping previous templates");
+            }
+        }
+    }
+
     /// Reload skills from disk.
     pub fn reload_skills(&self, skills: crate::skills::SkillSet) {
         self.skills.store(Arc::new(skills));
         tracing::info!("skills reloaded");
     }
+
+    /// Whether a kill switch file pauses this agent. While paused, channels
+    /// record inbound messages but don't respond, outbound responses are
+    /// dropped, and no workers are dispatched.
+    pub fn is_paused(&self) -> bool {
+        self.instance_paused
+            .load(std::sync::atomic::Ordering::Relaxed)
+            || self.agent_paused.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Re-check the instance and agent kill switch files, logging when either
+    /// pause starts or ends.
+    pub fn refresh_pause_state(&self, agent_id: &str) {
+        let scopes = [
+            (
+                &self.instance_paused,
+                self.instance_dir.join(PAUSE_FILE_NAME),
+            ),
+            (&self.agent_paused, self.identity_dir.join(PAUSE_FILE_NAME)),
+        ];
+        for (flag, path) in scopes {
+            let paused = path.exists();
+            let was_paused = flag.swap(paused, std::sync::atomic::Ordering::Relaxed);
+            if paused && !was_paused {
+                tracing::warn!(
+                    agent_id,
+                    path = %path.display(),
+                    "pause file detected, agent paused: no responses or worker dispatch until it is removed"
+                );
+            } else if !paused && was_paused {
+                tracing::warn!(
+                    agent_id,
+                    path = %path.display(),
+                    "pause file cleared, agent resumed"
+                );
+            }
+        }
+    }
 }
 
 impl std::fmt::Debug for RuntimeConfig {
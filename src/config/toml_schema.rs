@@ -27,6 +27,10 @@ pub(super) struct TomlConfig {
     pub(super) metrics: TomlMetricsConfig,
     #[serde(default)]
     pub(super) telemetry: TomlTelemetryConfig,
+    #[serde(default)]
+    pub(super) db: TomlDbConfig,
+    #[serde(default)]
+    pub(super) event_sink: TomlEventSinkConfig,
 }
 
 #[derive(Deserialize)]
@@ -68,6 +72,7 @@ pub(super) struct TomlHumanDef {
     pub(super) telegram_id: Option<String>,
     pub(super) slack_id: Option<String>,
     pub(super) email: Option<String>,
+    pub(super) handoff_target: Option<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -88,6 +93,10 @@ pub(super) struct TomlApiConfig {
     pub(super) bind: String,
     #[serde(default)]
     pub(super) auth_token: Option<String>,
+    #[serde(default)]
+    pub(super) access_log: bool,
+    pub(super) access_log_max_size_mb: Option<u64>,
+    pub(super) access_log_max_files: Option<usize>,
 }
 
 impl Default for TomlApiConfig {
@@ -97,6 +106,9 @@ impl Default for TomlApiConfig {
             port: default_api_port(),
             bind: default_api_bind(),
             auth_token: None,
+            access_log: false,
+            access_log_max_size_mb: None,
+            access_log_max_files: None,
         }
     }
 }
@@ -146,12 +158,39 @@ pub(super) fn default_metrics_bind() -> String {
     "0.0.0.0".into()
 }
 
+#[derive(Deserialize, Default)]
+pub(super) struct TomlEventSinkConfig {
+    pub(super) url: Option<String>,
+    pub(super) auth_token: Option<String>,
+    #[serde(default)]
+    pub(super) headers: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) event_types: Vec<String>,
+    pub(super) batch_size: Option<usize>,
+    pub(super) flush_interval_ms: Option<u64>,
+    pub(super) max_retries: Option<u32>,
+    pub(super) queue_capacity: Option<usize>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlDbConfig {
+    pub(super) max_connections: Option<u32>,
+    pub(super) busy_timeout_secs: Option<u64>,
+    pub(super) acquire_timeout_secs: Option<u64>,
+    pub(super) statement_timeout_secs: Option<u64>,
+    pub(super) wal: Option<bool>,
+}
+
 #[derive(Deserialize, Debug)]
 pub(super) struct TomlProviderConfig {
     pub(super) api_type: super::ApiType,
     pub(super) base_url: String,
     pub(super) api_key: String,
     pub(super) name: Option<String>,
+    #[serde(default)]
+    pub(super) headers: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) fallback_base_urls: Vec<String>,
 }
 
 #[derive(Deserialize, Default)]
@@ -180,6 +219,10 @@ pub(super) struct TomlLlmConfigFields {
     pub(super) github_copilot_key: Option<String>,
     #[serde(default)]
     pub(super) providers: HashMap<String, TomlProviderConfig>,
+    pub(super) health: Option<TomlProviderHealthConfig>,
+    pub(super) validate_models: Option<bool>,
+    pub(super) default_model: Option<String>,
+    pub(super) attribution: Option<TomlAppAttribution>,
     #[serde(default)]
     #[serde(flatten)]
     pub(super) extra: HashMap<String, toml::Value>,
@@ -210,6 +253,23 @@ pub(super) struct TomlLlmConfig {
     pub(super) zai_coding_plan_key: Option<String>,
     pub(super) github_copilot_key: Option<String>,
     pub(super) providers: HashMap<String, TomlProviderConfig>,
+    pub(super) health: Option<TomlProviderHealthConfig>,
+    pub(super) validate_models: Option<bool>,
+    pub(super) default_model: Option<String>,
+    pub(super) attribution: Option<TomlAppAttribution>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlProviderHealthConfig {
+    pub(super) failure_threshold: Option<u32>,
+    pub(super) probe_interval_secs: Option<u64>,
+}
+
+#[derive(Deserialize, Default)]
+pub(super) struct TomlAppAttribution {
+    pub(super) referer: Option<String>,
+    pub(super) title: Option<String>,
+    pub(super) user_agent: Option<String>,
 }
 
 impl<'de> Deserialize<'de> for TomlLlmConfig {
@@ -266,6 +326,10 @@ impl<'de> Deserialize<'de> for TomlLlmConfig {
             zai_coding_plan_key: fields.zai_coding_plan_key,
             github_copilot_key: fields.github_copilot_key,
             providers: fields.providers,
+            health: fields.health,
+            validate_models: fields.validate_models,
+            default_model: fields.default_model,
+            attribution: fields.attribution,
         })
     }
 }
@@ -275,9 +339,14 @@ pub(super) struct TomlDefaultsConfig {
     pub(super) routing: Option<TomlRoutingConfig>,
     pub(super) max_concurrent_branches: Option<usize>,
     pub(super) max_concurrent_workers: Option<usize>,
+    pub(super) max_concurrent_processes: Option<usize>,
     pub(super) max_turns: Option<usize>,
     pub(super) branch_max_turns: Option<usize>,
     pub(super) context_window: Option<usize>,
+    pub(super) system_prompt_token_budget: Option<usize>,
+    pub(super) output_language: Option<String>,
+    pub(super) cortex_language: Option<String>,
+    pub(super) response_footer: Option<String>,
     pub(super) compaction: Option<TomlCompactionConfig>,
     pub(super) memory_persistence: Option<TomlMemoryPersistenceConfig>,
     pub(super) coalesce: Option<TomlCoalesceConfig>,
@@ -286,6 +355,25 @@ pub(super) struct TomlDefaultsConfig {
     pub(super) warmup: Option<TomlWarmupConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
+    pub(super) media_safety: Option<TomlMediaSafetyConfig>,
+    pub(super) task_extraction: Option<TomlTaskExtractionConfig>,
+    pub(super) transcription: Option<TomlTranscriptionConfig>,
+    pub(super) embedding: Option<TomlEmbeddingConfig>,
+    pub(super) worker_retry: Option<TomlWorkerRetryConfig>,
+    pub(super) memory_decay: Option<TomlMemoryDecayConfig>,
+    pub(super) memory_scrub: Option<TomlMemoryScrubConfig>,
+    pub(super) tool_call_limits: Option<TomlToolCallLimits>,
+    pub(super) stream_pacing: Option<TomlStreamPacingConfig>,
+    #[serde(default)]
+    pub(super) reaction_triggers: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) personas: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) prompt_overrides: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) worker_buttons: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) disabled_tools: Vec<String>,
     #[serde(default)]
     pub(super) mcp: Vec<TomlMcpServerConfig>,
     pub(super) brave_search_key: Option<String>,
@@ -293,6 +381,9 @@ pub(super) struct TomlDefaultsConfig {
     pub(super) user_timezone: Option<String>,
     pub(super) opencode: Option<TomlOpenCodeConfig>,
     pub(super) worker_log_mode: Option<String>,
+    pub(super) worker_log_retention_days: Option<u64>,
+    pub(super) worker_log_max_total_mb: Option<u64>,
+    pub(super) tool_results: Option<TomlToolResultLimits>,
     pub(super) projects: Option<TomlProjectsConfig>,
 }
 
@@ -305,16 +396,30 @@ pub(super) struct TomlRoutingConfig {
     pub(super) cortex: Option<String>,
     pub(super) voice: Option<String>,
     pub(super) rate_limit_cooldown_secs: Option<u64>,
+    pub(super) tool_call_repair_attempts: Option<usize>,
     pub(super) channel_thinking_effort: Option<String>,
     pub(super) branch_thinking_effort: Option<String>,
     pub(super) worker_thinking_effort: Option<String>,
     pub(super) compactor_thinking_effort: Option<String>,
     pub(super) cortex_thinking_effort: Option<String>,
+    pub(super) gemini_thinking_budgets: Option<TomlGeminiThinkingBudgets>,
+    pub(super) channel_max_output_tokens: Option<u64>,
+    pub(super) branch_max_output_tokens: Option<u64>,
+    pub(super) worker_max_output_tokens: Option<u64>,
+    pub(super) compactor_max_output_tokens: Option<u64>,
+    pub(super) cortex_max_output_tokens: Option<u64>,
     #[serde(default)]
     pub(super) task_overrides: HashMap<String, String>,
     pub(super) fallbacks: Option<HashMap<String, Vec<String>>>,
 }
 
+#[derive(Deserialize, Default)]
+pub(super) struct TomlGeminiThinkingBudgets {
+    pub(super) low: Option<u32>,
+    pub(super) medium: Option<u32>,
+    pub(super) high: Option<u32>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlMemoryPersistenceConfig {
     pub(super) enabled: Option<bool>,
@@ -335,6 +440,9 @@ pub(super) struct TomlIngestionConfig {
     pub(super) enabled: Option<bool>,
     pub(super) poll_interval_secs: Option<u64>,
     pub(super) chunk_size: Option<usize>,
+    pub(super) concurrency: Option<usize>,
+    pub(super) extensions: Option<Vec<String>>,
+    pub(super) max_retries: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -342,6 +450,8 @@ pub(super) struct TomlCompactionConfig {
     pub(super) background_threshold: Option<f32>,
     pub(super) aggressive_threshold: Option<f32>,
     pub(super) emergency_threshold: Option<f32>,
+    pub(super) summary_max_tokens: Option<usize>,
+    pub(super) max_turns_before_compact: Option<usize>,
 }
 
 #[derive(Deserialize)]
@@ -366,12 +476,16 @@ pub(super) struct TomlCortexConfig {
     pub(super) association_max_per_pass: Option<usize>,
     pub(super) knowledge_synthesis_max_words: Option<usize>,
     pub(super) knowledge_synthesis_debounce_secs: Option<u64>,
+    pub(super) consolidation_interval_secs: Option<u64>,
+    pub(super) consolidation_min_cluster_size: Option<usize>,
+    pub(super) consolidation_min_importance: Option<f32>,
 }
 
 #[derive(Deserialize)]
 pub(super) struct TomlWarmupConfig {
     pub(super) enabled: Option<bool>,
     pub(super) eager_embedding_load: Option<bool>,
+    pub(super) preload_models: Option<bool>,
     pub(super) refresh_secs: Option<u64>,
     pub(super) startup_delay_secs: Option<u64>,
 }
@@ -387,10 +501,103 @@ pub(super) struct TomlBrowserConfig {
     pub(super) close_policy: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlMediaSafetyConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) endpoint: Option<String>,
+    pub(super) model: Option<String>,
+    pub(super) api_key: Option<String>,
+    pub(super) block_threshold: Option<f32>,
+    pub(super) blocked_response: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlTaskExtractionConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) min_confidence: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlTranscriptionConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) endpoint: Option<String>,
+    pub(super) model: Option<String>,
+    pub(super) api_key: Option<String>,
+    pub(super) language: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlEmbeddingConfig {
+    pub(super) query_prefix: Option<String>,
+    pub(super) passage_prefix: Option<String>,
+    pub(super) routes: Option<Vec<TomlEmbeddingRoute>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlEmbeddingRoute {
+    pub(super) content_type: String,
+    pub(super) model: String,
+    #[serde(default)]
+    pub(super) query_prefix: String,
+    #[serde(default)]
+    pub(super) passage_prefix: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlMemoryDecayConfig {
+    pub(super) half_life_days: Option<f64>,
+    pub(super) min_weight: Option<f64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlMemoryScrubConfig {
+    pub(super) enabled: Option<bool>,
+    pub(super) redact_emails: Option<bool>,
+    pub(super) redact_phone_numbers: Option<bool>,
+    pub(super) patterns: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlToolCallLimits {
+    pub(super) max_tool_calls_per_turn: Option<u32>,
+    pub(super) max_repeat_same_tool: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlStreamPacingConfig {
+    pub(super) chars_per_second: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlWorkerRetryConfig {
+    pub(super) max_retries: Option<u32>,
+    pub(super) backoff_secs: Option<u64>,
+    pub(super) preserve_context: Option<bool>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlChannelConfig {
     pub(super) listen_only_mode: Option<bool>,
     pub(super) save_attachments: Option<bool>,
+    pub(super) idle_timeout_secs: Option<u64>,
+    pub(super) notes_prompt_max_chars: Option<usize>,
+    pub(super) memory_recall_count: Option<usize>,
+    pub(super) memory_recall_order: Option<String>,
+    pub(super) max_concurrent_channels: Option<usize>,
+    pub(super) overflow_policy: Option<String>,
+    pub(super) max_queued_conversations: Option<usize>,
+    pub(super) busy_message: Option<String>,
+    pub(super) max_inbound_chars: Option<usize>,
+    pub(super) oversized_inbound: Option<String>,
+    pub(super) oversized_message: Option<String>,
+    pub(super) command_prefix: Option<String>,
+    pub(super) commands: Option<Vec<String>>,
+    pub(super) followup_after_secs: Option<u64>,
+    pub(super) followup_message: Option<String>,
+    pub(super) followup_quiet_start_hour: Option<u8>,
+    pub(super) followup_quiet_end_hour: Option<u8>,
+    pub(super) history_max_age_days: Option<u64>,
+    pub(super) slots_in_prompt: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -410,6 +617,14 @@ pub(super) struct TomlOpenCodePermissions {
     pub(super) webfetch: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub(super) struct TomlToolResultLimits {
+    pub(super) max_bytes: Option<usize>,
+    #[serde(default)]
+    pub(super) per_tool: HashMap<String, usize>,
+    pub(super) save_full_results: Option<bool>,
+}
+
 #[derive(Deserialize)]
 pub(super) struct TomlProjectsConfig {
     pub(super) use_worktrees: Option<bool>,
@@ -434,6 +649,8 @@ pub(super) struct TomlMcpServerConfig {
     pub(super) url: Option<String>,
     #[serde(default)]
     pub(super) headers: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) agents: Vec<String>,
 }
 
 pub(super) fn default_mcp_enabled() -> bool {
@@ -453,9 +670,14 @@ pub(super) struct TomlAgentConfig {
     pub(super) routing: Option<TomlRoutingConfig>,
     pub(super) max_concurrent_branches: Option<usize>,
     pub(super) max_concurrent_workers: Option<usize>,
+    pub(super) max_concurrent_processes: Option<usize>,
     pub(super) max_turns: Option<usize>,
     pub(super) branch_max_turns: Option<usize>,
     pub(super) context_window: Option<usize>,
+    pub(super) system_prompt_token_budget: Option<usize>,
+    pub(super) output_language: Option<String>,
+    pub(super) cortex_language: Option<String>,
+    pub(super) response_footer: Option<String>,
     pub(super) compaction: Option<TomlCompactionConfig>,
     pub(super) memory_persistence: Option<TomlMemoryPersistenceConfig>,
     pub(super) coalesce: Option<TomlCoalesceConfig>,
@@ -464,6 +686,20 @@ pub(super) struct TomlAgentConfig {
     pub(super) warmup: Option<TomlWarmupConfig>,
     pub(super) browser: Option<TomlBrowserConfig>,
     pub(super) channel: Option<TomlChannelConfig>,
+    pub(super) media_safety: Option<TomlMediaSafetyConfig>,
+    pub(super) task_extraction: Option<TomlTaskExtractionConfig>,
+    pub(super) transcription: Option<TomlTranscriptionConfig>,
+    pub(super) embedding: Option<TomlEmbeddingConfig>,
+    pub(super) worker_retry: Option<TomlWorkerRetryConfig>,
+    pub(super) memory_decay: Option<TomlMemoryDecayConfig>,
+    pub(super) memory_scrub: Option<TomlMemoryScrubConfig>,
+    pub(super) tool_call_limits: Option<TomlToolCallLimits>,
+    pub(super) stream_pacing: Option<TomlStreamPacingConfig>,
+    pub(super) reaction_triggers: Option<HashMap<String, String>>,
+    pub(super) personas: Option<HashMap<String, String>>,
+    pub(super) prompt_overrides: Option<HashMap<String, String>>,
+    pub(super) worker_buttons: Option<HashMap<String, String>>,
+    pub(super) disabled_tools: Option<Vec<String>>,
     pub(super) mcp: Option<Vec<TomlMcpServerConfig>>,
     pub(super) brave_search_key: Option<String>,
     pub(super) cron_timezone: Option<String>,
@@ -518,6 +754,11 @@ pub(super) struct TomlDiscordConfig {
     pub(super) dm_allowed_users: Vec<String>,
     #[serde(default)]
     pub(super) allow_bot_messages: bool,
+    #[serde(default)]
+    pub(super) require_mention_default: bool,
+    #[serde(default)]
+    pub(super) stream_edits: bool,
+    pub(super) stream_edit_interval_ms: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -544,6 +785,8 @@ pub(super) struct TomlSlackConfig {
     pub(super) dm_allowed_users: Vec<String>,
     #[serde(default)]
     pub(super) commands: Vec<TomlSlackCommandConfig>,
+    #[serde(default)]
+    pub(super) require_mention_default: bool,
 }
 
 #[derive(Deserialize)]
@@ -575,6 +818,8 @@ pub(super) struct TomlTelegramConfig {
     pub(super) instances: Vec<TomlTelegramInstanceConfig>,
     #[serde(default)]
     pub(super) dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    pub(super) require_mention_default: bool,
 }
 
 #[derive(Deserialize)]
@@ -663,6 +908,8 @@ pub(super) struct TomlWebhookConfig {
     #[serde(default = "default_webhook_bind")]
     pub(super) bind: String,
     pub(super) auth_token: Option<String>,
+    pub(super) signing_secret: Option<String>,
+    pub(super) signature_header: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -679,6 +926,8 @@ pub(super) struct TomlTwitchConfig {
     #[serde(default)]
     pub(super) channels: Vec<String>,
     pub(super) trigger_prefix: Option<String>,
+    #[serde(default)]
+    pub(super) require_mention_default: bool,
 }
 
 #[derive(Deserialize)]
@@ -788,9 +1037,27 @@ pub(super) struct TomlBinding {
     #[serde(default)]
     pub(super) channel_ids: Vec<String>,
     #[serde(default)]
-    pub(super) require_mention: bool,
+    pub(super) require_mention: Option<bool>,
     #[serde(default)]
     pub(super) dm_allowed_users: Vec<String>,
+    #[serde(default)]
+    pub(super) welcome_message: Option<String>,
+    /// IANA timezone the `schedule` windows are read in.
+    #[serde(default)]
+    pub(super) timezone: Option<String>,
+    #[serde(default)]
+    pub(super) schedule: Vec<TomlBindingWindow>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct TomlBindingWindow {
+    pub(super) agent_id: String,
+    /// Local `HH:MM` the window opens.
+    pub(super) start: String,
+    /// Local `HH:MM` the window closes.
+    pub(super) end: String,
+    #[serde(default)]
+    pub(super) days: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -806,6 +1073,8 @@ pub(super) struct TomlMattermostConfig {
     pub(super) dm_allowed_users: Vec<String>,
     #[serde(default = "default_mattermost_max_attachment_bytes")]
     pub(super) max_attachment_bytes: usize,
+    #[serde(default)]
+    pub(super) require_mention_default: bool,
 }
 
 #[derive(Deserialize)]
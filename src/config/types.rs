@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
 
 pub(super) const CRON_TIMEZONE_ENV_VAR: &str = "SPACEBOT_CRON_TIMEZONE";
 pub(super) const USER_TIMEZONE_ENV_VAR: &str = "SPACEBOT_USER_TIMEZONE";
@@ -59,6 +60,10 @@ pub struct Config {
     pub metrics: MetricsConfig,
     /// OpenTelemetry export configuration.
     pub telemetry: TelemetryConfig,
+    /// SQLite pool tuning for the per-agent databases.
+    pub db: DbConfig,
+    /// Outbound HTTP forwarding of raw process events.
+    pub event_sink: EventSinkConfig,
 }
 
 impl Config {
@@ -117,6 +122,10 @@ pub struct HumanDef {
     pub slack_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    /// Delivery target (`adapter:target`) for handoff notifications. When
+    /// unset, handoffs DM the human on a platform they have an ID for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handoff_target: Option<String>,
 }
 
 /// A visual group definition for the topology UI.
@@ -138,6 +147,12 @@ pub struct ApiConfig {
     /// Address to bind the HTTP server on.
     pub bind: String,
     pub auth_token: Option<String>,
+    /// Whether to write a JSONL access log of API requests.
+    pub access_log: bool,
+    /// Size at which the access log is rotated, in bytes.
+    pub access_log_max_bytes: u64,
+    /// Number of rotated access log files to keep.
+    pub access_log_max_files: usize,
 }
 
 impl Default for ApiConfig {
@@ -147,6 +162,9 @@ impl Default for ApiConfig {
             port: 19898,
             bind: "127.0.0.1".into(),
             auth_token: None,
+            access_log: false,
+            access_log_max_bytes: 10 * 1024 * 1024,
+            access_log_max_files: 5,
         }
     }
 }
@@ -172,6 +190,102 @@ impl Default for MetricsConfig {
     }
 }
 
+/// SQLite pool tuning, applied to every agent's database.
+#[derive(Debug, Clone, Copy)]
+pub struct DbConfig {
+    /// Maximum open connections per agent database.
+    pub max_connections: u32,
+    /// How long a connection waits on a locked database before failing with
+    /// `SQLITE_BUSY`.
+    pub busy_timeout_secs: u64,
+    /// How long a caller waits for a free connection from the pool.
+    pub acquire_timeout_secs: u64,
+    /// Longest a connection may stay checked out before whatever SQL it is
+    /// running is interrupted. The clock starts at checkout, not per
+    /// statement, so callers that hold a connection across many statements
+    /// (transactions, batch jobs) count all of them. 0, the default,
+    /// disables the limit.
+    pub statement_timeout_secs: u64,
+    /// Use write-ahead logging so readers don't block the writer.
+    pub wal: bool,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 8,
+            busy_timeout_secs: 10,
+            acquire_timeout_secs: 30,
+            statement_timeout_secs: 0,
+            wal: true,
+        }
+    }
+}
+
+/// Forwarding of every agent's raw `ProcessEvent`s to an HTTP endpoint.
+///
+/// Events are POSTed as JSON in batches. The sink is off unless `url` is set.
+#[derive(Clone)]
+pub struct EventSinkConfig {
+    pub url: Option<String>,
+    /// Sent as `Authorization: Bearer <token>`.
+    pub auth_token: Option<String>,
+    /// Extra headers sent with every request.
+    pub headers: HashMap<String, String>,
+    /// Event `type` names to forward, e.g. `"worker_started"`. Empty forwards
+    /// every event.
+    pub event_types: Vec<String>,
+    /// Most events sent in one request.
+    pub batch_size: usize,
+    /// Longest a partial batch waits before it is sent.
+    pub flush_interval_ms: u64,
+    /// Retries for a failed request before its batch is dropped.
+    pub max_retries: u32,
+    /// Events held while the endpoint is slow or down. Newer events are
+    /// dropped once the queue is full.
+    pub queue_capacity: usize,
+}
+
+impl EventSinkConfig {
+    /// Whether an event with this `type` name should be forwarded.
+    pub fn forwards(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.iter().any(|name| name == event_type)
+    }
+}
+
+impl Default for EventSinkConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            auth_token: None,
+            headers: HashMap::new(),
+            event_types: Vec::new(),
+            batch_size: 100,
+            flush_interval_ms: 1000,
+            max_retries: 5,
+            queue_capacity: 10_000,
+        }
+    }
+}
+
+impl std::fmt::Debug for EventSinkConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSinkConfig")
+            .field("url", &self.url)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("event_types", &self.event_types)
+            .field("batch_size", &self.batch_size)
+            .field("flush_interval_ms", &self.flush_interval_ms)
+            .field("max_retries", &self.max_retries)
+            .field("queue_capacity", &self.queue_capacity)
+            .finish()
+    }
+}
+
 /// API types supported by LLM providers.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ApiType {
@@ -220,9 +334,13 @@ pub struct ProviderConfig {
     /// Anthropic requests. Set automatically when the key originates from
     /// `ANTHROPIC_AUTH_TOKEN` (proxy-compatible auth).
     pub use_bearer_auth: bool,
-    /// Additional HTTP headers included in requests to this provider.
-    /// Currently applied in `call_openai()` (the `OpenAiCompletions` path).
+    /// Additional HTTP headers included in every request to this provider.
+    /// Built-in attribution headers plus any configured under `headers`.
     pub extra_headers: Vec<(String, String)>,
+    /// Base URLs tried in order when `base_url` can't be reached. Only
+    /// connection failures move to the next URL; errors the provider returns
+    /// do not.
+    pub fallback_base_urls: Vec<String>,
 }
 
 impl std::fmt::Debug for ProviderConfig {
@@ -241,6 +359,7 @@ impl std::fmt::Debug for ProviderConfig {
                     .map(|(key, _)| key.as_str())
                     .collect::<Vec<_>>(),
             )
+            .field("fallback_base_urls", &self.fallback_base_urls)
             .finish()
     }
 }
@@ -271,6 +390,81 @@ pub struct LlmConfig {
     pub zai_coding_plan_key: Option<String>,
     pub github_copilot_key: Option<String>,
     pub providers: HashMap<String, ProviderConfig>,
+    pub health: ProviderHealthConfig,
+    /// Check routing models against each provider's model list at startup.
+    pub validate_models: bool,
+    /// Model a request falls back to when no provider can serve the routed
+    /// model, e.g. after a provider removes or renames it. `None` lets the
+    /// request fail.
+    pub default_model: Option<String>,
+    /// App identity sent with OpenAI-compatible requests.
+    pub attribution: AppAttribution,
+}
+
+/// App identity sent with LLM requests so providers can credit the traffic.
+///
+/// `referer` and `title` become the `HTTP-Referer` and `X-Title` attribution
+/// headers for OpenRouter and Kilo Gateway. `user_agent` is sent with every
+/// OpenAI-compatible request. An empty value leaves its header out. None of
+/// these are secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppAttribution {
+    pub referer: String,
+    pub title: String,
+    pub user_agent: String,
+}
+
+impl AppAttribution {
+    /// `HTTP-Referer` and `X-Title` headers, skipping empty values.
+    pub fn referer_headers(&self) -> Vec<(String, String)> {
+        [("HTTP-Referer", &self.referer), ("X-Title", &self.title)]
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    /// Add a `User-Agent` header unless the headers already carry one.
+    pub fn add_user_agent(&self, headers: &mut Vec<(String, String)>) {
+        let already_set = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("user-agent"));
+        if !already_set && !self.user_agent.is_empty() {
+            headers.push(("User-Agent".to_string(), self.user_agent.clone()));
+        }
+    }
+}
+
+impl Default for AppAttribution {
+    fn default() -> Self {
+        Self {
+            referer: "https://spacebot.sh/".to_string(),
+            title: "Spacebot".to_string(),
+            user_agent: format!("spacebot/{}", env!("CARGO_PKG_VERSION")),
+        }
+    }
+}
+
+/// Thresholds for marking a provider unhealthy and probing it back.
+///
+/// A provider that keeps failing with auth or server errors is skipped in
+/// favour of fallbacks until a background probe gets a successful response.
+#[derive(Debug, Clone, Copy)]
+pub struct ProviderHealthConfig {
+    /// Consecutive auth/5xx failures before the provider is marked unhealthy.
+    /// Zero disables health tracking.
+    pub failure_threshold: u32,
+    /// How often unhealthy providers are probed, in seconds.
+    pub probe_interval_secs: u64,
+}
+
+impl Default for ProviderHealthConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            probe_interval_secs: 60,
+        }
+    }
 }
 
 impl std::fmt::Debug for LlmConfig {
@@ -346,6 +540,10 @@ impl std::fmt::Debug for LlmConfig {
                 &self.github_copilot_key.as_ref().map(|_| "[REDACTED]"),
             )
             .field("providers", &self.providers)
+            .field("health", &self.health)
+            .field("validate_models", &self.validate_models)
+            .field("default_model", &self.default_model)
+            .field("attribution", &self.attribution)
             .finish()
     }
 }
@@ -525,9 +723,25 @@ pub struct DefaultsConfig {
     pub routing: RoutingConfig,
     pub max_concurrent_branches: usize,
     pub max_concurrent_workers: usize,
+    /// Ceiling on LLM-using processes (channel turns, branches, workers,
+    /// compactions, cortex runs) running at once for an agent. 0 is unlimited.
+    pub max_concurrent_processes: usize,
     pub max_turns: usize,
     pub branch_max_turns: usize,
     pub context_window: usize,
+    /// Estimated token cap for the channel system prompt. When exceeded,
+    /// skills are dropped from the prompt until it fits. None means no cap.
+    pub system_prompt_token_budget: Option<usize>,
+    /// Language the agent always replies in (e.g. "Japanese"), regardless of
+    /// the language users write in. None leaves the reply language to the model.
+    pub output_language: Option<String>,
+    /// Language for cortex output (bulletin, knowledge synthesis, profile,
+    /// digests, consolidated memories). None falls back to `output_language`,
+    /// then English.
+    pub cortex_language: Option<String>,
+    /// Footer appended to every text reply (e.g. "— {{agent_name}} (AI)"),
+    /// for AI disclosure. `{{agent_name}}` expands to the display name.
+    pub response_footer: Option<String>,
     pub compaction: CompactionConfig,
     pub memory_persistence: MemoryPersistenceConfig,
     pub coalesce: CoalesceConfig,
@@ -536,6 +750,24 @@ pub struct DefaultsConfig {
     pub warmup: WarmupConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
+    pub media_safety: MediaSafetyConfig,
+    pub task_extraction: TaskExtractionConfig,
+    pub transcription: TranscriptionConfig,
+    pub embedding: EmbeddingConfig,
+    pub worker_retry: WorkerRetryConfig,
+    pub memory_decay: MemoryDecayConfig,
+    pub memory_scrub: MemoryScrubConfig,
+    pub tool_call_limits: ToolCallLimits,
+    pub stream_pacing: StreamPacingConfig,
+    pub reaction_triggers: ReactionTriggers,
+    /// Named personas a channel can switch between with `switch_persona`.
+    pub personas: Personas,
+    /// Prompt templates loaded from disk in place of the bundled ones.
+    pub prompt_overrides: PromptOverrides,
+    /// Worker tasks started by `run_worker:<name>` buttons.
+    pub worker_buttons: WorkerButtons,
+    /// Native tools left out of every tool server, by tool name.
+    pub disabled_tools: Vec<String>,
     pub mcp: Vec<McpServerConfig>,
     /// Brave Search API key for web search tool. Supports "env:VAR_NAME" references.
     pub brave_search_key: Option<String>,
@@ -548,6 +780,10 @@ pub struct DefaultsConfig {
     pub opencode: OpenCodeConfig,
     /// Worker log mode: "errors_only", "all_separate", or "all_combined".
     pub worker_log_mode: crate::settings::WorkerLogMode,
+    /// How long worker logs are kept and how much disk they may use.
+    pub worker_log_retention: WorkerLogRetention,
+    /// Size caps for MCP tool results handed back to workers.
+    pub tool_results: ToolResultLimits,
     /// Projects workspace management defaults.
     pub projects: ProjectsConfig,
 }
@@ -558,9 +794,17 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("routing", &self.routing)
             .field("max_concurrent_branches", &self.max_concurrent_branches)
             .field("max_concurrent_workers", &self.max_concurrent_workers)
+            .field("max_concurrent_processes", &self.max_concurrent_processes)
             .field("max_turns", &self.max_turns)
             .field("branch_max_turns", &self.branch_max_turns)
             .field("context_window", &self.context_window)
+            .field(
+                "system_prompt_token_budget",
+                &self.system_prompt_token_budget,
+            )
+            .field("output_language", &self.output_language)
+            .field("cortex_language", &self.cortex_language)
+            .field("response_footer", &self.response_footer)
             .field("compaction", &self.compaction)
             .field("memory_persistence", &self.memory_persistence)
             .field("coalesce", &self.coalesce)
@@ -569,6 +813,20 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("warmup", &self.warmup)
             .field("browser", &self.browser)
             .field("channel", &self.channel)
+            .field("media_safety", &self.media_safety)
+            .field("task_extraction", &self.task_extraction)
+            .field("transcription", &self.transcription)
+            .field("embedding", &self.embedding)
+            .field("worker_retry", &self.worker_retry)
+            .field("memory_decay", &self.memory_decay)
+            .field("memory_scrub", &self.memory_scrub)
+            .field("tool_call_limits", &self.tool_call_limits)
+            .field("stream_pacing", &self.stream_pacing)
+            .field("reaction_triggers", &self.reaction_triggers)
+            .field("personas", &self.personas)
+            .field("prompt_overrides", &self.prompt_overrides)
+            .field("worker_buttons", &self.worker_buttons)
+            .field("disabled_tools", &self.disabled_tools)
             .field("mcp", &self.mcp)
             .field(
                 "brave_search_key",
@@ -580,6 +838,8 @@ impl std::fmt::Debug for DefaultsConfig {
             .field("cron", &self.cron)
             .field("opencode", &self.opencode)
             .field("worker_log_mode", &self.worker_log_mode)
+            .field("worker_log_retention", &self.worker_log_retention)
+            .field("tool_results", &self.tool_results)
             .field("projects", &self.projects)
             .finish()
     }
@@ -605,6 +865,9 @@ pub struct McpServerConfig {
     pub name: String,
     pub transport: McpTransport,
     pub enabled: bool,
+    /// Agent IDs a `[[defaults.mcp]]` server is limited to. Empty means every
+    /// agent. Ignored on per-agent servers.
+    pub agents: Vec<String>,
 }
 
 /// MCP transport configuration.
@@ -636,6 +899,22 @@ pub struct CompactionConfig {
     pub background_threshold: f32,
     pub aggressive_threshold: f32,
     pub emergency_threshold: f32,
+    /// Token budget for each compaction summary. `None` derives it from the
+    /// context window (see [`CompactionConfig::summary_token_budget`]).
+    pub summary_max_tokens: Option<usize>,
+    /// Run a background compaction once the channel holds more than this many
+    /// turns, whatever the token estimate says. `None` disables the limit.
+    pub max_turns_before_compact: Option<usize>,
+}
+
+impl CompactionConfig {
+    /// Token budget for a compaction summary: `summary_max_tokens` when set,
+    /// otherwise 2% of the context window, kept between 500 and 4000 tokens.
+    pub fn summary_token_budget(&self, context_window: usize) -> usize {
+        self.summary_max_tokens
+            .unwrap_or_else(|| (context_window / 50).clamp(500, 4000))
+            .max(1)
+    }
 }
 
 /// Auto-branching memory persistence configuration.
@@ -660,6 +939,28 @@ impl Default for MemoryPersistenceConfig {
     }
 }
 
+/// Automatic task creation from conversation messages.
+///
+/// When enabled, each user message in a channel is checked in the background
+/// for action items. Items the model is confident about become tasks awaiting
+/// approval, skipping any that match an open task. Off by default.
+#[derive(Debug, Clone, Copy)]
+pub struct TaskExtractionConfig {
+    /// Whether action items are turned into tasks automatically.
+    pub enabled: bool,
+    /// Minimum model confidence (0.0-1.0) for an action item to become a task.
+    pub min_confidence: f32,
+}
+
+impl Default for TaskExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_confidence: 0.8,
+        }
+    }
+}
+
 /// Working memory system configuration.
 ///
 /// Controls the temporal event log, intra-day synthesis, channel activity map,
@@ -723,6 +1024,8 @@ impl Default for CompactionConfig {
             background_threshold: 0.80,
             aggressive_threshold: 0.85,
             emergency_threshold: 0.95,
+            summary_max_tokens: None,
+            max_turns_before_compact: None,
         }
     }
 }
@@ -758,12 +1061,19 @@ impl Default for CoalesceConfig {
     }
 }
 
+/// Extensions the ingestion loop extracts text from unless configured otherwise.
+pub const DEFAULT_INGEST_EXTENSIONS: &[&str] = &[
+    "txt", "md", "markdown", "json", "jsonl", "csv", "tsv", "log", "xml", "yaml", "yml", "toml",
+    "rst", "org", "html", "htm", "pdf", "docx",
+];
+
 /// File-based memory ingestion configuration.
 ///
-/// Watches a directory in the agent workspace for text files, chunks them, and
-/// processes each chunk through the memory recall + save flow. Files are deleted
-/// after successful ingestion.
-#[derive(Debug, Clone, Copy)]
+/// Watches a directory in the agent workspace for documents, extracts their
+/// text, chunks it, and processes each chunk through the memory recall + save
+/// flow. Files are deleted after successful ingestion. Files that fail are
+/// retried on later poll cycles before being moved aside.
+#[derive(Debug, Clone)]
 pub struct IngestionConfig {
     /// Whether file-based memory ingestion is enabled.
     pub enabled: bool,
@@ -772,6 +1082,17 @@ pub struct IngestionConfig {
     /// Target chunk size in characters. Chunks may be slightly larger to avoid
     /// splitting mid-line.
     pub chunk_size: usize,
+    /// Maximum number of files and chunks processed at once. Chunks across all
+    /// files share this limit so the embedder and recall path aren't flooded.
+    pub concurrency: usize,
+    /// Lowercase file extensions (without the dot) to extract text from.
+    /// Files with any other extension are moved to the `failed/` subdirectory.
+    /// Files without an extension are read as plain text.
+    pub extensions: Vec<String>,
+    /// How many times a file that failed ingestion is retried, one attempt
+    /// per poll cycle, before it is moved to `failed/`. `0` moves it on the
+    /// first failure.
+    pub max_retries: u32,
 }
 
 impl Default for IngestionConfig {
@@ -780,6 +1101,12 @@ impl Default for IngestionConfig {
             enabled: true,
             poll_interval_secs: 30,
             chunk_size: 4000,
+            concurrency: 1,
+            extensions: DEFAULT_INGEST_EXTENSIONS
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect(),
+            max_retries: 3,
         }
     }
 }
@@ -852,8 +1179,99 @@ impl Default for BrowserConfig {
     }
 }
 
+/// What happens to a new conversation when an agent already has
+/// `max_concurrent_channels` channels and none of them can be evicted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelOverflowPolicy {
+    /// Hold the conversation's messages until a channel slot frees up.
+    #[default]
+    Queue,
+    /// Reply with `busy_message` and drop the messages.
+    Reject,
+}
+
+impl ChannelOverflowPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queue => "queue",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// What happens to a message whose text is longer than `max_inbound_chars`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OversizedInboundPolicy {
+    /// Keep the first `max_inbound_chars` characters and append a notice.
+    #[default]
+    Truncate,
+    /// Replace the text with a model-written summary for the turn.
+    Summarize,
+    /// Reply with `oversized_message` and skip the turn.
+    Reject,
+}
+
+impl OversizedInboundPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Truncate => "truncate",
+            Self::Summarize => "summarize",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// A built-in chat command reachable through `command_prefix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatCommand {
+    /// Clear the channel's conversation context.
+    Reset,
+    /// Reply with the agent, channel, mode, and models.
+    Status,
+    /// List the enabled commands.
+    Help,
+}
+
+impl ChatCommand {
+    pub const ALL: [Self; 3] = [Self::Reset, Self::Status, Self::Help];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reset => "reset",
+            Self::Status => "status",
+            Self::Help => "help",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|command| command.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// How memories recalled into the channel prompt are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemoryRecallOrder {
+    /// Most relevant to the incoming message first.
+    #[default]
+    Relevance,
+    /// Most recently updated first. Which memories are included is still
+    /// decided by relevance.
+    Recency,
+}
+
+impl MemoryRecallOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Relevance => "relevance",
+            Self::Recency => "recency",
+        }
+    }
+}
+
 /// Channel behavior configuration.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone)]
 pub struct ChannelConfig {
     /// When true, unsolicited chat messages are ignored unless command/mention/reply.
     pub listen_only_mode: bool,
@@ -861,6 +1279,608 @@ pub struct ChannelConfig {
     /// `workspace/saved/` and tracked in the `saved_attachments` table so
     /// they can be recalled on later turns.
     pub save_attachments: bool,
+    /// Seconds a channel can sit without messages or running work before it
+    /// is torn down to free memory. The next message recreates it with its
+    /// history backfilled from the conversation log. 0 (the default) keeps
+    /// channels alive.
+    pub idle_timeout_secs: u64,
+    /// Notes (agent-wide and for this channel) up to this many characters
+    /// are rendered into the channel prompt. Longer notes are left for the
+    /// `notes` tool to read on demand. 0 disables the injection.
+    pub notes_prompt_max_chars: usize,
+    /// Memories recalled by hybrid search on the incoming message and
+    /// rendered into the channel prompt each turn. 0 disables the recall.
+    pub memory_recall_count: usize,
+    pub memory_recall_order: MemoryRecallOrder,
+    /// Most channels the agent keeps in memory at once. When a new
+    /// conversation arrives at the limit, the longest-idle channel is evicted
+    /// to make room, and `overflow_policy` applies if every channel is busy.
+    /// 0 means no limit.
+    pub max_concurrent_channels: usize,
+    pub overflow_policy: ChannelOverflowPolicy,
+    /// Most new conversations held under `ChannelOverflowPolicy::Queue`.
+    /// Conversations past it, and messages past 50 for one waiting
+    /// conversation, get `busy_message` instead.
+    pub max_queued_conversations: usize,
+    /// Reply sent to conversations rejected at the limit or when the queue
+    /// is full.
+    pub busy_message: String,
+    /// Longest inbound message text, in characters, that reaches a turn as-is.
+    /// Longer text is handled by `oversized_inbound`. The conversation log
+    /// always keeps the full original. 0 means no limit.
+    pub max_inbound_chars: usize,
+    pub oversized_inbound: OversizedInboundPolicy,
+    /// Reply sent for messages rejected under `OversizedInboundPolicy::Reject`.
+    pub oversized_message: String,
+    /// Prefix that marks a message as a built-in chat command on any
+    /// adapter, e.g. `!reset`. Empty disables prefixed commands.
+    pub command_prefix: String,
+    /// Commands answered by built-in handlers instead of the LLM. Anyone in
+    /// the conversation can run them, so none are enabled by default.
+    /// Prefixed messages naming any other command pass through as normal
+    /// messages.
+    pub commands: Vec<ChatCommand>,
+    /// Seconds a conversation can sit quiet after the agent replied before
+    /// `followup_message` is sent, once per idle period. 0 disables it.
+    pub followup_after_secs: u64,
+    pub followup_message: String,
+    /// Local hours `(start, end)` during which follow-ups are not sent, in
+    /// the agent's user timezone. The window may wrap past midnight.
+    pub followup_quiet_hours: Option<(u8, u8)>,
+    /// Days a message stays in the channel's context and conversation log.
+    /// Older turns are dropped from history before each prompt and the log
+    /// is pruned on the cortex tick. Pinned context is kept. 0 keeps
+    /// history forever.
+    pub history_max_age_days: u64,
+    /// Render the conversation's slots (set with `set_slot`) into the
+    /// channel prompt on every turn. When off, the agent reads them with
+    /// `get_slot`.
+    pub slots_in_prompt: bool,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            listen_only_mode: false,
+            save_attachments: false,
+            idle_timeout_secs: 0,
+            notes_prompt_max_chars: 2000,
+            memory_recall_count: 0,
+            memory_recall_order: MemoryRecallOrder::default(),
+            max_concurrent_channels: 0,
+            overflow_policy: ChannelOverflowPolicy::default(),
+            max_queued_conversations: 100,
+            busy_message:
+                "I'm handling too many conversations right now. Please try again in a few minutes."
+                    .into(),
+            max_inbound_chars: 0,
+            oversized_inbound: OversizedInboundPolicy::default(),
+            oversized_message:
+                "That message is too long for me to read. Please send a shorter version or attach it as a file."
+                    .into(),
+            command_prefix: "!".into(),
+            commands: Vec::new(),
+            followup_after_secs: 0,
+            followup_message: "Still there? Let me know if you'd like me to continue.".into(),
+            followup_quiet_hours: None,
+            history_max_age_days: 0,
+            slots_in_prompt: true,
+        }
+    }
+}
+
+impl ChannelConfig {
+    /// Messages older than this are past `history_max_age_days`. None when
+    /// history is kept forever.
+    pub fn history_cutoff(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        if self.history_max_age_days == 0 {
+            return None;
+        }
+        let days = i64::try_from(self.history_max_age_days).ok()?;
+        now.checked_sub_signed(chrono::Duration::try_days(days)?)
+    }
+}
+
+/// Content-safety screening for inbound image attachments.
+///
+/// When enabled, images are classified before they reach a vision model. The
+/// safety score is recorded in the message metadata, and images scoring at or
+/// above `block_threshold` are withheld from the model.
+#[derive(Clone)]
+pub struct MediaSafetyConfig {
+    pub enabled: bool,
+    /// OpenAI-compatible moderation endpoint used by the built-in classifier.
+    pub endpoint: String,
+    /// Moderation model name sent to the endpoint.
+    pub model: String,
+    /// API key for the endpoint. Supports "env:VAR_NAME" references.
+    pub api_key: Option<String>,
+    /// Scores at or above this (0.0–1.0) block the attachment.
+    pub block_threshold: f32,
+    /// Reply sent to the conversation when an attachment is blocked.
+    /// None blocks silently.
+    pub blocked_response: Option<String>,
+}
+
+impl Default for MediaSafetyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "https://api.openai.com/v1/moderations".into(),
+            model: "omni-moderation-latest".into(),
+            api_key: None,
+            block_threshold: 0.9,
+            blocked_response: Some(
+                "I can't look at that image — it was flagged by the content-safety filter.".into(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Debug for MediaSafetyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MediaSafetyConfig")
+            .field("enabled", &self.enabled)
+            .field("endpoint", &self.endpoint)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("block_threshold", &self.block_threshold)
+            .field("blocked_response", &self.blocked_response)
+            .finish()
+    }
+}
+
+/// OpenAI's transcription endpoint, the default, and the only one that falls
+/// back to `OPENAI_API_KEY`.
+pub const OPENAI_TRANSCRIPTION_ENDPOINT: &str = "https://api.openai.com/v1/audio/transcriptions";
+
+/// Speech-to-text for inbound voice notes and audio attachments.
+///
+/// When enabled, audio attachments are sent to an OpenAI-compatible
+/// `/v1/audio/transcriptions` endpoint and the transcript becomes the message
+/// text. The audio itself stays attached to the message. When disabled, audio
+/// falls back to the `voice` routing model, if one is configured.
+#[derive(Clone)]
+pub struct TranscriptionConfig {
+    pub enabled: bool,
+    /// OpenAI-compatible transcription endpoint.
+    pub endpoint: String,
+    /// Speech-to-text model name sent to the endpoint.
+    pub model: String,
+    /// API key for the endpoint. Supports "env:VAR_NAME" references.
+    pub api_key: Option<String>,
+    /// ISO-639-1 language hint. None lets the provider detect the language.
+    pub language: Option<String>,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: OPENAI_TRANSCRIPTION_ENDPOINT.into(),
+            model: "whisper-1".into(),
+            api_key: None,
+            language: None,
+        }
+    }
+}
+
+/// Text prepended before embedding memories and search queries.
+///
+/// Asymmetric embedding models expect different instructions on the two sides
+/// (for example `"query: "` and `"passage: "`). Both default to empty, which
+/// embeds text as-is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmbeddingConfig {
+    /// Prepended to search queries before embedding.
+    pub query_prefix: String,
+    /// Prepended to memory content before its vector is stored.
+    pub passage_prefix: String,
+    /// Extra embedders for detected content types. Matching memories get a
+    /// second vector from the routed model, and matching queries search it
+    /// first. At most one route per content type.
+    pub routes: Vec<EmbeddingRoute>,
+}
+
+/// Kind of content a memory or query is detected as, for embedding routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbeddingContentType {
+    Text,
+    Code,
+}
+
+impl EmbeddingContentType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Code => "code",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "code" => Some(Self::Code),
+            _ => None,
+        }
+    }
+}
+
+/// A fastembed model that embeds one content type alongside the default
+/// model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingRoute {
+    pub content_type: EmbeddingContentType,
+    /// fastembed model code, e.g. `"jinaai/jina-embeddings-v2-base-code"`.
+    pub model: String,
+    pub query_prefix: String,
+    pub passage_prefix: String,
+}
+
+impl EmbeddingRoute {
+    /// The text to embed for a search query.
+    pub fn query_text(&self, query: &str) -> String {
+        format!("{}{query}", self.query_prefix)
+    }
+
+    /// The text to embed for stored memory content.
+    pub fn passage_text(&self, content: &str) -> String {
+        format!("{}{content}", self.passage_prefix)
+    }
+}
+
+impl EmbeddingConfig {
+    /// The text to embed for a search query.
+    pub fn query_text(&self, query: &str) -> String {
+        format!("{}{query}", self.query_prefix)
+    }
+
+    /// The text to embed for stored memory content.
+    pub fn passage_text(&self, content: &str) -> String {
+        format!("{}{content}", self.passage_prefix)
+    }
+}
+
+/// Time decay applied to memory search relevance.
+///
+/// A memory's score is halved for every `half_life_days` since it was last
+/// reinforced (saved, updated, or recalled), so recent memories outrank
+/// equally relevant old ones. Identity memories never decay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryDecayConfig {
+    /// Days for a memory's score to halve. 0 disables decay.
+    pub half_life_days: f64,
+    /// Lowest multiplier decay can reach, so very old memories still surface
+    /// when nothing newer matches.
+    pub min_weight: f64,
+}
+
+impl Default for MemoryDecayConfig {
+    fn default() -> Self {
+        Self {
+            half_life_days: 0.0,
+            min_weight: 0.1,
+        }
+    }
+}
+
+impl MemoryDecayConfig {
+    /// Score multiplier for a memory last reinforced `age` ago.
+    pub fn weight(&self, age: chrono::Duration) -> f64 {
+        if self.half_life_days <= 0.0 {
+            return 1.0;
+        }
+        let age_days = age.num_seconds().max(0) as f64 / 86_400.0;
+        0.5_f64
+            .powf(age_days / self.half_life_days)
+            .max(self.min_weight)
+    }
+}
+
+/// Redaction applied to memory content before it is embedded and stored.
+///
+/// Off by default. Only persisted memories are scrubbed; conversation history
+/// keeps the raw text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryScrubConfig {
+    pub enabled: bool,
+    /// Replace email addresses with `[REDACTED:email]`.
+    pub redact_emails: bool,
+    /// Replace phone numbers with `[REDACTED:phone]`.
+    pub redact_phone_numbers: bool,
+    /// Extra regex patterns replaced with `[REDACTED]`. Validated at load.
+    pub patterns: Vec<String>,
+}
+
+impl Default for MemoryScrubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            redact_emails: true,
+            redact_phone_numbers: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Per-turn caps on tool calls, to stop runaway tool loops.
+///
+/// When a cap trips, further tool calls in the turn are rejected with a
+/// result telling the model to stop and answer with what it has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolCallLimits {
+    /// Total tool calls allowed in one turn. 0 disables the cap.
+    pub max_tool_calls_per_turn: u32,
+    /// Calls to any single tool allowed in one turn. 0 disables the cap.
+    pub max_repeat_same_tool: u32,
+}
+
+/// Typing-speed pacing for replies.
+///
+/// When set, a finished plain-text reply is revealed as a stream at this
+/// rate on platforms that render streams. Generation isn't slowed; only the
+/// reveal is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamPacingConfig {
+    /// Maximum characters revealed per second. 0 disables pacing.
+    pub chars_per_second: u32,
+}
+
+/// Retry policy for workers that fail with a transient error.
+///
+/// Only failures that look transient (network errors, timeouts, upstream 5xx)
+/// are retried. Permission denials, cancellations, and other errors fail the
+/// worker straight away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerRetryConfig {
+    /// How many times to re-run a failed worker before reporting failure.
+    /// 0 disables worker retries.
+    pub max_retries: u32,
+    /// Delay before the first retry, in seconds. Doubles on each later retry.
+    pub backoff_secs: u64,
+    /// Keep the failed attempt's history and continue from it. When false the
+    /// worker starts the task over with a clean history.
+    pub preserve_context: bool,
+}
+
+impl Default for WorkerRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            backoff_secs: 10,
+            preserve_context: true,
+        }
+    }
+}
+
+impl WorkerRetryConfig {
+    /// Backoff before retry number `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+        std::time::Duration::from_secs(self.backoff_secs.saturating_mul(factor))
+    }
+}
+
+impl std::fmt::Debug for TranscriptionConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranscriptionConfig")
+            .field("enabled", &self.enabled)
+            .field("endpoint", &self.endpoint)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+/// Reaction triggers of every loaded agent, keyed by agent ID. Messaging
+/// adapters are shared between agents, so they consult this before fetching
+/// anything for a reaction. Entries are replaced on reload and removed with
+/// the agent.
+static AGENT_REACTION_TRIGGERS: LazyLock<RwLock<HashMap<String, ReactionTriggers>>> =
+    LazyLock::new(Default::default);
+
+/// Emoji reactions that trigger an agent action on the reacted-to message.
+///
+/// Keys may be written as unicode (`"📌"`) or short-codes (`"pushpin"`,
+/// `":pushpin:"`). They are normalized on load so a single entry matches
+/// Discord (unicode) and Slack (short-code) reactions alike. Values describe
+/// the action the agent should take.
+#[derive(Debug, Clone, Default)]
+pub struct ReactionTriggers {
+    intents: HashMap<String, String>,
+}
+
+impl ReactionTriggers {
+    pub fn new(triggers: HashMap<String, String>) -> Self {
+        let mut resolved = Self::default();
+        resolved.extend(triggers);
+        resolved
+    }
+
+    /// Add triggers, replacing existing intents for the same emoji.
+    pub fn extend(&mut self, triggers: impl IntoIterator<Item = (String, String)>) {
+        for (emoji, intent) in triggers {
+            let intent = intent.trim();
+            if intent.is_empty() {
+                continue;
+            }
+            self.intents
+                .insert(normalize_reaction_emoji(&emoji), intent.to_string());
+        }
+    }
+
+    /// The intent mapped to a reaction emoji, if any.
+    pub fn intent_for(&self, emoji: &str) -> Option<&str> {
+        self.intents
+            .get(&normalize_reaction_emoji(emoji))
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intents.is_empty()
+    }
+
+    /// Publish an agent's triggers so adapters can filter reactions early.
+    pub fn register(agent_id: &str, triggers: &ReactionTriggers) {
+        AGENT_REACTION_TRIGGERS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(agent_id.to_string(), triggers.clone());
+    }
+
+    /// Forget a removed agent's triggers.
+    pub fn unregister(agent_id: &str) {
+        AGENT_REACTION_TRIGGERS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(agent_id);
+    }
+
+    /// Whether any loaded agent has a trigger for this emoji. Reactions that
+    /// fail this check can be dropped before any API call is made for them.
+    pub fn any_agent_triggers(emoji: &str) -> bool {
+        let emoji = normalize_reaction_emoji(emoji);
+        AGENT_REACTION_TRIGGERS
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .values()
+            .any(|triggers| triggers.intents.contains_key(&emoji))
+    }
+}
+
+/// Canonical form of a reaction emoji: the unicode emoji when the input is a
+/// known emoji or short-code, otherwise the lowercased name without colons
+/// (custom workspace/guild emoji).
+pub fn normalize_reaction_emoji(emoji: &str) -> String {
+    let trimmed = emoji.trim();
+    let name = trimmed.trim_start_matches(':').trim_end_matches(':');
+    emojis::get(trimmed)
+        .or_else(|| emojis::get_by_shortcode(name))
+        .map(|emoji| emoji.as_str().to_string())
+        .unwrap_or_else(|| name.to_lowercase())
+}
+
+/// Named personas, each a system-prompt fragment injected into the channel
+/// prompt while active. Names are matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct Personas {
+    prompts: std::collections::BTreeMap<String, String>,
+}
+
+impl Personas {
+    pub fn new(personas: HashMap<String, String>) -> Self {
+        let mut resolved = Self::default();
+        resolved.extend(personas);
+        resolved
+    }
+
+    /// Add personas, replacing existing prompts for the same name.
+    pub fn extend(&mut self, personas: impl IntoIterator<Item = (String, String)>) {
+        for (name, prompt) in personas {
+            let name = normalize_persona_name(&name);
+            let prompt = prompt.trim();
+            if name.is_empty() || prompt.is_empty() {
+                continue;
+            }
+            self.prompts.insert(name, prompt.to_string());
+        }
+    }
+
+    /// The prompt fragment for a persona, if one is defined under that name.
+    pub fn prompt_for(&self, name: &str) -> Option<&str> {
+        self.prompts
+            .get(&normalize_persona_name(name))
+            .map(String::as_str)
+    }
+
+    /// Persona names and prompts, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.prompts
+            .iter()
+            .map(|(name, prompt)| (name.as_str(), prompt.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prompts.is_empty()
+    }
+}
+
+/// Prompt template files that replace bundled process templates, keyed by
+/// template name (`worker`, `compactor`, ...). Paths are resolved against the
+/// instance dir at load.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptOverrides {
+    templates: std::collections::BTreeMap<String, PathBuf>,
+}
+
+impl PromptOverrides {
+    /// Add overrides, replacing existing paths for the same template.
+    pub fn extend(&mut self, overrides: impl IntoIterator<Item = (String, PathBuf)>) {
+        self.templates.extend(overrides);
+    }
+
+    /// Template names and override paths, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.templates
+            .iter()
+            .map(|(name, path)| (name.as_str(), path.as_path()))
+    }
+
+    /// Whether `path` is one of the override files.
+    pub fn contains_path(&self, path: &Path) -> bool {
+        self.templates.values().any(|template| template == path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+/// Canonical form of a persona name: trimmed and lowercased.
+pub fn normalize_persona_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Worker tasks that `run_worker:<name>` buttons spawn when clicked. Names
+/// are matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct WorkerButtons {
+    tasks: std::collections::BTreeMap<String, String>,
+}
+
+impl WorkerButtons {
+    pub fn new(buttons: HashMap<String, String>) -> Self {
+        let mut resolved = Self::default();
+        resolved.extend(buttons);
+        resolved
+    }
+
+    /// Add buttons, replacing existing tasks for the same name.
+    pub fn extend(&mut self, buttons: impl IntoIterator<Item = (String, String)>) {
+        for (name, task) in buttons {
+            let name = name.trim().to_lowercase();
+            let task = task.trim();
+            if name.is_empty() || task.is_empty() {
+                continue;
+            }
+            self.tasks.insert(name, task.to_string());
+        }
+    }
+
+    /// The worker task configured for a button name, if any.
+    pub fn task_for(&self, name: &str) -> Option<&str> {
+        self.tasks
+            .get(&name.trim().to_lowercase())
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
 }
 
 /// OpenCode subprocess worker configuration.
@@ -931,6 +1951,12 @@ pub struct CortexConfig {
     pub knowledge_synthesis_max_words: usize,
     /// Debounce seconds after last memory change before regenerating knowledge synthesis.
     pub knowledge_synthesis_debounce_secs: u64,
+    /// Interval in seconds between memory consolidation passes. 0 disables them.
+    pub consolidation_interval_secs: u64,
+    /// Minimum number of associated memories before a cluster is consolidated.
+    pub consolidation_min_cluster_size: usize,
+    /// Memories with importance below this are never consolidated.
+    pub consolidation_min_importance: f32,
 }
 
 impl Default for CortexConfig {
@@ -956,6 +1982,9 @@ impl Default for CortexConfig {
             association_max_per_pass: 100,
             knowledge_synthesis_max_words: 500,
             knowledge_synthesis_debounce_secs: 60,
+            consolidation_interval_secs: 0,
+            consolidation_min_cluster_size: 4,
+            consolidation_min_importance: 0.6,
         }
     }
 }
@@ -984,6 +2013,17 @@ impl CortexConfig {
                 ConfigError::Invalid("maintenance_interval_secs must be >= 1".to_string()).into(),
             );
         }
+        validate_unit_interval_f32(
+            "consolidation_min_importance",
+            self.consolidation_min_importance,
+        )?;
+        if self.consolidation_min_cluster_size < 2 {
+            return Err(ConfigError::Invalid(format!(
+                "consolidation_min_cluster_size must be >= 2, got {}",
+                self.consolidation_min_cluster_size
+            ))
+            .into());
+        }
         Ok(())
     }
 }
@@ -1005,6 +2045,9 @@ pub struct WarmupConfig {
     pub enabled: bool,
     /// Force-load the embedding model before first recall/write workloads.
     pub eager_embedding_load: bool,
+    /// Send a tiny throwaway completion to every routed model on the startup
+    /// pass, so provider connections are open before the first message.
+    pub preload_models: bool,
     /// Interval in seconds between warmup refresh passes.
     pub refresh_secs: u64,
     /// Startup delay before the first warmup pass.
@@ -1016,12 +2059,66 @@ impl Default for WarmupConfig {
         Self {
             enabled: true,
             eager_embedding_load: true,
+            preload_models: false,
             refresh_secs: 900,
             startup_delay_secs: 5,
         }
     }
 }
 
+/// Retention policy for worker execution logs, enforced by a periodic
+/// cleanup task. Both limits are off by default, so logs are only deleted
+/// once `worker_log_retention_days` or `worker_log_max_total_mb` is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkerLogRetention {
+    /// Delete logs older than this many days. None keeps logs regardless of age.
+    pub max_age_days: Option<u64>,
+    /// Delete the oldest logs once their total size exceeds this many bytes.
+    /// None disables the cap.
+    pub max_total_bytes: Option<u64>,
+}
+
+impl WorkerLogRetention {
+    /// Whether any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_age_days.is_some() || self.max_total_bytes.is_some()
+    }
+}
+
+/// Size caps for tool results. Results over the cap are truncated before
+/// they reach the LLM, with a `[truncated N bytes]` marker appended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolResultLimits {
+    /// Cap in bytes for tools without a per-tool override.
+    pub max_bytes: usize,
+    /// Per-tool caps keyed by tool name; MCP tools use the namespaced name
+    /// (e.g. `github_search_code`).
+    pub per_tool: HashMap<String, usize>,
+    /// Write the untruncated result to `.spacebot/tool-results` in the
+    /// workspace so the worker can read it on demand.
+    pub save_full_results: bool,
+}
+
+impl ToolResultLimits {
+    /// Resolve the cap for a tool, falling back to the global default.
+    pub fn max_bytes_for(&self, tool_name: &str) -> usize {
+        self.per_tool
+            .get(tool_name)
+            .copied()
+            .unwrap_or(self.max_bytes)
+    }
+}
+
+impl Default for ToolResultLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: crate::tools::MAX_TOOL_OUTPUT_BYTES,
+            per_tool: HashMap::new(),
+            save_full_results: true,
+        }
+    }
+}
+
 /// Projects configuration — agent-level defaults for project workspace management.
 #[derive(Debug, Clone)]
 pub struct ProjectsConfig {
@@ -1179,9 +2276,14 @@ pub struct AgentConfig {
     pub routing: Option<RoutingConfig>,
     pub max_concurrent_branches: Option<usize>,
     pub max_concurrent_workers: Option<usize>,
+    pub max_concurrent_processes: Option<usize>,
     pub max_turns: Option<usize>,
     pub branch_max_turns: Option<usize>,
     pub context_window: Option<usize>,
+    pub system_prompt_token_budget: Option<usize>,
+    pub output_language: Option<String>,
+    pub cortex_language: Option<String>,
+    pub response_footer: Option<String>,
     pub compaction: Option<CompactionConfig>,
     pub memory_persistence: Option<MemoryPersistenceConfig>,
     pub coalesce: Option<CoalesceConfig>,
@@ -1190,6 +2292,25 @@ pub struct AgentConfig {
     pub warmup: Option<WarmupConfig>,
     pub browser: Option<BrowserConfig>,
     pub channel: Option<ChannelConfig>,
+    pub media_safety: Option<MediaSafetyConfig>,
+    pub task_extraction: Option<TaskExtractionConfig>,
+    pub transcription: Option<TranscriptionConfig>,
+    pub embedding: Option<EmbeddingConfig>,
+    pub worker_retry: Option<WorkerRetryConfig>,
+    pub memory_decay: Option<MemoryDecayConfig>,
+    pub memory_scrub: Option<MemoryScrubConfig>,
+    pub tool_call_limits: Option<ToolCallLimits>,
+    pub stream_pacing: Option<StreamPacingConfig>,
+    /// Per-agent reaction triggers, layered over the defaults.
+    pub reaction_triggers: Option<ReactionTriggers>,
+    /// Per-agent personas, layered over the defaults.
+    pub personas: Option<Personas>,
+    /// Per-agent prompt template overrides, layered over the defaults.
+    pub prompt_overrides: Option<PromptOverrides>,
+    /// Per-agent worker buttons, layered over the defaults.
+    pub worker_buttons: Option<WorkerButtons>,
+    /// Per-agent disabled native tools. None inherits from defaults.
+    pub disabled_tools: Option<Vec<String>>,
     pub mcp: Option<Vec<McpServerConfig>>,
     /// Per-agent Brave Search API key override. None inherits from defaults.
     pub brave_search_key: Option<String>,
@@ -1242,9 +2363,21 @@ pub struct ResolvedAgentConfig {
     pub routing: RoutingConfig,
     pub max_concurrent_branches: usize,
     pub max_concurrent_workers: usize,
+    pub max_concurrent_processes: usize,
     pub max_turns: usize,
     pub branch_max_turns: usize,
     pub context_window: usize,
+    pub system_prompt_token_budget: Option<usize>,
+    /// Language the agent always replies in (e.g. "Japanese"), regardless of
+    /// the language users write in. None leaves the reply language to the model.
+    pub output_language: Option<String>,
+    /// Language for cortex output (bulletin, knowledge synthesis, profile,
+    /// digests, consolidated memories). None falls back to `output_language`,
+    /// then English.
+    pub cortex_language: Option<String>,
+    /// Footer appended to every text reply (e.g. "— {{agent_name}} (AI)"),
+    /// for AI disclosure. `{{agent_name}}` expands to the display name.
+    pub response_footer: Option<String>,
     pub compaction: CompactionConfig,
     pub memory_persistence: MemoryPersistenceConfig,
     pub coalesce: CoalesceConfig,
@@ -1253,6 +2386,21 @@ pub struct ResolvedAgentConfig {
     pub warmup: WarmupConfig,
     pub browser: BrowserConfig,
     pub channel: ChannelConfig,
+    pub media_safety: MediaSafetyConfig,
+    pub task_extraction: TaskExtractionConfig,
+    pub transcription: TranscriptionConfig,
+    pub embedding: EmbeddingConfig,
+    pub worker_retry: WorkerRetryConfig,
+    pub memory_decay: MemoryDecayConfig,
+    pub memory_scrub: MemoryScrubConfig,
+    pub tool_call_limits: ToolCallLimits,
+    pub stream_pacing: StreamPacingConfig,
+    pub reaction_triggers: ReactionTriggers,
+    pub personas: Personas,
+    pub prompt_overrides: PromptOverrides,
+    pub worker_buttons: WorkerButtons,
+    /// Native tools omitted from this agent's tool servers.
+    pub disabled_tools: Vec<String>,
     pub mcp: Vec<McpServerConfig>,
     pub brave_search_key: Option<String>,
     pub cron_timezone: Option<String>,
@@ -1272,9 +2420,14 @@ impl Default for DefaultsConfig {
             routing: RoutingConfig::default(),
             max_concurrent_branches: 5,
             max_concurrent_workers: 5,
+            max_concurrent_processes: 0,
             max_turns: 5,
             branch_max_turns: 50,
             context_window: 128_000,
+            system_prompt_token_budget: None,
+            output_language: None,
+            cortex_language: None,
+            response_footer: None,
             compaction: CompactionConfig::default(),
             memory_persistence: MemoryPersistenceConfig::default(),
             coalesce: CoalesceConfig::default(),
@@ -1283,6 +2436,20 @@ impl Default for DefaultsConfig {
             warmup: WarmupConfig::default(),
             browser: BrowserConfig::default(),
             channel: ChannelConfig::default(),
+            media_safety: MediaSafetyConfig::default(),
+            task_extraction: TaskExtractionConfig::default(),
+            transcription: TranscriptionConfig::default(),
+            embedding: EmbeddingConfig::default(),
+            worker_retry: WorkerRetryConfig::default(),
+            memory_decay: MemoryDecayConfig::default(),
+            memory_scrub: MemoryScrubConfig::default(),
+            tool_call_limits: ToolCallLimits::default(),
+            stream_pacing: StreamPacingConfig::default(),
+            reaction_triggers: ReactionTriggers::default(),
+            personas: Personas::default(),
+            prompt_overrides: PromptOverrides::default(),
+            worker_buttons: WorkerButtons::default(),
+            disabled_tools: Vec::new(),
             mcp: Vec::new(),
             brave_search_key: None,
             cron_timezone: None,
@@ -1291,6 +2458,8 @@ impl Default for DefaultsConfig {
             cron: Vec::new(),
             opencode: OpenCodeConfig::default(),
             worker_log_mode: crate::settings::WorkerLogMode::default(),
+            worker_log_retention: WorkerLogRetention::default(),
+            tool_results: ToolResultLimits::default(),
             projects: ProjectsConfig::default(),
         }
     }
@@ -1335,23 +2504,100 @@ impl AgentConfig {
             max_concurrent_workers: self
                 .max_concurrent_workers
                 .unwrap_or(defaults.max_concurrent_workers),
+            max_concurrent_processes: self
+                .max_concurrent_processes
+                .unwrap_or(defaults.max_concurrent_processes),
             max_turns: self.max_turns.unwrap_or(defaults.max_turns),
             branch_max_turns: self.branch_max_turns.unwrap_or(defaults.branch_max_turns),
             context_window: self.context_window.unwrap_or(defaults.context_window),
+            system_prompt_token_budget: self
+                .system_prompt_token_budget
+                .or(defaults.system_prompt_token_budget),
+            output_language: self
+                .output_language
+                .clone()
+                .or_else(|| defaults.output_language.clone()),
+            cortex_language: self
+                .cortex_language
+                .clone()
+                .or_else(|| defaults.cortex_language.clone()),
+            response_footer: self
+                .response_footer
+                .clone()
+                .or_else(|| defaults.response_footer.clone()),
             compaction: self.compaction.unwrap_or(defaults.compaction),
             memory_persistence: self
                 .memory_persistence
                 .unwrap_or(defaults.memory_persistence),
             coalesce: self.coalesce.unwrap_or(defaults.coalesce),
-            ingestion: self.ingestion.unwrap_or(defaults.ingestion),
+            ingestion: self
+                .ingestion
+                .clone()
+                .unwrap_or_else(|| defaults.ingestion.clone()),
             cortex: self.cortex.unwrap_or(defaults.cortex),
             warmup: self.warmup.unwrap_or(defaults.warmup),
             browser: self
                 .browser
                 .clone()
                 .unwrap_or_else(|| defaults.browser.clone()),
-            channel: self.channel.unwrap_or(defaults.channel),
-            mcp: resolve_mcp_configs(&defaults.mcp, self.mcp.as_deref()),
+            channel: self
+                .channel
+                .clone()
+                .unwrap_or_else(|| defaults.channel.clone()),
+            media_safety: self
+                .media_safety
+                .clone()
+                .unwrap_or_else(|| defaults.media_safety.clone()),
+            task_extraction: self.task_extraction.unwrap_or(defaults.task_extraction),
+            transcription: self
+                .transcription
+                .clone()
+                .unwrap_or_else(|| defaults.transcription.clone()),
+            embedding: self
+                .embedding
+                .clone()
+                .unwrap_or_else(|| defaults.embedding.clone()),
+            worker_retry: self.worker_retry.unwrap_or(defaults.worker_retry),
+            memory_decay: self.memory_decay.unwrap_or(defaults.memory_decay),
+            memory_scrub: self
+                .memory_scrub
+                .clone()
+                .unwrap_or_else(|| defaults.memory_scrub.clone()),
+            tool_call_limits: self.tool_call_limits.unwrap_or(defaults.tool_call_limits),
+            stream_pacing: self.stream_pacing.unwrap_or(defaults.stream_pacing),
+            reaction_triggers: {
+                let mut triggers = defaults.reaction_triggers.clone();
+                if let Some(overrides) = &self.reaction_triggers {
+                    triggers.extend(overrides.intents.clone());
+                }
+                triggers
+            },
+            personas: {
+                let mut personas = defaults.personas.clone();
+                if let Some(overrides) = &self.personas {
+                    personas.extend(overrides.prompts.clone());
+                }
+                personas
+            },
+            prompt_overrides: {
+                let mut prompt_overrides = defaults.prompt_overrides.clone();
+                if let Some(overrides) = &self.prompt_overrides {
+                    prompt_overrides.extend(overrides.templates.clone());
+                }
+                prompt_overrides
+            },
+            worker_buttons: {
+                let mut buttons = defaults.worker_buttons.clone();
+                if let Some(overrides) = &self.worker_buttons {
+                    buttons.extend(overrides.tasks.clone());
+                }
+                buttons
+            },
+            disabled_tools: self
+                .disabled_tools
+                .clone()
+                .unwrap_or_else(|| defaults.disabled_tools.clone()),
+            mcp: resolve_mcp_configs(&self.id, &defaults.mcp, self.mcp.as_deref()),
             brave_search_key: self
                 .brave_search_key
                 .clone()
@@ -1492,10 +2738,15 @@ fn resolve_user_timezone(
 }
 
 fn resolve_mcp_configs(
+    agent_id: &str,
     default_configs: &[McpServerConfig],
     agent_configs: Option<&[McpServerConfig]>,
 ) -> Vec<McpServerConfig> {
-    let mut merged = default_configs.to_vec();
+    let mut merged: Vec<McpServerConfig> = default_configs
+        .iter()
+        .filter(|config| config.agents.is_empty() || config.agents.iter().any(|id| id == agent_id))
+        .cloned()
+        .collect();
 
     if let Some(agent_configs) = agent_configs {
         for agent_config in agent_configs {
@@ -1540,9 +2791,74 @@ pub struct Binding {
     /// Channel IDs this binding applies to. If empty, all channels in the guild/workspace are allowed.
     pub channel_ids: Vec<String>,
     /// Require explicit @mention (or reply-to-bot) for inbound messages.
+    /// Falls back to the platform's `require_mention_default` when unset.
     pub require_mention: bool,
     /// User IDs allowed to DM the bot through this binding.
     pub dm_allowed_users: Vec<String>,
+    /// Sent once when a new conversation starts on this binding, before the
+    /// first turn. `{agent_name}` is replaced with the agent's display name.
+    pub welcome_message: Option<String>,
+    /// Time-of-day overrides of `agent_id`. `None` always routes to `agent_id`.
+    pub schedule: Option<BindingSchedule>,
+}
+
+/// Time-of-day routing for a binding: during a window, messages go to the
+/// window's agent instead of the binding's own `agent_id`.
+#[derive(Debug, Clone)]
+pub struct BindingSchedule {
+    /// Timezone the windows are read in. `None` uses the host's local time.
+    pub timezone: Option<Tz>,
+    /// Checked in order; the first window containing the current time wins.
+    pub windows: Vec<BindingTimeWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingTimeWindow {
+    pub agent_id: String,
+    /// Minutes after local midnight. The window covers `[start, end)` and
+    /// wraps past midnight when `end` is earlier than `start`. Equal values
+    /// cover the whole day.
+    pub start_minute: u16,
+    pub end_minute: u16,
+    /// Local weekdays the window applies on, judged by the current day.
+    /// Empty means every day.
+    pub days: Vec<chrono::Weekday>,
+}
+
+impl BindingTimeWindow {
+    fn contains(&self, weekday: chrono::Weekday, minute: u16) -> bool {
+        if !self.days.is_empty() && !self.days.contains(&weekday) {
+            return false;
+        }
+        match self.start_minute.cmp(&self.end_minute) {
+            std::cmp::Ordering::Equal => true,
+            std::cmp::Ordering::Less => minute >= self.start_minute && minute < self.end_minute,
+            std::cmp::Ordering::Greater => minute >= self.start_minute || minute < self.end_minute,
+        }
+    }
+}
+
+impl BindingSchedule {
+    /// Agent of the first window containing `now`, if any.
+    fn agent_id_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<&str> {
+        use chrono::{Datelike, Timelike};
+
+        let (weekday, hour, minute) = match self.timezone {
+            Some(timezone) => {
+                let local = now.with_timezone(&timezone);
+                (local.weekday(), local.hour(), local.minute())
+            }
+            None => {
+                let local = now.with_timezone(&chrono::Local);
+                (local.weekday(), local.hour(), local.minute())
+            }
+        };
+        let minute_of_day = (hour * 60 + minute) as u16;
+        self.windows
+            .iter()
+            .find(|window| window.contains(weekday, minute_of_day))
+            .map(|window| window.agent_id.as_str())
+    }
 }
 
 impl Binding {
@@ -1556,6 +2872,26 @@ impl Binding {
         self.adapter.is_none()
     }
 
+    /// Agent this binding routes to at `now`, after any schedule window.
+    pub fn agent_id_at(&self, now: chrono::DateTime<chrono::Utc>) -> &str {
+        self.schedule
+            .as_ref()
+            .and_then(|schedule| schedule.agent_id_at(now))
+            .unwrap_or(&self.agent_id)
+    }
+
+    /// Whether any message on this binding can route to `agent_id`, at any
+    /// time of day.
+    pub fn routes_to(&self, agent_id: &str) -> bool {
+        self.agent_id == agent_id
+            || self.schedule.as_ref().is_some_and(|schedule| {
+                schedule
+                    .windows
+                    .iter()
+                    .any(|window| window.agent_id == agent_id)
+            })
+    }
+
     /// Check if this binding matches on routing criteria (platform, guild,
     /// channel IDs, adapter, etc.) — everything *except* `require_mention`.
     fn matches_route(&self, message: &crate::InboundMessage) -> bool {
@@ -1631,12 +2967,22 @@ impl Binding {
                 .get("mattermost_channel_id")
                 .and_then(|v| v.as_str());
 
+            // Telegram channel IDs name forum topics
+            let telegram_topic = message
+                .metadata
+                .get("telegram_topic_id")
+                .and_then(|v| v.as_i64())
+                .map(|v| v.to_string());
+
             let direct_match = message_channel
                 .as_ref()
                 .is_some_and(|id| self.channel_ids.contains(id))
                 || slack_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
                 || twitch_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
-                || mattermost_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()));
+                || mattermost_channel.is_some_and(|id| self.channel_ids.contains(&id.to_string()))
+                || telegram_topic
+                    .as_ref()
+                    .is_some_and(|id| self.channel_ids.contains(id));
             let parent_match = parent_channel
                 .as_ref()
                 .is_some_and(|id| self.channel_ids.contains(id));
@@ -2102,6 +3448,16 @@ fn validate_runtime_keys(
     Ok(())
 }
 
+/// The binding that routes an inbound message, ignoring `require_mention`.
+pub fn binding_for_message<'a>(
+    bindings: &'a [Binding],
+    message: &crate::InboundMessage,
+) -> Option<&'a Binding> {
+    bindings
+        .iter()
+        .find(|binding| binding.matches_route(message))
+}
+
 /// Resolve which agent should handle an inbound message.
 ///
 /// Checks bindings in order. First routing match wins. Falls back to the
@@ -2113,11 +3469,21 @@ pub fn resolve_agent_for_message(
     bindings: &[Binding],
     message: &crate::InboundMessage,
     default_agent_id: &str,
+) -> Option<crate::AgentId> {
+    resolve_agent_for_message_at(bindings, message, default_agent_id, chrono::Utc::now())
+}
+
+/// [`resolve_agent_for_message`] with binding schedules evaluated at `now`.
+pub fn resolve_agent_for_message_at(
+    bindings: &[Binding],
+    message: &crate::InboundMessage,
+    default_agent_id: &str,
+    now: chrono::DateTime<chrono::Utc>,
 ) -> Option<crate::AgentId> {
     for binding in bindings {
         if binding.matches_route(message) {
             if binding.passes_require_mention(message) {
-                return Some(std::sync::Arc::from(binding.agent_id.as_str()));
+                return Some(std::sync::Arc::from(binding.agent_id_at(now)));
             }
             // Binding owns this message but require_mention blocked it.
             // Drop instead of falling through to the default agent.
@@ -2149,6 +3515,36 @@ pub struct MessagingConfig {
     pub mattermost: Option<MattermostConfig>,
 }
 
+impl MessagingConfig {
+    /// The `require_mention` a binding on `platform` gets when it doesn't set
+    /// one. False for platforms without a `require_mention_default` setting.
+    pub fn require_mention_default(&self, platform: &str) -> bool {
+        match platform {
+            "discord" => self
+                .discord
+                .as_ref()
+                .is_some_and(|config| config.require_mention_default),
+            "slack" => self
+                .slack
+                .as_ref()
+                .is_some_and(|config| config.require_mention_default),
+            "telegram" => self
+                .telegram
+                .as_ref()
+                .is_some_and(|config| config.require_mention_default),
+            "twitch" => self
+                .twitch
+                .as_ref()
+                .is_some_and(|config| config.require_mention_default),
+            "mattermost" => self
+                .mattermost
+                .as_ref()
+                .is_some_and(|config| config.require_mention_default),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct DiscordConfig {
     pub enabled: bool,
@@ -2159,6 +3555,26 @@ pub struct DiscordConfig {
     pub dm_allowed_users: Vec<String>,
     /// Whether to process messages from other bots (self-messages are always ignored).
     pub allow_bot_messages: bool,
+    /// `require_mention` for bindings on this platform that don't set it.
+    pub require_mention_default: bool,
+    /// Stream replies into a placeholder message that is edited as text
+    /// arrives. Off by default: streamed replies are sent once complete.
+    /// Named instances follow this setting.
+    pub stream_edits: bool,
+    /// Minimum gap between edits of a streamed reply, in milliseconds.
+    pub stream_edit_interval_ms: u64,
+}
+
+/// Default gap between Discord stream edits. Discord allows roughly five
+/// edits per five seconds per channel.
+pub const DEFAULT_DISCORD_STREAM_EDIT_INTERVAL_MS: u64 = 1000;
+
+impl DiscordConfig {
+    /// Edit interval for streamed replies, or None when stream edits are off.
+    pub fn stream_edit_interval(&self) -> Option<std::time::Duration> {
+        self.stream_edits
+            .then(|| std::time::Duration::from_millis(self.stream_edit_interval_ms))
+    }
 }
 
 #[derive(Clone)]
@@ -2192,6 +3608,9 @@ impl std::fmt::Debug for DiscordConfig {
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
             .field("allow_bot_messages", &self.allow_bot_messages)
+            .field("require_mention_default", &self.require_mention_default)
+            .field("stream_edits", &self.stream_edits)
+            .field("stream_edit_interval_ms", &self.stream_edit_interval_ms)
             .finish()
     }
 }
@@ -2242,6 +3661,8 @@ pub struct SlackConfig {
     pub dm_allowed_users: Vec<String>,
     /// Slash command definitions. If empty, all slash commands are ignored.
     pub commands: Vec<SlackCommandConfig>,
+    /// `require_mention` for bindings on this platform that don't set it.
+    pub require_mention_default: bool,
 }
 
 #[derive(Clone)]
@@ -2278,6 +3699,7 @@ impl std::fmt::Debug for SlackConfig {
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
             .field("commands", &self.commands)
+            .field("require_mention_default", &self.require_mention_default)
             .finish()
     }
 }
@@ -2321,6 +3743,8 @@ pub struct TelegramConfig {
     pub instances: Vec<TelegramInstanceConfig>,
     /// User IDs allowed to DM the bot. If empty, DMs are ignored entirely.
     pub dm_allowed_users: Vec<String>,
+    /// `require_mention` for bindings on this platform that don't set it.
+    pub require_mention_default: bool,
 }
 
 #[derive(Clone)]
@@ -2350,6 +3774,7 @@ impl std::fmt::Debug for TelegramConfig {
             .field("token", &"[REDACTED]")
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
+            .field("require_mention_default", &self.require_mention_default)
             .finish()
     }
 }
@@ -2534,6 +3959,8 @@ pub struct TwitchConfig {
     pub channels: Vec<String>,
     /// Optional prefix that triggers the bot (e.g. "!ask"). If empty, all messages are processed.
     pub trigger_prefix: Option<String>,
+    /// `require_mention` for bindings on this platform that don't set it.
+    pub require_mention_default: bool,
 }
 
 #[derive(Clone)]
@@ -2582,6 +4009,7 @@ impl std::fmt::Debug for TwitchConfig {
             .field("instances", &self.instances)
             .field("channels", &self.channels)
             .field("trigger_prefix", &self.trigger_prefix)
+            .field("require_mention_default", &self.require_mention_default)
             .finish()
     }
 }
@@ -2633,12 +4061,36 @@ impl SystemSecrets for TwitchConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WebhookConfig {
     pub enabled: bool,
     pub port: u16,
     pub bind: String,
     pub auth_token: Option<String>,
+    /// Shared secret for HMAC-SHA256 payload signatures. When set, signed
+    /// requests to `/send` are verified against `signature_header`.
+    pub signing_secret: Option<String>,
+    /// Header carrying the payload signature.
+    pub signature_header: String,
+}
+
+impl std::fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("enabled", &self.enabled)
+            .field("port", &self.port)
+            .field("bind", &self.bind)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field(
+                "signing_secret",
+                &self.signing_secret.as_ref().map(|_| "[REDACTED]"),
+            )
+            .field("signature_header", &self.signature_header)
+            .finish()
+    }
 }
 
 /// Signal messaging via signal-cli JSON-RPC daemon.
@@ -2749,6 +4201,8 @@ pub struct MattermostConfig {
     pub instances: Vec<MattermostInstanceConfig>,
     pub dm_allowed_users: Vec<String>,
     pub max_attachment_bytes: usize,
+    /// `require_mention` for bindings on this platform that don't set it.
+    pub require_mention_default: bool,
 }
 
 impl std::fmt::Debug for MattermostConfig {
@@ -2761,6 +4215,7 @@ impl std::fmt::Debug for MattermostConfig {
             .field("instances", &self.instances)
             .field("dm_allowed_users", &self.dm_allowed_users)
             .field("max_attachment_bytes", &self.max_attachment_bytes)
+            .field("require_mention_default", &self.require_mention_default)
             .finish()
     }
 }
@@ -1,9 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use super::{
-    Binding, Config, DiscordPermissions, MattermostPermissions, RuntimeConfig, SignalPermissions,
-    SlackPermissions, TelegramPermissions, TwitchPermissions, binding_runtime_adapter_key,
+    Binding, Config, DiscordPermissions, MattermostPermissions, PAUSE_FILE_NAME, RuntimeConfig,
+    SignalPermissions, SlackPermissions, TelegramPermissions, TwitchPermissions,
+    binding_runtime_adapter_key,
 };
 
 /// Per-agent context needed by the file watcher: (id, prompt_dir, identity_dir,
@@ -16,8 +17,43 @@ type WatchedAgent = (
     Arc<crate::mcp::McpManager>,
 );
 
+/// Hash of config.toml and its environment overlay, to skip no-op reloads.
+fn config_hash(config_path: &Path, overlay_path: Option<&Path>) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for path in std::iter::once(config_path).chain(overlay_path) {
+        std::fs::read(path).ok().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Watch the directories holding each agent's prompt override files. Watching
+/// the parent directory rather than the file survives editors that replace
+/// the file on save.
+fn watch_prompt_override_dirs(
+    watcher: &mut notify::RecommendedWatcher,
+    agents: &[WatchedAgent],
+    watched: &mut std::collections::HashSet<PathBuf>,
+) {
+    use notify::{RecursiveMode, Watcher};
+
+    for (_, _, _, runtime_config, _) in agents {
+        for (_, path) in runtime_config.prompt_overrides.load().iter() {
+            let Some(dir) = path.parent() else {
+                continue;
+            };
+            if watched.insert(dir.to_path_buf())
+                && let Err(error) = watcher.watch(dir, RecursiveMode::NonRecursive)
+            {
+                tracing::warn!(%error, path = %dir.display(), "failed to watch prompt override dir");
+            }
+        }
+    }
+}
+
 /// Watches config, prompt, identity, and skill files for changes and triggers
-/// hot reload on the corresponding RuntimeConfig.
+/// hot reload on the corresponding RuntimeConfig. Also tracks the `PAUSE`
+/// kill switch files in the instance dir and each agent root.
 ///
 /// Returns a JoinHandle that runs until dropped. File events are debounced
 /// to 2 seconds so rapid edits (e.g. :w in vim hitting multiple writes) are
@@ -72,11 +108,20 @@ pub fn spawn_file_watcher(
             }
         };
 
+        // The SPACEBOT_ENV overlay lives next to config.toml, in the instance
+        // root watched below, and reloads the same way.
+        let overlay_path = super::overlay::overlay_path(&config_path);
+
         // Watch config.toml
         if let Err(error) = watcher.watch(&config_path, RecursiveMode::NonRecursive) {
             tracing::warn!(%error, path = %config_path.display(), "failed to watch config file");
         }
 
+        // Watch the instance root for the instance-wide PAUSE file
+        if let Err(error) = watcher.watch(&instance_dir, RecursiveMode::NonRecursive) {
+            tracing::warn!(%error, path = %instance_dir.display(), "failed to watch instance dir");
+        }
+
         // Watch instance-level skills directory
         let instance_skills_dir = instance_dir.join("skills");
         if instance_skills_dir.is_dir()
@@ -103,17 +148,19 @@ pub fn spawn_file_watcher(
             }
         }
 
+        // Watch the directories holding prompt override files
+        let mut watched_prompt_dirs = std::collections::HashSet::new();
+        watch_prompt_override_dirs(&mut watcher, &agents, &mut watched_prompt_dirs);
+
         tracing::info!("file watcher started");
 
+        // Pick up PAUSE files that existed before startup
+        for (agent_id, _, _, runtime_config, _) in &agents {
+            runtime_config.refresh_pause_state(agent_id);
+        }
+
         // Track config.toml content hash to skip no-op reloads
-        let mut last_config_hash: u64 = std::fs::read(&config_path)
-            .map(|bytes| {
-                use std::hash::{Hash, Hasher};
-                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                bytes.hash(&mut hasher);
-                hasher.finish()
-            })
-            .unwrap_or(0);
+        let mut last_config_hash = config_hash(&config_path, overlay_path.as_deref());
 
         // Debounce loop: collect events for 2 seconds, then reload
         let debounce = Duration::from_secs(2);
@@ -126,7 +173,12 @@ pub fn spawn_file_watcher(
             }
 
             // Categorize what changed
-            let mut config_changed = changed_paths.iter().any(|p| p.ends_with("config.toml"));
+            let mut config_changed = changed_paths.iter().any(|p| {
+                p.ends_with("config.toml")
+                    || overlay_path
+                        .as_ref()
+                        .is_some_and(|overlay| p.file_name() == overlay.file_name())
+            });
             let identity_changed = changed_paths.iter().any(|p| {
                 let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
                 matches!(name, "SOUL.md" | "IDENTITY.md" | "ROLE.md")
@@ -134,26 +186,33 @@ pub fn spawn_file_watcher(
             let skills_changed = changed_paths
                 .iter()
                 .any(|p| p.to_string_lossy().contains("skills"));
+            let prompts_changed = changed_paths.iter().any(|path| {
+                agents.iter().any(|(_, _, _, runtime_config, _)| {
+                    runtime_config.prompt_overrides.load().contains_path(path)
+                })
+            });
+            let pause_changed = changed_paths
+                .iter()
+                .any(|p| p.file_name().is_some_and(|name| name == PAUSE_FILE_NAME));
+
+            if pause_changed {
+                for (agent_id, _, _, runtime_config, _) in &agents {
+                    runtime_config.refresh_pause_state(agent_id);
+                }
+            }
 
             // Skip entirely if nothing relevant changed
-            if !config_changed && !identity_changed && !skills_changed {
+            if !config_changed && !identity_changed && !skills_changed && !prompts_changed {
                 continue;
             }
 
             // Skip config reload if file content hasn't actually changed
             if config_changed {
-                let current_hash: u64 = std::fs::read(&config_path)
-                    .map(|bytes| {
-                        use std::hash::{Hash, Hasher};
-                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                        bytes.hash(&mut hasher);
-                        hasher.finish()
-                    })
-                    .unwrap_or(0);
+                let current_hash = config_hash(&config_path, overlay_path.as_deref());
                 if current_hash == last_config_hash {
                     config_changed = false;
                     // If config was the only thing that "changed", skip entirely
-                    if !identity_changed && !skills_changed {
+                    if !identity_changed && !skills_changed && !prompts_changed {
                         continue;
                     }
                 } else {
@@ -165,6 +224,7 @@ pub fn spawn_file_watcher(
                 config_changed.then_some("config"),
                 identity_changed.then_some("identity"),
                 skills_changed.then_some("skills"),
+                prompts_changed.then_some("prompts"),
             ]
             .into_iter()
             .flatten()
@@ -288,7 +348,8 @@ pub fn spawn_file_watcher(
                                         "discord",
                                         &discord_config.token,
                                         permissions,
-                                    );
+                                    )
+                                    .with_stream_edits(discord_config.stream_edit_interval());
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, "failed to hot-start discord adapter from config change");
                                     }
@@ -316,7 +377,8 @@ pub fn spawn_file_watcher(
                                         runtime_key,
                                         &instance.token,
                                         permissions,
-                                    );
+                                    )
+                                    .with_stream_edits(discord_config.stream_edit_interval());
                                     if let Err(error) = manager.register_and_start(adapter).await {
                                         tracing::error!(%error, adapter = %instance.name, "failed to hot-start named discord adapter from config change");
                                     }
@@ -698,6 +760,21 @@ pub fn spawn_file_watcher(
                     ));
                     runtime_config.reload_skills(skills);
                 }
+
+                if prompts_changed {
+                    let overrides = runtime_config.prompt_overrides.load();
+                    if changed_paths
+                        .iter()
+                        .any(|path| overrides.contains_path(path))
+                    {
+                        runtime_config.reload_prompts();
+                    }
+                }
+            }
+
+            // A reloaded config may point at override files in new directories.
+            if new_config.is_some() {
+                watch_prompt_override_dirs(&mut watcher, &agents, &mut watched_prompt_dirs);
             }
         }
 
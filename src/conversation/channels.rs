@@ -295,6 +295,7 @@ fn extract_platform_meta(
                 "discord_channel_id",
                 "discord_is_thread",
                 "discord_parent_channel_id",
+                "discord_is_forum_post",
             ] {
                 if let Some(value) = metadata.get(key) {
                     meta.insert(key.to_string(), value.clone());
@@ -309,7 +310,11 @@ fn extract_platform_meta(
             }
         }
         "telegram" => {
-            for key in ["telegram_chat_id", "telegram_chat_type"] {
+            for key in [
+                "telegram_chat_id",
+                "telegram_chat_type",
+                "telegram_topic_id",
+            ] {
                 if let Some(value) = metadata.get(key) {
                     meta.insert(key.to_string(), value.clone());
                 }
@@ -6,6 +6,10 @@ use serde::Serialize;
 use sqlx::{Row as _, SqlitePool};
 use std::collections::HashMap;
 
+/// Content of the system message that marks a conversation reset. Backfill
+/// only reads messages logged after the latest marker.
+pub const CONVERSATION_RESET_MARKER: &str = "[conversation reset]";
+
 /// Persists conversation messages (user and assistant) to SQLite.
 ///
 /// All write methods are fire-and-forget — they spawn a tokio task and return
@@ -28,6 +32,11 @@ pub struct ConversationMessage {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Whether `message` is the boundary logged by [`ConversationLogger::log_reset`].
+pub fn is_reset_marker(message: &ConversationMessage) -> bool {
+    message.role == "system" && message.content == CONVERSATION_RESET_MARKER
+}
+
 impl ConversationLogger {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
@@ -101,6 +110,11 @@ impl ConversationLogger {
         });
     }
 
+    /// Record a conversation reset boundary. Fire-and-forget.
+    pub fn log_reset(&self, channel_id: &str) {
+        self.log_system_message(channel_id, CONVERSATION_RESET_MARKER);
+    }
+
     /// Log a bot (assistant) message with an agent display name. Fire-and-forget.
     pub fn log_bot_message_with_name(
         &self,
@@ -172,6 +186,35 @@ impl ConversationLogger {
         Ok(messages)
     }
 
+    /// Load recent messages for a channel that were logged after its latest
+    /// reset (oldest first).
+    pub async fn load_recent_since_reset(
+        &self,
+        channel_id: &ChannelId,
+        limit: i64,
+    ) -> crate::error::Result<Vec<ConversationMessage>> {
+        let mut messages = self.load_recent(channel_id, limit).await?;
+        let boundary = messages.iter().rposition(is_reset_marker);
+        if let Some(boundary) = boundary {
+            messages.drain(..=boundary);
+        }
+        Ok(messages)
+    }
+
+    /// Delete every message logged before `cutoff`, across all channels.
+    /// Returns how many were removed.
+    pub async fn prune_older_than(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> crate::error::Result<u64> {
+        let result = sqlx::query("DELETE FROM conversation_messages WHERE created_at < ?")
+            .bind(cutoff.format("%Y-%m-%d %H:%M:%S").to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(result.rows_affected())
+    }
+
     /// Load messages from any channel (not just the current one).
     ///
     /// Supports optional temporal filtering via `before` and `after` (RFC 3339 strings)
@@ -241,6 +284,83 @@ impl ConversationLogger {
         }
         Ok(messages)
     }
+
+    /// Full-text search across every channel's transcript, best matches first.
+    ///
+    /// Each whitespace-separated term in `query` must appear in the message;
+    /// FTS5 operators are not interpreted. Returns an empty list when the
+    /// query has no terms.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> crate::error::Result<Vec<ConversationSearchHit>> {
+        let Some(match_expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+
+        let rows = sqlx::query(
+            "SELECT m.id, m.channel_id, c.display_name AS channel_name, m.role, m.sender_name, \
+                    m.created_at, \
+                    snippet(conversation_messages_fts, 0, '**', '**', '…', 24) AS snippet \
+             FROM conversation_messages_fts \
+             JOIN conversation_messages m ON m.rowid = conversation_messages_fts.rowid \
+             LEFT JOIN channels c ON c.id = m.channel_id \
+             WHERE conversation_messages_fts MATCH ? \
+             ORDER BY conversation_messages_fts.rank, m.created_at DESC \
+             LIMIT ? OFFSET ?",
+        )
+        .bind(&match_expression)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ConversationSearchHit {
+                id: row.try_get("id").unwrap_or_default(),
+                channel_id: row.try_get("channel_id").unwrap_or_default(),
+                channel_name: row.try_get("channel_name").ok().flatten(),
+                role: row.try_get("role").unwrap_or_default(),
+                sender_name: row.try_get("sender_name").ok().flatten(),
+                created_at: row
+                    .try_get::<chrono::DateTime<chrono::Utc>, _>("created_at")
+                    .map(|created_at| created_at.to_rfc3339())
+                    .unwrap_or_default(),
+                snippet: row.try_get("snippet").unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+/// A conversation message matched by [`ConversationLogger::search`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationSearchHit {
+    pub id: String,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub role: String,
+    pub sender_name: Option<String>,
+    pub created_at: String,
+    /// Excerpt around the match, with matched terms wrapped in `**`.
+    pub snippet: String,
+}
+
+/// Quote each term so user input is matched literally instead of being parsed
+/// as FTS5 query syntax (`AND`, `NEAR`, `col:`, unbalanced quotes).
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
 }
 
 /// A unified timeline item combining messages, branch runs, and worker runs.
@@ -1010,7 +1130,7 @@ pub struct WorkerDetailRow {
 
 #[cfg(test)]
 mod tests {
-    use super::ProcessRunLogger;
+    use super::{ConversationLogger, ProcessRunLogger, fts_match_expression};
 
     async fn setup_worker_runs_table() -> sqlx::SqlitePool {
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
@@ -1093,4 +1213,169 @@ mod tests {
         let status: String = sqlx::Row::try_get(&row, "status").expect("missing status");
         assert_eq!(status, "running");
     }
+
+    #[test]
+    fn fts_match_expression_quotes_terms() {
+        assert_eq!(fts_match_expression("   "), None);
+        assert_eq!(
+            fts_match_expression("deploy NEAR \"prod"),
+            Some("\"deploy\" \"NEAR\" \"\"\"prod\"".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn search_finds_messages_across_channels() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("failed to run migrations");
+
+        for (id, channel_id, content) in [
+            (
+                "m1",
+                "discord:1",
+                "We should migrate the database on Friday",
+            ),
+            ("m2", "slack:2", "Friday works, migrating the database then"),
+            ("m3", "slack:2", "Lunch plans?"),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content) \
+                 VALUES (?, ?, 'user', ?)",
+            )
+            .bind(id)
+            .bind(channel_id)
+            .bind(content)
+            .execute(&pool)
+            .await
+            .expect("failed to insert message");
+        }
+
+        let logger = ConversationLogger::new(pool.clone());
+        let hits = logger
+            .search("database friday", 10, 0)
+            .await
+            .expect("search should succeed");
+        let mut channels: Vec<&str> = hits.iter().map(|hit| hit.channel_id.as_str()).collect();
+        channels.sort();
+        assert_eq!(channels, vec!["discord:1", "slack:2"]);
+        assert!(hits.iter().all(|hit| hit.snippet.contains("**")));
+
+        let page = logger
+            .search("database", 1, 1)
+            .await
+            .expect("search should succeed");
+        assert_eq!(page.len(), 1);
+
+        sqlx::query("DELETE FROM conversation_messages WHERE channel_id = 'discord:1'")
+            .execute(&pool)
+            .await
+            .expect("failed to delete messages");
+        let hits = logger
+            .search("database", 10, 0)
+            .await
+            .expect("search should succeed");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].channel_id, "slack:2");
+    }
+
+    #[tokio::test]
+    async fn load_recent_since_reset_skips_messages_before_the_boundary() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("failed to run migrations");
+
+        for (id, role, content, created_at) in [
+            ("m1", "user", "before", "2026-01-01 00:00:01"),
+            (
+                "m2",
+                "system",
+                super::CONVERSATION_RESET_MARKER,
+                "2026-01-01 00:00:02",
+            ),
+            ("m3", "user", "after", "2026-01-01 00:00:03"),
+            ("m4", "assistant", "reply", "2026-01-01 00:00:04"),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+                 VALUES (?, 'web:1', ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(role)
+            .bind(content)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .expect("failed to insert message");
+        }
+
+        let logger = ConversationLogger::new(pool);
+        let channel_id: crate::ChannelId = std::sync::Arc::from("web:1");
+        let messages = logger
+            .load_recent_since_reset(&channel_id, 10)
+            .await
+            .expect("load should succeed");
+        let ids: Vec<&str> = messages.iter().map(|message| message.id.as_str()).collect();
+        assert_eq!(ids, vec!["m3", "m4"]);
+
+        let all = logger
+            .load_recent(&channel_id, 10)
+            .await
+            .expect("load should succeed");
+        assert_eq!(all.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn prune_older_than_drops_messages_before_the_cutoff() {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .expect("failed to create sqlite memory pool");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("failed to run migrations");
+
+        for (id, channel_id, created_at) in [
+            ("m1", "web:1", "2026-01-01 00:00:00"),
+            ("m2", "web:2", "2026-01-09 23:59:59"),
+            ("m3", "web:1", "2026-01-10 00:00:00"),
+            ("m4", "web:2", "2026-01-12 08:30:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO conversation_messages (id, channel_id, role, content, created_at) \
+                 VALUES (?, ?, 'user', 'hello', ?)",
+            )
+            .bind(id)
+            .bind(channel_id)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .expect("failed to insert message");
+        }
+
+        let logger = ConversationLogger::new(pool.clone());
+        let cutoff = chrono::NaiveDate::from_ymd_opt(2026, 1, 10)
+            .and_then(|day| day.and_hms_opt(0, 0, 0))
+            .expect("valid date")
+            .and_utc();
+        let removed = logger
+            .prune_older_than(cutoff)
+            .await
+            .expect("prune should succeed");
+        assert_eq!(removed, 2);
+
+        let remaining: Vec<String> =
+            sqlx::query_scalar("SELECT id FROM conversation_messages ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .expect("failed to list messages");
+        assert_eq!(remaining, vec!["m3", "m4"]);
+    }
 }
@@ -3,14 +3,18 @@
 //! Each cron job gets its own tokio task that fires on an interval.
 //! When a job fires, it creates a fresh short-lived channel,
 //! runs the job's prompt through the LLM, and delivers the result
-//! to the delivery target via the messaging system.
+//! to the delivery target via the messaging system. A job that calls the
+//! skip tool has its run recorded but nothing delivered, so conditional
+//! jobs ("only if there are open incidents") stay quiet.
 
 use crate::agent::channel::Channel;
-use crate::cron::store::CronStore;
+use crate::cron::store::{CronDeliveryResult, CronStore};
 use crate::error::Result;
 use crate::messaging::MessagingManager;
 use crate::messaging::target::{BroadcastTarget, parse_delivery_target};
-use crate::{AgentDeps, InboundMessage, MessageContent, OutboundResponse, RoutedResponse};
+use crate::{
+    AgentDeps, InboundMessage, MessageContent, OutboundResponse, RoutedResponse, StatusUpdate,
+};
 use chrono::Timelike;
 use chrono_tz::Tz;
 use cron::Schedule;
@@ -98,6 +102,8 @@ const SYSTEM_TIMEZONE_LABEL: &str = "system";
 pub struct Scheduler {
     jobs: Arc<RwLock<HashMap<String, CronJob>>>,
     timers: Arc<RwLock<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    /// When each running timer will next fire, keyed by job ID.
+    next_runs: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
     context: CronContext,
 }
 
@@ -123,6 +129,7 @@ impl Scheduler {
         Self {
             jobs: Arc::new(RwLock::new(HashMap::new())),
             timers: Arc::new(RwLock::new(HashMap::new())),
+            next_runs: Arc::new(RwLock::new(HashMap::new())),
             context,
         }
     }
@@ -216,6 +223,7 @@ impl Scheduler {
         let job_id_for_map = job_id.to_string();
         let job_id = job_id.to_string();
         let jobs = self.jobs.clone();
+        let next_runs = self.next_runs.clone();
         let context = self.context.clone();
 
         // Abort any existing timer for this job before starting a new one.
@@ -228,9 +236,19 @@ impl Scheduler {
             }
         }
 
+        // Compute the first sleep up front so the next run is known as soon
+        // as this returns (the pause/resume API reports it).
+        let first_job = {
+            let j = jobs.read().await;
+            j.get(&job_id).filter(|job| job.enabled).cloned()
+        };
+        let mut first_sleep = first_job.map(|job| job_sleep_duration(&context, &job, true, anchor));
+        if let Some(duration) = first_sleep {
+            record_next_run(&next_runs, &job_id, duration).await;
+        }
+
         let handle = tokio::spawn(async move {
             let execution_lock = Arc::new(std::sync::atomic::AtomicBool::new(false));
-            let mut interval_first_tick = true;
 
             loop {
                 let job = {
@@ -248,44 +266,13 @@ impl Scheduler {
                     }
                 };
 
-                let sleep_duration = if let Some(cron_expr) = job.cron_expr.as_deref() {
-                    match next_fire_duration(&context, &job_id, cron_expr) {
-                        Some((duration, next_fire_utc, timezone)) => {
-                            tracing::debug!(
-                                cron_id = %job_id,
-                                cron_expr,
-                                cron_timezone = %timezone,
-                                next_fire_utc = %next_fire_utc.to_rfc3339(),
-                                sleep_secs = duration.as_secs(),
-                                "wall-clock cron next fire computed"
-                            );
-                            duration
-                        }
-                        None => {
-                            tracing::warn!(
-                                cron_id = %job_id,
-                                cron_expr,
-                                "failed to compute next wall-clock fire; retrying in 60s"
-                            );
-                            Duration::from_secs(60)
-                        }
+                let sleep_duration = match first_sleep.take() {
+                    Some(duration) => duration,
+                    None => {
+                        let duration = job_sleep_duration(&context, &job, false, anchor);
+                        record_next_run(&next_runs, &job_id, duration).await;
+                        duration
                     }
-                } else {
-                    let interval_secs = job.interval_secs;
-                    let delay = if interval_first_tick {
-                        interval_first_tick = false;
-                        anchored_initial_delay(interval_secs, anchor)
-                    } else {
-                        Duration::from_secs(interval_secs)
-                    };
-                    tracing::debug!(
-                        cron_id = %job_id,
-                        interval_secs,
-                        sleep_secs = delay.as_secs(),
-                        anchored = anchor.is_some(),
-                        "interval cron next fire computed"
-                    );
-                    delay
                 };
 
                 tokio::time::sleep(sleep_duration).await;
@@ -441,6 +428,8 @@ impl Scheduler {
                     }
                 });
             }
+
+            next_runs.write().await.remove(&job_id);
         });
 
         // Insert the new handle. Any previously existing handle was already aborted above.
@@ -454,6 +443,7 @@ impl Scheduler {
             let mut timers = self.timers.write().await;
             timers.drain().collect()
         };
+        self.next_runs.write().await.clear();
 
         for (id, handle) in handles {
             handle.abort();
@@ -475,6 +465,7 @@ impl Scheduler {
             let _ = handle.await;
             tracing::debug!(cron_id = %job_id, "cron timer stopped");
         }
+        self.next_runs.write().await.remove(job_id);
 
         // Remove the job from the jobs map
         let removed = {
@@ -493,6 +484,11 @@ impl Scheduler {
         jobs.contains_key(job_id)
     }
 
+    /// When a job's timer will next fire, if it is currently scheduled.
+    pub async fn next_run_at(&self, job_id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.next_runs.read().await.get(job_id).copied()
+    }
+
     /// Return the number of enabled (active) cron jobs.
     pub async fn job_count(&self) -> usize {
         self.jobs
@@ -621,12 +617,72 @@ impl Scheduler {
                 handle.abort();
                 tracing::info!(cron_id = %job_id, "cron job disabled, timer aborted immediately");
             }
+            self.next_runs.write().await.remove(job_id);
         }
 
         Ok(())
     }
 }
 
+/// How long a timer should sleep before the job's next fire.
+///
+/// `first_tick` applies the anchored initial delay to interval-based jobs.
+fn job_sleep_duration(
+    context: &CronContext,
+    job: &CronJob,
+    first_tick: bool,
+    anchor: Option<chrono::DateTime<chrono::Utc>>,
+) -> Duration {
+    if let Some(cron_expr) = job.cron_expr.as_deref() {
+        match next_fire_duration(context, &job.id, cron_expr) {
+            Some((duration, next_fire_utc, timezone)) => {
+                tracing::debug!(
+                    cron_id = %job.id,
+                    cron_expr,
+                    cron_timezone = %timezone,
+                    next_fire_utc = %next_fire_utc.to_rfc3339(),
+                    sleep_secs = duration.as_secs(),
+                    "wall-clock cron next fire computed"
+                );
+                duration
+            }
+            None => {
+                tracing::warn!(
+                    cron_id = %job.id,
+                    cron_expr,
+                    "failed to compute next wall-clock fire; retrying in 60s"
+                );
+                Duration::from_secs(60)
+            }
+        }
+    } else {
+        let interval_secs = job.interval_secs;
+        let delay = if first_tick {
+            anchored_initial_delay(interval_secs, anchor)
+        } else {
+            Duration::from_secs(interval_secs)
+        };
+        tracing::debug!(
+            cron_id = %job.id,
+            interval_secs,
+            sleep_secs = delay.as_secs(),
+            anchored = anchor.is_some(),
+            "interval cron next fire computed"
+        );
+        delay
+    }
+}
+
+async fn record_next_run(
+    next_runs: &RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>,
+    job_id: &str,
+    sleep_duration: Duration,
+) {
+    let next_run = chrono::Utc::now()
+        + chrono::Duration::from_std(sleep_duration).unwrap_or(chrono::Duration::zero());
+    next_runs.write().await.insert(job_id.to_string(), next_run);
+}
+
 fn cron_timezone_label(context: &CronContext) -> String {
     let timezone = context.deps.runtime_config.cron_timezone.load();
     match timezone.as_deref() {
@@ -664,7 +720,7 @@ fn current_hour_and_timezone(context: &CronContext, cron_id: &str) -> (u8, Strin
     }
 }
 
-fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
+pub(crate) fn hour_in_active_window(current_hour: u8, start_hour: u8, end_hour: u8) -> bool {
     if start_hour == end_hour {
         return true;
     }
@@ -902,7 +958,10 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
         agent_id: Some(context.deps.agent_id.clone()),
         content: MessageContent::Text(job.prompt.clone()),
         timestamp: chrono::Utc::now(),
-        metadata: HashMap::new(),
+        metadata: HashMap::from([(
+            crate::metadata_keys::CRON_DELIVERY_TARGET.to_string(),
+            serde_json::Value::String(job.delivery_target.to_string()),
+        )]),
         formatted_author: None,
     };
 
@@ -912,8 +971,10 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
         .map_err(|error| anyhow::anyhow!("failed to send cron prompt to channel: {error}"))?;
 
     // Collect responses with a timeout. The channel may produce multiple messages
-    // (e.g. status updates, then text). We only care about text responses.
+    // (e.g. status updates, then text). We only care about text responses,
+    // and whether the job called the skip tool.
     let mut collected_text = Vec::new();
+    let mut skip_reason: Option<Option<String>> = None;
     let timeout = Duration::from_secs(job.timeout_secs.unwrap_or(120));
 
     // Drop the sender so the channel knows no more messages are coming.
@@ -942,6 +1003,31 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
             })) => {
                 collected_text.push(text);
             }
+            Ok(Some(RoutedResponse {
+                response: OutboundResponse::Status(StatusUpdate::Skipped { reason }),
+                ..
+            })) => {
+                skip_reason = Some(reason);
+            }
+            // Home views go straight to the user's Home tab rather than being
+            // collected into the delivered text.
+            Ok(Some(RoutedResponse {
+                response: response @ OutboundResponse::HomeView { .. },
+                ..
+            })) => {
+                if let Err(error) = context
+                    .messaging_manager
+                    .broadcast(
+                        &context.deps.agent_id,
+                        &job.delivery_target.adapter,
+                        &job.delivery_target.target,
+                        response,
+                    )
+                    .await
+                {
+                    tracing::warn!(cron_id = %job.id, %error, "failed to publish cron home view");
+                }
+            }
             Ok(Some(_)) => {}
             Ok(None) => {
                 break;
@@ -960,17 +1046,42 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
     let result_text = collected_text.join("\n\n");
     let has_result = !result_text.trim().is_empty();
 
+    if let Some(reason) = skip_reason {
+        tracing::info!(
+            cron_id = %job.id,
+            reason = reason.as_deref().unwrap_or("no reason given"),
+            "cron job called skip, suppressing delivery"
+        );
+        let summary = suppressed_run_summary(&result_text, reason.as_deref());
+        if let Err(error) = context
+            .store
+            .log_suppressed_execution(&job.id, summary.as_deref())
+            .await
+        {
+            tracing::warn!(%error, "failed to log cron execution");
+        }
+        return Ok(());
+    }
+
     // Deliver result to target (only if there's something to say)
+    let mut delivery_results = Vec::new();
     if has_result {
-        if let Err(error) = context
+        let delivery = context
             .messaging_manager
             .broadcast(
+                &context.deps.agent_id,
                 &job.delivery_target.adapter,
                 &job.delivery_target.target,
                 OutboundResponse::Text(result_text.clone()),
             )
-            .await
-        {
+            .await;
+        delivery_results.push(CronDeliveryResult {
+            target: job.delivery_target.to_string(),
+            success: delivery.is_ok(),
+            error: delivery.as_ref().err().map(ToString::to_string),
+        });
+
+        if let Err(error) = delivery {
             tracing::error!(
                 cron_id = %job.id,
                 target = %job.delivery_target,
@@ -979,7 +1090,7 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
             );
             if let Err(log_error) = context
                 .store
-                .log_execution(&job.id, false, Some(&error.to_string()))
+                .log_execution(&job.id, false, Some(&error.to_string()), &delivery_results)
                 .await
             {
                 tracing::warn!(%log_error, "failed to log cron execution");
@@ -1001,13 +1112,30 @@ async fn run_cron_job(job: &CronJob, context: &CronContext) -> Result<()> {
     } else {
         None
     };
-    if let Err(error) = context.store.log_execution(&job.id, true, summary).await {
+    if let Err(error) = context
+        .store
+        .log_execution(&job.id, true, summary, &delivery_results)
+        .await
+    {
         tracing::warn!(%error, "failed to log cron execution");
     }
 
     Ok(())
 }
 
+/// Execution summary for a run whose delivery was suppressed: the skip
+/// reason, followed by any text the run produced before skipping.
+fn suppressed_run_summary(result_text: &str, reason: Option<&str>) -> Option<String> {
+    let reason = reason.map(str::trim).filter(|reason| !reason.is_empty());
+    let result_text = result_text.trim();
+    match (reason, result_text.is_empty()) {
+        (Some(reason), true) => Some(format!("Skipped: {reason}")),
+        (Some(reason), false) => Some(format!("Skipped: {reason}\n\n{result_text}")),
+        (None, false) => Some(result_text.to_string()),
+        (None, true) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{hour_in_active_window, normalize_active_hours};
@@ -1043,4 +1171,21 @@ mod tests {
         assert_eq!(normalize_active_hours(Some((9, 17))), Some((9, 17)));
         assert_eq!(normalize_active_hours(None), None);
     }
+
+    #[test]
+    fn test_suppressed_run_summary() {
+        assert_eq!(
+            suppressed_run_summary("", Some("no open incidents")),
+            Some("Skipped: no open incidents".to_string())
+        );
+        assert_eq!(
+            suppressed_run_summary("draft", Some(" ")),
+            Some("draft".to_string())
+        );
+        assert_eq!(
+            suppressed_run_summary("draft", Some("nothing new")),
+            Some("Skipped: nothing new\n\ndraft".to_string())
+        );
+        assert_eq!(suppressed_run_summary("  ", None), None);
+    }
 }
@@ -4,7 +4,7 @@ use crate::cron::scheduler::CronConfig;
 use crate::error::Result;
 use anyhow::Context as _;
 use sqlx::SqlitePool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Cron job store for persistence.
 #[derive(Debug)]
@@ -56,13 +56,13 @@ impl CronStore {
         Ok(())
     }
 
-    /// Load all enabled cron job configurations.
+    /// Load all enabled, unpaused cron job configurations.
     pub async fn load_all(&self) -> Result<Vec<CronConfig>> {
         let rows = sqlx::query(
             r#"
             SELECT id, prompt, cron_expr, interval_secs, delivery_target, active_start_hour, active_end_hour, enabled, run_once, timeout_secs
             FROM cron_jobs
-            WHERE enabled = 1
+            WHERE enabled = 1 AND paused = 0
             ORDER BY created_at ASC
             "#
         )
@@ -122,25 +122,91 @@ impl CronStore {
         Ok(())
     }
 
-    /// Log a cron job execution result.
+    /// Set the runtime pause flag of a cron job. Returns whether the job exists.
+    ///
+    /// Pausing is separate from `enabled` so it survives the config re-seed
+    /// that runs on startup.
+    pub async fn set_paused(&self, id: &str, paused: bool) -> Result<bool> {
+        let result = sqlx::query("UPDATE cron_jobs SET paused = ? WHERE id = ?")
+            .bind(paused as i64)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to update cron job paused state")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// IDs of all paused cron jobs.
+    pub async fn paused_ids(&self) -> Result<HashSet<String>> {
+        let rows = sqlx::query("SELECT id FROM cron_jobs WHERE paused = 1")
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to load paused cron jobs")?;
+
+        let mut ids = HashSet::new();
+        for row in rows {
+            ids.insert(row.try_get::<String, _>("id")?);
+        }
+
+        Ok(ids)
+    }
+
+    /// Log a cron job execution result along with the per-target delivery
+    /// outcomes, if any delivery was attempted.
     pub async fn log_execution(
         &self,
         cron_id: &str,
         success: bool,
         result_summary: Option<&str>,
+        delivery_results: &[CronDeliveryResult],
+    ) -> Result<()> {
+        self.insert_execution(cron_id, success, result_summary, delivery_results, false)
+            .await
+    }
+
+    /// Log a successful run whose delivery was suppressed because the job
+    /// called the skip tool.
+    pub async fn log_suppressed_execution(
+        &self,
+        cron_id: &str,
+        result_summary: Option<&str>,
+    ) -> Result<()> {
+        self.insert_execution(cron_id, true, result_summary, &[], true)
+            .await
+    }
+
+    async fn insert_execution(
+        &self,
+        cron_id: &str,
+        success: bool,
+        result_summary: Option<&str>,
+        delivery_results: &[CronDeliveryResult],
+        delivery_suppressed: bool,
     ) -> Result<()> {
         let execution_id = uuid::Uuid::new_v4().to_string();
+        let delivery_results = if delivery_results.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(delivery_results)
+                    .context("failed to serialize cron delivery results")?,
+            )
+        };
 
         sqlx::query(
             r#"
-            INSERT INTO cron_executions (id, cron_id, success, result_summary)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO cron_executions
+                (id, cron_id, success, result_summary, delivery_results, delivery_suppressed)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&execution_id)
         .bind(cron_id)
         .bind(success as i64)
         .bind(result_summary)
+        .bind(delivery_results)
+        .bind(delivery_suppressed as i64)
         .execute(&self.pool)
         .await
         .context("failed to log cron execution")?;
@@ -198,7 +264,7 @@ impl CronStore {
     ) -> Result<Vec<CronExecutionEntry>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, executed_at, success, result_summary
+            SELECT id, executed_at, success, result_summary, delivery_results, delivery_suppressed
             FROM cron_executions
             WHERE cron_id = ?
             ORDER BY executed_at DESC
@@ -211,15 +277,7 @@ impl CronStore {
         .await
         .context("failed to load cron executions")?;
 
-        let entries = rows
-            .into_iter()
-            .map(|row| CronExecutionEntry {
-                id: row.try_get("id").unwrap_or_default(),
-                executed_at: row.try_get("executed_at").unwrap_or_default(),
-                success: row.try_get::<i64, _>("success").unwrap_or(0) != 0,
-                result_summary: row.try_get("result_summary").ok(),
-            })
-            .collect();
+        let entries = rows.iter().map(execution_entry_from_row).collect();
 
         Ok(entries)
     }
@@ -228,7 +286,8 @@ impl CronStore {
     pub async fn load_all_executions(&self, limit: i64) -> Result<Vec<CronExecutionEntry>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, cron_id, executed_at, success, result_summary
+            SELECT id, cron_id, executed_at, success, result_summary, delivery_results,
+                   delivery_suppressed
             FROM cron_executions
             ORDER BY executed_at DESC
             LIMIT ?
@@ -239,19 +298,16 @@ impl CronStore {
         .await
         .context("failed to load cron executions")?;
 
-        let entries = rows
-            .into_iter()
-            .map(|row| CronExecutionEntry {
-                id: row.try_get("id").unwrap_or_default(),
-                executed_at: row.try_get("executed_at").unwrap_or_default(),
-                success: row.try_get::<i64, _>("success").unwrap_or(0) != 0,
-                result_summary: row.try_get("result_summary").ok(),
-            })
-            .collect();
+        let entries = rows.iter().map(execution_entry_from_row).collect();
 
         Ok(entries)
     }
 
+    /// Load the most recent execution of a cron job, if it has run.
+    pub async fn last_run(&self, cron_id: &str) -> Result<Option<CronExecutionEntry>> {
+        Ok(self.load_executions(cron_id, 1).await?.into_iter().next())
+    }
+
     /// Get the most recent execution timestamp for each cron job.
     ///
     /// Returns a map of `cron_id -> last_executed_at` (UTC timestamp string).
@@ -314,6 +370,24 @@ impl CronStore {
     }
 }
 
+fn execution_entry_from_row(row: &sqlx::sqlite::SqliteRow) -> CronExecutionEntry {
+    let delivery_results = row
+        .try_get::<Option<String>, _>("delivery_results")
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+
+    CronExecutionEntry {
+        id: row.try_get("id").unwrap_or_default(),
+        executed_at: row.try_get("executed_at").unwrap_or_default(),
+        success: row.try_get::<i64, _>("success").unwrap_or(0) != 0,
+        result_summary: row.try_get("result_summary").ok(),
+        delivery_results,
+        delivery_suppressed: row.try_get::<i64, _>("delivery_suppressed").unwrap_or(0) != 0,
+    }
+}
+
 /// Entry in the cron execution log.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CronExecutionEntry {
@@ -321,6 +395,19 @@ pub struct CronExecutionEntry {
     pub executed_at: String,
     pub success: bool,
     pub result_summary: Option<String>,
+    /// Outcome per delivery target. Empty when the run produced no output.
+    pub delivery_results: Vec<CronDeliveryResult>,
+    /// The job called the skip tool, so nothing was delivered.
+    pub delivery_suppressed: bool,
+}
+
+/// Outcome of delivering a cron result to a single target.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CronDeliveryResult {
+    /// Delivery target in `adapter:target` form.
+    pub target: String,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 /// Execution statistics for a cron job.
@@ -332,3 +419,71 @@ pub struct CronExecutionStats {
 }
 
 use sqlx::Row as _;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqliteConnectOptions;
+
+    async fn open_store(path: &std::path::Path) -> CronStore {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        CronStore::new(pool)
+    }
+
+    fn job(id: &str) -> CronConfig {
+        CronConfig {
+            id: id.to_string(),
+            prompt: "summarize the day".to_string(),
+            cron_expr: None,
+            interval_secs: 3600,
+            delivery_target: "discord:123".to_string(),
+            active_hours: None,
+            enabled: true,
+            run_once: false,
+            timeout_secs: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn paused_state_survives_restart_and_reseed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spacebot.db");
+
+        let store = open_store(&path).await;
+        store.save(&job("daily")).await.unwrap();
+        store.save(&job("hourly")).await.unwrap();
+        assert!(store.set_paused("daily", true).await.unwrap());
+        assert!(!store.set_paused("missing", true).await.unwrap());
+        store.pool.close().await;
+
+        // Restart: reopen the database and re-seed the jobs from config.
+        let store = open_store(&path).await;
+        store.save(&job("daily")).await.unwrap();
+        store.save(&job("hourly")).await.unwrap();
+
+        assert_eq!(
+            store.paused_ids().await.unwrap(),
+            HashSet::from(["daily".to_string()])
+        );
+        let loaded: Vec<String> = store
+            .load_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|config| config.id)
+            .collect();
+        assert_eq!(loaded, vec!["hourly".to_string()]);
+        assert_eq!(store.load_all_unfiltered().await.unwrap().len(), 2);
+
+        assert!(store.set_paused("daily", false).await.unwrap());
+        store.pool.close().await;
+
+        let store = open_store(&path).await;
+        assert!(store.paused_ids().await.unwrap().is_empty());
+        assert_eq!(store.load_all().await.unwrap().len(), 2);
+    }
+}
@@ -1,9 +1,15 @@
 //! Database connection management and migrations.
 
+use crate::config::DbConfig;
 use crate::error::{DbError, Result};
 use anyhow::Context as _;
 use sqlx::SqlitePool;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// SQLite VM instructions between statement-timeout checks.
+const PROGRESS_HANDLER_OPS: i32 = 1000;
 
 /// Database connections bundle for per-agent databases.
 pub struct Db {
@@ -21,18 +27,28 @@ use std::sync::Arc;
 
 impl Db {
     /// Connect to all databases and run migrations.
-    pub async fn connect(data_dir: &Path) -> Result<Self> {
+    pub async fn connect(data_dir: &Path, config: &DbConfig) -> Result<Self> {
         // SQLite
-        let sqlite_url = format!("sqlite:{}?mode=rwc", data_dir.join("spacebot.db").display());
-        let sqlite = SqlitePool::connect(&sqlite_url)
+        let sqlite = connect_sqlite(&data_dir.join("spacebot.db"), config)
             .await
             .with_context(|| "failed to connect to SQLite")?;
 
-        // Run migrations
+        // Run migrations on a connection without the statement timeout, since
+        // rebuilding a large table can legitimately take longer.
+        let mut migration_connection = sqlite
+            .acquire()
+            .await
+            .with_context(|| "failed to acquire SQLite connection for migrations")?;
+        migration_connection
+            .lock_handle()
+            .await
+            .with_context(|| "failed to lock SQLite connection for migrations")?
+            .remove_progress_handler();
         sqlx::migrate!("./migrations")
-            .run(&sqlite)
+            .run(&mut *migration_connection)
             .await
             .with_context(|| "failed to run database migrations")?;
+        drop(migration_connection);
 
         // LanceDB
         let lance_path = data_dir.join("lancedb");
@@ -66,3 +82,77 @@ impl Db {
         // LanceDB and redb close automatically when dropped
     }
 }
+
+/// Open the SQLite pool with the configured limits.
+///
+/// When `statement_timeout_secs` is set, each pooled connection gets a
+/// progress handler that interrupts whatever it is executing once the current
+/// checkout has run past the limit, so a runaway query can't hold a
+/// connection (and with it, the write lock) indefinitely. The limit covers
+/// the whole checkout, not each statement, which is why it is off by default.
+async fn connect_sqlite(
+    path: &Path,
+    config: &DbConfig,
+) -> std::result::Result<SqlitePool, sqlx::Error> {
+    let journal_mode = if config.wal {
+        SqliteJournalMode::Wal
+    } else {
+        SqliteJournalMode::Delete
+    };
+    let connect_options = SqliteConnectOptions::new()
+        .filename(path)
+        .create_if_missing(true)
+        .journal_mode(journal_mode)
+        // NORMAL is durable under WAL and avoids an fsync per commit.
+        .synchronous(if config.wal {
+            SqliteSynchronous::Normal
+        } else {
+            SqliteSynchronous::Full
+        })
+        .busy_timeout(Duration::from_secs(config.busy_timeout_secs));
+
+    let statement_timeout = (config.statement_timeout_secs > 0)
+        .then(|| Duration::from_secs(config.statement_timeout_secs));
+
+    SqlitePoolOptions::new()
+        .max_connections(config.max_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+        .after_connect(move |connection, _| {
+            Box::pin(async move {
+                if let Some(timeout) = statement_timeout {
+                    arm_statement_timeout(connection, timeout).await?;
+                }
+                Ok(())
+            })
+        })
+        .before_acquire(move |connection, _| {
+            Box::pin(async move {
+                if let Some(timeout) = statement_timeout {
+                    arm_statement_timeout(connection, timeout).await?;
+                }
+                Ok(true)
+            })
+        })
+        .connect_with(connect_options)
+        .await
+}
+
+/// Start the statement-timeout clock for a fresh checkout of `connection`.
+async fn arm_statement_timeout(
+    connection: &mut sqlx::SqliteConnection,
+    timeout: Duration,
+) -> std::result::Result<(), sqlx::Error> {
+    let deadline = Instant::now() + timeout;
+    let mut handle = connection.lock_handle().await?;
+    handle.set_progress_handler(PROGRESS_HANDLER_OPS, move || {
+        let keep_going = Instant::now() < deadline;
+        if !keep_going {
+            tracing::warn!(
+                timeout_secs = timeout.as_secs(),
+                "interrupting SQLite statement on a connection checked out past the statement timeout"
+            );
+        }
+        keep_going
+    });
+    Ok(())
+}
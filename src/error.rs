@@ -204,6 +204,9 @@ pub enum AgentError {
     #[error("process cancelled: {reason}")]
     Cancelled { reason: String },
 
+    #[error("agent {agent_id} is paused by a PAUSE file; remove it to resume")]
+    Paused { agent_id: String },
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -242,6 +245,9 @@ pub enum SettingsError {
     #[error("setting not found: {key}")]
     NotFound { key: String },
 
+    #[error("invalid value for setting {key}: {details}")]
+    InvalidValue { key: String, details: String },
+
     #[error("settings error: {0}")]
     Other(String),
 }
@@ -0,0 +1,278 @@
+//! Outbound forwarding of raw `ProcessEvent`s to an HTTP endpoint.
+//!
+//! Configured with `[event_sink]`. Each agent's event bus gets a forwarder
+//! that filters events by type and queues them; a single delivery task drains
+//! the queue in batches and POSTs each batch as JSON, retrying with
+//! exponential backoff. A batch that still fails after `max_retries` is
+//! dropped, and a full queue drops new events, so a slow or dead endpoint
+//! never backs up the agents.
+//!
+//! This is separate from OpenTelemetry tracing: it carries the same semantic
+//! events the API's event stream is built from.
+
+use crate::ProcessEvent;
+use crate::config::EventSinkConfig;
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+
+/// Per-request timeout for deliveries.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// First retry delay; doubles on each further attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest delay between retries.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// One forwarded event, tagged with the agent it came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct SinkEvent {
+    pub agent_id: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The `ProcessEvent` in its serialized form, `type` field included.
+    pub event: serde_json::Value,
+}
+
+/// Request body for one delivery.
+#[derive(Debug, Serialize)]
+struct SinkBatch<'a> {
+    events: &'a [SinkEvent],
+}
+
+/// Handle to a running event sink. Cloning shares the same delivery queue.
+#[derive(Clone)]
+pub struct EventSink {
+    config: Arc<EventSinkConfig>,
+    queue: mpsc::Sender<SinkEvent>,
+}
+
+impl EventSink {
+    /// Start the delivery task. Returns `None` when no sink URL is configured.
+    pub fn start(config: &EventSinkConfig) -> Option<Self> {
+        let url = config.url.clone()?;
+        let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(error) => {
+                tracing::warn!(%error, "failed to build event sink HTTP client");
+                return None;
+            }
+        };
+
+        let config = Arc::new(config.clone());
+        let (queue, queued) = mpsc::channel(config.queue_capacity);
+        tokio::spawn(deliver(config.clone(), client, url, queued));
+        tracing::info!(
+            event_types = ?config.event_types,
+            "event sink started"
+        );
+
+        Some(Self { config, queue })
+    }
+
+    /// Forward events from one agent's event bus until it closes.
+    pub fn attach(&self, agent_id: String, mut event_rx: broadcast::Receiver<ProcessEvent>) {
+        let config = self.config.clone();
+        let queue = self.queue.clone();
+        tokio::spawn(async move {
+            let mut dropped: u64 = 0;
+            loop {
+                let event = match event_rx.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            agent_id = %agent_id,
+                            skipped,
+                            "event sink fell behind the event bus, events skipped"
+                        );
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let Some(sink_event) = sink_event(&config, &agent_id, &event) else {
+                    continue;
+                };
+                match queue.try_send(sink_event) {
+                    Ok(()) => {
+                        if dropped > 0 {
+                            tracing::warn!(
+                                agent_id = %agent_id,
+                                dropped,
+                                "event sink queue was full, events dropped"
+                            );
+                            dropped = 0;
+                        }
+                    }
+                    Err(mpsc::error::TrySendError::Full(_)) => dropped += 1,
+                    Err(mpsc::error::TrySendError::Closed(_)) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Serialize `event` for the sink, or `None` if its type is filtered out.
+fn sink_event(config: &EventSinkConfig, agent_id: &str, event: &ProcessEvent) -> Option<SinkEvent> {
+    let event = match serde_json::to_value(event) {
+        Ok(value) => value,
+        Err(error) => {
+            tracing::warn!(%error, "failed to serialize event for the event sink");
+            return None;
+        }
+    };
+    let event_type = event.get("type").and_then(|value| value.as_str())?;
+    if !config.forwards(event_type) {
+        return None;
+    }
+
+    Some(SinkEvent {
+        agent_id: agent_id.to_string(),
+        timestamp: chrono::Utc::now(),
+        event,
+    })
+}
+
+/// Drain the queue in batches of up to `batch_size`, waiting at most
+/// `flush_interval_ms` after the first event of a batch before sending it.
+async fn deliver(
+    config: Arc<EventSinkConfig>,
+    client: reqwest::Client,
+    url: String,
+    mut queued: mpsc::Receiver<SinkEvent>,
+) {
+    let flush_interval = Duration::from_millis(config.flush_interval_ms);
+    let mut batch = Vec::with_capacity(config.batch_size);
+
+    while let Some(event) = queued.recv().await {
+        batch.push(event);
+        let deadline = tokio::time::Instant::now() + flush_interval;
+        while batch.len() < config.batch_size {
+            match tokio::time::timeout_at(deadline, queued.recv()).await {
+                Ok(Some(event)) => batch.push(event),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        send_with_retry(&config, &client, &url, &batch).await;
+        batch.clear();
+    }
+}
+
+/// POST one batch, retrying failures up to `max_retries` times.
+async fn send_with_retry(
+    config: &EventSinkConfig,
+    client: &reqwest::Client,
+    url: &str,
+    batch: &[SinkEvent],
+) {
+    for attempt in 0..=config.max_retries {
+        if attempt > 0 {
+            tokio::time::sleep(retry_delay(attempt)).await;
+        }
+
+        match post_batch(config, client, url, batch).await {
+            Ok(()) => return,
+            Err(DeliveryError::Rejected(status)) => {
+                tracing::warn!(
+                    %status,
+                    count = batch.len(),
+                    "event sink rejected batch, dropping it"
+                );
+                return;
+            }
+            Err(DeliveryError::Retryable(error)) => {
+                tracing::debug!(attempt, %error, "event sink delivery failed");
+            }
+        }
+    }
+
+    tracing::warn!(
+        count = batch.len(),
+        retries = config.max_retries,
+        "event sink delivery kept failing, dropping batch"
+    );
+}
+
+enum DeliveryError {
+    /// The endpoint refused the batch; retrying would not help.
+    Rejected(reqwest::StatusCode),
+    /// Network errors, timeouts, 429, and 5xx responses.
+    Retryable(String),
+}
+
+async fn post_batch(
+    config: &EventSinkConfig,
+    client: &reqwest::Client,
+    url: &str,
+    batch: &[SinkEvent],
+) -> Result<(), DeliveryError> {
+    let mut request = client.post(url).json(&SinkBatch { events: batch });
+    if let Some(token) = &config.auth_token {
+        request = request.bearer_auth(token);
+    }
+    for (name, value) in &config.headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|error| DeliveryError::Retryable(error.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        Err(DeliveryError::Retryable(format!(
+            "endpoint returned {status}"
+        )))
+    } else {
+        Err(DeliveryError::Rejected(status))
+    }
+}
+
+/// Delay before retry number `attempt` (1-based).
+fn retry_delay(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(event_types: &[&str]) -> EventSinkConfig {
+        EventSinkConfig {
+            url: Some("http://localhost:9/events".to_string()),
+            event_types: event_types.iter().map(|name| name.to_string()).collect(),
+            ..EventSinkConfig::default()
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), Duration::from_millis(500));
+        assert_eq!(retry_delay(2), Duration::from_secs(1));
+        assert_eq!(retry_delay(4), Duration::from_secs(4));
+        assert_eq!(retry_delay(10), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn events_are_filtered_by_type() {
+        let event = ProcessEvent::StatusUpdate {
+            agent_id: Arc::from("main"),
+            process_id: crate::ProcessId::Channel(Arc::from("web:1")),
+            status: "thinking".to_string(),
+        };
+
+        let forwarded = sink_event(&config(&[]), "main", &event).expect("no filter forwards all");
+        assert_eq!(forwarded.agent_id, "main");
+        assert_eq!(forwarded.event["type"], "status_update");
+
+        assert!(sink_event(&config(&["status_update"]), "main", &event).is_some());
+        assert!(sink_event(&config(&["worker_started"]), "main", &event).is_none());
+    }
+}
@@ -0,0 +1,247 @@
+//! Human handoff: escalating a conversation to a real person.
+//!
+//! When the agent is unsure it calls `request_human`, which notifies a human
+//! linked to the agent in the `[[links]]` graph and opens a handoff on the
+//! channel. While a handoff is open the channel records messages but does not
+//! reply. Resolving the handoff through the API hands the conversation back.
+
+pub mod store;
+
+pub use store::{Handoff, HandoffStore};
+
+use crate::config::HumanDef;
+use crate::links::{AgentLink, LinkKind};
+use crate::messaging::target::{BroadcastTarget, normalize_target, parse_delivery_target};
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Whether a channel has an open handoff, cached so the channel doesn't
+/// query the store on every message. Unknown until first loaded; the
+/// `request_human` tool and the resolve API keep it current after that.
+#[derive(Debug, Default)]
+pub struct HandoffState(AtomicU8);
+
+impl HandoffState {
+    const UNKNOWN: u8 = 0;
+    const CLOSED: u8 = 1;
+    const OPEN: u8 = 2;
+
+    /// The cached state, or None if it hasn't been loaded yet.
+    pub fn get(&self) -> Option<bool> {
+        match self.0.load(Ordering::Acquire) {
+            Self::OPEN => Some(true),
+            Self::CLOSED => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn set(&self, open: bool) {
+        let state = if open { Self::OPEN } else { Self::CLOSED };
+        self.0.store(state, Ordering::Release);
+    }
+
+    /// Cache `open` as loaded from the store, unless the state was set in
+    /// the meantime.
+    pub fn load(&self, open: bool) -> bool {
+        let state = if open { Self::OPEN } else { Self::CLOSED };
+        match self
+            .0
+            .compare_exchange(Self::UNKNOWN, state, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => open,
+            Err(current) => current == Self::OPEN,
+        }
+    }
+}
+
+/// Humans linked to `agent_id`, most senior first: humans above the agent in
+/// a hierarchical link, then peers, then humans below it.
+pub fn linked_humans<'a>(
+    agent_id: &str,
+    links: &[AgentLink],
+    humans: &'a [HumanDef],
+) -> Vec<&'a HumanDef> {
+    let mut ranked: Vec<(u8, &HumanDef)> = Vec::new();
+    for link in crate::links::links_for_agent(links, agent_id) {
+        let is_from = link.from_agent_id == agent_id;
+        let other_id = if is_from {
+            &link.to_agent_id
+        } else {
+            &link.from_agent_id
+        };
+        let Some(human) = humans.iter().find(|human| &human.id == other_id) else {
+            continue;
+        };
+        if ranked.iter().any(|(_, ranked)| ranked.id == human.id) {
+            continue;
+        }
+        let rank = match link.kind {
+            LinkKind::Hierarchical if !is_from => 0,
+            LinkKind::Peer => 1,
+            LinkKind::Hierarchical => 2,
+        };
+        ranked.push((rank, human));
+    }
+
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked.into_iter().map(|(_, human)| human).collect()
+}
+
+/// Where to notify `human`, in the order to try them.
+///
+/// An explicit `handoff_target` comes first. After that, a DM on every
+/// platform the human has an ID for, starting with the platform of the
+/// current conversation so a named adapter instance is reused.
+pub fn notification_targets(
+    human: &HumanDef,
+    current_adapter: Option<&str>,
+) -> Vec<BroadcastTarget> {
+    let mut targets = Vec::new();
+
+    if let Some(raw) = human.handoff_target.as_deref() {
+        match parse_delivery_target(raw) {
+            Some(target) => targets.push(target),
+            None => tracing::warn!(
+                human_id = %human.id,
+                handoff_target = %raw,
+                "invalid handoff_target, falling back to platform IDs"
+            ),
+        }
+    }
+
+    let current_platform = current_adapter.map(platform_of);
+    let mut direct: Vec<BroadcastTarget> = [
+        (
+            "discord",
+            human.discord_id.as_ref().map(|id| format!("dm:{id}")),
+        ),
+        (
+            "slack",
+            human.slack_id.as_ref().map(|id| format!("dm:{id}")),
+        ),
+        ("telegram", human.telegram_id.clone()),
+        ("email", human.email.clone()),
+    ]
+    .into_iter()
+    .filter_map(|(platform, raw)| {
+        let target = normalize_target(platform, &raw?)?;
+        let adapter = match current_adapter {
+            Some(adapter) if current_platform == Some(platform) => adapter,
+            _ => platform,
+        };
+        Some(BroadcastTarget {
+            adapter: adapter.to_string(),
+            target,
+        })
+    })
+    .collect();
+    direct.sort_by_key(|target| current_platform != Some(platform_of(&target.adapter)));
+
+    for target in direct {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+fn platform_of(adapter: &str) -> &str {
+    adapter
+        .split_once(':')
+        .map_or(adapter, |(platform, _)| platform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::LinkDirection;
+
+    fn human(id: &str) -> HumanDef {
+        HumanDef {
+            id: id.to_string(),
+            display_name: None,
+            role: None,
+            bio: None,
+            description: None,
+            discord_id: None,
+            telegram_id: None,
+            slack_id: None,
+            email: None,
+            handoff_target: None,
+        }
+    }
+
+    fn link(from: &str, to: &str, kind: LinkKind) -> AgentLink {
+        AgentLink {
+            from_agent_id: from.to_string(),
+            to_agent_id: to.to_string(),
+            direction: LinkDirection::TwoWay,
+            kind,
+        }
+    }
+
+    #[test]
+    fn linked_humans_puts_superiors_first_and_skips_agents() {
+        let humans = vec![human("intern"), human("peer"), human("boss")];
+        let links = vec![
+            link("main", "intern", LinkKind::Hierarchical),
+            link("main", "peer", LinkKind::Peer),
+            link("main", "other-agent", LinkKind::Peer),
+            link("boss", "main", LinkKind::Hierarchical),
+            link("boss", "other-agent", LinkKind::Hierarchical),
+        ];
+
+        let ids: Vec<&str> = linked_humans("main", &links, &humans)
+            .into_iter()
+            .map(|human| human.id.as_str())
+            .collect();
+        assert_eq!(ids, ["boss", "peer", "intern"]);
+    }
+
+    #[test]
+    fn notification_targets_prefer_explicit_then_current_platform() {
+        let mut support = human("support");
+        support.discord_id = Some("42".to_string());
+        support.slack_id = Some("U123".to_string());
+        support.email = Some("support@example.com".to_string());
+
+        let targets = notification_targets(&support, Some("slack:work"));
+        assert_eq!(
+            targets,
+            [
+                BroadcastTarget {
+                    adapter: "slack:work".to_string(),
+                    target: "dm:U123".to_string(),
+                },
+                BroadcastTarget {
+                    adapter: "discord".to_string(),
+                    target: "dm:42".to_string(),
+                },
+                BroadcastTarget {
+                    adapter: "email".to_string(),
+                    target: "support@example.com".to_string(),
+                },
+            ]
+        );
+
+        support.handoff_target = Some("discord:1234".to_string());
+        let targets = notification_targets(&support, None);
+        assert_eq!(targets[0].adapter, "discord");
+        assert_eq!(targets[0].target, "1234");
+        assert_eq!(targets[1].target, "dm:42");
+    }
+
+    #[test]
+    fn handoff_state_keeps_updates_over_stale_loads() {
+        let state = HandoffState::default();
+        assert_eq!(state.get(), None);
+        assert!(!state.load(false));
+        assert_eq!(state.get(), Some(false));
+
+        state.set(true);
+        // A store read that started before the handoff opened is ignored.
+        assert!(state.load(false));
+        state.set(false);
+        assert_eq!(state.get(), Some(false));
+    }
+}
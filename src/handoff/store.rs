@@ -0,0 +1,217 @@
+//! Handoff storage (SQLite).
+
+use crate::error::Result;
+use anyhow::Context as _;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+const COLUMNS: &str = "id, channel_id, human_id, reason, notified_target, status, resolution, \
+                       created_at, resolved_at";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Handoff {
+    pub id: String,
+    pub channel_id: String,
+    /// The human the handoff was routed to.
+    pub human_id: String,
+    pub reason: String,
+    /// `adapter:target` the notification was delivered to.
+    pub notified_target: Option<String>,
+    /// `open` or `resolved`.
+    pub status: String,
+    pub resolution: Option<String>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandoffStore {
+    pool: SqlitePool,
+}
+
+impl HandoffStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Open a handoff on a channel. A channel has at most one open handoff;
+    /// if one exists already it is returned unchanged.
+    pub async fn open(
+        &self,
+        channel_id: &str,
+        human_id: &str,
+        reason: &str,
+        notified_target: Option<&str>,
+    ) -> Result<Handoff> {
+        sqlx::query(
+            r#"
+            INSERT INTO handoffs (id, channel_id, human_id, reason, notified_target)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(channel_id) WHERE status = 'open' DO NOTHING
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(channel_id)
+        .bind(human_id)
+        .bind(reason)
+        .bind(notified_target)
+        .execute(&self.pool)
+        .await
+        .context("failed to open handoff")?;
+
+        Ok(self
+            .open_for_channel(channel_id)
+            .await?
+            .context("handoff missing after open")?)
+    }
+
+    /// The open handoff on a channel, if any.
+    pub async fn open_for_channel(&self, channel_id: &str) -> Result<Option<Handoff>> {
+        let row = sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM handoffs WHERE channel_id = ? AND status = 'open'"
+        ))
+        .bind(channel_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to read open handoff")?;
+
+        row.map(handoff_from_row).transpose()
+    }
+
+    /// Every open handoff, oldest first.
+    pub async fn list_open(&self) -> Result<Vec<Handoff>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {COLUMNS} FROM handoffs WHERE status = 'open' ORDER BY created_at ASC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list open handoffs")?;
+
+        rows.into_iter().map(handoff_from_row).collect()
+    }
+
+    /// Resolve the open handoff on a channel so the agent replies again.
+    /// Returns `None` when the channel has no open handoff.
+    pub async fn resolve(
+        &self,
+        channel_id: &str,
+        resolution: Option<&str>,
+    ) -> Result<Option<Handoff>> {
+        let Some(handoff) = self.open_for_channel(channel_id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            "UPDATE handoffs SET status = 'resolved', resolution = ?, resolved_at = datetime('now') \
+             WHERE id = ?",
+        )
+        .bind(resolution)
+        .bind(&handoff.id)
+        .execute(&self.pool)
+        .await
+        .context("failed to resolve handoff")?;
+
+        let row = sqlx::query(&format!("SELECT {COLUMNS} FROM handoffs WHERE id = ?"))
+            .bind(&handoff.id)
+            .fetch_one(&self.pool)
+            .await
+            .context("failed to read resolved handoff")?;
+
+        handoff_from_row(row).map(Some)
+    }
+}
+
+fn handoff_from_row(row: sqlx::sqlite::SqliteRow) -> Result<Handoff> {
+    Ok(Handoff {
+        id: row.try_get("id").context("failed to read handoff id")?,
+        channel_id: row
+            .try_get("channel_id")
+            .context("failed to read handoff channel_id")?,
+        human_id: row
+            .try_get("human_id")
+            .context("failed to read handoff human_id")?,
+        reason: row
+            .try_get("reason")
+            .context("failed to read handoff reason")?,
+        notified_target: row
+            .try_get("notified_target")
+            .context("failed to read handoff notified_target")?,
+        status: row
+            .try_get("status")
+            .context("failed to read handoff status")?,
+        resolution: row
+            .try_get("resolution")
+            .context("failed to read handoff resolution")?,
+        created_at: row
+            .try_get::<chrono::NaiveDateTime, _>("created_at")
+            .map(|v| v.and_utc().to_rfc3339())
+            .context("failed to read handoff created_at")?,
+        resolved_at: row
+            .try_get::<Option<chrono::NaiveDateTime>, _>("resolved_at")
+            .map(|v| v.map(|v| v.and_utc().to_rfc3339()))
+            .context("failed to read handoff resolved_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_store() -> HandoffStore {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        HandoffStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn one_open_handoff_per_channel_until_resolved() {
+        let store = setup_store().await;
+
+        let first = store
+            .open(
+                "discord:1:2",
+                "admin",
+                "refund dispute",
+                Some("discord:dm:42"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.status, "open");
+
+        let again = store
+            .open("discord:1:2", "admin", "still unsure", None)
+            .await
+            .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.reason, "refund dispute");
+
+        store
+            .open("slack:T1:C1", "admin", "other channel", None)
+            .await
+            .unwrap();
+        assert_eq!(store.list_open().await.unwrap().len(), 2);
+
+        let resolved = store
+            .resolve("discord:1:2", Some("refunded"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.status, "resolved");
+        assert_eq!(resolved.resolution.as_deref(), Some("refunded"));
+        assert!(resolved.resolved_at.is_some());
+        assert!(
+            store
+                .open_for_channel("discord:1:2")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(store.resolve("discord:1:2", None).await.unwrap().is_none());
+
+        let reopened = store
+            .open("discord:1:2", "admin", "new question", None)
+            .await
+            .unwrap();
+        assert_ne!(reopened.id, first.id);
+    }
+}
@@ -1,9 +1,12 @@
 //! Prompt hooks for observing and controlling agent behavior.
 
+pub mod arg_validation;
 pub mod cortex;
+pub mod inbound;
 pub mod loop_guard;
 pub mod spacebot;
 
 pub use cortex::CortexHook;
+pub use inbound::{MessageHook, MessageHookAction, MessageHookChain, MessageHookDyn};
 pub use loop_guard::{LoopGuard, LoopGuardConfig, LoopGuardVerdict};
 pub use spacebot::{SpacebotHook, ToolNudgePolicy};
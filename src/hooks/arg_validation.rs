@@ -0,0 +1,349 @@
+//! Tool argument validation against the tool's published JSON schema.
+//!
+//! Models occasionally call tools with missing fields, wrong types, or values
+//! outside an enum. Left alone, the call fails inside the tool's argument
+//! deserialization with an opaque serde error. Checking the arguments against
+//! the schema first lets the hook hand back a message that names each bad
+//! field, so the model can correct the call on its next turn.
+//!
+//! This covers the subset of JSON Schema that native and MCP tools publish:
+//! `type`, `properties`, `required`, `additionalProperties: false`, `enum`,
+//! `const`, `items`, numeric and length bounds, `anyOf`/`oneOf`/`allOf`, and
+//! local `$ref`s. Unknown keywords are ignored rather than rejected.
+
+use serde_json::Value;
+
+/// Nesting limit for `$ref` and combinator recursion, so a cyclic schema
+/// can't hang the hook.
+const MAX_DEPTH: usize = 32;
+
+/// Check `args` (the raw JSON string the model sent) against `schema`.
+/// Returns one message per problem found.
+pub fn validate_tool_args(schema: &Value, args: &str) -> Result<(), Vec<String>> {
+    let value: Value = match serde_json::from_str(args) {
+        Ok(value) => value,
+        Err(error) => return Err(vec![format!("arguments are not valid JSON: {error}")]),
+    };
+
+    let mut errors = Vec::new();
+    validate(schema, schema, &value, "arguments", 0, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Tool result returned in place of executing a call with invalid arguments.
+pub fn format_validation_error(tool_name: &str, errors: &[String]) -> String {
+    let mut message = format!("Invalid arguments for `{tool_name}`:\n");
+    for error in errors {
+        message.push_str("- ");
+        message.push_str(error);
+        message.push('\n');
+    }
+    message
+        .push_str("The tool was not run. Fix the arguments to match its schema and call it again.");
+    message
+}
+
+fn validate(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    depth: usize,
+    errors: &mut Vec<String>,
+) {
+    let Some(schema) = schema.as_object() else {
+        // `true`, `{}`-like booleans and malformed schemas accept anything.
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: no value is allowed here"));
+        }
+        return;
+    };
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if let Some(target) = resolve_ref(root, reference) {
+            validate(root, target, value, path, depth + 1, errors);
+        }
+        return;
+    }
+
+    if let Some(types) = schema.get("type") {
+        let allowed: Vec<&str> = match types {
+            Value::String(single) => vec![single.as_str()],
+            Value::Array(many) => many.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|kind| matches_type(kind, value)) {
+            errors.push(format!(
+                "{path}: expected {}, got {}",
+                allowed.join(" or "),
+                describe(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array)
+        && !options.contains(value)
+    {
+        let options = options
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(format!("{path}: must be one of {options}, got {value}"));
+    }
+
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(format!("{path}: must be {expected}, got {value}"));
+    }
+
+    for (keyword, any) in [("anyOf", true), ("oneOf", true), ("allOf", false)] {
+        let Some(branches) = schema.get(keyword).and_then(Value::as_array) else {
+            continue;
+        };
+        if any {
+            let matched = branches.iter().any(|branch| {
+                let mut branch_errors = Vec::new();
+                validate(root, branch, value, path, depth + 1, &mut branch_errors);
+                branch_errors.is_empty()
+            });
+            if !matched {
+                errors.push(format!(
+                    "{path}: does not match any of the allowed shapes, got {}",
+                    describe(value)
+                ));
+            }
+        } else {
+            for branch in branches {
+                validate(root, branch, value, path, depth + 1, errors);
+            }
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|required| required.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_else(Vec::new);
+
+            for field in &required {
+                if object.get(*field).is_none_or(Value::is_null) {
+                    errors.push(format!("{}: missing required field", join(path, field)));
+                }
+            }
+
+            for (field, field_value) in object {
+                let field_path = join(path, field);
+                match properties.and_then(|properties| properties.get(field)) {
+                    // An explicit null for an optional field reads as "not
+                    // set", which is how the tools deserialize it.
+                    Some(_) if field_value.is_null() && !required.contains(&field.as_str()) => {}
+                    Some(field_schema) => {
+                        validate(
+                            root,
+                            field_schema,
+                            field_value,
+                            &field_path,
+                            depth + 1,
+                            errors,
+                        );
+                    }
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{field_path}: unknown field"));
+                        }
+                        Some(extra_schema @ Value::Object(_)) => {
+                            validate(
+                                root,
+                                extra_schema,
+                                field_value,
+                                &field_path,
+                                depth + 1,
+                                errors,
+                            );
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_bound(schema, "minItems", items.len(), path, "items", errors);
+            check_bound(schema, "maxItems", items.len(), path, "items", errors);
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    let item_path = format!("{path}[{index}]");
+                    validate(root, item_schema, item, &item_path, depth + 1, errors);
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count();
+            check_bound(schema, "minLength", length, path, "characters", errors);
+            check_bound(schema, "maxLength", length, path, "characters", errors);
+        }
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+                    && number < minimum
+                {
+                    errors.push(format!("{path}: must be at least {minimum}, got {number}"));
+                }
+                if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+                    && number > maximum
+                {
+                    errors.push(format!("{path}: must be at most {maximum}, got {number}"));
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+}
+
+fn matches_type(kind: &str, value: &Value) -> bool {
+    match kind {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn check_bound(
+    schema: &serde_json::Map<String, Value>,
+    keyword: &str,
+    actual: usize,
+    path: &str,
+    unit: &str,
+    errors: &mut Vec<String>,
+) {
+    let Some(bound) = schema.get(keyword).and_then(Value::as_u64) else {
+        return;
+    };
+    let bound = bound as usize;
+    let violated = if keyword.starts_with("min") {
+        actual < bound
+    } else {
+        actual > bound
+    };
+    if violated {
+        let limit = if keyword.starts_with("min") {
+            "at least"
+        } else {
+            "at most"
+        };
+        errors.push(format!(
+            "{path}: must have {limit} {bound} {unit}, got {actual}"
+        ));
+    }
+}
+
+/// Resolve a local `#/...` reference against the root schema.
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path == "arguments" {
+        field.to_string()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(flag) => format!("boolean {flag}"),
+        Value::Number(number) => format!("number {number}"),
+        Value::String(text) => {
+            let mut preview: String = text.chars().take(40).collect();
+            if preview.len() < text.len() {
+                preview.push('…');
+            }
+            format!("string \"{preview}\"")
+        }
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "minLength": 1 },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 50 },
+                "mode": { "type": "string", "enum": ["fast", "thorough"] },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/tag" } },
+                "note": { "type": "string" }
+            },
+            "required": ["query"],
+            "$defs": {
+                "tag": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } },
+                    "required": ["name"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn valid_args_pass() {
+        let args = r#"{"query":"rust","limit":10,"mode":"fast","tags":[{"name":"a"}],"note":null}"#;
+        assert!(validate_tool_args(&schema(), args).is_ok());
+    }
+
+    #[test]
+    fn each_problem_names_its_field() {
+        let args = r#"{"limit":"ten","mode":"slow","tags":[{"label":"a"}]}"#;
+        let errors = validate_tool_args(&schema(), args).unwrap_err();
+
+        assert!(errors.contains(&"query: missing required field".to_string()));
+        assert!(errors.contains(&"limit: expected integer, got string \"ten\"".to_string()));
+        assert!(
+            errors
+                .contains(&"mode: must be one of \"fast\", \"thorough\", got \"slow\"".to_string())
+        );
+        assert!(errors.contains(&"tags[0].name: missing required field".to_string()));
+        assert!(errors.contains(&"tags[0].label: unknown field".to_string()));
+    }
+
+    #[test]
+    fn bounds_and_malformed_json_are_reported() {
+        let errors = validate_tool_args(&schema(), r#"{"query":"","limit":99}"#).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&"query: must have at least 1 characters, got 0".to_string()));
+        assert!(errors.contains(&"limit: must be at most 50, got 99".to_string()));
+
+        let errors = validate_tool_args(&schema(), "{\"query\":").unwrap_err();
+        assert!(errors[0].starts_with("arguments are not valid JSON"));
+    }
+}
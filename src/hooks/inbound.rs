@@ -0,0 +1,207 @@
+//! Inbound message hooks that run in the main loop before routing.
+
+use crate::InboundMessage;
+
+use arc_swap::ArcSwap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Outcome of running an inbound hook over a message.
+#[derive(Debug, Clone)]
+pub enum MessageHookAction {
+    /// Keep processing with the (possibly transformed) message.
+    Continue(InboundMessage),
+    /// Short-circuit processing. The message is never routed to a channel.
+    Drop { reason: String },
+}
+
+/// Static trait for inbound message hooks.
+///
+/// Hooks may rewrite content, annotate `metadata`, or drop the message
+/// entirely. They run sequentially in registration order, so a hook sees the
+/// output of every hook registered before it.
+pub trait MessageHook: Send + Sync + 'static {
+    /// Unique name for this hook, used in logs.
+    fn name(&self) -> &str;
+
+    /// Inspect or transform an inbound message before it is routed.
+    fn on_inbound(&self, message: InboundMessage)
+    -> impl Future<Output = MessageHookAction> + Send;
+}
+
+/// Dynamic trait for runtime polymorphism.
+/// Use this when you need `Arc<dyn MessageHookDyn>` for storing different hooks.
+pub trait MessageHookDyn: Send + Sync + 'static {
+    fn name(&self) -> &str;
+
+    fn on_inbound<'a>(
+        &'a self,
+        message: InboundMessage,
+    ) -> Pin<Box<dyn Future<Output = MessageHookAction> + Send + 'a>>;
+}
+
+/// Blanket implementation: any type implementing MessageHook automatically implements MessageHookDyn.
+impl<T: MessageHook> MessageHookDyn for T {
+    fn name(&self) -> &str {
+        MessageHook::name(self)
+    }
+
+    fn on_inbound<'a>(
+        &'a self,
+        message: InboundMessage,
+    ) -> Pin<Box<dyn Future<Output = MessageHookAction> + Send + 'a>> {
+        Box::pin(MessageHook::on_inbound(self, message))
+    }
+}
+
+/// Ordered set of inbound hooks shared between the API and the main loop.
+///
+/// Backed by `ArcSwap` so registration never blocks message routing.
+#[derive(Default)]
+pub struct MessageHookChain {
+    hooks: ArcSwap<Vec<Arc<dyn MessageHookDyn>>>,
+}
+
+impl MessageHookChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a hook to the end of the chain.
+    pub fn register(&self, hook: Arc<dyn MessageHookDyn>) {
+        tracing::info!(hook = hook.name(), "registered inbound message hook");
+        self.hooks.rcu(|hooks| {
+            let mut hooks = Vec::clone(hooks);
+            hooks.push(hook.clone());
+            hooks
+        });
+    }
+
+    /// Remove every hook with the given name. Returns true if any were removed.
+    pub fn unregister(&self, name: &str) -> bool {
+        let previous = self.hooks.rcu(|hooks| {
+            hooks
+                .iter()
+                .filter(|hook| hook.name() != name)
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        previous.iter().any(|hook| hook.name() == name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.load().is_empty()
+    }
+
+    /// Run every registered hook in order, stopping at the first drop.
+    pub async fn run(&self, message: InboundMessage) -> MessageHookAction {
+        let hooks = self.hooks.load_full();
+        let mut message = message;
+        for hook in hooks.iter() {
+            match hook.on_inbound(message).await {
+                MessageHookAction::Continue(next) => message = next,
+                MessageHookAction::Drop { reason } => {
+                    tracing::debug!(hook = hook.name(), %reason, "inbound message dropped by hook");
+                    return MessageHookAction::Drop { reason };
+                }
+            }
+        }
+        MessageHookAction::Continue(message)
+    }
+}
+
+impl std::fmt::Debug for MessageHookChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hooks = self.hooks.load();
+        let names: Vec<&str> = hooks.iter().map(|hook| hook.name()).collect();
+        f.debug_struct("MessageHookChain")
+            .field("hooks", &names)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageContent;
+
+    struct StripPrefix;
+
+    impl MessageHook for StripPrefix {
+        fn name(&self) -> &str {
+            "strip_prefix"
+        }
+
+        async fn on_inbound(&self, mut message: InboundMessage) -> MessageHookAction {
+            if let MessageContent::Text(text) = &message.content
+                && let Some(stripped) = text.strip_prefix("!bot ")
+            {
+                message.content = MessageContent::Text(stripped.to_string());
+                message
+                    .metadata
+                    .insert("command_prefix".into(), serde_json::json!("!bot"));
+            }
+            MessageHookAction::Continue(message)
+        }
+    }
+
+    struct RejectSpam;
+
+    impl MessageHook for RejectSpam {
+        fn name(&self) -> &str {
+            "reject_spam"
+        }
+
+        async fn on_inbound(&self, message: InboundMessage) -> MessageHookAction {
+            if message.content.to_string().contains("buy now") {
+                MessageHookAction::Drop {
+                    reason: "spam".into(),
+                }
+            } else {
+                MessageHookAction::Continue(message)
+            }
+        }
+    }
+
+    fn text_message(text: &str) -> InboundMessage {
+        let mut message = InboundMessage::empty();
+        message.content = MessageContent::Text(text.into());
+        message
+    }
+
+    #[tokio::test]
+    async fn empty_chain_passes_message_through() {
+        let chain = MessageHookChain::new();
+        let action = chain.run(text_message("hello")).await;
+        let MessageHookAction::Continue(message) = action else {
+            panic!("expected continue");
+        };
+        assert_eq!(message.content.to_string(), "hello");
+    }
+
+    #[tokio::test]
+    async fn hooks_transform_in_order_and_short_circuit() {
+        let chain = MessageHookChain::new();
+        chain.register(Arc::new(StripPrefix));
+        chain.register(Arc::new(RejectSpam));
+
+        let MessageHookAction::Continue(message) = chain.run(text_message("!bot hi")).await else {
+            panic!("expected continue");
+        };
+        assert_eq!(message.content.to_string(), "hi");
+        assert!(message.metadata.contains_key("command_prefix"));
+
+        let action = chain.run(text_message("!bot buy now")).await;
+        assert!(matches!(action, MessageHookAction::Drop { reason } if reason == "spam"));
+    }
+
+    #[tokio::test]
+    async fn unregister_removes_hook_by_name() {
+        let chain = MessageHookChain::new();
+        chain.register(Arc::new(RejectSpam));
+        assert!(chain.unregister("reject_spam"));
+        assert!(!chain.unregister("reject_spam"));
+        assert!(chain.is_empty());
+    }
+}
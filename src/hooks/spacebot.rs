@@ -1,10 +1,12 @@
 //! SpacebotHook: Prompt hook for channels, branches, and workers.
 
+use crate::hooks::arg_validation::{format_validation_error, validate_tool_args};
 use crate::hooks::loop_guard::{LoopGuard, LoopGuardConfig, LoopGuardVerdict};
 use crate::tools::{MemoryPersistenceContractState, MemoryPersistenceTerminalOutcome};
 use crate::{AgentId, ChannelId, ProcessEvent, ProcessId, ProcessType};
 use rig::agent::{HookAction, PromptHook, ToolCallHookAction};
 use rig::completion::{CompletionModel, CompletionResponse, Message, Prompt, PromptError};
+use rig::tool::server::ToolServerHandle;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
@@ -61,8 +63,42 @@ pub struct SpacebotHook {
     /// append the messages to history before re-prompting.
     injected_messages: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
     memory_persistence_contract: Option<Arc<MemoryPersistenceContractState>>,
+    /// Where to surface model reasoning for the current turn. Channels set
+    /// this per turn when the conversation has reasoning visibility enabled.
+    reasoning_sender: std::sync::Arc<std::sync::Mutex<Option<crate::RoutedSender>>>,
+    /// Tools the agent has disabled. They are left out of the tool server, so
+    /// a call here means the model invented it; reject with a clear reason.
+    disabled_tools: std::sync::Arc<std::sync::Mutex<Arc<Vec<String>>>>,
+    /// Hard cap on output tokens per prompt request, summed across every
+    /// completion in the turn. Zero means uncapped.
+    output_token_cap: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Output tokens used so far by the current prompt request.
+    output_tokens_used: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Text of the completion that tripped the output cap, held for the
+    /// prompt loop to return in place of the aborted turn's result.
+    capped_output: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+    /// Token usage of the current turn, for outbound message economics.
+    /// Channels start it per turn; other processes leave it idle.
+    turn_usage: crate::llm::usage::TurnUsageTracker,
+    /// Per-turn tool call caps from config.
+    tool_call_limits: std::sync::Arc<std::sync::Mutex<crate::config::ToolCallLimits>>,
+    /// Tool calls made so far in the current prompt request, by tool name.
+    turn_tool_calls: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, u32>>>,
+    /// Set once a tool call cap trips, so the status event fires once per turn.
+    tool_call_limit_hit: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Tool server the process runs its tools on. When set, call arguments
+    /// are checked against the tool's schema before it executes.
+    tool_server: std::sync::Arc<std::sync::Mutex<Option<ToolServerHandle>>>,
+    /// Parameter schemas by tool name, loaded from `tool_server` on the first
+    /// call of a prompt request. Channels swap tools between turns, so the
+    /// cache is dropped with the rest of the per-prompt state.
+    tool_schemas: ToolSchemaCache,
 }
 
+type ToolSchemaCache = std::sync::Arc<
+    std::sync::Mutex<Option<std::sync::Arc<std::collections::HashMap<String, serde_json::Value>>>>,
+>;
+
 impl SpacebotHook {
     /// Prompt used to nudge tool-first behavior.
     pub const TOOL_NUDGE_PROMPT: &str = "You have not completed the task yet. Continue working using the available tools. \
@@ -75,6 +111,13 @@ impl SpacebotHook {
     /// PromptCancelled reason used for memory-persistence contract retries.
     pub const MEMORY_PERSISTENCE_CONTRACT_REASON: &str =
         "spacebot_memory_persistence_contract_retry";
+    /// PromptCancelled reason used when the output token cap is exceeded.
+    pub const OUTPUT_CAP_REASON: &str = "spacebot_output_cap_reached";
+    /// Channel tools that stay available after the tool call limit, so the
+    /// channel can still answer.
+    const CHANNEL_LIMIT_EXEMPT_TOOLS: &'static [&'static str] = &["reply", "skip"];
+    /// Note appended to the text returned from an output-capped turn.
+    pub const OUTPUT_CAP_NOTE: &str = "[output cap reached]";
     /// Maximum nudge retries per prompt request.
     pub const TOOL_NUDGE_MAX_RETRIES: usize = 2;
     /// Maximum completion-contract retries per prompt request.
@@ -115,6 +158,21 @@ impl SpacebotHook {
             inject_rx: None,
             injected_messages: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             memory_persistence_contract: None,
+            reasoning_sender: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            disabled_tools: std::sync::Arc::new(std::sync::Mutex::new(Arc::new(Vec::new()))),
+            output_token_cap: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            output_tokens_used: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            capped_output: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            turn_usage: crate::llm::usage::TurnUsageTracker::default(),
+            tool_call_limits: std::sync::Arc::new(std::sync::Mutex::new(
+                crate::config::ToolCallLimits::default(),
+            )),
+            turn_tool_calls: std::sync::Arc::new(std::sync::Mutex::new(
+                std::collections::HashMap::new(),
+            )),
+            tool_call_limit_hit: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            tool_server: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            tool_schemas: std::sync::Arc::new(std::sync::Mutex::new(None)),
         }
     }
 
@@ -161,9 +219,22 @@ impl SpacebotHook {
             .store(false, std::sync::atomic::Ordering::Relaxed);
         self.nudge_attempts
             .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.output_tokens_used
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        if let Ok(mut slot) = self.capped_output.lock() {
+            *slot = None;
+        }
         if let Ok(mut guard) = self.loop_guard.lock() {
             guard.reset();
         }
+        if let Ok(mut calls) = self.turn_tool_calls.lock() {
+            calls.clear();
+        }
+        self.tool_call_limit_hit
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        if let Ok(mut schemas) = self.tool_schemas.lock() {
+            *schemas = None;
+        }
     }
 
     fn set_tool_nudge_request_active(&self, active: bool) {
@@ -190,6 +261,160 @@ impl SpacebotHook {
         reason == Self::MEMORY_PERSISTENCE_CONTRACT_REASON
     }
 
+    /// Return true if a PromptCancelled reason indicates the output cap was hit.
+    pub fn is_output_cap_reason(reason: &str) -> bool {
+        reason == Self::OUTPUT_CAP_REASON
+    }
+
+    /// Surface reasoning from subsequent completions through `sender`, or stop
+    /// surfacing it with `None`.
+    pub fn set_reasoning_sender(&self, sender: Option<crate::RoutedSender>) {
+        if let Ok(mut slot) = self.reasoning_sender.lock() {
+            *slot = sender;
+        }
+    }
+
+    /// Reject calls to these tools. Long-lived processes refresh this when
+    /// the config is reloaded.
+    pub fn set_disabled_tools(&self, disabled_tools: Arc<Vec<String>>) {
+        if let Ok(mut slot) = self.disabled_tools.lock() {
+            *slot = disabled_tools;
+        }
+    }
+
+    pub fn with_disabled_tools(self, disabled_tools: Arc<Vec<String>>) -> Self {
+        self.set_disabled_tools(disabled_tools);
+        self
+    }
+
+    /// Cap the output tokens a single prompt request may generate. `None`
+    /// removes the cap. Long-lived processes refresh this when the config is
+    /// reloaded.
+    pub fn set_output_token_cap(&self, cap: Option<u64>) {
+        self.output_token_cap
+            .store(cap.unwrap_or(0), std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn with_output_token_cap(self, cap: Option<u64>) -> Self {
+        self.set_output_token_cap(cap);
+        self
+    }
+
+    /// The output cap, spend, and tool call limit state of the current
+    /// prompt request, for
+    /// [`SpacebotModel::with_turn_budget`](crate::llm::SpacebotModel::with_turn_budget).
+    pub fn turn_budget(&self) -> crate::llm::usage::TurnBudget {
+        let exempt_tools: &'static [&'static str] = if self.process_type == ProcessType::Channel {
+            Self::CHANNEL_LIMIT_EXEMPT_TOOLS
+        } else {
+            &[]
+        };
+        crate::llm::usage::TurnBudget::new(
+            self.output_token_cap.clone(),
+            self.output_tokens_used.clone(),
+            self.tool_call_limit_hit.clone(),
+            exempt_tools,
+        )
+    }
+
+    /// Cap the tool calls a single prompt request may make. Long-lived
+    /// processes refresh this when the config is reloaded.
+    pub fn set_tool_call_limits(&self, limits: crate::config::ToolCallLimits) {
+        if let Ok(mut slot) = self.tool_call_limits.lock() {
+            *slot = limits;
+        }
+    }
+
+    pub fn with_tool_call_limits(self, limits: crate::config::ToolCallLimits) -> Self {
+        self.set_tool_call_limits(limits);
+        self
+    }
+
+    /// Validate tool call arguments against the schemas this tool server
+    /// publishes. Processes that build their tool server after the hook set
+    /// it once the server is running.
+    pub fn set_tool_server(&self, tool_server: ToolServerHandle) {
+        if let Ok(mut slot) = self.tool_server.lock() {
+            *slot = Some(tool_server);
+        }
+        if let Ok(mut schemas) = self.tool_schemas.lock() {
+            *schemas = None;
+        }
+    }
+
+    pub fn with_tool_server(self, tool_server: ToolServerHandle) -> Self {
+        self.set_tool_server(tool_server);
+        self
+    }
+
+    /// Check a call's arguments against the tool's published schema. Returns
+    /// the message to hand back in place of the result when they don't fit.
+    async fn check_tool_args(&self, tool_name: &str, args: &str) -> Option<String> {
+        let schemas = self.tool_schemas().await?;
+        let schema = schemas.get(tool_name)?;
+
+        let errors = validate_tool_args(schema, args).err()?;
+        tracing::warn!(
+            process_id = %self.process_id,
+            tool_name = %tool_name,
+            errors = ?errors,
+            "tool call arguments failed schema validation"
+        );
+        Some(format_validation_error(tool_name, &errors))
+    }
+
+    /// Parameter schemas of the tool server's tools, cached for the prompt
+    /// request.
+    async fn tool_schemas(
+        &self,
+    ) -> Option<std::sync::Arc<std::collections::HashMap<String, serde_json::Value>>> {
+        if let Some(schemas) = self.tool_schemas.lock().ok()?.clone() {
+            return Some(schemas);
+        }
+
+        let tool_server = self.tool_server.lock().ok()?.clone()?;
+        let definitions = match tool_server.get_tool_defs(None).await {
+            Ok(definitions) => definitions,
+            Err(error) => {
+                tracing::debug!(%error, "failed to load tool definitions for argument validation");
+                return None;
+            }
+        };
+        let schemas = std::sync::Arc::new(
+            definitions
+                .into_iter()
+                .map(|definition| (definition.name, definition.parameters))
+                .collect::<std::collections::HashMap<_, _>>(),
+        );
+        if let Ok(mut slot) = self.tool_schemas.lock() {
+            *slot = Some(schemas.clone());
+        }
+        Some(schemas)
+    }
+
+    /// Usage tracker fed by every completion this hook observes.
+    pub fn turn_usage(&self) -> &crate::llm::usage::TurnUsageTracker {
+        &self.turn_usage
+    }
+
+    /// Finish a turn that was aborted by the output cap: record what the
+    /// model produced in history and return it with the cap note.
+    fn finish_output_capped_turn(&self, history: &mut Vec<Message>) -> String {
+        let produced = self
+            .capped_output
+            .lock()
+            .ok()
+            .and_then(|mut slot| slot.take())
+            .unwrap_or_default();
+        let text = if produced.trim().is_empty() {
+            Self::OUTPUT_CAP_NOTE.to_string()
+        } else {
+            format!("{}\n\n{}", produced.trim_end(), Self::OUTPUT_CAP_NOTE)
+        };
+        history.push(Message::assistant(text.clone()));
+        text
+    }
+
     /// Drain and return all buffered injected messages.
     pub fn take_injected_messages(&self) -> Vec<String> {
         self.injected_messages
@@ -227,6 +452,13 @@ impl SpacebotHook {
                 .await;
 
             match &result {
+                Err(PromptError::PromptCancelled { reason, .. })
+                    if Self::is_output_cap_reason(reason) =>
+                {
+                    self.set_tool_nudge_request_active(false);
+                    self.set_completion_contract_request_active(false);
+                    return Ok(self.finish_output_capped_turn(history));
+                }
                 // Context injection: the hook detected pending injected
                 // messages and terminated the agent loop. Drain the buffer,
                 // append each message to history as a User message, and
@@ -373,11 +605,19 @@ impl SpacebotHook {
     {
         self.reset_tool_nudge_state();
         self.set_tool_nudge_request_active(false);
-        agent
+        let result = agent
             .prompt(prompt)
-            .with_history(history)
+            .with_history(&mut *history)
             .with_hook(self.clone())
-            .await
+            .await;
+        match result {
+            Err(PromptError::PromptCancelled { reason, .. })
+                if Self::is_output_cap_reason(&reason) =>
+            {
+                Ok(self.finish_output_capped_turn(history))
+            }
+            other => other,
+        }
     }
 
     /// Send a status update event.
@@ -497,13 +737,20 @@ impl SpacebotHook {
     pub(crate) fn emit_tool_completed_event(&self, tool_name: &str, result: &str) {
         let capped_result =
             crate::tools::truncate_output(result, crate::tools::MAX_TOOL_OUTPUT_BYTES);
-        self.emit_tool_completed_event_from_capped(tool_name, capped_result);
+        self.emit_tool_completed_event_from_capped(
+            tool_name,
+            capped_result,
+            crate::tools::result_limit::truncated_bytes(result),
+        );
     }
 
+    /// Emit `ToolCompleted` with an already-capped payload. `truncated_bytes`
+    /// must come from the uncapped result, since capping can cut the marker.
     pub(crate) fn emit_tool_completed_event_from_capped(
         &self,
         tool_name: &str,
         capped_result: String,
+        truncated_bytes: Option<usize>,
     ) {
         let event = ProcessEvent::ToolCompleted {
             agent_id: self.agent_id.clone(),
@@ -511,10 +758,114 @@ impl SpacebotHook {
             channel_id: self.channel_id.clone(),
             tool_name: tool_name.to_string(),
             result: capped_result,
+            truncated_bytes,
         };
         self.event_tx.send(event).ok();
     }
 
+    /// Count a tool call against the turn's caps. Returns the tool result to
+    /// hand back instead of running the tool once a cap has been reached.
+    ///
+    /// Channel `reply` and `skip` are exempt so the model can still deliver
+    /// its final answer. Tripping a cap counts as a worker outcome, so the
+    /// text answer that follows isn't nudged back into tool use.
+    fn check_tool_call_limits(&self, tool_name: &str) -> Option<String> {
+        if self.process_type == ProcessType::Channel
+            && Self::CHANNEL_LIMIT_EXEMPT_TOOLS.contains(&tool_name)
+        {
+            return None;
+        }
+        let limits = self.tool_call_limits.lock().map(|limits| *limits).ok()?;
+        let mut calls = self.turn_tool_calls.lock().ok()?;
+
+        let total: u32 = calls.values().sum();
+        let repeats = calls.get(tool_name).copied().unwrap_or(0);
+        let exceeded =
+            if limits.max_tool_calls_per_turn > 0 && total >= limits.max_tool_calls_per_turn {
+                format!("{total} tool calls this turn")
+            } else if limits.max_repeat_same_tool > 0 && repeats >= limits.max_repeat_same_tool {
+                format!("`{tool_name}` called {repeats} times this turn")
+            } else {
+                *calls.entry(tool_name.to_string()).or_insert(0) += 1;
+                return None;
+            };
+        drop(calls);
+
+        if !self
+            .tool_call_limit_hit
+            .swap(true, std::sync::atomic::Ordering::Relaxed)
+        {
+            tracing::warn!(
+                process_id = %self.process_id,
+                process_type = %self.process_type,
+                tool_name = %tool_name,
+                "tool call limit reached: {exceeded}"
+            );
+            self.send_status(format!("tool call limit reached ({exceeded})"));
+        }
+        self.outcome_signaled
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        let next_step = if self.process_type == ProcessType::Channel {
+            "Do not call any other tools; reply to the user now"
+        } else {
+            "Do not call any more tools; give your final answer now"
+        };
+        Some(format!(
+            "Tool call limit reached ({exceeded}). {next_step} using what you already have."
+        ))
+    }
+
+    /// Add a completion's output tokens to the turn total and report whether
+    /// the turn has now reached the output cap. Emits a status event and
+    /// holds the completion's text for the prompt loop when it has.
+    ///
+    /// Completions from providers that don't report usage are counted from
+    /// an estimate of their content, so the cap still applies to them.
+    fn exceeds_output_cap<M>(&self, response: &CompletionResponse<M::Response>) -> bool
+    where
+        M: CompletionModel,
+    {
+        let output_tokens = if response.usage.output_tokens > 0 {
+            response.usage.output_tokens
+        } else {
+            crate::agent::compactor::estimate_completion_tokens(response.choice.iter())
+        };
+        let used = self
+            .output_tokens_used
+            .fetch_add(output_tokens, std::sync::atomic::Ordering::Relaxed)
+            + output_tokens;
+        let cap = self
+            .output_token_cap
+            .load(std::sync::atomic::Ordering::Relaxed);
+        if cap == 0 || used < cap {
+            return false;
+        }
+
+        tracing::warn!(
+            process_id = %self.process_id,
+            process_type = %self.process_type,
+            used,
+            cap,
+            "output token cap reached, aborting generation"
+        );
+        self.send_status(format!("output cap reached ({used}/{cap} tokens)"));
+
+        let text = response
+            .choice
+            .iter()
+            .filter_map(|content| match content {
+                rig::message::AssistantContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if let Ok(mut slot) = self.capped_output.lock() {
+            *slot = Some(text);
+        }
+        true
+    }
+
     /// Decide whether a text-only response should be rejected and nudged back
     /// into tool usage.
     ///
@@ -712,6 +1063,14 @@ where
             "completion response received"
         );
 
+        self.turn_usage.record(&response.usage);
+
+        if self.exceeds_output_cap::<M>(response) {
+            return HookAction::Terminate {
+                reason: Self::OUTPUT_CAP_REASON.into(),
+            };
+        }
+
         if self.should_nudge_tool_usage::<M>(response) {
             return HookAction::Terminate {
                 reason: Self::TOOL_NUDGE_REASON.into(),
@@ -724,6 +1083,37 @@ where
             };
         }
 
+        // Surface reasoning before any tool call (such as `reply`) runs, so it
+        // reaches the conversation ahead of the answer.
+        let reasoning_sender = self
+            .reasoning_sender
+            .lock()
+            .ok()
+            .and_then(|slot| slot.clone());
+        if let Some(sender) = reasoning_sender {
+            let reasoning = response
+                .choice
+                .iter()
+                .filter_map(|content| match content {
+                    rig::message::AssistantContent::Reasoning(reasoning) => Some(
+                        crate::llm::model::collect_reasoning_text_parts(reasoning).join("\n\n"),
+                    ),
+                    _ => None,
+                })
+                .filter(|text| !text.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if self.scan_for_leaks(&reasoning).is_some() {
+                tracing::warn!(process_id = %self.process_id, "withheld reasoning matching secret pattern");
+            } else if !reasoning.is_empty()
+                && let Err(error) = sender
+                    .send(crate::OutboundResponse::Reasoning(reasoning))
+                    .await
+            {
+                tracing::debug!(%error, process_id = %self.process_id, "failed to surface reasoning");
+            }
+        }
+
         // Emit text content from worker completion responses so the live
         // transcript can show the model's reasoning between tool calls.
         if self.process_type == ProcessType::Worker {
@@ -785,6 +1175,28 @@ where
         _internal_call_id: &str,
         args: &str,
     ) -> ToolCallHookAction {
+        let disabled = self
+            .disabled_tools
+            .lock()
+            .map(|tools| crate::tools::is_tool_disabled(&tools, tool_name))
+            .unwrap_or(false);
+        if disabled {
+            tracing::warn!(
+                process_id = %self.process_id,
+                tool_name = %tool_name,
+                "model called a disabled tool"
+            );
+            return ToolCallHookAction::Skip {
+                reason: format!(
+                    "The `{tool_name}` tool is disabled for this agent. Continue without it."
+                ),
+            };
+        }
+
+        if let Some(reason) = self.check_tool_call_limits(tool_name) {
+            return ToolCallHookAction::Skip { reason };
+        }
+
         // Loop guard: check for repetitive tool calling before execution.
         // Runs for all process types. Block → Skip (message becomes tool
         // result), CircuitBreak → Terminate.
@@ -810,6 +1222,13 @@ where
             }
         }
 
+        // Malformed arguments come back as the tool result, naming each bad
+        // field, so the model can correct the call instead of hitting an
+        // opaque deserialization error.
+        if let Some(reason) = self.check_tool_args(tool_name, args).await {
+            return ToolCallHookAction::Skip { reason };
+        }
+
         // Leak blocking is enforced at channel egress (`reply`). Worker and
         // branch tool calls may legitimately handle secrets internally.
         if self.process_type == ProcessType::Channel
@@ -894,7 +1313,11 @@ where
             let scrubbed = crate::secrets::scrub::scrub_leaks(result);
             let capped =
                 crate::tools::truncate_output(&scrubbed, crate::tools::MAX_TOOL_OUTPUT_BYTES);
-            self.emit_tool_completed_event_from_capped(tool_name, capped);
+            self.emit_tool_completed_event_from_capped(
+                tool_name,
+                capped,
+                crate::tools::result_limit::truncated_bytes(result),
+            );
         } else {
             self.emit_tool_completed_event(tool_name, result);
         }
@@ -981,7 +1404,7 @@ mod tests {
     use crate::tools::MemoryPersistenceContractState;
     use crate::{ProcessId, ProcessType};
     use rig::OneOrMany;
-    use rig::agent::{HookAction, PromptHook};
+    use rig::agent::{HookAction, PromptHook, ToolCallHookAction};
     use rig::completion::{CompletionResponse, Message, Usage};
     use rig::message::AssistantContent;
     use std::sync::Arc;
@@ -1095,6 +1518,206 @@ mod tests {
         assert!(matches!(response, HookAction::Continue));
     }
 
+    #[tokio::test]
+    async fn disabled_tool_call_is_rejected_with_reason() {
+        let hook = make_hook().with_disabled_tools(Arc::new(vec!["browser_navigate".into()]));
+
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+            &hook,
+            "browser_navigate",
+            None,
+            "internal_1",
+            "{\"url\":\"https://example.com\"}",
+        )
+        .await;
+        assert!(matches!(
+            action,
+            ToolCallHookAction::Skip { ref reason } if reason.contains("disabled")
+        ));
+
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+            &hook,
+            "shell",
+            None,
+            "internal_2",
+            "{}",
+        )
+        .await;
+        assert!(matches!(action, ToolCallHookAction::Continue));
+    }
+
+    #[derive(Clone)]
+    struct LookupTool;
+
+    #[derive(serde::Deserialize)]
+    struct LookupArgs {
+        #[allow(dead_code)]
+        query: String,
+    }
+
+    impl rig::tool::Tool for LookupTool {
+        const NAME: &'static str = "lookup";
+
+        type Error = std::io::Error;
+        type Args = LookupArgs;
+        type Output = String;
+
+        async fn definition(&self, _prompt: String) -> rig::completion::ToolDefinition {
+            rig::completion::ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: "Look something up.".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string" },
+                        "limit": { "type": "integer" }
+                    },
+                    "required": ["query"]
+                }),
+            }
+        }
+
+        async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+            Ok("found".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_tool_args_come_back_as_a_validation_result() {
+        let tool_server = rig::tool::server::ToolServer::new().tool(LookupTool).run();
+        let hook = make_hook().with_tool_server(tool_server);
+
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+            &hook,
+            "lookup",
+            None,
+            "internal_1",
+            "{\"limit\":\"five\"}",
+        )
+        .await;
+        let ToolCallHookAction::Skip { reason } = action else {
+            panic!("invalid arguments should skip the call");
+        };
+        assert!(reason.starts_with("Invalid arguments for `lookup`"));
+        assert!(reason.contains("- query: missing required field"));
+        assert!(reason.contains("- limit: expected integer, got string \"five\""));
+
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+            &hook,
+            "lookup",
+            None,
+            "internal_2",
+            "{\"query\":\"rust\",\"limit\":5}",
+        )
+        .await;
+        assert!(matches!(action, ToolCallHookAction::Continue));
+    }
+
+    #[tokio::test]
+    async fn tool_schemas_are_cached_until_the_next_prompt() {
+        let tool_server = rig::tool::server::ToolServer::new().run();
+        let hook = make_hook().with_tool_server(tool_server.clone());
+        hook.reset_tool_nudge_state();
+
+        // Distinct arguments per call keep the loop guard out of the way.
+        let call = |args: &'static str| {
+            <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+                &hook,
+                "lookup",
+                None,
+                "internal_1",
+                args,
+            )
+        };
+
+        // Unknown tools aren't validated.
+        assert!(matches!(
+            call("{\"limit\":\"one\"}").await,
+            ToolCallHookAction::Continue
+        ));
+        tool_server.add_tool(LookupTool).await.unwrap();
+        // Schemas loaded for this prompt request are reused.
+        assert!(matches!(
+            call("{\"limit\":\"two\"}").await,
+            ToolCallHookAction::Continue
+        ));
+
+        hook.reset_tool_nudge_state();
+        assert!(matches!(
+            call("{\"limit\":\"three\"}").await,
+            ToolCallHookAction::Skip { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn tool_call_limits_skip_calls_past_the_cap() {
+        let (event_tx, mut event_rx) = tokio::sync::broadcast::channel(16);
+        let hook = SpacebotHook::new(
+            std::sync::Arc::<str>::from("agent"),
+            ProcessId::Worker(uuid::Uuid::new_v4()),
+            ProcessType::Worker,
+            None,
+            event_tx,
+        )
+        .with_tool_call_limits(crate::config::ToolCallLimits {
+            max_tool_calls_per_turn: 3,
+            max_repeat_same_tool: 2,
+        });
+        hook.reset_tool_nudge_state();
+
+        let call = |tool_name: &'static str, call_id: &'static str| {
+            let hook = hook.clone();
+            async move {
+                <SpacebotHook as PromptHook<SpacebotModel>>::on_tool_call(
+                    &hook, tool_name, None, call_id, call_id,
+                )
+                .await
+            }
+        };
+
+        assert!(matches!(
+            call("shell", "1").await,
+            ToolCallHookAction::Continue
+        ));
+        assert!(matches!(
+            call("shell", "2").await,
+            ToolCallHookAction::Continue
+        ));
+        assert!(matches!(
+            call("shell", "3").await,
+            ToolCallHookAction::Skip { ref reason } if reason.contains("`shell` called 2 times")
+        ));
+        assert!(hook.outcome_signaled());
+
+        assert!(matches!(
+            call("file", "4").await,
+            ToolCallHookAction::Continue
+        ));
+        assert!(matches!(
+            call("read_skill", "5").await,
+            ToolCallHookAction::Skip { ref reason } if reason.contains("3 tool calls this turn")
+        ));
+
+        // The status event fires once per turn.
+        let mut limit_statuses = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if let ProcessEvent::StatusUpdate { status, .. } = event {
+                limit_statuses.push(status);
+            }
+        }
+        assert_eq!(
+            limit_statuses,
+            vec!["tool call limit reached (`shell` called 2 times this turn)".to_string()]
+        );
+
+        // A new prompt request starts with a fresh budget.
+        hook.reset_tool_nudge_state();
+        assert!(matches!(
+            call("shell", "6").await,
+            ToolCallHookAction::Continue
+        ));
+    }
+
     #[tokio::test]
     async fn nudges_after_tool_calls_without_outcome() {
         // This is the exact bug case: worker calls read_skill + set_status(progress),
@@ -1513,6 +2136,94 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn output_cap_terminates_turn_and_returns_produced_text() {
+        let (event_tx, mut event_rx) = tokio::sync::broadcast::channel(8);
+        let hook = SpacebotHook::new(
+            std::sync::Arc::<str>::from("agent"),
+            ProcessId::Branch(uuid::Uuid::new_v4()),
+            ProcessType::Branch,
+            None,
+            event_tx,
+        )
+        .with_output_token_cap(Some(100));
+        let prompt = prompt_message();
+        hook.reset_tool_nudge_state();
+
+        // The cap applies to the whole turn, not to a single completion.
+        let mut first = tool_call_response();
+        first.usage.output_tokens = 60;
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_completion_response(
+            &hook, &prompt, &first,
+        )
+        .await;
+        assert!(matches!(action, HookAction::Continue));
+
+        let mut second = text_response("partial answer");
+        second.usage.output_tokens = 60;
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_completion_response(
+            &hook, &prompt, &second,
+        )
+        .await;
+        assert!(matches!(
+            action,
+            HookAction::Terminate { ref reason } if SpacebotHook::is_output_cap_reason(reason)
+        ));
+
+        let event = event_rx.recv().await.expect("status event");
+        assert!(matches!(
+            event,
+            ProcessEvent::StatusUpdate { ref status, .. } if status.contains("output cap reached")
+        ));
+
+        let mut history = vec![Message::from("task")];
+        let text = hook.finish_output_capped_turn(&mut history);
+        assert_eq!(text, "partial answer\n\n[output cap reached]");
+        assert_eq!(history.len(), 2);
+        assert!(matches!(history[1], Message::Assistant { .. }));
+
+        // A new prompt request starts with a fresh budget.
+        hook.reset_tool_nudge_state();
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_completion_response(
+            &hook, &prompt, &second,
+        )
+        .await;
+        assert!(matches!(action, HookAction::Continue));
+    }
+
+    #[tokio::test]
+    async fn output_cap_estimates_tokens_when_usage_is_missing() {
+        let (event_tx, _event_rx) = tokio::sync::broadcast::channel(8);
+        let hook = SpacebotHook::new(
+            std::sync::Arc::<str>::from("agent"),
+            ProcessId::Branch(uuid::Uuid::new_v4()),
+            ProcessType::Branch,
+            None,
+            event_tx,
+        )
+        .with_output_token_cap(Some(100));
+        let prompt = prompt_message();
+        hook.reset_tool_nudge_state();
+
+        // No usage reported: roughly 50 tokens of text per completion.
+        let response = text_response(&"word ".repeat(40));
+        assert_eq!(response.usage.output_tokens, 0);
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_completion_response(
+            &hook, &prompt, &response,
+        )
+        .await;
+        assert!(matches!(action, HookAction::Continue));
+
+        let action = <SpacebotHook as PromptHook<SpacebotModel>>::on_completion_response(
+            &hook, &prompt, &response,
+        )
+        .await;
+        assert!(matches!(
+            action,
+            HookAction::Terminate { ref reason } if SpacebotHook::is_output_cap_reason(reason)
+        ));
+    }
+
     #[tokio::test]
     async fn tool_result_resets_consecutive_nudge_counter() {
         // The exact scenario from the Railway browser worker failure:
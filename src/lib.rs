@@ -9,7 +9,9 @@ pub mod cron;
 pub mod daemon;
 pub mod db;
 pub mod error;
+pub mod event_sink;
 pub mod factory;
+pub mod handoff;
 pub mod github_copilot_auth;
 pub mod hooks;
 pub mod identity;
@@ -18,6 +20,7 @@ pub mod llm;
 pub mod mcp;
 pub mod memory;
 pub mod messaging;
+pub mod notes;
 pub mod openai_auth;
 pub mod opencode;
 pub mod projects;
@@ -77,6 +80,29 @@ impl std::fmt::Display for ProcessId {
     }
 }
 
+/// Parses the `Display` form, e.g. `worker:<uuid>` or `channel:discord:123`.
+impl std::str::FromStr for ProcessId {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let (kind, id) = value
+            .split_once(':')
+            .ok_or_else(|| format!("process id '{value}' has no type prefix"))?;
+        match kind {
+            "channel" if !id.is_empty() => Ok(ProcessId::Channel(id.into())),
+            "worker" => id
+                .parse()
+                .map(ProcessId::Worker)
+                .map_err(|error| format!("invalid worker id '{id}': {error}")),
+            "branch" => id
+                .parse()
+                .map(ProcessId::Branch)
+                .map_err(|error| format!("invalid branch id '{id}': {error}")),
+            _ => Err(format!("process id '{value}' is not a channel, worker, or branch")),
+        }
+    }
+}
+
 /// Process types in the system.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -145,6 +171,16 @@ pub fn classify_broadcast_recv_result<T>(
     }
 }
 
+/// What made the compactor fire for a channel.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionTrigger {
+    /// Estimated context usage crossed one of the compaction thresholds.
+    TokenRatio,
+    /// The channel exceeded `compaction.max_turns_before_compact` turns.
+    TurnCount,
+}
+
 /// Events sent between processes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -208,6 +244,8 @@ pub enum ProcessEvent {
         channel_id: Option<ChannelId>,
         tool_name: String,
         result: String,
+        /// Bytes dropped when the result exceeded the tool's size cap.
+        truncated_bytes: Option<usize>,
     },
     MemorySaved {
         agent_id: AgentId,
@@ -217,10 +255,43 @@ pub enum ProcessEvent {
         importance: f32,
         content_summary: String,
     },
+    /// A memory was permanently deleted (e.g. a user asked the agent to forget).
+    MemoryDeleted {
+        agent_id: AgentId,
+        memory_id: String,
+        channel_id: Option<ChannelId>,
+    },
+    /// The cortex folded a cluster of related memories into one new memory
+    /// and marked the originals forgotten.
+    MemoriesConsolidated {
+        agent_id: AgentId,
+        memory_id: String,
+        archived_memory_ids: Vec<String>,
+    },
     CompactionTriggered {
         agent_id: AgentId,
         channel_id: ChannelId,
         threshold_reached: f32,
+        reason: CompactionTrigger,
+    },
+    /// A running compaction moved on to a new stage.
+    CompactionProgress {
+        agent_id: AgentId,
+        channel_id: ChannelId,
+        status: String,
+    },
+    /// A compaction finished. `turns_compacted` is 0 when nothing was removed
+    /// (history too short, or the run failed).
+    CompactionCompleted {
+        agent_id: AgentId,
+        channel_id: ChannelId,
+        turns_compacted: usize,
+        /// Estimated token size of the inserted summary.
+        summary_tokens: usize,
+        /// Budget the summary was held to.
+        summary_max_tokens: usize,
+        /// Estimated token size of the channel history afterwards.
+        context_tokens: usize,
     },
     StatusUpdate {
         agent_id: AgentId,
@@ -406,6 +477,9 @@ pub struct AgentDeps {
     pub task_store_registry:
         Arc<arc_swap::ArcSwap<std::collections::HashMap<String, Arc<tasks::TaskStore>>>>,
     pub process_control_registry: Arc<agent::process_control::ProcessControlRegistry>,
+    /// Slots shared by every process this agent runs, capped by
+    /// `max_concurrent_processes`.
+    pub process_limiter: Arc<agent::process_limit::ProcessLimiter>,
     /// Sender for injecting messages into channels from outside the normal
     /// inbound message flow (e.g. cross-agent task completion notifications).
     pub injection_tx: tokio::sync::mpsc::Sender<ChannelInjection>,
@@ -425,6 +499,20 @@ impl AgentDeps {
     pub fn routing(&self) -> arc_swap::Guard<Arc<llm::RoutingConfig>> {
         self.runtime_config.routing.load()
     }
+
+    /// Wait for a slot under the agent's `max_concurrent_processes` cap.
+    pub async fn acquire_process_permit(
+        &self,
+        priority: agent::process_limit::ProcessPriority,
+    ) -> agent::process_limit::ProcessPermit {
+        let runtime_config = self.runtime_config.clone();
+        self.process_limiter
+            .acquire(
+                move || **runtime_config.max_concurrent_processes.load(),
+                priority,
+            )
+            .await
+    }
 }
 
 /// A running agent instance with all its isolated resources.
@@ -450,7 +538,15 @@ pub mod metadata_keys {
     /// Set on retrigger metadata when a branch/worker completes.
     pub const REPLY_TO_MESSAGE_ID: &str = "reply_to_message_id";
     /// Quoted reply text preview from the message being replied to.
+    /// Capped at `messaging::traits::REPLY_TO_TEXT_MAX_BYTES`.
     pub const REPLY_TO_TEXT: &str = "reply_to_text";
+    /// Display name of the author of the message being replied to.
+    pub const REPLY_TO_AUTHOR: &str = "reply_to_author";
+    /// Per-attachment content-safety reports from inbound media screening.
+    pub const MEDIA_SAFETY: &str = "media_safety";
+    /// Delivery target (`adapter:target`) of the cron job that sent a
+    /// synthetic prompt.
+    pub const CRON_DELIVERY_TARGET: &str = "cron_delivery_target";
 }
 
 /// Inbound message from any messaging platform.
@@ -548,6 +644,18 @@ pub enum MessageContent {
         /// Platform-specific message reference (`ts` on Slack, message ID on Discord).
         message_ts: Option<String>,
     },
+    /// An emoji reaction was added to a message.
+    ///
+    /// Produced by Slack and Discord adapters. The channel only acts on reactions
+    /// whose emoji is mapped to an intent in `reaction_triggers`; others are dropped.
+    Reaction {
+        /// The emoji as reported by the platform (unicode on Discord, short-code on Slack).
+        emoji: String,
+        /// Platform-specific ID of the reacted-to message (`ts` on Slack, message ID on Discord).
+        message_id: String,
+        /// Text of the reacted-to message, when the adapter could fetch it.
+        message_text: Option<String>,
+    },
 }
 
 impl std::fmt::Display for MessageContent {
@@ -575,6 +683,17 @@ impl std::fmt::Display for MessageContent {
                     write!(f, "[interaction: {}]", action_id)
                 }
             }
+            MessageContent::Reaction {
+                emoji,
+                message_id,
+                message_text,
+            } => {
+                if let Some(text) = message_text {
+                    write!(f, "[reaction: {} on message {}: {}]", emoji, message_id, text)
+                } else {
+                    write!(f, "[reaction: {} on message {}]", emoji, message_id)
+                }
+            }
         }
     }
 }
@@ -602,6 +721,8 @@ pub struct Attachment {
 pub struct RoutedResponse {
     pub response: OutboundResponse,
     pub target: InboundMessage,
+    /// Usage of the turn that produced the response, when it came from one.
+    pub usage: Option<llm::usage::MessageUsage>,
 }
 
 /// A sender that automatically pairs outbound responses with a captured
@@ -611,21 +732,43 @@ pub struct RoutedResponse {
 pub struct RoutedSender {
     inner: mpsc::Sender<RoutedResponse>,
     target: InboundMessage,
+    usage: Option<llm::usage::TurnUsageTracker>,
 }
 
 impl RoutedSender {
     pub fn new(inner: mpsc::Sender<RoutedResponse>, target: InboundMessage) -> Self {
-        Self { inner, target }
+        Self {
+            inner,
+            target,
+            usage: None,
+        }
+    }
+
+    /// The inbound message responses are routed back to.
+    pub fn target(&self) -> &InboundMessage {
+        &self.target
+    }
+
+    /// Attach the usage taken from `tracker` to each message response sent.
+    pub fn with_usage(mut self, tracker: llm::usage::TurnUsageTracker) -> Self {
+        self.usage = Some(tracker);
+        self
     }
 
     pub async fn send(
         &self,
         response: OutboundResponse,
     ) -> std::result::Result<(), mpsc::error::SendError<RoutedResponse>> {
+        let usage = self
+            .usage
+            .as_ref()
+            .filter(|_| response.is_message())
+            .and_then(|tracker| tracker.take());
         self.inner
             .send(RoutedResponse {
                 response,
                 target: self.target.clone(),
+                usage,
             })
             .await
     }
@@ -655,8 +798,18 @@ pub enum OutboundResponse {
     /// Remove a reaction emoji from the triggering message.
     /// No-op on platforms that don't support reaction removal.
     RemoveReaction(String),
+    /// Send a platform sticker: a sticker ID on Discord, a sticker `file_id`
+    /// on Telegram. Other adapters send `fallback` as plain text instead, or
+    /// nothing when it's absent.
+    Sticker {
+        id: String,
+        #[serde(default)]
+        fallback: Option<String>,
+    },
     /// Send a message visible only to the triggering user (ephemeral).
-    /// Falls back to a regular `Text` message on platforms that don't support it.
+    /// Discord sends it as a follow-up on the triggering interaction, so it only
+    /// works when replying to a button click or select. Falls back to a regular
+    /// `Text` message otherwise and on platforms that don't support it.
     Ephemeral {
         /// The message text (mrkdwn on Slack, plain text on others).
         text: String,
@@ -692,13 +845,47 @@ pub enum OutboundResponse {
         /// Unix epoch seconds when the message should be delivered.
         post_at: i64,
     },
+    /// Publish a Slack App Home tab view for a user via `views.publish`.
+    /// Replaces the user's whole Home tab. Ignored by other adapters.
+    HomeView {
+        /// The user whose Home tab is replaced.
+        user_id: String,
+        /// Slack Block Kit blocks, serialised as raw JSON like `RichMessage::blocks`.
+        blocks: Vec<serde_json::Value>,
+    },
+    /// Model reasoning/thinking for the current turn, sent ahead of the reply
+    /// when the conversation has reasoning visibility enabled. Adapters render
+    /// it collapsed where the platform allows, otherwise as a quoted block.
+    Reasoning(String),
+    /// Begin a streamed reply. Adapters that render streams post a
+    /// placeholder message that later chunks edit.
     StreamStart,
+    /// The full text of the streamed reply so far, not a delta. Each chunk
+    /// replaces the previous one.
     StreamChunk(String),
+    /// Finish the streamed reply; its last chunk is the final text.
     StreamEnd,
     Status(StatusUpdate),
 }
 
 impl OutboundResponse {
+    /// Whether this response is shown as a message, and so reported as an
+    /// outbound message carrying the usage of the turn that produced it. A
+    /// streamed reply counts once, at `StreamEnd`.
+    pub fn is_message(&self) -> bool {
+        matches!(
+            self,
+            OutboundResponse::Text(_)
+                | OutboundResponse::StreamEnd
+                | OutboundResponse::RichMessage { .. }
+                | OutboundResponse::ThreadReply { .. }
+                | OutboundResponse::Sticker {
+                    fallback: Some(_),
+                    ..
+                }
+        )
+    }
+
     /// Ensure `RichMessage` variants have a non-empty `text` fallback.
     ///
     /// Some LLMs emit card-only payloads with empty content. This derives a
@@ -752,6 +939,24 @@ impl OutboundResponse {
         }
         sections.join("\n\n")
     }
+
+    /// Render reasoning as a labelled blockquote for adapters without a
+    /// collapsible presentation.
+    pub fn reasoning_as_quote(reasoning: &str) -> String {
+        let quoted = reasoning
+            .trim()
+            .lines()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("💭 Reasoning\n{quoted}")
+    }
 }
 
 /// A generic rich-formatted card (maps to Embeds in Discord).
@@ -858,8 +1063,13 @@ mod base64_bytes {
 #[serde(rename_all = "snake_case")]
 pub enum StatusUpdate {
     Thinking,
-    /// Cancel the typing indicator (e.g. when the skip tool fires).
+    /// Cancel the typing indicator.
     StopTyping,
+    /// The skip tool fired, so the turn sends no reply. Adapters treat this
+    /// like `StopTyping`; cron runs use it to suppress delivery.
+    Skipped {
+        reason: Option<String>,
+    },
     ToolStarted {
         tool_name: String,
     },
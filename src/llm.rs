@@ -1,11 +1,16 @@
 //! LLM provider management and routing.
 
 pub mod anthropic;
+pub mod health;
 pub mod manager;
 pub mod model;
+pub mod normalize;
 pub mod pricing;
 pub mod providers;
+pub mod rate_limit;
 pub mod routing;
+pub mod tool_repair;
+pub mod usage;
 
 pub use manager::LlmManager;
 pub use model::SpacebotModel;
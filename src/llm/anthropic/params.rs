@@ -194,6 +194,9 @@ fn build_tools(
         .collect();
 
     body["tools"] = serde_json::json!(tool_values);
+    if crate::llm::model::forbids_tool_calls(request) {
+        body["tool_choice"] = serde_json::json!({ "type": "none" });
+    }
 
     original_tools
 }
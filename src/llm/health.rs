@@ -0,0 +1,163 @@
+//! Provider health tracking and recovery probes.
+//!
+//! The manager counts consecutive auth and server failures per provider. Once
+//! a provider crosses the configured threshold it's marked unhealthy and model
+//! routing prefers fallbacks over it. A background probe sends a tiny
+//! completion to each unhealthy provider and clears the flag on the first
+//! success.
+
+use crate::llm::{LlmManager, SpacebotModel};
+
+use rig::agent::AgentBuilder;
+use rig::completion::{CompletionModel as _, Prompt as _};
+use serde::Serialize;
+
+use std::sync::Arc;
+
+/// Mutable health state for a single provider.
+#[derive(Debug, Default)]
+pub(crate) struct ProviderHealth {
+    consecutive_failures: u32,
+    unhealthy_since: Option<chrono::DateTime<chrono::Utc>>,
+    last_error: Option<String>,
+    /// Most recent model that failed, used as the probe target.
+    last_failed_model: Option<String>,
+    last_success_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ProviderHealth {
+    /// Record a failure. Returns true if this failure tipped the provider
+    /// into the unhealthy state.
+    pub(crate) fn record_failure(&mut self, model_name: &str, error: &str, threshold: u32) -> bool {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_error = Some(error.to_string());
+        self.last_failed_model = Some(model_name.to_string());
+
+        if threshold > 0 && self.unhealthy_since.is_none() && self.consecutive_failures >= threshold
+        {
+            self.unhealthy_since = Some(chrono::Utc::now());
+            return true;
+        }
+        false
+    }
+
+    /// Record a success. Returns true if the provider was unhealthy before.
+    pub(crate) fn record_success(&mut self) -> bool {
+        let recovered = self.unhealthy_since.is_some();
+        self.consecutive_failures = 0;
+        self.unhealthy_since = None;
+        self.last_success_at = Some(chrono::Utc::now());
+        recovered
+    }
+
+    pub(crate) fn has_failures(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.unhealthy_since.is_none()
+    }
+
+    pub(crate) fn probe_model(&self) -> Option<&str> {
+        self.last_failed_model.as_deref()
+    }
+
+    pub(crate) fn status(&self, provider: &str) -> ProviderHealthStatus {
+        ProviderHealthStatus {
+            provider: provider.to_string(),
+            healthy: self.is_healthy(),
+            consecutive_failures: self.consecutive_failures,
+            unhealthy_since: self.unhealthy_since.map(|at| at.to_rfc3339()),
+            last_error: self.last_error.clone(),
+            last_success_at: self.last_success_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Point-in-time health of a provider, as reported by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealthStatus {
+    pub provider: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub unhealthy_since: Option<String>,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<String>,
+}
+
+/// Periodically probe unhealthy providers until they recover.
+///
+/// Holds only a weak reference so the loop exits when the manager is
+/// replaced (e.g. after provider setup rebuilds it).
+pub fn spawn_health_probe(llm_manager: &Arc<LlmManager>) -> tokio::task::JoinHandle<()> {
+    let llm_manager = Arc::downgrade(llm_manager);
+    tokio::spawn(async move {
+        loop {
+            let Some(interval_secs) = llm_manager
+                .upgrade()
+                .map(|manager| manager.health_config().probe_interval_secs.max(1))
+            else {
+                break;
+            };
+            tokio::time::sleep(std::time::Duration::from_secs(interval_secs)).await;
+
+            let Some(manager) = llm_manager.upgrade() else {
+                break;
+            };
+            for (provider, model_name) in manager.unhealthy_providers().await {
+                match probe(&manager, &model_name).await {
+                    Ok(()) => manager.record_provider_success(&model_name).await,
+                    Err(error) => {
+                        tracing::debug!(%provider, model = %model_name, %error, "provider health probe failed");
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Send a minimal completion straight to the model, bypassing routing so the
+/// probe never falls back to a different provider.
+async fn probe(llm_manager: &Arc<LlmManager>, model_name: &str) -> Result<(), String> {
+    let model = SpacebotModel::make(llm_manager, model_name);
+    let agent = AgentBuilder::new(model)
+        .preamble("You are running a provider health check. Reply with exactly: OK")
+        .build();
+    agent
+        .prompt("Health check")
+        .await
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_turns_unhealthy_at_threshold_and_recovers_on_success() {
+        let mut health = ProviderHealth::default();
+        assert!(!health.record_failure("anthropic/claude", "401 Unauthorized", 3));
+        assert!(!health.record_failure("anthropic/claude", "401 Unauthorized", 3));
+        assert!(health.is_healthy());
+
+        assert!(health.record_failure("anthropic/claude", "401 Unauthorized", 3));
+        assert!(!health.is_healthy());
+        assert_eq!(health.probe_model(), Some("anthropic/claude"));
+        // Further failures don't re-trigger the transition.
+        assert!(!health.record_failure("anthropic/claude", "401 Unauthorized", 3));
+
+        assert!(health.record_success());
+        assert!(health.is_healthy());
+        assert_eq!(health.status("anthropic").consecutive_failures, 0);
+    }
+
+    #[test]
+    fn zero_threshold_disables_health_tracking() {
+        let mut health = ProviderHealth::default();
+        for _ in 0..10 {
+            assert!(!health.record_failure("openai/gpt", "500 Internal Server Error", 0));
+        }
+        assert!(health.is_healthy());
+    }
+}
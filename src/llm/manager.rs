@@ -1,7 +1,7 @@
 //! LLM manager for provider credentials and HTTP client.
 //!
 //! The manager is intentionally simple — it holds API keys, an HTTP client,
-//! and shared rate limit state. Routing decisions (which model for which
+//! and shared rate limit and provider health state. Routing decisions (which model for which
 //! process) live on the agent's RoutingConfig, not here.
 //!
 //! API keys are hot-reloadable via ArcSwap. The file watcher calls
@@ -9,9 +9,14 @@
 //! `get_api_key()` calls read the new values lock-free.
 
 use crate::auth::OAuthCredentials as AnthropicOAuthCredentials;
-use crate::config::{ApiType, LlmConfig, ProviderConfig};
+use crate::config::{ApiType, AppAttribution, LlmConfig, ProviderConfig, ProviderHealthConfig};
 use crate::error::{LlmError, Result};
 use crate::github_copilot_auth::CopilotToken;
+use crate::llm::health::{ProviderHealth, ProviderHealthStatus};
+use crate::llm::rate_limit::{
+    ProviderStatus, ProviderStatusEvent, RateLimitAdmission, RateLimiter,
+};
+use crate::llm::tool_repair::ToolCallRepairOutcome;
 use crate::openai_auth::OAuthCredentials as OpenAiOAuthCredentials;
 
 use anyhow::Context as _;
@@ -27,19 +32,29 @@ const COPILOT_EDITOR_VERSION: &str = "vscode/1.96.2";
 /// Matches Copilot Chat extension version 0.26.7.
 const COPILOT_EDITOR_PLUGIN_VERSION: &str = "copilot-chat/0.26.7";
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock, broadcast};
 
 /// Manages LLM provider clients and tracks rate limit state.
 pub struct LlmManager {
     config: ArcSwap<LlmConfig>,
     http_client: reqwest::Client,
-    /// Models currently in rate limit cooldown, with the time they were limited.
-    rate_limited: Arc<RwLock<HashMap<String, Instant>>>,
+    /// Providers currently in rate limit cooldown, keyed by provider ID.
+    rate_limits: RwLock<RateLimiter>,
+    /// Rate limit and fallback transitions, forwarded to the API event stream.
+    status_tx: broadcast::Sender<ProviderStatusEvent>,
+    /// Per-provider failure tracking, keyed by provider ID.
+    provider_health: RwLock<HashMap<String, ProviderHealth>>,
+    /// Last base URL that answered, keyed by provider ID. Only tracked for
+    /// providers with `fallback_base_urls`.
+    active_base_urls: RwLock<HashMap<String, String>>,
     /// Instance directory for reading/writing OAuth credentials.
     instance_dir: Option<PathBuf>,
-    /// Cached Anthropic OAuth credentials (refreshed lazily).
-    anthropic_oauth_credentials: RwLock<Option<AnthropicOAuthCredentials>>,
+    /// Anthropic OAuth accounts, rotated per call (refreshed lazily).
+    anthropic_accounts: RwLock<Vec<AnthropicAccount>>,
+    /// Index of the next Anthropic account to try.
+    anthropic_account_cursor: AtomicUsize,
     /// Cached OpenAI OAuth credentials (refreshed lazily).
     openai_oauth_credentials: RwLock<Option<OpenAiOAuthCredentials>>,
     /// Cached GitHub Copilot API token (exchanged from PAT, refreshed lazily).
@@ -57,9 +72,13 @@ impl LlmManager {
         Ok(Self {
             config: ArcSwap::from_pointee(config),
             http_client,
-            rate_limited: Arc::new(RwLock::new(HashMap::new())),
+            rate_limits: RwLock::new(RateLimiter::default()),
+            status_tx: broadcast::channel(64).0,
+            provider_health: RwLock::new(HashMap::new()),
+            active_base_urls: RwLock::new(HashMap::new()),
             instance_dir: None,
-            anthropic_oauth_credentials: RwLock::new(None),
+            anthropic_accounts: RwLock::new(Vec::new()),
+            anthropic_account_cursor: AtomicUsize::new(0),
             openai_oauth_credentials: RwLock::new(None),
             copilot_token: RwLock::new(None),
         })
@@ -67,9 +86,9 @@ impl LlmManager {
 
     /// Set the instance directory and load any existing OAuth credentials.
     pub async fn set_instance_dir(&self, instance_dir: PathBuf) {
-        if let Ok(Some(creds)) = crate::auth::load_credentials(&instance_dir) {
-            tracing::info!("loaded Anthropic OAuth credentials from auth.json");
-            *self.anthropic_oauth_credentials.write().await = Some(creds);
+        let anthropic_accounts = load_anthropic_accounts(&instance_dir);
+        if !anthropic_accounts.is_empty() {
+            *self.anthropic_accounts.write().await = anthropic_accounts;
         }
         if let Ok(Some(creds)) = crate::openai_auth::load_credentials(&instance_dir) {
             tracing::info!("loaded OpenAI OAuth credentials from openai_chatgpt_oauth.json");
@@ -98,17 +117,7 @@ impl LlmManager {
             .build()
             .with_context(|| "failed to build HTTP client")?;
 
-        let anthropic_oauth_credentials = match crate::auth::load_credentials(&instance_dir) {
-            Ok(Some(creds)) => {
-                tracing::info!("loaded Anthropic OAuth credentials from auth.json");
-                Some(creds)
-            }
-            Ok(None) => None,
-            Err(error) => {
-                tracing::warn!(%error, "failed to load Anthropic OAuth credentials");
-                None
-            }
-        };
+        let anthropic_accounts = load_anthropic_accounts(&instance_dir);
 
         let openai_oauth_credentials = match crate::openai_auth::load_credentials(&instance_dir) {
             Ok(Some(creds)) => {
@@ -137,14 +146,23 @@ impl LlmManager {
         Ok(Self {
             config: ArcSwap::from_pointee(config),
             http_client,
-            rate_limited: Arc::new(RwLock::new(HashMap::new())),
+            rate_limits: RwLock::new(RateLimiter::default()),
+            status_tx: broadcast::channel(64).0,
+            provider_health: RwLock::new(HashMap::new()),
+            active_base_urls: RwLock::new(HashMap::new()),
             instance_dir: Some(instance_dir),
-            anthropic_oauth_credentials: RwLock::new(anthropic_oauth_credentials),
+            anthropic_accounts: RwLock::new(anthropic_accounts),
+            anthropic_account_cursor: AtomicUsize::new(0),
             openai_oauth_credentials: RwLock::new(openai_oauth_credentials),
             copilot_token: RwLock::new(copilot_token),
         })
     }
 
+    /// App identity attached to OpenAI-compatible requests.
+    pub fn attribution(&self) -> AppAttribution {
+        self.config.load().attribution.clone()
+    }
+
     /// Atomically swap in new provider credentials.
     pub fn reload_config(&self, config: LlmConfig) {
         self.config.store(Arc::new(config));
@@ -166,39 +184,140 @@ impl LlmManager {
     ///
     /// If OAuth credentials are available and the provider is Anthropic,
     /// returns the OAuth access token (refreshing if needed). Otherwise
-    /// falls back to the static API key from config.
+    /// falls back to the static API key from config. With several accounts
+    /// stored, each call takes the next usable one.
     pub async fn get_anthropic_token(&self) -> Result<Option<String>> {
-        let mut creds_guard = self.anthropic_oauth_credentials.write().await;
-        let Some(creds) = creds_guard.as_ref() else {
-            return Ok(None);
-        };
+        Ok(self
+            .next_anthropic_account()
+            .await?
+            .map(|(_label, token)| token))
+    }
 
-        if !creds.is_expired() {
-            return Ok(Some(creds.access_token.clone()));
+    /// Number of stored Anthropic OAuth accounts.
+    pub async fn anthropic_account_count(&self) -> usize {
+        self.anthropic_accounts.read().await.len()
+    }
+
+    /// Pick the next Anthropic account in rotation as `(label, access_token)`.
+    ///
+    /// Accounts cooling down after a 429, or whose token can't be refreshed,
+    /// are skipped. With a single account it is always returned, matching
+    /// the behaviour before multiple accounts were supported. Returns an
+    /// error if every account is rate limited.
+    pub(crate) async fn next_anthropic_account(&self) -> Result<Option<(String, String)>> {
+        let count = self.anthropic_accounts.read().await.len();
+        if count == 0 {
+            return Ok(None);
         }
 
-        // Need to refresh
-        tracing::info!("Anthropic OAuth access token expired, refreshing...");
-        match creds.refresh().await {
-            Ok(new_creds) => {
-                // Save to disk
-                if let Some(ref instance_dir) = self.instance_dir
-                    && let Err(error) = crate::auth::save_credentials(instance_dir, &new_creds)
-                {
-                    tracing::warn!(%error, "failed to persist refreshed Anthropic OAuth credentials");
+        let start = self
+            .anthropic_account_cursor
+            .fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut rate_limited = 0;
+        for offset in 0..count {
+            let index = (start + offset) % count;
+            // Only hold the accounts lock long enough to inspect the account.
+            // Token refreshes go over the network and must not block callers
+            // that can use another account or an unexpired token.
+            let refresh_lock = {
+                let accounts = self.anthropic_accounts.read().await;
+                let Some(account) = accounts.get(index) else {
+                    continue;
+                };
+                if count > 1 && account.rate_limited_until.is_some_and(|until| now < until) {
+                    rate_limited += 1;
+                    continue;
+                }
+                if !account.credentials.is_expired() {
+                    return Ok(Some((
+                        account.label.clone(),
+                        account.credentials.access_token.clone(),
+                    )));
+                }
+                account.refresh_lock.clone()
+            };
+
+            // One refresh per account at a time. Whoever waited on the lock
+            // picks up the token the first caller stored.
+            let _refreshing = refresh_lock.lock().await;
+            let (label, credentials) = {
+                let accounts = self.anthropic_accounts.read().await;
+                let Some(account) = accounts.get(index) else {
+                    continue;
+                };
+                if !account.credentials.is_expired() {
+                    return Ok(Some((
+                        account.label.clone(),
+                        account.credentials.access_token.clone(),
+                    )));
+                }
+                (account.label.clone(), account.credentials.clone())
+            };
+
+            tracing::info!(account = %label, "Anthropic OAuth access token expired, refreshing...");
+            match credentials.refresh().await {
+                Ok(new_creds) => {
+                    if let Some(ref instance_dir) = self.instance_dir
+                        && let Err(error) =
+                            crate::auth::save_account_credentials(instance_dir, &label, &new_creds)
+                    {
+                        tracing::warn!(%error, account = %label, "failed to persist refreshed Anthropic OAuth credentials");
+                    }
+                    let access_token = new_creds.access_token.clone();
+                    if let Some(account) = self
+                        .anthropic_accounts
+                        .write()
+                        .await
+                        .iter_mut()
+                        .find(|account| account.label == label)
+                    {
+                        account.credentials = new_creds;
+                    }
+                    tracing::info!(account = %label, "Anthropic OAuth token refreshed successfully");
+                    return Ok(Some((label, access_token)));
+                }
+                Err(error) => {
+                    tracing::error!(%error, account = %label, "Anthropic OAuth token refresh failed");
+                    if count == 1 {
+                        // Return the expired token anyway — the API will reject it
+                        // and the error message will be clearer than "no key"
+                        return Ok(Some((label, credentials.access_token)));
+                    }
                 }
-                let token = new_creds.access_token.clone();
-                *creds_guard = Some(new_creds);
-                tracing::info!("Anthropic OAuth token refreshed successfully");
-                Ok(Some(token))
-            }
-            Err(error) => {
-                tracing::error!(%error, "Anthropic OAuth token refresh failed");
-                // Return the expired token anyway — the API will reject it
-                // and the error message will be clearer than "no key"
-                Ok(Some(creds.access_token.clone()))
             }
         }
+
+        if rate_limited == count {
+            return Err(LlmError::ProviderRequest(
+                "429: all Anthropic OAuth accounts are rate limited".into(),
+            )
+            .into());
+        }
+        Err(LlmError::ProviderRequest(
+            "no usable Anthropic OAuth account: every token refresh failed".into(),
+        )
+        .into())
+    }
+
+    /// Put an Anthropic account in cooldown after it returned 429, so the
+    /// next calls rotate to other accounts.
+    pub(crate) async fn record_anthropic_account_rate_limit(
+        &self,
+        label: &str,
+        cooldown_secs: u64,
+        retry_after_secs: Option<u64>,
+    ) {
+        let cooldown = super::rate_limit::cooldown_duration(cooldown_secs, retry_after_secs);
+        let mut accounts = self.anthropic_accounts.write().await;
+        if let Some(account) = accounts.iter_mut().find(|account| account.label == label) {
+            account.rate_limited_until = Some(Instant::now() + cooldown);
+            tracing::warn!(
+                account = %label,
+                cooldown_secs = cooldown.as_secs(),
+                "Anthropic OAuth account rate limited, rotating to the next account"
+            );
+        }
     }
 
     /// Resolve the Anthropic provider config, preferring OAuth credentials.
@@ -209,6 +328,20 @@ impl LlmManager {
     /// the OAuth token alone.
     pub async fn get_anthropic_provider(&self) -> Result<ProviderConfig> {
         let token = self.get_anthropic_token().await?;
+        self.anthropic_provider_with_token(token)
+    }
+
+    /// Like [`get_anthropic_provider`](Self::get_anthropic_provider), also
+    /// returning the label of the OAuth account whose token was used.
+    pub(crate) async fn get_anthropic_account_provider(
+        &self,
+    ) -> Result<(ProviderConfig, Option<String>)> {
+        let account = self.next_anthropic_account().await?;
+        let (label, token) = account.unzip();
+        Ok((self.anthropic_provider_with_token(token)?, label))
+    }
+
+    fn anthropic_provider_with_token(&self, token: Option<String>) -> Result<ProviderConfig> {
         let static_provider = self.get_provider("anthropic").ok();
 
         match (static_provider, token) {
@@ -224,6 +357,7 @@ impl LlmManager {
                 name: None,
                 use_bearer_auth: false,
                 extra_headers: vec![],
+                fallback_base_urls: Vec::new(),
             }),
             (None, None) => Err(LlmError::UnknownProvider("anthropic".to_string()).into()),
         }
@@ -294,6 +428,7 @@ impl LlmManager {
                 name: None,
                 use_bearer_auth: false,
                 extra_headers: vec![],
+                fallback_base_urls: Vec::new(),
             }),
             None => Err(LlmError::UnknownProvider("openai-chatgpt".to_string()).into()),
         }
@@ -406,6 +541,7 @@ impl LlmManager {
                     COPILOT_EDITOR_PLUGIN_VERSION.to_string(),
                 ),
             ],
+            fallback_base_urls: Vec::new(),
         })
     }
 
@@ -418,6 +554,21 @@ impl LlmManager {
         *self.copilot_token.write().await = None;
     }
 
+    /// Whether a provider has credentials configured. Unlike the `get_*`
+    /// provider lookups, this never refreshes or rotates OAuth tokens.
+    pub async fn has_provider(&self, provider_id: &str) -> bool {
+        match provider_id.to_lowercase().as_str() {
+            "anthropic" => {
+                self.get_provider("anthropic").is_ok() || self.anthropic_account_count().await > 0
+            }
+            "openai-chatgpt" => self.openai_oauth_credentials.read().await.is_some(),
+            "github-copilot" => self
+                .get_provider("github-copilot")
+                .is_ok_and(|provider| !provider.api_key.is_empty()),
+            provider_id => self.get_provider(provider_id).is_ok(),
+        }
+    }
+
     /// Get the appropriate API key for a provider.
     pub fn get_api_key(&self, provider_id: &str) -> Result<String> {
         let provider = self.get_provider(provider_id)?;
@@ -449,30 +600,437 @@ impl LlmManager {
         }
     }
 
-    /// Record that a model hit a rate limit.
-    pub async fn record_rate_limit(&self, model_name: &str) {
-        self.rate_limited
+    /// Subscribe to provider rate limit and fallback transitions.
+    pub fn subscribe_status(&self) -> broadcast::Receiver<ProviderStatusEvent> {
+        self.status_tx.subscribe()
+    }
+
+    fn emit_status(&self, provider: &str, model_name: &str, status: ProviderStatus) {
+        // No subscribers is fine; the event is informational.
+        let _ = self.status_tx.send(ProviderStatusEvent {
+            provider: provider.to_string(),
+            model: model_name.to_string(),
+            status,
+        });
+    }
+
+    /// Record that a model hit a rate limit. The model's provider cools down
+    /// for `cooldown_secs` or the provider's `Retry-After`, whichever is larger.
+    pub async fn record_rate_limit(
+        &self,
+        model_name: &str,
+        cooldown_secs: u64,
+        retry_after_secs: Option<u64>,
+    ) {
+        let provider = super::routing::provider_from_model(model_name);
+        let cooldown = super::rate_limit::cooldown_duration(cooldown_secs, retry_after_secs);
+        self.rate_limits
             .write()
             .await
-            .insert(model_name.to_string(), Instant::now());
-        tracing::warn!(model = %model_name, "model rate limited, entering cooldown");
+            .record(provider, cooldown, Instant::now());
+        tracing::warn!(
+            %provider,
+            model = %model_name,
+            cooldown_secs = cooldown.as_secs(),
+            "provider rate limited, entering cooldown"
+        );
+        self.emit_status(
+            provider,
+            model_name,
+            ProviderStatus::RateLimited {
+                cooldown_secs: cooldown.as_secs(),
+            },
+        );
     }
 
-    /// Check if a model is currently in rate limit cooldown.
-    pub async fn is_rate_limited(&self, model_name: &str, cooldown_secs: u64) -> bool {
-        let map = self.rate_limited.read().await;
-        if let Some(limited_at) = map.get(model_name) {
-            limited_at.elapsed().as_secs() < cooldown_secs
-        } else {
-            false
+    /// Record that a request is moving on from a failed model to the next one
+    /// in its fallback chain.
+    pub fn record_model_fallback(&self, from_model: &str, to_model: &str, attempt: usize) {
+        let provider = super::routing::provider_from_model(to_model);
+        tracing::info!(
+            from = %from_model,
+            to = %to_model,
+            attempt,
+            "falling back to next model in chain"
+        );
+        self.emit_status(
+            provider,
+            to_model,
+            ProviderStatus::ModelFallback {
+                from_model: from_model.to_string(),
+                attempt,
+            },
+        );
+    }
+
+    /// Base URLs to try for a provider, starting from the last one that
+    /// answered and wrapping around through the rest in configured order.
+    pub async fn base_urls_for(&self, provider: &str, config: &ProviderConfig) -> Vec<String> {
+        let mut base_urls: Vec<String> = std::iter::once(&config.base_url)
+            .chain(&config.fallback_base_urls)
+            .cloned()
+            .collect();
+        if let Some(active) = self.active_base_urls.read().await.get(provider)
+            && let Some(index) = base_urls.iter().position(|url| url == active)
+        {
+            base_urls.rotate_left(index);
+        }
+        base_urls
+    }
+
+    /// Remember the base URL that answered so the next request starts there.
+    pub async fn record_base_url_ok(&self, provider: &str, base_url: &str) {
+        let is_active = self
+            .active_base_urls
+            .read()
+            .await
+            .get(provider)
+            .is_some_and(|active| active == base_url);
+        if !is_active {
+            self.active_base_urls
+                .write()
+                .await
+                .insert(provider.to_string(), base_url.to_string());
         }
     }
 
-    /// Clean up expired rate limit entries.
-    pub async fn cleanup_rate_limits(&self, cooldown_secs: u64) {
-        self.rate_limited
+    /// Record that a request is moving from a base URL that could not be
+    /// reached to the provider's next one.
+    pub fn record_base_url_failover(
+        &self,
+        model_name: &str,
+        from_base_url: &str,
+        to_base_url: &str,
+    ) {
+        let provider = super::routing::provider_from_model(model_name);
+        tracing::warn!(
+            %provider,
+            model = %model_name,
+            from = %from_base_url,
+            to = %to_base_url,
+            "provider base URL unreachable, failing over to next base URL"
+        );
+        self.emit_status(
+            provider,
+            model_name,
+            ProviderStatus::BaseUrlFailover {
+                from_base_url: from_base_url.to_string(),
+                to_base_url: to_base_url.to_string(),
+            },
+        );
+    }
+
+    /// Record that a tool call from `model_name` had malformed arguments and
+    /// how repairing them went.
+    pub fn record_tool_call_repair(
+        &self,
+        model_name: &str,
+        tool_name: &str,
+        outcome: ToolCallRepairOutcome,
+    ) {
+        let provider = super::routing::provider_from_model(model_name);
+        tracing::warn!(
+            model = %model_name,
+            tool = %tool_name,
+            outcome = outcome.as_str(),
+            "model emitted malformed tool call arguments"
+        );
+        self.emit_status(
+            provider,
+            model_name,
+            ProviderStatus::ToolCallRepair {
+                tool_name: tool_name.to_string(),
+                outcome,
+            },
+        );
+    }
+
+    /// Model to fall back to when no provider can serve a routed model.
+    pub fn default_model(&self) -> Option<String> {
+        self.config.load().default_model.clone()
+    }
+
+    /// Record that a request fell back to `[llm] default_model` because no
+    /// provider could serve `from_model`. Logged as a warning every time, so
+    /// a model pinned in routing that has gone away doesn't go unnoticed.
+    pub fn record_default_model_fallback(&self, from_model: &str, to_model: &str, error: &str) {
+        let provider = super::routing::provider_from_model(to_model);
+        tracing::warn!(
+            from = %from_model,
+            to = %to_model,
+            %error,
+            "no provider can serve model, falling back to default_model; update routing to stop this"
+        );
+        self.emit_status(
+            provider,
+            to_model,
+            ProviderStatus::DefaultModelFallback {
+                from_model: from_model.to_string(),
+            },
+        );
+    }
+
+    /// Check whether a request may be sent to the model's provider. Returns
+    /// `Probe` for the single request allowed through after a cooldown.
+    pub async fn admit_rate_limited(&self, model_name: &str) -> RateLimitAdmission {
+        let provider = super::routing::provider_from_model(model_name);
+        let admission = self
+            .rate_limits
             .write()
             .await
-            .retain(|_, limited_at| limited_at.elapsed().as_secs() < cooldown_secs);
+            .admit(provider, Instant::now());
+        if admission == RateLimitAdmission::Probe {
+            tracing::info!(%provider, model = %model_name, "rate limit cooldown expired, probing provider");
+            self.emit_status(provider, model_name, ProviderStatus::Probing);
+        }
+        admission
+    }
+
+    /// Allow another probe after the current one failed with something other
+    /// than a rate limit.
+    pub async fn release_rate_limit_probe(&self, model_name: &str) {
+        let provider = super::routing::provider_from_model(model_name);
+        self.rate_limits.write().await.release_probe(provider);
+    }
+
+    /// Current provider health thresholds.
+    pub fn health_config(&self) -> ProviderHealthConfig {
+        self.config.load().health
+    }
+
+    /// Record an auth or server failure against the model's provider. Other
+    /// errors (rate limits, bad requests) are ignored.
+    pub async fn record_provider_failure(&self, model_name: &str, error: &str) {
+        if !super::routing::is_provider_health_error(error) {
+            return;
+        }
+        let provider = super::routing::provider_from_model(model_name);
+        let threshold = self.health_config().failure_threshold;
+        let became_unhealthy = self
+            .provider_health
+            .write()
+            .await
+            .entry(provider.to_string())
+            .or_default()
+            .record_failure(model_name, error, threshold);
+        if became_unhealthy {
+            tracing::warn!(
+                %provider,
+                model = %model_name,
+                %error,
+                "provider marked unhealthy, preferring fallbacks until a probe succeeds"
+            );
+        }
+    }
+
+    /// Record a successful call, clearing any rate limit cooldown and unhealthy
+    /// flag on the provider.
+    pub async fn record_provider_success(&self, model_name: &str) {
+        let provider = super::routing::provider_from_model(model_name);
+        // The read guard must drop before taking the write lock.
+        let in_cooldown = self.rate_limits.read().await.contains(provider);
+        let was_rate_limited =
+            in_cooldown && self.rate_limits.write().await.record_success(provider);
+        if was_rate_limited {
+            tracing::info!(%provider, model = %model_name, "provider rate limit cleared");
+            self.emit_status(provider, model_name, ProviderStatus::Recovered);
+        }
+        // Most calls succeed against a provider with no failures on record, so
+        // check under the read lock before taking the write lock.
+        let has_failures = self
+            .provider_health
+            .read()
+            .await
+            .get(provider)
+            .is_some_and(ProviderHealth::has_failures);
+        if !has_failures {
+            return;
+        }
+        let mut health = self.provider_health.write().await;
+        let Some(entry) = health.get_mut(provider) else {
+            return;
+        };
+        if entry.record_success() {
+            tracing::info!(%provider, model = %model_name, "provider recovered");
+        }
+    }
+
+    /// Whether the model's provider is currently considered healthy.
+    pub async fn is_provider_healthy(&self, model_name: &str) -> bool {
+        let provider = super::routing::provider_from_model(model_name);
+        self.provider_health
+            .read()
+            .await
+            .get(provider)
+            .is_none_or(ProviderHealth::is_healthy)
+    }
+
+    /// Health of every provider that has seen a failure, sorted by provider ID.
+    pub async fn provider_health(&self) -> Vec<ProviderHealthStatus> {
+        let health = self.provider_health.read().await;
+        let mut statuses: Vec<_> = health
+            .iter()
+            .map(|(provider, entry)| entry.status(provider))
+            .collect();
+        statuses.sort_by(|a, b| a.provider.cmp(&b.provider));
+        statuses
+    }
+
+    /// Unhealthy providers paired with the model to probe them with.
+    pub(crate) async fn unhealthy_providers(&self) -> Vec<(String, String)> {
+        self.provider_health
+            .read()
+            .await
+            .iter()
+            .filter(|(_, entry)| !entry.is_healthy())
+            .filter_map(|(provider, entry)| {
+                entry
+                    .probe_model()
+                    .map(|model| (provider.clone(), model.to_string()))
+            })
+            .collect()
+    }
+
+    /// List the model IDs a provider serves, from its models endpoint.
+    ///
+    /// IDs come back without the `provider/` prefix used in routing. Returns
+    /// `None` for providers without a models endpoint to query.
+    pub async fn list_models(&self, provider_id: &str) -> Result<Option<Vec<String>>> {
+        let provider = match provider_id {
+            "anthropic" => self.get_anthropic_provider().await?,
+            "github-copilot" => self.get_github_copilot_provider().await?,
+            // ChatGPT OAuth serves a fixed model set with no listing endpoint.
+            "openai-chatgpt" => return Ok(None),
+            _ => self.get_provider(provider_id)?,
+        };
+
+        let base_url = provider.base_url.trim_end_matches('/');
+        let request = match provider.api_type {
+            ApiType::Anthropic => {
+                let base_url = base_url.trim_end_matches("/v1/messages");
+                let request = self
+                    .http_client
+                    .get(format!("{base_url}/v1/models?limit=1000"))
+                    .header("anthropic-version", "2023-06-01");
+                crate::llm::anthropic::apply_auth_headers(
+                    request,
+                    &provider.api_key,
+                    false,
+                    provider.use_bearer_auth,
+                )
+                .0
+            }
+            ApiType::OpenAiCompletions | ApiType::OpenAiResponses => self
+                .http_client
+                .get(format!("{base_url}/v1/models"))
+                .bearer_auth(&provider.api_key),
+            ApiType::OpenAiChatCompletions | ApiType::KiloGateway | ApiType::Gemini => {
+                let request = self.http_client.get(format!("{base_url}/models"));
+                if provider.api_key.is_empty() {
+                    request
+                } else {
+                    request.bearer_auth(&provider.api_key)
+                }
+            }
+        };
+        let request = provider
+            .extra_headers
+            .iter()
+            .fold(request, |request, (key, value)| request.header(key, value));
+
+        let response = request
+            .send()
+            .await
+            .map_err(|error| LlmError::ProviderRequest(error.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(LlmError::ProviderRequest(format!(
+                "{provider_id} models endpoint returned {status}"
+            ))
+            .into());
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|error| LlmError::ProviderRequest(error.to_string()))?;
+
+        Ok(Some(parse_model_list(&body)))
+    }
+
+    /// Warn about routing models their provider does not list.
+    ///
+    /// `models` pairs each model with the config key it is set under. Providers
+    /// that can't be listed are skipped with a debug log; nothing here fails.
+    pub async fn warn_unlisted_models(&self, models: &[(String, String)]) {
+        let mut listings: HashMap<String, Option<Vec<String>>> = HashMap::new();
+        let mut checked = std::collections::HashSet::new();
+        for (key, model_name) in models {
+            if !checked.insert(model_name.as_str()) {
+                continue;
+            }
+            let Ok((provider, model)) = self.resolve_model(model_name) else {
+                continue;
+            };
+            let provider = provider.to_lowercase();
+            if !listings.contains_key(&provider) {
+                let listing = match self.list_models(&provider).await {
+                    Ok(listing) => listing,
+                    Err(error) => {
+                        tracing::debug!(%provider, %error, "skipping model check, could not list models");
+                        None
+                    }
+                };
+                listings.insert(provider.clone(), listing);
+            }
+            let Some(Some(listed)) = listings.get(&provider) else {
+                continue;
+            };
+            if !listed.iter().any(|id| id == &model) {
+                tracing::warn!(
+                    %key,
+                    model = %model_name,
+                    "routing model is not in {provider}'s model list; check for a typo"
+                );
+            }
+        }
+    }
+}
+
+/// Model IDs from an OpenAI- or Anthropic-style `{"data": [{"id": ...}]}`
+/// listing. Gemini prefixes its IDs with `models/`, which is stripped.
+fn parse_model_list(body: &serde_json::Value) -> Vec<String> {
+    body.get("data")
+        .and_then(|data| data.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|model| model.get("id").and_then(|id| id.as_str()))
+        .map(|id| id.strip_prefix("models/").unwrap_or(id).to_string())
+        .collect()
+}
+
+/// A stored Anthropic OAuth account and its rotation state.
+struct AnthropicAccount {
+    label: String,
+    credentials: AnthropicOAuthCredentials,
+    /// Set after a 429; the account is skipped until this passes.
+    rate_limited_until: Option<Instant>,
+    /// Held while the account's token is refreshed, so concurrent callers
+    /// wait for one refresh instead of racing on the refresh token.
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+fn load_anthropic_accounts(instance_dir: &std::path::Path) -> Vec<AnthropicAccount> {
+    let accounts: Vec<AnthropicAccount> = crate::auth::load_all_credentials(instance_dir)
+        .into_iter()
+        .map(|(label, credentials)| AnthropicAccount {
+            label,
+            credentials,
+            rate_limited_until: None,
+            refresh_lock: Arc::default(),
+        })
+        .collect();
+    if !accounts.is_empty() {
+        tracing::info!(count = accounts.len(), "loaded Anthropic OAuth credentials");
     }
+    accounts
 }
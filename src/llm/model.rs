@@ -2,9 +2,10 @@
 
 use crate::config::{ApiType, ProviderConfig};
 use crate::llm::manager::LlmManager;
-use crate::llm::routing::{
-    self, MAX_FALLBACK_ATTEMPTS, MAX_RETRIES_PER_MODEL, RETRY_BASE_DELAY_MS, RoutingConfig,
-};
+use crate::llm::normalize::normalize_history;
+use crate::llm::rate_limit::RateLimitAdmission;
+use crate::llm::routing::{self, MAX_RETRIES_PER_MODEL, RETRY_BASE_DELAY_MS, RoutingConfig};
+use crate::llm::tool_repair::{self, ToolCallRepairOutcome};
 
 use futures::StreamExt as _;
 use rig::completion::{self, CompletionError, CompletionModel, CompletionRequest, GetTokenUsage};
@@ -13,8 +14,11 @@ use rig::message::{
     ToolCall, ToolFunction, UserContent,
 };
 use rig::one_or_many::OneOrMany;
-use rig::streaming::{RawStreamingChoice, RawStreamingToolCall, StreamingCompletionResponse};
+use rig::streaming::{
+    RawStreamingChoice, RawStreamingToolCall, StreamingCompletionResponse, StreamingResult,
+};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
@@ -53,6 +57,8 @@ pub struct SpacebotModel {
     agent_id: Option<String>,
     process_type: Option<String>,
     worker_type: Option<String>,
+    /// Output and tool call budget of the prompt request this model serves.
+    turn_budget: Option<crate::llm::usage::TurnBudget>,
 }
 
 impl SpacebotModel {
@@ -89,6 +95,21 @@ impl SpacebotModel {
         self
     }
 
+    /// Fit each completion to what is left of `budget`, so an output cap
+    /// bounds spend and a tool call limit stops tool calls at the provider
+    /// instead of both being noticed afterwards.
+    pub fn with_turn_budget(mut self, budget: crate::llm::usage::TurnBudget) -> Self {
+        self.turn_budget = Some(budget);
+        self
+    }
+
+    fn apply_turn_budget(&self, mut request: CompletionRequest) -> CompletionRequest {
+        if let Some(budget) = &self.turn_budget {
+            budget.apply(&mut request);
+        }
+        request
+    }
+
     async fn provider_config_for_current_model(&self) -> Result<ProviderConfig, CompletionError> {
         let provider_id = self
             .full_model_name
@@ -96,7 +117,7 @@ impl SpacebotModel {
             .map(|(provider, _)| provider)
             .unwrap_or("anthropic");
 
-        match provider_id {
+        let mut provider_config = match provider_id {
             "anthropic" => self
                 .llm_manager
                 .get_anthropic_provider()
@@ -121,7 +142,14 @@ impl SpacebotModel {
                 .llm_manager
                 .get_provider(provider_id)
                 .map_err(|error| CompletionError::ProviderError(error.to_string())),
+        }?;
+
+        if provider_config.api_type != ApiType::Anthropic {
+            self.llm_manager
+                .attribution()
+                .add_user_agent(&mut provider_config.extra_headers);
         }
+        Ok(provider_config)
     }
 
     /// Direct call to the provider (no fallback logic).
@@ -129,11 +157,33 @@ impl SpacebotModel {
         &self,
         request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        if routing::provider_from_model(&self.full_model_name) == "anthropic"
+            && self.llm_manager.anthropic_account_count().await > 1
+        {
+            return self.attempt_anthropic_accounts(request).await;
+        }
+
         let provider_config = self.provider_config_for_current_model().await?;
+        if provider_config.fallback_base_urls.is_empty() {
+            return self.call_provider(request, &provider_config).await;
+        }
+
+        self.with_base_url_failover(provider_config, |provider_config| {
+            let request = request.clone();
+            async move { self.call_provider(request, &provider_config).await }
+        })
+        .await
+    }
 
+    /// Send a completion to the provider's API at `provider_config.base_url`.
+    async fn call_provider(
+        &self,
+        request: CompletionRequest,
+        provider_config: &ProviderConfig,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
         match provider_config.api_type {
-            ApiType::Anthropic => self.call_anthropic(request, &provider_config).await,
-            ApiType::OpenAiCompletions => self.call_openai(request, &provider_config).await,
+            ApiType::Anthropic => self.call_anthropic(request, provider_config).await,
+            ApiType::OpenAiCompletions => self.call_openai(request, provider_config).await,
             ApiType::OpenAiChatCompletions => {
                 let endpoint = format!(
                     "{}/chat/completions",
@@ -162,26 +212,225 @@ impl SpacebotModel {
                     "{}/chat/completions",
                     provider_config.base_url.trim_end_matches('/')
                 );
+                // Configured provider headers replace attribution ones.
+                let mut attribution_headers = self.llm_manager.attribution().referer_headers();
+                attribution_headers.retain(|(name, _)| {
+                    !provider_config
+                        .extra_headers
+                        .iter()
+                        .any(|(custom, _)| custom.eq_ignore_ascii_case(name))
+                });
+                let headers: Vec<(&str, &str)> = attribution_headers
+                    .iter()
+                    .chain(&provider_config.extra_headers)
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
                 self.call_openai_compatible_with_optional_auth(
                     request,
                     "Kilo Gateway",
                     &endpoint,
                     Some(provider_config.api_key.clone()),
-                    &[
-                        ("HTTP-Referer", "https://github.com/spacedriveapp/spacebot"),
-                        ("X-Title", "spacebot"),
-                    ],
+                    &headers,
                 )
                 .await
             }
-            ApiType::OpenAiResponses => self.call_openai_responses(request, &provider_config).await,
+            ApiType::OpenAiResponses => self.call_openai_responses(request, provider_config).await,
             ApiType::Gemini => {
-                self.call_openai_compatible(request, "Google Gemini", &provider_config)
+                self.call_openai_compatible(request, "Google Gemini", provider_config)
                     .await
             }
         }
     }
 
+    /// Run `call` against each of the provider's base URLs in turn. Only a
+    /// URL that can't be reached moves on to the next one; the URL that
+    /// answers is where the next request starts.
+    async fn with_base_url_failover<T, F, Fut>(
+        &self,
+        provider_config: ProviderConfig,
+        mut call: F,
+    ) -> Result<T, CompletionError>
+    where
+        F: FnMut(ProviderConfig) -> Fut,
+        Fut: std::future::Future<Output = Result<T, CompletionError>>,
+    {
+        let base_urls = self
+            .llm_manager
+            .base_urls_for(&self.provider, &provider_config)
+            .await;
+
+        let mut last_error = None;
+        for (index, base_url) in base_urls.iter().enumerate() {
+            if index > 0 {
+                self.llm_manager.record_base_url_failover(
+                    &self.full_model_name,
+                    &base_urls[index - 1],
+                    base_url,
+                );
+            }
+            let result = call(ProviderConfig {
+                base_url: base_url.clone(),
+                ..provider_config.clone()
+            })
+            .await;
+            match result {
+                Err(error) if is_connection_error(&error) => {
+                    last_error = Some(error);
+                }
+                result => {
+                    self.llm_manager
+                        .record_base_url_ok(&self.provider, base_url)
+                        .await;
+                    return result;
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            CompletionError::ProviderError("provider has no base URL configured".into())
+        }))
+    }
+
+    /// Fix tool calls whose arguments weren't valid JSON, first with a lenient
+    /// parse and then by asking the model to rewrite them. Fails the
+    /// completion if a call still can't be parsed.
+    async fn repair_tool_calls(
+        &self,
+        mut response: completion::CompletionResponse<RawResponse>,
+        request: &CompletionRequest,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let has_malformed = response.choice.iter().any(|content| match content {
+            AssistantContent::ToolCall(tool_call) => tool_repair::is_malformed(tool_call),
+            _ => false,
+        });
+        if !has_malformed {
+            return Ok(response);
+        }
+
+        let mut choice = Vec::new();
+        for content in response.choice {
+            let AssistantContent::ToolCall(mut tool_call) = content else {
+                choice.push(content);
+                continue;
+            };
+            if let Some(malformed) = tool_repair::take_malformed_arguments(&mut tool_call) {
+                tool_call.function.arguments = self
+                    .repair_tool_arguments(&tool_call.function.name, malformed, request)
+                    .await?;
+            }
+            choice.push(AssistantContent::ToolCall(tool_call));
+        }
+        response.choice = OneOrMany::many(choice)
+            .map_err(|_| CompletionError::ResponseError("empty response after repair".into()))?;
+        Ok(response)
+    }
+
+    async fn repair_tool_arguments(
+        &self,
+        tool_name: &str,
+        malformed: tool_repair::MalformedArguments,
+        request: &CompletionRequest,
+    ) -> Result<serde_json::Value, CompletionError> {
+        let sanitized = escape_control_characters_in_json_strings(&malformed.raw);
+        if let Some(arguments) = tool_repair::lenient_parse(&sanitized) {
+            self.record_tool_call_repair(tool_name, ToolCallRepairOutcome::Lenient);
+            return Ok(arguments);
+        }
+
+        let attempts = self
+            .routing
+            .as_ref()
+            .map(|routing| routing.tool_call_repair_attempts)
+            .unwrap_or_else(|| RoutingConfig::default().tool_call_repair_attempts);
+        let parameters = request
+            .tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+            .map(|tool| &tool.parameters);
+
+        for attempt in 1..=attempts {
+            let repair_request =
+                tool_repair::repair_request(tool_name, &malformed, parameters, request.max_tokens);
+            let reply = match self.attempt_completion(repair_request).await {
+                Ok(response) => response_text(&response.choice),
+                Err(error) => {
+                    tracing::warn!(
+                        model = %self.full_model_name,
+                        tool = %tool_name,
+                        attempt,
+                        %error,
+                        "tool call repair request failed"
+                    );
+                    continue;
+                }
+            };
+            let sanitized = escape_control_characters_in_json_strings(&reply);
+            if let Some(arguments) = tool_repair::lenient_parse(&sanitized) {
+                self.record_tool_call_repair(tool_name, ToolCallRepairOutcome::Model);
+                return Ok(arguments);
+            }
+        }
+
+        self.record_tool_call_repair(tool_name, ToolCallRepairOutcome::Failed);
+        Err(CompletionError::ProviderError(malformed.error))
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn record_tool_call_repair(&self, tool_name: &str, outcome: ToolCallRepairOutcome) {
+        self.llm_manager
+            .record_tool_call_repair(&self.full_model_name, tool_name, outcome);
+
+        #[cfg(feature = "metrics")]
+        crate::telemetry::Metrics::global()
+            .llm_tool_call_repairs_total
+            .with_label_values(&[
+                self.agent_id.as_deref().unwrap_or("unknown"),
+                &self.full_model_name,
+                outcome.as_str(),
+            ])
+            .inc();
+    }
+
+    /// Call Anthropic, rotating across stored OAuth accounts. An account that
+    /// returns 429 is put in cooldown and the next account is tried
+    /// immediately, so the call only fails as rate limited once every
+    /// account is.
+    async fn attempt_anthropic_accounts(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let account_count = self.llm_manager.anthropic_account_count().await;
+        let cooldown_secs = self
+            .routing
+            .as_ref()
+            .map(|routing| routing.rate_limit_cooldown_secs)
+            .unwrap_or_else(|| RoutingConfig::default().rate_limit_cooldown_secs);
+
+        let mut last_error = None;
+        for _ in 0..account_count {
+            let (provider_config, label) = self
+                .llm_manager
+                .get_anthropic_account_provider()
+                .await
+                .map_err(|error| CompletionError::ProviderError(error.to_string()))?;
+            let result = self.call_anthropic(request.clone(), &provider_config).await;
+            match (result, label) {
+                (Err(error), Some(label)) if routing::is_rate_limit_error(&error.to_string()) => {
+                    let retry_after = routing::retry_after_secs(&error.to_string());
+                    self.llm_manager
+                        .record_anthropic_account_rate_limit(&label, cooldown_secs, retry_after)
+                        .await;
+                    last_error = Some(error);
+                }
+                (result, _) => return result,
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            CompletionError::ProviderError("no Anthropic OAuth account available".into())
+        }))
+    }
+
     /// Try a model with retries and exponential backoff on transient errors.
     ///
     /// Returns `Ok(response)` on success, or `Err((last_error, was_rate_limit))`
@@ -213,11 +462,17 @@ impl SpacebotModel {
             }
 
             match model.attempt_completion(request.clone()).await {
-                Ok(response) => return Ok(response),
+                Ok(response) => {
+                    self.llm_manager.record_provider_success(model_name).await;
+                    return Ok(response);
+                }
                 Err(error) => {
                     let error_str = error.to_string();
                     if !routing::is_retriable_error(&error_str) {
                         // Non-retriable (auth error, bad request, etc) — bail immediately
+                        self.llm_manager
+                            .record_provider_failure(model_name, &error_str)
+                            .await;
                         return Err((error, false));
                     }
                     tracing::warn!(
@@ -232,6 +487,9 @@ impl SpacebotModel {
         }
 
         let error_str = last_error.unwrap_or_default();
+        self.llm_manager
+            .record_provider_failure(model_name, &error_str)
+            .await;
         let was_rate_limit = routing::is_rate_limit_error(&error_str);
         Err((
             CompletionError::ProviderError(format!(
@@ -240,6 +498,188 @@ impl SpacebotModel {
             was_rate_limit,
         ))
     }
+
+    /// Retry a request on `[llm] default_model` when it failed because no
+    /// provider can serve the routed model. Any other outcome is returned
+    /// unchanged.
+    async fn fall_back_to_default_model(
+        &self,
+        result: Result<completion::CompletionResponse<RawResponse>, CompletionError>,
+        request: &CompletionRequest,
+    ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let Err(error) = result else {
+            return result;
+        };
+        let error_message = error.to_string();
+        if !routing::is_model_not_found_error(&error_message) {
+            return Err(error);
+        }
+        let Some(default_model) = self
+            .llm_manager
+            .default_model()
+            .filter(|default_model| *default_model != self.full_model_name)
+        else {
+            return Err(error);
+        };
+
+        self.llm_manager.record_default_model_fallback(
+            &self.full_model_name,
+            &default_model,
+            &error_message,
+        );
+        self.attempt_with_retries(&default_model, request)
+            .await
+            .map_err(|(error, _)| error)
+    }
+}
+
+/// Try the primary model, then each fallback in order, until one succeeds.
+///
+/// `attempt` runs one model with its own retries. Every hop down the chain is
+/// reported through `LlmManager::record_model_fallback`. Models whose provider
+/// is unhealthy or cooling down are skipped; if that leaves nothing to try,
+/// the primary gets one attempt anyway. The error is returned only once the
+/// whole chain is exhausted.
+async fn complete_with_fallbacks<T, F, Fut>(
+    llm_manager: &LlmManager,
+    primary: &str,
+    fallbacks: &[String],
+    cooldown_secs: u64,
+    mut attempt: F,
+) -> Result<T, CompletionError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<T, (CompletionError, bool)>>,
+{
+    let mut last_error: Option<CompletionError> = None;
+
+    // Try the primary model (with retries) unless its provider is in
+    // rate-limit cooldown or unhealthy, and we have fallbacks to try instead.
+    let primary_unhealthy = !llm_manager.is_provider_healthy(primary).await;
+    let primary_admission = if fallbacks.is_empty() || primary_unhealthy {
+        RateLimitAdmission::Open
+    } else {
+        llm_manager.admit_rate_limited(primary).await
+    };
+    let primary_rate_limited = primary_admission == RateLimitAdmission::CoolingDown;
+
+    let skip_primary = (primary_rate_limited || primary_unhealthy) && !fallbacks.is_empty();
+
+    if skip_primary {
+        tracing::debug!(
+            model = %primary,
+            rate_limited = primary_rate_limited,
+            provider_unhealthy = primary_unhealthy,
+            "primary model unavailable, skipping to fallbacks"
+        );
+    } else {
+        match attempt(primary.to_string()).await {
+            Ok(response) => return Ok(response),
+            Err((error, was_rate_limit)) => {
+                record_routing_failure(
+                    llm_manager,
+                    primary,
+                    cooldown_secs,
+                    &error,
+                    was_rate_limit,
+                    primary_admission,
+                )
+                .await;
+                if fallbacks.is_empty() {
+                    // No fallbacks — this is the final error
+                    return Err(error);
+                }
+                tracing::warn!(
+                    model = %primary,
+                    "primary model exhausted retries, trying fallbacks"
+                );
+                last_error = Some(error);
+            }
+        }
+    }
+
+    // Walk the whole fallback chain, each model with its own retry loop
+    let mut previous = primary;
+    for (index, fallback_name) in fallbacks.iter().enumerate() {
+        if !llm_manager.is_provider_healthy(fallback_name).await {
+            tracing::debug!(
+                fallback = %fallback_name,
+                "fallback provider unhealthy, skipping"
+            );
+            continue;
+        }
+        let admission = llm_manager.admit_rate_limited(fallback_name).await;
+        if admission == RateLimitAdmission::CoolingDown {
+            tracing::debug!(
+                fallback = %fallback_name,
+                "fallback provider in cooldown, skipping"
+            );
+            continue;
+        }
+
+        llm_manager.record_model_fallback(previous, fallback_name, index + 1);
+        match attempt(fallback_name.clone()).await {
+            Ok(response) => {
+                tracing::info!(
+                    original = %primary,
+                    fallback = %fallback_name,
+                    attempt = index + 1,
+                    "fallback model succeeded"
+                );
+                return Ok(response);
+            }
+            Err((error, was_rate_limit)) => {
+                record_routing_failure(
+                    llm_manager,
+                    fallback_name,
+                    cooldown_secs,
+                    &error,
+                    was_rate_limit,
+                    admission,
+                )
+                .await;
+                tracing::warn!(
+                    fallback = %fallback_name,
+                    "fallback model exhausted retries, continuing chain"
+                );
+                last_error = Some(error);
+            }
+        }
+        previous = fallback_name;
+    }
+
+    // Every fallback was skipped without being tried. Rather than fail
+    // without making a single request, give the primary a last shot.
+    if skip_primary && last_error.is_none() {
+        return attempt(primary.to_string())
+            .await
+            .map_err(|(error, _)| error);
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        CompletionError::ProviderError("all models in fallback chain failed".into())
+    }))
+}
+
+/// Update rate limit state after a model exhausted its retries. A 429
+/// starts (or restarts) the provider's cooldown; any other failure of a
+/// probe request frees the probe slot so the next request can try again.
+async fn record_routing_failure(
+    llm_manager: &LlmManager,
+    model_name: &str,
+    cooldown_secs: u64,
+    error: &CompletionError,
+    was_rate_limit: bool,
+    admission: RateLimitAdmission,
+) {
+    if was_rate_limit {
+        let retry_after = routing::retry_after_secs(&error.to_string());
+        llm_manager
+            .record_rate_limit(model_name, cooldown_secs, retry_after)
+            .await;
+    } else if admission == RateLimitAdmission::Probe {
+        llm_manager.release_rate_limit_probe(model_name).await;
+    }
 }
 
 impl CompletionModel for SpacebotModel {
@@ -271,6 +711,7 @@ impl CompletionModel for SpacebotModel {
             agent_id: None,
             process_type: None,
             worker_type: None,
+            turn_budget: None,
         }
     }
 
@@ -278,98 +719,33 @@ impl CompletionModel for SpacebotModel {
         &self,
         request: CompletionRequest,
     ) -> Result<completion::CompletionResponse<RawResponse>, CompletionError> {
+        let request = self.apply_turn_budget(request);
         #[cfg(feature = "metrics")]
         let start = std::time::Instant::now();
 
         let result = async move {
-            let Some(routing) = &self.routing else {
+            let request = &request;
+            let result = match &self.routing {
                 // No routing config — just call the model directly, no fallback/retry
-                return self.attempt_completion(request).await;
-            };
-
-            let cooldown = routing.rate_limit_cooldown_secs;
-            let fallbacks = routing.get_fallbacks(&self.full_model_name);
-            let mut last_error: Option<CompletionError> = None;
-
-            // Try the primary model (with retries) unless it's in rate-limit cooldown
-            // and we have fallbacks to try instead.
-            let primary_rate_limited = self
-                .llm_manager
-                .is_rate_limited(&self.full_model_name, cooldown)
-                .await;
-
-            let skip_primary = primary_rate_limited && !fallbacks.is_empty();
-
-            if skip_primary {
-                tracing::debug!(
-                    model = %self.full_model_name,
-                    "primary model in rate-limit cooldown, skipping to fallbacks"
-                );
-            } else {
-                match self
-                    .attempt_with_retries(&self.full_model_name, &request)
-                    .await
-                {
-                    Ok(response) => return Ok(response),
-                    Err((error, was_rate_limit)) => {
-                        if was_rate_limit {
-                            self.llm_manager
-                                .record_rate_limit(&self.full_model_name)
-                                .await;
-                        }
-                        if fallbacks.is_empty() {
-                            // No fallbacks — this is the final error
-                            return Err(error);
-                        }
-                        tracing::warn!(
-                            model = %self.full_model_name,
-                            "primary model exhausted retries, trying fallbacks"
-                        );
-                        last_error = Some(error);
-                    }
-                }
-            }
-
-            // Try fallback chain, each with their own retry loop
-            for (index, fallback_name) in fallbacks.iter().take(MAX_FALLBACK_ATTEMPTS).enumerate() {
-                if self
-                    .llm_manager
-                    .is_rate_limited(fallback_name, cooldown)
+                None => self.attempt_completion(request.clone()).await,
+                Some(routing) => {
+                    complete_with_fallbacks(
+                        &self.llm_manager,
+                        &self.full_model_name,
+                        routing.get_fallbacks(&self.full_model_name),
+                        routing.rate_limit_cooldown_secs,
+                        |model_name| async move {
+                            self.attempt_with_retries(&model_name, request).await
+                        },
+                    )
                     .await
-                {
-                    tracing::debug!(
-                        fallback = %fallback_name,
-                        "fallback model in cooldown, skipping"
-                    );
-                    continue;
-                }
-
-                match self.attempt_with_retries(fallback_name, &request).await {
-                    Ok(response) => {
-                        tracing::info!(
-                            original = %self.full_model_name,
-                            fallback = %fallback_name,
-                            attempt = index + 1,
-                            "fallback model succeeded"
-                        );
-                        return Ok(response);
-                    }
-                    Err((error, was_rate_limit)) => {
-                        if was_rate_limit {
-                            self.llm_manager.record_rate_limit(fallback_name).await;
-                        }
-                        tracing::warn!(
-                            fallback = %fallback_name,
-                            "fallback model exhausted retries, continuing chain"
-                        );
-                        last_error = Some(error);
-                    }
                 }
+            };
+            let result = self.fall_back_to_default_model(result, request).await;
+            match result {
+                Ok(response) => self.repair_tool_calls(response, request).await,
+                Err(error) => Err(error),
             }
-
-            Err(last_error.unwrap_or_else(|| {
-                CompletionError::ProviderError("all models in fallback chain failed".into())
-            }))
         }
         .await;
 
@@ -493,10 +869,40 @@ impl CompletionModel for SpacebotModel {
         &self,
         request: CompletionRequest,
     ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
+        let request = self.apply_turn_budget(request);
         let provider_config = self.provider_config_for_current_model().await?;
+        // These APIs aren't streamed natively. `attempt_completion` handles
+        // them, including Anthropic account rotation and base URL failover.
+        if matches!(
+            provider_config.api_type,
+            ApiType::Anthropic | ApiType::OpenAiResponses
+        ) {
+            let response = self.attempt_completion(request.clone()).await?;
+            let response = self.repair_tool_calls(response, &request).await?;
+            return Ok(stream_from_completion_response(response));
+        }
+        if provider_config.fallback_base_urls.is_empty() {
+            return self.stream_provider(request, &provider_config).await;
+        }
+
+        self.with_base_url_failover(provider_config, |provider_config| {
+            let request = request.clone();
+            async move { self.stream_provider(request, &provider_config).await }
+        })
+        .await
+    }
+}
 
+impl SpacebotModel {
+    /// Stream a completion from the provider's API at
+    /// `provider_config.base_url`.
+    async fn stream_provider(
+        &self,
+        request: CompletionRequest,
+        provider_config: &ProviderConfig,
+    ) -> Result<StreamingCompletionResponse<RawStreamingResponse>, CompletionError> {
         match provider_config.api_type {
-            ApiType::OpenAiCompletions => self.stream_openai(request, &provider_config).await,
+            ApiType::OpenAiCompletions => self.stream_openai(request, provider_config).await,
             ApiType::OpenAiChatCompletions => {
                 let endpoint = format!(
                     "{}/chat/completions",
@@ -525,31 +931,40 @@ impl CompletionModel for SpacebotModel {
                     "{}/chat/completions",
                     provider_config.base_url.trim_end_matches('/')
                 );
+                // Configured provider headers replace attribution ones.
+                let mut attribution_headers = self.llm_manager.attribution().referer_headers();
+                attribution_headers.retain(|(name, _)| {
+                    !provider_config
+                        .extra_headers
+                        .iter()
+                        .any(|(custom, _)| custom.eq_ignore_ascii_case(name))
+                });
+                let headers: Vec<(&str, &str)> = attribution_headers
+                    .iter()
+                    .chain(&provider_config.extra_headers)
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
                 self.stream_openai_compatible_with_optional_auth(
                     request,
                     "Kilo Gateway",
                     &endpoint,
                     Some(provider_config.api_key.clone()),
-                    &[
-                        ("HTTP-Referer", "https://github.com/spacedriveapp/spacebot"),
-                        ("X-Title", "spacebot"),
-                    ],
+                    &headers,
                 )
                 .await
             }
             ApiType::Gemini => {
-                self.stream_openai_compatible(request, "Google Gemini", &provider_config)
+                self.stream_openai_compatible(request, "Google Gemini", provider_config)
                     .await
             }
             ApiType::Anthropic | ApiType::OpenAiResponses => {
-                let response = self.attempt_completion(request).await?;
+                let response = self.call_provider(request.clone(), provider_config).await?;
+                let response = self.repair_tool_calls(response, &request).await?;
                 Ok(stream_from_completion_response(response))
             }
         }
     }
-}
 
-impl SpacebotModel {
     async fn call_anthropic(
         &self,
         request: CompletionRequest,
@@ -576,13 +991,15 @@ impl SpacebotModel {
             anthropic_request.auth_path == crate::llm::anthropic::AnthropicAuthPath::OAuthToken;
         let original_tools = anthropic_request.original_tools;
 
-        let response = anthropic_request
-            .builder
-            .send()
-            .await
-            .map_err(|e| CompletionError::ProviderError(e.to_string()))?;
+        let mut request_builder = anthropic_request.builder;
+        for (key, value) in &provider_config.extra_headers {
+            request_builder = request_builder.header(key, value);
+        }
+
+        let response = request_builder.send().await.map_err(send_error)?;
 
         let status = response.status();
+        let retry_after = retry_after_hint(&response);
         let response_text = response.text().await.map_err(|e| {
             CompletionError::ProviderError(format!("failed to read response body: {e}"))
         })?;
@@ -600,7 +1017,7 @@ impl SpacebotModel {
                 .as_str()
                 .unwrap_or("unknown error");
             return Err(CompletionError::ProviderError(format!(
-                "Anthropic API error ({status}): {message}"
+                "Anthropic API error ({status}): {message}{retry_after}"
             )));
         }
 
@@ -677,6 +1094,9 @@ impl SpacebotModel {
                 })
                 .collect();
             body["tools"] = serde_json::json!(tools);
+            if forbids_tool_calls(&request) {
+                body["tool_choice"] = serde_json::json!("none");
+            }
         }
 
         let chat_completions_url = format!(
@@ -696,6 +1116,7 @@ impl SpacebotModel {
             || chat_completions_url.contains("moonshot.ai");
 
         self.stream_openai_chat_request(
+            &request,
             move |request_body| {
                 let mut request_builder = http_client
                     .post(&chat_completions_url)
@@ -785,6 +1206,9 @@ impl SpacebotModel {
                 })
                 .collect();
             body["tools"] = serde_json::json!(tools);
+            if forbids_tool_calls(&request) {
+                body["tool_choice"] = serde_json::json!("none");
+            }
         }
 
         let openai_account_id = if self.provider == "openai-chatgpt" {
@@ -814,14 +1238,18 @@ impl SpacebotModel {
                     format!("spacebot/{}", env!("CARGO_PKG_VERSION")),
                 );
         }
+        for (key, value) in &provider_config.extra_headers {
+            request_builder = request_builder.header(key, value);
+        }
 
         let response = request_builder
             .json(&body)
             .send()
             .await
-            .map_err(|e| CompletionError::ProviderError(e.to_string()))?;
+            .map_err(send_error)?;
 
         let status = response.status();
+        let retry_after = retry_after_hint(&response);
         let response_text = response.text().await.map_err(|e| {
             CompletionError::ProviderError(format!("failed to read response body: {e}"))
         })?;
@@ -830,7 +1258,7 @@ impl SpacebotModel {
             let message = parse_openai_error_message(&response_text)
                 .unwrap_or_else(|| "unknown error".to_string());
             return Err(CompletionError::ProviderError(format!(
-                "{provider_label} Responses API error ({status}): {message}"
+                "{provider_label} Responses API error ({status}): {message}{retry_after}"
             )));
         }
 
@@ -863,6 +1291,19 @@ impl SpacebotModel {
         collect_streaming_completion_response(stream).await
     }
 
+    /// Thinking budget for this Gemini model from the process type's
+    /// `*_thinking_effort`, or None to leave the model's dynamic default.
+    /// Only Gemini 2.5 and later accept a thinking config.
+    fn gemini_thinking_budget(&self) -> Option<u32> {
+        if !(self.model_name.starts_with("gemini-2.5") || self.model_name.starts_with("gemini-3")) {
+            return None;
+        }
+        let routing = self.routing.as_ref()?;
+        routing
+            .gemini_thinking_budgets
+            .budget_for_effort(routing.thinking_effort_for_model(&self.full_model_name))
+    }
+
     async fn stream_openai_compatible(
         &self,
         request: CompletionRequest,
@@ -912,6 +1353,16 @@ impl SpacebotModel {
             body["temperature"] = serde_json::json!(temperature);
         }
 
+        if provider_config.api_type == ApiType::Gemini
+            && let Some(thinking_budget) = self.gemini_thinking_budget()
+        {
+            body["extra_body"] = serde_json::json!({
+                "google": {
+                    "thinking_config": { "thinking_budget": thinking_budget },
+                },
+            });
+        }
+
         if !request.tools.is_empty() {
             let tools: Vec<serde_json::Value> = request
                 .tools
@@ -928,17 +1379,27 @@ impl SpacebotModel {
                 })
                 .collect();
             body["tools"] = serde_json::json!(tools);
+            if forbids_tool_calls(&request) {
+                body["tool_choice"] = serde_json::json!("none");
+            }
         }
 
         let http_client = self.llm_manager.http_client().clone();
         let auth_header = format!("Bearer {api_key}");
+        let extra_headers = provider_config.extra_headers.clone();
         self.stream_openai_chat_request(
+            &request,
             move |request_body| {
-                http_client
+                let mut request_builder = http_client
                     .post(&endpoint)
                     .header("authorization", auth_header.clone())
-                    .header("content-type", "application/json")
-                    .json(request_body)
+                    .header("content-type", "application/json");
+
+                for (key, value) in &extra_headers {
+                    request_builder = request_builder.header(key, value);
+                }
+
+                request_builder.json(request_body)
             },
             body,
             provider_display_name,
@@ -1021,6 +1482,9 @@ impl SpacebotModel {
                 })
                 .collect();
             body["tools"] = serde_json::json!(tools);
+            if forbids_tool_calls(&request) {
+                body["tool_choice"] = serde_json::json!("none");
+            }
         }
 
         let http_client = self.llm_manager.http_client().clone();
@@ -1032,6 +1496,7 @@ impl SpacebotModel {
             .collect();
 
         self.stream_openai_chat_request(
+            &request,
             move |request_body| {
                 let mut request_builder = http_client.post(&endpoint);
 
@@ -1055,6 +1520,7 @@ impl SpacebotModel {
 
     async fn stream_openai_chat_request<F>(
         &self,
+        request: &CompletionRequest,
         mut build_request: F,
         request_body: serde_json::Value,
         provider_label: &str,
@@ -1068,17 +1534,18 @@ impl SpacebotModel {
             .timeout(std::time::Duration::from_secs(STREAM_REQUEST_TIMEOUT_SECS))
             .send()
             .await
-            .map_err(|error| CompletionError::ProviderError(error.to_string()))?;
+            .map_err(send_error)?;
 
         let status = response.status();
         if !status.is_success() {
+            let retry_after = retry_after_hint(&response);
             let response_text = response
                 .text()
                 .await
                 .unwrap_or_else(|error| format!("failed to read error response body: {error}"));
 
             return Err(CompletionError::ProviderError(format!(
-                "{provider_label} API error ({})",
+                "{provider_label} API error ({}){retry_after}",
                 format_api_error_from_response_text(status, &response_text)
             )));
         }
@@ -1167,16 +1634,8 @@ impl SpacebotModel {
                 }
             }
 
-            match flush_openai_streaming_tool_calls(&mut pending_tool_calls) {
-                Ok(events) => {
-                    for event in events {
-                        yield Ok(event);
-                    }
-                }
-                Err(error) => {
-                    yield Err(error);
-                    return;
-                }
+            for event in flush_openai_streaming_tool_calls(&mut pending_tool_calls) {
+                yield Ok(event);
             }
 
             if saw_data_event {
@@ -1235,11 +1694,53 @@ impl SpacebotModel {
             }));
         };
 
-        Ok(StreamingCompletionResponse::stream(Box::pin(stream)))
+        Ok(StreamingCompletionResponse::stream(
+            self.repair_streamed_tool_calls(request, Box::pin(stream)),
+        ))
+    }
+
+    /// Repair tool calls marked as malformed before `inner` yields them, the
+    /// same way [`Self::repair_tool_calls`] does for a whole response. A call
+    /// that can't be repaired ends the stream with an error.
+    fn repair_streamed_tool_calls(
+        &self,
+        request: &CompletionRequest,
+        mut inner: StreamingResult<RawStreamingResponse>,
+    ) -> StreamingResult<RawStreamingResponse> {
+        let model = self.clone();
+        let request = request.clone();
+        Box::pin(async_stream::stream! {
+            while let Some(event) = inner.next().await {
+                let Ok(RawStreamingChoice::ToolCall(mut tool_call)) = event else {
+                    yield event;
+                    continue;
+                };
+                if let Some(malformed) =
+                    tool_repair::take_malformed_params(&mut tool_call.additional_params)
+                {
+                    match model
+                        .repair_tool_arguments(&tool_call.name, malformed, &request)
+                        .await
+                    {
+                        Ok(arguments) => tool_call.arguments = arguments,
+                        Err(error) => {
+                            yield Err(error);
+                            return;
+                        }
+                    }
+                }
+                yield Ok(RawStreamingChoice::ToolCall(tool_call));
+            }
+        })
     }
 }
 // --- Helpers ---
 
+/// Whether the request forbids tool calls while still defining tools.
+pub(crate) fn forbids_tool_calls(request: &CompletionRequest) -> bool {
+    matches!(request.tool_choice, Some(rig::message::ToolChoice::None))
+}
+
 /// Reverse-map Claude Code canonical tool names back to the original names
 /// from the request's tool definitions.
 fn reverse_map_tool_names(
@@ -1268,7 +1769,7 @@ fn tool_result_content_to_string(content: &OneOrMany<rig::message::ToolResultCon
 // --- Message conversion ---
 
 pub fn convert_messages_to_anthropic(messages: &OneOrMany<Message>) -> Vec<serde_json::Value> {
-    messages
+    normalize_history(messages)
         .iter()
         .filter_map(|message| match message {
             Message::User { content } => {
@@ -1280,8 +1781,8 @@ pub fn convert_messages_to_anthropic(messages: &OneOrMany<Message>) -> Vec<serde
                         UserContent::Image(image) => convert_image_anthropic(image),
                         UserContent::ToolResult(result) => Some(serde_json::json!({
                             "type": "tool_result",
-                            "tool_use_id": result.id,
-                            "content": tool_result_content_to_string(&result.content),
+                            "tool_use_id": anthropic_tool_id(&result.id),
+                            "content": tool_result_content_anthropic(&result.content),
                         })),
                         _ => None,
                     })
@@ -1296,7 +1797,7 @@ pub fn convert_messages_to_anthropic(messages: &OneOrMany<Message>) -> Vec<serde
                             .then(|| serde_json::json!({"type": "text", "text": t.text})),
                         AssistantContent::ToolCall(tc) => Some(serde_json::json!({
                             "type": "tool_use",
-                            "id": tc.id,
+                            "id": anthropic_tool_id(&tc.id),
                             "name": tc.function.name,
                             "input": tc.function.arguments,
                         })),
@@ -1310,15 +1811,68 @@ pub fn convert_messages_to_anthropic(messages: &OneOrMany<Message>) -> Vec<serde
         .collect()
 }
 
+/// Anthropic only accepts tool IDs matching `^[a-zA-Z0-9_-]+$`. IDs minted by
+/// other providers (Kimi's `functions.read:0`, for one) are rewritten the same
+/// way on the call and on its result, so the pair still matches.
+fn anthropic_tool_id(id: &str) -> Cow<'_, str> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    if id.is_empty() {
+        Cow::Borrowed("toolu_unknown")
+    } else if id.chars().all(valid) {
+        Cow::Borrowed(id)
+    } else {
+        Cow::Owned(id.chars().map(|c| if valid(c) { c } else { '_' }).collect())
+    }
+}
+
+/// Anthropic tool results are a plain string unless they carry images, in
+/// which case they become a list of text and image blocks.
+fn tool_result_content_anthropic(
+    content: &OneOrMany<rig::message::ToolResultContent>,
+) -> serde_json::Value {
+    let has_images = content
+        .iter()
+        .any(|c| matches!(c, rig::message::ToolResultContent::Image(_)));
+    if !has_images {
+        return serde_json::json!(tool_result_content_to_string(content));
+    }
+
+    let blocks: Vec<serde_json::Value> = content
+        .iter()
+        .filter_map(|c| match c {
+            rig::message::ToolResultContent::Text(t) => (!t.text.trim().is_empty())
+                .then(|| serde_json::json!({"type": "text", "text": t.text})),
+            rig::message::ToolResultContent::Image(image) => convert_image_anthropic(image),
+        })
+        .collect();
+    serde_json::json!(blocks)
+}
+
+/// Images returned inside tool results. Tool messages in the OpenAI formats
+/// are text-only, so these are sent in a user message after the results.
+fn tool_result_images(content: &OneOrMany<rig::message::ToolResultContent>) -> Vec<&Image> {
+    content
+        .iter()
+        .filter_map(|c| match c {
+            rig::message::ToolResultContent::Image(image) => Some(image),
+            rig::message::ToolResultContent::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Lead-in for the user message carrying tool result images.
+const TOOL_RESULT_IMAGES_NOTE: &str = "Images returned by the tool calls above:";
+
 fn convert_messages_to_openai(messages: &OneOrMany<Message>) -> Vec<serde_json::Value> {
     let mut result = Vec::new();
 
-    for message in messages.iter() {
+    for message in normalize_history(messages).iter() {
         match message {
             Message::User { content } => {
                 // Separate tool results (they need their own messages) from content parts
                 let mut content_parts: Vec<serde_json::Value> = Vec::new();
                 let mut tool_results: Vec<serde_json::Value> = Vec::new();
+                let mut result_images: Vec<serde_json::Value> = Vec::new();
 
                 for item in content.iter() {
                     match item {
@@ -1344,11 +1898,27 @@ fn convert_messages_to_openai(messages: &OneOrMany<Message>) -> Vec<serde_json::
                                 "tool_call_id": tool_call_id,
                                 "content": tool_result_content_to_string(&tr.content),
                             }));
+                            result_images.extend(
+                                tool_result_images(&tr.content)
+                                    .into_iter()
+                                    .filter_map(convert_image_openai),
+                            );
                         }
                         _ => {}
                     }
                 }
 
+                // Tool messages must directly follow the assistant message
+                // that made the calls, so they go before any user content.
+                result.extend(tool_results);
+                if !result_images.is_empty() {
+                    content_parts.push(serde_json::json!({
+                        "type": "text",
+                        "text": TOOL_RESULT_IMAGES_NOTE,
+                    }));
+                    content_parts.extend(result_images);
+                }
+
                 if !content_parts.is_empty() {
                     // If there's only one text part and no images, use simple string format
                     if content_parts.len() == 1 && content_parts[0]["type"] == "text" {
@@ -1364,8 +1934,6 @@ fn convert_messages_to_openai(messages: &OneOrMany<Message>) -> Vec<serde_json::
                         }));
                     }
                 }
-
-                result.extend(tool_results);
             }
             Message::Assistant { content, .. } => {
                 let mut text_parts = Vec::new();
@@ -1426,7 +1994,7 @@ fn convert_messages_to_openai(messages: &OneOrMany<Message>) -> Vec<serde_json::
     result
 }
 
-fn collect_reasoning_text_parts(reasoning: &rig::message::Reasoning) -> Vec<String> {
+pub(crate) fn collect_reasoning_text_parts(reasoning: &rig::message::Reasoning) -> Vec<String> {
     reasoning
         .content
         .iter()
@@ -1445,10 +2013,11 @@ fn collect_reasoning_text_parts(reasoning: &rig::message::Reasoning) -> Vec<Stri
 fn convert_messages_to_openai_responses(messages: &OneOrMany<Message>) -> Vec<serde_json::Value> {
     let mut result = Vec::new();
 
-    for message in messages.iter() {
+    for message in normalize_history(messages).iter() {
         match message {
             Message::User { content } => {
                 let mut content_parts = Vec::new();
+                let mut result_images = Vec::new();
 
                 for item in content.iter() {
                     match item {
@@ -1474,11 +2043,24 @@ fn convert_messages_to_openai_responses(messages: &OneOrMany<Message>) -> Vec<se
                                 "call_id": call_id,
                                 "output": tool_result_content_to_string(&tool_result.content),
                             }));
+                            result_images.extend(
+                                tool_result_images(&tool_result.content)
+                                    .into_iter()
+                                    .filter_map(convert_image_openai_responses),
+                            );
                         }
                         _ => {}
                     }
                 }
 
+                if !result_images.is_empty() {
+                    content_parts.push(serde_json::json!({
+                        "type": "input_text",
+                        "text": TOOL_RESULT_IMAGES_NOTE,
+                    }));
+                    content_parts.extend(result_images);
+                }
+
                 if !content_parts.is_empty() {
                     result.push(serde_json::json!({
                         "role": "user",
@@ -1561,22 +2143,21 @@ fn convert_image_anthropic(image: &Image) -> Option<serde_json::Value> {
         .unwrap_or("image/jpeg");
 
     match &image.data {
-        DocumentSourceKind::Base64(data) => Some(serde_json::json!({
+        DocumentSourceKind::Url(url) => Some(serde_json::json!({
             "type": "image",
             "source": {
-                "type": "base64",
-                "media_type": media_type,
-                "data": data,
+                "type": "url",
+                "url": url,
             }
         })),
-        DocumentSourceKind::Url(url) => Some(serde_json::json!({
+        data => Some(serde_json::json!({
             "type": "image",
             "source": {
-                "type": "url",
-                "url": url,
+                "type": "base64",
+                "media_type": media_type,
+                "data": inline_image_base64(data)?,
             }
         })),
-        _ => None,
     }
 }
 
@@ -1590,18 +2171,18 @@ fn convert_image_openai(image: &Image) -> Option<serde_json::Value> {
         .unwrap_or("image/jpeg");
 
     match &image.data {
-        DocumentSourceKind::Base64(data) => {
+        DocumentSourceKind::Url(url) => Some(serde_json::json!({
+            "type": "image_url",
+            "image_url": { "url": url }
+        })),
+        data => {
+            let data = inline_image_base64(data)?;
             let data_url = format!("data:{media_type};base64,{data}");
             Some(serde_json::json!({
                 "type": "image_url",
                 "image_url": { "url": data_url }
             }))
         }
-        DocumentSourceKind::Url(url) => Some(serde_json::json!({
-            "type": "image_url",
-            "image_url": { "url": url }
-        })),
-        _ => None,
     }
 }
 
@@ -1613,17 +2194,31 @@ fn convert_image_openai_responses(image: &Image) -> Option<serde_json::Value> {
         .unwrap_or("image/jpeg");
 
     match &image.data {
-        DocumentSourceKind::Base64(data) => {
+        DocumentSourceKind::Url(url) => Some(serde_json::json!({
+            "type": "input_image",
+            "image_url": url,
+        })),
+        data => {
+            let data = inline_image_base64(data)?;
             let data_url = format!("data:{media_type};base64,{data}");
             Some(serde_json::json!({
                 "type": "input_image",
                 "image_url": data_url,
             }))
         }
-        DocumentSourceKind::Url(url) => Some(serde_json::json!({
-            "type": "input_image",
-            "image_url": url,
-        })),
+    }
+}
+
+/// Base64 payload of an inline image, encoding raw bytes when needed.
+fn inline_image_base64(data: &DocumentSourceKind) -> Option<Cow<'_, str>> {
+    match data {
+        DocumentSourceKind::Base64(data) => Some(Cow::Borrowed(data)),
+        DocumentSourceKind::Raw(bytes) => {
+            use base64::Engine as _;
+            Some(Cow::Owned(
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+            ))
+        }
         _ => None,
     }
 }
@@ -1644,6 +2239,43 @@ fn with_streaming_enabled(request_body: &serde_json::Value) -> serde_json::Value
     body
 }
 
+/// Map a failed request send. Connection failures keep the `reqwest::Error`
+/// as an HTTP error so base URL failover can tell them apart from errors the
+/// provider returned.
+fn send_error(error: reqwest::Error) -> CompletionError {
+    if error.is_connect() {
+        CompletionError::HttpError(rig::http_client::Error::Instance(Box::new(error)))
+    } else {
+        CompletionError::ProviderError(error.to_string())
+    }
+}
+
+/// Whether the provider could not be reached at all, as mapped by
+/// [`send_error`].
+fn is_connection_error(error: &CompletionError) -> bool {
+    let CompletionError::HttpError(rig::http_client::Error::Instance(inner)) = error else {
+        return false;
+    };
+    inner
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(reqwest::Error::is_connect)
+}
+
+/// Routing marker carrying a 429's `Retry-After` delay (in seconds), or an
+/// empty string when the response isn't a rate limit or has no usable header.
+fn retry_after_hint(response: &reqwest::Response) -> String {
+    if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return String::new();
+    }
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(routing::retry_after_marker)
+        .unwrap_or_default()
+}
+
 fn format_api_error_from_response_text(status: reqwest::StatusCode, response_text: &str) -> String {
     if let Ok(body) = serde_json::from_str::<serde_json::Value>(response_text) {
         format_api_error(status, &body)
@@ -1873,7 +2505,7 @@ fn escape_control_characters_in_json_strings(input: &str) -> String {
 fn parse_streamed_tool_arguments(
     tool_name: &str,
     raw_arguments: &str,
-) -> Result<serde_json::Value, CompletionError> {
+) -> Result<serde_json::Value, String> {
     if raw_arguments.trim().is_empty() {
         return Ok(serde_json::json!({}));
     }
@@ -1894,21 +2526,51 @@ fn parse_streamed_tool_arguments(
                 return Ok(arguments);
             }
             Err(sanitized_parse_error) => {
-                return Err(CompletionError::ProviderError(format!(
+                return Err(format!(
                     "invalid streamed tool arguments for '{tool_name}': {direct_parse_error}; after sanitization: {sanitized_parse_error}"
-                )));
+                ));
             }
         }
     }
 
-    Err(CompletionError::ProviderError(format!(
+    Err(format!(
         "invalid streamed tool arguments for '{tool_name}': {direct_parse_error}"
-    )))
+    ))
+}
+
+/// Parse raw tool arguments. Arguments that aren't valid JSON come back as
+/// `{}` with the `additional_params` that mark the call for repair.
+fn parse_tool_arguments_for_repair(
+    tool_name: &str,
+    raw_arguments: &str,
+) -> (serde_json::Value, Option<serde_json::Value>) {
+    match parse_streamed_tool_arguments(tool_name, raw_arguments) {
+        Ok(arguments) => (arguments, None),
+        Err(error) => (
+            serde_json::json!({}),
+            Some(tool_repair::malformed_arguments_params(
+                raw_arguments,
+                &error,
+            )),
+        ),
+    }
+}
+
+/// Concatenated text parts of a completion choice.
+fn response_text(choice: &OneOrMany<AssistantContent>) -> String {
+    choice
+        .iter()
+        .filter_map(|content| match content {
+            AssistantContent::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("")
 }
 
 fn flush_openai_streaming_tool_calls(
     pending_tool_calls: &mut BTreeMap<usize, OpenAiStreamingToolCall>,
-) -> Result<Vec<RawStreamingChoice<RawStreamingResponse>>, CompletionError> {
+) -> Vec<RawStreamingChoice<RawStreamingResponse>> {
     let mut flushed = Vec::new();
 
     for (index, tool_call) in std::mem::take(pending_tool_calls) {
@@ -1922,7 +2584,8 @@ fn flush_openai_streaming_tool_calls(
             tool_call.id
         };
 
-        let arguments = parse_streamed_tool_arguments(&tool_call.name, &tool_call.arguments)?;
+        let (arguments, additional_params) =
+            parse_tool_arguments_for_repair(&tool_call.name, &tool_call.arguments);
 
         flushed.push(RawStreamingChoice::ToolCall(RawStreamingToolCall {
             id,
@@ -1931,11 +2594,11 @@ fn flush_openai_streaming_tool_calls(
             name: tool_call.name,
             arguments,
             signature: None,
-            additional_params: None,
+            additional_params,
         }));
     }
 
-    Ok(flushed)
+    flushed
 }
 
 fn process_openai_chat_stream_event(
@@ -2094,7 +2757,7 @@ fn process_openai_chat_stream_event(
             .and_then(serde_json::Value::as_str)
             && matches!(finish_reason, "tool_calls" | "function_call")
         {
-            events.extend(flush_openai_streaming_tool_calls(pending_tool_calls)?);
+            events.extend(flush_openai_streaming_tool_calls(pending_tool_calls));
         }
     }
 
@@ -2642,9 +3305,14 @@ fn parse_openai_tool_call(tool_call: &serde_json::Value, fallback_id: String) ->
         .and_then(|function| function.get("arguments"))
         .or_else(|| tool_call.get("arguments"))
         .unwrap_or(&null);
-    let arguments = parse_openai_tool_arguments(arguments_field);
+    let (arguments, additional_params) = match arguments_field.as_str() {
+        Some(raw_arguments) => parse_tool_arguments_for_repair(name, raw_arguments),
+        None => (parse_openai_tool_arguments(arguments_field), None),
+    };
 
-    Some(make_tool_call(id, name.to_string(), arguments))
+    let mut tool_call = make_tool_call(id, name.to_string(), arguments);
+    tool_call.additional_params = additional_params;
+    Some(tool_call)
 }
 
 fn extract_text_content_from_responses_output_item(
@@ -2755,11 +3423,14 @@ fn parse_openai_responses_response(
                     .or_else(|| call_id.clone())
                     .unwrap_or_else(|| format!("function_call_{index}"));
                 let name = output_item["name"].as_str().unwrap_or("").to_string();
-                let arguments = parse_openai_tool_arguments(&output_item["arguments"]);
+                let (arguments, additional_params) = match output_item["arguments"].as_str() {
+                    Some(raw_arguments) => parse_tool_arguments_for_repair(&name, raw_arguments),
+                    None => (parse_openai_tool_arguments(&output_item["arguments"]), None),
+                };
 
-                assistant_content.push(AssistantContent::ToolCall(
-                    make_openai_responses_tool_call(id, call_id, name, arguments),
-                ));
+                let mut tool_call = make_openai_responses_tool_call(id, call_id, name, arguments);
+                tool_call.additional_params = additional_params;
+                assistant_content.push(AssistantContent::ToolCall(tool_call));
             }
             _ => {
                 extract_text_content_from_responses_output_item(
@@ -3270,34 +3941,60 @@ mod tests {
 
     #[test]
     fn convert_messages_to_openai_tool_result_prefers_call_id_over_id() {
-        let messages = OneOrMany::one(Message::User {
-            content: OneOrMany::one(UserContent::ToolResult(rig::message::ToolResult {
-                id: "legacy-id".to_string(),
-                call_id: Some("stable-call-id".to_string()),
-                content: OneOrMany::one(rig::message::ToolResultContent::text("ok")),
-            })),
-        });
+        let messages = OneOrMany::many(vec![
+            Message::Assistant {
+                id: None,
+                content: OneOrMany::one(AssistantContent::ToolCall(
+                    ToolCall::new(
+                        "legacy-id".to_string(),
+                        rig::message::ToolFunction::new("reply".to_string(), serde_json::json!({})),
+                    )
+                    .with_call_id("stable-call-id".to_string()),
+                )),
+            },
+            Message::User {
+                content: OneOrMany::one(UserContent::ToolResult(rig::message::ToolResult {
+                    id: "legacy-id".to_string(),
+                    call_id: Some("stable-call-id".to_string()),
+                    content: OneOrMany::one(rig::message::ToolResultContent::text("ok")),
+                })),
+            },
+        ])
+        .expect("non-empty message list");
 
         let converted = convert_messages_to_openai(&messages);
-        assert_eq!(converted.len(), 1);
-        assert_eq!(converted[0]["role"], "tool");
-        assert_eq!(converted[0]["tool_call_id"], "stable-call-id");
+        assert_eq!(converted.len(), 2);
+        assert_eq!(converted[1]["role"], "tool");
+        assert_eq!(converted[1]["tool_call_id"], "stable-call-id");
     }
 
     #[test]
     fn convert_messages_to_openai_responses_function_call_output_prefers_call_id_over_id() {
-        let messages = OneOrMany::one(Message::User {
-            content: OneOrMany::one(UserContent::ToolResult(rig::message::ToolResult {
-                id: "legacy-id".to_string(),
-                call_id: Some("stable-call-id".to_string()),
-                content: OneOrMany::one(rig::message::ToolResultContent::text("ok")),
-            })),
-        });
+        let messages = OneOrMany::many(vec![
+            Message::Assistant {
+                id: None,
+                content: OneOrMany::one(AssistantContent::ToolCall(
+                    ToolCall::new(
+                        "legacy-id".to_string(),
+                        rig::message::ToolFunction::new("reply".to_string(), serde_json::json!({})),
+                    )
+                    .with_call_id("stable-call-id".to_string()),
+                )),
+            },
+            Message::User {
+                content: OneOrMany::one(UserContent::ToolResult(rig::message::ToolResult {
+                    id: "legacy-id".to_string(),
+                    call_id: Some("stable-call-id".to_string()),
+                    content: OneOrMany::one(rig::message::ToolResultContent::text("ok")),
+                })),
+            },
+        ])
+        .expect("non-empty message list");
 
         let converted = convert_messages_to_openai_responses(&messages);
-        assert_eq!(converted.len(), 1);
-        assert_eq!(converted[0]["type"], "function_call_output");
-        assert_eq!(converted[0]["call_id"], "stable-call-id");
+        assert_eq!(converted.len(), 2);
+        assert_eq!(converted[1]["type"], "function_call_output");
+        assert_eq!(converted[1]["call_id"], "stable-call-id");
     }
 
     #[test]
@@ -3575,7 +4272,7 @@ mod tests {
     }
 
     #[test]
-    fn flush_openai_streaming_tool_calls_errors_on_invalid_arguments() {
+    fn flush_openai_streaming_tool_calls_marks_invalid_arguments_for_repair() {
         let mut pending = BTreeMap::new();
         pending.insert(
             0,
@@ -3587,13 +4284,22 @@ mod tests {
             },
         );
 
-        let error = flush_openai_streaming_tool_calls(&mut pending)
-            .expect_err("invalid JSON arguments should error");
+        let events = flush_openai_streaming_tool_calls(&mut pending);
+        let [RawStreamingChoice::ToolCall(tool_call)] = events.as_slice() else {
+            panic!("expected a single tool call");
+        };
+        assert_eq!(tool_call.arguments, serde_json::json!({}));
+        let marker = &tool_call
+            .additional_params
+            .as_ref()
+            .expect("invalid arguments should be marked")[tool_repair::MALFORMED_ARGUMENTS_KEY];
+        assert_eq!(marker["raw"], "{\"operation\":\"list\"");
         assert!(
-            error
-                .to_string()
+            marker["error"]
+                .as_str()
+                .unwrap()
                 .contains("invalid streamed tool arguments for 'file'"),
-            "unexpected error: {error}"
+            "unexpected marker: {marker}"
         );
     }
 
@@ -3610,8 +4316,7 @@ mod tests {
             },
         );
 
-        let events = flush_openai_streaming_tool_calls(&mut pending)
-            .expect("control-char recovery should parse");
+        let events = flush_openai_streaming_tool_calls(&mut pending);
         let tool_calls: Vec<_> = events
             .into_iter()
             .filter_map(|event| match event {
@@ -3719,4 +4424,670 @@ mod tests {
         assert!(msg.contains("Google"));
         assert!(msg.contains("invalid schema"));
     }
+
+    /// The converter each API type sends chat history through. Exhaustive so
+    /// that a new API type has to pick one.
+    fn convert_history_for(
+        api_type: &ApiType,
+        messages: &OneOrMany<Message>,
+    ) -> Vec<serde_json::Value> {
+        match api_type {
+            ApiType::Anthropic => convert_messages_to_anthropic(messages),
+            ApiType::OpenAiResponses => convert_messages_to_openai_responses(messages),
+            ApiType::OpenAiCompletions
+            | ApiType::OpenAiChatCompletions
+            | ApiType::KiloGateway
+            | ApiType::Gemini => convert_messages_to_openai(messages),
+        }
+    }
+
+    const ALL_API_TYPES: [ApiType; 6] = [
+        ApiType::OpenAiCompletions,
+        ApiType::OpenAiChatCompletions,
+        ApiType::KiloGateway,
+        ApiType::OpenAiResponses,
+        ApiType::Anthropic,
+        ApiType::Gemini,
+    ];
+
+    fn png(data: &str) -> rig::message::Image {
+        rig::message::Image {
+            data: DocumentSourceKind::Base64(data.to_string()),
+            media_type: Some(rig::message::ImageMediaType::PNG),
+            detail: None,
+            additional_params: None,
+        }
+    }
+
+    fn tool_call(id: &str, call_id: &str, name: &str) -> AssistantContent {
+        AssistantContent::ToolCall(
+            ToolCall::new(
+                id.to_string(),
+                rig::message::ToolFunction::new(name.to_string(), serde_json::json!({"q": id})),
+            )
+            .with_call_id(call_id.to_string()),
+        )
+    }
+
+    fn tool_result(
+        id: &str,
+        call_id: &str,
+        content: Vec<rig::message::ToolResultContent>,
+    ) -> UserContent {
+        UserContent::ToolResult(rig::message::ToolResult {
+            id: id.to_string(),
+            call_id: Some(call_id.to_string()),
+            content: OneOrMany::many(content).expect("non-empty tool result"),
+        })
+    }
+
+    /// A multi-turn, multi-tool conversation in the shape it comes back from
+    /// compaction or a provider switch: results out of order and split from
+    /// their calls, an image in a tool result, a call whose result was lost,
+    /// and an orphan result.
+    fn multi_tool_history() -> OneOrMany<Message> {
+        OneOrMany::many(vec![
+            Message::User {
+                content: OneOrMany::many(vec![
+                    UserContent::text("what's in this screenshot?"),
+                    UserContent::Image(png("c2NyZWVu")),
+                ])
+                .unwrap(),
+            },
+            Message::Assistant {
+                id: None,
+                content: OneOrMany::many(vec![
+                    AssistantContent::text("checking"),
+                    tool_call("functions.browse:0", "call_a", "browse"),
+                    tool_call("functions.file:1", "call_b", "file"),
+                ])
+                .unwrap(),
+            },
+            Message::User {
+                content: OneOrMany::many(vec![
+                    tool_result(
+                        "functions.file:1",
+                        "call_b",
+                        vec![rig::message::ToolResultContent::text("file contents")],
+                    ),
+                    tool_result(
+                        "stale",
+                        "call_stale",
+                        vec![rig::message::ToolResultContent::text("orphan")],
+                    ),
+                ])
+                .unwrap(),
+            },
+            Message::User {
+                content: OneOrMany::one(tool_result(
+                    "functions.browse:0",
+                    "call_a",
+                    vec![
+                        rig::message::ToolResultContent::text("page loaded"),
+                        rig::message::ToolResultContent::Image(png("cGFnZQ==")),
+                    ],
+                )),
+            },
+            Message::assistant("it's a login page"),
+            Message::user("search for the docs"),
+            Message::Assistant {
+                id: None,
+                content: OneOrMany::one(tool_call("functions.search:2", "call_c", "search")),
+            },
+            Message::user("never mind"),
+        ])
+        .unwrap()
+    }
+
+    /// Tool call IDs and tool result IDs in wire order, as `(is_call, id)`.
+    fn tool_sequence(api_type: &ApiType, converted: &[serde_json::Value]) -> Vec<(bool, String)> {
+        let id = |value: &serde_json::Value| value.as_str().unwrap().to_string();
+        let mut sequence = Vec::new();
+        for message in converted {
+            match api_type {
+                ApiType::Anthropic => {
+                    for block in message["content"].as_array().into_iter().flatten() {
+                        match block["type"].as_str() {
+                            Some("tool_use") => sequence.push((true, id(&block["id"]))),
+                            Some("tool_result") => {
+                                sequence.push((false, id(&block["tool_use_id"])))
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                ApiType::OpenAiResponses => match message["type"].as_str() {
+                    Some("function_call") => sequence.push((true, id(&message["call_id"]))),
+                    Some("function_call_output") => sequence.push((false, id(&message["call_id"]))),
+                    _ => {}
+                },
+                _ => {
+                    for call in message["tool_calls"].as_array().into_iter().flatten() {
+                        sequence.push((true, id(&call["id"])));
+                    }
+                    if message["role"] == "tool" {
+                        sequence.push((false, id(&message["tool_call_id"])));
+                    }
+                }
+            }
+        }
+        sequence
+    }
+
+    #[test]
+    fn every_api_type_pairs_tool_calls_with_results_in_order() {
+        let history = multi_tool_history();
+
+        for api_type in &ALL_API_TYPES {
+            let converted = convert_history_for(api_type, &history);
+            let sequence = tool_sequence(api_type, &converted);
+
+            let calls: Vec<&String> = sequence
+                .iter()
+                .filter_map(|(is_call, id)| is_call.then_some(id))
+                .collect();
+            let results: Vec<&String> = sequence
+                .iter()
+                .filter_map(|(is_call, id)| (!is_call).then_some(id))
+                .collect();
+            assert_eq!(calls.len(), 3, "{api_type:?}: {converted:#?}");
+            assert_eq!(
+                calls, results,
+                "{api_type:?}: every call answered once, in order"
+            );
+
+            // Each batch of results comes before any later call.
+            let first_result = sequence.iter().position(|(is_call, _)| !is_call).unwrap();
+            assert_eq!(first_result, 2, "{api_type:?}: {sequence:?}");
+            assert!(
+                !sequence.iter().any(|(_, id)| id.contains("stale")),
+                "{api_type:?}: orphan result dropped"
+            );
+        }
+    }
+
+    #[test]
+    fn every_api_type_keeps_user_and_tool_result_images() {
+        let history = multi_tool_history();
+
+        for api_type in &ALL_API_TYPES {
+            let converted = convert_history_for(api_type, &history);
+            let serialized = serde_json::to_string(&converted).unwrap();
+            assert!(serialized.contains("c2NyZWVu"), "{api_type:?}: user image");
+            assert!(
+                serialized.contains("cGFnZQ=="),
+                "{api_type:?}: tool result image"
+            );
+        }
+    }
+
+    #[test]
+    fn anthropic_tool_results_follow_their_calls_with_sanitized_ids() {
+        let converted = convert_history_for(&ApiType::Anthropic, &multi_tool_history());
+
+        assert_eq!(converted[1]["role"], "assistant");
+        assert_eq!(converted[1]["content"][1]["id"], "functions_browse_0");
+        assert_eq!(converted[2]["role"], "user");
+        let results = converted[2]["content"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["tool_use_id"], "functions_browse_0");
+        assert_eq!(results[0]["content"][0]["type"], "text");
+        assert_eq!(results[0]["content"][1]["type"], "image");
+        assert_eq!(results[1]["tool_use_id"], "functions_file_1");
+        assert_eq!(results[1]["content"], "file contents");
+
+        // The lost result is filled in before the next user turn.
+        let last_results = converted[converted.len() - 2]["content"]
+            .as_array()
+            .unwrap();
+        assert_eq!(last_results[0]["tool_use_id"], "functions_search_2");
+        assert_eq!(
+            last_results[0]["content"],
+            crate::llm::normalize::MISSING_TOOL_RESULT
+        );
+    }
+
+    #[test]
+    fn openai_chat_sends_tool_messages_then_result_images_as_user_content() {
+        let converted = convert_history_for(&ApiType::OpenAiChatCompletions, &multi_tool_history());
+
+        assert_eq!(converted[0]["role"], "user");
+        assert_eq!(converted[0]["content"][1]["type"], "image_url");
+        assert_eq!(converted[1]["role"], "assistant");
+        assert_eq!(converted[2]["role"], "tool");
+        assert_eq!(converted[2]["tool_call_id"], "call_a");
+        assert_eq!(converted[3]["role"], "tool");
+        assert_eq!(converted[3]["tool_call_id"], "call_b");
+        assert_eq!(converted[4]["role"], "user");
+        assert_eq!(converted[4]["content"][0]["text"], TOOL_RESULT_IMAGES_NOTE);
+        assert_eq!(
+            converted[4]["content"][1]["image_url"]["url"],
+            "data:image/png;base64,cGFnZQ=="
+        );
+        assert_eq!(converted[5]["role"], "assistant");
+    }
+
+    #[test]
+    fn openai_responses_sends_outputs_then_result_images_as_input_images() {
+        let converted = convert_history_for(&ApiType::OpenAiResponses, &multi_tool_history());
+
+        let kinds: Vec<&str> = converted
+            .iter()
+            .map(|item| {
+                item["type"]
+                    .as_str()
+                    .or_else(|| item["role"].as_str())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            &kinds[..6],
+            [
+                "user",
+                "assistant",
+                "function_call",
+                "function_call",
+                "function_call_output",
+                "function_call_output",
+            ]
+        );
+        assert_eq!(converted[6]["role"], "user");
+        assert_eq!(converted[6]["content"][1]["type"], "input_image");
+    }
+
+    #[test]
+    fn raw_image_bytes_are_base64_encoded() {
+        let image = rig::message::Image {
+            data: DocumentSourceKind::Raw(b"raw".to_vec()),
+            media_type: Some(rig::message::ImageMediaType::JPEG),
+            detail: None,
+            additional_params: None,
+        };
+
+        assert_eq!(
+            convert_image_anthropic(&image).unwrap()["source"]["data"],
+            "cmF3"
+        );
+        assert_eq!(
+            convert_image_openai(&image).unwrap()["image_url"]["url"],
+            "data:image/jpeg;base64,cmF3"
+        );
+    }
+
+    fn empty_llm_config() -> crate::config::LlmConfig {
+        crate::config::LlmConfig {
+            anthropic_key: None,
+            openai_key: None,
+            openrouter_key: None,
+            kilo_key: None,
+            zhipu_key: None,
+            groq_key: None,
+            together_key: None,
+            fireworks_key: None,
+            deepseek_key: None,
+            xai_key: None,
+            mistral_key: None,
+            gemini_key: None,
+            ollama_key: None,
+            ollama_base_url: None,
+            opencode_zen_key: None,
+            opencode_go_key: None,
+            nvidia_key: None,
+            minimax_key: None,
+            minimax_cn_key: None,
+            moonshot_key: None,
+            zai_coding_plan_key: None,
+            github_copilot_key: None,
+            providers: std::collections::HashMap::new(),
+            health: crate::config::ProviderHealthConfig::default(),
+            validate_models: false,
+            default_model: None,
+            attribution: crate::config::AppAttribution::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback_chain_continues_until_a_model_succeeds() {
+        let manager = LlmManager::new(empty_llm_config()).await.unwrap();
+        let mut status_rx = manager.subscribe_status();
+        let fallbacks = vec!["openai/gpt-4.1".to_string(), "groq/llama".to_string()];
+        let attempted = std::sync::Mutex::new(Vec::new());
+
+        let result = complete_with_fallbacks(
+            &manager,
+            "anthropic/claude-sonnet",
+            &fallbacks,
+            60,
+            |model_name| {
+                attempted.lock().unwrap().push(model_name.clone());
+                async move {
+                    if model_name == "groq/llama" {
+                        Ok(model_name)
+                    } else {
+                        Err((
+                            CompletionError::ProviderError(format!("{model_name} is down")),
+                            false,
+                        ))
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), "groq/llama");
+        assert_eq!(
+            *attempted.lock().unwrap(),
+            vec!["anthropic/claude-sonnet", "openai/gpt-4.1", "groq/llama"]
+        );
+
+        let hops: Vec<_> = std::iter::from_fn(|| status_rx.try_recv().ok()).collect();
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].model, "openai/gpt-4.1");
+        assert_eq!(
+            hops[0].status,
+            crate::llm::rate_limit::ProviderStatus::ModelFallback {
+                from_model: "anthropic/claude-sonnet".into(),
+                attempt: 1,
+            }
+        );
+        assert_eq!(hops[1].model, "groq/llama");
+        assert_eq!(
+            hops[1].status,
+            crate::llm::rate_limit::ProviderStatus::ModelFallback {
+                from_model: "openai/gpt-4.1".into(),
+                attempt: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_chain_errors_once_every_model_failed() {
+        let manager = LlmManager::new(empty_llm_config()).await.unwrap();
+        let fallbacks = vec!["openai/gpt-4.1".to_string(), "groq/llama".to_string()];
+
+        let result: Result<(), _> = complete_with_fallbacks(
+            &manager,
+            "anthropic/claude-sonnet",
+            &fallbacks,
+            60,
+            |model_name| async move {
+                Err((
+                    CompletionError::ProviderError(format!("{model_name} is down")),
+                    false,
+                ))
+            },
+        )
+        .await;
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("groq/llama is down"), "{error}");
+    }
+
+    /// Answer a single OpenAI chat completion request with `text`.
+    /// Answer one streamed chat completion per entry in `choices`, in order.
+    async fn serve_chat_completions(
+        listener: tokio::net::TcpListener,
+        choices: Vec<serde_json::Value>,
+    ) {
+        for choice in choices {
+            serve_chat_completion(&listener, choice).await;
+        }
+    }
+
+    async fn serve_chat_completion(listener: &tokio::net::TcpListener, choice: serde_json::Value) {
+        use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let received = String::from_utf8_lossy(&request);
+            let complete = received
+                .split_once("\r\n\r\n")
+                .is_some_and(|(headers, body)| {
+                    let content_length = headers
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    body.len() >= content_length
+                });
+            if complete || read == 0 {
+                break;
+            }
+        }
+
+        let chunk = serde_json::json!({ "choices": [choice] });
+        let body = format!("data: {chunk}\n\ndata: [DONE]\n\n");
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        socket.shutdown().await.unwrap();
+    }
+
+    fn text_choice(text: &str) -> serde_json::Value {
+        serde_json::json!({"index": 0, "delta": {"content": text}, "finish_reason": "stop"})
+    }
+
+    #[tokio::test]
+    async fn connection_failures_are_typed_not_tagged() {
+        let dead_addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let send_failure = reqwest::Client::new()
+            .get(format!("http://{dead_addr}/v1"))
+            .send()
+            .await
+            .unwrap_err();
+
+        let error = send_error(send_failure);
+        assert!(is_connection_error(&error));
+        // The message stays readable and still counts as transient.
+        let message = error.to_string();
+        assert!(!message.contains("[connection"));
+        assert!(routing::is_retriable_error(&message));
+
+        let provider_error = CompletionError::ProviderError("503: connection reset".into());
+        assert!(!is_connection_error(&provider_error));
+    }
+
+    #[tokio::test]
+    async fn unreachable_base_url_fails_over_to_the_next() {
+        // Bind and drop a listener so the port refuses connections.
+        let dead_addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let live_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_addr = live_listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_chat_completions(
+            live_listener,
+            vec![text_choice("hello")],
+        ));
+
+        let dead_url = format!("http://{dead_addr}/v1");
+        let live_url = format!("http://{live_addr}/v1");
+        let mut config = empty_llm_config();
+        config.providers.insert(
+            "test".into(),
+            ProviderConfig {
+                api_type: ApiType::OpenAiChatCompletions,
+                base_url: dead_url.clone(),
+                api_key: "key".into(),
+                name: None,
+                use_bearer_auth: false,
+                extra_headers: vec![],
+                fallback_base_urls: vec![live_url.clone()],
+            },
+        );
+        let manager = Arc::new(LlmManager::new(config).await.unwrap());
+        let mut status_rx = manager.subscribe_status();
+        let model = SpacebotModel::make(&manager, "test/model");
+
+        let response = model
+            .attempt_completion(CompletionRequest {
+                model: None,
+                preamble: None,
+                chat_history: OneOrMany::one(Message::user("hi")),
+                documents: vec![],
+                tools: vec![],
+                temperature: None,
+                max_tokens: None,
+                tool_choice: None,
+                additional_params: None,
+                output_schema: None,
+            })
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        assert!(matches!(
+            response.choice.first(),
+            AssistantContent::Text(text) if text.text == "hello"
+        ));
+        let event = status_rx.try_recv().unwrap();
+        assert_eq!(event.provider, "test");
+        assert_eq!(
+            event.status,
+            crate::llm::rate_limit::ProviderStatus::BaseUrlFailover {
+                from_base_url: dead_url,
+                to_base_url: live_url.clone(),
+            }
+        );
+        let provider_config = manager.get_provider("test").unwrap();
+        assert_eq!(
+            manager.base_urls_for("test", &provider_config).await[0],
+            live_url
+        );
+    }
+
+    async fn chat_completions_manager(addr: std::net::SocketAddr) -> Arc<LlmManager> {
+        let mut config = empty_llm_config();
+        config.providers.insert(
+            "test".into(),
+            ProviderConfig {
+                api_type: ApiType::OpenAiChatCompletions,
+                base_url: format!("http://{addr}/v1"),
+                api_key: "key".into(),
+                name: None,
+                use_bearer_auth: false,
+                extra_headers: vec![],
+                fallback_base_urls: vec![],
+            },
+        );
+        Arc::new(LlmManager::new(config).await.unwrap())
+    }
+
+    fn list_files_request() -> CompletionRequest {
+        CompletionRequest {
+            model: None,
+            preamble: None,
+            chat_history: OneOrMany::one(Message::user("list the files")),
+            documents: vec![],
+            tools: vec![rig::completion::ToolDefinition {
+                name: "file".into(),
+                description: "File operations".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {"operation": {"type": "string"}},
+                }),
+            }],
+            temperature: None,
+            max_tokens: None,
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    fn broken_file_tool_call() -> serde_json::Value {
+        serde_json::json!({
+            "index": 0,
+            "delta": {"tool_calls": [{
+                "index": 0,
+                "id": "call_1",
+                "function": {"name": "file", "arguments": "{\"operation\": list}"},
+            }]},
+            "finish_reason": "tool_calls",
+        })
+    }
+
+    #[tokio::test]
+    async fn malformed_tool_call_arguments_are_repaired_by_the_model() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_chat_completions(
+            listener,
+            vec![
+                broken_file_tool_call(),
+                text_choice("{\"operation\": \"list\"}"),
+            ],
+        ));
+
+        let manager = chat_completions_manager(addr).await;
+        let mut status_rx = manager.subscribe_status();
+        let model = SpacebotModel::make(&manager, "test/model");
+
+        let response = model.completion(list_files_request()).await.unwrap();
+        server.await.unwrap();
+
+        let AssistantContent::ToolCall(tool_call) = response.choice.first() else {
+            panic!("expected a tool call");
+        };
+        assert_eq!(
+            tool_call.function.arguments,
+            serde_json::json!({"operation": "list"})
+        );
+        assert!(!tool_repair::is_malformed(&tool_call));
+        let event = status_rx.try_recv().unwrap();
+        assert_eq!(
+            event.status,
+            crate::llm::rate_limit::ProviderStatus::ToolCallRepair {
+                tool_name: "file".into(),
+                outcome: ToolCallRepairOutcome::Model,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn streamed_tool_calls_are_repaired_before_they_are_yielded() {
+        use rig::streaming::StreamedAssistantContent;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_chat_completions(
+            listener,
+            vec![
+                broken_file_tool_call(),
+                text_choice("{\"operation\": \"list\"}"),
+            ],
+        ));
+
+        let manager = chat_completions_manager(addr).await;
+        let model = SpacebotModel::make(&manager, "test/model");
+        let mut stream = model.stream(list_files_request()).await.unwrap();
+
+        let mut tool_calls = Vec::new();
+        while let Some(event) = stream.next().await {
+            if let StreamedAssistantContent::ToolCall { tool_call, .. } = event.unwrap() {
+                tool_calls.push(tool_call);
+            }
+        }
+        server.await.unwrap();
+
+        let [tool_call] = tool_calls.as_slice() else {
+            panic!("expected one tool call, got {tool_calls:?}");
+        };
+        assert_eq!(
+            tool_call.function.arguments,
+            serde_json::json!({"operation": "list"})
+        );
+        assert!(!tool_repair::is_malformed(tool_call));
+    }
 }
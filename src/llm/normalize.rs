@@ -0,0 +1,253 @@
+//! Provider-neutral repair of chat history before wire-format conversion.
+//!
+//! Every provider rejects tool calls and tool results that don't pair up, but
+//! each in its own way: Anthropic wants each `tool_result` in the user turn
+//! right after its `tool_use`, OpenAI chat wants `tool` messages directly
+//! after the assistant message that made the calls, and the Responses API
+//! wants a `function_call_output` for every `function_call`. History that was
+//! compacted, truncated, or produced while routed to a different provider
+//! family can break any of these. The converters in `model.rs` run history
+//! through [`normalize_history`] first, so they only ever see well-formed
+//! pairs.
+
+use rig::message::{
+    AssistantContent, Message, ToolCall, ToolResult, ToolResultContent, UserContent,
+};
+use rig::one_or_many::OneOrMany;
+
+/// Result text standing in for a tool call whose result is not in history.
+pub(crate) const MISSING_TOOL_RESULT: &str = "[tool result unavailable]";
+
+/// Rebuild `messages` so that every tool call is answered, in call order, by
+/// a user message placed directly after the assistant message that made it.
+///
+/// - Results are matched to calls by ID (or by `call_id`, so history from
+///   the Responses API pairs up too) and take the call's IDs.
+/// - Calls without a result get a [`MISSING_TOOL_RESULT`] placeholder, unless
+///   the assistant message is the last one: those calls are still pending.
+/// - Results that answer no call, or answer one twice, are dropped.
+/// - Other user content stays where it was, after any relocated results.
+pub(crate) fn normalize_history(messages: &OneOrMany<Message>) -> Vec<Message> {
+    let mut results: Vec<Option<ToolResult>> = messages
+        .iter()
+        .filter_map(|message| match message {
+            Message::User { content } => Some(content.iter()),
+            Message::Assistant { .. } => None,
+        })
+        .flatten()
+        .filter_map(|content| match content {
+            UserContent::ToolResult(result) => Some(Some(result.clone())),
+            _ => None,
+        })
+        .collect();
+
+    let mut normalized = Vec::with_capacity(messages.len());
+    let last = messages.len() - 1;
+    for (index, message) in messages.iter().enumerate() {
+        match message {
+            Message::Assistant { content, .. } => {
+                normalized.push(message.clone());
+
+                let pending = index == last;
+                let tool_results: Vec<UserContent> = content
+                    .iter()
+                    .filter_map(|content| match content {
+                        AssistantContent::ToolCall(call) => Some(call),
+                        _ => None,
+                    })
+                    .filter_map(|call| take_result_for(call, &mut results, pending))
+                    .map(UserContent::ToolResult)
+                    .collect();
+                if let Ok(content) = OneOrMany::many(tool_results) {
+                    normalized.push(Message::User { content });
+                }
+            }
+            Message::User { content } => {
+                let kept: Vec<UserContent> = content
+                    .iter()
+                    .filter(|content| !matches!(content, UserContent::ToolResult(_)))
+                    .cloned()
+                    .collect();
+                if let Ok(content) = OneOrMany::many(kept) {
+                    normalized.push(Message::User { content });
+                }
+            }
+        }
+    }
+
+    normalized
+}
+
+/// Take the first unused result answering `call`, or make a placeholder
+/// unless the call is still `pending`.
+fn take_result_for(
+    call: &ToolCall,
+    results: &mut [Option<ToolResult>],
+    pending: bool,
+) -> Option<ToolResult> {
+    let matched = results
+        .iter_mut()
+        .find(|slot| slot.as_ref().is_some_and(|result| answers(call, result)))
+        .and_then(Option::take);
+
+    let content = match matched {
+        Some(result) => result.content,
+        None if pending => return None,
+        None => {
+            tracing::debug!(
+                tool_call_id = %call.id,
+                tool = %call.function.name,
+                "tool call has no result in history, adding a placeholder"
+            );
+            OneOrMany::one(ToolResultContent::text(MISSING_TOOL_RESULT))
+        }
+    };
+
+    Some(ToolResult {
+        id: call.id.clone(),
+        call_id: call.call_id.clone(),
+        content,
+    })
+}
+
+fn answers(call: &ToolCall, result: &ToolResult) -> bool {
+    if result.id == call.id {
+        return true;
+    }
+    match (call.call_id.as_deref(), result.call_id.as_deref()) {
+        (Some(call_id), Some(result_call_id)) => !call_id.is_empty() && call_id == result_call_id,
+        (Some(call_id), None) => !call_id.is_empty() && call_id == result.id,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::message::ToolFunction;
+
+    fn call(id: &str, call_id: Option<&str>) -> AssistantContent {
+        AssistantContent::ToolCall(ToolCall {
+            id: id.to_string(),
+            call_id: call_id.map(str::to_string),
+            function: ToolFunction {
+                name: "lookup".to_string(),
+                arguments: serde_json::json!({}),
+            },
+            signature: None,
+            additional_params: None,
+        })
+    }
+
+    fn result(id: &str, call_id: Option<&str>, text: &str) -> UserContent {
+        UserContent::ToolResult(ToolResult {
+            id: id.to_string(),
+            call_id: call_id.map(str::to_string),
+            content: OneOrMany::one(ToolResultContent::text(text)),
+        })
+    }
+
+    fn assistant(content: Vec<AssistantContent>) -> Message {
+        Message::Assistant {
+            id: None,
+            content: OneOrMany::many(content).unwrap(),
+        }
+    }
+
+    fn user(content: Vec<UserContent>) -> Message {
+        Message::User {
+            content: OneOrMany::many(content).unwrap(),
+        }
+    }
+
+    fn result_ids_and_text(message: &Message) -> Vec<(String, String)> {
+        let Message::User { content } = message else {
+            panic!("expected a user message, got {message:?}");
+        };
+        content
+            .iter()
+            .filter_map(|content| match content {
+                UserContent::ToolResult(result) => Some((
+                    result.id.clone(),
+                    match result.content.first() {
+                        ToolResultContent::Text(text) => text.text,
+                        ToolResultContent::Image(_) => "<image>".to_string(),
+                    },
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_history_is_unchanged() {
+        let history = OneOrMany::many(vec![
+            Message::user("hi"),
+            assistant(vec![call("a", None), call("b", None)]),
+            user(vec![result("a", None, "1"), result("b", None, "2")]),
+            Message::assistant("done"),
+        ])
+        .unwrap();
+
+        let normalized = normalize_history(&history);
+        assert_eq!(normalized, history.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn results_are_relocated_reordered_and_filled_in() {
+        let history = OneOrMany::many(vec![
+            assistant(vec![call("a", None), call("b", None), call("c", None)]),
+            user(vec![UserContent::text("interjection")]),
+            user(vec![
+                result("b", None, "2"),
+                result("orphan", None, "x"),
+                result("a", None, "1"),
+                result("a", None, "duplicate"),
+            ]),
+        ])
+        .unwrap();
+
+        let normalized = normalize_history(&history);
+        assert_eq!(normalized.len(), 3);
+        assert_eq!(
+            result_ids_and_text(&normalized[1]),
+            vec![
+                ("a".to_string(), "1".to_string()),
+                ("b".to_string(), "2".to_string()),
+                ("c".to_string(), MISSING_TOOL_RESULT.to_string()),
+            ]
+        );
+        assert_eq!(normalized[2], Message::user("interjection"));
+    }
+
+    #[test]
+    fn responses_call_ids_pair_results_and_are_carried_over() {
+        let history = OneOrMany::many(vec![
+            assistant(vec![call("fc_1", Some("call_1"))]),
+            user(vec![result("call_1", None, "ok")]),
+        ])
+        .unwrap();
+
+        let normalized = normalize_history(&history);
+        let Message::User { content } = &normalized[1] else {
+            panic!("expected tool results");
+        };
+        let UserContent::ToolResult(result) = content.first() else {
+            panic!("expected a tool result");
+        };
+        assert_eq!(result.id, "fc_1");
+        assert_eq!(result.call_id.as_deref(), Some("call_1"));
+    }
+
+    #[test]
+    fn trailing_calls_are_left_pending() {
+        let history = OneOrMany::many(vec![
+            Message::user("look it up"),
+            assistant(vec![call("a", None)]),
+        ])
+        .unwrap();
+
+        let normalized = normalize_history(&history);
+        assert_eq!(normalized, history.into_iter().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,216 @@
+//! Per-provider rate-limit cooldowns.
+//!
+//! When a provider returns 429 it enters cooldown for `rate_limit_cooldown_secs`
+//! or the `Retry-After` delay, whichever is larger. Routing sends traffic to
+//! fallbacks while a provider cools down. Once the cooldown expires a single
+//! request is let through as a probe; full traffic resumes only after the
+//! probe succeeds. A probe that never reports back (its request was dropped
+//! mid-flight) stops blocking new probes after [`PROBE_TIMEOUT`].
+
+use crate::llm::tool_repair::ToolCallRepairOutcome;
+
+use serde::Serialize;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a probe may stay unresolved before another request is let
+/// through as a new probe. Matches the HTTP client's request timeout, so a
+/// probe that is still running normally is never doubled up.
+pub(crate) const PROBE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Whether a request may be sent to a provider right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitAdmission {
+    /// No cooldown on record, send normally.
+    Open,
+    /// Cooling down (or a probe is already in flight), route elsewhere.
+    CoolingDown,
+    /// Cooldown expired and this request is the single recovery probe.
+    Probe,
+}
+
+/// Rate-limit or fallback transition emitted by the manager for status
+/// displays.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderStatusEvent {
+    pub provider: String,
+    pub model: String,
+    #[serde(flatten)]
+    pub status: ProviderStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ProviderStatus {
+    /// The provider returned 429 and is cooling down.
+    RateLimited { cooldown_secs: u64 },
+    /// The cooldown expired and a single probe request was let through.
+    Probing,
+    /// A request succeeded after a cooldown. Full traffic resumes.
+    Recovered,
+    /// A request moved down the fallback chain to this model after
+    /// `from_model` failed. Emitted once per hop.
+    ModelFallback { from_model: String, attempt: usize },
+    /// No provider could serve `from_model`, so the request was retried on
+    /// `[llm] default_model`. Emitted once per occurrence.
+    DefaultModelFallback { from_model: String },
+    /// The provider's base URL refused the connection, so requests moved to
+    /// the next configured URL. Emitted once per hop.
+    BaseUrlFailover {
+        from_base_url: String,
+        to_base_url: String,
+    },
+    /// A tool call came back with arguments that weren't valid JSON and went
+    /// through repair. Emitted once per tool call.
+    ToolCallRepair {
+        tool_name: String,
+        outcome: ToolCallRepairOutcome,
+    },
+}
+
+#[derive(Debug)]
+struct ProviderCooldown {
+    until: Instant,
+    /// When the current probe was let through, if one is in flight.
+    probe_started: Option<Instant>,
+}
+
+/// Cooldown state for every provider that has hit a rate limit.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    providers: HashMap<String, ProviderCooldown>,
+}
+
+impl RateLimiter {
+    /// Start (or restart) a cooldown. Any in-flight probe is forgotten.
+    pub(crate) fn record(&mut self, provider: &str, cooldown: Duration, now: Instant) {
+        self.providers.insert(
+            provider.to_string(),
+            ProviderCooldown {
+                until: now + cooldown,
+                probe_started: None,
+            },
+        );
+    }
+
+    /// Decide whether a request may go to the provider. Returns `Probe` at
+    /// most once per expired cooldown until the probe resolves or times out.
+    pub(crate) fn admit(&mut self, provider: &str, now: Instant) -> RateLimitAdmission {
+        let Some(cooldown) = self.providers.get_mut(provider) else {
+            return RateLimitAdmission::Open;
+        };
+        let probe_pending = cooldown
+            .probe_started
+            .is_some_and(|started| now.duration_since(started) < PROBE_TIMEOUT);
+        if now < cooldown.until || probe_pending {
+            return RateLimitAdmission::CoolingDown;
+        }
+        cooldown.probe_started = Some(now);
+        RateLimitAdmission::Probe
+    }
+
+    /// Let the next request probe again after a probe failed for a reason
+    /// other than another rate limit.
+    pub(crate) fn release_probe(&mut self, provider: &str) {
+        if let Some(cooldown) = self.providers.get_mut(provider) {
+            cooldown.probe_started = None;
+        }
+    }
+
+    /// Clear the cooldown after a successful request. Returns true if the
+    /// provider had one.
+    pub(crate) fn record_success(&mut self, provider: &str) -> bool {
+        self.providers.remove(provider).is_some()
+    }
+
+    pub(crate) fn contains(&self, provider: &str) -> bool {
+        self.providers.contains_key(provider)
+    }
+}
+
+/// Cooldown to apply after a 429: the configured cooldown or the provider's
+/// `Retry-After`, whichever is larger.
+pub(crate) fn cooldown_duration(cooldown_secs: u64, retry_after_secs: Option<u64>) -> Duration {
+    Duration::from_secs(cooldown_secs.max(retry_after_secs.unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limited_provider_routes_to_fallback_until_probe_succeeds() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+
+        // Simulated 429 from the primary provider.
+        limiter.record("anthropic", cooldown_duration(60, None), start);
+
+        // Subsequent calls skip the primary; the fallback provider is open.
+        assert_eq!(
+            limiter.admit("anthropic", start + Duration::from_secs(10)),
+            RateLimitAdmission::CoolingDown
+        );
+        assert_eq!(
+            limiter.admit("openrouter", start + Duration::from_secs(10)),
+            RateLimitAdmission::Open
+        );
+
+        // After the cooldown exactly one request probes the primary.
+        let after = start + Duration::from_secs(61);
+        assert_eq!(limiter.admit("anthropic", after), RateLimitAdmission::Probe);
+        assert_eq!(
+            limiter.admit("anthropic", after),
+            RateLimitAdmission::CoolingDown
+        );
+
+        assert!(limiter.record_success("anthropic"));
+        assert_eq!(limiter.admit("anthropic", after), RateLimitAdmission::Open);
+    }
+
+    #[test]
+    fn retry_after_extends_cooldown_when_larger() {
+        assert_eq!(cooldown_duration(60, Some(120)), Duration::from_secs(120));
+        assert_eq!(cooldown_duration(60, Some(5)), Duration::from_secs(60));
+        assert_eq!(cooldown_duration(60, None), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn abandoned_probe_is_replaced_after_timeout() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+        limiter.record("anthropic", Duration::from_secs(30), start);
+
+        // The probe's request is dropped and never records a result.
+        let after = start + Duration::from_secs(31);
+        assert_eq!(limiter.admit("anthropic", after), RateLimitAdmission::Probe);
+        assert_eq!(
+            limiter.admit("anthropic", after + PROBE_TIMEOUT - Duration::from_secs(1)),
+            RateLimitAdmission::CoolingDown
+        );
+        assert_eq!(
+            limiter.admit("anthropic", after + PROBE_TIMEOUT),
+            RateLimitAdmission::Probe
+        );
+    }
+
+    #[test]
+    fn failed_probe_can_be_retried_and_new_429_restarts_cooldown() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+        limiter.record("openai", Duration::from_secs(30), start);
+
+        let after = start + Duration::from_secs(31);
+        assert_eq!(limiter.admit("openai", after), RateLimitAdmission::Probe);
+        limiter.release_probe("openai");
+        assert_eq!(limiter.admit("openai", after), RateLimitAdmission::Probe);
+
+        // The probe hit another 429: back to a full cooldown.
+        limiter.record("openai", Duration::from_secs(30), after);
+        assert_eq!(
+            limiter.admit("openai", after + Duration::from_secs(1)),
+            RateLimitAdmission::CoolingDown
+        );
+    }
+}
@@ -27,11 +27,87 @@ pub struct RoutingConfig {
     /// How long to deprioritize a rate-limited model (seconds).
     pub rate_limit_cooldown_secs: u64,
 
+    /// Extra model calls allowed to fix a tool call whose arguments aren't
+    /// valid JSON, after a local lenient parse fails. `0` fails the
+    /// completion without asking the model.
+    pub tool_call_repair_attempts: usize,
+
     pub channel_thinking_effort: String,
     pub branch_thinking_effort: String,
     pub worker_thinking_effort: String,
     pub compactor_thinking_effort: String,
     pub cortex_thinking_effort: String,
+
+    /// Token budgets the `*_thinking_effort` levels map to on Gemini models.
+    pub gemini_thinking_budgets: GeminiThinkingBudgets,
+
+    /// Hard cap on output tokens per process type, summed across every
+    /// completion in a turn. Generation is aborted once a turn exceeds it.
+    /// `None` means uncapped.
+    pub channel_max_output_tokens: Option<u64>,
+    pub branch_max_output_tokens: Option<u64>,
+    pub worker_max_output_tokens: Option<u64>,
+    pub compactor_max_output_tokens: Option<u64>,
+    pub cortex_max_output_tokens: Option<u64>,
+}
+
+/// Largest `thinking_budget` Gemini accepts.
+pub const GEMINI_MAX_THINKING_BUDGET: u32 = 32_768;
+
+/// Gemini `thinking_budget` (in tokens) for each thinking effort level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeminiThinkingBudgets {
+    pub low: u32,
+    pub medium: u32,
+    pub high: u32,
+}
+
+impl Default for GeminiThinkingBudgets {
+    fn default() -> Self {
+        Self {
+            low: 1_024,
+            medium: 8_192,
+            high: 24_576,
+        }
+    }
+}
+
+impl GeminiThinkingBudgets {
+    /// Map a `*_thinking_effort` value to a Gemini thinking budget.
+    ///
+    /// "auto" and unrecognized values return None so the model uses its own
+    /// dynamic budget. "off"/"none" disable thinking (only Flash models accept
+    /// a zero budget), "max" uses the `high` budget, and a plain integer is
+    /// used as the budget directly, capped at [`GEMINI_MAX_THINKING_BUDGET`].
+    pub fn budget_for_effort(&self, effort: &str) -> Option<u32> {
+        match effort.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Some(0),
+            "low" => Some(self.low),
+            "medium" => Some(self.medium),
+            "high" | "max" => Some(self.high),
+            other => other
+                .parse::<u32>()
+                .ok()
+                .map(|budget| budget.min(GEMINI_MAX_THINKING_BUDGET)),
+        }
+    }
+
+    /// Check that budgets are within Gemini's range and ordered low ≤ medium ≤ high.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.high > GEMINI_MAX_THINKING_BUDGET {
+            return Err(format!(
+                "gemini_thinking_budgets.high ({}) exceeds the maximum of {GEMINI_MAX_THINKING_BUDGET}",
+                self.high
+            ));
+        }
+        if self.low > self.medium || self.medium > self.high {
+            return Err(format!(
+                "gemini_thinking_budgets must satisfy low <= medium <= high (got {}, {}, {})",
+                self.low, self.medium, self.high
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl Default for RoutingConfig {
@@ -53,11 +129,18 @@ impl RoutingConfig {
             task_overrides: HashMap::new(),
             fallbacks: HashMap::new(),
             rate_limit_cooldown_secs: 60,
+            tool_call_repair_attempts: 1,
             channel_thinking_effort: "auto".into(),
             branch_thinking_effort: "auto".into(),
             worker_thinking_effort: "auto".into(),
             compactor_thinking_effort: "auto".into(),
             cortex_thinking_effort: "auto".into(),
+            gemini_thinking_budgets: GeminiThinkingBudgets::default(),
+            channel_max_output_tokens: None,
+            branch_max_output_tokens: None,
+            worker_max_output_tokens: None,
+            compactor_max_output_tokens: None,
+            cortex_max_output_tokens: None,
         }
     }
 }
@@ -101,6 +184,17 @@ impl RoutingConfig {
         "auto"
     }
 
+    /// Per-turn output token cap for a process type, if one is configured.
+    pub fn max_output_tokens(&self, process_type: ProcessType) -> Option<u64> {
+        match process_type {
+            ProcessType::Channel => self.channel_max_output_tokens,
+            ProcessType::Branch => self.branch_max_output_tokens,
+            ProcessType::Worker => self.worker_max_output_tokens,
+            ProcessType::Compactor => self.compactor_max_output_tokens,
+            ProcessType::Cortex => self.cortex_max_output_tokens,
+        }
+    }
+
     /// Get the fallback chain for a model, if any.
     pub fn get_fallbacks(&self, model_name: &str) -> &[String] {
         self.fallbacks
@@ -108,6 +202,67 @@ impl RoutingConfig {
             .map(|v| v.as_slice())
             .unwrap_or(&[])
     }
+
+    /// Every model this config references, paired with the key it is set
+    /// under (`channel`, `task_overrides.coding`, `fallbacks.<model>`).
+    /// Unset models are skipped.
+    pub fn model_refs(&self) -> Vec<(String, &str)> {
+        let mut refs: Vec<(String, &str)> = [
+            ("channel", &self.channel),
+            ("branch", &self.branch),
+            ("worker", &self.worker),
+            ("compactor", &self.compactor),
+            ("cortex", &self.cortex),
+            ("voice", &self.voice),
+        ]
+        .into_iter()
+        .map(|(key, model)| (key.to_string(), model.as_str()))
+        .collect();
+
+        let mut overrides: Vec<_> = self.task_overrides.iter().collect();
+        overrides.sort();
+        for (task, model) in overrides {
+            refs.push((format!("task_overrides.{task}"), model));
+        }
+
+        let mut fallbacks: Vec<_> = self.fallbacks.iter().collect();
+        fallbacks.sort();
+        for (model, chain) in fallbacks {
+            refs.push(("fallbacks".to_string(), model));
+            for fallback in chain {
+                refs.push((format!("fallbacks.{model}"), fallback));
+            }
+        }
+
+        refs.retain(|(_, model)| !model.trim().is_empty());
+        refs
+    }
+
+    /// Distinct models that processes are routed to: one per process type
+    /// plus task overrides, in that order. Voice and fallback models are left
+    /// out since they only serve a request when something else fails or a
+    /// voice message arrives.
+    pub fn routed_models(&self) -> Vec<&str> {
+        let mut overrides: Vec<_> = self.task_overrides.values().collect();
+        overrides.sort();
+
+        let mut models: Vec<&str> = Vec::new();
+        for model in [
+            &self.channel,
+            &self.branch,
+            &self.worker,
+            &self.compactor,
+            &self.cortex,
+        ]
+        .into_iter()
+        .chain(overrides)
+        {
+            if !model.trim().is_empty() && !models.contains(&model.as_str()) {
+                models.push(model);
+            }
+        }
+        models
+    }
 }
 
 /// Whether an HTTP status code should trigger a fallback to the next model.
@@ -455,9 +610,6 @@ pub fn provider_from_model(model: &str) -> &str {
     }
 }
 
-/// Max number of fallback models to try before giving up.
-pub const MAX_FALLBACK_ATTEMPTS: usize = 3;
-
 /// Max retries per model (primary or fallback) on retriable errors.
 pub const MAX_RETRIES_PER_MODEL: usize = 3;
 
@@ -472,10 +624,173 @@ pub fn is_rate_limit_error(error_message: &str) -> bool {
     lower.contains("429") || lower.contains("rate limit")
 }
 
+/// Marker appended to 429 error messages carrying the provider's
+/// `Retry-After` delay, so routing can honour it after the error has been
+/// flattened to a string.
+pub fn retry_after_marker(secs: u64) -> String {
+    format!(" [retry-after={secs}s]")
+}
+
+/// Extract the `Retry-After` delay embedded by [`retry_after_marker`].
+pub fn retry_after_secs(error_message: &str) -> Option<u64> {
+    let (_, rest) = error_message.rsplit_once("[retry-after=")?;
+    let (secs, _) = rest.split_once("s]")?;
+    secs.parse().ok()
+}
+
+/// Whether an error counts against a provider's health: auth failures and
+/// server errors. Rate limits, timeouts, and bad requests don't — they say
+/// nothing about whether the provider is usable at all.
+pub fn is_provider_health_error(error_message: &str) -> bool {
+    let lower = error_message.to_lowercase();
+    lower.contains("401")
+        || lower.contains("403")
+        || lower.contains("unauthorized")
+        || lower.contains("forbidden")
+        || lower.contains("invalid api key")
+        || lower.contains("invalid x-api-key")
+        || lower.contains("authentication")
+        || lower.contains("500")
+        || lower.contains("502")
+        || lower.contains("503")
+        || lower.contains("504")
+        || lower.contains("server error")
+        || lower.contains("server had an error")
+        || lower.contains("internal error")
+}
+
+/// Whether an error means no configured provider can serve the model: its
+/// provider isn't configured, or the provider doesn't know the model (it was
+/// removed or renamed).
+pub fn is_model_not_found_error(error_message: &str) -> bool {
+    let lower = error_message.to_lowercase();
+    lower.contains("unknown provider")
+        || lower.contains("missing api key for provider")
+        || lower.contains("model_not_found")
+        || lower.contains("model not found")
+        || lower.contains("no such model")
+        || lower.contains("unknown model")
+        || lower.contains("invalid model")
+        || lower.contains("not a valid model")
+        || (lower.contains("model")
+            && (lower.contains("404")
+                || lower.contains("does not exist")
+                || lower.contains("not_found_error")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn model_refs_cover_overrides_and_fallbacks() {
+        let mut routing = RoutingConfig::for_model("anthropic/claude-sonnet-4".into());
+        routing
+            .task_overrides
+            .insert("coding".into(), "openai/gpt-5".into());
+        routing.fallbacks.insert(
+            "anthropic/claude-sonnet-4".into(),
+            vec!["openrouter/anthropic/claude-haiku-4.5".into()],
+        );
+
+        let refs = routing.model_refs();
+        // The voice model is unset, so only five process types show up.
+        assert_eq!(
+            refs[0],
+            ("channel".to_string(), "anthropic/claude-sonnet-4")
+        );
+        assert!(!refs.iter().any(|(key, _)| key == "voice"));
+        assert!(refs.contains(&("task_overrides.coding".to_string(), "openai/gpt-5")));
+        assert!(refs.contains(&(
+            "fallbacks.anthropic/claude-sonnet-4".to_string(),
+            "openrouter/anthropic/claude-haiku-4.5"
+        )));
+        assert_eq!(refs.len(), 8);
+    }
+
+    #[test]
+    fn routed_models_are_distinct_and_skip_fallbacks() {
+        let mut routing = RoutingConfig::for_model("anthropic/claude-sonnet-4".into());
+        routing.worker = "anthropic/claude-haiku-4.5".into();
+        routing
+            .task_overrides
+            .insert("coding".into(), "openai/gpt-5".into());
+        routing
+            .task_overrides
+            .insert("research".into(), "anthropic/claude-sonnet-4".into());
+        routing.fallbacks.insert(
+            "anthropic/claude-sonnet-4".into(),
+            vec!["openrouter/anthropic/claude-haiku-4.5".into()],
+        );
+
+        assert_eq!(
+            routing.routed_models(),
+            vec![
+                "anthropic/claude-sonnet-4",
+                "anthropic/claude-haiku-4.5",
+                "openai/gpt-5",
+            ]
+        );
+    }
+
+    #[test]
+    fn gemini_thinking_budget_maps_effort_levels() {
+        let budgets = GeminiThinkingBudgets::default();
+        assert_eq!(budgets.budget_for_effort("auto"), None);
+        assert_eq!(budgets.budget_for_effort("off"), Some(0));
+        assert_eq!(budgets.budget_for_effort("low"), Some(1_024));
+        assert_eq!(budgets.budget_for_effort("Medium"), Some(8_192));
+        assert_eq!(budgets.budget_for_effort("high"), Some(24_576));
+        assert_eq!(budgets.budget_for_effort("max"), Some(24_576));
+        assert_eq!(budgets.budget_for_effort("2048"), Some(2_048));
+        assert_eq!(
+            budgets.budget_for_effort("100000"),
+            Some(GEMINI_MAX_THINKING_BUDGET)
+        );
+    }
+
+    #[test]
+    fn gemini_thinking_budgets_validate_range_and_order() {
+        assert!(GeminiThinkingBudgets::default().validate().is_ok());
+        let unordered = GeminiThinkingBudgets {
+            low: 4_096,
+            medium: 1_024,
+            high: 8_192,
+        };
+        assert!(unordered.validate().is_err());
+        let too_large = GeminiThinkingBudgets {
+            high: GEMINI_MAX_THINKING_BUDGET + 1,
+            ..GeminiThinkingBudgets::default()
+        };
+        assert!(too_large.validate().is_err());
+    }
+
+    #[test]
+    fn is_model_not_found_error_matches_unservable_models() {
+        assert!(is_model_not_found_error("unknown provider: opnai"));
+        assert!(is_model_not_found_error(
+            "missing API key for provider: groq"
+        ));
+        assert!(is_model_not_found_error(
+            r#"Anthropic API error (404 Not Found): {"type":"not_found_error","message":"model: claude-2"}"#
+        ));
+        assert!(is_model_not_found_error(
+            r#"OpenAI API error: {"error":{"code":"model_not_found","message":"The model `gpt-4-32k` does not exist"}}"#
+        ));
+        assert!(is_model_not_found_error(
+            "OpenRouter error: google/gemini-pro is not a valid model ID"
+        ));
+
+        assert!(!is_model_not_found_error("429 Too Many Requests"));
+        assert!(!is_model_not_found_error("404 Not Found"));
+        assert!(!is_model_not_found_error(
+            "401 Unauthorized: invalid api key"
+        ));
+        assert!(!is_model_not_found_error(
+            "400 Bad Request: context length exceeded"
+        ));
+    }
+
     #[test]
     fn is_retriable_error_catches_network_failures() {
         // DNS/connection failures from reqwest
@@ -547,4 +862,31 @@ mod tests {
         assert!(!is_rate_limit_error("503 Service Unavailable"));
         assert!(!is_rate_limit_error("timeout"));
     }
+
+    #[test]
+    fn is_provider_health_error_counts_auth_and_server_failures() {
+        assert!(is_provider_health_error(
+            "401 Unauthorized: invalid x-api-key"
+        ));
+        assert!(is_provider_health_error("403 Forbidden"));
+        assert!(is_provider_health_error("503 Service Unavailable"));
+        assert!(is_provider_health_error("The server had an error"));
+        // Transient or request-specific errors don't mark a provider unhealthy
+        assert!(!is_provider_health_error("429 Too Many Requests"));
+        assert!(!is_provider_health_error("timeout"));
+        assert!(!is_provider_health_error(
+            "400 Bad Request: context length exceeded"
+        ));
+    }
+
+    #[test]
+    fn retry_after_round_trips_through_error_message() {
+        let message = format!(
+            "openai/gpt-4.1 failed after 3 attempts: 429 Too Many Requests{}",
+            retry_after_marker(30)
+        );
+        assert!(is_rate_limit_error(&message));
+        assert_eq!(retry_after_secs(&message), Some(30));
+        assert_eq!(retry_after_secs("429 Too Many Requests"), None);
+    }
 }
@@ -0,0 +1,247 @@
+//! Repair of tool calls whose arguments are not valid JSON.
+//!
+//! Weaker models sometimes emit tool arguments with trailing commas, code
+//! fences, a missing closing brace, or worse. The response parsers in
+//! `model.rs` don't fail on these. They give the call `{}` arguments and
+//! record the raw text under [`MALFORMED_ARGUMENTS_KEY`] in the call's
+//! `additional_params`. `SpacebotModel` then repairs the call before the
+//! response leaves the model layer: first with [`lenient_parse`], then by
+//! asking the model to rewrite the JSON, up to
+//! `routing.tool_call_repair_attempts` times. Streamed tool calls get the
+//! same treatment before the stream yields them.
+
+use rig::completion::CompletionRequest;
+use rig::message::{Message, ToolCall};
+use rig::one_or_many::OneOrMany;
+use serde::Serialize;
+
+/// `additional_params` key carrying a tool call's unparseable arguments.
+pub(crate) const MALFORMED_ARGUMENTS_KEY: &str = "spacebot_malformed_arguments";
+
+const REPAIR_PREAMBLE: &str = "You fix malformed JSON tool-call arguments. Reply with only the \
+corrected JSON object: no explanation and no code fences. Keep every key and value the caller \
+wrote and change only what is needed to make it valid JSON that matches the schema.";
+
+/// How a malformed tool call was handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallRepairOutcome {
+    /// Fixed locally, without another model call.
+    Lenient,
+    /// Fixed by asking the model to rewrite the arguments.
+    Model,
+    /// Still unparseable after every attempt. The completion fails.
+    Failed,
+}
+
+impl ToolCallRepairOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lenient => "lenient",
+            Self::Model => "model",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Unparseable arguments taken off a tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MalformedArguments {
+    pub(crate) raw: String,
+    /// Error reported when the completion fails because repair gave up.
+    pub(crate) error: String,
+}
+
+/// `additional_params` marking a tool call's arguments as malformed.
+pub(crate) fn malformed_arguments_params(raw: &str, error: &str) -> serde_json::Value {
+    serde_json::json!({
+        MALFORMED_ARGUMENTS_KEY: {
+            "raw": raw,
+            "error": error,
+        }
+    })
+}
+
+/// Whether a tool call carries the malformed-arguments marker.
+pub(crate) fn is_malformed(tool_call: &ToolCall) -> bool {
+    tool_call
+        .additional_params
+        .as_ref()
+        .is_some_and(|params| params.get(MALFORMED_ARGUMENTS_KEY).is_some())
+}
+
+/// Remove the malformed-arguments marker from a tool call and return it.
+pub(crate) fn take_malformed_arguments(tool_call: &mut ToolCall) -> Option<MalformedArguments> {
+    take_malformed_params(&mut tool_call.additional_params)
+}
+
+/// Remove the malformed-arguments marker from a tool call's
+/// `additional_params`, streamed or not, and return it.
+pub(crate) fn take_malformed_params(
+    additional_params: &mut Option<serde_json::Value>,
+) -> Option<MalformedArguments> {
+    let params = additional_params.as_mut()?.as_object_mut()?;
+    let marker = params.remove(MALFORMED_ARGUMENTS_KEY)?;
+    if params.is_empty() {
+        *additional_params = None;
+    }
+    Some(MalformedArguments {
+        raw: marker["raw"].as_str().unwrap_or_default().to_string(),
+        error: marker["error"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// Parse arguments with fixes that can't change what the model meant: code
+/// fences, text around the object, trailing commas, and missing closing
+/// brackets. An unterminated string is left alone, since closing it could
+/// silently truncate a value. Only JSON objects are accepted.
+pub(crate) fn lenient_parse(raw: &str) -> Option<serde_json::Value> {
+    let start = raw.find('{')?;
+    let candidate = &raw[start..];
+
+    let mut repaired = String::with_capacity(candidate.len() + 4);
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for character in candidate.chars() {
+        if in_string {
+            repaired.push(character);
+            if escaped {
+                escaped = false;
+            } else if character == '\\' {
+                escaped = true;
+            } else if character == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match character {
+            '"' => {
+                in_string = true;
+                repaired.push(character);
+            }
+            '{' => {
+                closers.push('}');
+                repaired.push(character);
+            }
+            '[' => {
+                closers.push(']');
+                repaired.push(character);
+            }
+            '}' | ']' => {
+                if closers.pop() != Some(character) {
+                    return None;
+                }
+                strip_trailing_comma(&mut repaired);
+                repaired.push(character);
+                if closers.is_empty() {
+                    // Anything after the object is commentary or a fence.
+                    break;
+                }
+            }
+            _ => repaired.push(character),
+        }
+    }
+    if in_string {
+        return None;
+    }
+    while let Some(closer) = closers.pop() {
+        strip_trailing_comma(&mut repaired);
+        repaired.push(closer);
+    }
+
+    serde_json::from_str::<serde_json::Value>(&repaired)
+        .ok()
+        .filter(serde_json::Value::is_object)
+}
+
+fn strip_trailing_comma(text: &mut String) {
+    let trimmed_len = text.trim_end().len();
+    if text[..trimmed_len].ends_with(',') {
+        text.truncate(trimmed_len - 1);
+    }
+}
+
+/// Request asking the model to rewrite malformed arguments for `tool_name`.
+pub(crate) fn repair_request(
+    tool_name: &str,
+    malformed: &MalformedArguments,
+    parameters: Option<&serde_json::Value>,
+    max_tokens: Option<u64>,
+) -> CompletionRequest {
+    let schema = parameters
+        .map(|parameters| {
+            serde_json::to_string_pretty(parameters).unwrap_or_else(|_| parameters.to_string())
+        })
+        .unwrap_or_else(|| "(not available)".into());
+    let prompt = format!(
+        "Tool: {tool_name}\n\nParameter schema:\n{schema}\n\nParse error: {}\n\nMalformed arguments:\n{}",
+        malformed.error, malformed.raw
+    );
+
+    CompletionRequest {
+        model: None,
+        preamble: Some(REPAIR_PREAMBLE.into()),
+        chat_history: OneOrMany::one(Message::user(prompt)),
+        documents: Vec::new(),
+        tools: Vec::new(),
+        temperature: None,
+        max_tokens,
+        tool_choice: None,
+        additional_params: None,
+        output_schema: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::message::ToolFunction;
+
+    #[test]
+    fn lenient_parse_fixes_syntax_without_guessing_values() {
+        assert_eq!(
+            lenient_parse("{\"operation\": \"list\", \"path\": \"src\",}"),
+            Some(serde_json::json!({"operation": "list", "path": "src"}))
+        );
+        assert_eq!(
+            lenient_parse("```json\n{\"paths\": [\"a\", \"b\",]}\n```"),
+            Some(serde_json::json!({"paths": ["a", "b"]}))
+        );
+        assert_eq!(
+            lenient_parse("{\"query\": \"rust\", \"limit\": 5"),
+            Some(serde_json::json!({"query": "rust", "limit": 5}))
+        );
+        // A brace inside a string doesn't count.
+        assert_eq!(
+            lenient_parse("{\"content\": \"fn main() {\"} trailing"),
+            Some(serde_json::json!({"content": "fn main() {"}))
+        );
+
+        assert_eq!(lenient_parse("{\"content\": \"cut off mid-str"), None);
+        assert_eq!(lenient_parse("{\"operation\": list}"), None);
+        assert_eq!(lenient_parse("[1, 2]"), None);
+        assert_eq!(lenient_parse("{\"a\": [1}"), None);
+    }
+
+    #[test]
+    fn malformed_marker_round_trips_through_additional_params() {
+        let mut tool_call = ToolCall {
+            id: "call_1".into(),
+            call_id: None,
+            function: ToolFunction {
+                name: "file".into(),
+                arguments: serde_json::json!({}),
+            },
+            signature: None,
+            additional_params: Some(malformed_arguments_params("{bad", "invalid JSON")),
+        };
+        assert!(is_malformed(&tool_call));
+
+        let malformed = take_malformed_arguments(&mut tool_call).unwrap();
+        assert_eq!(malformed.raw, "{bad");
+        assert_eq!(malformed.error, "invalid JSON");
+        assert!(tool_call.additional_params.is_none());
+        assert!(!is_malformed(&tool_call));
+    }
+}
@@ -0,0 +1,290 @@
+//! Per-turn token usage tracking for outbound message economics.
+
+use serde::Serialize;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Cost, token usage, and latency of the turn that produced an outbound
+/// message. Token and cost fields are `None` when the provider reported no
+/// usage for the turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct MessageUsage {
+    pub cost_usd: Option<f64>,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub latency_ms: Option<u64>,
+}
+
+/// Accumulates usage across every completion in a turn. Clones share state,
+/// so the hook can record while reply senders take. Each completion's usage
+/// is handed to exactly one outbound message, so summing the usage of a
+/// turn's messages gives the turn total.
+#[derive(Debug, Clone, Default)]
+pub struct TurnUsageTracker {
+    inner: Arc<Mutex<Option<TurnUsageState>>>,
+}
+
+#[derive(Debug)]
+struct TurnUsageState {
+    model_name: String,
+    started_at: Instant,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    cached_input_tokens: u64,
+}
+
+impl TurnUsageTracker {
+    /// Start tracking a new turn against `model_name`, discarding the last one.
+    pub fn start(&self, model_name: &str) {
+        if let Ok(mut slot) = self.inner.lock() {
+            *slot = Some(TurnUsageState {
+                model_name: model_name.to_string(),
+                started_at: Instant::now(),
+                prompt_tokens: 0,
+                completion_tokens: 0,
+                cached_input_tokens: 0,
+            });
+        }
+    }
+
+    /// Add one completion's usage to the current turn. No-op before `start`.
+    pub fn record(&self, usage: &rig::completion::Usage) {
+        if let Ok(mut slot) = self.inner.lock()
+            && let Some(state) = slot.as_mut()
+        {
+            state.prompt_tokens += usage.input_tokens;
+            state.completion_tokens += usage.output_tokens;
+            state.cached_input_tokens += usage.cached_input_tokens;
+        }
+    }
+
+    /// Usage recorded since the last take, with latency measured from the
+    /// start of the turn. The taken tokens are cleared so the next message
+    /// only carries completions made after this one. `None` when no turn has
+    /// been started.
+    pub fn take(&self) -> Option<MessageUsage> {
+        let mut slot = self.inner.lock().ok()?;
+        let state = slot.as_mut()?;
+        let latency_ms = Some(state.started_at.elapsed().as_millis() as u64);
+        let prompt_tokens = std::mem::take(&mut state.prompt_tokens);
+        let completion_tokens = std::mem::take(&mut state.completion_tokens);
+        let cached_input_tokens = std::mem::take(&mut state.cached_input_tokens);
+        if prompt_tokens == 0 && completion_tokens == 0 {
+            return Some(MessageUsage {
+                latency_ms,
+                ..MessageUsage::default()
+            });
+        }
+        Some(MessageUsage {
+            cost_usd: Some(crate::llm::pricing::estimate_cost(
+                &state.model_name,
+                prompt_tokens,
+                completion_tokens,
+                cached_input_tokens,
+            )),
+            prompt_tokens: Some(prompt_tokens),
+            completion_tokens: Some(completion_tokens),
+            latency_ms,
+        })
+    }
+}
+
+/// What a prompt request may still spend: its output token cap and the tokens
+/// used against it, and whether its tool call limit has been reached. Shared
+/// between the hook, which counts completions and tool calls, and the model,
+/// which shapes each completion request to fit.
+#[derive(Debug, Clone)]
+pub struct TurnBudget {
+    cap: Arc<AtomicU64>,
+    used: Arc<AtomicU64>,
+    tool_calls_exhausted: Arc<AtomicBool>,
+    /// Tools still offered once tool calls are exhausted, such as a
+    /// channel's `reply`.
+    exempt_tools: &'static [&'static str],
+}
+
+impl TurnBudget {
+    /// A budget over `cap` (0 for uncapped), `used`, and the tool call limit
+    /// flag, shared with the caller.
+    pub fn new(
+        cap: Arc<AtomicU64>,
+        used: Arc<AtomicU64>,
+        tool_calls_exhausted: Arc<AtomicBool>,
+        exempt_tools: &'static [&'static str],
+    ) -> Self {
+        Self {
+            cap,
+            used,
+            tool_calls_exhausted,
+            exempt_tools,
+        }
+    }
+
+    /// Output tokens left, or `None` when the request is uncapped.
+    pub fn remaining(&self) -> Option<u64> {
+        let cap = self.cap.load(Ordering::Relaxed);
+        (cap > 0).then(|| cap.saturating_sub(self.used.load(Ordering::Relaxed)))
+    }
+
+    /// Fit a completion request to the budget: cap `max_tokens`, and once
+    /// tool calls are exhausted offer only the exempt tools, or forbid tool
+    /// calls when there are none. Tool definitions are otherwise kept, since
+    /// some providers reject histories with tool calls but no tools.
+    pub fn apply(&self, request: &mut rig::completion::CompletionRequest) {
+        request.max_tokens = self.clamp_max_tokens(request.max_tokens);
+        if !self.tool_calls_exhausted.load(Ordering::Relaxed) {
+            return;
+        }
+        let exempt: Vec<_> = request
+            .tools
+            .iter()
+            .filter(|tool| self.exempt_tools.contains(&tool.name.as_str()))
+            .cloned()
+            .collect();
+        if exempt.is_empty() {
+            request.tool_choice = Some(rig::message::ToolChoice::None);
+        } else {
+            request.tools = exempt;
+        }
+    }
+
+    /// Limit `max_tokens` to the tokens left. A spent budget still allows
+    /// one token, so the request is well-formed; the hook ends the turn
+    /// once the completion is counted.
+    pub fn clamp_max_tokens(&self, max_tokens: Option<u64>) -> Option<u64> {
+        match self.remaining() {
+            Some(remaining) => {
+                let remaining = remaining.max(1);
+                Some(max_tokens.map_or(remaining, |max_tokens| max_tokens.min(remaining)))
+            }
+            None => max_tokens,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_budget_limits_max_tokens_to_what_is_left() {
+        let cap = Arc::new(AtomicU64::new(0));
+        let used = Arc::new(AtomicU64::new(0));
+        let budget = TurnBudget::new(
+            cap.clone(),
+            used.clone(),
+            Arc::new(AtomicBool::new(false)),
+            &[],
+        );
+        assert_eq!(budget.remaining(), None);
+        assert_eq!(budget.clamp_max_tokens(Some(4096)), Some(4096));
+        assert_eq!(budget.clamp_max_tokens(None), None);
+
+        cap.store(1000, Ordering::Relaxed);
+        used.store(700, Ordering::Relaxed);
+        assert_eq!(budget.remaining(), Some(300));
+        assert_eq!(budget.clamp_max_tokens(Some(4096)), Some(300));
+        assert_eq!(budget.clamp_max_tokens(Some(100)), Some(100));
+        assert_eq!(budget.clamp_max_tokens(None), Some(300));
+
+        used.store(1200, Ordering::Relaxed);
+        assert_eq!(budget.clamp_max_tokens(Some(4096)), Some(1));
+    }
+
+    fn request_with_tools(names: &[&str]) -> rig::completion::CompletionRequest {
+        rig::completion::CompletionRequest {
+            model: None,
+            preamble: None,
+            chat_history: rig::one_or_many::OneOrMany::one(rig::message::Message::from("hi")),
+            documents: Vec::new(),
+            tools: names
+                .iter()
+                .map(|name| rig::completion::ToolDefinition {
+                    name: name.to_string(),
+                    description: String::new(),
+                    parameters: serde_json::json!({}),
+                })
+                .collect(),
+            temperature: None,
+            max_tokens: None,
+            tool_choice: None,
+            additional_params: None,
+            output_schema: None,
+        }
+    }
+
+    #[test]
+    fn exhausted_tool_calls_leave_only_exempt_tools() {
+        let exhausted = Arc::new(AtomicBool::new(false));
+        let budget = |exempt_tools| {
+            TurnBudget::new(
+                Arc::new(AtomicU64::new(0)),
+                Arc::new(AtomicU64::new(0)),
+                exhausted.clone(),
+                exempt_tools,
+            )
+        };
+
+        let mut request = request_with_tools(&["shell", "reply"]);
+        budget(&["reply", "skip"]).apply(&mut request);
+        assert_eq!(request.tools.len(), 2);
+        assert!(request.tool_choice.is_none());
+
+        exhausted.store(true, Ordering::Relaxed);
+        budget(&["reply", "skip"]).apply(&mut request);
+        let names: Vec<_> = request
+            .tools
+            .iter()
+            .map(|tool| tool.name.as_str())
+            .collect();
+        assert_eq!(names, ["reply"]);
+        assert!(request.tool_choice.is_none());
+
+        let mut request = request_with_tools(&["shell", "file"]);
+        budget(&[]).apply(&mut request);
+        assert_eq!(request.tools.len(), 2);
+        assert!(matches!(
+            request.tool_choice,
+            Some(rig::message::ToolChoice::None)
+        ));
+    }
+
+    #[test]
+    fn accumulates_usage_across_completions() {
+        let tracker = TurnUsageTracker::default();
+        assert!(tracker.take().is_none());
+
+        tracker.start("anthropic/claude-sonnet-4-20250514");
+        let empty = tracker.take().unwrap();
+        assert_eq!(empty.prompt_tokens, None);
+        assert_eq!(empty.cost_usd, None);
+        assert!(empty.latency_ms.is_some());
+
+        let mut usage = rig::completion::Usage::new();
+        usage.input_tokens = 1000;
+        usage.output_tokens = 200;
+        tracker.clone().record(&usage);
+        tracker.record(&usage);
+
+        let first = tracker.take().unwrap();
+        assert_eq!(first.prompt_tokens, Some(2000));
+        assert_eq!(first.completion_tokens, Some(400));
+        let expected =
+            crate::llm::pricing::estimate_cost("anthropic/claude-sonnet-4-20250514", 2000, 400, 0);
+        assert_eq!(first.cost_usd, Some(expected));
+
+        // A second message in the same turn only carries what came after
+        // the first, so the per-message usage sums to the turn total.
+        assert_eq!(tracker.take().unwrap().prompt_tokens, None);
+        tracker.record(&usage);
+        let second = tracker.take().unwrap();
+        assert_eq!(second.prompt_tokens, Some(1000));
+        assert_eq!(second.completion_tokens, Some(200));
+
+        tracker.record(&usage);
+        tracker.start("anthropic/claude-sonnet-4-20250514");
+        assert_eq!(tracker.take().unwrap().prompt_tokens, None);
+    }
+}
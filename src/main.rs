@@ -43,6 +43,15 @@ enum Command {
     },
     /// Show status of the running daemon
     Status,
+    /// Stream live agent events from the running daemon
+    Events {
+        /// Only show events for this agent
+        #[arg(short, long)]
+        agent: Option<String>,
+        /// Comma-separated event types to show (e.g. tool_started,worker_completed)
+        #[arg(short = 't', long = "type", value_delimiter = ',')]
+        types: Vec<String>,
+    },
     /// Manage skills
     #[command(subcommand)]
     Skill(SkillCommand),
@@ -61,13 +70,25 @@ enum AuthCommand {
         /// Use API console instead of Claude Pro/Max
         #[arg(long)]
         console: bool,
+        /// Store this login as an additional account with the given label.
+        /// Calls rotate across all stored accounts.
+        #[arg(long)]
+        label: Option<String>,
     },
-    /// Show current auth status
+    /// Show auth status for every stored account
     Status,
     /// Log out (remove stored credentials)
-    Logout,
+    Logout {
+        /// Account to remove (defaults to the default account)
+        #[arg(long)]
+        label: Option<String>,
+    },
     /// Refresh the access token
-    Refresh,
+    Refresh {
+        /// Account to refresh (defaults to the default account)
+        #[arg(long)]
+        label: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -178,11 +199,126 @@ enum SecretsCommand {
 
 /// Tracks an active conversation channel and its message sender.
 struct ActiveChannel {
+    /// Agent that owns the channel, so its channels can be dropped on removal.
+    agent_id: spacebot::AgentId,
     message_tx: mpsc::Sender<spacebot::InboundMessage>,
+    /// Used to evict the channel once it has been idle past the timeout.
+    idle_handle: spacebot::agent::channel::ChannelIdleHandle,
+    /// Process control registration, which tells this channel apart from
+    /// a later one for the same conversation.
+    registration_id: u64,
     /// Retained so the outbound routing task stays alive.
     _outbound_handle: tokio::task::JoinHandle<()>,
 }
 
+/// Channels torn down by eviction.
+#[derive(Default)]
+struct EvictedChannels {
+    /// When each conversation's channel was last evicted, so its next channel
+    /// backfills from the conversation log. Pruned after
+    /// `EVICTED_CHANNEL_RETENTION`.
+    recent: HashMap<String, std::time::Instant>,
+    /// Evicted channels still handling queued messages, keyed by
+    /// conversation and registration ID. They hold their slot until their
+    /// event loop exits.
+    draining: HashMap<(String, u64), spacebot::AgentId>,
+}
+
+impl EvictedChannels {
+    fn is_draining(&self, agent_id: &spacebot::AgentId) -> bool {
+        self.draining
+            .values()
+            .any(|draining_agent_id| draining_agent_id == agent_id)
+    }
+}
+
+/// Most messages held for one queued conversation. Later ones get the busy
+/// reply.
+const MAX_QUEUED_MESSAGES_PER_CONVERSATION: usize = 50;
+
+/// New conversations waiting for a channel slot under
+/// `max_concurrent_channels`, per agent in arrival order. Messages that arrive
+/// while a conversation waits are kept with it.
+#[derive(Default)]
+struct QueuedConversations {
+    queues: HashMap<
+        spacebot::AgentId,
+        std::collections::VecDeque<(String, Vec<spacebot::InboundMessage>)>,
+    >,
+}
+
+impl QueuedConversations {
+    /// Queue a message for its conversation. Returns true when the message
+    /// starts a new waiting conversation. Hands the message back when the
+    /// agent already has `max_conversations` waiting or the conversation is
+    /// holding `MAX_QUEUED_MESSAGES_PER_CONVERSATION` messages.
+    fn push(
+        &mut self,
+        agent_id: &spacebot::AgentId,
+        message: spacebot::InboundMessage,
+        max_conversations: usize,
+    ) -> Result<bool, spacebot::InboundMessage> {
+        let queue = self.queues.entry(agent_id.clone()).or_default();
+        if let Some((_, messages)) = queue
+            .iter_mut()
+            .find(|(conversation_id, _)| *conversation_id == message.conversation_id)
+        {
+            if messages.len() >= MAX_QUEUED_MESSAGES_PER_CONVERSATION {
+                return Err(message);
+            }
+            messages.push(message);
+            return Ok(false);
+        }
+        if queue.len() >= max_conversations {
+            if queue.is_empty() {
+                self.queues.remove(agent_id);
+            }
+            return Err(message);
+        }
+        queue.push_back((message.conversation_id.clone(), vec![message]));
+        Ok(true)
+    }
+
+    fn contains(&self, agent_id: &spacebot::AgentId, conversation_id: &str) -> bool {
+        self.queues.get(agent_id).is_some_and(|queue| {
+            queue
+                .iter()
+                .any(|(queued_id, _)| queued_id == conversation_id)
+        })
+    }
+
+    /// Number of conversations waiting for the agent.
+    fn len(&self, agent_id: &spacebot::AgentId) -> usize {
+        self.queues.get(agent_id).map_or(0, |queue| queue.len())
+    }
+
+    /// Take the agent's oldest waiting conversation.
+    fn pop(&mut self, agent_id: &spacebot::AgentId) -> Option<Vec<spacebot::InboundMessage>> {
+        let queue = self.queues.get_mut(agent_id)?;
+        let (_, messages) = queue.pop_front()?;
+        if queue.is_empty() {
+            self.queues.remove(agent_id);
+        }
+        Some(messages)
+    }
+
+    fn agent_ids(&self) -> Vec<spacebot::AgentId> {
+        self.queues.keys().cloned().collect()
+    }
+
+    fn remove_agent(&mut self, agent_id: &spacebot::AgentId) {
+        self.queues.remove(agent_id);
+    }
+}
+
+/// How often the main loop checks for channels to evict.
+const CHANNEL_IDLE_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How long an evicted conversation is remembered so its next channel
+/// backfills from the conversation log. Older entries are pruned on the idle
+/// sweep and fall back to platform backfill.
+const EVICTED_CHANNEL_RETENTION: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
 #[derive(Debug, serde::Serialize)]
 struct BackfillTranscriptEntry {
     role: String,
@@ -206,11 +342,18 @@ fn serialize_backfill_transcript(entries: Vec<BackfillTranscriptEntry>) -> Optio
     }
 }
 
+/// Platform messages sent before `cutoff` are left out. Messages without a
+/// timestamp are kept.
 fn render_platform_history_backfill(
     history_messages: &[spacebot::messaging::traits::HistoryMessage],
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Option<String> {
     let entries = history_messages
         .iter()
+        .filter(|entry| match (cutoff, entry.timestamp) {
+            (Some(cutoff), Some(timestamp)) => timestamp >= cutoff,
+            _ => true,
+        })
         .map(|entry| BackfillTranscriptEntry {
             role: if entry.is_bot {
                 "assistant".to_string()
@@ -235,10 +378,12 @@ fn render_platform_history_backfill(
 
 fn render_conversation_history_backfill(
     history_messages: &[spacebot::conversation::history::ConversationMessage],
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
 ) -> Option<String> {
     let entries = history_messages
         .iter()
         .filter(|entry| entry.role == "user" || entry.role == "assistant")
+        .filter(|entry| cutoff.is_none_or(|cutoff| entry.created_at >= cutoff))
         .map(|entry| {
             let author = if entry.role == "assistant" {
                 "(you)".to_string()
@@ -266,19 +411,310 @@ fn render_conversation_history_backfill(
     serialize_backfill_transcript(entries)
 }
 
+/// Backfill a channel from its own conversation log rather than the platform.
+/// Used when recreating a channel this process already ran, so the transcript
+/// includes everything the agent said, even on platforms without history.
+async fn backfill_from_conversation_log(
+    channel: &mut spacebot::agent::channel::Channel,
+    conversation_id: &str,
+    backfill_count: usize,
+) {
+    let backfill_limit = std::cmp::min(backfill_count, i64::MAX as usize) as i64;
+    let cutoff = channel
+        .deps
+        .runtime_config
+        .channel_config
+        .load()
+        .history_cutoff(chrono::Utc::now());
+    match channel
+        .state
+        .conversation_logger
+        .load_recent_since_reset(&channel.id, backfill_limit)
+        .await
+    {
+        Ok(history_messages) => {
+            if let Some(transcript) =
+                render_conversation_history_backfill(&history_messages, cutoff)
+            {
+                channel.set_backfill_transcript(transcript);
+                tracing::info!(
+                    conversation_id = %conversation_id,
+                    message_count = history_messages.len(),
+                    "backfilled channel history from conversation log"
+                );
+            }
+        }
+        Err(error) => {
+            tracing::warn!(
+                conversation_id = %conversation_id,
+                %error,
+                "failed to backfill channel history from conversation log"
+            );
+        }
+    }
+}
+
+/// Publish the number of in-memory channels per agent.
+#[cfg(feature = "metrics")]
+fn record_active_channels(
+    agents: &HashMap<spacebot::AgentId, spacebot::Agent>,
+    active_channels: &HashMap<String, ActiveChannel>,
+) {
+    let metrics = spacebot::telemetry::Metrics::global();
+    for agent_id in agents.keys() {
+        let count = active_channels
+            .values()
+            .filter(|channel| &channel.agent_id == agent_id)
+            .count();
+        metrics
+            .active_channels
+            .with_label_values(&[agent_id.as_ref()])
+            .set(count as i64);
+    }
+}
+
+/// Publish the number of conversations waiting for a channel slot per agent.
+#[cfg(feature = "metrics")]
+fn record_queued_conversations(
+    agents: &HashMap<spacebot::AgentId, spacebot::Agent>,
+    queued_conversations: &QueuedConversations,
+) {
+    let metrics = spacebot::telemetry::Metrics::global();
+    for agent_id in agents.keys() {
+        metrics
+            .queued_conversations
+            .with_label_values(&[agent_id.as_ref()])
+            .set(queued_conversations.len(agent_id) as i64);
+    }
+}
+
+/// Channel slots an agent is using: its live channels, evicted channels
+/// still draining, and conversations released from the queue whose channel
+/// hasn't been created yet.
+fn channel_slots_in_use(
+    agent_id: &spacebot::AgentId,
+    active_channels: &HashMap<String, ActiveChannel>,
+    evicted_channels: &EvictedChannels,
+    released_messages: &std::collections::VecDeque<spacebot::InboundMessage>,
+) -> usize {
+    let active = active_channels
+        .values()
+        .filter(|channel| &channel.agent_id == agent_id)
+        .count();
+    let draining = evicted_channels
+        .draining
+        .values()
+        .filter(|draining_agent_id| *draining_agent_id == agent_id)
+        .count();
+    let released = released_messages
+        .iter()
+        .filter(|message| {
+            message.agent_id.as_ref() == Some(agent_id)
+                && !active_channels.contains_key(&message.conversation_id)
+        })
+        .map(|message| message.conversation_id.as_str())
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+    active + draining + released
+}
+
+/// Tear down a channel. It handles anything still queued, then stops and
+/// unregisters itself. History is already in the conversation log, and the
+/// next channel for the conversation backfills from it.
+fn evict_channel(
+    conversation_id: String,
+    active_channels: &mut HashMap<String, ActiveChannel>,
+    evicted_channels: &mut EvictedChannels,
+    reason: &'static str,
+) {
+    let Some(active) = active_channels.remove(&conversation_id) else {
+        return;
+    };
+    active.idle_handle.shutdown();
+    #[cfg(feature = "metrics")]
+    spacebot::telemetry::Metrics::global()
+        .channels_evicted_total
+        .with_label_values(&[active.agent_id.as_ref()])
+        .inc();
+    tracing::info!(
+        conversation_id = %conversation_id,
+        agent_id = %active.agent_id,
+        reason,
+        "evicted channel"
+    );
+    evicted_channels
+        .recent
+        .insert(conversation_id.clone(), std::time::Instant::now());
+    evicted_channels
+        .draining
+        .insert((conversation_id, active.registration_id), active.agent_id);
+}
+
+/// Evict the agent's longest-idle channel to make room for a new
+/// conversation. Channels with a turn, branches, or workers running are
+/// never evicted. Returns false when every channel is busy.
+async fn evict_longest_idle_channel(
+    agent_id: &spacebot::AgentId,
+    active_channels: &mut HashMap<String, ActiveChannel>,
+    evicted_channels: &mut EvictedChannels,
+) -> bool {
+    let mut longest_idle: Option<(String, std::time::Duration)> = None;
+    for (conversation_id, active) in active_channels.iter() {
+        if &active.agent_id != agent_id {
+            continue;
+        }
+        if let Some(idle) = active.idle_handle.idle_for().await
+            && longest_idle
+                .as_ref()
+                .is_none_or(|(_, longest)| idle > *longest)
+        {
+            longest_idle = Some((conversation_id.clone(), idle));
+        }
+    }
+    let Some((conversation_id, _)) = longest_idle else {
+        return false;
+    };
+    evict_channel(
+        conversation_id,
+        active_channels,
+        evicted_channels,
+        "channel limit reached",
+    );
+    true
+}
+
+/// Send the agent's busy reply to a conversation turned away at the channel
+/// limit.
+fn reply_busy(
+    messaging: &std::sync::Arc<spacebot::messaging::MessagingManager>,
+    agent: &spacebot::Agent,
+    channel_config: &spacebot::config::ChannelConfig,
+    message: spacebot::InboundMessage,
+) {
+    let messaging = messaging.clone();
+    let runtime_config = agent.deps.runtime_config.clone();
+    let busy_message = channel_config.busy_message.clone();
+    tokio::spawn(async move {
+        route_outbound(
+            &messaging,
+            &runtime_config,
+            &message,
+            spacebot::OutboundResponse::Text(busy_message),
+            None,
+        )
+        .await;
+    });
+}
+
+/// Release queued conversations while their agent has a free channel slot.
+/// At the limit, the longest-idle channel is evicted and the next
+/// conversation is released once it stops. Released messages are replayed
+/// through the inbound path, which creates their channels.
+async fn release_queued_conversations(
+    agents: &HashMap<spacebot::AgentId, spacebot::Agent>,
+    active_channels: &mut HashMap<String, ActiveChannel>,
+    evicted_channels: &mut EvictedChannels,
+    queued_conversations: &mut QueuedConversations,
+    released_messages: &mut std::collections::VecDeque<spacebot::InboundMessage>,
+) {
+    for agent_id in queued_conversations.agent_ids() {
+        let Some(agent) = agents.get(&agent_id) else {
+            queued_conversations.remove_agent(&agent_id);
+            continue;
+        };
+        let limit = agent
+            .deps
+            .runtime_config
+            .channel_config
+            .load()
+            .max_concurrent_channels;
+        while queued_conversations.len(&agent_id) > 0 {
+            if limit > 0
+                && channel_slots_in_use(
+                    &agent_id,
+                    active_channels,
+                    evicted_channels,
+                    released_messages,
+                ) >= limit
+            {
+                // Make room, then release into the slot once the evicted
+                // channel has stopped.
+                if !evicted_channels.is_draining(&agent_id) {
+                    evict_longest_idle_channel(&agent_id, active_channels, evicted_channels).await;
+                }
+                break;
+            }
+            let Some(messages) = queued_conversations.pop(&agent_id) else {
+                break;
+            };
+            released_messages.extend(messages);
+        }
+    }
+    #[cfg(feature = "metrics")]
+    record_queued_conversations(agents, queued_conversations);
+}
+
 /// Forward outbound response events to SSE clients for the dashboard.
+///
+/// Each chunk of a streamed reply is reported as a delta for live rendering,
+/// and the whole reply once more at `StreamEnd` with the text of its last
+/// chunk. `stream_text` holds that text for the outbound task meanwhile.
 fn forward_sse_event(
     api_event_tx: &tokio::sync::broadcast::Sender<spacebot::api::ApiEvent>,
     agent_id: &str,
     channel_id: &str,
     response: &spacebot::OutboundResponse,
+    usage: Option<spacebot::llm::usage::MessageUsage>,
+    stream_text: &mut Option<String>,
 ) {
+    let send_message = |text: String| {
+        let usage = usage.unwrap_or_default();
+        api_event_tx
+            .send(spacebot::api::ApiEvent::OutboundMessage {
+                agent_id: agent_id.to_string(),
+                channel_id: channel_id.to_string(),
+                text,
+                cost_usd: usage.cost_usd,
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                latency_ms: usage.latency_ms,
+            })
+            .ok();
+    };
+
     match response {
         spacebot::OutboundResponse::Text(text)
         | spacebot::OutboundResponse::RichMessage { text, .. }
-        | spacebot::OutboundResponse::ThreadReply { text, .. } => {
+        | spacebot::OutboundResponse::ThreadReply { text, .. }
+        | spacebot::OutboundResponse::Sticker {
+            fallback: Some(text),
+            ..
+        } => send_message(text.clone()),
+        spacebot::OutboundResponse::StreamStart => *stream_text = None,
+        spacebot::OutboundResponse::StreamChunk(text) => {
+            // Chunks carry the cumulative text; the delta is what is new.
+            let text_delta = stream_text
+                .as_deref()
+                .and_then(|shown| text.strip_prefix(shown))
+                .unwrap_or(text);
             api_event_tx
-                .send(spacebot::api::ApiEvent::OutboundMessage {
+                .send(spacebot::api::ApiEvent::OutboundMessageDelta {
+                    agent_id: agent_id.to_string(),
+                    channel_id: channel_id.to_string(),
+                    text_delta: text_delta.to_string(),
+                    aggregated_text: text.clone(),
+                })
+                .ok();
+            *stream_text = Some(text.clone());
+        }
+        spacebot::OutboundResponse::StreamEnd => {
+            if let Some(text) = stream_text.take() {
+                send_message(text);
+            }
+        }
+        spacebot::OutboundResponse::Reasoning(text) => {
+            api_event_tx
+                .send(spacebot::api::ApiEvent::OutboundReasoning {
                     agent_id: agent_id.to_string(),
                     channel_id: channel_id.to_string(),
                     text: text.clone(),
@@ -294,7 +730,9 @@ fn forward_sse_event(
                 })
                 .ok();
         }
-        spacebot::OutboundResponse::Status(spacebot::StatusUpdate::StopTyping) => {
+        spacebot::OutboundResponse::Status(
+            spacebot::StatusUpdate::StopTyping | spacebot::StatusUpdate::Skipped { .. },
+        ) => {
             api_event_tx
                 .send(spacebot::api::ApiEvent::TypingState {
                     agent_id: agent_id.to_string(),
@@ -309,11 +747,23 @@ fn forward_sse_event(
 
 /// Route an outbound response to the messaging adapter using the pinned target
 /// message for platform routing metadata (thread_ts, channel_id, etc.).
+///
+/// Responses are dropped while a PAUSE kill switch file pauses the agent.
 async fn route_outbound(
     messaging: &std::sync::Arc<spacebot::messaging::MessagingManager>,
+    runtime_config: &spacebot::config::RuntimeConfig,
     target: &spacebot::InboundMessage,
     response: spacebot::OutboundResponse,
+    idempotency_key: Option<&str>,
 ) {
+    if runtime_config.is_paused() {
+        tracing::debug!(
+            conversation_id = %target.conversation_id,
+            "agent paused by PAUSE file, dropping outbound response"
+        );
+        return;
+    }
+
     match response {
         spacebot::OutboundResponse::Status(status) => {
             if let Err(error) = messaging.send_status(target, status).await {
@@ -321,7 +771,7 @@ async fn route_outbound(
             }
         }
         response => {
-            if let Err(error) = messaging.respond(target, response).await {
+            if let Err(error) = messaging.respond(target, response, idempotency_key).await {
                 tracing::error!(%error, "failed to send outbound response");
             }
         }
@@ -344,6 +794,7 @@ fn main() -> anyhow::Result<()> {
             cmd_start(cli.config, cli.debug, foreground)
         }
         Command::Status => cmd_status(),
+        Command::Events { agent, types } => cmd_events(cli.config, agent, types),
         Command::Skill(skill_cmd) => cmd_skill(cli.config, skill_cmd),
         Command::Auth(auth_cmd) => cmd_auth(cli.config, auth_cmd),
         Command::Secrets(secrets_cmd) => cmd_secrets(cli.config, secrets_cmd),
@@ -560,47 +1011,56 @@ fn cmd_auth(config_path: Option<std::path::PathBuf>, auth_cmd: AuthCommand) -> a
 
     runtime.block_on(async {
         match auth_cmd {
-            AuthCommand::Login { console } => {
+            AuthCommand::Login { console, label } => {
                 let mode = if console {
                     spacebot::auth::AuthMode::Console
                 } else {
                     spacebot::auth::AuthMode::Max
                 };
-                spacebot::auth::login_interactive(&instance_dir, mode).await?;
+                spacebot::auth::login_interactive(&instance_dir, mode, label.as_deref()).await?;
                 Ok(())
             }
             AuthCommand::Status => {
-                match spacebot::auth::load_credentials(&instance_dir)? {
-                    Some(creds) => {
-                        let expires_in = creds.expires_at - chrono::Utc::now().timestamp_millis();
-                        let expires_min = expires_in / 60_000;
-                        if creds.is_expired() {
-                            eprintln!("Anthropic OAuth: expired ({}m ago)", -expires_min);
-                        } else {
-                            eprintln!("Anthropic OAuth: valid (expires in {}m)", expires_min);
-                        }
-                        eprintln!(
-                            "  access token: <redacted> ({} bytes)",
-                            creds.access_token.len()
-                        );
-                        eprintln!(
-                            "  refresh token: <redacted> ({} bytes)",
-                            creds.refresh_token.len()
-                        );
+                let accounts = spacebot::auth::load_all_credentials(&instance_dir);
+                if accounts.is_empty() {
+                    eprintln!("No OAuth credentials found.");
+                    eprintln!("Run `spacebot auth login` to authenticate.");
+                }
+                for (label, creds) in &accounts {
+                    let expires_in = creds.expires_at - chrono::Utc::now().timestamp_millis();
+                    let expires_min = expires_in / 60_000;
+                    if creds.is_expired() {
+                        eprintln!("Anthropic OAuth [{label}]: expired ({}m ago)", -expires_min);
+                    } else {
                         eprintln!(
-                            "  credentials file: {}",
-                            spacebot::auth::credentials_path(&instance_dir).display()
+                            "Anthropic OAuth [{label}]: valid (expires in {}m)",
+                            expires_min
                         );
                     }
-                    None => {
-                        eprintln!("No OAuth credentials found.");
-                        eprintln!("Run `spacebot auth login` to authenticate.");
-                    }
+                    eprintln!(
+                        "  access token: <redacted> ({} bytes)",
+                        creds.access_token.len()
+                    );
+                    eprintln!(
+                        "  refresh token: <redacted> ({} bytes)",
+                        creds.refresh_token.len()
+                    );
+                    eprintln!(
+                        "  credentials file: {}",
+                        spacebot::auth::account_credentials_path(&instance_dir, label).display()
+                    );
+                }
+                if accounts.len() > 1 {
+                    eprintln!("Calls rotate across {} accounts.", accounts.len());
                 }
                 Ok(())
             }
-            AuthCommand::Logout => {
-                let path = spacebot::auth::credentials_path(&instance_dir);
+            AuthCommand::Logout { label } => {
+                let label = label
+                    .as_deref()
+                    .unwrap_or(spacebot::auth::DEFAULT_ACCOUNT_LABEL);
+                spacebot::auth::validate_account_label(label)?;
+                let path = spacebot::auth::account_credentials_path(&instance_dir, label);
                 if path.exists() {
                     std::fs::remove_file(&path)?;
                     eprintln!("Credentials removed.");
@@ -609,12 +1069,16 @@ fn cmd_auth(config_path: Option<std::path::PathBuf>, auth_cmd: AuthCommand) -> a
                 }
                 Ok(())
             }
-            AuthCommand::Refresh => {
-                let creds = spacebot::auth::load_credentials(&instance_dir)?
+            AuthCommand::Refresh { label } => {
+                let label = label
+                    .as_deref()
+                    .unwrap_or(spacebot::auth::DEFAULT_ACCOUNT_LABEL);
+                spacebot::auth::validate_account_label(label)?;
+                let creds = spacebot::auth::load_account_credentials(&instance_dir, label)?
                     .context("no credentials found — run `spacebot auth login` first")?;
                 eprintln!("Refreshing access token...");
                 let new_creds = creds.refresh().await.context("refresh failed")?;
-                spacebot::auth::save_credentials(&instance_dir, &new_creds)?;
+                spacebot::auth::save_account_credentials(&instance_dir, label, &new_creds)?;
                 let expires_min =
                     (new_creds.expires_at - chrono::Utc::now().timestamp_millis()) / 60_000;
                 eprintln!("Token refreshed (expires in {}m)", expires_min);
@@ -1093,6 +1557,161 @@ async fn secrets_api_delete(
     Ok(response)
 }
 
+fn cmd_events(
+    config_path: Option<std::path::PathBuf>,
+    agent: Option<String>,
+    types: Vec<String>,
+) -> anyhow::Result<()> {
+    // Bootstrap the secrets store so a `secret:` API auth token resolves.
+    bootstrap_secrets_store(&config_path);
+
+    let config = load_config(&config_path)?;
+    if let Some(agent_id) = &agent
+        && !config
+            .agents
+            .iter()
+            .any(|agent_config| &agent_config.id == agent_id)
+    {
+        anyhow::bail!("agent '{agent_id}' not found in config");
+    }
+    let api_base = format!("http://{}:{}/api", config.api.bind, config.api.port);
+    let auth_token = config.api.auth_token.clone();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to build tokio runtime")?;
+
+    runtime.block_on(async {
+        let client = reqwest::Client::new();
+        let response = secrets_api_get(&client, &api_base, &auth_token, "events").await?;
+        if !response.status().is_success() {
+            anyhow::bail!("event stream request failed: HTTP {}", response.status());
+        }
+        eprintln!("streaming events from {api_base}/events (ctrl-c to stop)");
+
+        let mut stream = response.bytes_stream();
+        let mut parser = SseParser::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.context("event stream interrupted")?;
+            for frame in parser.push(&chunk) {
+                if frame.event == "lagged" {
+                    eprintln!(
+                        "warning: fell behind the daemon, events dropped: {}",
+                        frame.data
+                    );
+                    continue;
+                }
+                let Ok(payload) = serde_json::from_str::<serde_json::Value>(&frame.data) else {
+                    continue;
+                };
+                if event_matches(&frame.event, &payload, agent.as_deref(), &types) {
+                    println!("{}", format_event(&frame.event, &payload));
+                }
+            }
+        }
+
+        eprintln!("event stream closed by the daemon");
+        Ok(())
+    })
+}
+
+/// One dispatched event from a `text/event-stream` body.
+#[derive(Debug, PartialEq, Eq)]
+struct SseFrame {
+    event: String,
+    data: String,
+}
+
+/// Incremental parser for the control API's SSE stream. Chunks can split
+/// lines and UTF-8 sequences anywhere, so bytes are buffered until a full
+/// line arrives.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: String,
+    data: String,
+}
+
+impl SseParser {
+    /// Feed a chunk and return every event it completes. Comment lines
+    /// (keep-alive pings) are skipped.
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseFrame> {
+        self.buffer.extend_from_slice(chunk);
+        let mut frames = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                let event = std::mem::take(&mut self.event);
+                if !self.data.is_empty() {
+                    frames.push(SseFrame {
+                        event: if event.is_empty() {
+                            "message".into()
+                        } else {
+                            event
+                        },
+                        data: std::mem::take(&mut self.data),
+                    });
+                }
+            } else if let Some(value) = line.strip_prefix("event:") {
+                self.event = value.trim_start().to_string();
+            } else if let Some(value) = line.strip_prefix("data:") {
+                if !self.data.is_empty() {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+        frames
+    }
+}
+
+/// Whether an event passes the `--agent` and `--type` filters. Events that
+/// aren't tied to an agent (like `config_reloaded`) pass the agent filter.
+fn event_matches(
+    event_type: &str,
+    payload: &serde_json::Value,
+    agent: Option<&str>,
+    types: &[String],
+) -> bool {
+    let type_matches = types.is_empty() || types.iter().any(|wanted| wanted == event_type);
+    let agent_matches = match (agent, payload.get("agent_id").and_then(|id| id.as_str())) {
+        (Some(wanted), Some(agent_id)) => wanted == agent_id,
+        _ => true,
+    };
+    type_matches && agent_matches
+}
+
+/// Render an event as a header line (time, type, agent, channel) followed
+/// by the remaining fields as indented JSON.
+fn format_event(event_type: &str, payload: &serde_json::Value) -> String {
+    let mut output = format!("{} {event_type}", chrono::Local::now().format("%H:%M:%S"));
+    for (key, label) in [("agent_id", "agent"), ("channel_id", "channel")] {
+        if let Some(value) = payload.get(key).and_then(|value| value.as_str()) {
+            output.push_str(&format!(" {label}={value}"));
+        }
+    }
+
+    let mut details = payload.clone();
+    if let Some(fields) = details.as_object_mut() {
+        for key in ["type", "agent_id", "channel_id"] {
+            fields.remove(key);
+        }
+        if fields.is_empty() {
+            return output;
+        }
+    }
+    let pretty = serde_json::to_string_pretty(&details).unwrap_or_default();
+    for line in pretty.lines() {
+        output.push_str("\n  ");
+        output.push_str(line);
+    }
+    output
+}
+
 fn cmd_skill(
     config_path: Option<std::path::PathBuf>,
     skill_cmd: SkillCommand,
@@ -1531,7 +2150,7 @@ fn has_provider_credentials(
     instance_dir: &std::path::Path,
 ) -> bool {
     llm_config.has_any_key()
-        || spacebot::auth::credentials_path(instance_dir).exists()
+        || spacebot::auth::has_credentials(instance_dir)
         || spacebot::openai_auth::credentials_path(instance_dir).exists()
 }
 
@@ -1596,6 +2215,22 @@ async fn run(
         task_store_registry.clone(),
     );
     api_state.auth_token = config.api.auth_token.clone();
+    if config.api.access_log {
+        let path = config.instance_dir.join("logs").join("api_access.jsonl");
+        match spacebot::api::access_log::AccessLog::spawn(
+            path.clone(),
+            config.api.access_log_max_bytes,
+            config.api.access_log_max_files,
+        ) {
+            Ok(access_log) => {
+                tracing::info!(path = %path.display(), "API access log enabled");
+                api_state.access_log = Some(access_log);
+            }
+            Err(error) => {
+                tracing::warn!(%error, path = %path.display(), "failed to open API access log");
+            }
+        }
+    }
     let api_state = Arc::new(api_state);
 
     // Keep the secrets API available in setup mode so encrypted stores can be
@@ -1667,6 +2302,12 @@ async fn run(
         .await
         .with_context(|| "failed to initialize LLM manager")?,
     );
+    spacebot::llm::health::spawn_health_probe(&llm_manager);
+    if config.llm.validate_models {
+        let llm_manager = llm_manager.clone();
+        let models = config.routing_models();
+        tokio::spawn(async move { llm_manager.warn_unlisted_models(&models).await });
+    }
 
     // Shared embedding model (stateless, agent-agnostic)
     let embedding_cache_dir = config.instance_dir.join("embedding_cache");
@@ -1680,7 +2321,8 @@ async fn run(
     // Initialize the language for all text lookups (must happen before PromptEngine/tools)
     spacebot::prompts::text::init("en").with_context(|| "failed to initialize language")?;
 
-    // Create the PromptEngine with bundled templates (no file watching, no user overrides)
+    // Create the PromptEngine with bundled templates. Agents layer their
+    // prompt_overrides on top when their runtime config is built.
     let prompt_engine = spacebot::prompts::PromptEngine::new("en")
         .with_context(|| "failed to initialize prompt engine")?;
 
@@ -1719,6 +2361,7 @@ async fn run(
     api_state.set_embedding_model(embedding_model.clone()).await;
     api_state.set_prompt_engine(prompt_engine.clone()).await;
     api_state.set_defaults_config(config.defaults.clone()).await;
+    api_state.set_db_config(config.db);
     api_state.set_agent_links((**agent_links.load()).clone());
     api_state.set_agent_groups(config.groups.clone());
     api_state.set_agent_humans(config.humans.clone());
@@ -1815,6 +2458,19 @@ async fn run(
 
     // Active conversation channels: conversation_id -> ActiveChannel
     let mut active_channels: HashMap<String, ActiveChannel> = HashMap::new();
+    // Evicted channels: conversations whose next channel backfills from the
+    // conversation log, and channels still draining before they stop.
+    let mut evicted_channels = EvictedChannels::default();
+    // Channel event loops report here when they stop, so a draining
+    // channel's slot goes to the next queued conversation right away.
+    let (channel_stopped_tx, mut channel_stopped_rx) = mpsc::unbounded_channel::<(String, u64)>();
+    // New conversations waiting for a slot under `max_concurrent_channels`,
+    // and queued messages released to be replayed through the inbound arm.
+    let mut queued_conversations = QueuedConversations::default();
+    let mut released_messages: std::collections::VecDeque<spacebot::InboundMessage> =
+        std::collections::VecDeque::new();
+    let mut idle_sweep = tokio::time::interval(CHANNEL_IDLE_SWEEP_INTERVAL);
+    idle_sweep.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
     // Resume idle interactive workers that survived the restart.
     // For each idle worker, pre-create the channel if needed and spawn
@@ -1899,8 +2555,7 @@ async fn run(
                         continue;
                     }
 
-                    let (response_tx, mut response_rx) =
-                        mpsc::channel::<spacebot::RoutedResponse>(32);
+                    let (response_tx, response_rx) = mpsc::channel::<spacebot::RoutedResponse>(32);
                     let event_rx = agent.deps.event_tx.subscribe();
                     let channel_id: spacebot::ChannelId = Arc::from(conversation_id.as_str());
 
@@ -1926,6 +2581,7 @@ async fn run(
                         .process_control_registry
                         .register_channel(channel.id.clone(), channel.control_handle().downgrade())
                         .await;
+                    let idle_handle = channel.idle_handle();
                     api_state
                         .register_channel_status(
                             conversation_id.clone(),
@@ -1938,34 +2594,12 @@ async fn run(
 
                     let backfill_count = agent.config.history_backfill_count();
                     if backfill_count > 0 {
-                        let backfill_limit =
-                            std::cmp::min(backfill_count, i64::MAX as usize) as i64;
-                        match channel
-                            .state
-                            .conversation_logger
-                            .load_recent(&channel.id, backfill_limit)
-                            .await
-                        {
-                            Ok(history_messages) => {
-                                if let Some(transcript) =
-                                    render_conversation_history_backfill(&history_messages)
-                                {
-                                    channel.set_backfill_transcript(transcript);
-                                    tracing::info!(
-                                        conversation_id = %conversation_id,
-                                        message_count = history_messages.len(),
-                                        "backfilled resumed channel history from conversation log"
-                                    );
-                                }
-                            }
-                            Err(error) => {
-                                tracing::warn!(
-                                    conversation_id = %conversation_id,
-                                    %error,
-                                    "failed to backfill resumed channel history from conversation log"
-                                );
-                            }
-                        }
+                        backfill_from_conversation_log(
+                            &mut channel,
+                            &conversation_id,
+                            backfill_count,
+                        )
+                        .await;
                     }
 
                     // Resume workers into the channel state before spawning the event loop.
@@ -2009,6 +2643,8 @@ async fn run(
 
                     // Spawn the channel event loop.
                     let cleanup_channel_id = conversation_id.clone();
+                    let cleanup_channel_state = channel.state.clone();
+                    let channel_stopped_tx = channel_stopped_tx.clone();
                     let process_control_registry = agent.deps.process_control_registry.clone();
                     let api_state_for_cleanup = api_state.clone();
                     tokio::spawn(async move {
@@ -2021,34 +2657,64 @@ async fn run(
                             .unregister_channel(&scoped_channel_id, channel_registration_id)
                             .await;
                         api_state_for_cleanup
-                            .unregister_channel_status(&cleanup_channel_id)
+                            .unregister_channel_status(
+                                &cleanup_channel_id,
+                                &cleanup_channel_state.status_block,
+                            )
                             .await;
                         api_state_for_cleanup
-                            .unregister_channel_state(&cleanup_channel_id)
+                            .unregister_channel_state(&cleanup_channel_id, &cleanup_channel_state)
                             .await;
+                        channel_stopped_tx
+                            .send((cleanup_channel_id, channel_registration_id))
+                            .ok();
                     });
 
                     let messaging_for_outbound = messaging_manager.clone();
+                    let runtime_config_for_outbound = agent.deps.runtime_config.clone();
                     let api_event_tx = api_state.event_tx.clone();
                     let sse_agent_id = agent_id.to_string();
                     let sse_channel_id = conversation_id.clone();
+                    let mut response_rx = spacebot::messaging::pacing::spawn_pacer(
+                        response_rx,
+                        runtime_config_for_outbound.clone(),
+                    );
                     let outbound_handle = tokio::spawn(async move {
+                        let mut stream_text = None;
                         while let Some(routed) = response_rx.recv().await {
-                            let spacebot::RoutedResponse { response, target } = routed;
+                            let spacebot::RoutedResponse {
+                                response,
+                                target,
+                                usage,
+                            } = routed;
                             forward_sse_event(
                                 &api_event_tx,
                                 &sse_agent_id,
                                 &sse_channel_id,
                                 &response,
+                                usage,
+                                &mut stream_text,
                             );
-                            route_outbound(&messaging_for_outbound, &target, response).await;
+                            let idempotency_key =
+                                spacebot::messaging::dedup::idempotency_key(&target, &response);
+                            route_outbound(
+                                &messaging_for_outbound,
+                                &runtime_config_for_outbound,
+                                &target,
+                                response,
+                                idempotency_key.as_deref(),
+                            )
+                            .await;
                         }
                     });
 
                     active_channels.insert(
                         conversation_id.clone(),
                         ActiveChannel {
+                            agent_id: agent_id.clone(),
                             message_tx: channel_tx,
+                            idle_handle,
+                            registration_id: channel_registration_id,
                             _outbound_handle: outbound_handle,
                         },
                     );
@@ -2066,15 +2732,39 @@ async fn run(
 
     // Main event loop: route inbound messages to agent channels
     loop {
-        // Poll the inbound stream if it exists, otherwise yield a never-resolving future
+        // Replay released messages first. Otherwise poll the inbound stream if
+        // it exists, or yield a never-resolving future.
+        let has_released_messages = !released_messages.is_empty();
         let inbound_next = async {
+            if has_released_messages {
+                return Some(None);
+            }
             match inbound_stream.as_mut() {
-                Some(stream) => stream.next().await,
+                Some(stream) => stream.next().await.map(Some),
                 None => std::future::pending().await,
             }
         };
         tokio::select! {
-            Some(mut message) = inbound_next, if agents_initialized => {
+            Some(next) = inbound_next, if agents_initialized => {
+                // Released messages already went through hooks and agent
+                // resolution before they were queued.
+                let (message, released) = match next {
+                    Some(message) => (message, false),
+                    None => match released_messages.pop_front() {
+                        Some(message) => (message, true),
+                        None => continue,
+                    },
+                };
+                let mut message = if released {
+                    message
+                } else {
+                    match api_state.message_hooks.run(message).await {
+                        spacebot::hooks::MessageHookAction::Continue(message) => message,
+                        // Hook short-circuited processing (spam, filtered command, etc.).
+                        spacebot::hooks::MessageHookAction::Drop { .. } => continue,
+                    }
+                };
+
                 let agent_id = if let Some(existing) = message.agent_id.as_ref() {
                     existing.clone()
                 } else {
@@ -2093,6 +2783,105 @@ async fn run(
 
                 let conversation_id = message.conversation_id.clone();
 
+                // Enforce the agent's channel limit before a new conversation
+                // gets a channel. Released conversations already hold a slot.
+                if !released
+                    && !active_channels.contains_key(&conversation_id)
+                    && let Some(agent) = agents.get(&agent_id)
+                {
+                    let channel_config = agent.deps.runtime_config.channel_config.load_full();
+                    let limit = channel_config.max_concurrent_channels;
+                    let max_queued = channel_config.max_queued_conversations;
+                    if queued_conversations.contains(&agent_id, &conversation_id) {
+                        if let Err(message) =
+                            queued_conversations.push(&agent_id, message, max_queued)
+                        {
+                            tracing::info!(
+                                conversation_id = %conversation_id,
+                                agent_id = %agent_id,
+                                "queued conversation is full, rejected message"
+                            );
+                            reply_busy(&messaging_manager, agent, &channel_config, message);
+                        }
+                        continue;
+                    }
+                    if limit > 0
+                        && (queued_conversations.len(&agent_id) > 0
+                            || channel_slots_in_use(&agent_id, &active_channels, &evicted_channels, &released_messages) >= limit)
+                    {
+                        // Conversations already waiting go first. Otherwise the
+                        // conversation takes the slot of a channel that is
+                        // draining or can be evicted, once that channel stops.
+                        let slot_freeing = queued_conversations.len(&agent_id) == 0
+                            && (evicted_channels.is_draining(&agent_id)
+                                || evict_longest_idle_channel(
+                                    &agent_id,
+                                    &mut active_channels,
+                                    &mut evicted_channels,
+                                ).await);
+                        if slot_freeing {
+                            // Not overflow: the conversation already has a slot
+                            // coming, so the queue cap doesn't apply.
+                            let _ = queued_conversations.push(&agent_id, message, usize::MAX);
+                            tracing::debug!(
+                                conversation_id = %conversation_id,
+                                agent_id = %agent_id,
+                                "waiting for an evicted channel to stop"
+                            );
+                            #[cfg(feature = "metrics")]
+                            record_queued_conversations(&agents, &queued_conversations);
+                            continue;
+                        }
+                        match channel_config.overflow_policy {
+                            spacebot::config::ChannelOverflowPolicy::Queue => {
+                                match queued_conversations.push(&agent_id, message, max_queued) {
+                                    Ok(_) => {
+                                        tracing::info!(
+                                            conversation_id = %conversation_id,
+                                            agent_id = %agent_id,
+                                            limit,
+                                            queued = queued_conversations.len(&agent_id),
+                                            "channel limit reached, queued new conversation"
+                                        );
+                                        #[cfg(feature = "metrics")]
+                                        record_queued_conversations(&agents, &queued_conversations);
+                                    }
+                                    Err(message) => {
+                                        tracing::info!(
+                                            conversation_id = %conversation_id,
+                                            agent_id = %agent_id,
+                                            limit,
+                                            max_queued,
+                                            "channel limit reached and queue is full, rejected new conversation"
+                                        );
+                                        #[cfg(feature = "metrics")]
+                                        spacebot::telemetry::Metrics::global()
+                                            .conversations_rejected_total
+                                            .with_label_values(&[agent_id.as_ref()])
+                                            .inc();
+                                        reply_busy(&messaging_manager, agent, &channel_config, message);
+                                    }
+                                }
+                            }
+                            spacebot::config::ChannelOverflowPolicy::Reject => {
+                                tracing::info!(
+                                    conversation_id = %conversation_id,
+                                    agent_id = %agent_id,
+                                    limit,
+                                    "channel limit reached, rejected new conversation"
+                                );
+                                #[cfg(feature = "metrics")]
+                                spacebot::telemetry::Metrics::global()
+                                    .conversations_rejected_total
+                                    .with_label_values(&[agent_id.as_ref()])
+                                    .inc();
+                                reply_busy(&messaging_manager, agent, &channel_config, message);
+                            }
+                        }
+                        continue;
+                    }
+                }
+
                 // Find or create a channel for this conversation
                 if !active_channels.contains_key(&conversation_id) {
                     let Some(agent) = agents.get(&agent_id) else {
@@ -2105,7 +2894,7 @@ async fn run(
                     };
 
                     // Create outbound response channel
-                    let (response_tx, mut response_rx) = mpsc::channel::<spacebot::RoutedResponse>(32);
+                    let (response_tx, response_rx) = mpsc::channel::<spacebot::RoutedResponse>(32);
 
                     // Subscribe to the agent's event bus
                     let event_rx = agent.deps.event_tx.subscribe();
@@ -2134,6 +2923,7 @@ async fn run(
                         .process_control_registry
                         .register_channel(channel.id.clone(), channel.control_handle().downgrade())
                         .await;
+                    let idle_handle = channel.idle_handle();
 
                     // Register the channel's status block with the API for snapshot queries
                     api_state.register_channel_status(
@@ -2150,13 +2940,28 @@ async fn run(
                     // Backfill recent message history from the platform.
                     // The transcript is injected into the system prompt (not chat
                     // history) so the LLM treats it as read-only system context
-                    // rather than actionable user messages.
+                    // rather than actionable user messages. Channels recreated
+                    // after an idle eviction backfill from the conversation log
+                    // instead, which also has the agent's own replies.
                     let backfill_count = agent.config.history_backfill_count();
-                    if backfill_count > 0 {
+                    let was_evicted = evicted_channels.recent.remove(&conversation_id).is_some();
+                    if backfill_count > 0 && was_evicted {
+                        backfill_from_conversation_log(
+                            &mut channel,
+                            &conversation_id,
+                            backfill_count,
+                        ).await;
+                    } else if backfill_count > 0 {
                         match messaging_manager.fetch_history(&message, backfill_count).await {
                             Ok(history_messages) => {
+                                let cutoff = channel
+                                    .deps
+                                    .runtime_config
+                                    .channel_config
+                                    .load()
+                                    .history_cutoff(chrono::Utc::now());
                                 if let Some(transcript) =
-                                    render_platform_history_backfill(&history_messages)
+                                    render_platform_history_backfill(&history_messages, cutoff)
                                 {
                                     channel.set_backfill_transcript(transcript);
 
@@ -2173,8 +2978,19 @@ async fn run(
                         }
                     }
 
+                    // Greet new conversations on bindings that configure a welcome message
+                    let welcome_message =
+                        spacebot::config::binding_for_message(&bindings.load(), &message)
+                        .filter(|binding| binding.routes_to(agent_id.as_ref()))
+                        .and_then(|binding| binding.welcome_message.clone());
+                    if let Some(welcome_message) = welcome_message {
+                        channel.set_welcome_message(welcome_message);
+                    }
+
                     // Spawn the channel's event loop
                     let cleanup_channel_id = conversation_id.clone();
+                    let cleanup_channel_state = channel.state.clone();
+                    let channel_stopped_tx = channel_stopped_tx.clone();
                     let process_control_registry = agent.deps.process_control_registry.clone();
                     let api_state_for_cleanup = api_state.clone();
                     tokio::spawn(async move {
@@ -2188,25 +3004,57 @@ async fn run(
                             .unregister_channel(&scoped_channel_id, channel_registration_id)
                             .await;
                         api_state_for_cleanup
-                            .unregister_channel_status(&cleanup_channel_id)
+                            .unregister_channel_status(
+                                &cleanup_channel_id,
+                                &cleanup_channel_state.status_block,
+                            )
                             .await;
                         api_state_for_cleanup
-                            .unregister_channel_state(&cleanup_channel_id)
+                            .unregister_channel_state(&cleanup_channel_id, &cleanup_channel_state)
                             .await;
+                        channel_stopped_tx
+                            .send((cleanup_channel_id, channel_registration_id))
+                            .ok();
                     });
 
                     // Spawn outbound response routing: reads from response_rx,
                     // sends to the messaging adapter and forwards to SSE
                     let messaging_for_outbound = messaging_manager.clone();
+                    let runtime_config_for_outbound = agent.deps.runtime_config.clone();
                     let outbound_conversation_id = conversation_id.clone();
                     let api_event_tx = api_state.event_tx.clone();
                     let sse_agent_id = agent_id.to_string();
                     let sse_channel_id = conversation_id.clone();
+                    let mut response_rx = spacebot::messaging::pacing::spawn_pacer(
+                        response_rx,
+                        runtime_config_for_outbound.clone(),
+                    );
                     let outbound_handle = tokio::spawn(async move {
+                        let mut stream_text = None;
                         while let Some(routed) = response_rx.recv().await {
-                            let spacebot::RoutedResponse { response, target } = routed;
-                            forward_sse_event(&api_event_tx, &sse_agent_id, &sse_channel_id, &response);
-                            route_outbound(&messaging_for_outbound, &target, response).await;
+                            let spacebot::RoutedResponse {
+                                response,
+                                target,
+                                usage,
+                            } = routed;
+                            forward_sse_event(
+                                &api_event_tx,
+                                &sse_agent_id,
+                                &sse_channel_id,
+                                &response,
+                                usage,
+                                &mut stream_text,
+                            );
+                            let idempotency_key =
+                                spacebot::messaging::dedup::idempotency_key(&target, &response);
+                            route_outbound(
+                                &messaging_for_outbound,
+                                &runtime_config_for_outbound,
+                                &target,
+                                response,
+                                idempotency_key.as_deref(),
+                            )
+                            .await;
                         }
                         tracing::debug!(
                             conversation_id = %outbound_conversation_id,
@@ -2215,13 +3063,17 @@ async fn run(
                     });
 
                     active_channels.insert(conversation_id.clone(), ActiveChannel {
+                        agent_id: agent_id.clone(),
                         message_tx: channel_tx,
+                        idle_handle,
+                        registration_id: channel_registration_id,
                         _outbound_handle: outbound_handle,
                     });
 
                     tracing::info!(
                         conversation_id = %conversation_id,
                         agent_id = %agent_id,
+                        was_evicted,
                         "new channel created"
                     );
                 }
@@ -2254,12 +3106,78 @@ async fn run(
                     }
                 }
             }
+            _ = idle_sweep.tick(), if agents_initialized => {
+                evicted_channels
+                    .recent
+                    .retain(|_, evicted_at| evicted_at.elapsed() < EVICTED_CHANNEL_RETENTION);
+                let mut idle_conversations = Vec::new();
+                for (conversation_id, active) in &active_channels {
+                    let Some(agent) = agents.get(&active.agent_id) else {
+                        continue;
+                    };
+                    let idle_timeout_secs =
+                        agent.deps.runtime_config.channel_config.load().idle_timeout_secs;
+                    if idle_timeout_secs == 0 {
+                        continue;
+                    }
+                    let idle_timeout = std::time::Duration::from_secs(idle_timeout_secs);
+                    if active
+                        .idle_handle
+                        .idle_for()
+                        .await
+                        .is_some_and(|idle| idle >= idle_timeout)
+                    {
+                        idle_conversations.push(conversation_id.clone());
+                    }
+                }
+                for conversation_id in idle_conversations {
+                    evict_channel(
+                        conversation_id,
+                        &mut active_channels,
+                        &mut evicted_channels,
+                        "idle timeout",
+                    );
+                }
+                release_queued_conversations(
+                    &agents,
+                    &mut active_channels,
+                    &mut evicted_channels,
+                    &mut queued_conversations,
+                    &mut released_messages,
+                ).await;
+                #[cfg(feature = "metrics")]
+                record_active_channels(&agents, &active_channels);
+            }
+            Some(stopped) = channel_stopped_rx.recv() => {
+                // An evicted channel finished draining and freed its slot.
+                if evicted_channels.draining.remove(&stopped).is_some() {
+                    release_queued_conversations(
+                        &agents,
+                        &mut active_channels,
+                        &mut evicted_channels,
+                        &mut queued_conversations,
+                        &mut released_messages,
+                    ).await;
+                }
+            }
             Some(agent) = agent_rx.recv() => {
-                tracing::info!(agent_id = %agent.id, "registering new agent in main loop");
-                agents.insert(agent.id.clone(), agent);
+                let agent_id = agent.id.clone();
+                if agents.insert(agent_id.clone(), agent).is_some() {
+                    // Restarted agent: drop channels still bound to the old
+                    // instance so the next message builds one on the new deps.
+                    active_channels.retain(|_, channel| channel.agent_id != agent_id);
+                    tracing::info!(agent_id = %agent_id, "replaced restarted agent in main loop");
+                } else {
+                    tracing::info!(agent_id = %agent_id, "registering new agent in main loop");
+                }
             }
             Some(agent_id) = agent_remove_rx.recv() => {
                 let key: spacebot::AgentId = Arc::from(agent_id.as_str());
+                active_channels.retain(|_, channel| channel.agent_id != key);
+                queued_conversations.remove_agent(&key);
+                evicted_channels.draining.retain(|_, draining_agent_id| *draining_agent_id != key);
+                released_messages.retain(|message| message.agent_id.as_ref() != Some(&key));
+                spacebot::config::ReactionTriggers::unregister(&agent_id);
                 if let Some(agent) = agents.remove(&key) {
                     agent.deps.mcp_manager.disconnect_all().await;
                     tracing::info!(agent_id = %agent_id, "removed agent from main loop");
@@ -2324,6 +3242,7 @@ async fn run(
                         {
                             Ok(new_llm) => {
                                 let new_llm_manager = Arc::new(new_llm);
+                                spacebot::llm::health::spawn_health_probe(&new_llm_manager);
                                 // Update agent_humans from the reloaded config
                                 // before initialize_agents so agents see the
                                 // latest [[humans]] entries.
@@ -2560,7 +3479,7 @@ async fn initialize_agents(
         })?;
 
         // Per-agent database connections
-        let db = spacebot::db::Db::connect(&agent_config.data_dir)
+        let db = spacebot::db::Db::connect(&agent_config.data_dir, &config.db)
             .await
             .with_context(|| {
                 format!(
@@ -2634,11 +3553,76 @@ async fn initialize_agents(
             tracing::warn!(%error, agent = %agent_config.id, "failed to create FTS index");
         }
 
-        let memory_search = Arc::new(spacebot::memory::MemorySearch::new(
-            memory_store,
-            embedding_table,
-            embedding_model.clone(),
-        ));
+        let routed_embedders = match spacebot::memory::routing::open_routed_embedders(
+            &agent_config.embedding.routes,
+            &db.lance,
+            &config.instance_dir.join("embedding_cache"),
+        )
+        .await
+        {
+            Ok(routed_embedders) => routed_embedders,
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    agent = %agent_config.id,
+                    "failed to load routed embedding models, using the default model only"
+                );
+                Vec::new()
+            }
+        };
+
+        let memory_search = Arc::new(
+            spacebot::memory::MemorySearch::new(
+                memory_store,
+                embedding_table,
+                embedding_model.clone(),
+            )
+            .with_agent_id(&agent_config.id)
+            .with_embedding_config(agent_config.embedding.clone())
+            .with_routed_embedders(routed_embedders)
+            .with_decay_config(agent_config.memory_decay)
+            .with_scrub_config(&agent_config.memory_scrub),
+        );
+
+        // One-time import of workspace seed memories. Failures are retried on
+        // the next start since the marker is only written on success.
+        match spacebot::memory::seed::seed_memories_once(
+            &memory_search,
+            &agent_config.workspace,
+            &agent_config.data_dir,
+        )
+        .await
+        {
+            Ok(0) => {}
+            Ok(count) => {
+                tracing::info!(agent_id = %agent_config.id, count, "seeded memories from workspace");
+            }
+            Err(error) => {
+                tracing::warn!(
+                    agent_id = %agent_config.id,
+                    %error,
+                    "failed to seed memories, will retry on next start"
+                );
+            }
+        }
+
+        // Routes configured after memories were saved start with empty
+        // tables; embed the existing memories into them in the background.
+        {
+            let memory_search = memory_search.clone();
+            let agent_id = agent_config.id.clone();
+            tokio::spawn(async move {
+                match memory_search.backfill_routed_embeddings().await {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        tracing::info!(agent_id = %agent_id, count, "backfilled routed embeddings");
+                    }
+                    Err(error) => {
+                        tracing::warn!(agent_id = %agent_id, %error, "failed to backfill routed embeddings");
+                    }
+                }
+            });
+        }
 
         // Working memory event log (temporal situational awareness).
         let working_memory_timezone = {
@@ -2677,12 +3661,21 @@ async fn initialize_agents(
             spacebot::skills::SkillSet::load(&config.skills_dir(), &agent_config.skills_dir())
                 .await;
 
+        let prompts = prompt_engine
+            .with_overrides(&agent_config.prompt_overrides)
+            .with_context(|| {
+                format!(
+                    "failed to load prompt overrides for agent '{}'",
+                    agent_config.id
+                )
+            })?;
+
         // Build the RuntimeConfig with all hot-reloadable values
         let runtime_config = Arc::new(spacebot::config::RuntimeConfig::new(
             &config.instance_dir,
             agent_config,
             &config.defaults,
-            prompt_engine.clone(),
+            prompts,
             identity,
             skills,
         ));
@@ -2756,6 +3749,9 @@ async fn initialize_agents(
             process_control_registry: Arc::new(
                 spacebot::agent::process_control::ProcessControlRegistry::new(),
             ),
+            process_limiter: Arc::new(spacebot::agent::process_limit::ProcessLimiter::new(
+                agent_id.clone(),
+            )),
             injection_tx: injection_tx.clone(),
             working_memory,
         };
@@ -2820,6 +3816,12 @@ async fn initialize_agents(
             .record();
     }
 
+    // Forward raw process events to the configured sink, if any. Set before
+    // agents register their event streams so they get attached.
+    if let Some(sink) = spacebot::event_sink::EventSink::start(&config.event_sink) {
+        api_state.set_event_sink(sink);
+    }
+
     // Wire agent event streams, DB pools, and config summaries into the API server
     {
         let mut agent_pools = std::collections::HashMap::new();
@@ -2944,7 +3946,8 @@ async fn initialize_agents(
                 discord_permissions.clone().ok_or_else(|| {
                     anyhow::anyhow!("discord permissions not initialized when discord is enabled")
                 })?,
-            );
+            )
+            .with_stream_edits(discord_config.stream_edit_interval());
             new_messaging_manager.register(adapter).await;
         }
 
@@ -2971,7 +3974,8 @@ async fn initialize_agents(
                 runtime_key,
                 &instance.token,
                 perms,
-            );
+            )
+            .with_stream_edits(discord_config.stream_edit_interval());
             new_messaging_manager.register(adapter).await;
         }
     }
@@ -3140,6 +4144,10 @@ async fn initialize_agents(
             webhook_config.port,
             &webhook_config.bind,
             webhook_config.auth_token.clone(),
+        )
+        .with_signing_secret(
+            webhook_config.signing_secret.clone(),
+            &webhook_config.signature_header,
         );
         new_messaging_manager.register(adapter).await;
     }
@@ -3384,6 +4392,16 @@ async fn initialize_agents(
     for (agent_id, agent) in agents.iter_mut() {
         let store = Arc::new(spacebot::cron::CronStore::new(agent.db.sqlite.clone()));
         agent.deps.messaging_manager = Some(messaging_manager.clone());
+        messaging_manager.register_agent_footer(
+            agent_id.to_string(),
+            agent.deps.runtime_config.clone(),
+            agent
+                .deps
+                .agent_names
+                .get(agent_id.as_ref())
+                .cloned()
+                .unwrap_or_else(|| agent_id.to_string()),
+        );
 
         // Seed cron jobs from config into the database
         for cron_def in &agent.config.cron {
@@ -3471,6 +4489,9 @@ async fn initialize_agents(
                 agent.config.ingest_dir(),
                 agent.deps.clone(),
             );
+            api_state
+                .register_agent_tasks(agent_id, [handle.abort_handle()])
+                .await;
             ingestion_handles.push(handle);
             tracing::info!(agent_id = %agent_id, "memory ingestion loop started");
         }
@@ -3481,25 +4502,57 @@ async fn initialize_agents(
         let cortex_logger = spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone());
         let warmup_handle =
             spacebot::agent::cortex::spawn_warmup_loop(agent.deps.clone(), cortex_logger.clone());
-        cortex_handles.push(warmup_handle);
         tracing::info!(agent_id = %agent_id, "warmup loop started");
 
         let cortex_handle =
             spacebot::agent::cortex::spawn_cortex_loop(agent.deps.clone(), cortex_logger.clone());
-        cortex_handles.push(cortex_handle);
         tracing::info!(agent_id = %agent_id, "cortex loop started");
 
-        let association_handle =
-            spacebot::agent::cortex::spawn_association_loop(agent.deps.clone(), cortex_logger);
-        cortex_handles.push(association_handle);
+        let association_handle = spacebot::agent::cortex::spawn_association_loop(
+            agent.deps.clone(),
+            cortex_logger.clone(),
+        );
         tracing::info!(agent_id = %agent_id, "cortex association loop started");
 
+        let consolidation_handle =
+            spacebot::agent::cortex::spawn_consolidation_loop(agent.deps.clone(), cortex_logger);
+        tracing::info!(agent_id = %agent_id, "cortex consolidation loop started");
+
         let ready_task_handle = spacebot::agent::cortex::spawn_ready_task_loop(
             agent.deps.clone(),
             spacebot::agent::cortex::CortexLogger::new(agent.db.sqlite.clone()),
         );
-        cortex_handles.push(ready_task_handle);
         tracing::info!(agent_id = %agent_id, "cortex ready-task loop started");
+
+        let worker_log_cleanup_handle = spacebot::agent::worker_logs::spawn_worker_log_cleanup_loop(
+            spacebot::agent::worker_logs::worker_log_dirs(
+                &agent.config.data_dir,
+                &agent.config.workspace,
+            ),
+            agent.deps.clone(),
+        );
+
+        api_state
+            .register_agent_tasks(
+                agent_id,
+                [
+                    warmup_handle.abort_handle(),
+                    cortex_handle.abort_handle(),
+                    association_handle.abort_handle(),
+                    consolidation_handle.abort_handle(),
+                    ready_task_handle.abort_handle(),
+                    worker_log_cleanup_handle.abort_handle(),
+                ],
+            )
+            .await;
+        cortex_handles.extend([
+            warmup_handle,
+            cortex_handle,
+            association_handle,
+            consolidation_handle,
+            ready_task_handle,
+            worker_log_cleanup_handle,
+        ]);
     }
 
     // Create cortex chat sessions for each agent
@@ -3567,7 +4620,10 @@ async fn initialize_agents(
 
 #[cfg(test)]
 mod tests {
-    use super::wait_for_startup_warmup_tasks;
+    use super::{
+        EvictedChannels, MAX_QUEUED_MESSAGES_PER_CONVERSATION, QueuedConversations, SseFrame,
+        SseParser, channel_slots_in_use, event_matches, wait_for_startup_warmup_tasks,
+    };
     use std::future::pending;
     use std::sync::Arc;
     use std::time::Duration;
@@ -3630,4 +4686,163 @@ mod tests {
             "startup warmup timeout should return without waiting for non-cooperative task"
         );
     }
+
+    #[test]
+    fn sse_parser_reassembles_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": ping\n\nevent: tool_st").is_empty());
+        assert!(parser.push(b"arted\ndata: {\"agent_id\":").is_empty());
+        let frames = parser.push(b"\"main\"}\r\n\nevent: config_reloaded\ndata: {}\n\n");
+        assert_eq!(
+            frames,
+            vec![
+                SseFrame {
+                    event: "tool_started".into(),
+                    data: r#"{"agent_id":"main"}"#.into(),
+                },
+                SseFrame {
+                    event: "config_reloaded".into(),
+                    data: "{}".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn event_filters_match_agent_and_type() {
+        let payload = serde_json::json!({"type": "tool_started", "agent_id": "main"});
+        let types = vec!["tool_started".to_string(), "worker_completed".to_string()];
+        assert!(event_matches(
+            "tool_started",
+            &payload,
+            Some("main"),
+            &types
+        ));
+        assert!(event_matches("tool_started", &payload, None, &[]));
+        assert!(!event_matches(
+            "tool_started",
+            &payload,
+            Some("other"),
+            &types
+        ));
+        assert!(!event_matches(
+            "tool_started",
+            &payload,
+            Some("main"),
+            &types[1..]
+        ));
+
+        let global = serde_json::json!({"type": "config_reloaded"});
+        assert!(event_matches("config_reloaded", &global, Some("main"), &[]));
+    }
+
+    #[test]
+    fn queued_conversations_keep_arrival_order_and_group_messages() {
+        let agent_id: spacebot::AgentId = Arc::from("main");
+        let message = |conversation_id: &str, id: &str| spacebot::InboundMessage {
+            id: id.into(),
+            conversation_id: conversation_id.into(),
+            ..spacebot::InboundMessage::empty()
+        };
+
+        let mut queued = QueuedConversations::default();
+        assert!(matches!(
+            queued.push(&agent_id, message("discord:1", "a"), 10),
+            Ok(true)
+        ));
+        assert!(matches!(
+            queued.push(&agent_id, message("discord:2", "b"), 10),
+            Ok(true)
+        ));
+        assert!(matches!(
+            queued.push(&agent_id, message("discord:1", "c"), 10),
+            Ok(false)
+        ));
+        assert_eq!(queued.len(&agent_id), 2);
+        assert!(queued.contains(&agent_id, "discord:2"));
+
+        let first = queued.pop(&agent_id).expect("first conversation");
+        let ids: Vec<&str> = first.iter().map(|message| message.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(
+            queued.pop(&agent_id).expect("second conversation")[0].id,
+            "b"
+        );
+        assert!(queued.pop(&agent_id).is_none());
+        assert!(queued.agent_ids().is_empty());
+    }
+
+    #[test]
+    fn full_overflow_queue_hands_messages_back() {
+        let agent_id: spacebot::AgentId = Arc::from("main");
+        let message = |conversation_id: &str, id: &str| spacebot::InboundMessage {
+            id: id.into(),
+            conversation_id: conversation_id.into(),
+            ..spacebot::InboundMessage::empty()
+        };
+
+        let mut queued = QueuedConversations::default();
+        assert!(queued.push(&agent_id, message("discord:1", "a"), 1).is_ok());
+        let rejected = queued
+            .push(&agent_id, message("discord:2", "b"), 1)
+            .expect_err("queue is full");
+        assert_eq!(rejected.id, "b");
+        assert!(!queued.contains(&agent_id, "discord:2"));
+
+        // A waiting conversation holds a bounded number of messages.
+        for index in 1..MAX_QUEUED_MESSAGES_PER_CONVERSATION {
+            assert!(
+                queued
+                    .push(&agent_id, message("discord:1", &index.to_string()), 1)
+                    .is_ok()
+            );
+        }
+        assert!(
+            queued
+                .push(&agent_id, message("discord:1", "overflow"), 1)
+                .is_err()
+        );
+
+        // Nothing queues when the cap is zero.
+        let other_agent_id: spacebot::AgentId = Arc::from("other");
+        assert!(
+            queued
+                .push(&other_agent_id, message("discord:3", "c"), 0)
+                .is_err()
+        );
+        assert_eq!(queued.agent_ids(), vec![agent_id.clone()]);
+    }
+
+    #[test]
+    fn draining_channels_hold_their_slot_until_they_stop() {
+        let agent_id: spacebot::AgentId = Arc::from("main");
+        let other_agent_id: spacebot::AgentId = Arc::from("other");
+        let mut evicted = EvictedChannels::default();
+        evicted
+            .draining
+            .insert(("discord:1".into(), 1), agent_id.clone());
+        evicted
+            .draining
+            .insert(("discord:1".into(), 2), agent_id.clone());
+        evicted
+            .draining
+            .insert(("discord:2".into(), 1), other_agent_id.clone());
+        let active = std::collections::HashMap::new();
+        let released = std::collections::VecDeque::new();
+
+        assert_eq!(
+            channel_slots_in_use(&agent_id, &active, &evicted, &released),
+            2
+        );
+        assert!(evicted.is_draining(&agent_id));
+
+        evicted.draining.remove(&("discord:1".to_string(), 1));
+        evicted.draining.remove(&("discord:1".to_string(), 2));
+        assert_eq!(
+            channel_slots_in_use(&agent_id, &active, &evicted, &released),
+            0
+        );
+        assert!(!evicted.is_draining(&agent_id));
+        assert!(evicted.is_draining(&other_agent_id));
+    }
 }
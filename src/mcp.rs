@@ -31,6 +31,8 @@ pub struct McpServerStatus {
     pub enabled: bool,
     pub transport: String,
     pub state: McpConnectionState,
+    /// Number of tools exposed by the server. Zero unless connected.
+    pub tool_count: usize,
 }
 
 #[derive(Clone)]
@@ -136,6 +138,11 @@ impl McpConnection {
         matches!(self.state().await, McpConnectionState::Connected)
     }
 
+    /// Number of cached tools from the last successful listing.
+    pub async fn tool_count(&self) -> usize {
+        self.tools.read().await.len()
+    }
+
     pub async fn connect(&self) -> Result<()> {
         #[cfg(feature = "metrics")]
         let connect_start = std::time::Instant::now();
@@ -647,6 +654,30 @@ impl McpManager {
         result
     }
 
+    /// Disconnect a single server and drop it from the active set.
+    ///
+    /// The server stays configured, so `reconnect` brings it back. Removing
+    /// the connection (rather than only closing it) means workers spawned
+    /// afterwards stop seeing its tools immediately.
+    pub async fn disconnect(&self, name: &str) -> Result<()> {
+        if !self
+            .configs
+            .read()
+            .await
+            .iter()
+            .any(|config| config.name == name)
+        {
+            return Err(anyhow!("mcp server '{}' is not configured", name));
+        }
+
+        let removed = self.connections.write().await.remove(name);
+        if let Some(connection) = removed {
+            connection.disconnect().await;
+        }
+
+        Ok(())
+    }
+
     pub async fn reconcile(
         &self,
         old_configs: &[McpServerConfig],
@@ -750,10 +781,16 @@ impl McpManager {
 
         let mut statuses = Vec::with_capacity(configs.len());
         for config in configs {
-            let state = if let Some(connection) = connections.get(&config.name) {
-                connection.state().await
+            let (state, tool_count) = if let Some(connection) = connections.get(&config.name) {
+                let state = connection.state().await;
+                let tool_count = if state == McpConnectionState::Connected {
+                    connection.tool_count().await
+                } else {
+                    0
+                };
+                (state, tool_count)
             } else {
-                McpConnectionState::Disconnected
+                (McpConnectionState::Disconnected, 0)
             };
 
             statuses.push(McpServerStatus {
@@ -761,6 +798,7 @@ impl McpManager {
                 enabled: config.enabled,
                 transport: config.transport.kind().to_string(),
                 state,
+                tool_count,
             });
         }
 
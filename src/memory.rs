@@ -1,15 +1,21 @@
 //! Memory storage and retrieval system.
 
+pub mod consolidation;
 pub mod embedding;
 pub mod lance;
 pub mod maintenance;
+pub mod routing;
+pub mod scrub;
 pub mod search;
+pub mod seed;
+pub mod snapshot;
 pub mod store;
 pub mod types;
 pub mod working;
 
 pub use embedding::EmbeddingModel;
 pub use lance::EmbeddingTable;
+pub use scrub::MemoryScrubber;
 pub use search::{MemorySearch, SearchConfig, SearchMode, SearchSort, curate_results};
 pub use store::MemoryStore;
 pub use types::{Association, Memory, MemoryType, RelationType};
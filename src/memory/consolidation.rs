@@ -0,0 +1,126 @@
+//! Memory consolidation: group associated memories into clusters that the
+//! cortex can fold into a single higher-level memory.
+
+use crate::memory::Association;
+
+use std::collections::{HashMap, HashSet};
+
+/// Upper bound on memories folded into one consolidated memory.
+pub const MAX_CLUSTER_SIZE: usize = 12;
+
+/// Group memories into consolidation clusters using their association edges.
+///
+/// Each cluster is a seed memory plus its direct neighbours, so every member
+/// is associated with the seed rather than only transitively related to it.
+/// Seeds are tried from the most connected down, neighbours are taken in
+/// order of edge weight, and a memory joins at most one cluster per pass.
+/// Clusters smaller than `min_cluster_size` are dropped.
+pub fn find_clusters(edges: &[Association], min_cluster_size: usize) -> Vec<Vec<String>> {
+    let mut neighbours: HashMap<&str, Vec<(&str, f32)>> = HashMap::new();
+    for edge in edges {
+        if edge.source_id == edge.target_id {
+            continue;
+        }
+        neighbours
+            .entry(edge.source_id.as_str())
+            .or_default()
+            .push((edge.target_id.as_str(), edge.weight));
+        neighbours
+            .entry(edge.target_id.as_str())
+            .or_default()
+            .push((edge.source_id.as_str(), edge.weight));
+    }
+
+    // A pair can be linked by more than one edge; keep the strongest.
+    for list in neighbours.values_mut() {
+        list.sort_by(|a, b| a.0.cmp(b.0).then_with(|| b.1.total_cmp(&a.1)));
+        list.dedup_by(|a, b| a.0 == b.0);
+        list.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    }
+
+    let mut seeds: Vec<&str> = neighbours.keys().copied().collect();
+    seeds.sort_by(|a, b| {
+        neighbours[b]
+            .len()
+            .cmp(&neighbours[a].len())
+            .then_with(|| a.cmp(b))
+    });
+
+    let mut claimed: HashSet<&str> = HashSet::new();
+    let mut clusters = Vec::new();
+    for seed in seeds {
+        if claimed.contains(seed) {
+            continue;
+        }
+        let mut members = vec![seed];
+        for (neighbour, _) in &neighbours[seed] {
+            if members.len() >= MAX_CLUSTER_SIZE {
+                break;
+            }
+            if !claimed.contains(neighbour) && !members.contains(neighbour) {
+                members.push(neighbour);
+            }
+        }
+        if members.len() < min_cluster_size.max(2) {
+            continue;
+        }
+        claimed.extend(members.iter().copied());
+        clusters.push(members.into_iter().map(str::to_string).collect());
+    }
+
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::RelationType;
+
+    fn edge(source: &str, target: &str, weight: f32) -> Association {
+        Association::new(source, target, RelationType::RelatedTo).with_weight(weight)
+    }
+
+    #[test]
+    fn star_around_most_connected_memory_forms_a_cluster() {
+        let edges = vec![
+            edge("hub", "a", 0.9),
+            edge("hub", "b", 0.8),
+            edge("c", "hub", 0.7),
+            edge("x", "y", 0.9),
+        ];
+
+        let clusters = find_clusters(&edges, 3);
+        assert_eq!(clusters, vec![vec!["hub", "a", "b", "c"]]);
+    }
+
+    #[test]
+    fn memories_join_at_most_one_cluster() {
+        let edges = vec![
+            edge("one", "a", 0.9),
+            edge("one", "b", 0.9),
+            edge("one", "shared", 0.9),
+            edge("two", "shared", 0.9),
+            edge("two", "c", 0.9),
+            edge("two", "d", 0.9),
+            edge("two", "e", 0.9),
+        ];
+
+        let clusters = find_clusters(&edges, 3);
+        assert_eq!(clusters.len(), 2);
+        let shared_count = clusters
+            .iter()
+            .filter(|cluster| cluster.iter().any(|id| id == "shared"))
+            .count();
+        assert_eq!(shared_count, 1);
+    }
+
+    #[test]
+    fn clusters_are_capped_in_size() {
+        let edges: Vec<Association> = (0..30)
+            .map(|index| edge("hub", &format!("leaf-{index:02}"), 0.9))
+            .collect();
+
+        let clusters = find_clusters(&edges, 3);
+        assert_eq!(clusters[0].len(), MAX_CLUSTER_SIZE);
+    }
+}
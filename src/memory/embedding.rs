@@ -1,21 +1,78 @@
 //! Embedding generation via fastembed.
 
 use crate::error::{LlmError, Result};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::Bound;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::Instant;
+use tokio::sync::oneshot;
+
+/// Maximum number of texts sent to the model in a single `embed` call.
+pub const MAX_EMBED_BATCH_SIZE: usize = 32;
+
+/// The fastembed model every agent embeds with (fastembed's default).
+pub const EMBEDDING_MODEL_NAME: &str = "all-MiniLM-L6-v2";
+
+/// Queue for requests that aren't made on behalf of a specific agent.
+pub const SHARED_EMBED_QUEUE: &str = "shared";
+
+/// Models loaded for embedding routes, keyed by model code. Agents routing
+/// to the same model share one instance.
+static ROUTED_MODELS: LazyLock<Mutex<HashMap<String, Arc<EmbeddingModel>>>> =
+    LazyLock::new(Default::default);
 
 /// Embedding model wrapper with thread-safe sharing.
 ///
 /// fastembed's TextEmbedding is not Send, so we hold it behind an Arc and
 /// use spawn_blocking to call into it from async contexts.
+///
+/// Concurrent requests are coalesced: while one batch is running on the
+/// blocking pool, new requests queue up and are sent together as the next
+/// batch. Ingestion with `concurrency > 1` and parallel memory saves end up
+/// sharing model calls instead of embedding one text at a time.
+///
+/// Every agent shares the one model, so requests are queued per agent and
+/// batches are filled round-robin across the queues. A burst of ingestion on
+/// one agent then delays another agent's recall by at most a batch, instead
+/// of leaving it behind the whole burst.
 pub struct EmbeddingModel {
     model: Arc<fastembed::TextEmbedding>,
+    pending: Arc<Mutex<PendingEmbeds>>,
+    /// Length of the vectors the model produces.
+    dimension: usize,
+}
+
+/// Requests waiting for the next coalesced batch.
+#[derive(Default)]
+struct PendingEmbeds {
+    /// Waiting requests, one queue per agent. Empty queues are removed.
+    queues: BTreeMap<Arc<str>, VecDeque<EmbedRequest>>,
+    /// The queue the last request was taken from. The next batch starts
+    /// with the queue after it.
+    last_served: Option<Arc<str>>,
+    /// Whether a flush task is currently draining `queues`.
+    flushing: bool,
+}
+
+struct EmbedRequest {
+    text: String,
+    reply: oneshot::Sender<Result<Vec<f32>>>,
 }
 
 impl EmbeddingModel {
     /// Create a new embedding model, storing downloaded model files in `cache_dir`.
     pub fn new(cache_dir: &Path) -> Result<Self> {
-        let options = fastembed::InitOptions::default()
+        Self::with_model(cache_dir, fastembed::InitOptions::default().model_name)
+    }
+
+    /// Load a specific fastembed model, storing downloaded model files in
+    /// `cache_dir`.
+    pub fn with_model(cache_dir: &Path, model_name: fastembed::EmbeddingModel) -> Result<Self> {
+        let dimension = fastembed::TextEmbedding::get_model_info(&model_name)
+            .map_err(|e| LlmError::EmbeddingFailed(e.to_string()))?
+            .dim;
+        let options = fastembed::InitOptions::new(model_name)
             .with_cache_dir(cache_dir.to_path_buf())
             .with_show_download_progress(true);
 
@@ -24,9 +81,32 @@ impl EmbeddingModel {
 
         Ok(Self {
             model: Arc::new(model),
+            pending: Arc::new(Mutex::new(PendingEmbeds::default())),
+            dimension,
         })
     }
 
+    /// The shared instance of the model named `model_code` (a fastembed model
+    /// code such as `"jinaai/jina-embeddings-v2-base-code"`), loading it on
+    /// first use.
+    pub fn routed(cache_dir: &Path, model_code: &str) -> Result<Arc<Self>> {
+        let mut models = ROUTED_MODELS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(model) = models.get(model_code) {
+            return Ok(model.clone());
+        }
+        let model_name = model_code
+            .parse::<fastembed::EmbeddingModel>()
+            .map_err(LlmError::EmbeddingFailed)?;
+        let model = Arc::new(Self::with_model(cache_dir, model_name)?);
+        models.insert(model_code.to_string(), model.clone());
+        Ok(model)
+    }
+
+    /// Length of the vectors this model produces.
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
     /// Generate embeddings for multiple texts (blocking).
     pub fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
         self.model
@@ -40,28 +120,340 @@ impl EmbeddingModel {
         Ok(embeddings.into_iter().next().unwrap_or_default())
     }
 
-    /// Generate embedding for a single text (async, spawns blocking task).
+    /// Generate embeddings for multiple texts on the shared queue.
+    /// See [`EmbeddingModel::embed_batch_for`].
+    pub async fn embed_batch(self: &Arc<Self>, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        self.embed_batch_for(SHARED_EMBED_QUEUE, texts).await
+    }
+
+    /// Generate embeddings for multiple texts on `agent_id`'s queue.
+    ///
+    /// The texts take turns with other agents' requests like any others, so
+    /// a large batch can't hold the model to itself. The returned vectors are
+    /// in input order.
+    pub async fn embed_batch_for(
+        self: &Arc<Self>,
+        agent_id: &str,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let replies = self.enqueue(agent_id, texts);
+        let mut embeddings = Vec::with_capacity(replies.len());
+        for reply in replies {
+            embeddings.push(await_reply(reply).await?);
+        }
+        Ok(embeddings)
+    }
+
+    /// Generate embedding for a single text on the shared queue.
+    /// See [`EmbeddingModel::embed_one_for`].
     pub async fn embed_one(self: &Arc<Self>, text: &str) -> Result<Vec<f32>> {
+        self.embed_one_for(SHARED_EMBED_QUEUE, text).await
+    }
+
+    /// Generate embedding for a single text on `agent_id`'s queue (async,
+    /// coalesced with any other in-flight requests into a shared batch).
+    pub async fn embed_one_for(self: &Arc<Self>, agent_id: &str, text: &str) -> Result<Vec<f32>> {
         #[cfg(feature = "metrics")]
         let _timer = crate::telemetry::Metrics::global()
             .memory_embedding_duration_seconds
             .start_timer();
 
-        let text = text.to_string();
-        let model = self.model.clone();
-        let result = tokio::task::spawn_blocking(move || {
-            model.embed(vec![text], None).map_err(|e| {
-                crate::Error::from(crate::error::LlmError::EmbeddingFailed(e.to_string()))
-            })
+        let reply = self
+            .enqueue(agent_id, vec![text.to_string()])
+            .pop()
+            .expect("one reply per queued text");
+        await_reply(reply).await
+    }
+
+    /// Queue `texts` on `agent_id`'s queue and start a flush if none is
+    /// running. Returns one reply receiver per text, in order.
+    fn enqueue(
+        self: &Arc<Self>,
+        agent_id: &str,
+        texts: Vec<String>,
+    ) -> Vec<oneshot::Receiver<Result<Vec<f32>>>> {
+        let mut replies = Vec::with_capacity(texts.len());
+        let start_flush = {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            let queue = pending.queues.entry(Arc::from(agent_id)).or_default();
+            for text in texts {
+                let (reply, receiver) = oneshot::channel();
+                queue.push_back(EmbedRequest { text, reply });
+                replies.push(receiver);
+            }
+            #[cfg(feature = "metrics")]
+            set_queue_depth(agent_id, queue.len());
+            !std::mem::replace(&mut pending.flushing, true)
+        };
+
+        if start_flush {
+            tokio::spawn(flush_pending(self.model.clone(), self.pending.clone()));
+        }
+        replies
+    }
+}
+
+async fn await_reply(reply: oneshot::Receiver<Result<Vec<f32>>>) -> Result<Vec<f32>> {
+    reply.await.map_err(|_| {
+        crate::Error::Other(anyhow::anyhow!(
+            "embedding batch was dropped before completing"
+        ))
+    })?
+}
+
+#[cfg(feature = "metrics")]
+fn set_queue_depth(agent_id: &str, depth: usize) {
+    crate::telemetry::Metrics::global()
+        .memory_embedding_queue_depth
+        .with_label_values(&[agent_id])
+        .set(depth as i64);
+}
+
+/// Drain queued requests in batches until every queue is empty.
+async fn flush_pending(model: Arc<fastembed::TextEmbedding>, pending: Arc<Mutex<PendingEmbeds>>) {
+    let mut guard = FlushGuard {
+        pending: pending.clone(),
+        armed: true,
+    };
+
+    loop {
+        let batch = {
+            let mut pending = pending.lock().unwrap_or_else(PoisonError::into_inner);
+            let batch = take_batch(&mut pending, MAX_EMBED_BATCH_SIZE);
+            if batch.is_empty() {
+                // Cleared under the same lock as the empty check so a request
+                // queued right after always starts a new flush.
+                pending.flushing = false;
+                guard.armed = false;
+                return;
+            }
+            batch
+        };
+
+        let texts = batch.iter().map(|request| request.text.clone()).collect();
+        let result = embed_blocking(model.clone(), texts).await;
+        dispatch_batch(batch, result);
+    }
+}
+
+/// Resets the flush flag if the flush task is dropped mid-batch (e.g. on
+/// runtime shutdown). Queued requests are dropped so their callers get an
+/// error instead of waiting on a flush that will never run.
+struct FlushGuard {
+    pending: Arc<Mutex<PendingEmbeds>>,
+    armed: bool,
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+            #[cfg(feature = "metrics")]
+            for agent_id in pending.queues.keys() {
+                set_queue_depth(agent_id, 0);
+            }
+            pending.queues.clear();
+            pending.flushing = false;
+        }
+    }
+}
+
+/// Take up to `max` requests, one from each agent's queue in turn, starting
+/// after the queue served last. Each queue keeps its own order.
+fn take_batch(pending: &mut PendingEmbeds, max: usize) -> Vec<EmbedRequest> {
+    let mut batch = Vec::new();
+    while batch.len() < max {
+        let Some(agent_id) = next_queue(&pending.queues, pending.last_served.as_deref()) else {
+            break;
+        };
+        let queue = pending
+            .queues
+            .get_mut(&agent_id)
+            .expect("next_queue returns an existing queue");
+        batch.extend(queue.pop_front());
+        let depth = queue.len();
+        if depth == 0 {
+            pending.queues.remove(&agent_id);
+        }
+        #[cfg(feature = "metrics")]
+        set_queue_depth(&agent_id, depth);
+        pending.last_served = Some(agent_id);
+    }
+    batch
+}
+
+/// The queue after `after` in key order, wrapping around to the first.
+fn next_queue(
+    queues: &BTreeMap<Arc<str>, VecDeque<EmbedRequest>>,
+    after: Option<&str>,
+) -> Option<Arc<str>> {
+    after
+        .and_then(|after| {
+            queues
+                .range::<str, _>((Bound::Excluded(after), Bound::Unbounded))
+                .next()
         })
-        .await
-        .map_err(|e| crate::Error::Other(anyhow::anyhow!("embedding task failed: {}", e)))??;
+        .or_else(|| queues.iter().next())
+        .map(|(agent_id, _)| agent_id.clone())
+}
+
+/// Send each embedding back to the request at the same position.
+fn dispatch_batch(requests: Vec<EmbedRequest>, result: Result<Vec<Vec<f32>>>) {
+    match result {
+        Ok(embeddings) => {
+            for (request, embedding) in requests.into_iter().zip(embeddings) {
+                let _ = request.reply.send(Ok(embedding));
+            }
+        }
+        Err(error) => {
+            let message = error.to_string();
+            for request in requests {
+                let _ = request
+                    .reply
+                    .send(Err(LlmError::EmbeddingFailed(message.clone()).into()));
+            }
+        }
+    }
+}
+
+/// Run one model call on the blocking pool and log its throughput.
+async fn embed_blocking(
+    model: Arc<fastembed::TextEmbedding>,
+    texts: Vec<String>,
+) -> Result<Vec<Vec<f32>>> {
+    let count = texts.len();
+    let started = Instant::now();
+
+    let embeddings = tokio::task::spawn_blocking(move || {
+        model
+            .embed(texts, None)
+            .map_err(|e| crate::Error::from(LlmError::EmbeddingFailed(e.to_string())))
+    })
+    .await
+    .map_err(|e| crate::Error::Other(anyhow::anyhow!("embedding task failed: {}", e)))??;
 
-        Ok(result.into_iter().next().unwrap_or_default())
+    if embeddings.len() != count {
+        return Err(LlmError::EmbeddingFailed(format!(
+            "expected {count} embeddings, model returned {}",
+            embeddings.len()
+        ))
+        .into());
     }
+
+    let elapsed = started.elapsed();
+    tracing::debug!(
+        texts = count,
+        elapsed_ms = elapsed.as_millis() as u64,
+        texts_per_sec = count as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        "embedded batch"
+    );
+
+    Ok(embeddings)
 }
 
 /// Async function to embed text using a shared model.
 pub async fn embed_text(model: &Arc<EmbeddingModel>, text: &str) -> Result<Vec<f32>> {
     model.embed_one(text).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(text: &str) -> (EmbedRequest, oneshot::Receiver<Result<Vec<f32>>>) {
+        let (reply, receiver) = oneshot::channel();
+        (
+            EmbedRequest {
+                text: text.to_string(),
+                reply,
+            },
+            receiver,
+        )
+    }
+
+    fn queue_texts(
+        pending: &mut PendingEmbeds,
+        agent_id: &str,
+        texts: &[&str],
+    ) -> Vec<oneshot::Receiver<Result<Vec<f32>>>> {
+        let mut receivers = Vec::new();
+        for text in texts {
+            let (request, receiver) = request(text);
+            pending
+                .queues
+                .entry(Arc::from(agent_id))
+                .or_default()
+                .push_back(request);
+            receivers.push(receiver);
+        }
+        receivers
+    }
+
+    fn batch_texts(batch: &[EmbedRequest]) -> Vec<&str> {
+        batch.iter().map(|request| request.text.as_str()).collect()
+    }
+
+    #[test]
+    fn take_batch_respects_max_and_preserves_order() {
+        let mut pending = PendingEmbeds::default();
+        let _receivers = queue_texts(&mut pending, "main", &["a", "b", "c"]);
+
+        let batch = take_batch(&mut pending, 2);
+        assert_eq!(batch_texts(&batch), vec!["a", "b"]);
+        assert_eq!(pending.queues["main"].len(), 1);
+        assert_eq!(pending.queues["main"][0].text, "c");
+
+        let batch = take_batch(&mut pending, 2);
+        assert_eq!(batch_texts(&batch), vec!["c"]);
+        assert!(pending.queues.is_empty());
+    }
+
+    #[test]
+    fn take_batch_round_robins_across_agents() {
+        let mut pending = PendingEmbeds::default();
+        let _ingest = queue_texts(&mut pending, "ingest", &["i1", "i2", "i3", "i4", "i5"]);
+        let _chat = queue_texts(&mut pending, "chat", &["c1"]);
+        let _ops = queue_texts(&mut pending, "ops", &["o1", "o2"]);
+
+        // A burst on one agent doesn't push the others to the back.
+        let batch = take_batch(&mut pending, 4);
+        assert_eq!(batch_texts(&batch), vec!["c1", "i1", "o1", "i2"]);
+
+        // The next batch picks up after the agent served last.
+        let _chat = queue_texts(&mut pending, "chat", &["c2"]);
+        let batch = take_batch(&mut pending, 3);
+        assert_eq!(batch_texts(&batch), vec!["o2", "c2", "i3"]);
+
+        let batch = take_batch(&mut pending, 10);
+        assert_eq!(batch_texts(&batch), vec!["i4", "i5"]);
+        assert!(pending.queues.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dispatch_batch_replies_in_input_order() {
+        let (first, first_rx) = request("first");
+        let (second, second_rx) = request("second");
+
+        dispatch_batch(vec![first, second], Ok(vec![vec![1.0], vec![2.0]]));
+
+        assert_eq!(first_rx.await.unwrap().unwrap(), vec![1.0]);
+        assert_eq!(second_rx.await.unwrap().unwrap(), vec![2.0]);
+    }
+
+    #[tokio::test]
+    async fn dispatch_batch_fans_out_errors() {
+        let (first, first_rx) = request("first");
+        let (second, second_rx) = request("second");
+
+        dispatch_batch(
+            vec![first, second],
+            Err(LlmError::EmbeddingFailed("boom".into()).into()),
+        );
+
+        assert!(first_rx.await.unwrap().is_err());
+        assert!(second_rx.await.unwrap().is_err());
+    }
+}
@@ -5,21 +5,29 @@ use arrow_array::cast::AsArray;
 use arrow_array::types::Float32Type;
 use arrow_array::{Array, RecordBatchIterator};
 use futures::TryStreamExt;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Schema constants for the embeddings table.
 const TABLE_NAME: &str = "memory_embeddings";
-const EMBEDDING_DIM: i32 = 384; // all-MiniLM-L6-v2 dimension
+pub const EMBEDDING_DIM: i32 = 384; // all-MiniLM-L6-v2 dimension
 
 /// LanceDB table for memory embeddings with HNSW index and FTS.
+///
+/// Every memory has a row in the default table, embedded with the shared
+/// model. Embedding routes add a table per routed model, named after it, so
+/// each vector is tagged with the model that produced it and vectors of
+/// different dimensions never share a column.
 pub struct EmbeddingTable {
     table: lancedb::Table,
+    dimension: i32,
 }
 
 impl Clone for EmbeddingTable {
     fn clone(&self) -> Self {
         Self {
             table: self.table.clone(),
+            dimension: self.dimension,
         }
     }
 }
@@ -30,17 +38,36 @@ impl EmbeddingTable {
     /// If the table exists but is corrupted (e.g. process killed mid-write),
     /// it is dropped and recreated. Embeddings can be regenerated from SQLite.
     pub async fn open_or_create(connection: &lancedb::Connection) -> Result<Self> {
+        Self::open_or_create_named(connection, TABLE_NAME, EMBEDDING_DIM).await
+    }
+
+    /// Open or create the table holding vectors from a routed model.
+    pub async fn open_or_create_for_model(
+        connection: &lancedb::Connection,
+        model_code: &str,
+        dimension: usize,
+    ) -> Result<Self> {
+        let dimension = i32::try_from(dimension)
+            .map_err(|_| DbError::LanceDb(format!("embedding dimension {dimension} too large")))?;
+        Self::open_or_create_named(connection, &routed_table_name(model_code), dimension).await
+    }
+
+    async fn open_or_create_named(
+        connection: &lancedb::Connection,
+        name: &str,
+        dimension: i32,
+    ) -> Result<Self> {
         // Try to open existing table
-        match connection.open_table(TABLE_NAME).execute().await {
-            Ok(table) => return Ok(Self { table }),
+        match connection.open_table(name).execute().await {
+            Ok(table) => return Ok(Self { table, dimension }),
             Err(error) => {
-                tracing::debug!(%error, "failed to open embeddings table, will create");
+                tracing::debug!(%error, table = name, "failed to open embeddings table, will create");
             }
         }
 
         // Table doesn't exist or is unreadable — try creating it
-        match Self::create_empty_table(connection).await {
-            Ok(table) => return Ok(Self { table }),
+        match Self::create_empty_table(connection, name, dimension).await {
+            Ok(table) => return Ok(Self { table, dimension }),
             Err(error) => {
                 tracing::warn!(
                     %error,
@@ -51,23 +78,30 @@ impl EmbeddingTable {
 
         // Both open and create failed — table data exists but is corrupted.
         // Drop it and recreate from scratch.
-        if let Err(error) = connection.drop_table(TABLE_NAME, &[]).await {
+        if let Err(error) = connection.drop_table(name, &[]).await {
             tracing::warn!(%error, "drop_table failed during recovery, proceeding anyway");
         }
 
-        let table = Self::create_empty_table(connection).await?;
-        tracing::info!("embeddings table recovered — embeddings will be rebuilt from memory store");
+        let table = Self::create_empty_table(connection, name, dimension).await?;
+        tracing::info!(
+            table = name,
+            "embeddings table recovered — embeddings will be rebuilt from memory store"
+        );
 
-        Ok(Self { table })
+        Ok(Self { table, dimension })
     }
 
     /// Create an empty embeddings table.
-    async fn create_empty_table(connection: &lancedb::Connection) -> Result<lancedb::Table> {
-        let schema = Self::schema();
+    async fn create_empty_table(
+        connection: &lancedb::Connection,
+        name: &str,
+        dimension: i32,
+    ) -> Result<lancedb::Table> {
+        let schema = Self::schema(dimension);
         let batches = RecordBatchIterator::new(vec![].into_iter().map(Ok), Arc::new(schema));
 
         connection
-            .create_table(TABLE_NAME, Box::new(batches))
+            .create_table(name, Box::new(batches))
             .execute()
             .await
             .map_err(|e| DbError::LanceDb(e.to_string()).into())
@@ -76,28 +110,40 @@ impl EmbeddingTable {
     /// Store an embedding with content for a memory.
     /// The content is stored for FTS search capability.
     pub async fn store(&self, memory_id: &str, content: &str, embedding: &[f32]) -> Result<()> {
-        if embedding.len() != EMBEDDING_DIM as usize {
-            return Err(DbError::LanceDb(format!(
-                "Embedding dimension mismatch: expected {}, got {}",
-                EMBEDDING_DIM,
-                embedding.len()
-            ))
-            .into());
+        self.store_many(&[(memory_id, content, embedding)]).await
+    }
+
+    /// Store several `(memory_id, content, embedding)` rows in one write.
+    pub async fn store_many(&self, rows: &[(&str, &str, &[f32])]) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        for (_, _, embedding) in rows {
+            if embedding.len() != self.dimension as usize {
+                return Err(DbError::LanceDb(format!(
+                    "Embedding dimension mismatch: expected {}, got {}",
+                    self.dimension,
+                    embedding.len()
+                ))
+                .into());
+            }
         }
 
         use arrow_array::{RecordBatch, StringArray};
 
-        let schema = Self::schema();
+        let schema = Self::schema(self.dimension);
 
         // Build arrays for the record batch
-        let id_array = StringArray::from(vec![memory_id]);
-        let content_array = StringArray::from(vec![content]);
+        let id_array = StringArray::from_iter_values(rows.iter().map(|(id, _, _)| *id));
+        let content_array =
+            StringArray::from_iter_values(rows.iter().map(|(_, content, _)| *content));
 
-        // Convert embedding to FixedSizeListArray
+        // Convert embeddings to FixedSizeListArray
         let embedding_array =
             arrow_array::FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
-                vec![Some(embedding.iter().map(|v| Some(*v)).collect::<Vec<_>>())],
-                EMBEDDING_DIM,
+                rows.iter()
+                    .map(|(_, _, embedding)| Some(embedding.iter().map(|v| Some(*v)))),
+                self.dimension,
             );
 
         let batch = RecordBatch::try_new(
@@ -111,7 +157,8 @@ impl EmbeddingTable {
         .map_err(|e| DbError::LanceDb(e.to_string()))?;
 
         // Create iterator for IntoArrow trait
-        let batches = RecordBatchIterator::new(vec![Ok(batch)], Arc::new(Self::schema()));
+        let batches =
+            RecordBatchIterator::new(vec![Ok(batch)], Arc::new(Self::schema(self.dimension)));
 
         self.table
             .add(Box::new(batches))
@@ -134,6 +181,89 @@ impl EmbeddingTable {
         Ok(())
     }
 
+    /// Delete the embeddings for several memories.
+    pub async fn delete_many(&self, memory_ids: &[String]) -> Result<()> {
+        // Keep predicates to a reasonable length.
+        for chunk in memory_ids.chunks(500) {
+            let mut quoted = Vec::with_capacity(chunk.len());
+            for memory_id in chunk {
+                Self::validate_memory_id(memory_id)?;
+                quoted.push(format!("'{memory_id}'"));
+            }
+            let predicate = format!("id IN ({})", quoted.join(", "));
+            self.table
+                .delete(&predicate)
+                .await
+                .map_err(|e| DbError::LanceDb(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete every stored embedding.
+    pub async fn delete_all(&self) -> Result<()> {
+        self.table
+            .delete("id IS NOT NULL")
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Whether the table holds no embeddings.
+    pub async fn is_empty(&self) -> Result<bool> {
+        let rows = self
+            .table
+            .count_rows(None)
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?;
+
+        Ok(rows == 0)
+    }
+
+    /// Every stored embedding, keyed by memory ID.
+    pub async fn all_embeddings(&self) -> Result<HashMap<String, Vec<f32>>> {
+        use lancedb::query::{ExecutableQuery, QueryBase, Select};
+
+        let batches: Vec<arrow_array::RecordBatch> = self
+            .table
+            .query()
+            .select(Select::columns(&["id", "embedding"]))
+            .execute()
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| DbError::LanceDb(e.to_string()))?;
+
+        let mut embeddings = HashMap::new();
+        for batch in batches {
+            let (Some(id_col), Some(embedding_col)) = (
+                batch.column_by_name("id"),
+                batch.column_by_name("embedding"),
+            ) else {
+                continue;
+            };
+            let ids: &arrow_array::StringArray = id_col.as_string::<i32>();
+            let Some(lists) = embedding_col
+                .as_any()
+                .downcast_ref::<arrow_array::FixedSizeListArray>()
+            else {
+                continue;
+            };
+
+            for i in 0..ids.len() {
+                if ids.is_valid(i) && lists.is_valid(i) {
+                    let values = lists.value(i);
+                    let embedding = values.as_primitive::<Float32Type>().values().to_vec();
+                    embeddings.insert(ids.value(i).to_string(), embedding);
+                }
+            }
+        }
+
+        Ok(embeddings)
+    }
+
     /// Vector similarity search using cosine distance.
     /// Returns (memory_id, distance) pairs sorted by distance (ascending).
     pub async fn vector_search(
@@ -141,10 +271,10 @@ impl EmbeddingTable {
         query_embedding: &[f32],
         limit: usize,
     ) -> Result<Vec<(String, f32)>> {
-        if query_embedding.len() != EMBEDDING_DIM as usize {
+        if query_embedding.len() != self.dimension as usize {
             return Err(DbError::LanceDb(format!(
                 "Query embedding dimension mismatch: expected {}, got {}",
-                EMBEDDING_DIM,
+                self.dimension,
                 query_embedding.len()
             ))
             .into());
@@ -339,8 +469,8 @@ impl EmbeddingTable {
         }
     }
 
-    /// Get the Arrow schema for the embeddings table.
-    fn schema() -> arrow_schema::Schema {
+    /// Get the Arrow schema for an embeddings table.
+    fn schema(dimension: i32) -> arrow_schema::Schema {
         arrow_schema::Schema::new(vec![
             arrow_schema::Field::new("id", arrow_schema::DataType::Utf8, false),
             arrow_schema::Field::new("content", arrow_schema::DataType::Utf8, false),
@@ -352,13 +482,18 @@ impl EmbeddingTable {
                         arrow_schema::DataType::Float32,
                         true,
                     )),
-                    EMBEDDING_DIM,
+                    dimension,
                 ),
                 false,
             ),
         ])
     }
 
+    /// Length of the vectors this table stores.
+    pub fn dimension(&self) -> usize {
+        self.dimension as usize
+    }
+
     /// Validate that a memory ID is a well-formed UUID to prevent predicate injection.
     fn validate_memory_id(memory_id: &str) -> Result<()> {
         if memory_id.len() != 36 || !memory_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
@@ -369,3 +504,19 @@ impl EmbeddingTable {
         Ok(())
     }
 }
+
+/// Table name for vectors from a routed model, e.g.
+/// `memory_embeddings_jinaai_jina_embeddings_v2_base_code`.
+fn routed_table_name(model_code: &str) -> String {
+    let slug: String = model_code
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{TABLE_NAME}_{slug}")
+}
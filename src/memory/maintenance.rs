@@ -1,7 +1,11 @@
 //! Memory maintenance: decay, prune, merge, reindex.
 
+use crate::config::EmbeddingConfig;
 use crate::error::Result;
-use crate::memory::{EmbeddingModel, EmbeddingTable, Memory, MemoryStore, MemoryType};
+use crate::memory::embedding::SHARED_EMBED_QUEUE;
+use crate::memory::{
+    EmbeddingModel, EmbeddingTable, Memory, MemorySearch, MemoryStore, MemoryType,
+};
 use anyhow::Context;
 
 use sqlx::Row;
@@ -46,6 +50,7 @@ pub async fn run_maintenance(
     memory_store: &MemoryStore,
     embedding_table: &EmbeddingTable,
     embedding_model: &Arc<EmbeddingModel>,
+    embedding_config: &EmbeddingConfig,
     config: &MaintenanceConfig,
 ) -> Result<MaintenanceReport> {
     let (_maintenance_cancel_tx, maintenance_cancel_rx) = watch::channel(false);
@@ -53,6 +58,8 @@ pub async fn run_maintenance(
         memory_store,
         embedding_table,
         embedding_model,
+        embedding_config,
+        None,
         config,
         maintenance_cancel_rx,
     )
@@ -62,10 +69,14 @@ pub async fn run_maintenance(
 /// Run maintenance tasks with a cancellation signal.
 ///
 /// The signal allows maintenance to exit quickly when the caller decides to stop it.
+/// When `routed` is set, merged memories are re-embedded on its embedding
+/// queue and also stored in its routed embedding tables.
 pub async fn run_maintenance_with_cancel(
     memory_store: &MemoryStore,
     embedding_table: &EmbeddingTable,
     embedding_model: &Arc<EmbeddingModel>,
+    embedding_config: &EmbeddingConfig,
+    routed: Option<&MemorySearch>,
     config: &MaintenanceConfig,
     mut maintenance_cancel_rx: watch::Receiver<bool>,
 ) -> Result<MaintenanceReport> {
@@ -84,6 +95,8 @@ pub async fn run_maintenance_with_cancel(
             memory_store,
             embedding_table,
             embedding_model,
+            embedding_config,
+            routed,
             config.merge_similarity_threshold,
             &mut maintenance_cancel_rx,
         )
@@ -195,6 +208,8 @@ async fn merge_similar_memories(
     memory_store: &MemoryStore,
     embedding_table: &EmbeddingTable,
     embedding_model: &Arc<EmbeddingModel>,
+    embedding_config: &EmbeddingConfig,
+    routed: Option<&MemorySearch>,
     similarity_threshold: f32,
     maintenance_cancel_rx: &mut watch::Receiver<bool>,
 ) -> Result<usize> {
@@ -276,6 +291,8 @@ async fn merge_similar_memories(
                 memory_store,
                 embedding_table,
                 embedding_model,
+                embedding_config,
+                routed,
                 &winner,
                 &loser,
                 maintenance_cancel_rx,
@@ -341,6 +358,8 @@ async fn merge_pair(
     memory_store: &MemoryStore,
     embedding_table: &EmbeddingTable,
     embedding_model: &Arc<EmbeddingModel>,
+    embedding_config: &EmbeddingConfig,
+    routed: Option<&MemorySearch>,
     survivor: &Memory,
     merged: &Memory,
     maintenance_cancel_rx: &mut watch::Receiver<bool>,
@@ -357,9 +376,15 @@ async fn merge_pair(
     )
     .await?;
 
+    // Re-embed on the agent's own queue so a large merge pass is batched
+    // fairly against other agents' requests.
+    let embedding_queue = routed.map_or(SHARED_EMBED_QUEUE, MemorySearch::embedding_queue);
     let updated_survivor_embedding = maintenance_cancelable_op(
         maintenance_cancel_rx,
-        embedding_model.embed_one(&updated_survivor.content),
+        embedding_model.embed_one_for(
+            embedding_queue,
+            &embedding_config.passage_text(&updated_survivor.content),
+        ),
     )
     .await?;
     maintenance_cancelable_op(
@@ -377,6 +402,18 @@ async fn merge_pair(
     )
     .await?;
     maintenance_cancelable_op(maintenance_cancel_rx, embedding_table.delete(&merged.id)).await?;
+
+    if let Some(routed) = routed {
+        // The merged content may now route to a different model, so clear
+        // both memories from every routed table before re-embedding.
+        routed.delete_routed_embeddings(&merged.id).await;
+        routed.delete_routed_embeddings(&updated_survivor.id).await;
+        maintenance_cancelable_op(
+            maintenance_cancel_rx,
+            routed.store_routed_embedding(&updated_survivor.id, &updated_survivor.content),
+        )
+        .await?;
+    }
     Ok(updated_survivor)
 }
 
@@ -592,9 +629,15 @@ mod tests {
         };
 
         let embedding_model = shared_embedding_model();
-        let report = run_maintenance(&store, &embedding_table, &embedding_model, &config)
-            .await
-            .expect("maintenance should succeed");
+        let report = run_maintenance(
+            &store,
+            &embedding_table,
+            &embedding_model,
+            &EmbeddingConfig::default(),
+            &config,
+        )
+        .await
+        .expect("maintenance should succeed");
 
         assert_eq!(report.merged, 1);
 
@@ -754,6 +797,7 @@ mod tests {
             &store,
             &embedding_table,
             &embedding_model,
+            &EmbeddingConfig::default(),
             &MaintenanceConfig {
                 prune_threshold: 0.2,
                 decay_rate: 0.05,
@@ -834,6 +878,8 @@ mod tests {
             &store,
             &embedding_table,
             &embedding_model,
+            &EmbeddingConfig::default(),
+            None,
             &MaintenanceConfig::default(),
             maintenance_cancel_rx,
         )
@@ -875,8 +921,14 @@ mod tests {
         };
 
         let embedding_model = shared_embedding_model();
-        let result =
-            run_maintenance(&store, &embedding_table, &embedding_model, &invalid_config).await;
+        let result = run_maintenance(
+            &store,
+            &embedding_table,
+            &embedding_model,
+            &EmbeddingConfig::default(),
+            &invalid_config,
+        )
+        .await;
         assert!(result.is_err(), "expected invalid config to fail");
         assert!(
             result
@@ -0,0 +1,212 @@
+//! Embedding routes: extra models for detected content types.
+//!
+//! Every memory is embedded with the shared default model. When an agent
+//! routes a content type (say, code) to another fastembed model, memories
+//! detected as that type also get a vector from the routed model, stored in
+//! a table of its own. Queries detected as the same type search the routed
+//! table first, so code is matched against code embeddings.
+
+use crate::config::{EmbeddingContentType, EmbeddingRoute};
+use crate::error::Result;
+use crate::memory::{EmbeddingModel, EmbeddingTable};
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Keywords that open a line of source when followed by `(`, `=`, or `{`
+/// somewhere on the line.
+const CODE_KEYWORDS: &[&str] = &[
+    "fn ",
+    "pub ",
+    "def ",
+    "let ",
+    "const ",
+    "var ",
+    "function ",
+    "class ",
+    "impl ",
+    "struct ",
+    "async ",
+    "return ",
+    "if ",
+    "for ",
+    "while ",
+    "import ",
+    "#include",
+];
+
+/// Syntax that prose rarely contains.
+const CODE_TOKENS: &[&str] = &[
+    "::", "->", "=>", "();", "!=", "==", "&&", "||", "</", "/>", "#[",
+];
+
+/// A route with its loaded model and the table holding its vectors.
+#[derive(Clone)]
+pub struct RoutedEmbedder {
+    pub route: EmbeddingRoute,
+    pub model: Arc<EmbeddingModel>,
+    pub table: EmbeddingTable,
+}
+
+impl std::fmt::Debug for RoutedEmbedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutedEmbedder")
+            .field("route", &self.route)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Load the model and open the table for each route. Models are shared
+/// across agents and downloaded into `cache_dir` on first use.
+pub async fn open_routed_embedders(
+    routes: &[EmbeddingRoute],
+    connection: &lancedb::Connection,
+    cache_dir: &Path,
+) -> Result<Vec<RoutedEmbedder>> {
+    let mut embedders = Vec::with_capacity(routes.len());
+    for route in routes {
+        let model = {
+            let cache_dir = cache_dir.to_path_buf();
+            let model_code = route.model.clone();
+            tokio::task::spawn_blocking(move || EmbeddingModel::routed(&cache_dir, &model_code))
+                .await
+                .map_err(|error| {
+                    crate::Error::Other(anyhow::anyhow!("embedding model load failed: {error}"))
+                })??
+        };
+        let table =
+            EmbeddingTable::open_or_create_for_model(connection, &route.model, model.dimension())
+                .await?;
+        embedders.push(RoutedEmbedder {
+            route: route.clone(),
+            model,
+            table,
+        });
+    }
+    Ok(embedders)
+}
+
+/// Classify `text` as code or prose.
+///
+/// A fenced block is code outright. Multi-line text is code when most of its
+/// non-empty lines look like source. A single line (most queries) is code
+/// when it looks like source or carries two pieces of code syntax.
+pub fn detect_content_type(text: &str) -> EmbeddingContentType {
+    if text.contains("```") {
+        return EmbeddingContentType::Code;
+    }
+
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let is_code = match lines.as_slice() {
+        [] => false,
+        [line] => {
+            looks_like_code_line(line)
+                || CODE_TOKENS
+                    .iter()
+                    .filter(|token| line.contains(*token))
+                    .count()
+                    >= 2
+        }
+        lines => {
+            let code_lines = lines
+                .iter()
+                .filter(|line| looks_like_code_line(line))
+                .count();
+            code_lines * 2 > lines.len()
+        }
+    };
+
+    if is_code {
+        EmbeddingContentType::Code
+    } else {
+        EmbeddingContentType::Text
+    }
+}
+
+fn looks_like_code_line(line: &str) -> bool {
+    if line.ends_with(';') || line.ends_with('{') || line == "}" || line == ")" {
+        return true;
+    }
+    if CODE_KEYWORDS
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+        && line.contains(['(', '=', '{'])
+    {
+        return true;
+    }
+    CODE_TOKENS.iter().any(|token| line.contains(token))
+}
+
+/// Merge vector matches from the routed model ahead of the default model's,
+/// keeping each memory's first (best) position. Distances from different
+/// models aren't comparable, so routed matches are ranked first rather than
+/// interleaved by distance.
+pub fn merge_routed_matches(
+    routed: Vec<(String, f32)>,
+    default: Vec<(String, f32)>,
+) -> Vec<(String, f32)> {
+    let mut seen = HashSet::new();
+    routed
+        .into_iter()
+        .chain(default)
+        .filter(|(memory_id, _)| seen.insert(memory_id.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_code_and_prose() {
+        let code = "fn main() {\n    let config = load()?;\n    run(config);\n}";
+        assert_eq!(detect_content_type(code), EmbeddingContentType::Code);
+        assert_eq!(
+            detect_content_type("Here's the fix:\n```rust\nx += 1\n```"),
+            EmbeddingContentType::Code
+        );
+        assert_eq!(
+            detect_content_type("how does Config::load -> Result work"),
+            EmbeddingContentType::Code
+        );
+        assert_eq!(
+            detect_content_type("def parse(value):"),
+            EmbeddingContentType::Code
+        );
+
+        assert_eq!(
+            detect_content_type("The deploy runs every Friday at noon."),
+            EmbeddingContentType::Text
+        );
+        assert_eq!(
+            detect_content_type("let me know when the build is green"),
+            EmbeddingContentType::Text
+        );
+        assert_eq!(
+            detect_content_type("Alice prefers tabs.\nShe reviews PRs in the morning.\n}"),
+            EmbeddingContentType::Text
+        );
+        assert_eq!(detect_content_type(""), EmbeddingContentType::Text);
+    }
+
+    #[test]
+    fn routed_matches_rank_first_without_duplicates() {
+        let routed = vec![("b".to_string(), 0.4), ("c".to_string(), 0.5)];
+        let default = vec![
+            ("a".to_string(), 0.1),
+            ("b".to_string(), 0.2),
+            ("d".to_string(), 0.3),
+        ];
+
+        let merged: Vec<String> = merge_routed_matches(routed, default)
+            .into_iter()
+            .map(|(memory_id, _)| memory_id)
+            .collect();
+        assert_eq!(merged, vec!["b", "c", "a", "d"]);
+    }
+}
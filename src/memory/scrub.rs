@@ -0,0 +1,133 @@
+//! Redaction of personal data from memory content before it is persisted.
+//!
+//! Runs on the memory-save path only. Conversation history is never touched,
+//! so the raw text stays available to the channel that produced it.
+
+use crate::config::MemoryScrubConfig;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+static EMAIL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").expect("hardcoded regex")
+});
+
+/// Eight or more digits in common phone layouts: optional country code,
+/// optional parenthesized area code, and space, dot, or dash separators.
+static PHONE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{2,4}\)\s?|\b\d{2,4}[\s.-]?)\d{3,4}[\s.-]?\d{3,4}\b")
+        .expect("hardcoded regex")
+});
+
+/// Redacts emails, phone numbers, and custom patterns from memory content.
+#[derive(Debug, Clone)]
+pub struct MemoryScrubber {
+    redact_emails: bool,
+    redact_phone_numbers: bool,
+    patterns: Vec<Regex>,
+}
+
+impl MemoryScrubber {
+    /// Build a scrubber from config. Returns `None` when scrubbing is disabled.
+    pub fn from_config(config: &MemoryScrubConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        // Patterns are validated at config load, so failures here only come
+        // from configs built in code.
+        let patterns = config
+            .patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(error) => {
+                    tracing::warn!(%pattern, %error, "skipping invalid memory_scrub pattern");
+                    None
+                }
+            })
+            .collect();
+
+        Some(Self {
+            redact_emails: config.redact_emails,
+            redact_phone_numbers: config.redact_phone_numbers,
+            patterns,
+        })
+    }
+
+    /// Return `content` with every matched pattern replaced by a redaction marker.
+    pub fn scrub(&self, content: &str) -> String {
+        let mut scrubbed = content.to_string();
+        // Emails first: the phone pattern would otherwise eat digit runs
+        // inside addresses.
+        if self.redact_emails {
+            scrubbed = EMAIL_PATTERN
+                .replace_all(&scrubbed, "[REDACTED:email]")
+                .into_owned();
+        }
+        if self.redact_phone_numbers {
+            scrubbed = PHONE_PATTERN
+                .replace_all(&scrubbed, "[REDACTED:phone]")
+                .into_owned();
+        }
+        for pattern in &self.patterns {
+            scrubbed = pattern.replace_all(&scrubbed, "[REDACTED]").into_owned();
+        }
+        scrubbed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scrubber(patterns: &[&str]) -> MemoryScrubber {
+        MemoryScrubber::from_config(&MemoryScrubConfig {
+            enabled: true,
+            patterns: patterns.iter().map(|pattern| pattern.to_string()).collect(),
+            ..MemoryScrubConfig::default()
+        })
+        .expect("scrubbing enabled")
+    }
+
+    #[test]
+    fn disabled_config_builds_no_scrubber() {
+        assert!(MemoryScrubber::from_config(&MemoryScrubConfig::default()).is_none());
+    }
+
+    #[test]
+    fn redacts_emails_and_phone_numbers() {
+        let scrubbed = scrubber(&[])
+            .scrub("Reach Sam at sam.lee+work@example.co.uk, (555) 123-4567, or +44 20 7946 0958.");
+        assert_eq!(
+            scrubbed,
+            "Reach Sam at [REDACTED:email], [REDACTED:phone], or [REDACTED:phone]."
+        );
+    }
+
+    #[test]
+    fn leaves_dates_and_short_numbers_alone() {
+        let content = "Deployed v2.3 on 2026-01-15 after 42 retries.";
+        assert_eq!(scrubber(&[]).scrub(content), content);
+    }
+
+    #[test]
+    fn applies_custom_patterns() {
+        let scrubbed = scrubber(&[r"(?i)\bdarn\b", r"ACCT-\d+"])
+            .scrub("Darn, account ACCT-99812 is locked again.");
+        assert_eq!(scrubbed, "[REDACTED], account [REDACTED] is locked again.");
+    }
+
+    #[test]
+    fn respects_disabled_builtins() {
+        let scrubber = MemoryScrubber::from_config(&MemoryScrubConfig {
+            enabled: true,
+            redact_emails: false,
+            redact_phone_numbers: false,
+            patterns: Vec::new(),
+        })
+        .expect("scrubbing enabled");
+        let content = "sam@example.com 555-123-4567";
+        assert_eq!(scrubber.scrub(content), content);
+    }
+}
@@ -1,12 +1,18 @@
 //! Memory search: hybrid (vector + FTS + RRF + graph), temporal, importance, and typed queries.
 
+use crate::config::{EmbeddingConfig, MemoryDecayConfig, MemoryScrubConfig};
 use crate::error::Result;
+use crate::memory::embedding::SHARED_EMBED_QUEUE;
+use crate::memory::routing::{RoutedEmbedder, detect_content_type, merge_routed_matches};
 use crate::memory::types::{Memory, MemorySearchResult, MemoryType, RelationType};
-use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryStore};
+use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryScrubber, MemoryStore};
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Memories embedded per call when filling routed tables in bulk.
+const ROUTED_EMBED_BATCH_SIZE: usize = 64;
+
 /// Which search strategy to use.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SearchMode {
@@ -21,6 +27,17 @@ pub enum SearchMode {
     Typed,
 }
 
+impl SearchMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hybrid => "hybrid",
+            Self::Recent => "recent",
+            Self::Important => "important",
+            Self::Typed => "typed",
+        }
+    }
+}
+
 /// Sort order for non-hybrid search modes.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SearchSort {
@@ -38,6 +55,14 @@ pub struct MemorySearch {
     store: Arc<MemoryStore>,
     embedding_table: EmbeddingTable,
     embedding_model: Arc<EmbeddingModel>,
+    /// Queue this agent's embedding requests wait in on the shared model.
+    embedding_queue: Arc<str>,
+    embedding_config: EmbeddingConfig,
+    /// Extra embedders for routed content types. Empty unless the agent
+    /// configures embedding routes.
+    routed_embedders: Vec<RoutedEmbedder>,
+    decay_config: MemoryDecayConfig,
+    scrubber: Option<MemoryScrubber>,
 }
 
 impl Clone for MemorySearch {
@@ -46,6 +71,11 @@ impl Clone for MemorySearch {
             store: Arc::clone(&self.store),
             embedding_table: self.embedding_table.clone(),
             embedding_model: Arc::clone(&self.embedding_model),
+            embedding_queue: Arc::clone(&self.embedding_queue),
+            embedding_config: self.embedding_config.clone(),
+            routed_embedders: self.routed_embedders.clone(),
+            decay_config: self.decay_config,
+            scrubber: self.scrubber.clone(),
         }
     }
 }
@@ -54,6 +84,10 @@ impl std::fmt::Debug for MemorySearch {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MemorySearch")
             .field("store", &self.store)
+            .field("embedding_config", &self.embedding_config)
+            .field("routed_embedders", &self.routed_embedders)
+            .field("decay_config", &self.decay_config)
+            .field("scrubber", &self.scrubber)
             .finish_non_exhaustive()
     }
 }
@@ -69,6 +103,52 @@ impl MemorySearch {
             store,
             embedding_table,
             embedding_model,
+            embedding_queue: Arc::from(SHARED_EMBED_QUEUE),
+            embedding_config: EmbeddingConfig::default(),
+            routed_embedders: Vec::new(),
+            decay_config: MemoryDecayConfig::default(),
+            scrubber: None,
+        }
+    }
+
+    /// Queue embedding requests under `agent_id`, so the shared model serves
+    /// this agent fairly alongside the others.
+    pub fn with_agent_id(mut self, agent_id: &str) -> Self {
+        self.embedding_queue = Arc::from(agent_id);
+        self
+    }
+
+    /// Set the prefixes applied when embedding queries and memory content.
+    pub fn with_embedding_config(mut self, embedding_config: EmbeddingConfig) -> Self {
+        self.embedding_config = embedding_config;
+        self
+    }
+
+    /// Set the embedders for routed content types, opened with
+    /// [`crate::memory::routing::open_routed_embedders`].
+    pub fn with_routed_embedders(mut self, routed_embedders: Vec<RoutedEmbedder>) -> Self {
+        self.routed_embedders = routed_embedders;
+        self
+    }
+
+    /// Set the time decay applied to hybrid search scores.
+    pub fn with_decay_config(mut self, decay_config: MemoryDecayConfig) -> Self {
+        self.decay_config = decay_config;
+        self
+    }
+
+    /// Set the redaction applied to memory content before it is stored.
+    pub fn with_scrub_config(mut self, scrub_config: &MemoryScrubConfig) -> Self {
+        self.scrubber = MemoryScrubber::from_config(scrub_config);
+        self
+    }
+
+    /// Redact configured patterns from content about to be saved as a memory.
+    /// Returns the content unchanged when scrubbing is disabled.
+    pub fn scrub_content(&self, content: &str) -> String {
+        match &self.scrubber {
+            Some(scrubber) => scrubber.scrub(content),
+            None => content.to_string(),
         }
     }
 
@@ -77,6 +157,21 @@ impl MemorySearch {
         &self.store
     }
 
+    /// Count each result as an access, which refreshes it against time
+    /// decay. Call this for results that were actually recalled, not for
+    /// listings or previews. Failures are logged and skipped.
+    pub async fn record_recall(&self, results: &[MemorySearchResult]) {
+        for result in results {
+            if let Err(error) = self.store.record_access(&result.memory.id).await {
+                tracing::warn!(
+                    memory_id = %result.memory.id,
+                    %error,
+                    "failed to record memory access"
+                );
+            }
+        }
+    }
+
     /// Get a reference to the embedding table.
     pub fn embedding_table(&self) -> &EmbeddingTable {
         &self.embedding_table
@@ -92,6 +187,201 @@ impl MemorySearch {
         &self.embedding_model
     }
 
+    /// Get the queue this agent's embedding requests are batched under.
+    pub fn embedding_queue(&self) -> &str {
+        &self.embedding_queue
+    }
+
+    /// Get the embedding prefixes for this agent.
+    pub fn embedding_config(&self) -> &EmbeddingConfig {
+        &self.embedding_config
+    }
+
+    /// Embed a search query, with the query prefix applied.
+    pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        self.embedding_model
+            .embed_one_for(
+                &self.embedding_queue,
+                &self.embedding_config.query_text(query),
+            )
+            .await
+    }
+
+    /// Embed memory content for storage, with the passage prefix applied.
+    pub async fn embed_passage(&self, content: &str) -> Result<Vec<f32>> {
+        self.embedding_model
+            .embed_one_for(
+                &self.embedding_queue,
+                &self.embedding_config.passage_text(content),
+            )
+            .await
+    }
+
+    /// Embed a batch of memory contents for storage, with the passage prefix
+    /// applied to each.
+    pub async fn embed_passages(&self, contents: &[String]) -> Result<Vec<Vec<f32>>> {
+        let texts = contents
+            .iter()
+            .map(|content| self.embedding_config.passage_text(content))
+            .collect();
+        self.embedding_model
+            .embed_batch_for(&self.embedding_queue, texts)
+            .await
+    }
+
+    /// The routed embedder for `text`'s detected content type, if any.
+    fn routed_embedder_for(&self, text: &str) -> Option<&RoutedEmbedder> {
+        if self.routed_embedders.is_empty() {
+            return None;
+        }
+        let content_type = detect_content_type(text);
+        self.routed_embedders
+            .iter()
+            .find(|embedder| embedder.route.content_type == content_type)
+    }
+
+    /// Embed memory content with the model routed to its content type and
+    /// store the vector in that model's table. Does nothing when no route
+    /// matches. The default vector is stored separately, in
+    /// [`MemorySearch::embedding_table`].
+    pub async fn store_routed_embedding(&self, memory_id: &str, content: &str) -> Result<()> {
+        let Some(embedder) = self.routed_embedder_for(content) else {
+            return Ok(());
+        };
+        let embedding = embedder
+            .model
+            .embed_one_for(&self.embedding_queue, &embedder.route.passage_text(content))
+            .await?;
+        embedder.table.store(memory_id, content, &embedding).await
+    }
+
+    /// Embed `(memory_id, content)` pairs with their routed models and store
+    /// the vectors, batched per route. Pairs no route matches are skipped.
+    /// Returns how many vectors were stored.
+    pub async fn store_routed_embeddings(&self, memories: &[(&str, &str)]) -> Result<usize> {
+        let mut stored = 0;
+        for embedder in &self.routed_embedders {
+            stored += self.store_routed_batch(embedder, memories).await?;
+        }
+        Ok(stored)
+    }
+
+    /// Embed existing memories into routed tables that are still empty, so a
+    /// newly configured route also covers memories saved before it existed.
+    /// Returns how many vectors were stored.
+    pub async fn backfill_routed_embeddings(&self) -> Result<usize> {
+        let mut empty_embedders = Vec::new();
+        for embedder in &self.routed_embedders {
+            if embedder.table.is_empty().await? {
+                empty_embedders.push(embedder);
+            }
+        }
+        if empty_embedders.is_empty() {
+            return Ok(0);
+        }
+
+        let memories = self.store.get_all().await?;
+        let pairs: Vec<(&str, &str)> = memories
+            .iter()
+            .filter(|memory| !memory.forgotten)
+            .map(|memory| (memory.id.as_str(), memory.content.as_str()))
+            .collect();
+
+        let mut stored = 0;
+        for embedder in empty_embedders {
+            stored += self.store_routed_batch(embedder, &pairs).await?;
+        }
+        Ok(stored)
+    }
+
+    /// Store vectors for the pairs whose content routes to `embedder`.
+    async fn store_routed_batch(
+        &self,
+        embedder: &RoutedEmbedder,
+        memories: &[(&str, &str)],
+    ) -> Result<usize> {
+        let routed: Vec<(&str, &str)> = memories
+            .iter()
+            .copied()
+            .filter(|(_, content)| detect_content_type(content) == embedder.route.content_type)
+            .collect();
+
+        for chunk in routed.chunks(ROUTED_EMBED_BATCH_SIZE) {
+            let texts = chunk
+                .iter()
+                .map(|(_, content)| embedder.route.passage_text(content))
+                .collect();
+            let embeddings = embedder
+                .model
+                .embed_batch_for(&self.embedding_queue, texts)
+                .await?;
+            let rows: Vec<(&str, &str, &[f32])> = chunk
+                .iter()
+                .zip(&embeddings)
+                .map(|((memory_id, content), embedding)| {
+                    (*memory_id, *content, embedding.as_slice())
+                })
+                .collect();
+            embedder.table.store_many(&rows).await?;
+        }
+        Ok(routed.len())
+    }
+
+    /// Remove every vector from every routed table.
+    pub async fn clear_routed_embeddings(&self) -> Result<()> {
+        for embedder in &self.routed_embedders {
+            embedder.table.delete_all().await?;
+        }
+        Ok(())
+    }
+
+    /// Remove several memories' vectors from every routed table.
+    pub async fn delete_routed_embeddings_many(&self, memory_ids: &[String]) -> Result<()> {
+        for embedder in &self.routed_embedders {
+            embedder.table.delete_many(memory_ids).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove a memory's vectors from every routed table. Failures are logged,
+    /// not returned, like the default embedding cleanup in [`Self::purge`].
+    pub async fn delete_routed_embeddings(&self, memory_id: &str) {
+        for embedder in &self.routed_embedders {
+            if let Err(error) = embedder.table.delete(memory_id).await {
+                tracing::warn!(
+                    %error,
+                    memory_id,
+                    model = %embedder.route.model,
+                    "failed to delete routed embedding"
+                );
+            }
+        }
+    }
+
+    /// Permanently remove a memory, its graph edges, and its embedding.
+    ///
+    /// Unlike `MemoryStore::forget`, nothing is left behind. Used for explicit
+    /// user deletion requests where a soft-delete isn't enough. Returns false
+    /// if no memory exists with this ID.
+    pub async fn purge(&self, id: &str) -> Result<bool> {
+        if self.store.load(id).await?.is_none() {
+            return Ok(false);
+        }
+
+        let removed_edges = self.store.delete_associations_for_memory(id).await?;
+        self.store.delete(id).await?;
+
+        // The SQLite row is the source of truth. A stale embedding can't
+        // surface on its own because search results are joined back to SQLite.
+        if let Err(error) = self.embedding_table.delete(id).await {
+            tracing::warn!(%error, memory_id = %id, "failed to delete embedding for purged memory");
+        }
+        self.delete_routed_embeddings(id).await;
+
+        tracing::info!(memory_id = %id, removed_edges, "memory purged");
+        Ok(true)
+    }
+
     /// Unified search entry point. Dispatches to the appropriate strategy
     /// based on `config.mode`.
     pub async fn search(
@@ -99,6 +389,12 @@ impl MemorySearch {
         query: &str,
         config: &SearchConfig,
     ) -> Result<Vec<MemorySearchResult>> {
+        #[cfg(feature = "metrics")]
+        let recall_timer = crate::telemetry::Metrics::global()
+            .memory_recall_duration_seconds
+            .with_label_values(&[self.metrics_agent_label(), config.mode.as_str()])
+            .start_timer();
+
         let results = match config.mode {
             SearchMode::Hybrid => self.hybrid_search(query, config).await,
             SearchMode::Recent => self.metadata_search(SearchSort::Recent, config).await,
@@ -108,21 +404,43 @@ impl MemorySearch {
 
         #[cfg(feature = "metrics")]
         {
-            let agent_id = self.store.agent_id();
-            let agent_label = if agent_id.is_empty() {
-                "unknown"
-            } else {
-                agent_id
-            };
-            crate::telemetry::Metrics::global()
+            recall_timer.observe_duration();
+            let metrics = crate::telemetry::Metrics::global();
+            let agent_label = self.metrics_agent_label();
+            metrics
                 .memory_search_results
                 .with_label_values(&[agent_label])
                 .observe(results.len() as f64);
+            if results.is_empty() {
+                metrics
+                    .memory_recall_empty_total
+                    .with_label_values(&[agent_label, config.mode.as_str()])
+                    .inc();
+            }
         }
 
         Ok(results)
     }
 
+    #[cfg(feature = "metrics")]
+    fn metrics_agent_label(&self) -> &str {
+        let agent_id = self.store.agent_id();
+        if agent_id.is_empty() {
+            "unknown"
+        } else {
+            agent_id
+        }
+    }
+
+    /// Timer for one stage of a hybrid recall. Observes when dropped.
+    #[cfg(feature = "metrics")]
+    fn recall_stage_timer(&self, stage: &str) -> prometheus::HistogramTimer {
+        crate::telemetry::Metrics::global()
+            .memory_recall_stage_duration_seconds
+            .with_label_values(&[self.metrics_agent_label(), stage])
+            .start_timer()
+    }
+
     /// Metadata-based search: queries SQLite directly, no vector/FTS/RRF.
     /// Used by Recent, Important, and Typed modes.
     async fn metadata_search(
@@ -172,11 +490,15 @@ impl MemorySearch {
         // 1. Full-text search via LanceDB
         // FTS requires an inverted index. If the index doesn't exist yet (empty
         // table, first run) this will fail — fall back to vector + graph search.
-        match self
+        #[cfg(feature = "metrics")]
+        let stage_timer = self.recall_stage_timer("text_search");
+        let fts_matches = self
             .embedding_table
             .text_search(query, config.max_results_per_source)
-            .await
-        {
+            .await;
+        #[cfg(feature = "metrics")]
+        drop(stage_timer);
+        match fts_matches {
             Ok(fts_matches) => {
                 for (memory_id, score) in fts_matches {
                     if let Some(memory) = self.store.load(&memory_id).await?
@@ -194,13 +516,46 @@ impl MemorySearch {
             }
         }
 
-        // 2. Vector similarity search via LanceDB
-        let query_embedding = self.embedding_model.embed_one(query).await?;
-        match self
+        // 2. Vector similarity search via LanceDB. Embedding the query is
+        // timed apart from the search so a slow embedder can't hide in it.
+        #[cfg(feature = "metrics")]
+        let stage_timer = self.recall_stage_timer("embedding");
+        let query_embedding = self.embed_query(query).await?;
+        #[cfg(feature = "metrics")]
+        drop(stage_timer);
+
+        #[cfg(feature = "metrics")]
+        let stage_timer = self.recall_stage_timer("vector_search");
+        let mut vector_matches = self
             .embedding_table
             .vector_search(&query_embedding, config.max_results_per_source)
-            .await
-        {
+            .await;
+        #[cfg(feature = "metrics")]
+        drop(stage_timer);
+
+        // 2b. A query of a routed content type also searches the routed
+        // model's vectors, and those matches rank first.
+        if let Some(embedder) = self.routed_embedder_for(query) {
+            match self
+                .routed_vector_search(embedder, query, config.max_results_per_source)
+                .await
+            {
+                Ok(routed_matches) => {
+                    vector_matches = Ok(merge_routed_matches(
+                        routed_matches,
+                        vector_matches.unwrap_or_default(),
+                    ));
+                }
+                Err(error) => {
+                    tracing::debug!(
+                        %error,
+                        model = %embedder.route.model,
+                        "routed vector search unavailable, using the default model only"
+                    );
+                }
+            }
+        }
+        match vector_matches {
             Ok(vector_matches) => {
                 for (memory_id, distance) in vector_matches {
                     let similarity = 1.0 - distance;
@@ -221,6 +576,8 @@ impl MemorySearch {
 
         // 3. Graph traversal from high-importance memories
         // Get identity and high-importance memories as starting points
+        #[cfg(feature = "metrics")]
+        let stage_timer = self.recall_stage_timer("graph");
         let seed_memories = self.store.get_high_importance(0.8, 20).await?;
 
         for seed in seed_memories {
@@ -241,10 +598,16 @@ impl MemorySearch {
             }
         }
 
+        #[cfg(feature = "metrics")]
+        drop(stage_timer);
+
         // 4. Merge results using Reciprocal Rank Fusion (RRF)
-        let fused_results =
+        let mut fused_results =
             reciprocal_rank_fusion(&vector_results, &fts_results, &graph_results, config.rrf_k);
 
+        // 5. Rank recently reinforced memories above equally relevant stale ones
+        apply_time_decay(&mut fused_results, &self.decay_config, chrono::Utc::now());
+
         // Convert to MemorySearchResult with ranks, applying optional type filter
         let results: Vec<MemorySearchResult> = fused_results
             .into_iter()
@@ -266,6 +629,27 @@ impl MemorySearch {
         Ok(results)
     }
 
+    /// Embed `query` with a routed model and search that model's vectors.
+    async fn routed_vector_search(
+        &self,
+        embedder: &RoutedEmbedder,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, f32)>> {
+        #[cfg(feature = "metrics")]
+        let stage_timer = self.recall_stage_timer("embedding");
+        let query_embedding = embedder
+            .model
+            .embed_one_for(&self.embedding_queue, &embedder.route.query_text(query))
+            .await?;
+        #[cfg(feature = "metrics")]
+        drop(stage_timer);
+
+        #[cfg(feature = "metrics")]
+        let _stage_timer = self.recall_stage_timer("vector_search");
+        embedder.table.vector_search(&query_embedding, limit).await
+    }
+
     /// Traverse the memory graph to find related memories (iterative to avoid async recursion).
     async fn traverse_graph(
         &self,
@@ -431,6 +815,27 @@ fn reciprocal_rank_fusion(
     fused
 }
 
+/// Scale fused scores by how long ago each memory was last reinforced, then
+/// re-sort. Identity memories keep their score.
+fn apply_time_decay(
+    results: &mut [ScoredMemory],
+    decay: &MemoryDecayConfig,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    if decay.half_life_days <= 0.0 {
+        return;
+    }
+
+    for scored in results.iter_mut() {
+        if scored.memory.memory_type == MemoryType::Identity {
+            continue;
+        }
+        scored.score *= decay.weight(now - scored.memory.last_reinforced_at());
+    }
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
+
 /// Curate search results to return only the most relevant.
 pub fn curate_results(
     results: &[MemorySearchResult],
@@ -488,6 +893,63 @@ mod tests {
         assert!(fused[0].score > fused[1].score);
     }
 
+    #[test]
+    fn test_time_decay_ranks_newer_of_equally_similar_memories_first() {
+        let now = Utc::now();
+        let mut stale = Memory::new("the office is on 5th street", MemoryType::Fact);
+        stale.id = "stale".into();
+        stale.updated_at = now - Duration::days(200);
+        stale.last_accessed_at = now - Duration::days(200);
+        let mut fresh = Memory::new("the office is on 9th street", MemoryType::Fact);
+        fresh.id = "fresh".into();
+        fresh.updated_at = now - Duration::days(2);
+        fresh.last_accessed_at = now - Duration::days(2);
+
+        // Equal similarity, with the stale memory ahead before decay.
+        let mut fused = vec![
+            ScoredMemory {
+                memory: stale,
+                score: 0.8,
+            },
+            ScoredMemory {
+                memory: fresh,
+                score: 0.8,
+            },
+        ];
+
+        let decay = MemoryDecayConfig {
+            half_life_days: 30.0,
+            min_weight: 0.0,
+        };
+        apply_time_decay(&mut fused, &decay, now);
+
+        assert_eq!(fused[0].memory.id, "fresh");
+        assert_eq!(fused[1].memory.id, "stale");
+        assert!(fused[0].score > fused[1].score);
+    }
+
+    #[test]
+    fn test_time_decay_disabled_and_identity_untouched() {
+        let now = Utc::now();
+        let mut identity = Memory::new("my name is Ada", MemoryType::Identity);
+        identity.updated_at = now - Duration::days(400);
+        identity.last_accessed_at = now - Duration::days(400);
+        let mut results = vec![ScoredMemory {
+            memory: identity,
+            score: 0.5,
+        }];
+
+        apply_time_decay(&mut results, &MemoryDecayConfig::default(), now);
+        assert_eq!(results[0].score, 0.5);
+
+        let decay = MemoryDecayConfig {
+            half_life_days: 7.0,
+            min_weight: 0.0,
+        };
+        apply_time_decay(&mut results, &decay, now);
+        assert_eq!(results[0].score, 0.5);
+    }
+
     #[test]
     fn test_rrf_empty_lists() {
         let fused = reciprocal_rank_fusion(&[], &[], &[], 60.0);
@@ -671,4 +1133,129 @@ mod tests {
         let results = search.search("", &config).await.unwrap();
         assert!(results.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_query_and_passage_embeddings_use_their_own_prefix() {
+        let store = MemoryStore::connect_in_memory().await;
+        let lance_dir = tempfile::tempdir().unwrap();
+        let lance_conn = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
+        let search = MemorySearch::new(store, embedding_table, embedding_model.clone())
+            .with_embedding_config(EmbeddingConfig {
+                query_prefix: "query: ".into(),
+                passage_prefix: "passage: ".into(),
+                ..EmbeddingConfig::default()
+            });
+
+        let text = "the deploy runs every friday";
+        let query_embedding = search.embed_query(text).await.unwrap();
+        let passage_embedding = search.embed_passage(text).await.unwrap();
+
+        assert_eq!(
+            query_embedding,
+            embedding_model
+                .embed_one("query: the deploy runs every friday")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            passage_embedding,
+            embedding_model
+                .embed_one("passage: the deploy runs every friday")
+                .await
+                .unwrap()
+        );
+        assert_ne!(query_embedding, passage_embedding);
+    }
+
+    #[tokio::test]
+    async fn purge_removes_memory_edges_and_embedding() {
+        let store = MemoryStore::connect_in_memory().await;
+        let lance_dir = tempfile::tempdir().unwrap();
+        let lance_conn = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
+        let search = MemorySearch::new(store.clone(), embedding_table, embedding_model);
+
+        let forgotten = Memory::new("my home address is 12 Elm St", MemoryType::Fact);
+        let kept = Memory::new("prefers email over calls", MemoryType::Preference);
+        for memory in [&forgotten, &kept] {
+            store.save(memory).await.unwrap();
+            let embedding = vec![0.1; crate::memory::lance::EMBEDDING_DIM as usize];
+            search
+                .embedding_table()
+                .store(&memory.id, &memory.content, &embedding)
+                .await
+                .unwrap();
+        }
+        store
+            .create_association(&crate::memory::types::Association::new(
+                &forgotten.id,
+                &kept.id,
+                RelationType::RelatedTo,
+            ))
+            .await
+            .unwrap();
+
+        assert!(search.purge(&forgotten.id).await.unwrap());
+
+        assert!(store.load(&forgotten.id).await.unwrap().is_none());
+        assert!(store.get_associations(&kept.id).await.unwrap().is_empty());
+        let embeddings = search.embedding_table().all_embeddings().await.unwrap();
+        assert!(!embeddings.contains_key(&forgotten.id));
+        assert!(embeddings.contains_key(&kept.id));
+        assert!(store.load(&kept.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn purge_rejects_unknown_ids() {
+        let (store, memories) = setup_search_with_memories().await;
+        let lance_dir = tempfile::tempdir().unwrap();
+        let lance_conn = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
+        let search = MemorySearch::new(store.clone(), embedding_table, embedding_model);
+
+        assert!(!search.purge("no-such-memory").await.unwrap());
+        assert_eq!(store.get_all().await.unwrap().len(), memories.len());
+    }
+
+    #[tokio::test]
+    async fn recalled_memories_count_as_reinforced() {
+        let (store, memories) = setup_search_with_memories().await;
+        let lance_dir = tempfile::tempdir().unwrap();
+        let lance_conn = lancedb::connect(lance_dir.path().to_str().unwrap())
+            .execute()
+            .await
+            .unwrap();
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn).await.unwrap();
+        let embedding_model = Arc::new(EmbeddingModel::new(lance_dir.path()).unwrap());
+        let search = MemorySearch::new(store.clone(), embedding_table, embedding_model);
+
+        let recalled = store.load(&memories[0].id).await.unwrap().unwrap();
+        let before = recalled.last_reinforced_at();
+        search
+            .record_recall(&[MemorySearchResult {
+                memory: recalled,
+                score: 1.0,
+                rank: 1,
+            }])
+            .await;
+
+        let reloaded = store.load(&memories[0].id).await.unwrap().unwrap();
+        assert_eq!(reloaded.access_count, 1);
+        assert!(reloaded.last_reinforced_at() > before);
+        let untouched = store.load(&memories[1].id).await.unwrap().unwrap();
+        assert_eq!(untouched.access_count, 0);
+    }
 }
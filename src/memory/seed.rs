@@ -0,0 +1,226 @@
+//! One-time memory seeding from `seed_memories.jsonl` in an agent workspace.
+//!
+//! Unlike the ingest directory, the seed file is never consumed or deleted.
+//! A marker in the data dir records that seeding ran, so the file is imported
+//! once and restarts don't duplicate it. A progress file records each entry
+//! as it is saved, so an import interrupted partway resumes where it stopped.
+
+use crate::error::Result;
+use crate::memory::{Memory, MemorySearch, MemoryType};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+use sha2::{Digest as _, Sha256};
+
+use std::path::Path;
+
+/// Seed file looked up in the agent workspace.
+pub const SEED_FILE_NAME: &str = "seed_memories.jsonl";
+
+/// Marker written to the data dir once the seed file has been imported.
+pub const SEED_MARKER_FILE_NAME: &str = "seed_memories.done";
+
+/// Progress of an unfinished import: a digest of the seed file and the
+/// number of entries saved so far. Removed once the marker is written.
+pub const SEED_PROGRESS_FILE_NAME: &str = "seed_memories.progress";
+
+/// Source recorded on seeded memories.
+const SEED_MEMORY_SOURCE: &str = "seed";
+
+/// One line of the seed file.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct SeedEntry {
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl SeedEntry {
+    /// Memory content for the entry. Memories have no tag field, so tags are
+    /// appended to the content where recall can match them.
+    fn content(&self) -> String {
+        let text = self.text.trim();
+        let tags: Vec<&str> = self
+            .tags
+            .iter()
+            .map(|tag| tag.trim())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.is_empty() {
+            text.to_string()
+        } else {
+            format!("{text}\n\nTags: {}", tags.join(", "))
+        }
+    }
+}
+
+/// Import `seed_memories.jsonl` from `workspace` unless the marker in
+/// `data_dir` says it was already imported. Returns the number of memories
+/// created.
+///
+/// Every line is parsed and embedded before anything is written, so a bad
+/// line or an embedding failure leaves the store untouched and the import is
+/// retried on the next start. Progress is recorded after each saved entry,
+/// so a failure partway through resumes after the last saved entry instead
+/// of importing the earlier ones again. If the seed file changed since the
+/// interrupted import, it starts over.
+pub async fn seed_memories_once(
+    memory_search: &MemorySearch,
+    workspace: &Path,
+    data_dir: &Path,
+) -> Result<usize> {
+    let marker_path = data_dir.join(SEED_MARKER_FILE_NAME);
+    let seed_path = workspace.join(SEED_FILE_NAME);
+    if marker_path.exists() || !seed_path.exists() {
+        return Ok(0);
+    }
+
+    let content = tokio::fs::read_to_string(&seed_path)
+        .await
+        .with_context(|| format!("failed to read {}", seed_path.display()))?;
+    let entries = parse_seed_file(&content)?;
+    let digest = hex::encode(Sha256::digest(content.as_bytes()));
+    let progress_path = data_dir.join(SEED_PROGRESS_FILE_NAME);
+    let already_seeded = read_progress(&progress_path, &digest)
+        .await
+        .min(entries.len());
+
+    let memories: Vec<Memory> = entries[already_seeded..]
+        .iter()
+        .map(|entry| {
+            Memory::new(
+                memory_search.scrub_content(&entry.content()),
+                MemoryType::Fact,
+            )
+            .with_source(SEED_MEMORY_SOURCE)
+        })
+        .collect();
+    let contents: Vec<String> = memories
+        .iter()
+        .map(|memory| memory.content.clone())
+        .collect();
+    let embeddings = memory_search.embed_passages(&contents).await?;
+
+    let store = memory_search.store();
+    let embedding_table = memory_search.embedding_table();
+    for (index, (memory, embedding)) in memories.iter().zip(&embeddings).enumerate() {
+        store.save(memory).await?;
+        embedding_table
+            .store(&memory.id, &memory.content, embedding)
+            .await?;
+        if let Err(error) = memory_search
+            .store_routed_embedding(&memory.id, &memory.content)
+            .await
+        {
+            tracing::warn!(%error, memory_id = %memory.id, "failed to store routed embedding for seed memory");
+        }
+        let progress = format!("{digest}\n{}\n", already_seeded + index + 1);
+        tokio::fs::write(&progress_path, progress)
+            .await
+            .with_context(|| format!("failed to write {}", progress_path.display()))?;
+    }
+    if !memories.is_empty()
+        && let Err(error) = embedding_table.ensure_fts_index().await
+    {
+        tracing::warn!(%error, "failed to ensure FTS index after seeding memories");
+    }
+
+    let marker = format!(
+        "seeded {} memories from {SEED_FILE_NAME} at {}\n",
+        entries.len(),
+        chrono::Utc::now().to_rfc3339()
+    );
+    tokio::fs::write(&marker_path, marker)
+        .await
+        .with_context(|| format!("failed to write {}", marker_path.display()))?;
+    if let Err(error) = tokio::fs::remove_file(&progress_path).await
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!(%error, path = %progress_path.display(), "failed to remove seed progress file");
+    }
+
+    Ok(memories.len())
+}
+
+/// Entries already saved by an interrupted import of the seed file with
+/// `digest`. A missing or unreadable progress file, or one for a different
+/// version of the seed file, counts as none.
+async fn read_progress(progress_path: &Path, digest: &str) -> usize {
+    let Ok(progress) = tokio::fs::read_to_string(progress_path).await else {
+        return 0;
+    };
+    parse_progress(&progress, digest)
+}
+
+fn parse_progress(progress: &str, digest: &str) -> usize {
+    let mut lines = progress.lines();
+    if lines.next() != Some(digest) {
+        return 0;
+    }
+    lines
+        .next()
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Parse the seed file. Blank lines are skipped; any other malformed line
+/// fails the whole file so a typo can't silently drop facts.
+fn parse_seed_file(content: &str) -> Result<Vec<SeedEntry>> {
+    let mut entries = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let entry: SeedEntry = serde_json::from_str(line)
+            .with_context(|| format!("{SEED_FILE_NAME} line {}: invalid entry", index + 1))?;
+        if entry.text.trim().is_empty() {
+            return Err(
+                anyhow::anyhow!("{SEED_FILE_NAME} line {}: text is empty", index + 1).into(),
+            );
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_text_and_optional_tags() {
+        let content = r#"
+{"text": "The office is in Lisbon."}
+
+{"text": "Deploys happen on Tuesdays.", "tags": ["ops", " release "]}
+"#;
+        let entries = parse_seed_file(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].content(), "The office is in Lisbon.");
+        assert_eq!(
+            entries[1].content(),
+            "Deploys happen on Tuesdays.\n\nTags: ops, release"
+        );
+    }
+
+    #[test]
+    fn malformed_lines_fail_the_whole_file() {
+        let error = parse_seed_file("{\"text\": \"ok\"}\nnot json\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+
+        let error = parse_seed_file("{\"text\": \"  \"}").unwrap_err();
+        assert!(error.to_string().contains("text is empty"), "{error}");
+
+        assert!(parse_seed_file("{\"text\": \"ok\", \"tag\": \"typo\"}").is_err());
+    }
+
+    #[test]
+    fn progress_only_counts_for_the_same_seed_file() {
+        assert_eq!(parse_progress("abc123\n4\n", "abc123"), 4);
+        assert_eq!(parse_progress("abc123\n4\n", "def456"), 0);
+        assert_eq!(parse_progress("abc123\n", "abc123"), 0);
+        assert_eq!(parse_progress("", "abc123"), 0);
+    }
+}
@@ -0,0 +1,331 @@
+//! Portable snapshots of an agent's full memory store, for backup and for
+//! moving an agent between instances.
+//!
+//! A snapshot carries every memory row (forgotten ones included), every graph
+//! edge, and the stored vectors, along with a description of how the vectors
+//! were produced. Restoring reuses the vectors when the target embeds the same
+//! way and re-embeds the content otherwise, so a snapshot stays usable across
+//! embedding changes.
+
+use crate::config::EmbeddingConfig;
+use crate::error::Result;
+use crate::memory::embedding::EMBEDDING_MODEL_NAME;
+use crate::memory::lance::EMBEDDING_DIM;
+use crate::memory::{Association, Memory, MemorySearch};
+
+use anyhow::Context as _;
+use base64::Engine as _;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read as _, Write as _};
+
+/// Snapshot layout version. Bumped on incompatible changes.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Upper bound on the decompressed size of a snapshot.
+const MAX_SNAPSHOT_UNPACKED_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// How a snapshot's vectors were produced. Vectors are only reused on restore
+/// when this matches the target exactly.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EmbeddingSignature {
+    pub model: String,
+    pub dimension: usize,
+    /// Prefix prepended to memory content before embedding.
+    pub passage_prefix: String,
+}
+
+impl EmbeddingSignature {
+    /// The signature of vectors produced with `config` on this build.
+    pub fn current(config: &EmbeddingConfig) -> Self {
+        Self {
+            model: EMBEDDING_MODEL_NAME.to_string(),
+            dimension: EMBEDDING_DIM as usize,
+            passage_prefix: config.passage_prefix.clone(),
+        }
+    }
+}
+
+/// An agent's complete memory store.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub format_version: u32,
+    pub source_agent_id: String,
+    pub spacebot_version: String,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub embedding: EmbeddingSignature,
+    pub memories: Vec<Memory>,
+    pub associations: Vec<Association>,
+    /// Stored vectors keyed by memory ID, as base64 little-endian `f32`s.
+    /// Memories without an entry are embedded on restore.
+    pub embeddings: BTreeMap<String, String>,
+}
+
+/// What a restore wrote.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RestoreSummary {
+    pub memories: usize,
+    pub associations: usize,
+    /// Associations dropped because an endpoint was missing from the snapshot.
+    pub skipped_associations: usize,
+    pub reused_embeddings: usize,
+    pub reembedded: usize,
+}
+
+/// Capture the full memory store behind `memory_search`.
+pub async fn export_snapshot(
+    memory_search: &MemorySearch,
+    agent_id: &str,
+) -> Result<MemorySnapshot> {
+    let store = memory_search.store();
+    let memories = store.get_all().await?;
+    let associations = store.get_all_associations().await?;
+
+    let memory_ids: HashSet<&str> = memories.iter().map(|memory| memory.id.as_str()).collect();
+    let embeddings = memory_search
+        .embedding_table()
+        .all_embeddings()
+        .await?
+        .into_iter()
+        // Embeddings left behind by deleted memories are not worth carrying.
+        .filter(|(memory_id, _)| memory_ids.contains(memory_id.as_str()))
+        .map(|(memory_id, vector)| (memory_id, encode_vector(&vector)))
+        .collect();
+
+    Ok(MemorySnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        source_agent_id: agent_id.to_string(),
+        spacebot_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now(),
+        embedding: EmbeddingSignature::current(memory_search.embedding_config()),
+        memories,
+        associations,
+        embeddings,
+    })
+}
+
+/// Write `snapshot` into the memory store behind `memory_search`.
+///
+/// Memories and associations are upserted by ID; with `replace`, the existing
+/// store and embeddings are wiped first. Any content that needs embedding is
+/// embedded before anything is written, so an embedding failure leaves the
+/// store untouched.
+pub async fn restore_snapshot(
+    memory_search: &MemorySearch,
+    snapshot: MemorySnapshot,
+    replace: bool,
+) -> Result<RestoreSummary> {
+    if snapshot.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(anyhow::anyhow!(
+            "snapshot format version {} is newer than supported version {SNAPSHOT_FORMAT_VERSION}",
+            snapshot.format_version
+        )
+        .into());
+    }
+    for memory in &snapshot.memories {
+        // IDs end up in LanceDB predicates, which only accept UUIDs.
+        uuid::Uuid::parse_str(&memory.id)
+            .with_context(|| format!("snapshot memory ID '{}' is not a UUID", memory.id))?;
+    }
+
+    let memory_ids: HashSet<&str> = snapshot
+        .memories
+        .iter()
+        .map(|memory| memory.id.as_str())
+        .collect();
+    let (associations, skipped): (Vec<Association>, Vec<Association>) =
+        snapshot.associations.into_iter().partition(|association| {
+            memory_ids.contains(association.source_id.as_str())
+                && memory_ids.contains(association.target_id.as_str())
+        });
+
+    let signature_matches =
+        snapshot.embedding == EmbeddingSignature::current(memory_search.embedding_config());
+    let mut vectors: Vec<Option<Vec<f32>>> = snapshot
+        .memories
+        .iter()
+        .map(|memory| {
+            snapshot
+                .embeddings
+                .get(&memory.id)
+                .filter(|_| signature_matches)
+                .and_then(|encoded| decode_vector(encoded))
+                .filter(|vector| vector.len() == EMBEDDING_DIM as usize)
+        })
+        .collect();
+
+    let missing: Vec<usize> = (0..vectors.len())
+        .filter(|&index| vectors[index].is_none())
+        .collect();
+    let contents: Vec<String> = missing
+        .iter()
+        .map(|&index| snapshot.memories[index].content.clone())
+        .collect();
+    let embedded = memory_search.embed_passages(&contents).await?;
+    for (index, vector) in missing.iter().zip(embedded) {
+        vectors[*index] = Some(vector);
+    }
+
+    let store = memory_search.store();
+    store
+        .restore(&snapshot.memories, &associations, replace)
+        .await?;
+
+    let embedding_table = memory_search.embedding_table();
+    if replace {
+        embedding_table.delete_all().await?;
+        memory_search.clear_routed_embeddings().await?;
+    } else {
+        let ids: Vec<String> = snapshot
+            .memories
+            .iter()
+            .map(|memory| memory.id.clone())
+            .collect();
+        embedding_table.delete_many(&ids).await?;
+        memory_search.delete_routed_embeddings_many(&ids).await?;
+    }
+    let rows: Vec<(&str, &str, &[f32])> = snapshot
+        .memories
+        .iter()
+        .zip(&vectors)
+        .filter_map(|(memory, vector)| {
+            vector
+                .as_deref()
+                .map(|vector| (memory.id.as_str(), memory.content.as_str(), vector))
+        })
+        .collect();
+    for chunk in rows.chunks(1000) {
+        embedding_table.store_many(chunk).await?;
+    }
+    if !rows.is_empty()
+        && let Err(error) = embedding_table.ensure_fts_index().await
+    {
+        tracing::warn!(%error, "failed to ensure FTS index after restoring memories");
+    }
+
+    // Snapshots only carry default-model vectors, so routed vectors are
+    // always rebuilt from the restored content.
+    let routed_pairs: Vec<(&str, &str)> = snapshot
+        .memories
+        .iter()
+        .map(|memory| (memory.id.as_str(), memory.content.as_str()))
+        .collect();
+    memory_search.store_routed_embeddings(&routed_pairs).await?;
+
+    if !skipped.is_empty() {
+        tracing::warn!(
+            count = skipped.len(),
+            "skipped snapshot associations with missing endpoints"
+        );
+    }
+
+    Ok(RestoreSummary {
+        memories: snapshot.memories.len(),
+        associations: associations.len(),
+        skipped_associations: skipped.len(),
+        reused_embeddings: snapshot.memories.len() - missing.len(),
+        reembedded: missing.len(),
+    })
+}
+
+/// Serialize a snapshot as gzipped JSON.
+pub fn encode_snapshot(snapshot: &MemorySnapshot) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, snapshot).context("failed to serialize snapshot")?;
+    encoder.flush().context("failed to compress snapshot")?;
+    Ok(encoder.finish().context("failed to compress snapshot")?)
+}
+
+/// Parse a gzipped JSON snapshot.
+pub fn decode_snapshot(bytes: &[u8]) -> Result<MemorySnapshot> {
+    let mut json = Vec::new();
+    GzDecoder::new(bytes)
+        .take(MAX_SNAPSHOT_UNPACKED_BYTES + 1)
+        .read_to_end(&mut json)
+        .context("snapshot is not valid gzip")?;
+    if json.len() as u64 > MAX_SNAPSHOT_UNPACKED_BYTES {
+        return Err(anyhow::anyhow!(
+            "snapshot exceeds {} MiB unpacked",
+            MAX_SNAPSHOT_UNPACKED_BYTES / (1024 * 1024)
+        )
+        .into());
+    }
+    Ok(serde_json::from_slice(&json).context("snapshot is not a valid memory snapshot")?)
+}
+
+fn encode_vector(vector: &[f32]) -> String {
+    let bytes: Vec<u8> = vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn decode_vector(encoded: &str) -> Option<Vec<f32>> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()?;
+    if bytes.len() % 4 != 0 {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryType, RelationType};
+
+    #[test]
+    fn vectors_round_trip() {
+        let vector = vec![0.25, -1.5, f32::MIN_POSITIVE, 3.0e7];
+        assert_eq!(decode_vector(&encode_vector(&vector)), Some(vector));
+        assert_eq!(decode_vector("AAA="), None);
+        assert_eq!(decode_vector("not base64!"), None);
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_gzip() {
+        let first = Memory::new("likes tea", MemoryType::Preference);
+        let second = Memory::new("drinks it at 4pm", MemoryType::Fact);
+        let snapshot = MemorySnapshot {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            source_agent_id: "main".to_string(),
+            spacebot_version: "0.0.0".to_string(),
+            exported_at: chrono::Utc::now(),
+            embedding: EmbeddingSignature::current(&EmbeddingConfig::default()),
+            associations: vec![Association::new(
+                &first.id,
+                &second.id,
+                RelationType::RelatedTo,
+            )],
+            embeddings: BTreeMap::from([(first.id.clone(), encode_vector(&[1.0, 2.0]))]),
+            memories: vec![first, second],
+        };
+
+        let decoded = decode_snapshot(&encode_snapshot(&snapshot).unwrap()).unwrap();
+        assert_eq!(decoded.memories, snapshot.memories);
+        assert_eq!(decoded.associations, snapshot.associations);
+        assert_eq!(decoded.embeddings, snapshot.embeddings);
+        assert_eq!(decoded.embedding, snapshot.embedding);
+    }
+
+    #[test]
+    fn signature_tracks_passage_prefix() {
+        let plain = EmbeddingSignature::current(&EmbeddingConfig::default());
+        let prefixed = EmbeddingSignature::current(&EmbeddingConfig {
+            passage_prefix: "passage: ".to_string(),
+            ..EmbeddingConfig::default()
+        });
+        assert_ne!(plain, prefixed);
+        assert_eq!(plain.dimension, EMBEDDING_DIM as usize);
+    }
+}
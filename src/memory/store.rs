@@ -259,6 +259,21 @@ impl MemoryStore {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Forget every memory saved from `channel_id`. Returns how many were
+    /// newly forgotten.
+    pub async fn forget_channel(&self, channel_id: &str) -> Result<u64> {
+        let result = sqlx::query(
+            "UPDATE memories SET forgotten = 1, updated_at = ? WHERE channel_id = ? AND forgotten = 0",
+        )
+        .bind(chrono::Utc::now())
+        .bind(channel_id)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("failed to forget memories for channel {}", channel_id))?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Merge one memory into a survivor with atomic SQLite updates.
     ///
     /// This updates survivor content/metadata, rewires associations, records an
@@ -297,89 +312,63 @@ impl MemoryStore {
         .await
         .with_context(|| format!("failed to update survivor memory {}", updated_survivor.id))?;
 
-        sqlx::query(
-            r#"
-            INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
-            SELECT
-                lower(hex(randomblob(16))),
-                CASE WHEN source_id = ?2 THEN ?1 ELSE source_id END,
-                CASE WHEN target_id = ?2 THEN ?1 ELSE target_id END,
-                relation_type,
-                weight,
-                created_at
-            FROM associations
-            WHERE (source_id = ?2 OR target_id = ?2)
-              AND source_id != ?1
-              AND CASE WHEN source_id = ?2 THEN ?1 ELSE source_id END != CASE WHEN target_id = ?2 THEN ?1 ELSE target_id END
-            ON CONFLICT(source_id, target_id, relation_type) DO UPDATE SET
-                weight = excluded.weight
-            "#,
-        )
-        .bind(&updated_survivor.id)
-        .bind(&merged_memory.id)
-        .execute(&mut *transaction)
-        .await
-        .with_context(|| {
-            format!(
-                "failed to rewire associations while merging {} into {}",
-                merged_memory.id, updated_survivor.id
-            )
-        })?;
+        absorb_memory(&mut transaction, &updated_survivor.id, &merged_memory.id).await?;
 
-        sqlx::query("DELETE FROM associations WHERE source_id = ? OR target_id = ?")
-            .bind(&merged_memory.id)
-            .bind(&merged_memory.id)
-            .execute(&mut *transaction)
+        transaction
+            .commit()
             .await
-            .with_context(|| {
-                format!(
-                    "failed to delete associations for merged memory {}",
-                    merged_memory.id
-                )
-            })?;
-
-        let updates_association = Association::new(
-            &updated_survivor.id,
-            &merged_memory.id,
-            RelationType::Updates,
-        )
-        .with_weight(1.0);
+            .with_context(|| "failed to commit memory merge transaction")?;
+
+        Ok(())
+    }
+
+    /// Save `consolidated` and fold each of `originals` into it in one
+    /// transaction.
+    ///
+    /// The originals' associations move to the consolidated memory, each
+    /// original gets an Updates edge from it, and the originals are marked
+    /// forgotten so they drop out of recall but stay in the database.
+    pub async fn consolidate_memories_atomic(
+        &self,
+        consolidated: &Memory,
+        originals: &[Memory],
+    ) -> Result<()> {
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .with_context(|| "failed to start memory consolidation transaction")?;
+
         sqlx::query(
             r#"
-            INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-            ON CONFLICT(source_id, target_id, relation_type) DO UPDATE SET
-                weight = excluded.weight
+            INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
+                                 last_accessed_at, access_count, source, channel_id, forgotten)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(&updates_association.id)
-        .bind(&updates_association.source_id)
-        .bind(&updates_association.target_id)
-        .bind(updates_association.relation_type.to_string())
-        .bind(updates_association.weight)
-        .bind(updates_association.created_at)
+        .bind(&consolidated.id)
+        .bind(&consolidated.content)
+        .bind(consolidated.memory_type.to_string())
+        .bind(consolidated.importance)
+        .bind(consolidated.created_at)
+        .bind(consolidated.updated_at)
+        .bind(consolidated.last_accessed_at)
+        .bind(consolidated.access_count)
+        .bind(&consolidated.source)
+        .bind(consolidated.channel_id.as_ref().map(|id| id.as_ref()))
+        .bind(consolidated.forgotten)
         .execute(&mut *transaction)
         .await
-        .with_context(|| {
-            format!(
-                "failed to create updates association {} -> {}",
-                updated_survivor.id, merged_memory.id
-            )
-        })?;
+        .with_context(|| format!("failed to save consolidated memory {}", consolidated.id))?;
 
-        sqlx::query(
-            "UPDATE memories SET forgotten = 1, updated_at = ? WHERE id = ? AND forgotten = 0",
-        )
-        .bind(chrono::Utc::now())
-        .bind(&merged_memory.id)
-        .execute(&mut *transaction)
-        .await
-        .with_context(|| format!("failed to forget merged memory {}", merged_memory.id))?;
+        for original in originals {
+            absorb_memory(&mut transaction, &consolidated.id, &original.id).await?;
+        }
 
         transaction
             .commit()
             .await
-            .with_context(|| "failed to commit memory merge transaction")?;
+            .with_context(|| "failed to commit memory consolidation transaction")?;
 
         Ok(())
     }
@@ -486,6 +475,43 @@ impl MemoryStore {
             .collect())
     }
 
+    /// Associations that can seed memory consolidation.
+    ///
+    /// Returns RelatedTo and Updates edges whose endpoints are both live,
+    /// non-identity memories of the same type with importance at or above
+    /// `min_importance`. Memories involved in any Contradicts edge are left
+    /// out entirely, since folding a conflict into one memory would hide it.
+    pub async fn get_consolidation_edges(&self, min_importance: f32) -> Result<Vec<Association>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.id, a.source_id, a.target_id, a.relation_type, a.weight, a.created_at
+            FROM associations a
+            JOIN memories s ON s.id = a.source_id
+            JOIN memories t ON t.id = a.target_id
+            WHERE a.relation_type IN ('related_to', 'updates')
+              AND s.forgotten = 0 AND t.forgotten = 0
+              AND s.memory_type != 'identity'
+              AND s.memory_type = t.memory_type
+              AND s.importance >= ?1 AND t.importance >= ?1
+              AND NOT EXISTS (
+                  SELECT 1 FROM associations c
+                  WHERE c.relation_type = 'contradicts'
+                    AND (c.source_id IN (a.source_id, a.target_id)
+                         OR c.target_id IN (a.source_id, a.target_id))
+              )
+            "#,
+        )
+        .bind(min_importance)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to get consolidation edges")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| row_to_association(&row))
+            .collect())
+    }
+
     /// Get neighbors of a memory: all associations plus the connected memories.
     /// Returns (neighbors, edges) where neighbors excludes any IDs in `exclude_ids`.
     pub async fn get_neighbors(
@@ -644,6 +670,135 @@ impl MemoryStore {
         Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
     }
 
+    /// Every memory row, forgotten ones included, oldest first. Used for
+    /// snapshots, where the store has to round-trip exactly.
+    pub async fn get_all(&self) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, content, memory_type, importance, created_at, updated_at,
+                   last_accessed_at, access_count, source, channel_id, forgotten
+            FROM memories
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to get all memories")?;
+
+        Ok(rows.into_iter().map(|row| row_to_memory(&row)).collect())
+    }
+
+    /// Every association in the graph.
+    pub async fn get_all_associations(&self) -> Result<Vec<Association>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, source_id, target_id, relation_type, weight, created_at
+            FROM associations
+            ORDER BY created_at ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to get all associations")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| row_to_association(&row))
+            .collect())
+    }
+
+    /// Write memories and associations from a snapshot in one transaction.
+    ///
+    /// Rows are upserted by ID, so restoring the same snapshot twice is a
+    /// no-op. With `replace`, every existing memory and association is
+    /// deleted first. Associations must only reference memories in
+    /// `memories`.
+    pub async fn restore(
+        &self,
+        memories: &[Memory],
+        associations: &[Association],
+        replace: bool,
+    ) -> Result<()> {
+        let mut transaction = self
+            .pool
+            .begin()
+            .await
+            .context("failed to begin memory restore transaction")?;
+
+        if replace {
+            sqlx::query("DELETE FROM associations")
+                .execute(&mut *transaction)
+                .await
+                .context("failed to clear associations")?;
+            sqlx::query("DELETE FROM memories")
+                .execute(&mut *transaction)
+                .await
+                .context("failed to clear memories")?;
+        }
+
+        for memory in memories {
+            sqlx::query(
+                r#"
+                INSERT INTO memories (id, content, memory_type, importance, created_at, updated_at,
+                                     last_accessed_at, access_count, source, channel_id, forgotten)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    content = excluded.content,
+                    memory_type = excluded.memory_type,
+                    importance = excluded.importance,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    last_accessed_at = excluded.last_accessed_at,
+                    access_count = excluded.access_count,
+                    source = excluded.source,
+                    channel_id = excluded.channel_id,
+                    forgotten = excluded.forgotten
+                "#,
+            )
+            .bind(&memory.id)
+            .bind(&memory.content)
+            .bind(memory.memory_type.to_string())
+            .bind(memory.importance)
+            .bind(memory.created_at)
+            .bind(memory.updated_at)
+            .bind(memory.last_accessed_at)
+            .bind(memory.access_count)
+            .bind(&memory.source)
+            .bind(memory.channel_id.as_ref().map(|id| id.as_ref()))
+            .bind(memory.forgotten)
+            .execute(&mut *transaction)
+            .await
+            .with_context(|| format!("failed to restore memory {}", memory.id))?;
+        }
+
+        for association in associations {
+            sqlx::query(
+                r#"
+                INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT(source_id, target_id, relation_type) DO UPDATE SET
+                    weight = excluded.weight
+                "#,
+            )
+            .bind(&association.id)
+            .bind(&association.source_id)
+            .bind(&association.target_id)
+            .bind(association.relation_type.to_string())
+            .bind(association.weight)
+            .bind(association.created_at)
+            .execute(&mut *transaction)
+            .await
+            .with_context(|| format!("failed to restore association {}", association.id))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .context("failed to commit memory restore transaction")?;
+
+        Ok(())
+    }
+
     /// Create an in-memory store for testing. Each call creates an isolated
     /// database so tests can run in parallel without migration conflicts.
     #[cfg(test)]
@@ -671,6 +826,89 @@ impl MemoryStore {
     }
 }
 
+/// Move `absorbed_id`'s associations onto `survivor_id`, record an Updates
+/// edge between them, and mark the absorbed memory forgotten.
+async fn absorb_memory(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    survivor_id: &str,
+    absorbed_id: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
+        SELECT
+            lower(hex(randomblob(16))),
+            CASE WHEN source_id = ?2 THEN ?1 ELSE source_id END,
+            CASE WHEN target_id = ?2 THEN ?1 ELSE target_id END,
+            relation_type,
+            weight,
+            created_at
+        FROM associations
+        WHERE (source_id = ?2 OR target_id = ?2)
+          AND source_id != ?1
+          AND CASE WHEN source_id = ?2 THEN ?1 ELSE source_id END != CASE WHEN target_id = ?2 THEN ?1 ELSE target_id END
+        ON CONFLICT(source_id, target_id, relation_type) DO UPDATE SET
+            weight = excluded.weight
+        "#,
+    )
+    .bind(survivor_id)
+    .bind(absorbed_id)
+    .execute(&mut **transaction)
+    .await
+    .with_context(|| {
+        format!(
+            "failed to rewire associations while merging {} into {}",
+            absorbed_id, survivor_id
+        )
+    })?;
+
+    sqlx::query("DELETE FROM associations WHERE source_id = ? OR target_id = ?")
+        .bind(absorbed_id)
+        .bind(absorbed_id)
+        .execute(&mut **transaction)
+        .await
+        .with_context(|| {
+            format!(
+                "failed to delete associations for merged memory {}",
+                absorbed_id
+            )
+        })?;
+
+    let updates_association =
+        Association::new(survivor_id, absorbed_id, RelationType::Updates).with_weight(1.0);
+    sqlx::query(
+        r#"
+        INSERT INTO associations (id, source_id, target_id, relation_type, weight, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(source_id, target_id, relation_type) DO UPDATE SET
+            weight = excluded.weight
+        "#,
+    )
+    .bind(&updates_association.id)
+    .bind(&updates_association.source_id)
+    .bind(&updates_association.target_id)
+    .bind(updates_association.relation_type.to_string())
+    .bind(updates_association.weight)
+    .bind(updates_association.created_at)
+    .execute(&mut **transaction)
+    .await
+    .with_context(|| {
+        format!(
+            "failed to create updates association {} -> {}",
+            survivor_id, absorbed_id
+        )
+    })?;
+
+    sqlx::query("UPDATE memories SET forgotten = 1, updated_at = ? WHERE id = ? AND forgotten = 0")
+        .bind(chrono::Utc::now())
+        .bind(absorbed_id)
+        .execute(&mut **transaction)
+        .await
+        .with_context(|| format!("failed to forget merged memory {}", absorbed_id))?;
+
+    Ok(())
+}
+
 /// Helper: Convert a database row to a Memory.
 fn row_to_memory(row: &sqlx::sqlite::SqliteRow) -> Memory {
     let mem_type_str: String = row.try_get("memory_type").unwrap_or_default();
@@ -776,6 +1014,20 @@ mod tests {
         assert_eq!(loaded.memory_type, MemoryType::Fact);
     }
 
+    #[tokio::test]
+    async fn test_forget_channel() {
+        let store = MemoryStore::connect_in_memory().await;
+        let scoped = Memory::new("From web", MemoryType::Fact).with_channel_id("web:1".into());
+        let other = Memory::new("From slack", MemoryType::Fact).with_channel_id("slack:2".into());
+        store.save(&scoped).await.unwrap();
+        store.save(&other).await.unwrap();
+
+        assert_eq!(store.forget_channel("web:1").await.unwrap(), 1);
+        assert_eq!(store.forget_channel("web:1").await.unwrap(), 0);
+        assert!(store.load(&scoped.id).await.unwrap().unwrap().forgotten);
+        assert!(!store.load(&other.id).await.unwrap().unwrap().forgotten);
+    }
+
     #[tokio::test]
     async fn test_get_sorted_recent() {
         let store = MemoryStore::connect_in_memory().await;
@@ -903,4 +1155,93 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, visible.id);
     }
+
+    #[tokio::test]
+    async fn test_consolidate_memories_archives_originals() {
+        let store = MemoryStore::connect_in_memory().await;
+        let now = Utc::now();
+
+        let first = insert_memory_at(&store, "uses postgres", MemoryType::Fact, 0.7, now).await;
+        let second = insert_memory_at(&store, "postgres 16", MemoryType::Fact, 0.7, now).await;
+        let outside = insert_memory_at(&store, "unrelated", MemoryType::Fact, 0.7, now).await;
+        store
+            .create_association(&Association::new(
+                &first.id,
+                &outside.id,
+                RelationType::RelatedTo,
+            ))
+            .await
+            .unwrap();
+
+        let consolidated = Memory::new("uses postgres 16", MemoryType::Fact);
+        store
+            .consolidate_memories_atomic(&consolidated, &[first.clone(), second.clone()])
+            .await
+            .unwrap();
+
+        assert!(store.load(&first.id).await.unwrap().unwrap().forgotten);
+        assert!(store.load(&second.id).await.unwrap().unwrap().forgotten);
+        assert!(
+            !store
+                .load(&consolidated.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .forgotten
+        );
+
+        let edges = store.get_associations(&consolidated.id).await.unwrap();
+        let updates = edges
+            .iter()
+            .filter(|edge| edge.relation_type == RelationType::Updates)
+            .count();
+        assert_eq!(updates, 2);
+        assert!(edges.iter().any(|edge| edge.target_id == outside.id));
+    }
+
+    #[tokio::test]
+    async fn restore_round_trips_and_replaces() {
+        let source = MemoryStore::connect_in_memory().await;
+        let first = Memory::new("likes tea", MemoryType::Preference);
+        let mut second = Memory::new("used to like coffee", MemoryType::Preference);
+        second.forgotten = true;
+        source.save(&first).await.unwrap();
+        source.save(&second).await.unwrap();
+        source
+            .create_association(&Association::new(
+                &first.id,
+                &second.id,
+                RelationType::Updates,
+            ))
+            .await
+            .unwrap();
+
+        let memories = source.get_all().await.unwrap();
+        let associations = source.get_all_associations().await.unwrap();
+        assert_eq!(memories.len(), 2);
+
+        let target = MemoryStore::connect_in_memory().await;
+        let stale = Memory::new("stale", MemoryType::Fact);
+        target.save(&stale).await.unwrap();
+
+        target
+            .restore(&memories, &associations, false)
+            .await
+            .unwrap();
+        // Restoring twice is a no-op.
+        target
+            .restore(&memories, &associations, false)
+            .await
+            .unwrap();
+        assert_eq!(target.get_all().await.unwrap().len(), 3);
+        assert_eq!(target.get_all_associations().await.unwrap(), associations);
+        assert!(target.load(&second.id).await.unwrap().unwrap().forgotten);
+
+        target
+            .restore(&memories, &associations, true)
+            .await
+            .unwrap();
+        assert!(target.load(&stale.id).await.unwrap().is_none());
+        assert_eq!(target.get_all().await.unwrap().len(), 2);
+    }
 }
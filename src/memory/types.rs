@@ -61,6 +61,11 @@ impl Memory {
         self
     }
 
+    /// When the memory was last reinforced: saved, updated, or recalled.
+    pub fn last_reinforced_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.updated_at.max(self.last_accessed_at)
+    }
+
     /// Identity memories have maximum importance and don't decay.
     pub const fn identity_importance() -> f32 {
         1.0
@@ -1,9 +1,13 @@
 //! Messaging adapters (Discord, Slack, Telegram, Twitch, Signal, Email, Webhook, WebChat, Mattermost).
 
+pub mod commands;
+pub mod dedup;
 pub mod discord;
 pub mod email;
+pub mod footer;
 pub mod manager;
 pub mod mattermost;
+pub mod pacing;
 pub mod signal;
 pub mod slack;
 pub mod target;
@@ -16,3 +20,4 @@ pub mod webhook;
 pub use manager::MessagingManager;
 pub use traits::Messaging;
 pub use traits::apply_runtime_adapter_to_conversation_id;
+pub use traits::truncate_reply_text;
@@ -0,0 +1,66 @@
+//! Prefixed chat commands shared by every adapter.
+//!
+//! A message like `!reset` is matched against the agent's `channel.commands`
+//! before it reaches the LLM, so the same control commands work on every
+//! platform. Only enabled commands are recognised; any other prefixed text
+//! (`!roll d20`) is an ordinary message.
+
+use crate::config::ChatCommand;
+
+/// Match `text` against the enabled commands. Returns `None` when the prefix
+/// is empty, the text does not start with it, or the command is not enabled.
+///
+/// Words after the command name are ignored, so `!reset please` still resets.
+pub fn parse_prefixed_command(
+    text: &str,
+    prefix: &str,
+    enabled: &[ChatCommand],
+) -> Option<ChatCommand> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let rest = text.trim().strip_prefix(prefix)?;
+    let name = rest.split_whitespace().next()?;
+    // `rest` must start with the name: `! reset` is not a command.
+    if !rest.starts_with(name) {
+        return None;
+    }
+
+    let command = ChatCommand::parse(name)?;
+    enabled.contains(&command).then_some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_enabled_commands_only() {
+        let enabled = [ChatCommand::Reset, ChatCommand::Help];
+
+        assert_eq!(
+            parse_prefixed_command("!reset", "!", &enabled),
+            Some(ChatCommand::Reset)
+        );
+        assert_eq!(
+            parse_prefixed_command("  !HELP me  ", "!", &enabled),
+            Some(ChatCommand::Help)
+        );
+        assert_eq!(parse_prefixed_command("!status", "!", &enabled), None);
+        assert_eq!(parse_prefixed_command("!roll d20", "!", &enabled), None);
+        assert_eq!(parse_prefixed_command("! reset", "!", &enabled), None);
+        assert_eq!(parse_prefixed_command("reset", "!", &enabled), None);
+    }
+
+    #[test]
+    fn supports_multi_character_prefixes_and_disabling() {
+        let enabled = ChatCommand::ALL;
+
+        assert_eq!(
+            parse_prefixed_command("bot:status", "bot:", &enabled),
+            Some(ChatCommand::Status)
+        );
+        assert_eq!(parse_prefixed_command("!status", "bot:", &enabled), None);
+        assert_eq!(parse_prefixed_command("!status", "", &enabled), None);
+    }
+}
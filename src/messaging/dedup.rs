@@ -0,0 +1,193 @@
+//! Idempotency keys and a local dedup cache for outbound platform sends.
+//!
+//! None of the adapters' platform APIs offer client-assigned dedup for posted
+//! messages, so the manager claims each send's key here before delivering it.
+//! A second send with the same key inside the TTL is dropped. Keys are derived
+//! from the conversation, the turn (the inbound message being answered), and
+//! a hash of the response itself, so a turn that is retried or replayed and
+//! produces the same response again gets the same key.
+
+use crate::{InboundMessage, OutboundResponse};
+
+use sha2::{Digest as _, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a delivered key suppresses duplicates.
+const DEDUP_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Upper bound on remembered keys. The oldest are evicted first.
+const DEDUP_MAX_KEYS: usize = 4096;
+
+/// Whether `response` is deduplicated. Status updates, stream frames, and
+/// reaction removals always go out.
+fn is_keyed(response: &OutboundResponse) -> bool {
+    !matches!(
+        response,
+        OutboundResponse::Status(_)
+            | OutboundResponse::StreamStart
+            | OutboundResponse::StreamChunk(_)
+            | OutboundResponse::StreamEnd
+            | OutboundResponse::RemoveReaction(_)
+    )
+}
+
+/// Idempotency key for `response` in the turn answering `target`, or None
+/// for responses that must always go out.
+///
+/// The key combines the conversation ID, the ID of the inbound message that
+/// started the turn, and a hash of the response, so it is the same every
+/// time that response is sent for that turn.
+pub fn idempotency_key(target: &InboundMessage, response: &OutboundResponse) -> Option<String> {
+    if !is_keyed(response) {
+        return None;
+    }
+    // Synthetic targets (e.g. send_file tests) carry no turn to key on.
+    if target.id.is_empty() {
+        return None;
+    }
+    let encoded = serde_json::to_vec(response).ok()?;
+    let digest = Sha256::digest(&encoded);
+    let hash: String = digest[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Some(format!("{}:{}:{hash}", target.conversation_id, target.id))
+}
+
+/// Whether a failed send certainly never reached the platform, so its key
+/// can be released for a retry. Timeouts, server errors, and errors that
+/// can't be classified may have been delivered, so their keys are kept.
+pub fn send_definitely_failed(error: &crate::Error) -> bool {
+    let crate::Error::Other(error) = error else {
+        return false;
+    };
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|error| {
+            error.is_connect()
+                || error.is_builder()
+                || error
+                    .status()
+                    .is_some_and(|status| status.is_client_error())
+        })
+}
+
+/// Whether a failed send can be retried under the same key: the connection
+/// was never made, so nothing reached the platform.
+pub fn send_retryable(error: &crate::Error) -> bool {
+    let crate::Error::Other(error) = error else {
+        return false;
+    };
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|error| error.is_connect())
+}
+
+/// Bounded, time-limited record of outbound keys that were claimed.
+#[derive(Debug, Default)]
+pub struct OutboundDedupCache {
+    inner: Mutex<DedupState>,
+}
+
+#[derive(Debug, Default)]
+struct DedupState {
+    claimed: HashMap<String, Instant>,
+    /// Claims in the order they were made, oldest first.
+    order: VecDeque<(String, Instant)>,
+}
+
+impl OutboundDedupCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Claim a key before sending. Returns false if the key was already
+    /// claimed within the TTL, meaning the send is a duplicate.
+    pub fn claim(&self, key: &str) -> bool {
+        self.claim_at(key, Instant::now())
+    }
+
+    /// Release a claimed key after a failed send so a retry can go through.
+    pub fn release(&self, key: &str) {
+        let mut state = self.inner.lock().expect("dedup cache lock poisoned");
+        state.claimed.remove(key);
+    }
+
+    fn claim_at(&self, key: &str, now: Instant) -> bool {
+        let mut state = self.inner.lock().expect("dedup cache lock poisoned");
+
+        while let Some((_, claimed_at)) = state.order.front() {
+            let expired = now.duration_since(*claimed_at) >= DEDUP_TTL;
+            if !expired && state.order.len() < DEDUP_MAX_KEYS {
+                break;
+            }
+            let (oldest, claimed_at) = state.order.pop_front().expect("front exists");
+            // Only forget the key if it wasn't released and re-claimed since.
+            if state.claimed.get(&oldest) == Some(&claimed_at) {
+                state.claimed.remove(&oldest);
+            }
+        }
+
+        if state.claimed.contains_key(key) {
+            return false;
+        }
+
+        state.claimed.insert(key.to_string(), now);
+        state.order.push_back((key.to_string(), now));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inbound(id: &str) -> InboundMessage {
+        let mut message = InboundMessage::empty();
+        message.id = id.to_string();
+        message.conversation_id = "discord:1:2".to_string();
+        message
+    }
+
+    #[test]
+    fn keys_are_stable_for_the_same_response_in_a_turn() {
+        let reply = OutboundResponse::Text("hello".into());
+
+        let first = idempotency_key(&inbound("m1"), &reply).unwrap();
+        assert!(first.starts_with("discord:1:2:m1:"));
+        // A replay of the same response for the same turn gets the same key.
+        assert_eq!(idempotency_key(&inbound("m1"), &reply), Some(first.clone()));
+        assert_ne!(idempotency_key(&inbound("m2"), &reply), Some(first.clone()));
+        assert_ne!(
+            idempotency_key(&inbound("m1"), &OutboundResponse::Text("bye".into())),
+            Some(first)
+        );
+        assert_eq!(
+            idempotency_key(&inbound("m1"), &OutboundResponse::StreamChunk("x".into())),
+            None
+        );
+        assert_eq!(idempotency_key(&inbound(""), &reply), None);
+    }
+
+    #[test]
+    fn unclassified_send_errors_keep_their_key() {
+        let error = crate::Error::Other(anyhow::anyhow!("gateway timed out"));
+        assert!(!send_definitely_failed(&error));
+    }
+
+    #[test]
+    fn cache_rejects_duplicates_until_released_or_expired() {
+        let cache = OutboundDedupCache::new();
+        let start = Instant::now();
+        assert!(cache.claim_at("k", start));
+        assert!(!cache.claim_at("k", start + Duration::from_secs(1)));
+
+        cache.release("k");
+        assert!(cache.claim_at("k", start + Duration::from_secs(2)));
+        assert!(cache.claim_at("k", start + DEDUP_TTL + Duration::from_secs(3)));
+    }
+}
@@ -10,15 +10,88 @@ use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use serenity::all::{
     ButtonStyle, ChannelId, ChannelType, Context, CreateActionRow, CreateAttachment, CreateButton,
-    CreateEmbed, CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
-    CreateMessage, CreatePoll, CreatePollAnswer, CreateSelectMenu, CreateSelectMenuKind,
-    CreateSelectMenuOption, CreateThread, EditMessage, EventHandler, GatewayIntents, GetMessages,
-    Http, Interaction, Message, MessageId, ReactionType, Ready, ShardManager, User, UserId,
+    CreateEmbed, CreateEmbedFooter, CreateForumPost, CreateInteractionResponse,
+    CreateInteractionResponseFollowup, CreateMessage, CreatePoll, CreatePollAnswer,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, CreateThread, EditMessage,
+    EventHandler, GatewayIntents, GetMessages, GuildChannel, GuildId, Http, Interaction, Message,
+    MessageId, MessageReferenceKind, Reaction, ReactionType, Ready, ShardManager, StickerId, User,
+    UserId,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{RwLock, mpsc};
 
+/// How long to wait when fetching the message a reply points at.
+const REPLY_FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How long Discord accepts follow-ups on an interaction token.
+const INTERACTION_TOKEN_TTL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// Interaction token captured from a component interaction, used to send
+/// ephemeral follow-ups to the user who triggered it.
+#[derive(Debug, Clone)]
+struct InteractionToken {
+    token: String,
+    received_at: std::time::Instant,
+}
+
+impl InteractionToken {
+    fn is_expired(&self) -> bool {
+        self.received_at.elapsed() >= INTERACTION_TOKEN_TTL
+    }
+}
+
+/// A streamed reply in progress for one inbound message.
+#[derive(Debug)]
+struct StreamingMessage {
+    /// Placeholder being edited, or None when stream edits are off and the
+    /// reply is sent once the stream ends.
+    message_id: Option<MessageId>,
+    edits: EditBatcher,
+}
+
+/// Coalesces cumulative stream chunks into message edits spaced at least
+/// `interval` apart, so long replies stay inside Discord's edit rate limit.
+#[derive(Debug)]
+struct EditBatcher {
+    interval: std::time::Duration,
+    last_edit: std::time::Instant,
+    /// Newest cumulative text of the stream.
+    latest: Option<String>,
+    /// Whether `latest` has changed since the last edit.
+    dirty: bool,
+}
+
+impl EditBatcher {
+    /// Start batching; posting the placeholder at `now` counts as an edit.
+    fn new(interval: std::time::Duration, now: std::time::Instant) -> Self {
+        Self {
+            interval,
+            last_edit: now,
+            latest: None,
+            dirty: false,
+        }
+    }
+
+    /// Record the newest cumulative text and return it if an edit is due.
+    fn push(&mut self, text: String, now: std::time::Instant) -> Option<String> {
+        self.latest = Some(text);
+        self.dirty = true;
+        if now.saturating_duration_since(self.last_edit) < self.interval {
+            return None;
+        }
+        self.last_edit = now;
+        self.dirty = false;
+        self.latest.clone()
+    }
+
+    /// The final text of the stream, and whether it still needs writing.
+    fn finish(self) -> Option<(String, bool)> {
+        let dirty = self.dirty;
+        self.latest.map(|text| (text, dirty))
+    }
+}
+
 /// Discord adapter state.
 pub struct DiscordAdapter {
     runtime_key: String,
@@ -26,10 +99,15 @@ pub struct DiscordAdapter {
     permissions: Arc<ArcSwap<DiscordPermissions>>,
     http: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id: Arc<RwLock<Option<UserId>>>,
-    /// Maps InboundMessage.id to the Discord MessageId being edited during streaming.
-    active_messages: Arc<RwLock<HashMap<String, serenity::all::MessageId>>>,
+    /// Maps InboundMessage.id to the reply being streamed for it.
+    active_messages: Arc<RwLock<HashMap<String, StreamingMessage>>>,
+    /// Minimum gap between edits of a streamed reply. None sends streamed
+    /// replies as a single message once they finish.
+    stream_edit_interval: Option<std::time::Duration>,
     /// Typing handles per message. Typing stops when the handle is dropped.
     typing_tasks: Arc<RwLock<HashMap<String, serenity::http::Typing>>>,
+    /// Maps the InboundMessage.id of a component interaction to its token.
+    interaction_tokens: Arc<RwLock<HashMap<String, InteractionToken>>>,
     shard_manager: Arc<RwLock<Option<Arc<ShardManager>>>>,
 }
 
@@ -46,11 +124,20 @@ impl DiscordAdapter {
             http: Arc::new(RwLock::new(None)),
             bot_user_id: Arc::new(RwLock::new(None)),
             active_messages: Arc::new(RwLock::new(HashMap::new())),
+            stream_edit_interval: None,
             typing_tasks: Arc::new(RwLock::new(HashMap::new())),
+            interaction_tokens: Arc::new(RwLock::new(HashMap::new())),
             shard_manager: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Stream replies by editing a placeholder message at most once per
+    /// `interval`. Pass None to send streamed replies once they complete.
+    pub fn with_stream_edits(mut self, interval: Option<std::time::Duration>) -> Self {
+        self.stream_edit_interval = interval;
+        self
+    }
+
     async fn get_http(&self) -> anyhow::Result<Arc<Http>> {
         self.http
             .read()
@@ -85,6 +172,16 @@ impl DiscordAdapter {
             .remove(&Self::channel_key(message));
     }
 
+    /// Token of the component interaction that produced `message`, if it
+    /// hasn't expired yet.
+    async fn interaction_token(&self, message: &InboundMessage) -> Option<String> {
+        let tokens = self.interaction_tokens.read().await;
+        tokens
+            .get(&message.id)
+            .filter(|token| !token.is_expired())
+            .map(|token| token.token.clone())
+    }
+
     fn extract_reply_message_id(message: &InboundMessage) -> Option<MessageId> {
         message
             .metadata
@@ -112,11 +209,14 @@ impl Messaging for DiscordAdapter {
             permissions: self.permissions.clone(),
             http_slot: self.http.clone(),
             bot_user_id_slot: self.bot_user_id.clone(),
+            interaction_tokens: self.interaction_tokens.clone(),
         };
 
         let intents = GatewayIntents::GUILD_MESSAGES
             | GatewayIntents::DIRECT_MESSAGES
             | GatewayIntents::MESSAGE_CONTENT
+            | GatewayIntents::GUILD_MESSAGE_REACTIONS
+            | GatewayIntents::DIRECT_MESSAGE_REACTIONS
             | GatewayIntents::GUILDS;
 
         let mut client = serenity::Client::builder(&self.token, intents)
@@ -224,6 +324,23 @@ impl Messaging for DiscordAdapter {
             OutboundResponse::ThreadReply { thread_name, text } => {
                 self.stop_typing(message).await;
 
+                // Already inside a thread (including forum posts) — threads
+                // can't nest, so reply in place.
+                let in_thread = message
+                    .metadata
+                    .get("discord_is_thread")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                if in_thread {
+                    for chunk in split_message(&text, 2000) {
+                        channel_id
+                            .say(&*http, &chunk)
+                            .await
+                            .context("failed to send discord message in thread")?;
+                    }
+                    return Ok(());
+                }
+
                 // Try to create a public thread from the source message.
                 // Requires the "Create Public Threads" bot permission.
                 let message_id = message
@@ -313,36 +430,105 @@ impl Messaging for DiscordAdapter {
                     .await
                     .context("failed to add reaction")?;
             }
-            OutboundResponse::StreamStart => {
+            OutboundResponse::Sticker { id, .. } => {
                 self.stop_typing(message).await;
+                let sticker_id = id
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|id| *id != 0)
+                    .with_context(|| format!("invalid discord sticker id '{id}'"))?;
 
-                let placeholder = channel_id
-                    .say(&*http, "\u{200B}")
+                let mut builder = CreateMessage::new().sticker_id(StickerId::new(sticker_id));
+                if let Some(reply_message_id) = Self::extract_reply_message_id(message) {
+                    builder = builder.reference_message((channel_id, reply_message_id));
+                }
+                channel_id
+                    .send_message(&*http, builder)
                     .await
-                    .context("failed to send stream placeholder")?;
+                    .context("failed to send discord sticker")?;
+            }
+            OutboundResponse::StreamStart => {
+                let message_id = match self.stream_edit_interval {
+                    Some(_) => {
+                        self.stop_typing(message).await;
+                        let placeholder = channel_id
+                            .say(&*http, "\u{200B}")
+                            .await
+                            .context("failed to send stream placeholder")?;
+                        Some(placeholder.id)
+                    }
+                    None => None,
+                };
 
-                self.active_messages
-                    .write()
-                    .await
-                    .insert(message.id.clone(), placeholder.id);
+                let interval = self.stream_edit_interval.unwrap_or_default();
+                self.active_messages.write().await.insert(
+                    message.id.clone(),
+                    StreamingMessage {
+                        message_id,
+                        edits: EditBatcher::new(interval, std::time::Instant::now()),
+                    },
+                );
             }
             OutboundResponse::StreamChunk(text) => {
-                let active = self.active_messages.read().await;
-                if let Some(&message_id) = active.get(&message.id) {
-                    let display_text = if text.len() > 2000 {
-                        let end = text.floor_char_boundary(1997);
-                        format!("{}...", &text[..end])
-                    } else {
-                        text
-                    };
-                    let builder = EditMessage::new().content(display_text);
+                let due_edit = self
+                    .active_messages
+                    .write()
+                    .await
+                    .get_mut(&message.id)
+                    .and_then(|streaming| {
+                        let text = streaming.edits.push(text, std::time::Instant::now())?;
+                        Some((streaming.message_id?, text))
+                    });
+                if let Some((message_id, text)) = due_edit {
+                    let builder = EditMessage::new().content(truncate_stream_text(&text));
                     if let Err(error) = channel_id.edit_message(&*http, message_id, builder).await {
                         tracing::warn!(%error, "failed to edit streaming message");
                     }
                 }
             }
             OutboundResponse::StreamEnd => {
-                self.active_messages.write().await.remove(&message.id);
+                let streaming = self.active_messages.write().await.remove(&message.id);
+                let Some(streaming) = streaming else {
+                    return Ok(());
+                };
+                let Some((text, dirty)) = streaming.edits.finish() else {
+                    return Ok(());
+                };
+
+                let mut chunks = split_message(&text, 2000).into_iter();
+                let reply_to = match streaming.message_id {
+                    Some(message_id) => {
+                        // The placeholder takes the first chunk; anything past
+                        // Discord's length limit follows as new messages.
+                        if let Some(first) = chunks.next()
+                            && (dirty || text.len() > 2000)
+                        {
+                            let builder = EditMessage::new().content(first);
+                            if let Err(error) =
+                                channel_id.edit_message(&*http, message_id, builder).await
+                            {
+                                tracing::warn!(%error, "failed to finalize streaming message");
+                            }
+                        }
+                        None
+                    }
+                    None => {
+                        self.stop_typing(message).await;
+                        Self::extract_reply_message_id(message)
+                    }
+                };
+                for (index, chunk) in chunks.enumerate() {
+                    let mut builder = CreateMessage::new().content(chunk);
+                    if index == 0
+                        && let Some(reply_message_id) = reply_to
+                    {
+                        builder = builder.reference_message((channel_id, reply_message_id));
+                    }
+                    channel_id
+                        .send_message(&*http, builder)
+                        .await
+                        .context("failed to send discord message")?;
+                }
             }
             OutboundResponse::Status(status) => {
                 self.send_status(message, status).await?;
@@ -350,13 +536,27 @@ impl Messaging for DiscordAdapter {
             // Slack-specific variants — graceful fallbacks for Discord
             OutboundResponse::RemoveReaction(_) => {} // no-op
             OutboundResponse::Ephemeral { text, .. } => {
-                // Discord has no ephemeral equivalent here; send as regular text
-                if let Ok(channel_id) = self.extract_channel_id(message) {
-                    let http = self.get_http().await?;
-                    channel_id
-                        .say(&*http, &text)
-                        .await
-                        .context("failed to send ephemeral fallback on discord")?;
+                self.stop_typing(message).await;
+
+                // Ephemeral messages need the token of the interaction that
+                // triggered this turn; Discord knows who clicked, so no user_id.
+                if let Some(token) = self.interaction_token(message).await {
+                    for chunk in split_message(&text, 2000) {
+                        let followup = CreateInteractionResponseFollowup::new()
+                            .content(chunk)
+                            .ephemeral(true);
+                        http.create_followup_message(&token, &followup, Vec::new())
+                            .await
+                            .context("failed to send ephemeral follow-up on discord")?;
+                    }
+                } else {
+                    // Not an interaction, or its token expired: send as regular text
+                    for chunk in split_message(&text, 2000) {
+                        channel_id
+                            .say(&*http, chunk)
+                            .await
+                            .context("failed to send ephemeral fallback on discord")?;
+                    }
                 }
             }
             OutboundResponse::ScheduledMessage { text, .. } => {
@@ -369,6 +569,17 @@ impl Messaging for DiscordAdapter {
                         .context("failed to send scheduled message fallback on discord")?;
                 }
             }
+            OutboundResponse::Reasoning(reasoning) => {
+                // Spoiler-wrap so the reasoning stays collapsed until clicked.
+                // Typing continues: the reply is still on its way.
+                let reasoning = reasoning.trim().replace("||", "| |");
+                for chunk in split_message(&reasoning, 1980) {
+                    channel_id
+                        .say(&*http, format!("💭 ||{chunk}||"))
+                        .await
+                        .context("failed to send reasoning on discord")?;
+                }
+            }
         }
 
         Ok(())
@@ -421,63 +632,89 @@ impl Messaging for DiscordAdapter {
             )
         };
 
-        if let OutboundResponse::Text(text) = response {
-            for chunk in split_message(&text, 2000) {
-                channel_id
-                    .say(&*http, &chunk)
-                    .await
-                    .context("failed to broadcast discord message")?;
-            }
-        } else if let OutboundResponse::RichMessage {
-            text,
-            cards,
-            interactive_elements,
-            poll,
-            ..
-        } = response
-        {
-            let parts =
-                prepare_rich_message_parts(text, &cards, &interactive_elements, poll.as_ref());
-            if parts.dropped_invalid_poll {
-                tracing::warn!(
-                    "dropping invalid discord poll payload while broadcasting rich message"
-                );
+        let (title_source, messages) = match response {
+            OutboundResponse::Text(text) => {
+                let messages = split_message(&text, 2000)
+                    .into_iter()
+                    .map(|chunk| CreateMessage::new().content(chunk))
+                    .collect::<Vec<_>>();
+                (text, messages)
             }
-
-            let chunks = split_message(&parts.text, 2000);
-            for (i, chunk) in chunks.iter().enumerate() {
-                let is_last = i == chunks.len() - 1;
-                let mut msg = CreateMessage::new();
-                if !chunk.is_empty() {
-                    msg = msg.content(chunk);
+            OutboundResponse::RichMessage {
+                text,
+                cards,
+                interactive_elements,
+                poll,
+                ..
+            } => {
+                let parts =
+                    prepare_rich_message_parts(text, &cards, &interactive_elements, poll.as_ref());
+                if parts.dropped_invalid_poll {
+                    tracing::warn!(
+                        "dropping invalid discord poll payload while broadcasting rich message"
+                    );
                 }
 
-                // Attach rich content only to the final chunk
-                if is_last {
-                    let embeds: Vec<_> = parts.cards.iter().map(build_embed).collect();
-                    if !embeds.is_empty() {
-                        msg = msg.embeds(embeds);
+                let chunks = split_message(&parts.text, 2000);
+                let mut messages = Vec::with_capacity(chunks.len());
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let is_last = i == chunks.len() - 1;
+                    let mut msg = CreateMessage::new();
+                    if !chunk.is_empty() {
+                        msg = msg.content(chunk);
                     }
 
-                    let components: Vec<_> = parts
-                        .interactive_elements
-                        .iter()
-                        .map(build_action_row)
-                        .collect();
-                    if !components.is_empty() {
-                        msg = msg.components(components);
-                    }
+                    // Attach rich content only to the final chunk
+                    if is_last {
+                        let embeds: Vec<_> = parts.cards.iter().map(build_embed).collect();
+                        if !embeds.is_empty() {
+                            msg = msg.embeds(embeds);
+                        }
 
-                    if let Some(poll_data) = parts.poll.as_ref().and_then(build_poll) {
-                        msg = msg.poll(poll_data);
+                        let components: Vec<_> = parts
+                            .interactive_elements
+                            .iter()
+                            .map(build_action_row)
+                            .collect();
+                        if !components.is_empty() {
+                            msg = msg.components(components);
+                        }
+
+                        if let Some(poll_data) = parts.poll.as_ref().and_then(build_poll) {
+                            msg = msg.poll(poll_data);
+                        }
                     }
+                    messages.push(msg);
                 }
-
-                channel_id
-                    .send_message(&*http, msg)
-                    .await
-                    .context("failed to broadcast discord rich message")?;
+                (parts.text, messages)
             }
+            _ => return Ok(()),
+        };
+
+        let mut messages = messages.into_iter();
+
+        // Forum channels can't hold messages directly — every post is a
+        // thread. Open a new post with the first message as its starter and
+        // send the rest into that thread.
+        let channel_id = if is_forum_channel(&http, channel_id).await {
+            let Some(starter) = messages.next() else {
+                return Ok(());
+            };
+            let post = CreateForumPost::new(forum_post_title(&title_source), starter);
+            channel_id
+                .create_forum_post(&*http, post)
+                .await
+                .context("failed to create discord forum post")?
+                .id
+        } else {
+            channel_id
+        };
+
+        for msg in messages {
+            channel_id
+                .send_message(&*http, msg)
+                .await
+                .context("failed to broadcast discord message")?;
         }
 
         Ok(())
@@ -588,6 +825,7 @@ struct Handler {
     permissions: Arc<ArcSwap<DiscordPermissions>>,
     http_slot: Arc<RwLock<Option<Arc<Http>>>>,
     bot_user_id_slot: Arc<RwLock<Option<UserId>>>,
+    interaction_tokens: Arc<RwLock<HashMap<String, InteractionToken>>>,
 }
 
 #[async_trait]
@@ -681,12 +919,10 @@ impl EventHandler for Handler {
         };
 
         // Acknowledge the interaction immediately to prevent "This interaction failed" in the UI.
-        // We use Defer to indicate we've received it and might edit the message soon.
+        // A deferred update shows no loading message, so later follow-ups (such as
+        // ephemeral replies) are posted as new messages rather than filling one in.
         if let Err(error) = component
-            .create_response(
-                &ctx.http,
-                CreateInteractionResponse::Defer(CreateInteractionResponseMessage::new()),
-            )
+            .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
             .await
         {
             tracing::warn!(%error, "failed to acknowledge interaction");
@@ -763,6 +999,18 @@ impl EventHandler for Handler {
             serde_json::Value::String(formatted_author.clone()),
         );
 
+        {
+            let mut tokens = self.interaction_tokens.write().await;
+            tokens.retain(|_, token| !token.is_expired());
+            tokens.insert(
+                component.id.to_string(),
+                InteractionToken {
+                    token: component.token.clone(),
+                    received_at: std::time::Instant::now(),
+                },
+            );
+        }
+
         let inbound = InboundMessage {
             id: component.id.to_string(), // Use interaction ID to ensure uniqueness
             source: "discord".into(),
@@ -783,6 +1031,174 @@ impl EventHandler for Handler {
             );
         }
     }
+
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        let Some(user_id) = reaction.user_id else {
+            return;
+        };
+        // Ignore our own reactions (e.g. from the react tool) to prevent loops
+        let bot_user_id = *self.bot_user_id_slot.read().await;
+        if bot_user_id.is_some_and(|id| user_id == id) {
+            return;
+        }
+
+        let emoji = match &reaction.emoji {
+            ReactionType::Unicode(emoji) => emoji.clone(),
+            ReactionType::Custom { name, id, .. } => {
+                name.clone().unwrap_or_else(|| id.get().to_string())
+            }
+            _ => return,
+        };
+        // Most reactions map to nothing; drop them before any API call.
+        if !crate::config::ReactionTriggers::any_agent_triggers(&emoji) {
+            return;
+        }
+
+        let permissions = self.permissions.load();
+
+        let reactor_is_bot = reaction
+            .member
+            .as_ref()
+            .is_some_and(|member| member.user.bot);
+        if reactor_is_bot && !permissions.allow_bot_messages {
+            return;
+        }
+
+        if reaction.guild_id.is_none()
+            && (permissions.dm_allowed_users.is_empty()
+                || !permissions.dm_allowed_users.contains(&user_id.get()))
+        {
+            return;
+        }
+
+        if let Some(filter) = &permissions.guild_filter
+            && let Some(guild_id) = reaction.guild_id
+            && !filter.contains(&guild_id.get())
+        {
+            return;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "discord_channel_id".into(),
+            serde_json::Value::Number(reaction.channel_id.get().into()),
+        );
+        metadata.insert(
+            "discord_message_id".into(),
+            serde_json::Value::Number(reaction.message_id.get().into()),
+        );
+        metadata.insert(
+            crate::metadata_keys::MESSAGE_ID.into(),
+            serde_json::Value::String(reaction.message_id.get().to_string()),
+        );
+        if let Some(guild_id) = reaction.guild_id {
+            metadata.insert(
+                "discord_guild_id".into(),
+                serde_json::Value::Number(guild_id.get().into()),
+            );
+            if let Ok(channel) = reaction.channel_id.to_channel(&ctx.http).await
+                && let Some(guild_channel) = channel.guild()
+                && guild_channel.thread_metadata.is_some()
+                && let Some(parent_id) = guild_channel.parent_id
+            {
+                metadata.insert("discord_parent_channel_id".into(), parent_id.get().into());
+            }
+        }
+
+        // Channel filter: allow if the channel ID or its parent (for threads) is in the allowlist
+        if let Some(guild_id) = reaction.guild_id
+            && let Some(allowed_channels) = permissions.channel_filter.get(&guild_id.get())
+            && !allowed_channels.is_empty()
+        {
+            let parent_channel_id = metadata
+                .get("discord_parent_channel_id")
+                .and_then(|v| v.as_u64());
+
+            let direct_match = allowed_channels.contains(&reaction.channel_id.get());
+            let parent_match = parent_channel_id.is_some_and(|pid| allowed_channels.contains(&pid));
+
+            if !direct_match && !parent_match {
+                return;
+            }
+        }
+
+        let message_text = match reaction.message(&ctx).await {
+            Ok(message) => Some(message.content).filter(|text| !text.trim().is_empty()),
+            Err(error) => {
+                tracing::debug!(%error, "failed to fetch reacted-to Discord message");
+                None
+            }
+        };
+
+        let base_conversation_id = match reaction.guild_id {
+            Some(guild_id) => format!("discord:{}:{}", guild_id, reaction.channel_id),
+            None => format!("discord:dm:{}", user_id),
+        };
+        let conversation_id =
+            apply_runtime_adapter_to_conversation_id(&self.runtime_key, base_conversation_id);
+
+        let content = MessageContent::Reaction {
+            emoji: emoji.clone(),
+            message_id: reaction.message_id.get().to_string(),
+            message_text,
+        };
+
+        // A reaction is directed at the bot the same way a component click is.
+        let discord_mentioned_bot = false;
+        let discord_reply_to_bot = true;
+        metadata.insert("discord_mentioned_bot".into(), discord_mentioned_bot.into());
+        metadata.insert("discord_reply_to_bot".into(), discord_reply_to_bot.into());
+        metadata.insert(
+            "discord_mentions_or_replies_to_bot".into(),
+            (discord_mentioned_bot || discord_reply_to_bot).into(),
+        );
+        metadata.insert(
+            "discord_user_id".into(),
+            serde_json::Value::Number(user_id.get().into()),
+        );
+        metadata.insert("sender_id".into(), user_id.get().into());
+        metadata.insert(
+            "discord_user_mention".into(),
+            serde_json::Value::String(format!("<@{}>", user_id)),
+        );
+
+        let display_name = reaction
+            .member
+            .as_ref()
+            .map(|member| {
+                member
+                    .nick
+                    .clone()
+                    .or_else(|| member.user.global_name.clone())
+                    .unwrap_or_else(|| member.user.name.clone())
+            })
+            .unwrap_or_else(|| user_id.to_string());
+        let formatted_author = format!("{} (<@{}>)", display_name, user_id);
+        metadata.insert(
+            "sender_display_name".into(),
+            serde_json::Value::String(display_name),
+        );
+
+        let inbound = InboundMessage {
+            id: format!("reaction:{}:{}:{}", reaction.message_id, user_id, emoji),
+            source: "discord".into(),
+            adapter: Some(self.runtime_key.clone()),
+            conversation_id,
+            sender_id: user_id.to_string(),
+            agent_id: None,
+            content,
+            timestamp: chrono::Utc::now(),
+            metadata,
+            formatted_author: Some(formatted_author),
+        };
+
+        if let Err(error) = self.inbound_tx.send(inbound).await {
+            tracing::warn!(
+                %error,
+                "failed to send inbound reaction from Discord (receiver dropped)"
+            );
+        }
+    }
 }
 
 fn is_mention_or_reply_to_bot(message: &Message, bot_user_id: Option<UserId>) -> bool {
@@ -922,10 +1338,22 @@ async fn build_metadata(
         }
     }
 
-    // Try to get channel name and detect threads
-    if let Ok(channel) = message.channel_id.to_channel(&ctx.http).await
-        && let Some(guild_channel) = channel.guild()
-    {
+    // Try to get channel name and detect threads. The gateway cache holds
+    // every channel and active thread of the bot's guilds; only fall back to
+    // the API when the message's channel isn't cached.
+    let guild_channel = match message.guild_id {
+        Some(guild_id) => match cached_guild_channel(ctx, guild_id, message.channel_id) {
+            Some(channel) => Some(channel),
+            None => message
+                .channel_id
+                .to_channel(&ctx.http)
+                .await
+                .ok()
+                .and_then(|channel| channel.guild()),
+        },
+        None => None,
+    };
+    if let Some(guild_channel) = guild_channel {
         metadata.insert(
             "discord_channel_name".into(),
             guild_channel.name.clone().into(),
@@ -935,35 +1363,53 @@ async fn build_metadata(
             guild_channel.name.clone().into(),
         );
 
-        // Threads have a parent_id pointing to the text channel they were created in
+        // Threads have a parent_id pointing to the text or forum channel they
+        // were created in. Forum posts are threads too, so bindings and channel
+        // filters on the forum match every post through the parent ID.
         if guild_channel.thread_metadata.is_some() {
             metadata.insert("discord_is_thread".into(), true.into());
             if let Some(parent_id) = guild_channel.parent_id {
                 metadata.insert("discord_parent_channel_id".into(), parent_id.get().into());
+                if let Some(parent) = cached_guild_channel(ctx, guild_channel.guild_id, parent_id) {
+                    metadata.insert(
+                        "discord_parent_channel_name".into(),
+                        parent.name.clone().into(),
+                    );
+                    if parent.kind == ChannelType::Forum {
+                        metadata.insert("discord_is_forum_post".into(), true.into());
+                    }
+                }
             }
         }
     }
 
-    // Reply-to context: resolve the referenced message's author and content
-    if let Some(referenced) = &message.referenced_message {
+    // Reply-to context: resolve the referenced message's author and content.
+    // The gateway omits the referenced message when Discord doesn't have it
+    // at hand (common for older messages), so fall back to fetching it.
+    let fetched_reference = if message.referenced_message.is_none() {
+        fetch_referenced_message(ctx, message).await
+    } else {
+        None
+    };
+    if let Some(referenced) = message
+        .referenced_message
+        .as_deref()
+        .or(fetched_reference.as_ref())
+    {
         let reply_author = referenced
             .author
             .global_name
             .as_deref()
             .unwrap_or(&referenced.author.name);
-        metadata.insert("reply_to_author".into(), reply_author.into());
+        metadata.insert(
+            crate::metadata_keys::REPLY_TO_AUTHOR.into(),
+            reply_author.into(),
+        );
         metadata.insert("reply_to_is_bot".into(), referenced.author.bot.into());
 
         let reply_content = resolve_mentions(&referenced.content, &referenced.mentions);
         // Truncate to avoid bloating context with long quoted messages
-        let truncated = if reply_content.len() > 200 {
-            format!(
-                "{}...",
-                &reply_content[..reply_content.floor_char_boundary(200)]
-            )
-        } else {
-            reply_content
-        };
+        let truncated = crate::messaging::truncate_reply_text(&reply_content);
         metadata.insert("reply_to_content".into(), truncated.clone().into());
         metadata.insert(crate::metadata_keys::REPLY_TO_TEXT.into(), truncated.into());
     }
@@ -984,6 +1430,87 @@ async fn build_metadata(
     (metadata, formatted_author)
 }
 
+/// Best-effort fetch of the message a reply points at, for replies whose
+/// gateway payload didn't include it. Returns None on any failure.
+async fn fetch_referenced_message(ctx: &Context, message: &Message) -> Option<Message> {
+    let reference = message.message_reference.as_ref()?;
+    if reference.kind != MessageReferenceKind::Default {
+        return None;
+    }
+    let message_id = reference.message_id?;
+
+    match tokio::time::timeout(
+        REPLY_FETCH_TIMEOUT,
+        reference.channel_id.message(ctx, message_id),
+    )
+    .await
+    {
+        Ok(Ok(referenced)) => Some(referenced),
+        Ok(Err(error)) => {
+            tracing::debug!(%error, "failed to fetch discord reply target");
+            None
+        }
+        Err(_) => {
+            tracing::debug!("timed out fetching discord reply target");
+            None
+        }
+    }
+}
+
+/// A guild channel or active thread from the gateway cache.
+fn cached_guild_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Option<GuildChannel> {
+    let guild = ctx.cache.guild(guild_id)?;
+    guild.channels.get(&channel_id).cloned().or_else(|| {
+        guild
+            .threads
+            .iter()
+            .find(|thread| thread.id == channel_id)
+            .cloned()
+    })
+}
+
+/// Whether a channel is a forum, where messages can only be posted as new
+/// threads. Lookup failures are treated as "not a forum".
+async fn is_forum_channel(http: &Http, channel_id: ChannelId) -> bool {
+    match channel_id.to_channel(http).await {
+        Ok(channel) => channel
+            .guild()
+            .is_some_and(|channel| channel.kind == ChannelType::Forum),
+        Err(error) => {
+            tracing::debug!(%error, %channel_id, "failed to look up discord channel type");
+            false
+        }
+    }
+}
+
+/// Derive a forum post title from message text: the first non-empty line,
+/// capped at Discord's 100 character limit.
+fn forum_post_title(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("New post");
+    match line.char_indices().nth(100) {
+        Some((end, _)) => line[..end].to_string(),
+        None => line.to_string(),
+    }
+}
+
+/// Fit in-progress stream text into one Discord message.
+fn truncate_stream_text(text: &str) -> String {
+    if text.len() > 2000 {
+        let end = text.floor_char_boundary(1997);
+        format!("{}...", &text[..end])
+    } else {
+        text.to_string()
+    }
+}
+
 /// Split a message into chunks that fit within Discord's 2000 char limit.
 /// Tries to split at newlines, then spaces, then hard-cuts.
 fn split_message(text: &str, max_len: usize) -> Vec<String> {
@@ -1212,6 +1739,200 @@ mod tests {
     use super::*;
     use crate::{Button, ButtonStyle, Card, CardField, InteractiveElements, Poll};
 
+    #[test]
+    fn edit_batcher_coalesces_chunks_within_interval() {
+        let start = std::time::Instant::now();
+        let interval = std::time::Duration::from_millis(1000);
+        let mut edits = EditBatcher::new(interval, start);
+
+        assert_eq!(edits.push("he".into(), start), None);
+        assert_eq!(
+            edits.push("hello".into(), start + interval),
+            Some("hello".into())
+        );
+        assert_eq!(edits.push("hello wo".into(), start + interval), None);
+        assert_eq!(edits.push("hello world".into(), start + interval), None);
+
+        assert_eq!(edits.finish(), Some(("hello world".into(), true)));
+    }
+
+    #[test]
+    fn edit_batcher_finish_reports_already_written_text() {
+        let start = std::time::Instant::now();
+        let mut edits = EditBatcher::new(std::time::Duration::ZERO, start);
+
+        assert_eq!(edits.push("done".into(), start), Some("done".into()));
+        assert_eq!(edits.finish(), Some(("done".into(), false)));
+        assert_eq!(
+            EditBatcher::new(std::time::Duration::ZERO, start).finish(),
+            None
+        );
+    }
+
+    /// A request the fake Discord API received.
+    #[derive(Debug, Clone, PartialEq)]
+    enum DiscordCall {
+        Send(String),
+        Edit(String),
+    }
+
+    fn fake_message(content: &str) -> axum::Json<serde_json::Value> {
+        axum::Json(serde_json::json!({
+            "id": "20",
+            "channel_id": "10",
+            "author": {"id": "30", "username": "spacebot"},
+            "content": content,
+            "timestamp": "2026-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        }))
+    }
+
+    /// Serve the message endpoints `respond` uses and record every call.
+    async fn fake_discord() -> (Arc<Http>, Arc<std::sync::Mutex<Vec<DiscordCall>>>) {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let on_send = {
+            let calls = calls.clone();
+            move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                let content = body["content"].as_str().unwrap_or_default().to_string();
+                calls
+                    .lock()
+                    .unwrap()
+                    .push(DiscordCall::Send(content.clone()));
+                fake_message(&content)
+            }
+        };
+        let on_edit = {
+            let calls = calls.clone();
+            move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                let content = body["content"].as_str().unwrap_or_default().to_string();
+                calls
+                    .lock()
+                    .unwrap()
+                    .push(DiscordCall::Edit(content.clone()));
+                fake_message(&content)
+            }
+        };
+        let router = axum::Router::new()
+            .route(
+                "/api/v10/channels/{channel_id}/messages",
+                axum::routing::post(on_send),
+            )
+            .route(
+                "/api/v10/channels/{channel_id}/messages/{message_id}",
+                axum::routing::patch(on_edit),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let http = serenity::http::HttpBuilder::new("test-token")
+            .proxy(format!("http://{addr}"))
+            .ratelimiter_disabled(true)
+            .build();
+        (Arc::new(http), calls)
+    }
+
+    /// Send `text` through the pacer and hand everything it emits to the
+    /// adapter, returning the Discord API calls `respond` made.
+    async fn respond_paced(
+        stream_edit_interval: Option<std::time::Duration>,
+        text: &str,
+    ) -> Vec<DiscordCall> {
+        let (http, calls) = fake_discord().await;
+        let adapter = DiscordAdapter::new("discord", "test-token", Default::default())
+            .with_stream_edits(stream_edit_interval);
+        *adapter.http.write().await = Some(http);
+
+        let target = InboundMessage {
+            id: "reply-1".into(),
+            source: "discord".into(),
+            metadata: HashMap::from([("discord_channel_id".to_string(), 10.into())]),
+            ..InboundMessage::empty()
+        };
+        let (tx, paced_rx) = mpsc::channel(256);
+        crate::RoutedSender::new(tx, target)
+            .send(OutboundResponse::Text(text.into()))
+            .await
+            .unwrap();
+        let mut rx = crate::messaging::pacing::spawn_pacer_with(paced_rx, || 100);
+        while let Some(routed) = rx.recv().await {
+            adapter
+                .respond(&routed.target, routed.response)
+                .await
+                .unwrap();
+        }
+
+        calls.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn paced_reply_edits_placeholder_as_it_streams() {
+        let text = "Streaming this reply into the placeholder a few characters at a time.";
+        let calls = respond_paced(Some(std::time::Duration::ZERO), text).await;
+
+        let (placeholder, edits) = calls.split_first().unwrap();
+        assert_eq!(placeholder, &DiscordCall::Send("\u{200B}".into()));
+        let edits: Vec<&str> = edits
+            .iter()
+            .map(|call| match call {
+                DiscordCall::Edit(content) => content.as_str(),
+                DiscordCall::Send(content) => panic!("unexpected new message {content:?}"),
+            })
+            .collect();
+        assert!(edits.len() > 1, "placeholder should be edited repeatedly");
+        assert!(edits.windows(2).all(|pair| pair[1].starts_with(pair[0])));
+        assert_eq!(edits.last().copied(), Some(text));
+    }
+
+    #[tokio::test]
+    async fn paced_reply_is_finalized_when_edits_are_throttled() {
+        let text = "Every edit lands inside the interval, so StreamEnd writes it.";
+        let calls = respond_paced(Some(std::time::Duration::from_secs(3600)), text).await;
+
+        assert_eq!(
+            calls,
+            vec![
+                DiscordCall::Send("\u{200B}".into()),
+                DiscordCall::Edit(text.into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn paced_reply_is_sent_whole_without_stream_edits() {
+        let text = "With stream edits off the reply goes out once it is complete.";
+        let calls = respond_paced(None, text).await;
+
+        assert_eq!(calls, vec![DiscordCall::Send(text.into())]);
+    }
+
+    #[test]
+    fn interaction_tokens_expire_after_fifteen_minutes() {
+        let fresh = InteractionToken {
+            token: "fresh".into(),
+            received_at: std::time::Instant::now(),
+        };
+        assert!(!fresh.is_expired());
+
+        let Some(received_at) = std::time::Instant::now().checked_sub(INTERACTION_TOKEN_TTL) else {
+            return;
+        };
+        let stale = InteractionToken {
+            token: "stale".into(),
+            received_at,
+        };
+        assert!(stale.is_expired());
+    }
+
     #[test]
     fn test_build_embed_limits() {
         let mut card = Card::default();
@@ -1326,4 +2047,13 @@ mod tests {
         assert_eq!(parts.text, "Status\n\nAll green");
         assert!(!parts.dropped_invalid_poll);
     }
+
+    #[test]
+    fn test_forum_post_title_uses_first_non_empty_line() {
+        assert_eq!(forum_post_title("\n  Daily report  \nbody"), "Daily report");
+        assert_eq!(forum_post_title(""), "New post");
+
+        let long = "é".repeat(150);
+        assert_eq!(forum_post_title(&long).chars().count(), 100);
+    }
 }
@@ -477,8 +477,13 @@ impl Messaging for EmailAdapter {
                 )
                 .await?;
             }
+            // Reasoning would arrive as its own email every turn, so it's dropped.
+            // A lone sticker fallback isn't worth an email either.
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Sticker { .. }
+            | OutboundResponse::HomeView { .. }
+            | OutboundResponse::Reasoning(_)
             | OutboundResponse::Status(_) => {}
             OutboundResponse::Ephemeral { text, .. } => {
                 self.send_email(
@@ -562,6 +567,9 @@ impl Messaging for EmailAdapter {
             }
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::Sticker { .. }
+            | OutboundResponse::HomeView { .. }
+            | OutboundResponse::Reasoning(_)
             | OutboundResponse::Status(_)
             | OutboundResponse::StreamStart
             | OutboundResponse::StreamChunk(_)
@@ -0,0 +1,272 @@
+//! Response footers for AI disclosure.
+//!
+//! `MessagingManager` appends the sending agent's `response_footer` to text
+//! replies and proactive messages on their way to an adapter, so channel
+//! replies, cron deliveries, broadcasts, and cross-channel sends all carry
+//! it. Streamed replies are edited in place as they grow, so their chunks
+//! pass through unchanged and the footer is added once, in a final chunk
+//! sent just before `StreamEnd`. Reactions, files, stickers, ephemeral
+//! messages, and statuses pass through unchanged. The footer is added on delivery only, so it never
+//! shows up in the conversation history the model reads back.
+
+use crate::config::RuntimeConfig;
+use crate::{InboundMessage, OutboundResponse};
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Template variable for the agent's display name.
+const AGENT_NAME_VAR: &str = "{{agent_name}}";
+
+/// Footer settings for one agent.
+struct AgentFooter {
+    runtime_config: Arc<RuntimeConfig>,
+    agent_name: String,
+}
+
+/// Response footers of every agent, keyed by agent ID.
+#[derive(Default)]
+pub struct ResponseFooters {
+    agents: RwLock<HashMap<String, AgentFooter>>,
+    /// Streams in progress, so the footer can be added when they end.
+    streams: Mutex<StreamFooters>,
+}
+
+impl ResponseFooters {
+    /// Footer `agent_id`'s messages with its `response_footer`. The template
+    /// is read per response, so config reloads apply immediately.
+    pub fn register(
+        &self,
+        agent_id: impl Into<String>,
+        runtime_config: Arc<RuntimeConfig>,
+        agent_name: impl Into<String>,
+    ) {
+        self.agents
+            .write()
+            .unwrap_or_else(|error| error.into_inner())
+            .insert(
+                agent_id.into(),
+                AgentFooter {
+                    runtime_config,
+                    agent_name: agent_name.into(),
+                },
+            );
+    }
+
+    /// `response` with `agent_id`'s footer appended, if it has one.
+    pub fn apply(&self, agent_id: Option<&str>, response: OutboundResponse) -> OutboundResponse {
+        match self.footer(agent_id) {
+            Some(footer) => apply_footer(response, &footer),
+            None => response,
+        }
+    }
+
+    /// The responses to deliver for a reply to `message`: `response` with
+    /// the footer applied, or for a footed agent's `StreamEnd`, a last chunk
+    /// with the footer followed by the `StreamEnd`.
+    pub fn apply_reply(
+        &self,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> Vec<OutboundResponse> {
+        let Some(footer) = self.footer(message.agent_id.as_deref()) else {
+            return vec![response];
+        };
+        self.streams
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .apply(&message.conversation_id, &footer, response)
+    }
+
+    fn footer(&self, agent_id: Option<&str>) -> Option<String> {
+        let agents = self
+            .agents
+            .read()
+            .unwrap_or_else(|error| error.into_inner());
+        let agent = agents.get(agent_id?)?;
+        let template = agent.runtime_config.response_footer.load();
+        render_footer(template.as_deref()?, &agent.agent_name)
+    }
+}
+
+/// The latest chunk of each footed stream in progress, by conversation.
+#[derive(Default)]
+struct StreamFooters {
+    last_chunks: HashMap<String, String>,
+}
+
+impl StreamFooters {
+    fn apply(
+        &mut self,
+        conversation_id: &str,
+        footer: &str,
+        response: OutboundResponse,
+    ) -> Vec<OutboundResponse> {
+        match response {
+            OutboundResponse::StreamStart => {
+                self.last_chunks.remove(conversation_id);
+                vec![response]
+            }
+            OutboundResponse::StreamChunk(text) => {
+                self.last_chunks
+                    .insert(conversation_id.to_string(), text.clone());
+                vec![OutboundResponse::StreamChunk(text)]
+            }
+            OutboundResponse::StreamEnd => match self.last_chunks.remove(conversation_id) {
+                Some(text) => vec![
+                    OutboundResponse::StreamChunk(with_footer(text, footer)),
+                    OutboundResponse::StreamEnd,
+                ],
+                None => vec![OutboundResponse::StreamEnd],
+            },
+            other => vec![apply_footer(other, footer)],
+        }
+    }
+}
+
+/// Expand `{{agent_name}}` in a footer template. Blank footers disable it.
+fn render_footer(template: &str, agent_name: &str) -> Option<String> {
+    let footer = template.trim();
+    if footer.is_empty() {
+        return None;
+    }
+    Some(
+        footer
+            .replace(AGENT_NAME_VAR, agent_name)
+            .replace("{{ agent_name }}", agent_name),
+    )
+}
+
+fn with_footer(text: String, footer: &str) -> String {
+    if text.trim_end().ends_with(footer) {
+        text
+    } else if text.trim().is_empty() {
+        footer.to_string()
+    } else {
+        format!("{}\n\n{footer}", text.trim_end())
+    }
+}
+
+/// Append `footer` to the text of a reply. Other responses, including stream
+/// frames, are unchanged.
+fn apply_footer(response: OutboundResponse, footer: &str) -> OutboundResponse {
+    match response {
+        OutboundResponse::Text(text) => OutboundResponse::Text(with_footer(text, footer)),
+        OutboundResponse::ThreadReply { thread_name, text } => OutboundResponse::ThreadReply {
+            thread_name,
+            text: with_footer(text, footer),
+        },
+        OutboundResponse::RichMessage {
+            text,
+            blocks,
+            cards,
+            interactive_elements,
+            poll,
+        } => OutboundResponse::RichMessage {
+            text: with_footer(text, footer),
+            blocks,
+            cards,
+            interactive_elements,
+            poll,
+        },
+        OutboundResponse::ScheduledMessage { text, post_at } => {
+            OutboundResponse::ScheduledMessage {
+                text: with_footer(text, footer),
+                post_at,
+            }
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOOTER: &str = "— Support Bot (AI)";
+
+    fn text(response: &OutboundResponse) -> String {
+        match response {
+            OutboundResponse::Text(text) | OutboundResponse::StreamChunk(text) => text.clone(),
+            other => format!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn renders_agent_name_and_disables_when_blank() {
+        assert_eq!(
+            render_footer("— {{agent_name}} (AI)", "Support Bot").as_deref(),
+            Some(FOOTER)
+        );
+        assert_eq!(
+            render_footer("— {{ agent_name }} (AI)", "Support Bot").as_deref(),
+            Some(FOOTER)
+        );
+        assert_eq!(render_footer("   ", "Support Bot"), None);
+    }
+
+    #[test]
+    fn text_replies_get_the_footer_and_other_responses_do_not() {
+        let replied = apply_footer(OutboundResponse::Text("hi there\n".into()), FOOTER);
+        assert_eq!(text(&replied), format!("hi there\n\n{FOOTER}"));
+
+        let reacted = apply_footer(OutboundResponse::Reaction("👍".into()), FOOTER);
+        assert!(matches!(&reacted, OutboundResponse::Reaction(emoji) if emoji == "👍"));
+
+        let file = apply_footer(
+            OutboundResponse::File {
+                filename: "a.txt".into(),
+                data: Vec::new(),
+                mime_type: "text/plain".into(),
+                caption: Some("here".into()),
+            },
+            FOOTER,
+        );
+        assert!(matches!(
+            &file,
+            OutboundResponse::File { caption: Some(caption), .. } if caption == "here"
+        ));
+
+        // Already footed text isn't footed twice.
+        let again = apply_footer(replied, FOOTER);
+        assert_eq!(text(&again), format!("hi there\n\n{FOOTER}"));
+    }
+
+    #[test]
+    fn stream_chunks_pass_through_and_the_footer_lands_at_stream_end() {
+        let chunk = apply_footer(OutboundResponse::StreamChunk("hel".into()), FOOTER);
+        assert_eq!(text(&chunk), "hel");
+
+        let mut streams = StreamFooters::default();
+        let mut send = |response| streams.apply("conv", FOOTER, response);
+        assert!(matches!(
+            send(OutboundResponse::StreamStart).as_slice(),
+            [OutboundResponse::StreamStart]
+        ));
+        for partial in ["hel", "hello"] {
+            let sent = send(OutboundResponse::StreamChunk(partial.into()));
+            assert_eq!(sent.len(), 1);
+            assert_eq!(text(&sent[0]), partial);
+        }
+
+        let ended = send(OutboundResponse::StreamEnd);
+        assert_eq!(ended.len(), 2);
+        assert_eq!(text(&ended[0]), format!("hello\n\n{FOOTER}"));
+        assert!(matches!(ended[1], OutboundResponse::StreamEnd));
+
+        // The stream is forgotten once it ends.
+        assert!(matches!(
+            send(OutboundResponse::StreamEnd).as_slice(),
+            [OutboundResponse::StreamEnd]
+        ));
+    }
+
+    #[test]
+    fn unregistered_agents_send_unchanged() {
+        let footers = ResponseFooters::default();
+        let response = footers.apply(Some("main"), OutboundResponse::Text("plain".into()));
+        assert_eq!(text(&response), "plain");
+        let response = footers.apply(None, OutboundResponse::Text("plain".into()));
+        assert_eq!(text(&response), "plain");
+    }
+}
@@ -1,5 +1,8 @@
 //! MessagingManager: Fan-in and routing for all adapters.
 
+use crate::config::RuntimeConfig;
+use crate::messaging::dedup::{self, OutboundDedupCache};
+use crate::messaging::footer::ResponseFooters;
 use crate::messaging::traits::{HistoryMessage, InboundStream, Messaging, MessagingDyn};
 use crate::{InboundMessage, OutboundResponse, StatusUpdate};
 
@@ -19,6 +22,11 @@ pub struct MessagingManager {
     fan_in_tx: mpsc::Sender<InboundMessage>,
     /// Receiver side, taken once by `start()`.
     fan_in_rx: RwLock<Option<mpsc::Receiver<InboundMessage>>>,
+    /// Idempotency keys of recent outbound sends, so a response re-sent for
+    /// the same turn isn't posted twice.
+    dedup: OutboundDedupCache,
+    /// Each agent's `response_footer`, appended to what it sends.
+    footers: ResponseFooters,
 }
 
 impl MessagingManager {
@@ -28,9 +36,21 @@ impl MessagingManager {
             adapters: RwLock::new(HashMap::new()),
             fan_in_tx,
             fan_in_rx: RwLock::new(Some(fan_in_rx)),
+            dedup: OutboundDedupCache::new(),
+            footers: ResponseFooters::default(),
         }
     }
 
+    /// Append `agent_id`'s `response_footer` to its replies and broadcasts.
+    pub fn register_agent_footer(
+        &self,
+        agent_id: impl Into<String>,
+        runtime_config: Arc<RuntimeConfig>,
+        agent_name: impl Into<String>,
+    ) {
+        self.footers.register(agent_id, runtime_config, agent_name);
+    }
+
     /// Register an adapter (before start). Use `register_and_start` for runtime addition.
     pub async fn register(&self, adapter: impl Messaging) {
         let name = adapter.name().to_string();
@@ -214,18 +234,90 @@ impl MessagingManager {
             .map_err(|_| crate::error::Error::Other(anyhow::anyhow!("fan-in channel closed")))
     }
 
+    /// Sends of the same response that fail before connecting are retried.
+    const OUTBOUND_SEND_ATTEMPTS: u32 = 3;
+
     /// Route a response back to the correct adapter based on message source.
+    ///
+    /// Sends carrying an idempotency key (see [`dedup::idempotency_key`]) are
+    /// claimed before delivery and dropped if the same key was already sent.
+    /// A send that never reached the platform is retried under the same
+    /// claim. A send that certainly failed releases its key so a later replay
+    /// can go out; one that may have been delivered keeps it.
     pub async fn respond(
         &self,
         message: &InboundMessage,
         response: OutboundResponse,
+        idempotency_key: Option<&str>,
     ) -> crate::Result<()> {
         let adapters = self.adapters.read().await;
         let adapter_key = message.adapter_key();
         let adapter = adapters
             .get(adapter_key)
             .with_context(|| format!("no messaging adapter named '{}'", adapter_key))?;
-        adapter.respond(message, response).await
+
+        if let Some(key) = idempotency_key
+            && !self.dedup.claim(key)
+        {
+            tracing::debug!(
+                adapter = %adapter_key,
+                idempotency_key = %key,
+                "skipping duplicate outbound response"
+            );
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for response in self.footers.apply_reply(message, response) {
+            result = self.deliver(adapter.as_ref(), message, response).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        if let Err(error) = &result
+            && let Some(key) = idempotency_key
+        {
+            if dedup::send_definitely_failed(error) {
+                self.dedup.release(key);
+            } else {
+                tracing::debug!(
+                    adapter = %adapter_key,
+                    idempotency_key = %key,
+                    "keeping idempotency key after a send that may have been delivered"
+                );
+            }
+        }
+        result
+    }
+
+    /// Hand `response` to `adapter`, retrying sends that never connected.
+    async fn deliver(
+        &self,
+        adapter: &dyn MessagingDyn,
+        message: &InboundMessage,
+        response: OutboundResponse,
+    ) -> crate::Result<()> {
+        let adapter_key = message.adapter_key();
+        let mut attempt = 1;
+        loop {
+            let result = adapter.respond(message, response.clone()).await;
+            match &result {
+                Err(error)
+                    if attempt < Self::OUTBOUND_SEND_ATTEMPTS && dedup::send_retryable(error) =>
+                {
+                    tracing::debug!(
+                        adapter = %adapter_key,
+                        attempt,
+                        %error,
+                        "outbound send could not connect, retrying"
+                    );
+                    tokio::time::sleep(std::time::Duration::from_millis(500 * u64::from(attempt)))
+                        .await;
+                    attempt += 1;
+                }
+                _ => break result,
+            }
+        }
     }
 
     /// Route a status update to the correct adapter.
@@ -242,9 +334,10 @@ impl MessagingManager {
         adapter.send_status(message, status).await
     }
 
-    /// Send a proactive message through a specific adapter.
+    /// Send a proactive message from `agent_id` through a specific adapter.
     pub async fn broadcast(
         &self,
+        agent_id: &str,
         adapter_name: &str,
         target: &str,
         response: OutboundResponse,
@@ -253,6 +346,7 @@ impl MessagingManager {
         let adapter = adapters
             .get(adapter_name)
             .with_context(|| format!("no messaging adapter named '{adapter_name}'"))?;
+        let response = self.footers.apply(Some(agent_id), response);
         adapter.broadcast(target, response).await
     }
 
@@ -316,3 +410,122 @@ impl Default for MessagingManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts deliveries. The first `connect_failures` sends fail as if the
+    /// platform couldn't be reached.
+    struct CountingAdapter {
+        delivered: Arc<AtomicUsize>,
+        connect_failures: AtomicUsize,
+    }
+
+    impl Messaging for CountingAdapter {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        async fn start(&self) -> crate::Result<InboundStream> {
+            Ok(Box::pin(futures::stream::empty()))
+        }
+
+        async fn respond(
+            &self,
+            _message: &InboundMessage,
+            _response: OutboundResponse,
+        ) -> crate::Result<()> {
+            let failures_left = self.connect_failures.load(Ordering::SeqCst);
+            if failures_left > 0 {
+                self.connect_failures
+                    .store(failures_left - 1, Ordering::SeqCst);
+                return Err(connect_error().await);
+            }
+            self.delivered.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn health_check(&self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A real connection-refused error from reqwest.
+    async fn connect_error() -> crate::Error {
+        let closed_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let error = reqwest::get(format!("http://127.0.0.1:{closed_port}"))
+            .await
+            .unwrap_err();
+        crate::Error::Other(anyhow::Error::new(error))
+    }
+
+    async fn manager(connect_failures: usize) -> (MessagingManager, Arc<AtomicUsize>) {
+        let delivered = Arc::new(AtomicUsize::new(0));
+        let manager = MessagingManager::new();
+        manager
+            .register(CountingAdapter {
+                delivered: delivered.clone(),
+                connect_failures: AtomicUsize::new(connect_failures),
+            })
+            .await;
+        (manager, delivered)
+    }
+
+    fn target() -> InboundMessage {
+        InboundMessage {
+            id: "m1".into(),
+            source: "test".into(),
+            conversation_id: "test:1".into(),
+            ..InboundMessage::empty()
+        }
+    }
+
+    #[tokio::test]
+    async fn replayed_response_is_delivered_once() {
+        let (manager, delivered) = manager(0).await;
+        let target = target();
+        let response = OutboundResponse::Text("hello".into());
+
+        for _ in 0..2 {
+            let key = dedup::idempotency_key(&target, &response);
+            manager
+                .respond(&target, response.clone(), key.as_deref())
+                .await
+                .unwrap();
+        }
+        assert_eq!(delivered.load(Ordering::SeqCst), 1);
+
+        // A different reply in the same turn still goes out.
+        let other = OutboundResponse::Text("something else".into());
+        let key = dedup::idempotency_key(&target, &other);
+        manager
+            .respond(&target, other, key.as_deref())
+            .await
+            .unwrap();
+        assert_eq!(delivered.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn send_that_could_not_connect_is_retried_under_the_same_key() {
+        let (manager, delivered) = manager(1).await;
+        let target = target();
+        let response = OutboundResponse::Text("hello".into());
+        let key = dedup::idempotency_key(&target, &response);
+
+        manager
+            .respond(&target, response.clone(), key.as_deref())
+            .await
+            .unwrap();
+        manager
+            .respond(&target, response, key.as_deref())
+            .await
+            .unwrap();
+        assert_eq!(delivered.load(Ordering::SeqCst), 1);
+    }
+}
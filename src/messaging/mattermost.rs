@@ -48,7 +48,8 @@ struct ActiveStream {
     #[allow(dead_code)]
     channel_id: Arc<str>,
     last_edit: Instant,
-    accumulated_text: String,
+    /// Newest cumulative text of the stream.
+    text: String,
 }
 
 struct MessageBuildContext<'a> {
@@ -703,7 +704,7 @@ impl Messaging for MattermostAdapter {
                         post_id: post.id.into(),
                         channel_id: channel_id.to_string().into(),
                         last_edit: Instant::now(),
-                        accumulated_text: String::new(),
+                        text: String::new(),
                     },
                 );
             }
@@ -712,17 +713,14 @@ impl Messaging for MattermostAdapter {
                 let pending_edit = {
                     let mut active_messages = self.active_messages.write().await;
                     if let Some(active) = active_messages.get_mut(&message.id) {
-                        active.accumulated_text.push_str(&chunk);
+                        active.text = chunk;
 
                         if active.last_edit.elapsed() > STREAM_EDIT_THROTTLE {
-                            let display_text = if active.accumulated_text.len() > MAX_MESSAGE_LENGTH
-                            {
-                                let end = active
-                                    .accumulated_text
-                                    .floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
-                                format!("{}...", &active.accumulated_text[..end])
+                            let display_text = if active.text.len() > MAX_MESSAGE_LENGTH {
+                                let end = active.text.floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
+                                format!("{}...", &active.text[..end])
                             } else {
-                                active.accumulated_text.clone()
+                                active.text.clone()
                             };
                             active.last_edit = Instant::now();
                             Some((active.post_id.clone(), display_text))
@@ -753,7 +751,7 @@ impl Messaging for MattermostAdapter {
                             .and_then(|v| v.as_str())
                     });
                 if let Some(active) = self.active_messages.write().await.remove(&message.id) {
-                    let chunks = split_message(&active.accumulated_text, MAX_MESSAGE_LENGTH);
+                    let chunks = split_message(&active.text, MAX_MESSAGE_LENGTH);
                     let mut first = true;
                     for chunk in chunks {
                         if first {
@@ -889,6 +887,41 @@ impl Messaging for MattermostAdapter {
                 }
             }
 
+            OutboundResponse::Reasoning(reasoning) => {
+                let root_id = message
+                    .metadata
+                    .get("mattermost_root_id")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| {
+                        message
+                            .metadata
+                            .get(crate::metadata_keys::REPLY_TO_MESSAGE_ID)
+                            .and_then(|v| v.as_str())
+                    });
+                let text = OutboundResponse::reasoning_as_quote(&reasoning);
+                for chunk in split_message(&text, MAX_MESSAGE_LENGTH) {
+                    self.create_post(channel_id, &chunk, root_id).await?;
+                }
+            }
+
+            // Mattermost has no stickers — post the fallback text instead.
+            OutboundResponse::Sticker {
+                fallback: Some(text),
+                ..
+            } => {
+                let root_id = message
+                    .metadata
+                    .get("mattermost_root_id")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| {
+                        message
+                            .metadata
+                            .get(crate::metadata_keys::REPLY_TO_MESSAGE_ID)
+                            .and_then(|v| v.as_str())
+                    });
+                self.create_post(channel_id, &text, root_id).await?;
+            }
+
             _ => {
                 tracing::debug!(
                     ?response,
@@ -911,7 +944,7 @@ impl Messaging for MattermostAdapter {
             StatusUpdate::Thinking => {
                 self.start_typing(channel_id).await;
             }
-            StatusUpdate::StopTyping => {
+            StatusUpdate::StopTyping | StatusUpdate::Skipped { .. } => {
                 self.stop_typing(channel_id).await;
             }
             _ => {}
@@ -0,0 +1,259 @@
+//! Typing-speed pacing for replies.
+//!
+//! Channel replies come back from the model whole, so when `[stream_pacing]
+//! chars_per_second` is set a plain-text reply is revealed as a stream
+//! instead: `StreamStart`, then `StreamChunk`s carrying the cumulative text
+//! revealed so far, then `StreamEnd`. Only platforms that render streams as
+//! an edited message (or, for webchat, as live SSE deltas) get paced replies;
+//! everywhere else the reply is sent as a single `Text`.
+//!
+//! Pacing runs in a per-conversation forwarder task between the channel and
+//! its outbound router, so the channel and the reply tool hand off the text
+//! and move on while it is revealed.
+
+use crate::config::RuntimeConfig;
+use crate::tools::char_prefix;
+use crate::{OutboundResponse, RoutedResponse};
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::SendError;
+
+/// Minimum gap between paced chunks. Every chunk becomes a message edit on
+/// the platform, so revealing one character at a time would hit rate limits.
+const PACED_EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Platforms whose adapters render a stream by editing a placeholder and
+/// split overflow into follow-up messages when it ends.
+/// Webchat has no adapter send path; its streams reach the dashboard as SSE
+/// deltas.
+const STREAMING_PLATFORMS: &[&str] = &["discord", "mattermost", "telegram", "webchat"];
+
+/// Whether replies routed to `adapter` (a runtime key such as
+/// `"discord"` or `"telegram:support"`) can be paced.
+pub fn renders_streams(adapter: &str) -> bool {
+    let platform = adapter
+        .split_once(':')
+        .map_or(adapter, |(platform, _)| platform);
+    STREAMING_PLATFORMS.contains(&platform)
+}
+
+/// Put a pacing stage in front of a conversation's outbound channel.
+///
+/// Spawns a task that forwards everything from `inbound` to the returned
+/// receiver in order, turning each plain-text reply into a paced stream
+/// when `[stream_pacing] chars_per_second` is currently non-zero and the
+/// reply's target renders streams. The task ends when `inbound` closes or
+/// the returned receiver is dropped.
+pub fn spawn_pacer(
+    inbound: mpsc::Receiver<RoutedResponse>,
+    runtime_config: Arc<RuntimeConfig>,
+) -> mpsc::Receiver<RoutedResponse> {
+    spawn_pacer_with(inbound, move || {
+        runtime_config.stream_pacing.load().chars_per_second
+    })
+}
+
+/// [`spawn_pacer`] with the rate read from `chars_per_second`.
+pub(crate) fn spawn_pacer_with(
+    inbound: mpsc::Receiver<RoutedResponse>,
+    chars_per_second: impl Fn() -> u32 + Send + 'static,
+) -> mpsc::Receiver<RoutedResponse> {
+    let (tx, rx) = mpsc::channel(inbound.max_capacity());
+    tokio::spawn(forward_paced(
+        inbound,
+        tx,
+        chars_per_second,
+        PACED_EMIT_INTERVAL,
+    ));
+    rx
+}
+
+/// Forward `inbound` to `outbound`, pacing text replies at the rate
+/// `chars_per_second` reports when each reply arrives.
+async fn forward_paced(
+    mut inbound: mpsc::Receiver<RoutedResponse>,
+    outbound: mpsc::Sender<RoutedResponse>,
+    chars_per_second: impl Fn() -> u32,
+    emit_interval: Duration,
+) {
+    while let Some(routed) = inbound.recv().await {
+        let rate = chars_per_second();
+        let sent = match routed {
+            RoutedResponse {
+                response: OutboundResponse::Text(text),
+                target,
+                usage,
+            } if rate > 0 && !text.trim().is_empty() && renders_streams(target.adapter_key()) => {
+                send_paced(&outbound, &text, target, usage, rate, emit_interval).await
+            }
+            routed => outbound.send(routed).await,
+        };
+        if sent.is_err() {
+            break;
+        }
+    }
+}
+
+/// Reveal a `Text` reply as a stream. Usage is reported with `StreamEnd`, the
+/// frame that completes the reply.
+async fn send_paced(
+    outbound: &mpsc::Sender<RoutedResponse>,
+    text: &str,
+    target: crate::InboundMessage,
+    usage: Option<crate::llm::usage::MessageUsage>,
+    chars_per_second: u32,
+    emit_interval: Duration,
+) -> Result<(), SendError<RoutedResponse>> {
+    let frame = |response| RoutedResponse {
+        response,
+        target: target.clone(),
+        usage: None,
+    };
+    let total = text.chars().count();
+    let started = Instant::now();
+    let mut shown = 0;
+
+    outbound.send(frame(OutboundResponse::StreamStart)).await?;
+    while shown < total {
+        tokio::time::sleep(emit_interval).await;
+        let budget = started.elapsed().as_secs_f64() * f64::from(chars_per_second);
+        let revealed = (budget as usize).min(total);
+        if revealed > shown {
+            shown = revealed;
+            outbound
+                .send(frame(OutboundResponse::StreamChunk(
+                    char_prefix(text, shown).to_string(),
+                )))
+                .await?;
+        }
+    }
+    outbound
+        .send(RoutedResponse {
+            response: OutboundResponse::StreamEnd,
+            target,
+            usage,
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InboundMessage;
+    use crate::llm::usage::MessageUsage;
+
+    fn reply(source: &str, text: &str) -> RoutedResponse {
+        RoutedResponse {
+            response: OutboundResponse::Text(text.into()),
+            target: InboundMessage {
+                source: source.into(),
+                ..InboundMessage::empty()
+            },
+            usage: Some(MessageUsage::default()),
+        }
+    }
+
+    /// Run `replies` through the forwarder at `chars_per_second` and collect
+    /// what comes out the other side.
+    async fn forward(replies: Vec<RoutedResponse>, chars_per_second: u32) -> Vec<RoutedResponse> {
+        let (in_tx, in_rx) = mpsc::channel(64);
+        let (out_tx, mut out_rx) = mpsc::channel(64);
+        for reply in replies {
+            in_tx.send(reply).await.unwrap();
+        }
+        drop(in_tx);
+        forward_paced(
+            in_rx,
+            out_tx,
+            move || chars_per_second,
+            Duration::from_millis(10),
+        )
+        .await;
+
+        let mut forwarded = Vec::new();
+        while let Ok(routed) = out_rx.try_recv() {
+            forwarded.push(routed);
+        }
+        forwarded
+    }
+
+    #[test]
+    fn only_streaming_platforms_are_paced() {
+        assert!(renders_streams("discord"));
+        assert!(renders_streams("telegram:support"));
+        assert!(renders_streams("webchat"));
+        assert!(!renders_streams("slack"));
+        assert!(!renders_streams("webhook"));
+        assert!(!renders_streams("discordish"));
+    }
+
+    #[tokio::test]
+    async fn paced_reply_streams_cumulative_prefixes() {
+        let text = "héllo wörld, this is paced";
+        let forwarded = forward(vec![reply("discord", text)], 400).await;
+
+        assert!(matches!(
+            forwarded.first().map(|routed| &routed.response),
+            Some(OutboundResponse::StreamStart)
+        ));
+        let end = forwarded.last().unwrap();
+        assert!(matches!(end.response, OutboundResponse::StreamEnd));
+        assert!(end.usage.is_some(), "usage rides on the final frame");
+        assert!(
+            forwarded[..forwarded.len() - 1]
+                .iter()
+                .all(|routed| routed.usage.is_none())
+        );
+
+        let chunks: Vec<&str> = forwarded
+            .iter()
+            .filter_map(|routed| match &routed.response {
+                OutboundResponse::StreamChunk(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(
+            chunks.len() > 1,
+            "reply should be revealed over several chunks"
+        );
+        assert!(chunks.windows(2).all(|pair| pair[1].starts_with(pair[0])));
+        assert_eq!(chunks.last(), Some(&text));
+    }
+
+    #[tokio::test]
+    async fn unpaced_reply_is_forwarded_as_text() {
+        let forwarded = forward(vec![reply("slack", "hello")], 40).await;
+        assert!(matches!(
+            forwarded.as_slice(),
+            [RoutedResponse { response: OutboundResponse::Text(text), .. }] if text == "hello"
+        ));
+
+        let forwarded = forward(vec![reply("discord", "hello")], 0).await;
+        assert!(matches!(
+            forwarded.as_slice(),
+            [RoutedResponse {
+                response: OutboundResponse::Text(_),
+                ..
+            }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn responses_after_a_paced_reply_keep_their_order() {
+        let mut status = reply("discord", "");
+        status.response = OutboundResponse::Status(crate::StatusUpdate::StopTyping);
+        let forwarded = forward(vec![reply("discord", "hi there"), status], 400).await;
+
+        let end = forwarded
+            .iter()
+            .position(|routed| matches!(routed.response, OutboundResponse::StreamEnd))
+            .unwrap();
+        assert_eq!(end, forwarded.len() - 2);
+        assert!(matches!(
+            forwarded.last().map(|routed| &routed.response),
+            Some(OutboundResponse::Status(_))
+        ));
+    }
+}
@@ -814,6 +814,13 @@ impl Messaging for SignalAdapter {
                     "signal: remove reactions not supported, dropping"
                 );
             }
+            OutboundResponse::Sticker { fallback, .. } => {
+                // Signal stickers need a pack ID and key — send the fallback text.
+                if let Some(text) = fallback {
+                    self.stop_typing(&message.conversation_id).await;
+                    self.send_text(&target, &text).await?;
+                }
+            }
             OutboundResponse::Ephemeral { text, .. } => {
                 // Signal has no ephemeral messages — send as regular text.
                 self.stop_typing(&message.conversation_id).await;
@@ -824,6 +831,10 @@ impl Messaging for SignalAdapter {
                 self.stop_typing(&message.conversation_id).await;
                 self.send_text(&target, &text).await?;
             }
+            OutboundResponse::Reasoning(reasoning) => {
+                self.send_text(&target, &OutboundResponse::reasoning_as_quote(&reasoning))
+                    .await?;
+            }
             OutboundResponse::StreamStart
             | OutboundResponse::StreamChunk(_)
             | OutboundResponse::StreamEnd => {
@@ -5,6 +5,9 @@
 //! **Inbound**
 //! - Plain text and file-attachment messages (Socket Mode)
 //! - `app_mention` events — agent responds when @-mentioned in any channel
+//! - `reaction_added` events — emoji reactions on messages as agent triggers
+//! - `app_home_opened` events — forwarded so the agent can refresh the Home tab
+//! - Block actions from messages and the App Home tab, routed as interactions
 //! - Message subtype filtering (edits/deletes ignored)
 //! - Per-workspace / per-channel / DM permission filtering (hot-reloadable)
 //! - Full user identity resolution (display name, mention tag)
@@ -17,6 +20,7 @@
 //! - Ephemeral messages (visible only to the triggering user)
 //! - Block Kit rich messages with plain-text fallback
 //! - Scheduled messages (`chat.scheduleMessage`)
+//! - App Home tab views (`views.publish`)
 //! - Streaming via `chat.update` edits
 //! - Typing indicator via `assistant.threads.setStatus`
 //! - DM broadcast via `conversations.open`
@@ -48,8 +52,16 @@ struct SlackAdapterState {
     user_identity_cache: Arc<RwLock<HashMap<String, SlackUserIdentity>>>,
     /// Cache of resolved channel names to avoid repeated `conversations.info` API calls.
     channel_name_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// When each user's `app_home_opened` was last forwarded, so reopening the
+    /// Home tab doesn't start a turn every time.
+    home_opened_at: Arc<RwLock<HashMap<String, std::time::Instant>>>,
 }
 
+/// How long a forwarded `app_home_opened` covers later opens by the same user.
+/// The published view stays on the tab in between; buttons and cron jobs can
+/// still refresh it.
+const HOME_OPENED_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
 #[derive(Debug, Clone)]
 struct SlackUserIdentity {
     display_name: String,
@@ -110,6 +122,21 @@ impl SlackAdapter {
     fn session(&self) -> SlackClientSession<'_, SlackClientHyperHttpsConnector> {
         self.client.open_session(&self.token)
     }
+
+    /// Replace a user's App Home tab with the given Block Kit blocks.
+    pub async fn publish_home_view(
+        &self,
+        user_id: &str,
+        blocks: &[serde_json::Value],
+    ) -> crate::Result<()> {
+        let view = SlackView::Home(SlackHomeView::new(deserialize_blocks(blocks)));
+        let req = SlackApiViewsPublishRequest::new(SlackUserId(user_id.to_string()), view);
+        self.session()
+            .views_publish(&req)
+            .await
+            .context("failed to publish slack home view")?;
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -143,6 +170,12 @@ async fn handle_push_event(
         SlackEventCallbackBody::AppMention(mention) => {
             handle_app_mention_event(mention, &event.team_id, client, states).await
         }
+        SlackEventCallbackBody::ReactionAdded(reaction) => {
+            handle_reaction_added_event(reaction, &event.team_id, client, states).await
+        }
+        SlackEventCallbackBody::AppHomeOpened(home_opened) => {
+            handle_app_home_opened_event(home_opened, &event.team_id, &event.event_id, states).await
+        }
         _ => {
             tracing::debug!(event_type = ?std::mem::discriminant(&event.event), "slack push event: unhandled");
             Ok(())
@@ -238,7 +271,8 @@ async fn handle_message_event(
         }
     }
 
-    let base_conversation_id = format!("slack:{}:{}", team_id_str, channel_id);
+    let thread_ts = msg_event.origin.thread_ts.as_ref().map(|t| t.0.as_str());
+    let base_conversation_id = slack_conversation_id(&team_id_str, &channel_id, &ts, thread_ts);
     let conversation_id =
         apply_runtime_adapter_to_conversation_id(&adapter_state.runtime_key, base_conversation_id);
 
@@ -248,7 +282,7 @@ async fn handle_message_event(
         &team_id_str,
         &channel_id,
         &ts,
-        msg_event.origin.thread_ts.as_ref().map(|t| t.0.as_str()),
+        thread_ts,
         user_id.as_deref(),
         msg_event.sender.user.as_ref(),
         &client,
@@ -269,7 +303,8 @@ async fn handle_message_event(
     let session = client.open_session(&token);
     let replied_to_bot = if let Some(thread_ts) = msg_event.origin.thread_ts.as_ref() {
         // For threaded replies, treat as explicit invoke only when the thread
-        // root message belongs to this bot.
+        // root message belongs to this bot. The root also serves as the
+        // reply-to context, since it is often outside the backfill window.
         if thread_ts.0 != ts {
             let thread_replies_request = SlackApiConversationsRepliesRequest::new(
                 SlackChannelId(channel_id.clone()),
@@ -282,11 +317,20 @@ async fn handle_message_event(
             )
             .await
             {
-                Ok(Ok(response)) => response
-                    .messages
-                    .first()
-                    .and_then(|message| message.sender.user.as_ref())
-                    .is_some_and(|user| user.0 == adapter_state.bot_user_id),
+                Ok(Ok(response)) => {
+                    let parent = response.messages.first();
+                    if let Some(parent) = parent {
+                        insert_thread_parent_context(
+                            &mut metadata,
+                            parent,
+                            &adapter_state.user_identity_cache,
+                        )
+                        .await;
+                    }
+                    parent
+                        .and_then(|message| message.sender.user.as_ref())
+                        .is_some_and(|user| user.0 == adapter_state.bot_user_id)
+                }
                 Ok(Err(error)) => {
                     tracing::debug!(%error, "failed to resolve slack thread parent for reply invoke");
                     false
@@ -325,6 +369,245 @@ async fn handle_message_event(
     Ok(())
 }
 
+/// Handle `reaction_added` events on messages.
+///
+/// Every reaction is forwarded as `MessageContent::Reaction`; the channel
+/// decides whether the emoji maps to an action. Reactions on files and the
+/// bot's own reactions are ignored.
+async fn handle_reaction_added_event(
+    reaction: SlackReactionAddedEvent,
+    team_id: &SlackTeamId,
+    client: Arc<SlackHyperClient>,
+    states: SlackClientEventsUserState,
+) -> UserCallbackResult<()> {
+    let SlackReactionsItem::Message(item) = reaction.item else {
+        return Ok(());
+    };
+    // Most reactions map to nothing; drop them before any API call.
+    if !crate::config::ReactionTriggers::any_agent_triggers(&reaction.reaction.0) {
+        return Ok(());
+    }
+
+    let state_guard = states.read().await;
+    let adapter_state = state_guard
+        .get_user_state::<Arc<SlackAdapterState>>()
+        .ok_or_else(|| {
+            Box::<dyn std::error::Error + Send + Sync>::from(
+                "SlackAdapterState not found in user_state",
+            )
+        })?;
+
+    let user_id = reaction.user.0.clone();
+    if user_id == adapter_state.bot_user_id {
+        return Ok(()); // ignore our own reactions
+    }
+
+    let team_id_str = team_id.0.clone();
+    let Some(channel_id) = item.origin.channel.as_ref().map(|c| c.0.clone()) else {
+        return Ok(());
+    };
+    let message_ts = item.origin.ts.0.clone();
+
+    let perms = adapter_state.permissions.load();
+    if channel_id.starts_with('D') {
+        if !perms.dm_allowed_users.contains(&user_id) {
+            return Ok(());
+        }
+    } else {
+        if let Some(ref filter) = perms.workspace_filter
+            && !filter.contains(&team_id_str)
+        {
+            return Ok(());
+        }
+        if let Some(allowed) = perms.channel_filter.get(&team_id_str)
+            && !allowed.is_empty()
+            && !allowed.contains(&channel_id)
+        {
+            return Ok(());
+        }
+    }
+
+    // The event only carries the message reference; fetch its text.
+    let token = SlackApiToken::new(SlackApiTokenValue(adapter_state.bot_token.clone()));
+    let session = client.open_session(&token);
+    let history_request = SlackApiConversationsHistoryRequest::new()
+        .with_channel(SlackChannelId(channel_id.clone()))
+        .with_latest(item.origin.ts.clone())
+        .with_oldest(item.origin.ts.clone())
+        .with_inclusive(true)
+        .with_limit(1);
+    let message_text = match timeout(
+        Duration::from_secs(2),
+        session.conversations_history(&history_request),
+    )
+    .await
+    {
+        Ok(Ok(response)) => response
+            .messages
+            .into_iter()
+            .next()
+            .and_then(|message| message.content.text)
+            .filter(|text| !text.trim().is_empty()),
+        Ok(Err(error)) => {
+            tracing::debug!(%error, "failed to fetch reacted-to slack message");
+            None
+        }
+        Err(error) => {
+            tracing::debug!(%error, "timed out fetching reacted-to slack message");
+            None
+        }
+    };
+
+    let base_conversation_id = format!("slack:{}:{}", team_id_str, channel_id);
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(&adapter_state.runtime_key, base_conversation_id);
+
+    let (mut metadata, formatted_author) = build_metadata_and_author(
+        &team_id_str,
+        &channel_id,
+        &message_ts,
+        Some(&message_ts),
+        Some(&user_id),
+        Some(&reaction.user),
+        &client,
+        &adapter_state.bot_token,
+        &adapter_state.user_identity_cache,
+        &adapter_state.channel_name_cache,
+    )
+    .await;
+    // A reaction is directed at the bot the same way a block action is.
+    metadata.insert(
+        "slack_mentions_or_replies_to_bot".into(),
+        serde_json::Value::Bool(true),
+    );
+
+    let emoji = reaction.reaction.0;
+    let content = MessageContent::Reaction {
+        emoji: emoji.clone(),
+        message_id: message_ts.clone(),
+        message_text,
+    };
+
+    send_inbound(
+        &adapter_state.inbound_tx,
+        &adapter_state.runtime_key,
+        format!("reaction:{}:{}:{}", message_ts, user_id, emoji),
+        conversation_id,
+        user_id,
+        content,
+        metadata,
+        formatted_author,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Handle `app_home_opened` events — fired when a user opens the app in Slack.
+///
+/// Only the Home tab is forwarded, as an `app_home_opened` interaction in the
+/// user's DM conversation, so the agent can publish a fresh Home view.
+async fn handle_app_home_opened_event(
+    home_opened: SlackAppHomeOpenedEvent,
+    team_id: &SlackTeamId,
+    event_id: &SlackEventId,
+    states: SlackClientEventsUserState,
+) -> UserCallbackResult<()> {
+    if home_opened.tab.as_deref() != Some("home") {
+        return Ok(());
+    }
+
+    let state_guard = states.read().await;
+    let adapter_state = state_guard
+        .get_user_state::<Arc<SlackAdapterState>>()
+        .ok_or_else(|| {
+            Box::<dyn std::error::Error + Send + Sync>::from(
+                "SlackAdapterState not found in user_state",
+            )
+        })?;
+
+    let user_id = home_opened.user.0.clone();
+    let team_id_str = team_id.0.clone();
+    let channel_id = home_opened.channel.0.clone();
+
+    // The Home tab hangs off the app's DM with the user, so DM rules apply.
+    let perms = adapter_state.permissions.load();
+    if !perms.dm_allowed_users.contains(&user_id) {
+        tracing::debug!(user_id = %user_id, "app_home_opened from user not allowed to DM — dropping");
+        return Ok(());
+    }
+
+    if !claim_home_opened(&adapter_state.home_opened_at, &user_id).await {
+        tracing::debug!(user_id = %user_id, "app_home_opened within cooldown — dropping");
+        return Ok(());
+    }
+
+    let base_conversation_id = format!("slack:{}:{}", team_id_str, channel_id);
+    let conversation_id =
+        apply_runtime_adapter_to_conversation_id(&adapter_state.runtime_key, base_conversation_id);
+
+    let content = MessageContent::Interaction {
+        action_id: "app_home_opened".into(),
+        block_id: None,
+        values: Vec::new(),
+        label: None,
+        message_ts: None,
+    };
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "slack_workspace_id".into(),
+        serde_json::Value::String(team_id_str),
+    );
+    metadata.insert(
+        "slack_channel_id".into(),
+        serde_json::Value::String(channel_id),
+    );
+    metadata.insert(
+        "slack_user_id".into(),
+        serde_json::Value::String(user_id.clone()),
+    );
+    metadata.insert(
+        "sender_id".into(),
+        serde_json::Value::String(user_id.clone()),
+    );
+    metadata.insert(
+        "slack_user_mention".into(),
+        serde_json::Value::String(format!("<@{}>", user_id)),
+    );
+    metadata.insert("slack_home_tab".into(), serde_json::Value::Bool(true));
+
+    send_inbound(
+        &adapter_state.inbound_tx,
+        &adapter_state.runtime_key,
+        format!("app_home_opened:{}", event_id.0),
+        conversation_id,
+        user_id.clone(),
+        content,
+        metadata,
+        Some(format!("<@{}>", user_id)),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Record a forwarded `app_home_opened` for `user_id`. Returns false when one
+/// was already forwarded within [`HOME_OPENED_COOLDOWN`].
+async fn claim_home_opened(
+    home_opened_at: &RwLock<HashMap<String, std::time::Instant>>,
+    user_id: &str,
+) -> bool {
+    let mut home_opened_at = home_opened_at.write().await;
+    let now = std::time::Instant::now();
+    home_opened_at.retain(|_, opened_at| now.duration_since(*opened_at) < HOME_OPENED_COOLDOWN);
+    if home_opened_at.contains_key(user_id) {
+        return false;
+    }
+    home_opened_at.insert(user_id.to_string(), now);
+    true
+}
+
 /// Handle `app_mention` events — fired when the bot is @-mentioned in a channel
 /// it may not be a primary member of.
 ///
@@ -372,7 +655,8 @@ async fn handle_app_mention_event(
         return Ok(());
     }
 
-    let base_conversation_id = format!("slack:{}:{}", team_id_str, channel_id);
+    let thread_ts = mention.origin.thread_ts.as_ref().map(|t| t.0.as_str());
+    let base_conversation_id = slack_conversation_id(&team_id_str, &channel_id, &ts, thread_ts);
     let conversation_id =
         apply_runtime_adapter_to_conversation_id(&adapter_state.runtime_key, base_conversation_id);
 
@@ -386,7 +670,7 @@ async fn handle_app_mention_event(
         &team_id_str,
         &channel_id,
         &ts,
-        mention.origin.thread_ts.as_ref().map(|t| t.0.as_str()),
+        thread_ts,
         Some(&user_id),
         Some(&slack_uid),
         &client,
@@ -580,7 +864,7 @@ async fn handle_command_event(
 /// types (view submissions, shortcuts, etc.) are logged and acknowledged.
 async fn handle_interaction_event(
     event: SlackInteractionEvent,
-    _client: Arc<SlackHyperClient>,
+    client: Arc<SlackHyperClient>,
     states: SlackClientEventsUserState,
 ) -> UserCallbackResult<()> {
     let SlackInteractionEvent::BlockActions(block_actions) = event else {
@@ -606,12 +890,37 @@ async fn handle_interaction_event(
 
     let team_id = block_actions.team.id.0.clone();
 
-    let channel_id = block_actions
+    let mut channel_id = block_actions
         .channel
         .as_ref()
         .map(|c| c.id.0.clone())
         .unwrap_or_default();
 
+    // Home tab views carry no channel; route their actions to the app's DM
+    // with the user so replies land under the Home tab's Messages tab.
+    let home_view_id = match (&block_actions.container, &block_actions.view) {
+        (SlackInteractionActionContainer::View(view_container), Some(SlackView::Home(_))) => {
+            Some(view_container.view_id.0.clone())
+        }
+        _ => None,
+    };
+    if home_view_id.is_some() && channel_id.is_empty() && !user_id.is_empty() {
+        let token = SlackApiToken::new(SlackApiTokenValue(adapter_state.bot_token.clone()));
+        let open_req =
+            SlackApiConversationsOpenRequest::new().with_users(vec![SlackUserId(user_id.clone())]);
+        match client
+            .open_session(&token)
+            .conversations_open(&open_req)
+            .await
+        {
+            Ok(response) => channel_id = response.channel.id.0,
+            Err(error) => {
+                tracing::warn!(%error, "failed to open DM for home tab interaction — dropping");
+                return Ok(());
+            }
+        }
+    }
+
     // Apply workspace / channel permission filters — interactions are subject to
     // the same access rules as regular messages.
     {
@@ -627,7 +936,15 @@ async fn handle_interaction_event(
             return Ok(());
         }
 
-        if !channel_id.is_empty()
+        if home_view_id.is_some() {
+            if !perms.dm_allowed_users.contains(&user_id) {
+                tracing::debug!(
+                    user_id = %user_id,
+                    "home tab interaction from user not allowed to DM — dropping"
+                );
+                return Ok(());
+            }
+        } else if !channel_id.is_empty()
             && let Some(allowed) = perms.channel_filter.get(&team_id)
             && !allowed.is_empty()
             && !allowed.contains(&channel_id)
@@ -728,6 +1045,13 @@ async fn handle_interaction_event(
                 serde_json::Value::String(bid.clone()),
             );
         }
+        if let Some(ref view_id) = home_view_id {
+            metadata.insert("slack_home_tab".into(), serde_json::Value::Bool(true));
+            metadata.insert(
+                "slack_view_id".into(),
+                serde_json::Value::String(view_id.clone()),
+            );
+        }
 
         let inbound = InboundMessage {
             id,
@@ -783,6 +1107,7 @@ impl Messaging for SlackAdapter {
             commands: self.commands.clone(),
             user_identity_cache: Arc::new(RwLock::new(HashMap::new())),
             channel_name_cache: Arc::new(RwLock::new(HashMap::new())),
+            home_opened_at: Arc::new(RwLock::new(HashMap::new())),
         });
 
         let callbacks = SlackSocketModeListenerCallbacks::new()
@@ -877,7 +1202,8 @@ impl Messaging for SlackAdapter {
 
         let status_text = match &status {
             StatusUpdate::Thinking => "Thinking…".to_string(),
-            StatusUpdate::StopTyping => String::new(), // empty string clears the status
+            // empty string clears the status
+            StatusUpdate::StopTyping | StatusUpdate::Skipped { .. } => String::new(),
             StatusUpdate::ToolStarted { .. } => "Working…".to_string(),
             StatusUpdate::ToolCompleted { .. } => "Working…".to_string(),
             _ => "Working…".to_string(),
@@ -1058,6 +1384,21 @@ impl Messaging for SlackAdapter {
                     .context("failed to send slack rich message")?;
             }
 
+            OutboundResponse::Sticker { fallback, .. } => {
+                // Slack has no stickers — post the fallback text, if any.
+                let Some(text) = fallback else {
+                    return Ok(());
+                };
+                let thread_ts = extract_thread_ts(message);
+                let req =
+                    SlackApiChatPostMessageRequest::new(channel_id.clone(), markdown_content(text))
+                        .opt_thread_ts(thread_ts);
+                session
+                    .chat_post_message(&req)
+                    .await
+                    .context("failed to send slack sticker fallback")?;
+            }
+
             OutboundResponse::ScheduledMessage { text, post_at } => {
                 let thread_ts = extract_thread_ts(message);
                 let post_at_dt = chrono::DateTime::<chrono::Utc>::from_timestamp(post_at, 0)
@@ -1074,6 +1415,10 @@ impl Messaging for SlackAdapter {
                     .context("failed to schedule slack message")?;
             }
 
+            OutboundResponse::HomeView { user_id, blocks } => {
+                self.publish_home_view(&user_id, &blocks).await?;
+            }
+
             OutboundResponse::StreamStart => {
                 let req = SlackApiChatPostMessageRequest::new(
                     channel_id.clone(),
@@ -1116,12 +1461,34 @@ impl Messaging for SlackAdapter {
             OutboundResponse::Status(_) => {
                 // Status updates are handled via send_status(); ignored here.
             }
+
+            OutboundResponse::Reasoning(reasoning) => {
+                // Slack has no collapsible text — post it quoted in the thread.
+                let thread_ts = extract_thread_ts(message);
+                let text = OutboundResponse::reasoning_as_quote(&reasoning);
+                for chunk in split_message(&text, 12_000) {
+                    let req = SlackApiChatPostMessageRequest::new(
+                        channel_id.clone(),
+                        markdown_content(chunk),
+                    )
+                    .opt_thread_ts(thread_ts.clone());
+                    session
+                        .chat_post_message(&req)
+                        .await
+                        .context("failed to send slack reasoning")?;
+                }
+            }
         }
 
         Ok(())
     }
 
     async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        // Home views are addressed by user, not channel, so skip target resolution.
+        if let OutboundResponse::HomeView { user_id, blocks } = &response {
+            return self.publish_home_view(user_id, blocks).await;
+        }
+
         let session = self.session();
 
         // Parse an optional thread target encoded as `#thread:<ts>` suffix.
@@ -1405,6 +1772,61 @@ fn extract_message_content(
     MessageContent::Text(msg_content.text.clone().unwrap_or_default())
 }
 
+/// Record a thread's root message as the reply-to context of a threaded reply.
+///
+/// The author is resolved from the identity cache only, so this never costs
+/// an extra API call. Unknown authors fall back to the sender's username or ID.
+async fn insert_thread_parent_context(
+    metadata: &mut HashMap<String, serde_json::Value>,
+    parent: &SlackHistoryMessage,
+    user_identity_cache: &Arc<RwLock<HashMap<String, SlackUserIdentity>>>,
+) {
+    let Some(text) = parent
+        .content
+        .text
+        .as_deref()
+        .filter(|text| !text.trim().is_empty())
+    else {
+        return;
+    };
+    metadata.insert(
+        crate::metadata_keys::REPLY_TO_TEXT.into(),
+        serde_json::Value::String(crate::messaging::truncate_reply_text(text)),
+    );
+
+    let author = match &parent.sender.user {
+        Some(user) => user_identity_cache
+            .read()
+            .await
+            .get(&user.0)
+            .map(|identity| identity.display_name.clone())
+            .unwrap_or_else(|| user.0.clone()),
+        None => match &parent.sender.username {
+            Some(username) => username.clone(),
+            None => return,
+        },
+    };
+    metadata.insert(
+        crate::metadata_keys::REPLY_TO_AUTHOR.into(),
+        serde_json::Value::String(author),
+    );
+}
+
+/// Conversation ID for a Slack message. Replies in a thread get their own
+/// conversation keyed by the thread root's `ts`, so each thread keeps its own
+/// history. Top-level messages, including a thread's root, use the channel.
+fn slack_conversation_id(
+    team_id: &str,
+    channel_id: &str,
+    ts: &str,
+    thread_ts: Option<&str>,
+) -> String {
+    match thread_ts {
+        Some(thread_ts) if thread_ts != ts => format!("slack:{team_id}:{channel_id}:{thread_ts}"),
+        _ => format!("slack:{team_id}:{channel_id}"),
+    }
+}
+
 /// Build the metadata map and formatted author string shared by all inbound paths.
 #[allow(clippy::too_many_arguments)]
 async fn build_metadata_and_author(
@@ -1444,6 +1866,14 @@ async fn build_metadata_and_author(
             "slack_thread_ts".into(),
             serde_json::Value::String(tts.into()),
         );
+        if tts != ts {
+            // Thread replies run in their own conversation; keep a pointer to
+            // the channel conversation the thread hangs off.
+            metadata.insert(
+                "slack_parent_conversation_id".into(),
+                serde_json::Value::String(format!("slack:{team_id}:{channel_id}")),
+            );
+        }
     }
 
     if let Some(uid) = user_id {
@@ -1633,9 +2063,12 @@ fn variant_name(response: &OutboundResponse) -> &'static str {
         OutboundResponse::File { .. } => "File",
         OutboundResponse::Reaction(_) => "Reaction",
         OutboundResponse::RemoveReaction(_) => "RemoveReaction",
+        OutboundResponse::Sticker { .. } => "Sticker",
         OutboundResponse::Ephemeral { .. } => "Ephemeral",
         OutboundResponse::RichMessage { .. } => "RichMessage",
         OutboundResponse::ScheduledMessage { .. } => "ScheduledMessage",
+        OutboundResponse::HomeView { .. } => "HomeView",
+        OutboundResponse::Reasoning(_) => "Reasoning",
         OutboundResponse::StreamStart => "StreamStart",
         OutboundResponse::StreamChunk(_) => "StreamChunk",
         OutboundResponse::StreamEnd => "StreamEnd",
@@ -1732,6 +2165,23 @@ fn resolve_slack_user_identity(user: &SlackUser, user_id: &str) -> SlackUserIden
 mod tests {
     use super::*;
 
+    #[test]
+    fn thread_replies_get_their_own_conversation() {
+        assert_eq!(
+            slack_conversation_id("T01", "C01", "1700000000.000100", None),
+            "slack:T01:C01"
+        );
+        // The thread root itself stays in the channel conversation.
+        assert_eq!(
+            slack_conversation_id("T01", "C01", "1700000000.000100", Some("1700000000.000100")),
+            "slack:T01:C01"
+        );
+        assert_eq!(
+            slack_conversation_id("T01", "C01", "1700000050.000200", Some("1700000000.000100")),
+            "slack:T01:C01:1700000000.000100"
+        );
+    }
+
     #[test]
     fn sanitize_reaction_name_unicode_emoji_with_shortcode() {
         // gemoji maps 👍 to "+1" — verify we get the shortcode, not the unicode back
@@ -1803,4 +2253,21 @@ mod tests {
         let result = sanitize_reaction_name(":partyparrot:");
         assert_eq!(result, "partyparrot");
     }
+
+    #[tokio::test]
+    async fn home_opened_is_forwarded_once_per_cooldown() {
+        let home_opened_at = RwLock::new(HashMap::new());
+        assert!(claim_home_opened(&home_opened_at, "U1").await);
+        assert!(!claim_home_opened(&home_opened_at, "U1").await);
+        assert!(claim_home_opened(&home_opened_at, "U2").await);
+
+        let expired = std::time::Instant::now()
+            .checked_sub(HOME_OPENED_COOLDOWN)
+            .expect("monotonic clock past cooldown");
+        home_opened_at
+            .write()
+            .await
+            .insert("U1".to_string(), expired);
+        assert!(claim_home_opened(&home_opened_at, "U1").await);
+    }
 }
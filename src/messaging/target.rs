@@ -1,5 +1,6 @@
 //! Shared delivery target parsing and channel target resolution.
 
+use crate::config::Binding;
 use crate::conversation::channels::ChannelInfo;
 
 /// Canonical target for `MessagingManager::broadcast`.
@@ -53,7 +54,8 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
             }
         }
         "slack" => {
-            if let Some(channel_id) = channel
+            let parts: Vec<&str> = channel.id.split(':').collect();
+            let channel_id = if let Some(channel_id) = channel
                 .platform_meta
                 .as_ref()
                 .and_then(|meta| meta.get("slack_channel_id"))
@@ -61,26 +63,39 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
             {
                 channel_id
             } else {
-                let parts: Vec<&str> = channel.id.split(':').collect();
                 match parts.as_slice() {
                     ["slack", _, channel_id] => (*channel_id).to_string(),
                     ["slack", _, channel_id, _] => (*channel_id).to_string(),
                     _ => return None,
                 }
+            };
+            // Thread conversations deliver back into their thread.
+            match parts.last() {
+                Some(thread_ts) if parts.len() >= 4 && is_slack_ts(thread_ts) => {
+                    format!("{channel_id}#thread:{thread_ts}")
+                }
+                _ => channel_id,
             }
         }
         "telegram" => {
-            if let Some(chat_id) = channel
-                .platform_meta
-                .as_ref()
+            let platform_meta = channel.platform_meta.as_ref();
+            if let Some(chat_id) = platform_meta
                 .and_then(|meta| meta.get("telegram_chat_id"))
                 .and_then(json_value_to_string)
             {
-                chat_id
+                // Forum topic conversations deliver back into their topic.
+                match platform_meta
+                    .and_then(|meta| meta.get("telegram_topic_id"))
+                    .and_then(json_value_to_string)
+                {
+                    Some(topic_id) => format!("{chat_id}#topic:{topic_id}"),
+                    None => chat_id,
+                }
             } else {
                 let parts: Vec<&str> = channel.id.split(':').collect();
                 match parts.as_slice() {
                     ["telegram", chat_id] => (*chat_id).to_string(),
+                    ["telegram", chat_id, topic_id] => format!("{chat_id}#topic:{topic_id}"),
                     _ => return None,
                 }
             }
@@ -177,6 +192,57 @@ pub fn resolve_broadcast_target(channel: &ChannelInfo) -> Option<BroadcastTarget
     })
 }
 
+/// Resolve every concrete channel a binding covers. Fails with the reason
+/// when the binding doesn't name one, e.g. a Discord binding for a whole
+/// guild or a webchat binding.
+pub fn resolve_binding_targets(binding: &Binding) -> Result<Vec<BroadcastTarget>, String> {
+    let platform = binding.channel.as_str();
+    let raw_targets: Vec<String> = match platform {
+        "telegram" => {
+            let chat_id = binding
+                .chat_id
+                .as_deref()
+                .ok_or("telegram binding has no chat_id")?;
+            // Telegram `channel_ids` name forum topics within the chat.
+            if binding.channel_ids.is_empty() {
+                vec![chat_id.to_string()]
+            } else {
+                binding
+                    .channel_ids
+                    .iter()
+                    .map(|topic_id| format!("{chat_id}#topic:{topic_id}"))
+                    .collect()
+            }
+        }
+        "discord" | "slack" | "twitch" | "mattermost" => {
+            if binding.channel_ids.is_empty() {
+                return Err(format!(
+                    "{platform} binding has no channel_ids, so it names no channel to send to"
+                ));
+            }
+            binding.channel_ids.clone()
+        }
+        _ => {
+            return Err(format!(
+                "{platform} bindings have no fixed channel to send to"
+            ));
+        }
+    };
+
+    let adapter = binding.runtime_adapter_key();
+    raw_targets
+        .into_iter()
+        .map(|raw_target| {
+            let target = normalize_target(platform, &raw_target)
+                .ok_or_else(|| format!("invalid {platform} target '{raw_target}'"))?;
+            Ok(BroadcastTarget {
+                adapter: adapter.clone(),
+                target,
+            })
+        })
+        .collect()
+}
+
 pub fn normalize_target(adapter: &str, raw_target: &str) -> Option<String> {
     let trimmed = raw_target.trim();
     if trimmed.is_empty() {
@@ -228,6 +294,14 @@ fn normalize_discord_target(raw_target: &str) -> Option<String> {
 fn normalize_slack_target(raw_target: &str) -> Option<String> {
     let target = strip_repeated_prefix(raw_target, "slack");
 
+    if let Some((bare_target, thread_ts)) = target.split_once("#thread:") {
+        if thread_ts.is_empty() {
+            return None;
+        }
+        let channel_id = normalize_slack_target(bare_target)?;
+        return Some(format!("{channel_id}#thread:{thread_ts}"));
+    }
+
     if let Some(user_id) = target.strip_prefix("dm:") {
         if !user_id.is_empty() {
             return Some(format!("dm:{user_id}"));
@@ -236,10 +310,17 @@ fn normalize_slack_target(raw_target: &str) -> Option<String> {
     }
 
     if let Some((workspace_id, channel_id)) = target.split_once(':') {
-        if !workspace_id.is_empty() && !channel_id.is_empty() {
-            return Some(channel_id.to_string());
+        if workspace_id.is_empty() || channel_id.is_empty() {
+            return None;
         }
-        return None;
+        // `workspace:channel:thread_ts` is a thread conversation ID.
+        return match channel_id.split_once(':') {
+            Some((channel_id, thread_ts)) if !channel_id.is_empty() && is_slack_ts(thread_ts) => {
+                Some(format!("{channel_id}#thread:{thread_ts}"))
+            }
+            Some(_) => None,
+            None => Some(channel_id.to_string()),
+        };
     }
 
     if target.is_empty() {
@@ -249,8 +330,28 @@ fn normalize_slack_target(raw_target: &str) -> Option<String> {
     }
 }
 
+/// Whether a value looks like a Slack message timestamp (`1700000000.000100`).
+fn is_slack_ts(value: &str) -> bool {
+    value.split_once('.').is_some_and(|(seconds, fraction)| {
+        !seconds.is_empty()
+            && !fraction.is_empty()
+            && seconds.bytes().all(|byte| byte.is_ascii_digit())
+            && fraction.bytes().all(|byte| byte.is_ascii_digit())
+    })
+}
+
 fn normalize_telegram_target(raw_target: &str) -> Option<String> {
     let target = strip_repeated_prefix(raw_target, "telegram");
+    // A forum topic, as `chat_id#topic:topic_id` or the conversation ID's
+    // `chat_id:topic_id`.
+    if let Some((chat_id, topic_id)) = target
+        .split_once("#topic:")
+        .or_else(|| target.split_once(':'))
+    {
+        let chat_id = chat_id.parse::<i64>().ok()?;
+        let topic_id = topic_id.parse::<i32>().ok()?;
+        return Some(format!("{chat_id}#topic:{topic_id}"));
+    }
     let chat_id = target.parse::<i64>().ok()?;
     Some(chat_id.to_string())
 }
@@ -512,7 +613,8 @@ pub fn parse_signal_target_parts(parts: &[&str]) -> Option<BroadcastTarget> {
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_delivery_target, resolve_broadcast_target};
+    use super::{parse_delivery_target, resolve_binding_targets, resolve_broadcast_target};
+    use crate::config::Binding;
     use crate::conversation::channels::ChannelInfo;
 
     fn test_channel_info(id: &str, platform: &str) -> ChannelInfo {
@@ -551,6 +653,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn slack_thread_conversations_target_their_thread() {
+        let parsed = parse_delivery_target("slack:T012345:C012345:1700000000.000100");
+        assert_eq!(
+            parsed.map(|target| target.target),
+            Some("C012345#thread:1700000000.000100".to_string())
+        );
+        let parsed = parse_delivery_target("slack:C012345#thread:1700000000.000100");
+        assert_eq!(
+            parsed.map(|target| target.target),
+            Some("C012345#thread:1700000000.000100".to_string())
+        );
+
+        let channel = test_channel_info("slack:T012345:C012345:1700000000.000100", "slack");
+        assert_eq!(
+            resolve_broadcast_target(&channel).map(|target| target.target),
+            Some("C012345#thread:1700000000.000100".to_string())
+        );
+    }
+
+    #[test]
+    fn telegram_topic_conversations_target_their_topic() {
+        let parsed = parse_delivery_target("telegram:-1001234:42");
+        assert_eq!(
+            parsed.map(|target| target.target),
+            Some("-1001234#topic:42".to_string())
+        );
+        let parsed = parse_delivery_target("telegram:-1001234#topic:42");
+        assert_eq!(
+            parsed.map(|target| target.target),
+            Some("-1001234#topic:42".to_string())
+        );
+        assert!(parse_delivery_target("telegram:-1001234#topic:general").is_none());
+
+        let channel = test_channel_info("telegram:-1001234:42", "telegram");
+        assert_eq!(
+            resolve_broadcast_target(&channel).map(|target| target.target),
+            Some("-1001234#topic:42".to_string())
+        );
+    }
+
     #[test]
     fn parse_twitch_target_with_prefix() {
         let parsed = parse_delivery_target("twitch:twitch:jamiepinelive");
@@ -790,4 +933,58 @@ mod tests {
         assert!(super::parse_signal_target_parts(&["uuid"]).is_none()); // missing UUID value
         assert!(super::parse_signal_target_parts(&["gvoice1", "unknown"]).is_none());
     }
+
+    fn test_binding(channel: &str) -> Binding {
+        Binding {
+            agent_id: "main".into(),
+            channel: channel.into(),
+            adapter: None,
+            guild_id: None,
+            workspace_id: None,
+            chat_id: None,
+            team_id: None,
+            channel_ids: vec![],
+            require_mention: false,
+            dm_allowed_users: vec![],
+            welcome_message: None,
+            schedule: None,
+        }
+    }
+
+    #[test]
+    fn binding_targets_cover_each_listed_channel() {
+        let mut binding = test_binding("discord");
+        binding.adapter = Some("ops".into());
+        binding.guild_id = Some("1".into());
+        binding.channel_ids = vec!["111".into(), "222".into()];
+        let targets: Vec<String> = resolve_binding_targets(&binding)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(targets, vec!["discord:ops:111", "discord:ops:222"]);
+
+        let mut binding = test_binding("telegram");
+        binding.chat_id = Some("-100123".into());
+        let targets = resolve_binding_targets(&binding).unwrap();
+        assert_eq!(targets[0].to_string(), "telegram:-100123");
+
+        binding.channel_ids = vec!["7".into()];
+        let targets = resolve_binding_targets(&binding).unwrap();
+        assert_eq!(targets[0].to_string(), "telegram:-100123#topic:7");
+    }
+
+    #[test]
+    fn binding_without_a_concrete_channel_is_unresolvable() {
+        let mut binding = test_binding("discord");
+        binding.guild_id = Some("1".into());
+        assert!(resolve_binding_targets(&binding).is_err());
+        assert!(resolve_binding_targets(&test_binding("telegram")).is_err());
+        assert!(resolve_binding_targets(&test_binding("webchat")).is_err());
+
+        let mut binding = test_binding("discord");
+        binding.channel_ids = vec!["general".into()];
+        let error = resolve_binding_targets(&binding).unwrap_err();
+        assert!(error.contains("general"), "{error}");
+    }
 }
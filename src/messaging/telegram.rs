@@ -12,7 +12,7 @@ use teloxide::payloads::setters::*;
 use teloxide::requests::{Request, Requester};
 use teloxide::types::{
     ChatAction, ChatId, FileId, InputFile, InputPollOption, MediaKind, MessageId, MessageKind,
-    ParseMode, ReactionType, ReplyParameters, UpdateKind, UserId,
+    ParseMode, ReactionType, ReplyParameters, ThreadId, UpdateKind, UserId,
 };
 use teloxide::{ApiError, Bot, RequestError};
 
@@ -45,6 +45,10 @@ struct ActiveStream {
     chat_id: ChatId,
     message_id: MessageId,
     last_edit: Instant,
+    /// Newest cumulative text of the stream.
+    text: String,
+    /// Whether `text` has changed since the last edit.
+    dirty: bool,
 }
 
 /// Telegram's per-message character limit.
@@ -86,6 +90,16 @@ impl TelegramAdapter {
         Ok(ChatId(id))
     }
 
+    /// Forum topic the message came from. `None` outside forum groups and in
+    /// the General topic, where replies need no thread ID.
+    fn extract_topic_id(&self, message: &InboundMessage) -> Option<ThreadId> {
+        message
+            .metadata
+            .get("telegram_topic_id")
+            .and_then(|v| v.as_i64())
+            .map(|id| ThreadId(MessageId(id as i32)))
+    }
+
     fn extract_message_id(&self, message: &InboundMessage) -> anyhow::Result<MessageId> {
         let id = message
             .metadata
@@ -247,7 +261,8 @@ impl Messaging for TelegramAdapter {
                             }
 
                             let content = build_content(&bot, message, &text).await;
-                            let base_conversation_id = format!("telegram:{chat_id}");
+                            let base_conversation_id =
+                                build_conversation_id(chat_id, topic_id(message));
                             let conversation_id = apply_runtime_adapter_to_conversation_id(
                                 &runtime_key,
                                 base_conversation_id,
@@ -299,18 +314,19 @@ impl Messaging for TelegramAdapter {
         response: OutboundResponse,
     ) -> crate::Result<()> {
         let chat_id = self.extract_chat_id(message)?;
+        let topic = self.extract_topic_id(message);
 
         match response {
             OutboundResponse::Text(text) => {
                 self.stop_typing(&message.conversation_id).await;
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                send_formatted(&self.bot, chat_id, topic, &text, None).await?;
             }
             OutboundResponse::RichMessage { text, poll, .. } => {
                 self.stop_typing(&message.conversation_id).await;
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                send_formatted(&self.bot, chat_id, topic, &text, None).await?;
 
                 if let Some(poll_data) = poll {
-                    send_poll(&self.bot, chat_id, &poll_data).await?;
+                    send_poll(&self.bot, chat_id, topic, &poll_data).await?;
                 }
             }
             OutboundResponse::ThreadReply {
@@ -321,7 +337,7 @@ impl Messaging for TelegramAdapter {
 
                 // Telegram doesn't have named threads. Reply to the source message instead.
                 let reply_to = self.extract_message_id(message).ok();
-                send_formatted(&self.bot, chat_id, &text, reply_to).await?;
+                send_formatted(&self.bot, chat_id, topic, &text, reply_to).await?;
             }
             OutboundResponse::File {
                 filename,
@@ -335,16 +351,19 @@ impl Messaging for TelegramAdapter {
                 // Fall back to send_document for everything else.
                 if mime_type.starts_with("audio/") {
                     let input_file = InputFile::memory(data.clone()).file_name(filename.clone());
+                    let mut request = self.bot.send_audio(chat_id, input_file);
+                    if let Some(topic) = topic {
+                        request = request.message_thread_id(topic);
+                    }
                     let sent = if let Some(ref caption_text) = caption {
                         let html_caption = markdown_to_telegram_html(caption_text);
-                        self.bot
-                            .send_audio(chat_id, input_file)
+                        request
                             .caption(&html_caption)
                             .parse_mode(ParseMode::Html)
                             .send()
                             .await
                     } else {
-                        self.bot.send_audio(chat_id, input_file).send().await
+                        request.send().await
                     };
 
                     if let Err(error) = sent {
@@ -355,6 +374,9 @@ impl Messaging for TelegramAdapter {
                             );
                             let fallback_file = InputFile::memory(data).file_name(filename);
                             let mut request = self.bot.send_audio(chat_id, fallback_file);
+                            if let Some(topic) = topic {
+                                request = request.message_thread_id(topic);
+                            }
                             if let Some(caption_text) = caption {
                                 request = request.caption(caption_text);
                             }
@@ -369,16 +391,19 @@ impl Messaging for TelegramAdapter {
                     }
                 } else {
                     let input_file = InputFile::memory(data.clone()).file_name(filename.clone());
+                    let mut request = self.bot.send_document(chat_id, input_file);
+                    if let Some(topic) = topic {
+                        request = request.message_thread_id(topic);
+                    }
                     let sent = if let Some(ref caption_text) = caption {
                         let html_caption = markdown_to_telegram_html(caption_text);
-                        self.bot
-                            .send_document(chat_id, input_file)
+                        request
                             .caption(&html_caption)
                             .parse_mode(ParseMode::Html)
                             .send()
                             .await
                     } else {
-                        self.bot.send_document(chat_id, input_file).send().await
+                        request.send().await
                     };
 
                     if let Err(error) = sent {
@@ -389,6 +414,9 @@ impl Messaging for TelegramAdapter {
                             );
                             let fallback_file = InputFile::memory(data).file_name(filename);
                             let mut request = self.bot.send_document(chat_id, fallback_file);
+                            if let Some(topic) = topic {
+                                request = request.message_thread_id(topic);
+                            }
                             if let Some(caption_text) = caption {
                                 request = request.caption(caption_text);
                             }
@@ -425,12 +453,27 @@ impl Messaging for TelegramAdapter {
                     );
                 }
             }
+            OutboundResponse::Sticker { id, .. } => {
+                self.stop_typing(&message.conversation_id).await;
+                let mut request = self
+                    .bot
+                    .send_sticker(chat_id, InputFile::file_id(FileId(id)));
+                if let Some(topic) = topic {
+                    request = request.message_thread_id(topic);
+                }
+                request
+                    .send()
+                    .await
+                    .context("failed to send telegram sticker")?;
+            }
             OutboundResponse::StreamStart => {
                 self.stop_typing(&message.conversation_id).await;
 
-                let placeholder = self
-                    .bot
-                    .send_message(chat_id, "...")
+                let mut request = self.bot.send_message(chat_id, "...");
+                if let Some(topic) = topic {
+                    request = request.message_thread_id(topic);
+                }
+                let placeholder = request
                     .send()
                     .await
                     .context("failed to send stream placeholder")?;
@@ -441,49 +484,50 @@ impl Messaging for TelegramAdapter {
                         chat_id,
                         message_id: placeholder.id,
                         last_edit: Instant::now(),
+                        text: String::new(),
+                        dirty: false,
                     },
                 );
             }
             OutboundResponse::StreamChunk(text) => {
                 let mut active = self.active_messages.write().await;
                 if let Some(stream) = active.get_mut(&message.conversation_id) {
+                    stream.text = text;
+                    stream.dirty = true;
                     if stream.last_edit.elapsed() < STREAM_EDIT_INTERVAL {
                         return Ok(());
                     }
 
-                    let display_text = if text.len() > MAX_MESSAGE_LENGTH {
-                        let end = text.floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
-                        format!("{}...", &text[..end])
+                    let display_text = if stream.text.len() > MAX_MESSAGE_LENGTH {
+                        let end = stream.text.floor_char_boundary(MAX_MESSAGE_LENGTH - 3);
+                        format!("{}...", &stream.text[..end])
                     } else {
-                        text
+                        stream.text.clone()
                     };
-
-                    let html = markdown_to_telegram_html(&display_text);
-                    if let Err(html_error) = self
-                        .bot
-                        .edit_message_text(stream.chat_id, stream.message_id, &html)
-                        .parse_mode(ParseMode::Html)
-                        .send()
-                        .await
-                    {
-                        tracing::debug!(%html_error, "HTML edit failed, retrying as plain text");
-                        if let Err(error) = self
-                            .bot
-                            .edit_message_text(stream.chat_id, stream.message_id, &display_text)
-                            .send()
-                            .await
-                        {
-                            tracing::debug!(%error, "failed to edit streaming message");
-                        }
-                    }
+                    edit_stream_message(&self.bot, stream, &display_text).await;
                     stream.last_edit = Instant::now();
+                    stream.dirty = false;
                 }
             }
             OutboundResponse::StreamEnd => {
-                self.active_messages
+                let stream = self
+                    .active_messages
                     .write()
                     .await
                     .remove(&message.conversation_id);
+                if let Some(stream) = stream
+                    && (stream.dirty || stream.text.len() > MAX_MESSAGE_LENGTH)
+                {
+                    // The placeholder takes the first chunk of the final text;
+                    // anything past the length limit follows as new messages.
+                    let mut chunks = split_message(&stream.text, MAX_MESSAGE_LENGTH).into_iter();
+                    if let Some(first) = chunks.next() {
+                        edit_stream_message(&self.bot, &stream, &first).await;
+                    }
+                    for chunk in chunks {
+                        send_formatted(&self.bot, chat_id, topic, &chunk, None).await?;
+                    }
+                }
             }
             OutboundResponse::Status(status) => {
                 self.send_status(message, status).await?;
@@ -492,11 +536,14 @@ impl Messaging for TelegramAdapter {
             OutboundResponse::RemoveReaction(_) => {} // no-op
             OutboundResponse::Ephemeral { text, .. } => {
                 // Telegram has no ephemeral messages — send as regular text
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                send_formatted(&self.bot, chat_id, topic, &text, None).await?;
             }
             OutboundResponse::ScheduledMessage { text, .. } => {
                 // Telegram has no scheduled messages — send immediately
-                send_formatted(&self.bot, chat_id, &text, None).await?;
+                send_formatted(&self.bot, chat_id, topic, &text, None).await?;
+            }
+            OutboundResponse::Reasoning(reasoning) => {
+                send_reasoning(&self.bot, chat_id, topic, &reasoning).await?;
             }
         }
 
@@ -511,6 +558,7 @@ impl Messaging for TelegramAdapter {
         match status {
             StatusUpdate::Thinking => {
                 let chat_id = self.extract_chat_id(message)?;
+                let topic = self.extract_topic_id(message);
                 let bot = self.bot.clone();
                 let conversation_id = message.conversation_id.clone();
 
@@ -518,11 +566,11 @@ impl Messaging for TelegramAdapter {
                 // Send one immediately, then repeat every 4 seconds.
                 let handle = tokio::spawn(async move {
                     loop {
-                        if let Err(error) = bot
-                            .send_chat_action(chat_id, ChatAction::Typing)
-                            .send()
-                            .await
-                        {
+                        let mut request = bot.send_chat_action(chat_id, ChatAction::Typing);
+                        if let Some(topic) = topic {
+                            request = request.message_thread_id(topic);
+                        }
+                        if let Err(error) = request.send().await {
                             tracing::debug!(%error, "failed to send typing indicator");
                             break;
                         }
@@ -544,19 +592,29 @@ impl Messaging for TelegramAdapter {
     }
 
     async fn broadcast(&self, target: &str, response: OutboundResponse) -> crate::Result<()> {
+        // Forum topics are addressed as `<chat_id>#topic:<topic_id>`.
+        let (chat_target, topic) = match target.split_once("#topic:") {
+            Some((chat_target, topic_id)) => {
+                let topic_id = topic_id
+                    .parse::<i32>()
+                    .context("invalid telegram topic id for broadcast target")?;
+                (chat_target, Some(ThreadId(MessageId(topic_id))))
+            }
+            None => (target, None),
+        };
         let chat_id = ChatId(
-            target
+            chat_target
                 .parse::<i64>()
                 .context("invalid telegram chat id for broadcast target")?,
         );
 
         if let OutboundResponse::Text(text) = response {
-            send_formatted(&self.bot, chat_id, &text, None).await?;
+            send_formatted(&self.bot, chat_id, topic, &text, None).await?;
         } else if let OutboundResponse::RichMessage { text, poll, .. } = response {
-            send_formatted(&self.bot, chat_id, &text, None).await?;
+            send_formatted(&self.bot, chat_id, topic, &text, None).await?;
 
             if let Some(poll_data) = poll {
-                send_poll(&self.bot, chat_id, &poll_data).await?;
+                send_poll(&self.bot, chat_id, topic, &poll_data).await?;
             }
         }
 
@@ -781,6 +839,20 @@ async fn resolve_file_url(bot: &Bot, file_id: &str) -> anyhow::Result<String> {
     Ok(url.to_string())
 }
 
+/// Forum topic a message was posted in. Messages in the General topic and in
+/// groups without topics have none.
+fn topic_id(message: &teloxide::types::Message) -> Option<ThreadId> {
+    message.thread_id.filter(|_| message.is_topic_message)
+}
+
+/// Conversation ID for a chat. Each forum topic is its own conversation.
+fn build_conversation_id(chat_id: i64, topic_id: Option<ThreadId>) -> String {
+    match topic_id {
+        Some(ThreadId(MessageId(topic_id))) => format!("telegram:{chat_id}:{topic_id}"),
+        None => format!("telegram:{chat_id}"),
+    }
+}
+
 /// Build platform-specific metadata for a Telegram message.
 fn build_metadata(
     message: &teloxide::types::Message,
@@ -818,16 +890,45 @@ fn build_metadata(
         metadata.insert("telegram_chat_title".into(), (*title).into());
         metadata.insert(crate::metadata_keys::SERVER_NAME.into(), (*title).into());
     }
-    let channel_name = message
-        .chat
-        .title()
-        .map(|title| title.to_string())
-        .or_else(|| message.from.as_ref().map(build_display_name))
-        .unwrap_or_else(|| chat_type.to_string());
-    metadata.insert(
-        crate::metadata_keys::CHANNEL_NAME.into(),
-        channel_name.into(),
-    );
+
+    // Telegram presents a topic message that isn't a reply as a reply to the
+    // topic's creation message, which is the only place the topic name shows.
+    let topic_created = message
+        .reply_to_message()
+        .and_then(|reply| reply.forum_topic_created());
+    if let Some(ThreadId(MessageId(topic_id))) = topic_id(message) {
+        metadata.insert(
+            "telegram_topic_id".into(),
+            serde_json::Value::Number(topic_id.into()),
+        );
+        if let Some(topic_created) = topic_created {
+            metadata.insert(
+                "telegram_topic_name".into(),
+                topic_created.name.clone().into(),
+            );
+        }
+    }
+
+    let channel_name = match (message.chat.title(), topic_created) {
+        (Some(title), Some(topic_created)) => Some(format!("{title} / {}", topic_created.name)),
+        // A reply inside a topic doesn't carry the topic name. Leave the
+        // name out rather than replace the stored one with the chat title.
+        (_, None) if topic_id(message).is_some() => None,
+        (Some(title), None) => Some(title.to_string()),
+        (None, _) => Some(
+            message
+                .from
+                .as_ref()
+                .map(build_display_name)
+                .unwrap_or_else(|| chat_type.to_string()),
+        ),
+    };
+    if let Some(channel_name) = channel_name {
+        metadata.insert(
+            crate::metadata_keys::CHANNEL_NAME.into(),
+            channel_name.into(),
+        );
+    }
 
     let formatted_author = if let Some(from) = &message.from {
         metadata.insert(
@@ -886,21 +987,25 @@ fn build_metadata(
 
     // Reply-to context for threading
     let mut reply_to_is_bot_match = false;
-    if let Some(reply) = message.reply_to_message() {
+    if let Some(reply) = message
+        .reply_to_message()
+        .filter(|reply| reply.forum_topic_created().is_none())
+    {
         metadata.insert(
             "reply_to_message_id".into(),
             serde_json::Value::Number(reply.id.0.into()),
         );
         if let Some(text) = extract_text(reply) {
-            let truncated = if text.len() > 200 {
-                format!("{}...", &text[..text.floor_char_boundary(197)])
-            } else {
-                text
-            };
-            metadata.insert("reply_to_text".into(), truncated.into());
+            metadata.insert(
+                crate::metadata_keys::REPLY_TO_TEXT.into(),
+                crate::messaging::truncate_reply_text(&text).into(),
+            );
         }
         if let Some(from) = &reply.from {
-            metadata.insert("reply_to_author".into(), build_display_name(from).into());
+            metadata.insert(
+                crate::metadata_keys::REPLY_TO_AUTHOR.into(),
+                build_display_name(from).into(),
+            );
             metadata.insert(
                 "reply_to_user_id".into(),
                 serde_json::Value::Number(from.id.0.into()),
@@ -948,7 +1053,12 @@ fn build_display_name(user: &teloxide::types::User) -> String {
 /// max 100 chars. `open_period` only supports 5–600 seconds so we only set it
 /// when `duration_hours` converts to ≤600s; otherwise the poll stays open
 /// indefinitely (until manually stopped via the Telegram client).
-async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Result<()> {
+async fn send_poll(
+    bot: &Bot,
+    chat_id: ChatId,
+    topic: Option<ThreadId>,
+    poll: &crate::Poll,
+) -> anyhow::Result<()> {
     let question = if poll.question.len() > 300 {
         format!(
             "{}…",
@@ -991,6 +1101,9 @@ async fn send_poll(bot: &Bot, chat_id: ChatId, poll: &crate::Poll) -> anyhow::Re
     if poll.allow_multiselect {
         request = request.allows_multiple_answers(true);
     }
+    if let Some(topic) = topic {
+        request = request.message_thread_id(topic);
+    }
 
     request
         .send()
@@ -1207,10 +1320,14 @@ fn format_markdown_spans(text: &str) -> String {
 async fn send_plain_text(
     bot: &Bot,
     chat_id: ChatId,
+    topic: Option<ThreadId>,
     text: &str,
     reply_to: Option<MessageId>,
 ) -> anyhow::Result<()> {
     let mut request = bot.send_message(chat_id, text);
+    if let Some(topic) = topic {
+        request = request.message_thread_id(topic);
+    }
     if let Some(reply_id) = reply_to {
         request = request.reply_parameters(ReplyParameters::new(reply_id));
     }
@@ -1221,11 +1338,33 @@ async fn send_plain_text(
     Ok(())
 }
 
+/// Replace the text of a streamed reply's placeholder, falling back to plain
+/// text when Telegram rejects the HTML.
+async fn edit_stream_message(bot: &Bot, stream: &ActiveStream, text: &str) {
+    let html = markdown_to_telegram_html(text);
+    if let Err(html_error) = bot
+        .edit_message_text(stream.chat_id, stream.message_id, &html)
+        .parse_mode(ParseMode::Html)
+        .send()
+        .await
+    {
+        tracing::debug!(%html_error, "HTML edit failed, retrying as plain text");
+        if let Err(error) = bot
+            .edit_message_text(stream.chat_id, stream.message_id, text)
+            .send()
+            .await
+        {
+            tracing::debug!(%error, "failed to edit streaming message");
+        }
+    }
+}
+
 /// Send a message with Telegram HTML formatting, splitting at the message
 /// length limit. Falls back to plain text if the API rejects the HTML.
 async fn send_formatted(
     bot: &Bot,
     chat_id: ChatId,
+    topic: Option<ThreadId>,
     text: &str,
     reply_to: Option<MessageId>,
 ) -> anyhow::Result<()> {
@@ -1244,20 +1383,50 @@ async fn send_formatted(
             }
 
             let plain_chunk = strip_html_tags(&html_chunk);
-            send_plain_text(bot, chat_id, &plain_chunk, reply_to).await?;
+            send_plain_text(bot, chat_id, topic, &plain_chunk, reply_to).await?;
             continue;
         }
 
         let mut request = bot
             .send_message(chat_id, &html_chunk)
             .parse_mode(ParseMode::Html);
+        if let Some(topic) = topic {
+            request = request.message_thread_id(topic);
+        }
         if let Some(reply_id) = reply_to {
             request = request.reply_parameters(ReplyParameters::new(reply_id));
         }
         if let Err(error) = request.send().await {
             tracing::debug!(%error, "HTML send failed, retrying as plain text");
             let plain_chunk = strip_html_tags(&html_chunk);
-            send_plain_text(bot, chat_id, &plain_chunk, reply_to).await?;
+            send_plain_text(bot, chat_id, topic, &plain_chunk, reply_to).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Send model reasoning as collapsed (expandable) blockquotes.
+async fn send_reasoning(
+    bot: &Bot,
+    chat_id: ChatId,
+    topic: Option<ThreadId>,
+    reasoning: &str,
+) -> anyhow::Result<()> {
+    for chunk in split_message(reasoning.trim(), FORMATTED_SPLIT_LENGTH) {
+        let html_chunk = format!(
+            "💭 <b>Reasoning</b>\n<blockquote expandable>{}</blockquote>",
+            escape_html(&chunk)
+        );
+        let mut request = bot
+            .send_message(chat_id, &html_chunk)
+            .parse_mode(ParseMode::Html);
+        if let Some(topic) = topic {
+            request = request.message_thread_id(topic);
+        }
+        if let Err(error) = request.send().await {
+            tracing::debug!(%error, "HTML send failed, retrying as plain text");
+            let plain_chunk = OutboundResponse::reasoning_as_quote(&chunk);
+            send_plain_text(bot, chat_id, topic, &plain_chunk, None).await?;
         }
     }
     Ok(())
@@ -1424,6 +1593,15 @@ mod tests {
         assert_eq!(markdown_to_telegram_html(input), expected);
     }
 
+    #[test]
+    fn forum_topics_get_their_own_conversation() {
+        assert_eq!(build_conversation_id(-1001234, None), "telegram:-1001234");
+        assert_eq!(
+            build_conversation_id(-1001234, Some(ThreadId(MessageId(42)))),
+            "telegram:-1001234:42"
+        );
+    }
+
     #[test]
     fn retries_plain_caption_only_for_parse_entity_errors() {
         let parse_error = RequestError::Api(ApiError::CantParseEntities(
@@ -172,6 +172,23 @@ impl<T: Messaging> MessagingDyn for T {
     }
 }
 
+/// Size cap, in bytes, for quoted reply text stored in inbound metadata.
+pub const REPLY_TO_TEXT_MAX_BYTES: usize = 500;
+
+/// Truncate replied-to message text to [`REPLY_TO_TEXT_MAX_BYTES`] so a long
+/// quoted message doesn't bloat the turn.
+pub fn truncate_reply_text(text: &str) -> String {
+    let text = text.trim();
+    if text.len() > REPLY_TO_TEXT_MAX_BYTES {
+        format!(
+            "{}...",
+            &text[..text.floor_char_boundary(REPLY_TO_TEXT_MAX_BYTES - 3)]
+        )
+    } else {
+        text.to_string()
+    }
+}
+
 /// Rewrite a conversation ID's platform prefix to use a named adapter's runtime key.
 ///
 /// Given a `runtime_key` like `"discord:ops"` and a `base_conversation_id` like
@@ -433,9 +433,12 @@ impl Messaging for TwitchAdapter {
                 // which sends a Text response after StreamEnd.
             }
             OutboundResponse::StreamEnd => {}
-            // Reactions, status updates, and Slack-specific variants aren't meaningful in Twitch chat
+            // Reactions, status updates, and Slack-specific variants aren't meaningful in Twitch chat.
+            // Reasoning would flood a public chat with short line limits, so it's dropped too.
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::HomeView { .. }
+            | OutboundResponse::Reasoning(_)
             | OutboundResponse::Status(_) => {}
             OutboundResponse::Ephemeral { text, .. } => {
                 // No ephemeral concept in Twitch — send as regular chat message
@@ -444,6 +447,15 @@ impl Messaging for TwitchAdapter {
                     .await
                     .context("failed to send ephemeral fallback on twitch")?;
             }
+            OutboundResponse::Sticker { fallback, .. } => {
+                // No stickers on Twitch — send the fallback emote text, if any
+                if let Some(text) = fallback {
+                    client
+                        .say(channel.to_owned(), text)
+                        .await
+                        .context("failed to send sticker fallback on twitch")?;
+                }
+            }
             OutboundResponse::ScheduledMessage { text, .. } => {
                 // No scheduled messages on Twitch — send immediately
                 client
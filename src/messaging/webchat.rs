@@ -96,6 +96,10 @@ impl Messaging for WebChatAdapter {
             agent_id: agent_id.to_string(),
             channel_id: target.to_string(),
             text,
+            cost_usd: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            latency_ms: None,
         })
         .ok();
 
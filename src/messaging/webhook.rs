@@ -4,6 +4,9 @@
 //! delivers responses via a per-conversation polling endpoint. This is
 //! the integration point for scripts, CI pipelines, and other programs
 //! that need to interact with Spacebot programmatically.
+//!
+//! Requests are authenticated with a shared bearer token, or for senders
+//! that sign their payloads, an HMAC-SHA256 signature over the raw body.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,17 +17,25 @@ use axum::extract::{Json, State};
 use axum::http::header::AUTHORIZATION;
 use axum::http::{HeaderMap, StatusCode};
 use axum::routing::{get, post};
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use tokio::sync::{RwLock, mpsc};
 
 use crate::messaging::traits::{InboundStream, Messaging};
 use crate::{InboundMessage, MessageContent, OutboundResponse};
 
+/// Header carrying the payload signature unless configured otherwise
+/// (GitHub's format: `sha256=<hex>`).
+pub const DEFAULT_SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
 /// Webhook adapter state.
 pub struct WebhookAdapter {
     port: u16,
     bind: String,
     auth_token: Option<String>,
+    signing: Option<SigningConfig>,
     inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
     /// Buffered responses per conversation_id, waiting to be polled.
     response_buffers: Arc<RwLock<HashMap<String, Vec<WebhookResponse>>>>,
@@ -37,9 +48,17 @@ struct AppState {
     inbound_tx: Arc<RwLock<Option<mpsc::Sender<InboundMessage>>>>,
     response_buffers: Arc<RwLock<HashMap<String, Vec<WebhookResponse>>>>,
     auth_token: Option<String>,
+    signing: Option<SigningConfig>,
     runtime_key: String,
 }
 
+/// HMAC payload signature settings.
+#[derive(Clone)]
+struct SigningConfig {
+    secret: String,
+    header: String,
+}
+
 /// Inbound webhook request body.
 #[derive(Debug, Deserialize)]
 struct WebhookRequest {
@@ -81,11 +100,24 @@ impl WebhookAdapter {
             port,
             bind: bind.into(),
             auth_token,
+            signing: None,
             inbound_tx: Arc::new(RwLock::new(None)),
             response_buffers: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx: Arc::new(RwLock::new(None)),
         }
     }
+
+    /// Verify HMAC-SHA256 payload signatures on `/send`, read from `header`.
+    /// `None` leaves signature verification off.
+    pub fn with_signing_secret(mut self, secret: Option<String>, header: &str) -> Self {
+        self.signing = secret
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| SigningConfig {
+                secret,
+                header: header.to_string(),
+            });
+        self
+    }
 }
 
 impl Messaging for WebhookAdapter {
@@ -104,10 +136,11 @@ impl Messaging for WebhookAdapter {
             inbound_tx: self.inbound_tx.clone(),
             response_buffers: self.response_buffers.clone(),
             auth_token: self.auth_token.clone(),
+            signing: self.signing.clone(),
             runtime_key: self.name().to_string(),
         };
 
-        if self.auth_token.is_none() {
+        if self.auth_token.is_none() && self.signing.is_none() {
             tracing::warn!(
                 "webhook authentication is disabled because no auth token is configured"
             );
@@ -194,9 +227,17 @@ impl Messaging for WebhookAdapter {
                 filename: None,
                 caption: None,
             },
-            // Reactions, status updates, and remove-reaction aren't meaningful over webhook
+            OutboundResponse::Reasoning(text) => WebhookResponse {
+                response_type: "reasoning".into(),
+                content: Some(text),
+                filename: None,
+                caption: None,
+            },
+            // Reactions, status updates, remove-reaction, and Slack Home views aren't
+            // meaningful over webhook
             OutboundResponse::Reaction(_)
             | OutboundResponse::RemoveReaction(_)
+            | OutboundResponse::HomeView { .. }
             | OutboundResponse::Status(_) => return Ok(()),
             // Slack-specific rich variants — fall back to plain text
             OutboundResponse::Ephemeral { text, .. } => WebhookResponse {
@@ -211,6 +252,15 @@ impl Messaging for WebhookAdapter {
                 filename: None,
                 caption: None,
             },
+            OutboundResponse::Sticker { fallback, .. } => match fallback {
+                Some(text) => WebhookResponse {
+                    response_type: "text".into(),
+                    content: Some(text),
+                    filename: None,
+                    caption: None,
+                },
+                None => return Ok(()),
+            },
         };
 
         self.response_buffers
@@ -241,12 +291,19 @@ impl Messaging for WebhookAdapter {
 async fn handle_send(
     headers: HeaderMap,
     State(state): State<AppState>,
-    Json(request): Json<WebhookRequest>,
+    body: axum::body::Bytes,
 ) -> Result<StatusCode, (StatusCode, String)> {
-    if !is_authorized(&headers, state.auth_token.as_deref()) {
+    if !is_send_authorized(&headers, &body, &state) {
         return Err((StatusCode::UNAUTHORIZED, "unauthorized".into()));
     }
 
+    let request: WebhookRequest = serde_json::from_slice(&body).map_err(|error| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid request body: {error}"),
+        )
+    })?;
+
     let tx = state.inbound_tx.read().await;
     let Some(tx) = tx.as_ref() else {
         return Err((
@@ -319,6 +376,55 @@ async fn handle_health() -> StatusCode {
     StatusCode::OK
 }
 
+/// Authorize a `/send` request.
+///
+/// With a signing secret configured, a signed request is accepted only if the
+/// signature matches. Unsigned requests then fall back to the bearer token,
+/// and are rejected when no token is configured.
+fn is_send_authorized(headers: &HeaderMap, body: &[u8], state: &AppState) -> bool {
+    let Some(signing) = &state.signing else {
+        return is_authorized(headers, state.auth_token.as_deref());
+    };
+
+    match headers
+        .get(signing.header.as_str())
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(signature) => verify_signature(&signing.secret, body, signature),
+        None => state.auth_token.is_some() && is_authorized(headers, state.auth_token.as_deref()),
+    }
+}
+
+/// Check an HMAC-SHA256 signature of `body`.
+///
+/// Accepts `sha256=<hex>` (GitHub, Gitea), bare hex, and base64 (Shopify and
+/// similar) encodings. Comparison is constant-time.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let signature = signature
+        .strip_prefix("sha256=")
+        .or_else(|| signature.strip_prefix("SHA256="))
+        .unwrap_or(signature);
+
+    // A SHA-256 MAC is 32 bytes: 64 hex characters or 44 base64 characters.
+    let expected = if signature.len() == 64 {
+        hex::decode(signature).ok()
+    } else {
+        base64::engine::general_purpose::STANDARD
+            .decode(signature)
+            .ok()
+    };
+    let Some(expected) = expected else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
 fn is_authorized(headers: &HeaderMap, expected_token: Option<&str>) -> bool {
     let Some(expected_token) = expected_token else {
         return true;
@@ -338,3 +444,66 @@ fn is_authorized(headers: &HeaderMap, expected_token: Option<&str>) -> bool {
         .and_then(|value| value.strip_prefix("Bearer "))
         .is_some_and(|token| token == expected_token)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector from GitHub's webhook signature documentation.
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+    const HEX_SIGNATURE: &str = "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    fn state(auth_token: Option<&str>) -> AppState {
+        AppState {
+            inbound_tx: Arc::new(RwLock::new(None)),
+            response_buffers: Arc::new(RwLock::new(HashMap::new())),
+            auth_token: auth_token.map(Into::into),
+            signing: Some(SigningConfig {
+                secret: SECRET.into(),
+                header: DEFAULT_SIGNATURE_HEADER.into(),
+            }),
+            runtime_key: "webhook".into(),
+        }
+    }
+
+    #[test]
+    fn verifies_common_signature_formats() {
+        assert!(verify_signature(
+            SECRET,
+            BODY,
+            &format!("sha256={HEX_SIGNATURE}")
+        ));
+        assert!(verify_signature(SECRET, BODY, HEX_SIGNATURE));
+        assert!(verify_signature(
+            SECRET,
+            BODY,
+            "dXEH6g6yUJ/CESIczphLijdXC211hsIsRvQ3nIsEPhc="
+        ));
+
+        assert!(!verify_signature(SECRET, b"Hello, World?", HEX_SIGNATURE));
+        assert!(!verify_signature("wrong secret", BODY, HEX_SIGNATURE));
+        assert!(!verify_signature(SECRET, BODY, "sha256=not-a-signature"));
+    }
+
+    #[test]
+    fn signed_requests_must_match_and_unsigned_need_a_token() {
+        let mut signed = HeaderMap::new();
+        signed.insert(
+            DEFAULT_SIGNATURE_HEADER,
+            format!("sha256={HEX_SIGNATURE}").parse().unwrap(),
+        );
+        assert!(is_send_authorized(&signed, BODY, &state(None)));
+        assert!(!is_send_authorized(&signed, b"tampered", &state(None)));
+
+        let mut bearer = HeaderMap::new();
+        bearer.insert(AUTHORIZATION, "Bearer token".parse().unwrap());
+        assert!(!is_send_authorized(&bearer, BODY, &state(None)));
+        assert!(is_send_authorized(&bearer, BODY, &state(Some("token"))));
+
+        // A bad signature is rejected even when a valid token is also sent.
+        let mut both = bearer.clone();
+        both.insert(DEFAULT_SIGNATURE_HEADER, "sha256=00".parse().unwrap());
+        assert!(!is_send_authorized(&both, BODY, &state(Some("token"))));
+    }
+}
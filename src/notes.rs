@@ -0,0 +1,9 @@
+//! Agent scratchpad notes: free-form text the agent manages itself.
+//!
+//! Unlike memories, which are recalled semantically, notes are read back
+//! verbatim. They suit a running plan or state the agent keeps between turns.
+//! Each agent has one agent-wide note and one note per channel.
+
+pub mod store;
+
+pub use store::{MAX_NOTE_CHARS, Note, NotesStore};
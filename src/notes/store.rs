@@ -0,0 +1,207 @@
+//! Notes storage (SQLite).
+
+use crate::error::Result;
+use anyhow::Context as _;
+use serde::Serialize;
+use sqlx::{Row as _, SqlitePool};
+
+/// Longest a single note may grow, in characters. Appends and replacements
+/// past this are rejected so a runaway loop can't bloat the prompt or the
+/// database.
+pub const MAX_NOTE_CHARS: usize = 16_000;
+
+/// Scope key for the agent-wide note.
+const AGENT_SCOPE: &str = "";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Note {
+    /// Channel the note belongs to, or `None` for the agent-wide note.
+    pub channel_id: Option<String>,
+    pub content: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotesStore {
+    pool: SqlitePool,
+}
+
+impl NotesStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Read a note. `channel_id` of `None` reads the agent-wide note.
+    pub async fn read(&self, channel_id: Option<&str>) -> Result<Option<Note>> {
+        let row =
+            sqlx::query("SELECT scope, content, created_at, updated_at FROM notes WHERE scope = ?")
+                .bind(scope_key(channel_id))
+                .fetch_optional(&self.pool)
+                .await
+                .context("failed to read note")?;
+
+        row.map(note_from_row).transpose()
+    }
+
+    /// Append a line to a note, creating it if it doesn't exist.
+    pub async fn append(&self, channel_id: Option<&str>, text: &str) -> Result<Note> {
+        let scope = scope_key(channel_id);
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("failed to open note append transaction")?;
+
+        let existing: Option<String> =
+            sqlx::query_scalar("SELECT content FROM notes WHERE scope = ?")
+                .bind(scope)
+                .fetch_optional(&mut *tx)
+                .await
+                .context("failed to read note for append")?;
+
+        let content = match existing {
+            Some(existing) if !existing.is_empty() => format!("{existing}\n{text}"),
+            _ => text.to_string(),
+        };
+        check_length(&content)?;
+
+        upsert(&mut *tx, scope, &content).await?;
+        tx.commit().await.context("failed to commit note append")?;
+
+        Ok(self
+            .read(channel_id)
+            .await?
+            .context("note missing after append")?)
+    }
+
+    /// Overwrite a note with new content.
+    pub async fn replace(&self, channel_id: Option<&str>, content: &str) -> Result<Note> {
+        check_length(content)?;
+        upsert(&self.pool, scope_key(channel_id), content).await?;
+
+        Ok(self
+            .read(channel_id)
+            .await?
+            .context("note missing after replace")?)
+    }
+
+    /// Delete a note. Returns whether one existed.
+    pub async fn clear(&self, channel_id: Option<&str>) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM notes WHERE scope = ?")
+            .bind(scope_key(channel_id))
+            .execute(&self.pool)
+            .await
+            .context("failed to clear note")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn scope_key(channel_id: Option<&str>) -> &str {
+    channel_id.unwrap_or(AGENT_SCOPE)
+}
+
+fn check_length(content: &str) -> Result<()> {
+    let length = content.chars().count();
+    if length > MAX_NOTE_CHARS {
+        return Err(anyhow::anyhow!(
+            "note would be {length} characters, over the {MAX_NOTE_CHARS} character limit; \
+             replace it with a condensed version instead"
+        )
+        .into());
+    }
+    Ok(())
+}
+
+async fn upsert<'e, E>(executor: E, scope: &str, content: &str) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO notes (scope, content) VALUES (?, ?)
+        ON CONFLICT(scope) DO UPDATE SET
+            content = excluded.content,
+            updated_at = datetime('now')
+        "#,
+    )
+    .bind(scope)
+    .bind(content)
+    .execute(executor)
+    .await
+    .context("failed to write note")?;
+
+    Ok(())
+}
+
+fn note_from_row(row: sqlx::sqlite::SqliteRow) -> Result<Note> {
+    let scope: String = row.try_get("scope").context("failed to read note scope")?;
+
+    Ok(Note {
+        channel_id: (scope != AGENT_SCOPE).then_some(scope),
+        content: row
+            .try_get("content")
+            .context("failed to read note content")?,
+        created_at: row
+            .try_get::<chrono::NaiveDateTime, _>("created_at")
+            .map(|v| v.and_utc().to_rfc3339())
+            .context("failed to read note created_at")?,
+        updated_at: row
+            .try_get::<chrono::NaiveDateTime, _>("updated_at")
+            .map(|v| v.and_utc().to_rfc3339())
+            .context("failed to read note updated_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_store() -> NotesStore {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        NotesStore::new(pool)
+    }
+
+    #[tokio::test]
+    async fn append_replace_and_clear_are_scoped() {
+        let store = setup_store().await;
+
+        store.append(None, "plan: ship notes").await.unwrap();
+        let note = store.append(None, "then: docs").await.unwrap();
+        assert_eq!(note.content, "plan: ship notes\nthen: docs");
+        assert_eq!(note.channel_id, None);
+
+        let channel_note = store
+            .replace(Some("discord:1:2"), "channel only")
+            .await
+            .unwrap();
+        assert_eq!(channel_note.channel_id.as_deref(), Some("discord:1:2"));
+
+        let agent_note = store.read(None).await.unwrap().unwrap();
+        assert_eq!(agent_note.content, "plan: ship notes\nthen: docs");
+
+        assert!(store.clear(Some("discord:1:2")).await.unwrap());
+        assert!(!store.clear(Some("discord:1:2")).await.unwrap());
+        assert!(store.read(Some("discord:1:2")).await.unwrap().is_none());
+        assert!(store.read(None).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn rejects_notes_over_the_limit() {
+        let store = setup_store().await;
+
+        store
+            .replace(None, &"a".repeat(MAX_NOTE_CHARS))
+            .await
+            .unwrap();
+        assert!(store.append(None, "more").await.is_err());
+        assert!(
+            store
+                .replace(None, &"b".repeat(MAX_NOTE_CHARS + 1))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -4,10 +4,11 @@
 //! delegates to an OpenCode subprocess that has its own codebase exploration,
 //! context management, and tool suite. Communication happens over HTTP + SSE.
 
+use crate::agent::process_limit::{ProcessPermit, ProcessPriority};
 use crate::opencode::server::OpenCodeServerPool;
 use crate::opencode::types::*;
 use crate::secrets::store::SecretsStore;
-use crate::{AgentId, ChannelId, ProcessEvent, WorkerId};
+use crate::{AgentDeps, AgentId, ChannelId, ProcessEvent, WorkerId};
 
 use anyhow::{Context as _, bail};
 use futures::StreamExt as _;
@@ -44,6 +45,9 @@ pub struct OpenCodeWorker {
     pub sqlite_pool: Option<sqlx::SqlitePool>,
     /// Pre-populated session state for resumed workers (set by `resume_interactive`).
     pub resuming_session: Option<ResumeSession>,
+    /// Agent whose process limit each OpenCode turn counts against (set by
+    /// channel_dispatch). No slot is held while waiting for a follow-up.
+    pub process_deps: Option<AgentDeps>,
 }
 
 /// Accumulated state from SSE event processing.
@@ -109,6 +113,7 @@ impl OpenCodeWorker {
             secrets_store: None,
             sqlite_pool: None,
             resuming_session: None,
+            process_deps: None,
         }
     }
 
@@ -151,6 +156,23 @@ impl OpenCodeWorker {
         self
     }
 
+    /// Count each OpenCode turn against the agent's process limit.
+    pub fn with_process_limit(mut self, deps: AgentDeps) -> Self {
+        self.process_deps = Some(deps);
+        self
+    }
+
+    /// Wait for a process slot, if a process limit is set.
+    async fn acquire_process_permit(&self) -> Option<ProcessPermit> {
+        match &self.process_deps {
+            Some(deps) => Some(
+                deps.acquire_process_permit(ProcessPriority::Foreground)
+                    .await,
+            ),
+            None => None,
+        }
+    }
+
     /// Create a resumed interactive OpenCode worker for an idle session.
     ///
     /// Instead of creating a new session, reconnects to `session_id` on the
@@ -303,7 +325,9 @@ impl OpenCodeWorker {
 
                 (server, resume.session_id, event_state, String::new())
             } else {
-                // Fresh worker: create a new server + session.
+                // Fresh worker: create a new server + session. The process
+                // slot is held until the initial task finishes.
+                let _permit = self.acquire_process_permit().await;
                 self.send_status("starting OpenCode server");
 
                 let server = self
@@ -408,6 +432,7 @@ impl OpenCodeWorker {
             }
 
             while let Some(follow_up) = input_rx.recv().await {
+                let _permit = self.acquire_process_permit().await;
                 self.send_status("processing follow-up");
 
                 // Subscribe to fresh events for the follow-up
@@ -18,16 +18,31 @@ pub struct RetriggerResult {
     pub result: String,
 }
 
+/// Process templates that an agent's `prompt_overrides` may replace.
+pub const OVERRIDABLE_TEMPLATES: &[&str] = &[
+    "channel",
+    "branch",
+    "worker",
+    "compactor",
+    "cortex",
+    "cortex_chat",
+    "ingestion",
+];
+
 /// Template engine for rendering system prompts with dynamic variables.
 ///
 /// Prompts are bundled in the binary as `include_str!` embedded templates.
-/// Language selection is done at initialization and templates are not
-/// reloadable at runtime (no file watching, no hot reload).
+/// Language selection is done at initialization. Process templates can be
+/// replaced per agent with files on disk via [`PromptEngine::with_overrides`];
+/// the runtime config rebuilds the engine when those files change.
 #[derive(Clone)]
 pub struct PromptEngine {
     /// The MiniJinja environment holding all templates for the configured language.
     /// Wrapped in Arc to make PromptEngine Clone.
     env: Arc<Environment<'static>>,
+    /// Override template sources by template name. Rendered in place of the
+    /// bundled template of the same name.
+    overrides: Arc<HashMap<String, String>>,
     /// Selected language code (e.g., "en").
     language: String,
 }
@@ -69,6 +84,10 @@ impl PromptEngine {
             "cortex_daily_summary",
             crate::prompts::text::get("cortex_daily_summary"),
         )?;
+        env.add_template(
+            "cortex_memory_consolidation",
+            crate::prompts::text::get("cortex_memory_consolidation"),
+        )?;
         env.add_template("compactor", crate::prompts::text::get("compactor"))?;
         env.add_template(
             "memory_persistence",
@@ -81,6 +100,14 @@ impl PromptEngine {
             crate::prompts::text::get("cortex_profile"),
         )?;
         env.add_template("factory", crate::prompts::text::get("factory"))?;
+        env.add_template(
+            "task_extraction",
+            crate::prompts::text::get("task_extraction"),
+        )?;
+        env.add_template(
+            "inbound_summary",
+            crate::prompts::text::get("inbound_summary"),
+        )?;
 
         // Adapter-specific prompt fragments
         env.add_template(
@@ -148,10 +175,18 @@ impl PromptEngine {
             "fragments/system/cortex_synthesis",
             crate::prompts::text::get("fragments/system/cortex_synthesis"),
         )?;
+        env.add_template(
+            "fragments/system/memory_consolidation",
+            crate::prompts::text::get("fragments/system/memory_consolidation"),
+        )?;
         env.add_template(
             "fragments/system/profile_synthesis",
             crate::prompts::text::get("fragments/system/profile_synthesis"),
         )?;
+        env.add_template(
+            "fragments/system/cortex_language",
+            crate::prompts::text::get("fragments/system/cortex_language"),
+        )?;
         env.add_template(
             "fragments/system/ingestion_chunk",
             crate::prompts::text::get("fragments/system/ingestion_chunk"),
@@ -171,10 +206,53 @@ impl PromptEngine {
 
         Ok(Self {
             env: Arc::new(env),
+            overrides: Arc::new(HashMap::new()),
             language: language.to_string(),
         })
     }
 
+    /// Return an engine that renders the given files in place of the bundled
+    /// templates. Replaces any overrides this engine already has.
+    ///
+    /// Each file is read and compiled here so a broken template is reported
+    /// when it is loaded rather than on the next turn that renders it.
+    pub fn with_overrides(&self, overrides: &crate::config::PromptOverrides) -> Result<Self> {
+        let mut sources = HashMap::new();
+        for (name, path) in overrides.iter() {
+            if !OVERRIDABLE_TEMPLATES.contains(&name) {
+                return Err(
+                    anyhow::anyhow!("'{name}' is not an overridable prompt template").into(),
+                );
+            }
+            let source = std::fs::read_to_string(path).with_context(|| {
+                format!(
+                    "failed to read prompt override '{name}' from {}",
+                    path.display()
+                )
+            })?;
+            Environment::new()
+                .template_from_named_str(name, &source)
+                .with_context(|| {
+                    format!("invalid prompt override '{name}' in {}", path.display())
+                })?;
+            tracing::info!(template = name, path = %path.display(), "prompt template overridden");
+            sources.insert(name.to_string(), source);
+        }
+
+        Ok(Self {
+            env: self.env.clone(),
+            overrides: Arc::new(sources),
+            language: self.language.clone(),
+        })
+    }
+
+    /// Names of the templates currently replaced by override files.
+    pub fn overridden_templates(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.overrides.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
     /// Render a template by name with the given context variables.
     ///
     /// # Arguments
@@ -193,6 +271,17 @@ impl PromptEngine {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn render(&self, template_name: &str, context: Value) -> Result<String> {
+        // Override sources are owned by the engine rather than leaked into the
+        // 'static environment, so they compile against a scratch environment.
+        if let Some(source) = self.overrides.get(template_name) {
+            let env = Environment::new();
+            return env
+                .template_from_named_str(template_name, source)
+                .and_then(|template| template.render(context))
+                .with_context(|| format!("failed to render template '{}'", template_name))
+                .map_err(Into::into);
+        }
+
         let template = self
             .env
             .get_template(template_name)
@@ -234,6 +323,17 @@ impl PromptEngine {
         )
     }
 
+    /// Convenience method for rendering the compactor prompt with its summary budget.
+    pub fn render_compactor_prompt(&self, summary_max_tokens: usize) -> Result<String> {
+        self.render(
+            "compactor",
+            context! {
+                summary_max_tokens => summary_max_tokens,
+                summary_max_words => summary_max_tokens * 3 / 4,
+            },
+        )
+    }
+
     /// Convenience method for rendering conversation context fragment.
     pub fn render_conversation_context(
         &self,
@@ -395,6 +495,16 @@ impl PromptEngine {
         )
     }
 
+    /// Render the output language instruction appended to cortex preambles.
+    pub fn render_system_cortex_language(&self, language: &str) -> Result<String> {
+        self.render(
+            "fragments/system/cortex_language",
+            context! {
+                language => language,
+            },
+        )
+    }
+
     /// Convenience method for rendering cortex synthesis prompt.
     pub fn render_system_cortex_synthesis(
         &self,
@@ -410,6 +520,31 @@ impl PromptEngine {
         )
     }
 
+    /// Convenience method for rendering the memory consolidation prompt.
+    /// Memories are listed in the order given, which should be oldest first.
+    pub fn render_system_memory_consolidation(
+        &self,
+        memory_type: &str,
+        memories: &[crate::memory::Memory],
+    ) -> Result<String> {
+        let memories: Vec<_> = memories
+            .iter()
+            .map(|memory| {
+                context! {
+                    created_at => memory.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    content => memory.content.as_str(),
+                }
+            })
+            .collect();
+        self.render(
+            "fragments/system/memory_consolidation",
+            context! {
+                memory_type => memory_type,
+                memories => memories,
+            },
+        )
+    }
+
     /// Render the intra-day synthesis prompt.
     pub fn render_intraday_synthesis(
         &self,
@@ -522,6 +657,12 @@ impl PromptEngine {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
     }
 
@@ -631,6 +772,12 @@ impl PromptEngine {
         backfill_transcript: Option<String>,
         working_memory: Option<String>,
         channel_activity_map: Option<String>,
+        output_language: Option<String>,
+        pinned_context: Option<String>,
+        conversation_slots: Option<String>,
+        notes_context: Option<String>,
+        recalled_memories: Option<String>,
+        persona: Option<ActivePersona>,
     ) -> Result<String> {
         // During the transition, the bulletin is also exposed as knowledge_synthesis
         // so the template can render it under the new heading.
@@ -655,6 +802,12 @@ impl PromptEngine {
                 working_memory => working_memory,
                 channel_activity_map => channel_activity_map,
                 knowledge_synthesis => knowledge_synthesis,
+                output_language => output_language,
+                pinned_context => pinned_context,
+                conversation_slots => conversation_slots,
+                notes_context => notes_context,
+                recalled_memories => recalled_memories,
+                persona => persona,
             },
         )
     }
@@ -665,6 +818,13 @@ impl PromptEngine {
     }
 }
 
+/// Persona active in a channel, injected into the channel prompt.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ActivePersona {
+    pub name: String,
+    pub prompt: String,
+}
+
 /// Organizational context for an agent — grouped by relationship.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct OrgContext {
@@ -68,12 +68,17 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "cortex_daily_summary") => {
             include_str!("../../prompts/en/cortex_daily_summary.md.j2")
         }
+        ("en", "cortex_memory_consolidation") => {
+            include_str!("../../prompts/en/cortex_memory_consolidation.md.j2")
+        }
         ("en", "cortex_profile") => include_str!("../../prompts/en/cortex_profile.md.j2"),
         ("en", "compactor") => include_str!("../../prompts/en/compactor.md.j2"),
         ("en", "memory_persistence") => include_str!("../../prompts/en/memory_persistence.md.j2"),
         ("en", "ingestion") => include_str!("../../prompts/en/ingestion.md.j2"),
         ("en", "cortex_chat") => include_str!("../../prompts/en/cortex_chat.md.j2"),
         ("en", "factory") => include_str!("../../prompts/en/factory.md.j2"),
+        ("en", "task_extraction") => include_str!("../../prompts/en/task_extraction.md.j2"),
+        ("en", "inbound_summary") => include_str!("../../prompts/en/inbound_summary.md.j2"),
 
         // Adapter-specific prompt fragments
         ("en", "adapters/email") => include_str!("../../prompts/en/adapters/email.md.j2"),
@@ -116,9 +121,15 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "fragments/system/cortex_synthesis") => {
             include_str!("../../prompts/en/fragments/system/cortex_synthesis.md.j2")
         }
+        ("en", "fragments/system/memory_consolidation") => {
+            include_str!("../../prompts/en/fragments/system/memory_consolidation.md.j2")
+        }
         ("en", "fragments/system/profile_synthesis") => {
             include_str!("../../prompts/en/fragments/system/profile_synthesis.md.j2")
         }
+        ("en", "fragments/system/cortex_language") => {
+            include_str!("../../prompts/en/fragments/system/cortex_language.md.j2")
+        }
         ("en", "fragments/system/ingestion_chunk") => {
             include_str!("../../prompts/en/fragments/system/ingestion_chunk.md.j2")
         }
@@ -150,7 +161,39 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/route") => include_str!("../../prompts/en/tools/route_description.md.j2"),
         ("en", "tools/cancel") => include_str!("../../prompts/en/tools/cancel_description.md.j2"),
         ("en", "tools/skip") => include_str!("../../prompts/en/tools/skip_description.md.j2"),
+        ("en", "tools/request_human") => {
+            include_str!("../../prompts/en/tools/request_human_description.md.j2")
+        }
+        ("en", "tools/reset_conversation") => {
+            include_str!("../../prompts/en/tools/reset_conversation_description.md.j2")
+        }
         ("en", "tools/react") => include_str!("../../prompts/en/tools/react_description.md.j2"),
+        ("en", "tools/send_sticker") => {
+            include_str!("../../prompts/en/tools/send_sticker_description.md.j2")
+        }
+        ("en", "tools/publish_home_view") => {
+            include_str!("../../prompts/en/tools/publish_home_view_description.md.j2")
+        }
+        ("en", "tools/pin_context") => {
+            include_str!("../../prompts/en/tools/pin_context_description.md.j2")
+        }
+        ("en", "tools/unpin") => include_str!("../../prompts/en/tools/unpin_description.md.j2"),
+        ("en", "tools/list_pins") => {
+            include_str!("../../prompts/en/tools/list_pins_description.md.j2")
+        }
+        ("en", "tools/set_slot") => {
+            include_str!("../../prompts/en/tools/set_slot_description.md.j2")
+        }
+        ("en", "tools/get_slot") => {
+            include_str!("../../prompts/en/tools/get_slot_description.md.j2")
+        }
+        ("en", "tools/notes") => include_str!("../../prompts/en/tools/notes_description.md.j2"),
+        ("en", "tools/switch_persona") => {
+            include_str!("../../prompts/en/tools/switch_persona_description.md.j2")
+        }
+        ("en", "tools/list_personas") => {
+            include_str!("../../prompts/en/tools/list_personas_description.md.j2")
+        }
         ("en", "tools/set_status") => {
             include_str!("../../prompts/en/tools/set_status_description.md.j2")
         }
@@ -186,6 +229,9 @@ fn lookup(lang: &str, key: &str) -> &'static str {
         ("en", "tools/memory_delete") => {
             include_str!("../../prompts/en/tools/memory_delete_description.md.j2")
         }
+        ("en", "tools/forget_memory") => {
+            include_str!("../../prompts/en/tools/forget_memory_description.md.j2")
+        }
         ("en", "tools/channel_recall") => {
             include_str!("../../prompts/en/tools/channel_recall_description.md.j2")
         }
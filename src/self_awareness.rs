@@ -182,12 +182,14 @@ pub fn runtime_snapshot_value(agent_id: &str, runtime_config: &RuntimeConfig) ->
             "context_window": **runtime_config.context_window.load(),
             "max_concurrent_branches": **runtime_config.max_concurrent_branches.load(),
             "max_concurrent_workers": **runtime_config.max_concurrent_workers.load(),
+            "max_concurrent_processes": **runtime_config.max_concurrent_processes.load(),
             "history_backfill_count": **runtime_config.history_backfill_count.load(),
         },
         "compaction": {
             "background_threshold": compaction.background_threshold,
             "aggressive_threshold": compaction.aggressive_threshold,
             "emergency_threshold": compaction.emergency_threshold,
+            "max_turns_before_compact": compaction.max_turns_before_compact,
         },
         "memory_persistence": {
             "enabled": memory_persistence.enabled,
@@ -204,6 +206,9 @@ pub fn runtime_snapshot_value(agent_id: &str, runtime_config: &RuntimeConfig) ->
             "enabled": ingestion.enabled,
             "poll_interval_secs": ingestion.poll_interval_secs,
             "chunk_size": ingestion.chunk_size,
+            "concurrency": ingestion.concurrency,
+            "extensions": ingestion.extensions,
+            "max_retries": ingestion.max_retries,
         },
         "cortex": {
             "tick_interval_secs": cortex.tick_interval_secs,
@@ -217,10 +222,14 @@ pub fn runtime_snapshot_value(agent_id: &str, runtime_config: &RuntimeConfig) ->
             "association_similarity_threshold": cortex.association_similarity_threshold,
             "association_updates_threshold": cortex.association_updates_threshold,
             "association_max_per_pass": cortex.association_max_per_pass,
+            "consolidation_interval_secs": cortex.consolidation_interval_secs,
+            "consolidation_min_cluster_size": cortex.consolidation_min_cluster_size,
+            "consolidation_min_importance": cortex.consolidation_min_importance,
         },
         "warmup": {
             "enabled": warmup.enabled,
             "eager_embedding_load": warmup.eager_embedding_load,
+            "preload_models": warmup.preload_models,
             "refresh_secs": warmup.refresh_secs,
             "startup_delay_secs": warmup.startup_delay_secs,
             "state": warmup_status.state,
@@ -2,4 +2,7 @@
 
 pub mod store;
 
-pub use store::{CHANNEL_LISTEN_ONLY_MODE_KEY, SettingsStore, WORKER_LOG_MODE_KEY, WorkerLogMode};
+pub use store::{
+    BulletinOverride, CHANNEL_LISTEN_ONLY_MODE_KEY, ConversationSlot, MEMORY_BULLETIN_OVERRIDE_KEY,
+    PinnedContext, SettingsStore, WORKER_LOG_MODE_KEY, WorkerLogMode,
+};
@@ -3,6 +3,7 @@
 use crate::error::{Result, SettingsError};
 use redb::{Database, TableDefinition};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -15,6 +16,25 @@ pub const WORKER_LOG_MODE_KEY: &str = "worker_log_mode";
 pub const CHANNEL_LISTEN_ONLY_MODE_KEY: &str = "channel_listen_only_mode";
 const CHANNEL_LISTEN_ONLY_MODE_PREFIX: &str = "channel_listen_only_mode:";
 const PROMPT_CAPTURE_PREFIX: &str = "prompt_capture:";
+const OUTPUT_LANGUAGE_PREFIX: &str = "output_language:";
+const SHOW_REASONING_PREFIX: &str = "show_reasoning:";
+const PINNED_CONTEXT_PREFIX: &str = "pinned_context:";
+const ACTIVE_PERSONA_PREFIX: &str = "active_persona:";
+const CHANNEL_MODEL_PREFIX: &str = "channel_model:";
+const CONVERSATION_SLOTS_PREFIX: &str = "conversation_slots:";
+/// Key for a manually set memory bulletin.
+pub const MEMORY_BULLETIN_OVERRIDE_KEY: &str = "memory_bulletin_override";
+
+/// Maximum number of pinned snippets per channel.
+pub const MAX_PINS_PER_CHANNEL: usize = 20;
+/// Maximum length of a single pinned snippet, in characters.
+pub const MAX_PIN_CHARS: usize = 2_000;
+/// Maximum number of slots per channel.
+pub const MAX_SLOTS_PER_CHANNEL: usize = 50;
+/// Maximum length of a slot key, in characters.
+pub const MAX_SLOT_KEY_CHARS: usize = 64;
+/// Maximum length of a slot value, in characters.
+pub const MAX_SLOT_VALUE_CHARS: usize = 1_000;
 
 /// How worker execution logs are stored.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -52,6 +72,34 @@ impl std::str::FromStr for WorkerLogMode {
     }
 }
 
+/// A snippet pinned to a conversation. Pinned snippets are rendered into
+/// every channel prompt and are never compacted away.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PinnedContext {
+    /// Short identifier used to unpin the snippet.
+    pub id: String,
+    pub text: String,
+    pub pinned_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A value the agent stored under a key in one conversation. Unlike memories,
+/// slots are exact: what was set is what is read back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConversationSlot {
+    pub value: String,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A memory bulletin set by hand in place of the cortex-generated one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BulletinOverride {
+    pub bulletin: String,
+    /// Keep the override across cortex refreshes. Otherwise the next
+    /// generated bulletin replaces it.
+    pub permanent: bool,
+    pub set_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Settings store backed by redb.
 pub struct SettingsStore {
     db: Arc<Database>,
@@ -155,6 +203,86 @@ impl SettingsStore {
         Ok(())
     }
 
+    /// Remove a key. Missing keys are not an error.
+    fn remove_raw(&self, key: &str) -> Result<()> {
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|e| SettingsError::WriteFailed {
+                key: key.to_string(),
+                details: e.to_string(),
+            })?;
+
+        {
+            let mut table =
+                write_txn
+                    .open_table(SETTINGS_TABLE)
+                    .map_err(|e| SettingsError::WriteFailed {
+                        key: key.to_string(),
+                        details: e.to_string(),
+                    })?;
+
+            table.remove(key).map_err(|e| SettingsError::WriteFailed {
+                key: key.to_string(),
+                details: e.to_string(),
+            })?;
+        }
+
+        write_txn.commit().map_err(|e| SettingsError::WriteFailed {
+            key: key.to_string(),
+            details: e.to_string(),
+        })?;
+
+        Ok(())
+    }
+
+    /// Read and rewrite a key inside one write transaction, so concurrent
+    /// updates can't overwrite each other. `update` gets the current value
+    /// and returns the new one (`None` removes the key) plus a result to hand
+    /// back. Nothing is written if it returns an error.
+    fn update_raw<T>(
+        &self,
+        key: &str,
+        update: impl FnOnce(Option<&str>) -> Result<(Option<String>, T)>,
+    ) -> Result<T> {
+        let write_failed = |details: String| SettingsError::WriteFailed {
+            key: key.to_string(),
+            details,
+        };
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|e| write_failed(e.to_string()))?;
+
+        let output = {
+            let mut table = write_txn
+                .open_table(SETTINGS_TABLE)
+                .map_err(|e| write_failed(e.to_string()))?;
+            let current = table
+                .get(key)
+                .map_err(|e| write_failed(e.to_string()))?
+                .map(|value| value.value().to_string());
+            let (next, output) = update(current.as_deref())?;
+            match next {
+                Some(value) => {
+                    table
+                        .insert(key, value.as_str())
+                        .map_err(|e| write_failed(e.to_string()))?;
+                }
+                None => {
+                    table.remove(key).map_err(|e| write_failed(e.to_string()))?;
+                }
+            }
+            output
+        };
+
+        write_txn
+            .commit()
+            .map_err(|e| write_failed(e.to_string()))?;
+
+        Ok(output)
+    }
+
     /// Get the worker log mode setting.
     pub fn worker_log_mode(&self) -> WorkerLogMode {
         match self.get_raw(WORKER_LOG_MODE_KEY) {
@@ -230,6 +358,273 @@ impl SettingsStore {
         let key = format!("{PROMPT_CAPTURE_PREFIX}{channel_id}");
         self.set_raw(&key, if enabled { "true" } else { "false" })
     }
+
+    /// Check whether model reasoning is surfaced in a specific channel.
+    /// Hidden unless explicitly enabled.
+    pub fn show_reasoning_enabled(&self, channel_id: &str) -> bool {
+        let key = format!("{SHOW_REASONING_PREFIX}{channel_id}");
+        matches!(self.get_raw(&key), Ok(v) if v == "true")
+    }
+
+    /// Show or hide model reasoning in a specific channel.
+    pub fn set_show_reasoning(&self, channel_id: &str, enabled: bool) -> Result<()> {
+        let key = format!("{SHOW_REASONING_PREFIX}{channel_id}");
+        self.set_raw(&key, if enabled { "true" } else { "false" })
+    }
+
+    /// Get the reply language override for a specific channel, if set.
+    pub fn output_language_for(&self, channel_id: &str) -> Option<String> {
+        let key = format!("{OUTPUT_LANGUAGE_PREFIX}{channel_id}");
+        self.get_raw(&key).ok().filter(|value| !value.is_empty())
+    }
+
+    /// Set or clear the reply language override for a specific channel.
+    /// Clearing falls back to the agent's configured `output_language`.
+    pub fn set_output_language_for(&self, channel_id: &str, language: Option<&str>) -> Result<()> {
+        let key = format!("{OUTPUT_LANGUAGE_PREFIX}{channel_id}");
+        match language {
+            Some(language) => self.set_raw(&key, language),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// Get the model pinned to a specific channel, if one is set.
+    pub fn channel_model_for(&self, channel_id: &str) -> Option<String> {
+        let key = format!("{CHANNEL_MODEL_PREFIX}{channel_id}");
+        self.get_raw(&key).ok().filter(|value| !value.is_empty())
+    }
+
+    /// Pin or unpin the model for a specific channel.
+    /// Unpinning returns the channel to the agent's routing.
+    pub fn set_channel_model_for(&self, channel_id: &str, model: Option<&str>) -> Result<()> {
+        let key = format!("{CHANNEL_MODEL_PREFIX}{channel_id}");
+        match model {
+            Some(model) => self.set_raw(&key, model),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// Get the active persona name for a specific channel, if one is set.
+    pub fn active_persona_for(&self, channel_id: &str) -> Option<String> {
+        let key = format!("{ACTIVE_PERSONA_PREFIX}{channel_id}");
+        self.get_raw(&key).ok().filter(|value| !value.is_empty())
+    }
+
+    /// Set or clear the active persona for a specific channel.
+    /// Clearing returns the channel to the agent's base prompt.
+    pub fn set_active_persona_for(&self, channel_id: &str, persona: Option<&str>) -> Result<()> {
+        let key = format!("{ACTIVE_PERSONA_PREFIX}{channel_id}");
+        match persona {
+            Some(persona) => self.set_raw(&key, persona),
+            None => self.remove_raw(&key),
+        }
+    }
+
+    /// Snippets pinned to a specific channel, oldest first.
+    pub fn pinned_context_for(&self, channel_id: &str) -> Vec<PinnedContext> {
+        let key = format!("{PINNED_CONTEXT_PREFIX}{channel_id}");
+        let Ok(raw) = self.get_raw(&key) else {
+            return Vec::new();
+        };
+        serde_json::from_str(&raw).unwrap_or_else(|error| {
+            tracing::warn!(%error, %channel_id, "ignoring unreadable pinned context");
+            Vec::new()
+        })
+    }
+
+    /// Pin a snippet to a channel and return it with its assigned ID.
+    pub fn pin_context(&self, channel_id: &str, text: &str) -> Result<PinnedContext> {
+        let key = format!("{PINNED_CONTEXT_PREFIX}{channel_id}");
+        let text = text.trim();
+        let invalid = |details: String| SettingsError::InvalidValue {
+            key: key.clone(),
+            details,
+        };
+        if text.is_empty() {
+            return Err(invalid("pinned text is empty".into()).into());
+        }
+        let length = text.chars().count();
+        if length > MAX_PIN_CHARS {
+            return Err(invalid(format!(
+                "pinned text is {length} characters, the limit is {MAX_PIN_CHARS}"
+            ))
+            .into());
+        }
+
+        let mut pins = self.pinned_context_for(channel_id);
+        if pins.len() >= MAX_PINS_PER_CHANNEL {
+            return Err(invalid(format!(
+                "channel already has {MAX_PINS_PER_CHANNEL} pins, unpin one first"
+            ))
+            .into());
+        }
+        let pin = PinnedContext {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            text: text.to_string(),
+            pinned_at: chrono::Utc::now(),
+        };
+        pins.push(pin.clone());
+        self.set_pinned_context(&key, &pins)?;
+        Ok(pin)
+    }
+
+    /// Remove a pinned snippet by ID. Returns false if no pin had that ID.
+    pub fn unpin_context(&self, channel_id: &str, pin_id: &str) -> Result<bool> {
+        let key = format!("{PINNED_CONTEXT_PREFIX}{channel_id}");
+        let mut pins = self.pinned_context_for(channel_id);
+        let before = pins.len();
+        pins.retain(|pin| pin.id != pin_id.trim());
+        if pins.len() == before {
+            return Ok(false);
+        }
+        self.set_pinned_context(&key, &pins)?;
+        Ok(true)
+    }
+
+    /// Slots set in a specific channel, ordered by key.
+    pub fn conversation_slots_for(&self, channel_id: &str) -> BTreeMap<String, ConversationSlot> {
+        let key = format!("{CONVERSATION_SLOTS_PREFIX}{channel_id}");
+        let Ok(raw) = self.get_raw(&key) else {
+            return BTreeMap::new();
+        };
+        parse_conversation_slots(channel_id, &raw)
+    }
+
+    /// Set a slot in a channel, replacing any previous value.
+    pub fn set_conversation_slot(
+        &self,
+        channel_id: &str,
+        slot_key: &str,
+        value: &str,
+    ) -> Result<ConversationSlot> {
+        let key = format!("{CONVERSATION_SLOTS_PREFIX}{channel_id}");
+        let slot_key = slot_key.trim();
+        let invalid = |details: String| SettingsError::InvalidValue {
+            key: key.clone(),
+            details,
+        };
+        if slot_key.is_empty() {
+            return Err(invalid("slot key is empty".into()).into());
+        }
+        if slot_key.chars().count() > MAX_SLOT_KEY_CHARS {
+            return Err(invalid(format!(
+                "slot key is longer than {MAX_SLOT_KEY_CHARS} characters"
+            ))
+            .into());
+        }
+        if !slot_key
+            .chars()
+            .all(|character| character.is_alphanumeric() || "_-.".contains(character))
+        {
+            return Err(invalid(format!(
+                "slot key '{slot_key}' may only contain letters, digits, '_', '-', and '.'"
+            ))
+            .into());
+        }
+        if value.trim().is_empty() {
+            return Err(invalid("slot value is empty".into()).into());
+        }
+        let length = value.chars().count();
+        if length > MAX_SLOT_VALUE_CHARS {
+            return Err(invalid(format!(
+                "slot value is {length} characters, the limit is {MAX_SLOT_VALUE_CHARS}"
+            ))
+            .into());
+        }
+
+        self.update_raw(&key, |raw| {
+            let mut slots = raw
+                .map(|raw| parse_conversation_slots(channel_id, raw))
+                .unwrap_or_default();
+            if !slots.contains_key(slot_key) && slots.len() >= MAX_SLOTS_PER_CHANNEL {
+                return Err(invalid(format!(
+                    "channel already has {MAX_SLOTS_PER_CHANNEL} slots, clear one first"
+                ))
+                .into());
+            }
+            let slot = ConversationSlot {
+                value: value.to_string(),
+                updated_at: chrono::Utc::now(),
+            };
+            slots.insert(slot_key.to_string(), slot.clone());
+            Ok((encode_conversation_slots(&key, &slots)?, slot))
+        })
+    }
+
+    /// Remove a slot from a channel. Returns false if the slot wasn't set.
+    pub fn clear_conversation_slot(&self, channel_id: &str, slot_key: &str) -> Result<bool> {
+        let key = format!("{CONVERSATION_SLOTS_PREFIX}{channel_id}");
+        self.update_raw(&key, |raw| {
+            let Some(raw) = raw else {
+                return Ok((None, false));
+            };
+            let mut slots = parse_conversation_slots(channel_id, raw);
+            if slots.remove(slot_key.trim()).is_none() {
+                return Ok((Some(raw.to_string()), false));
+            }
+            Ok((encode_conversation_slots(&key, &slots)?, true))
+        })
+    }
+
+    /// The manually set memory bulletin, if any.
+    pub fn memory_bulletin_override(&self) -> Option<BulletinOverride> {
+        let raw = self.get_raw(MEMORY_BULLETIN_OVERRIDE_KEY).ok()?;
+        serde_json::from_str(&raw)
+            .map_err(|error| {
+                tracing::warn!(%error, "ignoring unreadable memory bulletin override");
+            })
+            .ok()
+    }
+
+    /// Set or clear the manual memory bulletin.
+    pub fn set_memory_bulletin_override(
+        &self,
+        bulletin_override: Option<&BulletinOverride>,
+    ) -> Result<()> {
+        let Some(bulletin_override) = bulletin_override else {
+            return self.remove_raw(MEMORY_BULLETIN_OVERRIDE_KEY);
+        };
+        let raw = serde_json::to_string(bulletin_override).map_err(|error| {
+            SettingsError::WriteFailed {
+                key: MEMORY_BULLETIN_OVERRIDE_KEY.to_string(),
+                details: error.to_string(),
+            }
+        })?;
+        self.set_raw(MEMORY_BULLETIN_OVERRIDE_KEY, &raw)
+    }
+
+    fn set_pinned_context(&self, key: &str, pins: &[PinnedContext]) -> Result<()> {
+        if pins.is_empty() {
+            return self.remove_raw(key);
+        }
+        let raw = serde_json::to_string(pins).map_err(|error| SettingsError::WriteFailed {
+            key: key.to_string(),
+            details: error.to_string(),
+        })?;
+        self.set_raw(key, &raw)
+    }
+}
+
+fn parse_conversation_slots(channel_id: &str, raw: &str) -> BTreeMap<String, ConversationSlot> {
+    serde_json::from_str(raw).unwrap_or_else(|error| {
+        tracing::warn!(%error, %channel_id, "ignoring unreadable conversation slots");
+        BTreeMap::new()
+    })
+}
+
+/// The stored form of a channel's slots, or `None` when there are none left.
+fn encode_conversation_slots(
+    key: &str,
+    slots: &BTreeMap<String, ConversationSlot>,
+) -> Result<Option<String>> {
+    if slots.is_empty() {
+        return Ok(None);
+    }
+    let raw = serde_json::to_string(slots).map_err(|error| SettingsError::WriteFailed {
+        key: key.to_string(),
+        details: error.to_string(),
+    })?;
+    Ok(Some(raw))
 }
 
 impl std::fmt::Debug for SettingsStore {
@@ -237,3 +632,111 @@ impl std::fmt::Debug for SettingsStore {
         f.debug_struct("SettingsStore").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn store() -> (tempfile::TempDir, SettingsStore) {
+        let dir = tempdir().expect("failed to create temp dir");
+        let store = SettingsStore::new(&dir.path().join("settings.redb"))
+            .expect("failed to open settings store");
+        (dir, store)
+    }
+
+    #[test]
+    fn slots_round_trip_and_clear() {
+        let (_dir, store) = store();
+        store
+            .set_conversation_slot("channel", "order_id", "A-123")
+            .unwrap();
+        store
+            .set_conversation_slot("channel", "order_id", "A-456")
+            .unwrap();
+
+        let slots = store.conversation_slots_for("channel");
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots["order_id"].value, "A-456");
+        assert!(store.conversation_slots_for("other").is_empty());
+
+        assert!(
+            store
+                .clear_conversation_slot("channel", "order_id")
+                .unwrap()
+        );
+        assert!(
+            !store
+                .clear_conversation_slot("channel", "order_id")
+                .unwrap()
+        );
+        assert!(store.conversation_slots_for("channel").is_empty());
+    }
+
+    #[test]
+    fn bulletin_override_persists_across_reopen_and_clears() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("settings.redb");
+        let bulletin_override = BulletinOverride {
+            bulletin: "Focus on the launch.".into(),
+            permanent: true,
+            set_at: chrono::Utc::now(),
+        };
+        {
+            let store = SettingsStore::new(&path).unwrap();
+            assert!(store.memory_bulletin_override().is_none());
+            store
+                .set_memory_bulletin_override(Some(&bulletin_override))
+                .unwrap();
+        }
+
+        let store = SettingsStore::new(&path).unwrap();
+        assert_eq!(store.memory_bulletin_override(), Some(bulletin_override));
+        store.set_memory_bulletin_override(None).unwrap();
+        assert!(store.memory_bulletin_override().is_none());
+    }
+
+    #[test]
+    fn slot_limit_is_enforced() {
+        let (_dir, store) = store();
+        for index in 0..MAX_SLOTS_PER_CHANNEL {
+            store
+                .set_conversation_slot("channel", &format!("slot_{index}"), "value")
+                .unwrap();
+        }
+        assert!(
+            store
+                .set_conversation_slot("channel", "one_more", "value")
+                .is_err()
+        );
+        // Overwriting an existing slot doesn't count against the limit.
+        store
+            .set_conversation_slot("channel", "slot_0", "updated")
+            .unwrap();
+        assert_eq!(
+            store.conversation_slots_for("channel").len(),
+            MAX_SLOTS_PER_CHANNEL
+        );
+    }
+
+    #[test]
+    fn concurrent_slot_writes_are_not_lost() {
+        let (_dir, store) = store();
+        let store = Arc::new(store);
+        let writers: Vec<_> = (0..16)
+            .map(|index| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    store
+                        .set_conversation_slot("channel", &format!("slot_{index}"), "value")
+                        .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(store.conversation_slots_for("channel").len(), 16);
+    }
+}
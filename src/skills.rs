@@ -37,6 +37,9 @@ pub struct Skill {
     pub source: SkillSource,
     /// GitHub `owner/repo` that this skill was installed from, if any.
     pub source_repo: Option<String>,
+    /// Frontmatter `priority`. Higher-priority skills are the last to be
+    /// dropped when the system prompt exceeds its token budget.
+    pub priority: i32,
 }
 
 /// Where a skill was loaded from, used for precedence tracking.
@@ -121,11 +124,23 @@ impl SkillSet {
         &self,
         prompt_engine: &crate::prompts::PromptEngine,
     ) -> crate::error::Result<String> {
-        if self.skills.is_empty() {
+        self.render_channel_prompt_excluding(prompt_engine, &[])
+    }
+
+    /// Render the channel skills summary, leaving out the named skills.
+    pub fn render_channel_prompt_excluding(
+        &self,
+        prompt_engine: &crate::prompts::PromptEngine,
+        excluded: &[String],
+    ) -> crate::error::Result<String> {
+        let mut sorted_skills: Vec<&Skill> = self
+            .skills
+            .values()
+            .filter(|skill| !excluded.contains(&skill.name))
+            .collect();
+        if sorted_skills.is_empty() {
             return Ok(String::new());
         }
-
-        let mut sorted_skills: Vec<&Skill> = self.skills.values().collect();
         sorted_skills.sort_by(|a, b| a.name.cmp(&b.name));
 
         let skill_infos: Vec<crate::prompts::SkillInfo> = sorted_skills
@@ -141,6 +156,26 @@ impl SkillSet {
         prompt_engine.render_skills_channel(skill_infos)
     }
 
+    /// Skill names in the order they should be dropped to shrink the system
+    /// prompt: lowest priority first, instance skills before workspace
+    /// skills, then reverse alphabetical so the result is deterministic.
+    pub fn trim_order(&self) -> Vec<String> {
+        let mut skills: Vec<&Skill> = self.skills.values().collect();
+        skills.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| {
+                    let rank = |skill: &Skill| match skill.source {
+                        SkillSource::Instance => 0,
+                        SkillSource::Workspace => 1,
+                    };
+                    rank(a).cmp(&rank(b))
+                })
+                .then_with(|| b.name.cmp(&a.name))
+        });
+        skills.into_iter().map(|skill| skill.name.clone()).collect()
+    }
+
     /// Render the skills listing for injection into a worker system prompt.
     ///
     /// Workers see all available skills with any channel-suggested skills flagged.
@@ -314,6 +349,10 @@ async fn load_skill(
 
     let description = frontmatter.get("description").cloned().unwrap_or_default();
     let source_repo = frontmatter.get("source_repo").cloned();
+    let priority = frontmatter
+        .get("priority")
+        .and_then(|value| value.parse::<i32>().ok())
+        .unwrap_or(0);
 
     // Resolve {baseDir} template variable in the body
     let base_dir_str = base_dir.to_string_lossy();
@@ -327,6 +366,7 @@ async fn load_skill(
         content,
         source,
         source_repo,
+        priority,
     })
 }
 
@@ -488,6 +528,7 @@ mod tests {
                 content: "# Weather\n\nUse curl.".into(),
                 source: SkillSource::Instance,
                 source_repo: None,
+                priority: 0,
             },
         );
 
@@ -511,6 +552,7 @@ mod tests {
                 content: "# Weather\n\nUse curl.".into(),
                 source: SkillSource::Instance,
                 source_repo: None,
+                priority: 0,
             },
         );
 
@@ -542,9 +584,33 @@ mod tests {
             content: format!("# {name}"),
             source,
             source_repo: None,
+            priority: 0,
         }
     }
 
+    #[test]
+    fn trim_order_drops_low_priority_and_instance_skills_first() {
+        let mut set = SkillSet::default();
+        let mut pinned = make_skill("pinned", SkillSource::Instance);
+        pinned.priority = 10;
+        set.skills.insert("pinned".into(), pinned);
+        set.skills
+            .insert("alpha".into(), make_skill("alpha", SkillSource::Workspace));
+        set.skills
+            .insert("beta".into(), make_skill("beta", SkillSource::Instance));
+        set.skills
+            .insert("gamma".into(), make_skill("gamma", SkillSource::Instance));
+
+        assert_eq!(set.trim_order(), vec!["gamma", "beta", "alpha", "pinned"]);
+
+        let engine = crate::prompts::PromptEngine::new("en").unwrap();
+        let prompt = set
+            .render_channel_prompt_excluding(&engine, &["gamma".into(), "beta".into()])
+            .unwrap();
+        assert!(prompt.contains("<name>alpha</name>"));
+        assert!(!prompt.contains("<name>gamma</name>"));
+    }
+
     #[tokio::test]
     async fn remove_instance_skill_is_rejected() {
         let mut set = SkillSet::default();
@@ -1,5 +1,6 @@
 //! Task tracking data model and storage.
 
+pub mod extraction;
 pub mod store;
 
 pub use store::{
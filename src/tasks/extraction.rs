@@ -0,0 +1,242 @@
+//! Automatic task creation from action items in conversation messages.
+//!
+//! Opt-in via `[defaults.task_extraction]`. Each user message, or each
+//! coalesced batch of messages, is checked once by the cortex model;
+//! confident action items become tasks awaiting approval, unless an open
+//! task with a similar title already exists. Extractions can run
+//! concurrently, so the store also refuses a second open task with the same
+//! normalized title.
+
+use crate::config::TaskExtractionConfig;
+use crate::error::Result;
+use crate::hooks::CortexHook;
+use crate::llm::SpacebotModel;
+use crate::tasks::{CreateTaskInput, Task, TaskPriority, TaskStatus};
+use crate::{AgentDeps, ProcessEvent, ProcessType};
+
+use rig::agent::AgentBuilder;
+use rig::completion::TypedPrompt;
+
+use std::collections::HashSet;
+
+/// Title similarity (token Jaccard) at or above which an item counts as a duplicate.
+const DUPLICATE_SIMILARITY_THRESHOLD: f32 = 0.6;
+
+/// How many open tasks to compare new items against.
+const OPEN_TASK_SCAN_LIMIT: i64 = 200;
+
+/// LLM response shape for action item extraction.
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ActionItemExtraction {
+    #[serde(default)]
+    action_items: Vec<ExtractedActionItem>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct ExtractedActionItem {
+    title: String,
+    description: Option<String>,
+    priority: Option<String>,
+    /// 0.0-1.0, how sure the model is that this is a real commitment.
+    confidence: f32,
+}
+
+/// Spawn action item extraction for a message in the background, if enabled.
+pub fn spawn_extraction(deps: &AgentDeps, conversation_id: &str, message_text: &str) {
+    let config = **deps.runtime_config.task_extraction.load();
+    if !config.enabled || message_text.trim().is_empty() {
+        return;
+    }
+
+    let deps = deps.clone();
+    let conversation_id = conversation_id.to_string();
+    let message_text = message_text.to_string();
+    tokio::spawn(async move {
+        match extract_and_create_tasks(&deps, config, &conversation_id, &message_text).await {
+            Ok(tasks) if !tasks.is_empty() => {
+                tracing::info!(
+                    agent_id = %deps.agent_id,
+                    conversation_id = %conversation_id,
+                    count = tasks.len(),
+                    "created tasks from conversation action items"
+                );
+            }
+            Ok(_) => {}
+            Err(error) => {
+                tracing::warn!(
+                    agent_id = %deps.agent_id,
+                    conversation_id = %conversation_id,
+                    %error,
+                    "task extraction failed"
+                );
+            }
+        }
+    });
+}
+
+/// Ask the cortex model for action items in `message_text` and create a
+/// pending-approval task for each confident, non-duplicate item.
+pub async fn extract_and_create_tasks(
+    deps: &AgentDeps,
+    config: TaskExtractionConfig,
+    conversation_id: &str,
+    message_text: &str,
+) -> Result<Vec<Task>> {
+    let prompt_engine = deps.runtime_config.prompts.load();
+    let preamble = prompt_engine.render_static("task_extraction")?;
+
+    let routing = deps.runtime_config.routing.load();
+    let model_name = routing.resolve(ProcessType::Cortex, None).to_string();
+    let model = SpacebotModel::make(&deps.llm_manager, &model_name)
+        .with_context(&*deps.agent_id, "cortex")
+        .with_routing((**routing).clone());
+
+    let agent = AgentBuilder::new(model)
+        .preamble(&preamble)
+        .hook(CortexHook::new())
+        .build();
+
+    let extraction = agent
+        .prompt_typed::<ActionItemExtraction>(message_text)
+        .await
+        .map_err(|error| anyhow::anyhow!("action item extraction failed: {error}"))?;
+
+    let candidates: Vec<ExtractedActionItem> = extraction
+        .action_items
+        .into_iter()
+        .filter(|item| item.confidence >= config.min_confidence && !item.title.trim().is_empty())
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut known_titles: Vec<String> = deps
+        .task_store
+        .list(&deps.agent_id, None, None, OPEN_TASK_SCAN_LIMIT)
+        .await?
+        .into_iter()
+        .filter(|task| task.status != TaskStatus::Done)
+        .map(|task| task.title)
+        .collect();
+
+    let mut created = Vec::new();
+    for item in candidates {
+        let title = item.title.trim().to_string();
+        if known_titles
+            .iter()
+            .any(|existing| is_similar_title(existing, &title))
+        {
+            tracing::debug!(title = %title, "skipping extracted action item similar to an open task");
+            continue;
+        }
+
+        let priority = item
+            .priority
+            .as_deref()
+            .and_then(TaskPriority::parse)
+            .unwrap_or(TaskPriority::Medium);
+        let Some(task) = deps
+            .task_store
+            .create_unless_duplicate(
+                CreateTaskInput {
+                    agent_id: deps.agent_id.to_string(),
+                    title: title.clone(),
+                    description: item.description.filter(|d| !d.trim().is_empty()),
+                    status: TaskStatus::PendingApproval,
+                    priority,
+                    subtasks: Vec::new(),
+                    metadata: serde_json::json!({
+                        "source": "auto_extraction",
+                        "conversation_id": conversation_id,
+                        "confidence": item.confidence,
+                    }),
+                    source_memory_id: None,
+                    created_by: "channel".to_string(),
+                },
+                &dedupe_key(&title),
+            )
+            .await?
+        else {
+            tracing::debug!(title = %title, "skipping extracted action item created concurrently");
+            continue;
+        };
+
+        let _ = deps.event_tx.send(ProcessEvent::TaskUpdated {
+            agent_id: deps.agent_id.clone(),
+            task_number: task.task_number,
+            status: task.status.to_string(),
+            action: "created".to_string(),
+        });
+        deps.working_memory
+            .emit(
+                crate::memory::WorkingMemoryEventType::TaskUpdate,
+                format!(
+                    "Task created from conversation #{}: {}",
+                    task.task_number, task.title
+                ),
+            )
+            .importance(0.5)
+            .record();
+
+        known_titles.push(title);
+        created.push(task);
+    }
+
+    Ok(created)
+}
+
+/// Whether two task titles describe the same work, by word-set overlap.
+fn is_similar_title(a: &str, b: &str) -> bool {
+    let a = title_tokens(a);
+    let b = title_tokens(b);
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let intersection = a.intersection(&b).count() as f32;
+    let union = a.union(&b).count() as f32;
+    intersection / union >= DUPLICATE_SIMILARITY_THRESHOLD
+}
+
+/// Order-insensitive normalized title, unique among an agent's open tasks.
+fn dedupe_key(title: &str) -> String {
+    let mut tokens: Vec<String> = title_tokens(title).into_iter().collect();
+    tokens.sort();
+    tokens.join(" ")
+}
+
+fn title_tokens(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similar_titles_are_duplicates() {
+        assert!(is_similar_title(
+            "Send the Q3 report to Dana",
+            "send Q3 report to dana"
+        ));
+        assert!(is_similar_title("Fix login bug", "Fix the login bug!"));
+        assert!(!is_similar_title(
+            "Fix login bug",
+            "Write release notes for login"
+        ));
+        assert!(!is_similar_title("", "anything"));
+    }
+
+    #[test]
+    fn dedupe_key_ignores_case_punctuation_and_word_order() {
+        assert_eq!(dedupe_key("Fix the login bug!"), "bug fix login the");
+        assert_eq!(
+            dedupe_key("login bug: fix the"),
+            dedupe_key("Fix the login bug!")
+        );
+        assert_ne!(dedupe_key("Fix login bug"), dedupe_key("Fix the login bug"));
+    }
+}
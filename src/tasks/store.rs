@@ -166,6 +166,28 @@ impl TaskStore {
     const MAX_CREATE_RETRIES: usize = 3;
 
     pub async fn create(&self, input: CreateTaskInput) -> Result<Task> {
+        self.insert(input, None)
+            .await?
+            .context("task create skipped without a dedupe key")
+            .map_err(Into::into)
+    }
+
+    /// Create a task unless an open task for the agent already has
+    /// `dedupe_key`. Returns `None` when one does. The check is a unique
+    /// index, so concurrent creates with the same key can't both succeed.
+    pub async fn create_unless_duplicate(
+        &self,
+        input: CreateTaskInput,
+        dedupe_key: &str,
+    ) -> Result<Option<Task>> {
+        self.insert(input, Some(dedupe_key)).await
+    }
+
+    async fn insert(
+        &self,
+        input: CreateTaskInput,
+        dedupe_key: Option<&str>,
+    ) -> Result<Option<Task>> {
         let subtasks_json =
             serde_json::to_string(&input.subtasks).context("failed to serialize subtasks")?;
         let metadata_json = input.metadata.to_string();
@@ -191,9 +213,9 @@ impl TaskStore {
                 r#"
                 INSERT INTO tasks (
                     id, agent_id, task_number, title, description, status, priority,
-                    subtasks, metadata, source_memory_id, created_by
+                    subtasks, metadata, source_memory_id, created_by, dedupe_key
                 )
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(&task_id)
@@ -207,6 +229,7 @@ impl TaskStore {
             .bind(&metadata_json)
             .bind(&input.source_memory_id)
             .bind(&input.created_by)
+            .bind(dedupe_key)
             .execute(&mut *tx)
             .await;
 
@@ -220,8 +243,16 @@ impl TaskStore {
                         .get_by_number(&input.agent_id, task_number)
                         .await?
                         .context("task inserted but not found")
+                        .map(Some)
                         .map_err(Into::into);
                 }
+                Err(sqlx::Error::Database(ref db_error))
+                    if db_error.code().as_deref() == Some("2067")
+                        && db_error.message().contains("dedupe_key") =>
+                {
+                    // An open task with the same dedupe key already exists.
+                    return Ok(None);
+                }
                 Err(sqlx::Error::Database(ref db_error))
                     if db_error.code().as_deref() == Some("2067") =>
                 {
@@ -618,6 +649,7 @@ mod tests {
                 created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 completed_at TIMESTAMP,
+                dedupe_key TEXT,
                 UNIQUE(agent_id, task_number)
             )
             "#,
@@ -625,10 +657,74 @@ mod tests {
         .execute(&pool)
         .await
         .expect("tasks schema should be created");
+        sqlx::query(
+            "CREATE UNIQUE INDEX idx_tasks_open_dedupe_key ON tasks(agent_id, dedupe_key) \
+             WHERE dedupe_key IS NOT NULL AND status != 'done'",
+        )
+        .execute(&pool)
+        .await
+        .expect("tasks dedupe index should be created");
 
         TaskStore::new(pool)
     }
 
+    #[tokio::test]
+    async fn concurrent_creates_with_the_same_dedupe_key_make_one_task() {
+        let store = setup_store().await;
+        let input = |agent_id: &str| CreateTaskInput {
+            agent_id: agent_id.to_string(),
+            title: "Send the report".to_string(),
+            description: None,
+            status: TaskStatus::PendingApproval,
+            priority: TaskPriority::Medium,
+            subtasks: Vec::new(),
+            metadata: serde_json::json!({}),
+            source_memory_id: None,
+            created_by: "channel".to_string(),
+        };
+
+        let (first, second) = tokio::join!(
+            store.create_unless_duplicate(input("agent-test"), "report send the"),
+            store.create_unless_duplicate(input("agent-test"), "report send the"),
+        );
+        let created: Vec<Task> = [first.unwrap(), second.unwrap()]
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(created.len(), 1);
+
+        // Keys are per agent.
+        assert!(
+            store
+                .create_unless_duplicate(input("agent-other"), "report send the")
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // A finished task no longer blocks the key.
+        for status in [TaskStatus::Ready, TaskStatus::InProgress, TaskStatus::Done] {
+            store
+                .update(
+                    "agent-test",
+                    created[0].task_number,
+                    UpdateTaskInput {
+                        status: Some(status),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        assert!(
+            store
+                .create_unless_duplicate(input("agent-test"), "report send the")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
     #[tokio::test]
     async fn rejects_invalid_status_transition() {
         let store = setup_store().await;
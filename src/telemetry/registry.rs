@@ -60,11 +60,19 @@ pub struct Metrics {
     /// Labels: agent_id, model, tier, worker_type.
     pub llm_estimated_cost_dollars: CounterVec,
 
+    /// Tool calls with malformed JSON arguments, by how they were handled.
+    /// Labels: agent_id, model, outcome (lenient, model, failed).
+    pub llm_tool_call_repairs_total: IntCounterVec,
+
     // -- Worker visibility --
     /// Currently active branches per agent.
     /// Label: agent_id.
     pub active_branches: IntGaugeVec,
 
+    /// Processes holding a `max_concurrent_processes` slot per agent.
+    /// Label: agent_id.
+    pub active_processes: IntGaugeVec,
+
     /// Worker lifetime duration in seconds.
     /// Labels: agent_id, worker_type.
     pub worker_duration_seconds: HistogramVec,
@@ -140,6 +148,23 @@ pub struct Metrics {
     /// Labels: agent_id, channel_type, error_type.
     pub channel_errors_total: IntCounterVec,
 
+    /// Conversation channels currently held in memory.
+    /// Label: agent_id.
+    pub active_channels: IntGaugeVec,
+
+    /// Channels torn down after sitting idle past `idle_timeout_secs`, or
+    /// to make room under `max_concurrent_channels`.
+    /// Label: agent_id.
+    pub channels_evicted_total: IntCounterVec,
+
+    /// New conversations waiting for a channel slot.
+    /// Label: agent_id.
+    pub queued_conversations: IntGaugeVec,
+
+    /// New conversations turned away with the busy message.
+    /// Label: agent_id.
+    pub conversations_rejected_total: IntCounterVec,
+
     // -- Memory operations --
     /// Memory operation duration.
     /// Labels: agent_id, operation.
@@ -152,6 +177,23 @@ pub struct Metrics {
     /// Embedding generation duration.
     pub memory_embedding_duration_seconds: Histogram,
 
+    /// Texts waiting for the shared embedding model.
+    /// Label: agent_id (`shared` for requests not made for an agent).
+    pub memory_embedding_queue_depth: IntGaugeVec,
+
+    /// End-to-end memory recall duration.
+    /// Labels: agent_id, mode.
+    pub memory_recall_duration_seconds: HistogramVec,
+
+    /// Time spent in each stage of a hybrid recall, so query embedding can
+    /// be told apart from the searches.
+    /// Labels: agent_id, stage.
+    pub memory_recall_stage_duration_seconds: HistogramVec,
+
+    /// Recalls that returned no results.
+    /// Labels: agent_id, mode.
+    pub memory_recall_empty_total: IntCounterVec,
+
     // -- API --
     /// Total HTTP requests.
     /// Labels: method, handler, status.
@@ -184,6 +226,9 @@ pub struct Metrics {
     /// Ingestion files processed.
     /// Labels: agent_id, result.
     pub ingestion_files_processed_total: IntCounterVec,
+    /// Files waiting in the ingest directory, its retry queue, or `failed/`.
+    /// Labels: agent_id, state.
+    pub ingestion_files_queued: IntGaugeVec,
 }
 
 impl Metrics {
@@ -277,12 +322,30 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let llm_tool_call_repairs_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_llm_tool_call_repairs_total",
+                "Tool calls with malformed JSON arguments",
+            ),
+            &["agent_id", "model", "outcome"],
+        )
+        .expect("hardcoded metric descriptor");
+
         let active_branches = IntGaugeVec::new(
             Opts::new("spacebot_active_branches", "Currently active branches"),
             &["agent_id"],
         )
         .expect("hardcoded metric descriptor");
 
+        let active_processes = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_active_processes",
+                "Processes holding a concurrency slot",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
         let worker_duration_seconds = HistogramVec::new(
             HistogramOpts::new(
                 "spacebot_worker_duration_seconds",
@@ -402,7 +465,7 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
-        // Channel/Messaging (4)
+        // Channel/Messaging (8)
         let messages_received_total = IntCounterVec::new(
             Opts::new(
                 "spacebot_messages_received_total",
@@ -434,7 +497,43 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
-        // Memory (3)
+        let active_channels = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_active_channels",
+                "Conversation channels currently in memory",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let channels_evicted_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_channels_evicted_total",
+                "Channels torn down after the idle timeout or to make room for new conversations",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let queued_conversations = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_queued_conversations",
+                "New conversations waiting for a channel slot",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let conversations_rejected_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_conversations_rejected_total",
+                "New conversations rejected at the channel limit",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        // Memory (7)
         let memory_operation_duration_seconds = HistogramVec::new(
             HistogramOpts::new(
                 "spacebot_memory_operation_duration_seconds",
@@ -466,6 +565,48 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let memory_embedding_queue_depth = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_memory_embedding_queue_depth",
+                "Texts waiting for the shared embedding model",
+            ),
+            &["agent_id"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let memory_recall_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "spacebot_memory_recall_duration_seconds",
+                "Memory recall duration",
+            )
+            .buckets(vec![
+                0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+            ]),
+            &["agent_id", "mode"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let memory_recall_stage_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "spacebot_memory_recall_stage_duration_seconds",
+                "Memory recall duration by stage",
+            )
+            .buckets(vec![
+                0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+            ]),
+            &["agent_id", "stage"],
+        )
+        .expect("hardcoded metric descriptor");
+
+        let memory_recall_empty_total = IntCounterVec::new(
+            Opts::new(
+                "spacebot_memory_recall_empty_total",
+                "Memory recalls that returned no results",
+            ),
+            &["agent_id", "mode"],
+        )
+        .expect("hardcoded metric descriptor");
+
         // API (2)
         let http_requests_total = IntCounterVec::new(
             Opts::new("spacebot_http_requests_total", "Total HTTP requests"),
@@ -525,6 +666,15 @@ impl Metrics {
         )
         .expect("hardcoded metric descriptor");
 
+        let ingestion_files_queued = IntGaugeVec::new(
+            Opts::new(
+                "spacebot_ingestion_files_queued",
+                "Ingestion files pending, awaiting retry, or failed",
+            ),
+            &["agent_id", "state"],
+        )
+        .expect("hardcoded metric descriptor");
+
         // === Register all metrics ===
 
         // Existing (upgraded)
@@ -558,9 +708,15 @@ impl Metrics {
         registry
             .register(Box::new(llm_estimated_cost_dollars.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(llm_tool_call_repairs_total.clone()))
+            .expect("hardcoded metric");
         registry
             .register(Box::new(active_branches.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(active_processes.clone()))
+            .expect("hardcoded metric");
         registry
             .register(Box::new(worker_duration_seconds.clone()))
             .expect("hardcoded metric");
@@ -616,6 +772,18 @@ impl Metrics {
         registry
             .register(Box::new(channel_errors_total.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(active_channels.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(channels_evicted_total.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(queued_conversations.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(conversations_rejected_total.clone()))
+            .expect("hardcoded metric");
 
         // New: Memory operations
         registry
@@ -627,6 +795,18 @@ impl Metrics {
         registry
             .register(Box::new(memory_embedding_duration_seconds.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(memory_embedding_queue_depth.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(memory_recall_duration_seconds.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(memory_recall_stage_duration_seconds.clone()))
+            .expect("hardcoded metric");
+        registry
+            .register(Box::new(memory_recall_empty_total.clone()))
+            .expect("hardcoded metric");
 
         // New: API
         registry
@@ -658,6 +838,9 @@ impl Metrics {
         registry
             .register(Box::new(ingestion_files_processed_total.clone()))
             .expect("hardcoded metric");
+        registry
+            .register(Box::new(ingestion_files_queued.clone()))
+            .expect("hardcoded metric");
 
         Self {
             registry,
@@ -671,7 +854,9 @@ impl Metrics {
             memory_entry_count,
             llm_tokens_total,
             llm_estimated_cost_dollars,
+            llm_tool_call_repairs_total,
             active_branches,
+            active_processes,
             worker_duration_seconds,
             process_errors_total,
             memory_updates_total,
@@ -690,9 +875,17 @@ impl Metrics {
             messages_sent_total,
             message_handling_duration_seconds,
             channel_errors_total,
+            active_channels,
+            channels_evicted_total,
+            queued_conversations,
+            conversations_rejected_total,
             memory_operation_duration_seconds,
             memory_search_results,
             memory_embedding_duration_seconds,
+            memory_embedding_queue_depth,
+            memory_recall_duration_seconds,
+            memory_recall_stage_duration_seconds,
+            memory_recall_empty_total,
             http_requests_total,
             http_request_duration_seconds,
             branches_spawned_total,
@@ -700,6 +893,7 @@ impl Metrics {
             worker_cost_dollars,
             cron_executions_total,
             ingestion_files_processed_total,
+            ingestion_files_queued,
         }
     }
 
@@ -9,10 +9,13 @@
 //! - `reply`, `branch`, `spawn_worker`, `route`, `cancel`, `skip`, `react` — added
 //!   dynamically per conversation turn via `add_channel_tools()` /
 //!   `remove_channel_tools()` because they hold per-channel state.
+//! - `publish_home_view` — added the same way, for Slack conversations and
+//!   cron jobs delivering to a Slack DM.
 //! - No memory tools — the channel delegates memory work to branches.
 //!
 //! **Branch ToolServer** (one per branch, isolated):
 //! - `memory_save` + `memory_recall` + `memory_delete` + `channel_recall`
+//! - `forget_memory` for channel-originated branches only
 //! - `spacebot_docs` for embedded self-documentation lookup
 //! - `task_create` + `task_list` + `task_update`
 //! - `spawn_worker` is included for channel-originated branches only
@@ -37,25 +40,35 @@ pub mod config_inspect;
 pub mod cron;
 pub mod email_search;
 pub mod file;
+pub mod forget_memory;
 pub mod install_skill;
 pub mod mcp;
 pub mod memory_delete;
 pub mod memory_persistence_complete;
 pub mod memory_recall;
 pub mod memory_save;
+pub mod notes;
+pub mod persona;
+pub mod pin_context;
 pub mod project_manage;
+pub mod publish_home_view;
 pub mod react;
 pub mod read_skill;
 pub mod reply;
+pub mod request_human;
+pub mod reset_conversation;
+pub mod result_limit;
 pub mod route;
 pub mod secret_set;
 pub mod send_agent_message;
 pub mod send_file;
 pub mod send_message_to_another_channel;
+pub mod send_sticker;
 pub mod set_status;
 pub mod shell;
 pub mod skills_search;
 pub mod skip;
+pub mod slots;
 pub mod spacebot_docs;
 pub mod spawn_worker;
 pub mod task_create;
@@ -93,6 +106,9 @@ pub use file::{
     FileOutput, FileReadArgs, FileReadTool, FileType, FileWriteArgs, FileWriteTool,
     register_file_tools,
 };
+pub use forget_memory::{
+    ForgetMemoryArgs, ForgetMemoryError, ForgetMemoryMatch, ForgetMemoryOutput, ForgetMemoryTool,
+};
 pub use install_skill::{
     InstallSkillArgs, InstallSkillError, InstallSkillOutput, InstallSkillTool,
 };
@@ -111,12 +127,34 @@ pub use memory_recall::{
 pub use memory_save::{
     AssociationInput, MemorySaveArgs, MemorySaveError, MemorySaveOutput, MemorySaveTool,
 };
+pub use notes::{NotesArgs, NotesError, NotesOutput, NotesTool};
+pub use persona::{
+    ListPersonasArgs, ListPersonasOutput, ListPersonasTool, PersonaError, PersonaSummary,
+    SwitchPersonaArgs, SwitchPersonaOutput, SwitchPersonaTool,
+};
+pub use pin_context::{
+    ListPinsArgs, ListPinsOutput, ListPinsTool, PinContextArgs, PinContextError, PinContextOutput,
+    PinContextTool, UnpinContextArgs, UnpinContextOutput, UnpinContextTool,
+};
 pub use project_manage::{
     ProjectManageArgs, ProjectManageError, ProjectManageOutput, ProjectManageTool,
 };
+pub use publish_home_view::{
+    PublishHomeViewArgs, PublishHomeViewError, PublishHomeViewOutput, PublishHomeViewTool,
+};
 pub use react::{ReactArgs, ReactError, ReactOutput, ReactTool};
-pub use read_skill::{ReadSkillArgs, ReadSkillError, ReadSkillOutput, ReadSkillTool};
+pub use read_skill::{
+    ListSkillsArgs, ListSkillsOutput, ListSkillsTool, ReadSkillArgs, ReadSkillError,
+    ReadSkillOutput, ReadSkillTool, SkillSummary,
+};
 pub use reply::{RepliedFlag, ReplyArgs, ReplyError, ReplyOutput, ReplyTool, new_replied_flag};
+pub use request_human::{
+    RequestHumanArgs, RequestHumanError, RequestHumanOutput, RequestHumanTool,
+};
+pub use reset_conversation::{
+    ResetConversationArgs, ResetConversationError, ResetConversationOutput, ResetConversationTool,
+};
+pub use result_limit::{LimitedTool, LimitedToolOutput, ResultLimiter, TruncatedToolResult};
 pub use route::{RouteArgs, RouteError, RouteOutput, RouteTool};
 pub use secret_set::{SecretSetArgs, SecretSetError, SecretSetOutput, SecretSetTool};
 pub use send_agent_message::{
@@ -126,12 +164,16 @@ pub use send_file::{SendFileArgs, SendFileError, SendFileOutput, SendFileTool};
 pub use send_message_to_another_channel::{
     SendMessageArgs, SendMessageError, SendMessageOutput, SendMessageTool,
 };
+pub use send_sticker::{SendStickerArgs, SendStickerError, SendStickerOutput, SendStickerTool};
 pub use set_status::{SetStatusArgs, SetStatusError, SetStatusOutput, SetStatusTool, StatusKind};
 pub use shell::{EnvVar, ShellArgs, ShellError, ShellOutput, ShellResult, ShellTool};
 pub use skills_search::{
     SkillsSearchArgs, SkillsSearchError, SkillsSearchOutput, SkillsSearchTool,
 };
 pub use skip::{SkipArgs, SkipError, SkipFlag, SkipOutput, SkipTool, new_skip_flag};
+pub use slots::{
+    GetSlotArgs, GetSlotOutput, GetSlotTool, SetSlotArgs, SetSlotOutput, SetSlotTool, SlotError,
+};
 pub use spacebot_docs::{
     SpacebotDocContent, SpacebotDocsArgs, SpacebotDocsError, SpacebotDocsOutput, SpacebotDocsTool,
 };
@@ -171,14 +213,14 @@ pub use factory_update_identity::{
 };
 
 use crate::agent::channel::ChannelState;
-use crate::config::{BrowserConfig, RuntimeConfig};
+use crate::config::{BrowserConfig, RuntimeConfig, ToolResultLimits};
 use crate::memory::MemorySearch;
 use crate::sandbox::Sandbox;
 use crate::tasks::TaskStore;
 use crate::{AgentId, ChannelId, ProcessEvent, RoutedSender, WorkerId};
 use rig::tool::Tool as _;
 use rig::tool::server::{ToolServer, ToolServerHandle};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
@@ -249,7 +291,7 @@ pub const MAX_DIR_ENTRIES: usize = 500;
 /// Cuts at the last valid char boundary before `max_bytes` so we never split
 /// a multi-byte character. The truncation notice tells the LLM the original
 /// size and how to get the rest (pipe through head/tail or read with offset).
-fn truncate_at_char_boundary(value: &str, max_bytes: usize) -> usize {
+pub(crate) fn truncate_at_char_boundary(value: &str, max_bytes: usize) -> usize {
     let mut end = max_bytes.min(value.len());
     while end > 0 && !value.is_char_boundary(end) {
         end -= 1;
@@ -257,6 +299,14 @@ fn truncate_at_char_boundary(value: &str, max_bytes: usize) -> usize {
     end
 }
 
+/// The first `chars` characters of `text`.
+pub(crate) fn char_prefix(text: &str, chars: usize) -> &str {
+    match text.char_indices().nth(chars) {
+        Some((byte_index, _)) => &text[..byte_index],
+        None => text,
+    }
+}
+
 pub fn truncate_output(value: &str, max_bytes: usize) -> String {
     if value.len() <= max_bytes {
         return value.to_string();
@@ -273,6 +323,26 @@ pub fn truncate_output(value: &str, max_bytes: usize) -> String {
     )
 }
 
+/// Truncate a tool result to `max_bytes`, appending a `[truncated N bytes]`
+/// marker. When the full result was saved to disk, the marker names the file.
+pub fn truncate_tool_result(value: &str, max_bytes: usize, saved_to: Option<&Path>) -> String {
+    if value.len() <= max_bytes {
+        return value.to_string();
+    }
+
+    let end = truncate_at_char_boundary(value, max_bytes);
+    let truncated_bytes = value.len() - end;
+    match saved_to {
+        Some(path) => format!(
+            "{}\n\n[truncated {truncated_bytes} bytes; full result saved to {}, \
+             read it with the file tool]",
+            &value[..end],
+            path.display()
+        ),
+        None => format!("{}\n\n[truncated {truncated_bytes} bytes]", &value[..end]),
+    }
+}
+
 /// Truncate to a byte limit and append `...`, preserving UTF-8 boundaries.
 ///
 /// The returned string will never exceed `max_bytes`. If there's not enough
@@ -341,6 +411,56 @@ pub fn should_block_user_visible_text(value: &str) -> bool {
     lower.starts_with("<system-reminder>") || lower.starts_with("<path>")
 }
 
+/// Whether `tool_name` is listed in an agent's `disabled_tools`.
+pub fn is_tool_disabled(disabled_tools: &[String], tool_name: &str) -> bool {
+    disabled_tools.iter().any(|name| name == tool_name)
+}
+
+/// ToolServer builder that leaves out the tools an agent has disabled.
+///
+/// The factories below build through this so `disabled_tools` applies to
+/// every native tool, rather than each feature growing its own enable flag.
+/// Channel tools are added per turn and filtered in `add_channel_tools`.
+pub struct FilteredToolServer {
+    server: ToolServer,
+    disabled_tools: Arc<Vec<String>>,
+    result_limiter: Option<ResultLimiter>,
+}
+
+impl FilteredToolServer {
+    pub fn new(disabled_tools: Arc<Vec<String>>) -> Self {
+        Self {
+            server: ToolServer::new(),
+            disabled_tools,
+            result_limiter: None,
+        }
+    }
+
+    /// Cap the results of tools registered after this call, per
+    /// `[defaults.tool_results]`.
+    pub fn with_result_limits(mut self, limits: &ToolResultLimits, workspace: &Path) -> Self {
+        self.result_limiter = Some(ResultLimiter::new(limits, workspace));
+        self
+    }
+
+    /// Register `tool` unless its name is disabled.
+    pub fn tool(mut self, tool: impl rig::tool::Tool + 'static) -> Self {
+        let tool_name = tool.name();
+        if is_tool_disabled(&self.disabled_tools, &tool_name) {
+            tracing::debug!(tool_name = %tool_name, "tool disabled by config, not registering");
+        } else if let Some(limiter) = &self.result_limiter {
+            self.server = self.server.tool(limiter.wrap(tool));
+        } else {
+            self.server = self.server.tool(tool);
+        }
+        self
+    }
+
+    pub fn run(self) -> ToolServerHandle {
+        self.server.run()
+    }
+}
+
 /// Add per-turn tools to a channel's ToolServer.
 ///
 /// Called when a conversation turn begins. These tools hold per-turn state
@@ -361,6 +481,7 @@ pub async fn add_channel_tools(
     slack_thread_ts: Option<&str>,
 ) -> Result<(), rig::tool::server::ToolServerError> {
     let conversation_id = conversation_id.into();
+    let disabled_tools = state.deps.runtime_config.disabled_tools.load_full();
 
     if allow_direct_reply {
         let agent_display_name = state
@@ -369,15 +490,22 @@ pub async fn add_channel_tools(
             .get(state.deps.agent_id.as_ref())
             .cloned()
             .unwrap_or_else(|| state.deps.agent_id.to_string());
+        let output_language = crate::agent::channel_prompt::resolve_output_language(
+            &state.deps.runtime_config,
+            &state.channel_id,
+        );
         handle
-            .add_tool(ReplyTool::new(
-                response_tx.clone(),
-                conversation_id.clone(),
-                state.conversation_logger.clone(),
-                state.channel_id.clone(),
-                replied_flag.clone(),
-                agent_display_name,
-            ))
+            .add_tool(
+                ReplyTool::new(
+                    response_tx.clone(),
+                    conversation_id.clone(),
+                    state.conversation_logger.clone(),
+                    state.channel_id.clone(),
+                    replied_flag.clone(),
+                    agent_display_name,
+                )
+                .with_output_language(output_language),
+            )
             .await?;
     }
     handle.add_tool(BranchTool::new(state.clone())).await?;
@@ -392,6 +520,7 @@ pub async fn add_channel_tools(
             .unwrap_or_else(|| state.deps.agent_id.to_string());
         handle
             .add_tool(SendMessageTool::new(
+                state.deps.agent_id.clone(),
                 messaging_manager.clone(),
                 state.channel_store.clone(),
                 state.conversation_logger.clone(),
@@ -428,11 +557,80 @@ pub async fn add_channel_tools(
             ))
             .await?;
     }
+    // Pinned context and slots live in the settings store; skip the tools
+    // without one.
+    let settings = state.deps.runtime_config.settings.load_full();
+    if let Some(settings) = settings.as_ref() {
+        handle
+            .add_tool(PinContextTool::new(settings.clone(), state.channel_id.clone()))
+            .await?;
+        handle
+            .add_tool(UnpinContextTool::new(settings.clone(), state.channel_id.clone()))
+            .await?;
+        handle
+            .add_tool(ListPinsTool::new(settings.clone(), state.channel_id.clone()))
+            .await?;
+        handle
+            .add_tool(SetSlotTool::new(settings.clone(), state.channel_id.clone()))
+            .await?;
+        handle
+            .add_tool(GetSlotTool::new(settings.clone(), state.channel_id.clone()))
+            .await?;
+
+        // Persona tools only make sense when personas are configured.
+        let personas = state.deps.runtime_config.personas.load_full();
+        if !personas.is_empty() {
+            handle
+                .add_tool(SwitchPersonaTool::new(
+                    settings.clone(),
+                    personas.clone(),
+                    state.channel_id.clone(),
+                ))
+                .await?;
+            handle
+                .add_tool(ListPersonasTool::new(
+                    settings.clone(),
+                    personas,
+                    state.channel_id.clone(),
+                ))
+                .await?;
+        }
+    }
+    handle
+        .add_tool(NotesTool::new(
+            crate::notes::NotesStore::new(state.deps.sqlite_pool.clone()),
+            Some(state.channel_id.clone()),
+        ))
+        .await?;
+    handle.add_tool(ResetConversationTool::new(state.clone())).await?;
+    // Handoffs need a linked human to route to and an adapter to reach them.
+    let has_linked_human = !crate::handoff::linked_humans(
+        &state.deps.agent_id,
+        &state.deps.links.load(),
+        &state.deps.humans.load(),
+    )
+    .is_empty();
+    if has_linked_human && state.deps.messaging_manager.is_some() {
+        handle
+            .add_tool(RequestHumanTool::new(state.clone(), current_adapter.clone()))
+            .await?;
+    }
     handle.add_tool(CancelTool::new(state)).await?;
     handle
         .add_tool(SkipTool::new(skip_flag.clone(), response_tx.clone()))
         .await?;
     handle.add_tool(ReactTool::new(response_tx.clone())).await?;
+    handle
+        .add_tool(SendStickerTool::new(response_tx.clone(), &conversation_id))
+        .await?;
+    // Home views only exist on Slack, and only for the user the turn belongs to.
+    if let Some(user_id) =
+        publish_home_view::home_view_user(&conversation_id, response_tx.target())
+    {
+        handle
+            .add_tool(PublishHomeViewTool::new(response_tx.clone(), user_id))
+            .await?;
+    }
     if let Some(cron_tool) = cron_tool {
         let cron_tool = cron_tool.with_default_delivery_target(
             default_delivery_target_for_conversation(&conversation_id, slack_thread_ts),
@@ -443,6 +641,11 @@ pub async fn add_channel_tools(
         agent_msg = agent_msg.with_skip_flag(skip_flag.clone());
         handle.add_tool(agent_msg).await?;
     }
+    // Drop whatever the agent has disabled. Removing a tool that was never
+    // added is a no-op.
+    for tool_name in disabled_tools.iter() {
+        handle.remove_tool(tool_name).await?;
+    }
     Ok(())
 }
 
@@ -486,16 +689,29 @@ pub async fn remove_channel_tools(
     handle.remove_tool(SpawnWorkerTool::NAME).await?;
     handle.remove_tool(RouteTool::NAME).await?;
     handle.remove_tool(CancelTool::NAME).await?;
+    handle.remove_tool(ResetConversationTool::NAME).await?;
     handle.remove_tool(SkipTool::NAME).await?;
     handle.remove_tool(SendFileTool::NAME).await?;
     handle.remove_tool(ReactTool::NAME).await?;
+    handle.remove_tool(SendStickerTool::NAME).await?;
     handle.remove_tool(ProjectManageTool::NAME).await?;
-    // Cron, send_message, send_agent_message, and attachment_recall removal is
-    // best-effort since not all channels have them
+    handle.remove_tool(NotesTool::NAME).await?;
+    // Cron, send_message, send_agent_message, attachment_recall, publish_home_view,
+    // request_human, and the pinned context, slot, and persona tools are best-effort
+    // since not all channels have them
     let _ = handle.remove_tool(CronTool::NAME).await;
     let _ = handle.remove_tool(SendMessageTool::NAME).await;
+    let _ = handle.remove_tool(RequestHumanTool::NAME).await;
     let _ = handle.remove_tool(SendAgentMessageTool::NAME).await;
     let _ = handle.remove_tool(AttachmentRecallTool::NAME).await;
+    let _ = handle.remove_tool(PublishHomeViewTool::NAME).await;
+    let _ = handle.remove_tool(PinContextTool::NAME).await;
+    let _ = handle.remove_tool(UnpinContextTool::NAME).await;
+    let _ = handle.remove_tool(ListPinsTool::NAME).await;
+    let _ = handle.remove_tool(SetSlotTool::NAME).await;
+    let _ = handle.remove_tool(GetSlotTool::NAME).await;
+    let _ = handle.remove_tool(SwitchPersonaTool::NAME).await;
+    let _ = handle.remove_tool(ListPersonasTool::NAME).await;
     Ok(())
 }
 
@@ -540,10 +756,14 @@ pub fn create_branch_tool_server(
         memory_save = memory_save.with_contract_state(contract_state.clone());
     }
 
-    let mut server = ToolServer::new()
+    let mut server = FilteredToolServer::new(runtime_config.disabled_tools.load_full())
+        .with_result_limits(
+            &runtime_config.tool_results.load(),
+            &runtime_config.workspace_dir,
+        )
         .tool(memory_save)
         .tool(MemoryRecallTool::new(memory_search.clone()))
-        .tool(MemoryDeleteTool::new(memory_search))
+        .tool(MemoryDeleteTool::new(memory_search.clone()))
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
         .tool(SpacebotDocsTool::new())
         .tool(EmailSearchTool::new(runtime_config))
@@ -570,6 +790,14 @@ pub fn create_branch_tool_server(
     }
 
     if let Some(state) = state {
+        server = server.tool(NotesTool::new(
+            crate::notes::NotesStore::new(state.deps.sqlite_pool.clone()),
+            Some(state.channel_id.clone()),
+        ));
+        server = server.tool(
+            ForgetMemoryTool::new(agent_id, memory_search, state.deps.event_tx.clone())
+                .with_runtime_config(state.deps.runtime_config.clone()),
+        );
         server = server.tool(SpawnWorkerTool::new(state));
     }
 
@@ -599,7 +827,8 @@ pub fn create_worker_tool_server(
     mcp_tools: Vec<McpToolAdapter>,
     runtime_config: Arc<RuntimeConfig>,
 ) -> ToolServerHandle {
-    let mut server = ToolServer::new()
+    let mut server = FilteredToolServer::new(runtime_config.disabled_tools.load_full())
+        .with_result_limits(&runtime_config.tool_results.load(), &workspace)
         .tool(ShellTool::new(workspace.clone(), sandbox.clone()))
         .tool(TaskUpdateTool::for_worker(
             task_store,
@@ -613,9 +842,10 @@ pub fn create_worker_tool_server(
             }
             status_tool
         })
+        .tool(ListSkillsTool::new(runtime_config.clone()))
         .tool(ReadSkillTool::new(runtime_config.clone()));
 
-    server = register_file_tools(server, workspace, sandbox);
+    server = register_file_tools(server, workspace.clone(), sandbox);
 
     if let Some(store) = runtime_config.secrets.load().as_ref() {
         server = server.tool(SecretSetTool::new(store.clone()));
@@ -683,6 +913,7 @@ pub fn create_cortex_chat_tool_server(
     cortex_ctx: Option<crate::tools::spawn_worker::CortexChatContext>,
 ) -> ToolServerHandle {
     let logs_dir = workspace.join(".spacebot").join("logs");
+    let event_tx = deps.event_tx.clone();
 
     let spawn_tool = {
         let tool = DetachedSpawnWorkerTool::new(deps, screenshot_dir.clone(), logs_dir);
@@ -692,7 +923,8 @@ pub fn create_cortex_chat_tool_server(
         }
     };
 
-    let mut server = ToolServer::new()
+    let mut server = FilteredToolServer::new(runtime_config.disabled_tools.load_full())
+        .with_result_limits(&runtime_config.tool_results.load(), &workspace)
         .tool(memory_save_with_events(
             memory_search.clone(),
             agent_id.clone(),
@@ -700,7 +932,11 @@ pub fn create_cortex_chat_tool_server(
             None,
         ))
         .tool(MemoryRecallTool::new(memory_search.clone()))
-        .tool(MemoryDeleteTool::new(memory_search))
+        .tool(MemoryDeleteTool::new(memory_search.clone()))
+        .tool(
+            ForgetMemoryTool::new(agent_id.clone(), memory_search, event_tx)
+                .with_runtime_config(runtime_config.clone()),
+        )
         .tool(ChannelRecallTool::new(conversation_logger, channel_store))
         .tool(SpacebotDocsTool::new())
         .tool(ConfigInspectTool::new(
@@ -826,6 +1062,24 @@ mod tests {
         assert!(args.env.is_empty());
     }
 
+    #[tokio::test]
+    async fn filtered_tool_server_omits_disabled_tools() {
+        let handle = FilteredToolServer::new(Arc::new(vec!["spacebot_docs".to_string()]))
+            .tool(SpacebotDocsTool::new())
+            .tool(FactoryListPresetsTool::new())
+            .run();
+
+        let names: Vec<String> = handle
+            .get_tool_defs(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|definition| definition.name)
+            .collect();
+        assert_eq!(names, vec!["factory_list_presets".to_string()]);
+        assert!(handle.call_tool("spacebot_docs", "{}").await.is_err());
+    }
+
     #[tokio::test]
     async fn shell_rejects_empty_env_var_name() {
         let config = std::sync::Arc::new(arc_swap::ArcSwap::from_pointee(
@@ -1013,6 +1267,19 @@ mod tests {
         assert!(truncate_output(text, 5).starts_with("🙂"));
     }
 
+    #[test]
+    fn truncate_tool_result_appends_marker() {
+        let text = "a".repeat(120);
+        assert_eq!(truncate_tool_result(&text, 200, None), text);
+
+        let truncated = truncate_tool_result(&text, 100, None);
+        assert!(truncated.starts_with(&"a".repeat(100)));
+        assert!(truncated.ends_with("[truncated 20 bytes]"));
+
+        let saved = truncate_tool_result(&text, 100, Some(Path::new("/tmp/full.txt")));
+        assert!(saved.contains("full result saved to /tmp/full.txt"));
+    }
+
     #[test]
     fn truncate_cyrillic_does_not_panic() {
         // Cyrillic chars are 2 bytes each in UTF-8
@@ -1095,4 +1362,11 @@ mod tests {
         let result = truncate_utf8_ellipsis(text, 7);
         assert_eq!(result, "абв"); // also fits, no truncation needed
     }
+
+    #[test]
+    fn char_prefix_respects_char_boundaries() {
+        assert_eq!(char_prefix("héllo wörld", 7), "héllo w");
+        assert_eq!(char_prefix("wörld", 2), "wö");
+        assert_eq!(char_prefix("hi", 5), "hi");
+    }
 }
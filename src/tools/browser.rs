@@ -2141,11 +2141,11 @@ impl Tool for BrowserCloseTool {
 /// `BrowserState` (via `SharedBrowserHandle` for persistent sessions, or a
 /// fresh instance for ephemeral sessions).
 pub fn register_browser_tools(
-    server: rig::tool::server::ToolServer,
+    server: crate::tools::FilteredToolServer,
     config: BrowserConfig,
     screenshot_dir: PathBuf,
     runtime_config: &crate::config::RuntimeConfig,
-) -> rig::tool::server::ToolServer {
+) -> crate::tools::FilteredToolServer {
     let state = if let Some(shared) = runtime_config
         .shared_browser
         .as_ref()
@@ -36,6 +36,11 @@ impl CronTool {
         self.default_delivery_target = default_delivery_target;
         self
     }
+
+    /// The scheduler this tool registers jobs with.
+    pub fn scheduler(&self) -> &Arc<Scheduler> {
+        &self.scheduler
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -287,9 +292,21 @@ impl CronTool {
             .await
             .map_err(|error| CronError(format!("failed to save: {error}")))?;
 
+        // Saving doesn't clear a pause set through the API, so re-registering
+        // an existing paused job must not start its timer again.
+        let paused = self
+            .store
+            .paused_ids()
+            .await
+            .map_err(|error| CronError(format!("failed to save: {error}")))?
+            .contains(&id);
+
         // Register with the running scheduler so it starts immediately
         self.scheduler
-            .register(config)
+            .register(CronConfig {
+                enabled: !paused,
+                ..config
+            })
             .await
             .map_err(|error| CronError(format!("failed to register: {error}")))?;
 
@@ -316,8 +333,11 @@ impl CronTool {
         } else {
             message.push_str(&format!(" Active-hours timezone: {timezone}."));
         }
+        if paused {
+            message.push_str(" The job is paused and won't run until it is resumed.");
+        }
 
-        tracing::info!(cron_id = %id, %interval_secs, %delivery_target, "cron job created via tool");
+        tracing::info!(cron_id = %id, %interval_secs, %delivery_target, paused, "cron job created via tool");
 
         Ok(CronOutput {
             success: true,
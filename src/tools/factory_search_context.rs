@@ -157,6 +157,9 @@ impl Tool for FactorySearchContextTool {
 
         let curated = curate_results(&search_results, max_results);
         let total_found = search_results.len();
+        self.memory_search
+            .record_recall(&search_results[..curated.len()])
+            .await;
 
         let results: Vec<ContextResult> = curated
             .iter()
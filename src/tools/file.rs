@@ -586,10 +586,10 @@ async fn do_file_list(path: &Path) -> Result<FileOutput, FileError> {
 /// Register all file tools on a `ToolServer`. The tools share a single
 /// `FileContext` for path validation and sandbox enforcement.
 pub fn register_file_tools(
-    server: rig::tool::server::ToolServer,
+    server: crate::tools::FilteredToolServer,
     workspace: PathBuf,
     sandbox: Arc<Sandbox>,
-) -> rig::tool::server::ToolServer {
+) -> crate::tools::FilteredToolServer {
     let context = FileContext::new(workspace, sandbox);
 
     server
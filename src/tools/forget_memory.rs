@@ -0,0 +1,377 @@
+//! Forget memory tool for branches.
+//!
+//! Resolves a memory ID or free-text query to matching memories and
+//! permanently deletes them, including graph edges and embeddings. Used for
+//! privacy requests where a soft-delete via `memory_delete` isn't enough.
+
+use crate::memory::MemorySearch;
+use crate::memory::search::{SearchConfig, SearchMode};
+use crate::memory::types::Memory;
+use crate::{AgentId, ProcessEvent};
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+/// Queries matching more memories than this require `confirm: true` before
+/// anything is deleted. Keeps a vague query from wiping half the graph.
+pub const FORGET_CONFIRMATION_THRESHOLD: usize = 3;
+
+/// Hard cap on memories deleted by a single call, even when confirmed.
+const FORGET_MAX_MATCHES: usize = 20;
+
+/// Tool for permanently deleting memories by ID or query.
+#[derive(Debug, Clone)]
+pub struct ForgetMemoryTool {
+    agent_id: AgentId,
+    memory_search: Arc<MemorySearch>,
+    event_tx: tokio::sync::broadcast::Sender<ProcessEvent>,
+    runtime_config: Option<Arc<crate::config::RuntimeConfig>>,
+}
+
+impl ForgetMemoryTool {
+    /// Create a new forget memory tool.
+    pub fn new(
+        agent_id: AgentId,
+        memory_search: Arc<MemorySearch>,
+        event_tx: tokio::sync::broadcast::Sender<ProcessEvent>,
+    ) -> Self {
+        Self {
+            agent_id,
+            memory_search,
+            event_tx,
+            runtime_config: None,
+        }
+    }
+
+    /// Enable knowledge synthesis dirty-flag bumping on delete.
+    pub fn with_runtime_config(mut self, config: Arc<crate::config::RuntimeConfig>) -> Self {
+        self.runtime_config = Some(config);
+        self
+    }
+
+    async fn resolve_matches(&self, query_or_id: &str) -> Result<Vec<Memory>, ForgetMemoryError> {
+        let store = self.memory_search.store();
+        if let Some(memory) = store
+            .load(query_or_id)
+            .await
+            .map_err(|error| ForgetMemoryError(format!("Failed to look up memory: {error}")))?
+        {
+            return Ok(vec![memory]);
+        }
+
+        let config = SearchConfig {
+            mode: SearchMode::Hybrid,
+            max_results: FORGET_MAX_MATCHES,
+            ..SearchConfig::default()
+        };
+        let results = self
+            .memory_search
+            .search(query_or_id, &config)
+            .await
+            .map_err(|error| ForgetMemoryError(format!("Memory search failed: {error}")))?;
+
+        Ok(results.into_iter().map(|result| result.memory).collect())
+    }
+
+    /// Load the memories a confirmed call names explicitly. IDs that no
+    /// longer exist are skipped.
+    async fn load_confirmed(
+        &self,
+        memory_ids: &[String],
+    ) -> Result<Vec<Memory>, ForgetMemoryError> {
+        let store = self.memory_search.store();
+        let mut memories = Vec::with_capacity(memory_ids.len());
+        for memory_id in memory_ids.iter().take(FORGET_MAX_MATCHES) {
+            if let Some(memory) = store
+                .load(memory_id.trim())
+                .await
+                .map_err(|error| ForgetMemoryError(format!("Failed to look up memory: {error}")))?
+            {
+                memories.push(memory);
+            }
+        }
+        Ok(memories)
+    }
+}
+
+/// Error type for forget memory tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Forget memory failed: {0}")]
+pub struct ForgetMemoryError(String);
+
+/// Arguments for forget memory tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ForgetMemoryArgs {
+    /// A memory ID, or a search query describing what to forget.
+    pub query_or_id: String,
+    /// Required when the query matches more than a few memories.
+    #[serde(default)]
+    pub confirm: bool,
+    /// IDs from `pending_confirmation` to delete when confirming. Only these
+    /// memories are deleted; the query is not searched again.
+    #[serde(default)]
+    pub memory_ids: Vec<String>,
+    /// Brief reason for forgetting (for audit purposes).
+    pub reason: Option<String>,
+}
+
+/// A memory matched by the forget query.
+#[derive(Debug, Serialize)]
+pub struct ForgetMemoryMatch {
+    pub id: String,
+    pub memory_type: String,
+    pub preview: String,
+}
+
+/// Output from forget memory tool.
+#[derive(Debug, Serialize)]
+pub struct ForgetMemoryOutput {
+    /// IDs of memories that were deleted.
+    pub deleted: Vec<String>,
+    /// Matches that were found but not deleted because confirmation is needed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending_confirmation: Vec<ForgetMemoryMatch>,
+    /// Description of what happened.
+    pub message: String,
+}
+
+impl Tool for ForgetMemoryTool {
+    const NAME: &'static str = "forget_memory";
+
+    type Error = ForgetMemoryError;
+    type Args = ForgetMemoryArgs;
+    type Output = ForgetMemoryOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/forget_memory").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "query_or_id": {
+                        "type": "string",
+                        "description": "A memory ID, or a description of what to forget"
+                    },
+                    "confirm": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": format!(
+                            "Set to true to delete when more than {FORGET_CONFIRMATION_THRESHOLD} memories match"
+                        )
+                    },
+                    "memory_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "With confirm: the IDs from pending_confirmation to delete. Only these are deleted"
+                    },
+                    "reason": {
+                        "type": "string",
+                        "description": "Optional reason for forgetting"
+                    }
+                },
+                "required": ["query_or_id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let query_or_id = args.query_or_id.trim();
+        if query_or_id.is_empty() {
+            return Err(ForgetMemoryError(
+                "can't forget memory: query_or_id is empty".into(),
+            ));
+        }
+
+        // Confirmation only covers the IDs the caller saw. Re-running the
+        // search could pick up memories that weren't in the pending list.
+        let explicit_ids = !args.memory_ids.is_empty();
+        let matches = if explicit_ids {
+            self.load_confirmed(&args.memory_ids).await?
+        } else {
+            self.resolve_matches(query_or_id).await?
+        };
+        if matches.is_empty() {
+            return Ok(ForgetMemoryOutput {
+                deleted: Vec::new(),
+                pending_confirmation: Vec::new(),
+                message: format!("No memories matched \"{query_or_id}\"."),
+            });
+        }
+
+        if matches.len() > FORGET_CONFIRMATION_THRESHOLD && !(args.confirm && explicit_ids) {
+            let pending_confirmation = matches
+                .iter()
+                .map(|memory| ForgetMemoryMatch {
+                    id: memory.id.clone(),
+                    memory_type: memory.memory_type.to_string(),
+                    preview: truncate(memory.content.lines().next().unwrap_or(""), 80).to_string(),
+                })
+                .collect();
+            return Ok(ForgetMemoryOutput {
+                deleted: Vec::new(),
+                pending_confirmation,
+                message: format!(
+                    "{} memories matched. Nothing was deleted. Review the matches and call again \
+                     with confirm: true and memory_ids set to the IDs that should go, or use a \
+                     more specific query or a memory ID.",
+                    matches.len()
+                ),
+            });
+        }
+
+        let mut deleted = Vec::with_capacity(matches.len());
+        for memory in &matches {
+            let purged = self
+                .memory_search
+                .purge(&memory.id)
+                .await
+                .map_err(|error| ForgetMemoryError(format!("Failed to delete memory: {error}")))?;
+            if !purged {
+                continue;
+            }
+
+            self.event_tx
+                .send(ProcessEvent::MemoryDeleted {
+                    agent_id: self.agent_id.clone(),
+                    memory_id: memory.id.clone(),
+                    channel_id: memory.channel_id.clone(),
+                })
+                .ok();
+            deleted.push(memory.id.clone());
+        }
+
+        if !deleted.is_empty()
+            && let Some(runtime_config) = &self.runtime_config
+        {
+            runtime_config.bump_knowledge_synthesis_version();
+        }
+
+        tracing::info!(
+            agent_id = %self.agent_id,
+            deleted_count = deleted.len(),
+            reason = ?args.reason,
+            "memories permanently forgotten"
+        );
+
+        let message = format!("Permanently deleted {} memories.", deleted.len());
+        Ok(ForgetMemoryOutput {
+            deleted,
+            pending_confirmation: Vec::new(),
+            message,
+        })
+    }
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        s
+    } else {
+        &s[..s.floor_char_boundary(max)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::MemoryType;
+    use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryStore};
+
+    async fn forget_tool() -> (tempfile::TempDir, Arc<MemorySearch>, ForgetMemoryTool) {
+        let store = MemoryStore::connect_in_memory().await;
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let lance_conn = lancedb::connect(dir.path().to_str().expect("temp path"))
+            .execute()
+            .await
+            .expect("failed to connect to lancedb");
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn)
+            .await
+            .expect("failed to create embedding table");
+        let embedding_model =
+            Arc::new(EmbeddingModel::new(dir.path()).expect("failed to load embedding model"));
+        let memory_search = Arc::new(MemorySearch::new(store, embedding_table, embedding_model));
+        let (event_tx, _event_rx) = tokio::sync::broadcast::channel(8);
+        let tool = ForgetMemoryTool::new(Arc::from("agent"), memory_search.clone(), event_tx);
+        (dir, memory_search, tool)
+    }
+
+    async fn save_embedded(memory_search: &MemorySearch, content: &str) -> Memory {
+        let memory = Memory::new(content, MemoryType::Fact);
+        memory_search.store().save(&memory).await.unwrap();
+        let embedding = memory_search.embed_passage(content).await.unwrap();
+        memory_search
+            .embedding_table()
+            .store(&memory.id, content, &embedding)
+            .await
+            .unwrap();
+        memory
+    }
+
+    fn args(query_or_id: &str) -> ForgetMemoryArgs {
+        ForgetMemoryArgs {
+            query_or_id: query_or_id.into(),
+            confirm: false,
+            memory_ids: Vec::new(),
+            reason: Some("user asked".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn forgetting_by_id_deletes_the_memory_and_its_embedding() {
+        let (_dir, memory_search, tool) = forget_tool().await;
+        let memory = save_embedded(&memory_search, "my phone number is 555-0100").await;
+
+        let output = tool.call(args(&memory.id)).await.unwrap();
+
+        assert_eq!(output.deleted, vec![memory.id.clone()]);
+        assert!(
+            memory_search
+                .store()
+                .load(&memory.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let embeddings = memory_search
+            .embedding_table()
+            .all_embeddings()
+            .await
+            .unwrap();
+        assert!(!embeddings.contains_key(&memory.id));
+    }
+
+    #[tokio::test]
+    async fn unknown_confirmed_ids_delete_nothing() {
+        let (_dir, memory_search, tool) = forget_tool().await;
+        let memory = save_embedded(&memory_search, "works at the harbour office").await;
+
+        let output = tool
+            .call(ForgetMemoryArgs {
+                confirm: true,
+                memory_ids: vec!["no-such-memory".into()],
+                ..args("harbour")
+            })
+            .await
+            .unwrap();
+
+        assert!(output.deleted.is_empty());
+        assert!(
+            memory_search
+                .store()
+                .load(&memory.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_queries_are_rejected() {
+        let (_dir, _memory_search, tool) = forget_tool().await;
+        assert!(tool.call(args("   ")).await.is_err());
+    }
+}
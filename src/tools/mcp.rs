@@ -112,8 +112,8 @@ impl Tool for McpToolAdapter {
             .await
             .map_err(|error| McpToolError(error.to_string()))?;
 
+        // Successful results are capped by the tool server's result limits.
         let output_text = Self::collect_result_text(&result);
-        let output_text = truncate_output(&output_text, crate::tools::MAX_TOOL_OUTPUT_BYTES);
 
         if result.is_error.unwrap_or(false) {
             let message = if output_text.is_empty() {
@@ -122,7 +122,7 @@ impl Tool for McpToolAdapter {
                     self.server_name, self.tool_name
                 )
             } else {
-                output_text
+                truncate_output(&output_text, crate::tools::MAX_TOOL_OUTPUT_BYTES)
             };
             return Err(McpToolError(message));
         }
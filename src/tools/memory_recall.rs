@@ -229,18 +229,12 @@ impl Tool for MemoryRecallTool {
 
         let curated = curate_results(&search_results, args.max_results);
 
-        let store = self.memory_search.store();
-        let mut memories = Vec::new();
+        self.memory_search
+            .record_recall(&search_results[..curated.len()])
+            .await;
 
+        let mut memories = Vec::new();
         for result in &curated {
-            if let Err(error) = store.record_access(&result.memory.id).await {
-                tracing::warn!(
-                    memory_id = %result.memory.id,
-                    %error,
-                    "failed to record memory access"
-                );
-            }
-
             memories.push(MemoryOutput {
                 id: result.memory.id.clone(),
                 content: result.memory.content.clone(),
@@ -238,7 +238,10 @@ impl Tool for MemorySaveTool {
             _ => MemoryType::Fact,
         };
 
-        let mut memory = Memory::new(&args.content, memory_type);
+        // Redact before anything is persisted so neither the row nor the
+        // embedding ever sees the raw text.
+        let content = self.memory_search.scrub_content(&args.content);
+        let mut memory = Memory::new(content, memory_type);
 
         if let Some(importance) = args.importance {
             memory = memory.with_importance(importance);
@@ -309,12 +312,7 @@ impl Tool for MemorySaveTool {
 
         // Generate and store embedding. On failure, compensate by deleting the
         // SQLite row (and any associations already written) so there is no orphan.
-        let embedding = match self
-            .memory_search
-            .embedding_model_arc()
-            .embed_one(&args.content)
-            .await
-        {
+        let embedding = match self.memory_search.embed_passage(&memory.content).await {
             Ok(emb) => emb,
             Err(embed_err) => {
                 if let Err(assoc_err) = self
@@ -345,13 +343,24 @@ impl Tool for MemorySaveTool {
         match self
             .memory_search
             .embedding_table()
-            .store(&memory.id, &args.content, &embedding)
+            .store(&memory.id, &memory.content, &embedding)
             .await
         {
             Ok(()) => {
                 if let Some(contract_state) = &self.contract_state {
                     contract_state.record_saved_memory_id(memory.id.clone());
                 }
+                if let Err(error) = self
+                    .memory_search
+                    .store_routed_embedding(&memory.id, &memory.content)
+                    .await
+                {
+                    tracing::warn!(
+                        %error,
+                        memory_id = %memory.id,
+                        "failed to store routed embedding, keeping the default vector only"
+                    );
+                }
             }
             Err(embed_err) => {
                 if let Err(assoc_err) = self
@@ -480,7 +489,62 @@ fn summarize_memory_content(content: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::summarize_memory_content;
+    use super::*;
+    use crate::config::MemoryScrubConfig;
+    use crate::memory::{EmbeddingModel, EmbeddingTable, MemoryStore};
+    use std::sync::OnceLock;
+
+    fn shared_embedding_model() -> Arc<EmbeddingModel> {
+        static MODEL: OnceLock<Arc<EmbeddingModel>> = OnceLock::new();
+        Arc::clone(MODEL.get_or_init(|| {
+            let cache_dir = std::env::temp_dir().join("spacebot-test-embedding-cache");
+            std::fs::create_dir_all(&cache_dir).expect("failed to create embedding cache dir");
+            Arc::new(EmbeddingModel::new(&cache_dir).expect("failed to initialize embedding model"))
+        }))
+    }
+
+    #[tokio::test]
+    async fn scrubbed_memories_are_stored_redacted() {
+        let store = MemoryStore::connect_in_memory().await;
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let lance_conn = lancedb::connect(dir.path().to_str().expect("temp path"))
+            .execute()
+            .await
+            .expect("failed to connect to lancedb");
+        let embedding_table = EmbeddingTable::open_or_create(&lance_conn)
+            .await
+            .expect("failed to create embedding table");
+        let memory_search = Arc::new(
+            MemorySearch::new(store.clone(), embedding_table, shared_embedding_model())
+                .with_scrub_config(&MemoryScrubConfig {
+                    enabled: true,
+                    patterns: vec![r"ACCT-\d+".into()],
+                    ..MemoryScrubConfig::default()
+                }),
+        );
+
+        let output = MemorySaveTool::new(memory_search)
+            .call(MemorySaveArgs {
+                content: "Jordan (jordan@example.com, 555-123-4567) owns ACCT-4411".into(),
+                memory_type: "fact".into(),
+                importance: None,
+                source: None,
+                channel_id: None,
+                associations: Vec::new(),
+            })
+            .await
+            .expect("memory save should succeed");
+
+        let saved = store
+            .load(&output.memory_id)
+            .await
+            .expect("failed to load memory")
+            .expect("memory should exist");
+        assert_eq!(
+            saved.content,
+            "Jordan ([REDACTED:email], [REDACTED:phone]) owns [REDACTED]"
+        );
+    }
 
     #[test]
     fn summarize_memory_content_prefers_first_non_empty_line() {
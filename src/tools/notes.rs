@@ -0,0 +1,162 @@
+//! Notes tool: a scratchpad the agent reads back verbatim.
+
+use crate::ChannelId;
+use crate::notes::{Note, NotesStore};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool for reading and editing the agent's scratchpad notes.
+#[derive(Debug, Clone)]
+pub struct NotesTool {
+    store: NotesStore,
+    channel_id: Option<ChannelId>,
+}
+
+impl NotesTool {
+    pub fn new(store: NotesStore, channel_id: Option<ChannelId>) -> Self {
+        Self { store, channel_id }
+    }
+
+    fn resolve_channel(&self, scope: Option<&str>) -> Result<Option<&str>, NotesError> {
+        match scope.unwrap_or("agent") {
+            "agent" => Ok(None),
+            "channel" => self
+                .channel_id
+                .as_deref()
+                .map(Some)
+                .ok_or_else(|| NotesError("there is no channel in this context".into())),
+            other => Err(NotesError(format!(
+                "unknown scope '{other}'. Use 'agent' or 'channel'."
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Notes operation failed: {0}")]
+pub struct NotesError(String);
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NotesArgs {
+    /// The operation to perform: "read", "append", "replace", or "clear".
+    pub action: String,
+    /// Which note to use: "agent" (shared across all conversations, the
+    /// default) or "channel" (this conversation only).
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Required for "append" and "replace": the text to write.
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotesOutput {
+    pub success: bool,
+    pub message: String,
+    /// The note after the operation. Absent when it is empty or was cleared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<Note>,
+}
+
+impl Tool for NotesTool {
+    const NAME: &'static str = "notes";
+
+    type Error = NotesError;
+    type Args = NotesArgs;
+    type Output = NotesOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/notes").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "action": {
+                        "type": "string",
+                        "enum": ["read", "append", "replace", "clear"],
+                        "description": "read the note, append a line to it, replace it entirely, or clear it."
+                    },
+                    "scope": {
+                        "type": "string",
+                        "enum": ["agent", "channel"],
+                        "description": "'agent' (default) is shared across all conversations; 'channel' belongs to this conversation only."
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "For 'append' and 'replace': the text to write."
+                    }
+                },
+                "required": ["action"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let channel_id = self.resolve_channel(args.scope.as_deref())?;
+        let store_error = |error: crate::error::Error| NotesError(error.to_string());
+
+        match args.action.as_str() {
+            "read" => {
+                let note = self.store.read(channel_id).await.map_err(store_error)?;
+                let message = if note.is_some() {
+                    "Note loaded."
+                } else {
+                    "The note is empty."
+                };
+                Ok(NotesOutput {
+                    success: true,
+                    message: message.to_string(),
+                    note,
+                })
+            }
+            "append" | "replace" => {
+                let text = args
+                    .text
+                    .filter(|text| !text.trim().is_empty())
+                    .ok_or_else(|| NotesError(format!("'text' is required for {}", args.action)))?;
+                let note = if args.action == "append" {
+                    self.store.append(channel_id, &text).await
+                } else {
+                    self.store.replace(channel_id, &text).await
+                }
+                .map_err(store_error)?;
+
+                tracing::info!(
+                    channel_id = ?channel_id,
+                    action = %args.action,
+                    length = note.content.len(),
+                    "note updated"
+                );
+
+                Ok(NotesOutput {
+                    success: true,
+                    message: "Note saved.".to_string(),
+                    note: Some(note),
+                })
+            }
+            "clear" => {
+                let removed = self.store.clear(channel_id).await.map_err(store_error)?;
+                let message = if removed {
+                    "Note cleared."
+                } else {
+                    "The note was already empty."
+                };
+                Ok(NotesOutput {
+                    success: true,
+                    message: message.to_string(),
+                    note: None,
+                })
+            }
+            other => Ok(NotesOutput {
+                success: false,
+                message: format!(
+                    "Unknown action '{other}'. Use 'read', 'append', 'replace', or 'clear'."
+                ),
+                note: None,
+            }),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Persona tools for channels: switch the active persona for a conversation
+//! and list the personas defined in config.
+
+use crate::ChannelId;
+use crate::config::{Personas, normalize_persona_name};
+use crate::settings::SettingsStore;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Name that clears the active persona when no persona is defined under it.
+const DEFAULT_PERSONA: &str = "default";
+
+/// Error type for the persona tools.
+#[derive(Debug, thiserror::Error)]
+#[error("Persona switch failed: {0}")]
+pub struct PersonaError(String);
+
+/// Tool for switching the persona used in the current conversation.
+#[derive(Debug, Clone)]
+pub struct SwitchPersonaTool {
+    settings: Arc<SettingsStore>,
+    personas: Arc<Personas>,
+    channel_id: ChannelId,
+}
+
+impl SwitchPersonaTool {
+    pub fn new(
+        settings: Arc<SettingsStore>,
+        personas: Arc<Personas>,
+        channel_id: ChannelId,
+    ) -> Self {
+        Self {
+            settings,
+            personas,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for switch_persona tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SwitchPersonaArgs {
+    /// Persona to activate, or `default` to clear it.
+    pub name: String,
+}
+
+/// Output from switch_persona tool.
+#[derive(Debug, Serialize)]
+pub struct SwitchPersonaOutput {
+    pub success: bool,
+    /// The persona now active, or None when cleared.
+    pub active_persona: Option<String>,
+}
+
+impl Tool for SwitchPersonaTool {
+    const NAME: &'static str = "switch_persona";
+
+    type Error = PersonaError;
+    type Args = SwitchPersonaArgs;
+    type Output = SwitchPersonaOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let names: Vec<&str> = self.personas.iter().map(|(name, _)| name).collect();
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/switch_persona").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": format!(
+                            "Persona to activate ({}), or \"{DEFAULT_PERSONA}\" to return to your normal voice.",
+                            names.join(", ")
+                        )
+                    }
+                },
+                "required": ["name"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let name = normalize_persona_name(&args.name);
+        let active_persona = if self.personas.prompt_for(&name).is_some() {
+            Some(name)
+        } else if name == DEFAULT_PERSONA || name.is_empty() {
+            None
+        } else {
+            return Err(PersonaError(format!(
+                "no persona named '{}'; call list_personas to see the available ones",
+                args.name
+            )));
+        };
+
+        self.settings
+            .set_active_persona_for(&self.channel_id, active_persona.as_deref())
+            .map_err(|error| PersonaError(error.to_string()))?;
+
+        tracing::info!(
+            channel_id = %self.channel_id,
+            persona = active_persona.as_deref().unwrap_or(DEFAULT_PERSONA),
+            "persona switched"
+        );
+
+        Ok(SwitchPersonaOutput {
+            success: true,
+            active_persona,
+        })
+    }
+}
+
+/// Tool for listing the personas available to the current conversation.
+#[derive(Debug, Clone)]
+pub struct ListPersonasTool {
+    settings: Arc<SettingsStore>,
+    personas: Arc<Personas>,
+    channel_id: ChannelId,
+}
+
+impl ListPersonasTool {
+    pub fn new(
+        settings: Arc<SettingsStore>,
+        personas: Arc<Personas>,
+        channel_id: ChannelId,
+    ) -> Self {
+        Self {
+            settings,
+            personas,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for list_personas tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListPersonasArgs {}
+
+/// A persona as reported by list_personas.
+#[derive(Debug, Serialize)]
+pub struct PersonaSummary {
+    pub name: String,
+    pub prompt: String,
+    pub active: bool,
+}
+
+/// Output from list_personas tool.
+#[derive(Debug, Serialize)]
+pub struct ListPersonasOutput {
+    pub personas: Vec<PersonaSummary>,
+}
+
+impl Tool for ListPersonasTool {
+    const NAME: &'static str = "list_personas";
+
+    type Error = PersonaError;
+    type Args = ListPersonasArgs;
+    type Output = ListPersonasOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/list_personas").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let active = self
+            .settings
+            .active_persona_for(&self.channel_id)
+            .map(|name| normalize_persona_name(&name));
+        let personas = self
+            .personas
+            .iter()
+            .map(|(name, prompt)| PersonaSummary {
+                name: name.to_string(),
+                prompt: prompt.to_string(),
+                active: active.as_deref() == Some(name),
+            })
+            .collect();
+        Ok(ListPersonasOutput { personas })
+    }
+}
@@ -0,0 +1,303 @@
+//! Pinned context tools for channels: pin, unpin, and list snippets that are
+//! kept in the channel prompt across compaction.
+
+use crate::ChannelId;
+use crate::settings::{PinnedContext, SettingsStore};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Error type for the pinned context tools.
+#[derive(Debug, thiserror::Error)]
+#[error("Pinned context failed: {0}")]
+pub struct PinContextError(String);
+
+/// Tool for pinning a snippet to the current conversation.
+#[derive(Debug, Clone)]
+pub struct PinContextTool {
+    settings: Arc<SettingsStore>,
+    channel_id: ChannelId,
+}
+
+impl PinContextTool {
+    pub fn new(settings: Arc<SettingsStore>, channel_id: ChannelId) -> Self {
+        Self {
+            settings,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for pin_context tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PinContextArgs {
+    /// The fact or instruction to keep. Write it so it stands on its own.
+    pub text: String,
+}
+
+/// Output from pin_context tool.
+#[derive(Debug, Serialize)]
+pub struct PinContextOutput {
+    pub success: bool,
+    pub pin: PinnedContext,
+}
+
+impl Tool for PinContextTool {
+    const NAME: &'static str = "pin_context";
+
+    type Error = PinContextError;
+    type Args = PinContextArgs;
+    type Output = PinContextOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/pin_context").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "The fact or instruction to keep. Write it so it makes sense on its own, without the surrounding conversation."
+                    }
+                },
+                "required": ["text"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let pin = self
+            .settings
+            .pin_context(&self.channel_id, &args.text)
+            .map_err(|error| PinContextError(error.to_string()))?;
+
+        tracing::info!(channel_id = %self.channel_id, pin_id = %pin.id, "context pinned");
+
+        Ok(PinContextOutput { success: true, pin })
+    }
+}
+
+/// Tool for removing a pinned snippet from the current conversation.
+#[derive(Debug, Clone)]
+pub struct UnpinContextTool {
+    settings: Arc<SettingsStore>,
+    channel_id: ChannelId,
+}
+
+impl UnpinContextTool {
+    pub fn new(settings: Arc<SettingsStore>, channel_id: ChannelId) -> Self {
+        Self {
+            settings,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for unpin tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UnpinContextArgs {
+    /// ID of the pin to remove, as shown in brackets in the pinned context.
+    pub id: String,
+}
+
+/// Output from unpin tool.
+#[derive(Debug, Serialize)]
+pub struct UnpinContextOutput {
+    pub success: bool,
+    pub id: String,
+}
+
+impl Tool for UnpinContextTool {
+    const NAME: &'static str = "unpin";
+
+    type Error = PinContextError;
+    type Args = UnpinContextArgs;
+    type Output = UnpinContextOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/unpin").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "string",
+                        "description": "ID of the pin to remove, as shown in brackets in the pinned context."
+                    }
+                },
+                "required": ["id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let removed = self
+            .settings
+            .unpin_context(&self.channel_id, &args.id)
+            .map_err(|error| PinContextError(error.to_string()))?;
+        if !removed {
+            return Err(PinContextError(format!(
+                "no pin with id '{}' in this conversation",
+                args.id
+            )));
+        }
+
+        tracing::info!(channel_id = %self.channel_id, pin_id = %args.id, "context unpinned");
+
+        Ok(UnpinContextOutput {
+            success: true,
+            id: args.id,
+        })
+    }
+}
+
+/// Tool for listing the snippets pinned to the current conversation.
+#[derive(Debug, Clone)]
+pub struct ListPinsTool {
+    settings: Arc<SettingsStore>,
+    channel_id: ChannelId,
+}
+
+impl ListPinsTool {
+    pub fn new(settings: Arc<SettingsStore>, channel_id: ChannelId) -> Self {
+        Self {
+            settings,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for list_pins tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListPinsArgs {}
+
+/// Output from list_pins tool.
+#[derive(Debug, Serialize)]
+pub struct ListPinsOutput {
+    pub pins: Vec<PinnedContext>,
+}
+
+impl Tool for ListPinsTool {
+    const NAME: &'static str = "list_pins";
+
+    type Error = PinContextError;
+    type Args = ListPinsArgs;
+    type Output = ListPinsOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/list_pins").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+        Ok(ListPinsOutput {
+            pins: self.settings.pinned_context_for(&self.channel_id),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::store::{MAX_PIN_CHARS, MAX_PINS_PER_CHANNEL};
+
+    fn settings() -> (tempfile::TempDir, Arc<SettingsStore>) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let settings = SettingsStore::new(&dir.path().join("settings.redb"))
+            .expect("failed to open settings store");
+        (dir, Arc::new(settings))
+    }
+
+    fn tools(
+        settings: &Arc<SettingsStore>,
+        channel_id: &str,
+    ) -> (PinContextTool, UnpinContextTool, ListPinsTool) {
+        let channel_id = ChannelId::from(channel_id);
+        (
+            PinContextTool::new(settings.clone(), channel_id.clone()),
+            UnpinContextTool::new(settings.clone(), channel_id.clone()),
+            ListPinsTool::new(settings.clone(), channel_id),
+        )
+    }
+
+    async fn pin(tool: &PinContextTool, text: &str) -> Result<PinnedContext, PinContextError> {
+        tool.call(PinContextArgs { text: text.into() })
+            .await
+            .map(|output| output.pin)
+    }
+
+    #[tokio::test]
+    async fn pins_are_listed_per_channel_and_can_be_removed() {
+        let (_dir, settings) = settings();
+        let (pin_tool, unpin_tool, list_tool) = tools(&settings, "discord:1");
+        let (_, _, other_list_tool) = tools(&settings, "discord:2");
+
+        let first = pin(&pin_tool, "  Deploys go out on Thursdays only.  ")
+            .await
+            .unwrap();
+        let second = pin(&pin_tool, "Staging DB: db-staging-2").await.unwrap();
+        assert_eq!(first.text, "Deploys go out on Thursdays only.");
+
+        let listed = list_tool.call(ListPinsArgs {}).await.unwrap().pins;
+        assert_eq!(listed, vec![first.clone(), second.clone()]);
+        assert!(
+            other_list_tool
+                .call(ListPinsArgs {})
+                .await
+                .unwrap()
+                .pins
+                .is_empty()
+        );
+
+        unpin_tool
+            .call(UnpinContextArgs {
+                id: first.id.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            list_tool.call(ListPinsArgs {}).await.unwrap().pins,
+            vec![second]
+        );
+        assert!(
+            unpin_tool
+                .call(UnpinContextArgs { id: first.id })
+                .await
+                .is_err(),
+            "unpinning an unknown ID is an error"
+        );
+    }
+
+    #[tokio::test]
+    async fn pin_limits_are_enforced() {
+        let (_dir, settings) = settings();
+        let (pin_tool, _, list_tool) = tools(&settings, "discord:1");
+
+        assert!(pin(&pin_tool, "   ").await.is_err());
+        assert!(
+            pin(&pin_tool, &"x".repeat(MAX_PIN_CHARS + 1))
+                .await
+                .is_err()
+        );
+        pin(&pin_tool, &"é".repeat(MAX_PIN_CHARS)).await.unwrap();
+
+        for index in 1..MAX_PINS_PER_CHANNEL {
+            pin(&pin_tool, &format!("fact {index}")).await.unwrap();
+        }
+        assert!(pin(&pin_tool, "one too many").await.is_err());
+        assert_eq!(
+            list_tool.call(ListPinsArgs {}).await.unwrap().pins.len(),
+            MAX_PINS_PER_CHANNEL
+        );
+    }
+}
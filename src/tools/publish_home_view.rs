@@ -0,0 +1,211 @@
+//! Publish home view tool for refreshing a user's Slack App Home tab (channel only).
+
+use crate::{OutboundResponse, RoutedSender};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Slack rejects Home views with more blocks than this.
+const MAX_HOME_VIEW_BLOCKS: usize = 100;
+
+/// Tool for publishing a Block Kit view to a user's Slack App Home tab.
+///
+/// The user is fixed when the tool is registered, so the model can only
+/// replace the Home tab of the user the turn belongs to.
+#[derive(Debug, Clone)]
+pub struct PublishHomeViewTool {
+    response_tx: RoutedSender,
+    user_id: String,
+}
+
+impl PublishHomeViewTool {
+    pub fn new(response_tx: RoutedSender, user_id: impl Into<String>) -> Self {
+        Self {
+            response_tx,
+            user_id: user_id.into(),
+        }
+    }
+}
+
+/// The Slack user whose Home tab a turn may publish: the user behind the
+/// triggering Slack event, or for a cron job, the user of its `dm:` delivery
+/// target. `None` when the turn belongs to no Slack user.
+pub fn home_view_user(conversation_id: &str, target: &crate::InboundMessage) -> Option<String> {
+    if conversation_id.starts_with("slack:") {
+        return (target.source == "slack" && !target.sender_id.is_empty())
+            .then(|| target.sender_id.clone());
+    }
+    let delivery_target = target
+        .metadata
+        .get(crate::metadata_keys::CRON_DELIVERY_TARGET)
+        .and_then(|value| value.as_str())
+        .and_then(crate::messaging::target::parse_delivery_target)?;
+    if delivery_target.adapter != "slack" {
+        return None;
+    }
+    delivery_target
+        .target
+        .strip_prefix("dm:")
+        .filter(|user_id| !user_id.contains('#'))
+        .map(str::to_string)
+}
+
+/// Error type for publish home view tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Publish home view failed: {0}")]
+pub struct PublishHomeViewError(String);
+
+/// Arguments for publish home view tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PublishHomeViewArgs {
+    /// Slack Block Kit blocks making up the whole Home tab.
+    pub blocks: Vec<serde_json::Value>,
+}
+
+/// Output from publish home view tool.
+#[derive(Debug, Serialize)]
+pub struct PublishHomeViewOutput {
+    pub success: bool,
+    pub user_id: String,
+    pub block_count: usize,
+}
+
+impl Tool for PublishHomeViewTool {
+    const NAME: &'static str = "publish_home_view";
+
+    type Error = PublishHomeViewError;
+    type Args = PublishHomeViewArgs;
+    type Output = PublishHomeViewOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/publish_home_view").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "blocks": {
+                        "type": "array",
+                        "items": { "type": "object" },
+                        "description": "Slack Block Kit blocks for the whole Home tab (max 100). Buttons send their action_id back to you as an interaction."
+                    }
+                },
+                "required": ["blocks"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        validate_blocks(&args.blocks).map_err(PublishHomeViewError)?;
+
+        let user_id = self.user_id.clone();
+        let block_count = args.blocks.len();
+        tracing::info!(%user_id, block_count, "publish_home_view tool called");
+
+        self.response_tx
+            .send(OutboundResponse::HomeView {
+                user_id: user_id.clone(),
+                blocks: args.blocks,
+            })
+            .await
+            .map_err(|error| {
+                PublishHomeViewError(format!("failed to publish home view: {error}"))
+            })?;
+
+        Ok(PublishHomeViewOutput {
+            success: true,
+            user_id,
+            block_count,
+        })
+    }
+}
+
+/// Check the blocks look like a Block Kit view before handing them to Slack.
+fn validate_blocks(blocks: &[serde_json::Value]) -> Result<(), String> {
+    if blocks.is_empty() {
+        return Err("a home view needs at least one block".into());
+    }
+    if blocks.len() > MAX_HOME_VIEW_BLOCKS {
+        return Err(format!(
+            "a home view can have at most {MAX_HOME_VIEW_BLOCKS} blocks, got {}",
+            blocks.len()
+        ));
+    }
+    for (index, block) in blocks.iter().enumerate() {
+        if block.get("type").and_then(|kind| kind.as_str()).is_none() {
+            return Err(format!(
+                "block {index} is not a Block Kit block; every block needs a \"type\""
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_oversized_and_untyped_blocks() {
+        let section =
+            serde_json::json!({"type": "section", "text": {"type": "mrkdwn", "text": "hi"}});
+        assert!(validate_blocks(&[section.clone()]).is_ok());
+        assert!(validate_blocks(&[]).is_err());
+        assert!(validate_blocks(&vec![section; MAX_HOME_VIEW_BLOCKS + 1]).is_err());
+        assert!(validate_blocks(&[serde_json::json!({"text": "hi"})]).is_err());
+    }
+
+    #[tokio::test]
+    async fn publishes_to_the_turn_user() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let tool = PublishHomeViewTool::new(
+            RoutedSender::new(tx, crate::InboundMessage::empty()),
+            "U024BE7LH",
+        );
+
+        let output = tool
+            .call(PublishHomeViewArgs {
+                blocks: vec![serde_json::json!({"type": "divider"})],
+            })
+            .await
+            .unwrap();
+        assert_eq!(output.user_id, "U024BE7LH");
+        let routed = rx.recv().await.unwrap();
+        assert!(matches!(
+            routed.response,
+            OutboundResponse::HomeView { ref user_id, ref blocks }
+                if user_id == "U024BE7LH" && blocks.len() == 1
+        ));
+    }
+
+    #[test]
+    fn home_view_user_comes_from_the_event_or_cron_dm_target() {
+        let mut slack = crate::InboundMessage::empty();
+        slack.source = "slack".into();
+        slack.sender_id = "U024BE7LH".into();
+        assert_eq!(
+            home_view_user("slack:T1:D1", &slack).as_deref(),
+            Some("U024BE7LH")
+        );
+        assert_eq!(home_view_user("discord:1:2", &slack), None);
+
+        let mut cron = crate::InboundMessage::empty();
+        cron.source = "cron".into();
+        cron.sender_id = "system".into();
+        assert_eq!(home_view_user("cron:daily", &cron), None);
+        cron.metadata.insert(
+            crate::metadata_keys::CRON_DELIVERY_TARGET.into(),
+            serde_json::json!("slack:dm:U024BE7LH"),
+        );
+        assert_eq!(
+            home_view_user("cron:daily", &cron).as_deref(),
+            Some("U024BE7LH")
+        );
+        cron.metadata.insert(
+            crate::metadata_keys::CRON_DELIVERY_TARGET.into(),
+            serde_json::json!("slack:C0123"),
+        );
+        assert_eq!(home_view_user("cron:daily", &cron), None);
+    }
+}
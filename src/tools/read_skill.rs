@@ -1,9 +1,12 @@
-//! Read skill tool — lets workers read the full content of a named skill.
+//! Skill discovery tools — let workers list the available skills and read the
+//! full content of a named one.
 //!
 //! Workers see a listing of available skills (name + description) in their
 //! system prompt. When they decide a skill is relevant to their task, they
-//! call this tool to get the full instructions. This keeps the system prompt
+//! call read_skill to get the full instructions. This keeps the system prompt
 //! compact while still giving workers on-demand access to any skill.
+//! list_skills returns the same listing from the live skill set, so skills
+//! installed or removed after the prompt was built are still discoverable.
 
 use crate::config::RuntimeConfig;
 use rig::completion::ToolDefinition;
@@ -77,9 +80,75 @@ impl Tool for ReadSkillTool {
                 content: skill.content.clone(),
             }),
             None => Err(ReadSkillError(format!(
-                "skill '{}' not found. Available skills are listed in <available_skills> in your system prompt.",
+                "skill '{}' not found. Call list_skills to see the available skills.",
                 args.name
             ))),
         }
     }
 }
+
+/// Tool that lists the skills a worker can read.
+#[derive(Debug, Clone)]
+pub struct ListSkillsTool {
+    runtime_config: Arc<RuntimeConfig>,
+}
+
+impl ListSkillsTool {
+    pub fn new(runtime_config: Arc<RuntimeConfig>) -> Self {
+        Self { runtime_config }
+    }
+}
+
+/// Arguments for list_skills tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ListSkillsArgs {}
+
+/// A skill as shown by list_skills.
+#[derive(Debug, Serialize)]
+pub struct SkillSummary {
+    pub name: String,
+    pub description: String,
+}
+
+/// Output from list_skills tool.
+#[derive(Debug, Serialize)]
+pub struct ListSkillsOutput {
+    /// Available skills, sorted by name.
+    pub skills: Vec<SkillSummary>,
+}
+
+impl Tool for ListSkillsTool {
+    const NAME: &'static str = "list_skills";
+
+    type Error = ReadSkillError;
+    type Args = ListSkillsArgs;
+    type Output = ListSkillsOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: "List the skills you can read, with a one-line description of each. \
+                Use read_skill to load the full instructions for one."
+                .to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        }
+    }
+
+    async fn call(&self, _args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let skills = self
+            .runtime_config
+            .skills
+            .load()
+            .list()
+            .into_iter()
+            .map(|skill| SkillSummary {
+                name: skill.name,
+                description: skill.description,
+            })
+            .collect();
+        Ok(ListSkillsOutput { skills })
+    }
+}
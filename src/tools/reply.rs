@@ -45,6 +45,7 @@ pub struct ReplyTool {
     channel_id: ChannelId,
     replied_flag: RepliedFlag,
     agent_display_name: String,
+    output_language: Option<String>,
 }
 
 impl ReplyTool {
@@ -64,8 +65,15 @@ impl ReplyTool {
             channel_id,
             replied_flag,
             agent_display_name: agent_display_name.into(),
+            output_language: None,
         }
     }
+
+    /// Warn when replies don't appear to be written in this language.
+    pub fn with_output_language(mut self, output_language: Option<String>) -> Self {
+        self.output_language = output_language;
+        self
+    }
 }
 
 /// Error type for reply tool.
@@ -95,6 +103,10 @@ pub struct ReplyArgs {
     /// Optional: a poll to attach to the message.
     #[serde(default)]
     pub poll: Option<crate::Poll>,
+    /// Optional: show the reply only to the user who triggered this turn.
+    /// Honored on Slack, and on Discord when replying to an interaction.
+    #[serde(default)]
+    pub ephemeral: bool,
 }
 
 /// Output from reply tool.
@@ -302,7 +314,7 @@ impl Tool for ReplyTool {
                                     "type": "object",
                                     "properties": {
                                         "label": { "type": "string" },
-                                        "custom_id": { "type": "string", "description": "ID sent back to you when clicked" },
+                                        "custom_id": { "type": "string", "description": "ID sent back to you when clicked. Use `run_worker:<button name>` or `run_cron:<job id>` to run that worker button or cron job directly on click instead." },
                                         "style": { "type": "string", "enum": ["primary", "secondary", "success", "danger", "link"] },
                                         "url": { "type": "string", "description": "Required if style is link" }
                                     },
@@ -346,6 +358,10 @@ impl Tool for ReplyTool {
                         "duration_hours": { "type": "integer", "description": "Defaults to 24 if omitted" }
                     },
                     "required": ["question", "answers"]
+                },
+                "ephemeral": {
+                    "type": "boolean",
+                    "description": "Optional: show the reply only to the user who triggered this turn. Works on Slack, and on Discord when replying to a button click or select. Sent publicly elsewhere. Cannot be combined with thread_name, cards, interactive_elements, or poll."
                 }
             },
             "required": ["content"]
@@ -414,7 +430,23 @@ impl Tool for ReplyTool {
             ));
         }
 
-        let response = if let Some(name) = thread_name {
+        if let Some(language) = &self.output_language
+            && crate::agent::channel_prompt::reply_matches_language(&converted_content, language)
+                == Some(false)
+        {
+            tracing::warn!(
+                conversation_id = %self.conversation_id,
+                output_language = %language,
+                "reply does not appear to be in the configured output language"
+            );
+        }
+
+        let response = if args.ephemeral {
+            OutboundResponse::Ephemeral {
+                text: converted_content.clone(),
+                user_id: self.response_tx.target().sender_id.clone(),
+            }
+        } else if let Some(name) = thread_name {
             // Cap thread names at 100 characters (Discord limit)
             let thread_name = if name.len() > 100 {
                 name[..name.floor_char_boundary(100)].to_string()
@@ -0,0 +1,195 @@
+//! Handoff tool for escalating a conversation to a linked human.
+
+use crate::agent::channel::ChannelState;
+use crate::handoff::{self, HandoffStore};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool that notifies a human and pauses replies in the channel.
+#[derive(Debug, Clone)]
+pub struct RequestHumanTool {
+    state: ChannelState,
+    current_adapter: Option<String>,
+}
+
+impl RequestHumanTool {
+    /// Create a new handoff tool for a channel.
+    pub fn new(state: ChannelState, current_adapter: Option<String>) -> Self {
+        Self {
+            state,
+            current_adapter,
+        }
+    }
+}
+
+/// Error type for request_human tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Handoff failed: {0}")]
+pub struct RequestHumanError(String);
+
+/// Arguments for request_human tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RequestHumanArgs {
+    /// Why a human is needed, written for the person who will pick it up.
+    pub reason: String,
+    /// ID of the human to notify. Defaults to the most senior linked human.
+    #[serde(default)]
+    pub human: Option<String>,
+}
+
+/// Output from request_human tool.
+#[derive(Debug, Serialize)]
+pub struct RequestHumanOutput {
+    pub handoff_id: String,
+    pub human: String,
+    /// False when the channel was already waiting on a human.
+    pub newly_opened: bool,
+    pub message: String,
+}
+
+impl Tool for RequestHumanTool {
+    const NAME: &'static str = "request_human";
+
+    type Error = RequestHumanError;
+    type Args = RequestHumanArgs;
+    type Output = RequestHumanOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let humans = self.state.deps.humans.load();
+        let links = self.state.deps.links.load();
+        let human_ids: Vec<&str> =
+            handoff::linked_humans(&self.state.deps.agent_id, &links, &humans)
+                .into_iter()
+                .map(|human| human.id.as_str())
+                .collect();
+
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/request_human").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "reason": {
+                        "type": "string",
+                        "description": "Why a human is needed: the question, what you are unsure about, and anything they need to pick it up."
+                    },
+                    "human": {
+                        "type": "string",
+                        "enum": human_ids,
+                        "description": "Which human to notify. Omit to notify the most senior linked human."
+                    }
+                },
+                "required": ["reason"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let reason = args.reason.trim();
+        if reason.is_empty() {
+            return Err(RequestHumanError("reason must not be empty".to_string()));
+        }
+
+        let store = HandoffStore::new(self.state.deps.sqlite_pool.clone());
+        let channel_id = self.state.channel_id.as_ref();
+        if let Some(existing) = store
+            .open_for_channel(channel_id)
+            .await
+            .map_err(|error| RequestHumanError(format!("failed to check handoffs: {error}")))?
+        {
+            self.state.handoff.set(true);
+            return Ok(RequestHumanOutput {
+                handoff_id: existing.id,
+                human: existing.human_id,
+                newly_opened: false,
+                message: "A human was already asked to take over this conversation.".to_string(),
+            });
+        }
+
+        // Owned snapshots: the chosen human is used across the sends below.
+        let humans = self.state.deps.humans.load_full();
+        let links = self.state.deps.links.load_full();
+        let linked = handoff::linked_humans(&self.state.deps.agent_id, &links, &humans);
+        let human = match args.human.as_deref() {
+            Some(id) => linked
+                .into_iter()
+                .find(|human| human.id == id)
+                .ok_or_else(|| RequestHumanError(format!("'{id}' is not a human linked to you")))?,
+            None => linked
+                .into_iter()
+                .next()
+                .ok_or_else(|| RequestHumanError("no human is linked to this agent".to_string()))?,
+        };
+
+        let messaging_manager = self.state.deps.messaging_manager.as_ref().ok_or_else(|| {
+            RequestHumanError("messaging is not configured, so no one can be notified".to_string())
+        })?;
+        let agent_name = self
+            .state
+            .deps
+            .agent_names
+            .get(self.state.deps.agent_id.as_ref())
+            .cloned()
+            .unwrap_or_else(|| self.state.deps.agent_id.to_string());
+        let notification = format!(
+            "{agent_name} needs a human in {channel_id}.\n\
+             Reason: {reason}\n\
+             It won't reply there until the handoff is resolved."
+        );
+
+        let mut notified_target = None;
+        for target in handoff::notification_targets(human, self.current_adapter.as_deref()) {
+            match messaging_manager
+                .broadcast(
+                    &self.state.deps.agent_id,
+                    &target.adapter,
+                    &target.target,
+                    crate::OutboundResponse::Text(notification.clone()),
+                )
+                .await
+            {
+                Ok(()) => {
+                    notified_target = Some(target.to_string());
+                    break;
+                }
+                Err(error) => tracing::warn!(
+                    human_id = %human.id,
+                    adapter = %target.adapter,
+                    %error,
+                    "failed to deliver handoff notification, trying next target"
+                ),
+            }
+        }
+        let Some(notified_target) = notified_target else {
+            return Err(RequestHumanError(format!(
+                "could not reach {}; they need a handoff_target or a platform ID for a \
+                 connected adapter",
+                human.id
+            )));
+        };
+
+        let handoff = store
+            .open(channel_id, &human.id, reason, Some(&notified_target))
+            .await
+            .map_err(|error| RequestHumanError(format!("failed to open handoff: {error}")))?;
+        self.state.handoff.set(true);
+
+        tracing::info!(
+            channel_id = %channel_id,
+            human_id = %human.id,
+            handoff_id = %handoff.id,
+            "conversation handed off to a human"
+        );
+
+        Ok(RequestHumanOutput {
+            handoff_id: handoff.id,
+            human: human.id.clone(),
+            newly_opened: true,
+            message: "A human has been notified. Tell the user a person will follow up. You \
+                      won't reply in this channel again until the handoff is resolved."
+                .to_string(),
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! Reset tool for clearing a channel's conversation context.
+
+use crate::agent::channel::ChannelState;
+use crate::agent::channel_reset;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tool that drops the channel's history and background work.
+#[derive(Debug, Clone)]
+pub struct ResetConversationTool {
+    state: ChannelState,
+}
+
+impl ResetConversationTool {
+    /// Create a new reset tool with access to channel state.
+    pub fn new(state: ChannelState) -> Self {
+        Self { state }
+    }
+}
+
+/// Error type for reset tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Reset failed: {0}")]
+pub struct ResetConversationError(String);
+
+/// Arguments for reset tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResetConversationArgs {
+    /// Also forget the memories saved from this channel.
+    #[serde(default)]
+    pub forget_channel_memories: bool,
+}
+
+/// Output from reset tool.
+#[derive(Debug, Serialize)]
+pub struct ResetConversationOutput {
+    pub reset: bool,
+    pub messages_cleared: usize,
+    pub workers_cancelled: usize,
+    pub branches_cancelled: usize,
+    pub memories_forgotten: u64,
+    pub message: String,
+}
+
+impl Tool for ResetConversationTool {
+    const NAME: &'static str = "reset_conversation";
+
+    type Error = ResetConversationError;
+    type Args = ResetConversationArgs;
+    type Output = ResetConversationOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/reset_conversation").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "forget_channel_memories": {
+                        "type": "boolean",
+                        "default": false,
+                        "description": "Also forget every memory saved from this channel. Only when the user asks for that explicitly."
+                    }
+                }
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let reset =
+            channel_reset::reset_conversation(&self.state, args.forget_channel_memories).await;
+        // The channel loop clears the rest once this turn ends.
+        self.state.reset_signal.request(false);
+
+        Ok(ResetConversationOutput {
+            reset: true,
+            messages_cleared: reset.messages_cleared,
+            workers_cancelled: reset.workers_cancelled,
+            branches_cancelled: reset.branches_cancelled,
+            memories_forgotten: reset.memories_forgotten,
+            message: "Conversation reset. This turn is discarded once it ends; confirm the \
+                      reset to the user in a short reply."
+                .to_string(),
+        })
+    }
+}
@@ -0,0 +1,270 @@
+//! Size caps for tool results, applied to every tool a factory registers.
+
+use crate::config::ToolResultLimits;
+use crate::tools::truncate_tool_result;
+
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long saved full results are kept before they are pruned.
+pub const FULL_RESULT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Result caps for one tool server.
+#[derive(Debug, Clone)]
+pub struct ResultLimiter {
+    limits: ToolResultLimits,
+    /// Where oversized results are saved in full. None skips saving.
+    full_result_dir: Option<PathBuf>,
+}
+
+impl ResultLimiter {
+    /// Oversized results are saved under `workspace/.spacebot/tool-results`
+    /// when `save_full_results` is enabled.
+    pub fn new(limits: &ToolResultLimits, workspace: &Path) -> Self {
+        Self {
+            limits: limits.clone(),
+            full_result_dir: limits
+                .save_full_results
+                .then(|| workspace.join(".spacebot").join("tool-results")),
+        }
+    }
+
+    /// Wrap `tool` so its results are capped.
+    pub fn wrap<T: Tool>(&self, tool: T) -> LimitedTool<T> {
+        LimitedTool {
+            max_bytes: self.limits.max_bytes_for(&tool.name()),
+            full_result_dir: self.full_result_dir.clone(),
+            inner: tool,
+        }
+    }
+}
+
+/// A tool whose serialized result is cut to a byte cap.
+pub struct LimitedTool<T> {
+    inner: T,
+    max_bytes: usize,
+    full_result_dir: Option<PathBuf>,
+}
+
+/// A result that was cut to the tool's cap.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TruncatedToolResult {
+    /// The start of the serialized result, ending with a `[truncated N bytes]`
+    /// marker.
+    pub result: String,
+    pub truncated_bytes: usize,
+    /// Where the untruncated result was saved, if it was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_result_path: Option<String>,
+}
+
+/// Output of a [`LimitedTool`]: the tool's own output when it fits, otherwise
+/// the truncated result.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum LimitedToolOutput<O> {
+    Full(O),
+    Truncated(TruncatedToolResult),
+}
+
+/// Bytes cut from a tool result, read from a serialized [`TruncatedToolResult`].
+pub fn truncated_bytes(result: &str) -> Option<usize> {
+    serde_json::from_str::<TruncatedToolResult>(result)
+        .ok()
+        .map(|truncated| truncated.truncated_bytes)
+}
+
+impl<T: Tool> Tool for LimitedTool<T> {
+    const NAME: &'static str = T::NAME;
+
+    type Error = T::Error;
+    type Args = T::Args;
+    type Output = LimitedToolOutput<T::Output>;
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    async fn definition(&self, prompt: String) -> ToolDefinition {
+        self.inner.definition(prompt).await
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let output = self.inner.call(args).await?;
+        let serialized = match serde_json::to_string(&output) {
+            Ok(serialized) if serialized.len() > self.max_bytes => serialized,
+            // Fits, or fails to serialize the same way it would unwrapped.
+            _ => return Ok(LimitedToolOutput::Full(output)),
+        };
+        Ok(LimitedToolOutput::Truncated(
+            self.truncate(serialized).await,
+        ))
+    }
+}
+
+impl<T: Tool> LimitedTool<T> {
+    /// Truncate an oversized result to the tool's cap, saving the full text
+    /// first when a result directory is configured.
+    async fn truncate(&self, serialized: String) -> TruncatedToolResult {
+        let tool_name = self.inner.name();
+        let saved_to = match &self.full_result_dir {
+            Some(dir) => save_full_result(dir, &tool_name, &serialized).await,
+            None => None,
+        };
+
+        tracing::debug!(
+            tool = %tool_name,
+            result_bytes = serialized.len(),
+            max_result_bytes = self.max_bytes,
+            saved_to = ?saved_to,
+            "tool result truncated"
+        );
+
+        let result = truncate_tool_result(&serialized, self.max_bytes, saved_to.as_deref());
+        TruncatedToolResult {
+            truncated_bytes: serialized.len()
+                - crate::tools::truncate_at_char_boundary(&serialized, self.max_bytes),
+            full_result_path: saved_to.map(|path| path.display().to_string()),
+            result,
+        }
+    }
+}
+
+async fn save_full_result(dir: &Path, tool_name: &str, output_text: &str) -> Option<PathBuf> {
+    let path = dir.join(format!("{tool_name}-{}.txt", uuid::Uuid::new_v4()));
+    let result = async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&path, output_text).await
+    }
+    .await;
+
+    match result {
+        Ok(()) => {
+            prune_full_results(dir, FULL_RESULT_RETENTION).await;
+            Some(path)
+        }
+        Err(error) => {
+            tracing::warn!(
+                tool = %tool_name,
+                path = %path.display(),
+                %error,
+                "failed to save full tool result"
+            );
+            None
+        }
+    }
+}
+
+/// Delete saved results older than `retention`. Best effort: failures are
+/// logged and the rest of the directory is still checked.
+async fn prune_full_results(dir: &Path, retention: Duration) {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(error) => {
+            tracing::debug!(path = %dir.display(), %error, "failed to read tool result directory");
+            return;
+        }
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let expired = entry
+            .metadata()
+            .await
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= retention);
+        if expired && let Err(error) = tokio::fs::remove_file(entry.path()).await {
+            tracing::debug!(
+                path = %entry.path().display(),
+                %error,
+                "failed to prune saved tool result"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("echo failed")]
+    struct EchoError;
+
+    struct EchoTool;
+
+    impl Tool for EchoTool {
+        const NAME: &'static str = "echo";
+
+        type Error = EchoError;
+        type Args = String;
+        type Output = String;
+
+        async fn definition(&self, _prompt: String) -> ToolDefinition {
+            ToolDefinition {
+                name: Self::NAME.to_string(),
+                description: String::new(),
+                parameters: serde_json::json!({}),
+            }
+        }
+
+        async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+            Ok(args)
+        }
+    }
+
+    fn limits(max_bytes: usize) -> ToolResultLimits {
+        ToolResultLimits {
+            max_bytes,
+            save_full_results: false,
+            ..ToolResultLimits::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn results_over_the_cap_report_truncated_bytes() {
+        let tool = ResultLimiter::new(&limits(100), Path::new("/unused")).wrap(EchoTool);
+
+        let output = tool.call("short".into()).await.expect("echo");
+        let serialized = serde_json::to_string(&output).unwrap();
+        assert_eq!(serialized, "\"short\"");
+        assert_eq!(truncated_bytes(&serialized), None);
+
+        let output = tool.call("a".repeat(150)).await.expect("echo");
+        let serialized = serde_json::to_string(&output).unwrap();
+        // 150 bytes plus the two quotes, cut to 100.
+        assert_eq!(truncated_bytes(&serialized), Some(52));
+        let LimitedToolOutput::Truncated(truncated) = output else {
+            panic!("expected a truncated result");
+        };
+        assert!(truncated.result.ends_with("[truncated 52 bytes]"));
+        assert_eq!(truncated.full_result_path, None);
+    }
+
+    #[tokio::test]
+    async fn full_results_are_saved_and_old_ones_pruned() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let limits = ToolResultLimits {
+            save_full_results: true,
+            ..limits(10)
+        };
+        let tool = ResultLimiter::new(&limits, workspace.path()).wrap(EchoTool);
+
+        let output = tool.call("a".repeat(50)).await.expect("echo");
+        let LimitedToolOutput::Truncated(truncated) = output else {
+            panic!("expected a truncated result");
+        };
+        let path = PathBuf::from(truncated.full_result_path.expect("saved path"));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            format!("\"{}\"", "a".repeat(50))
+        );
+
+        prune_full_results(path.parent().unwrap(), Duration::ZERO).await;
+        assert!(!path.exists());
+    }
+}
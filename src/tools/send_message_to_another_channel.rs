@@ -1,9 +1,9 @@
 //! Send message tool for cross-channel messaging and DMs.
 
-use crate::ChannelId;
 use crate::conversation::ChannelStore;
 use crate::conversation::history::ConversationLogger;
 use crate::messaging::MessagingManager;
+use crate::{AgentId, ChannelId};
 
 use rig::completion::ToolDefinition;
 use rig::tool::Tool;
@@ -25,6 +25,7 @@ fn is_valid_uuid(s: &str) -> bool {
 /// channel's conversation history so it appears in future transcripts.
 #[derive(Clone)]
 pub struct SendMessageTool {
+    agent_id: AgentId,
     messaging_manager: Arc<MessagingManager>,
     channel_store: ChannelStore,
     conversation_logger: ConversationLogger,
@@ -40,6 +41,7 @@ impl std::fmt::Debug for SendMessageTool {
 
 impl SendMessageTool {
     pub fn new(
+        agent_id: AgentId,
         messaging_manager: Arc<MessagingManager>,
         channel_store: ChannelStore,
         conversation_logger: ConversationLogger,
@@ -47,6 +49,7 @@ impl SendMessageTool {
         current_adapter: Option<String>,
     ) -> Self {
         Self {
+            agent_id,
             messaging_manager,
             channel_store,
             conversation_logger,
@@ -161,6 +164,7 @@ impl Tool for SendMessageTool {
 
             self.messaging_manager
                 .broadcast(
+                    &self.agent_id,
                     &target.adapter,
                     &target.target,
                     crate::OutboundResponse::Text(args.message),
@@ -193,6 +197,7 @@ impl Tool for SendMessageTool {
                 Ok(Some(target)) => {
                     self.messaging_manager
                         .broadcast(
+                            &self.agent_id,
                             &target.adapter,
                             &target.target,
                             crate::OutboundResponse::Text(args.message),
@@ -227,6 +232,7 @@ impl Tool for SendMessageTool {
         if let Some(explicit_target) = parse_explicit_email_target(&args.target) {
             self.messaging_manager
                 .broadcast(
+                    &self.agent_id,
                     &explicit_target.adapter,
                     &explicit_target.target,
                     crate::OutboundResponse::Text(args.message),
@@ -279,6 +285,7 @@ impl Tool for SendMessageTool {
 
         self.messaging_manager
             .broadcast(
+                &self.agent_id,
                 &broadcast_target.adapter,
                 &broadcast_target.target,
                 crate::OutboundResponse::Text(args.message.clone()),
@@ -0,0 +1,210 @@
+//! Send sticker tool for posting platform stickers (channel only).
+
+use crate::{OutboundResponse, RoutedSender};
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Longest Telegram `file_id` we accept. Real IDs are well under this.
+const MAX_TELEGRAM_FILE_ID_LENGTH: usize = 255;
+
+/// Tool for sending a sticker to the current conversation.
+#[derive(Debug, Clone)]
+pub struct SendStickerTool {
+    response_tx: RoutedSender,
+    /// Platform of the conversation (e.g. "discord", "telegram").
+    platform: String,
+}
+
+impl SendStickerTool {
+    pub fn new(response_tx: RoutedSender, conversation_id: &str) -> Self {
+        let platform = conversation_id
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Self {
+            response_tx,
+            platform,
+        }
+    }
+}
+
+/// Error type for send sticker tool.
+#[derive(Debug, thiserror::Error)]
+#[error("Send sticker failed: {0}")]
+pub struct SendStickerError(String);
+
+/// Arguments for send sticker tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SendStickerArgs {
+    /// The sticker ID. A numeric sticker ID on Discord, a sticker file_id on Telegram.
+    pub id: String,
+    /// Emoji or short text to send instead on platforms without stickers.
+    #[serde(default)]
+    pub fallback: Option<String>,
+}
+
+/// Output from send sticker tool.
+#[derive(Debug, Serialize)]
+pub struct SendStickerOutput {
+    pub success: bool,
+    /// "sticker" when sent as a sticker, "fallback" when the fallback text was sent instead.
+    pub delivered_as: String,
+}
+
+impl Tool for SendStickerTool {
+    const NAME: &'static str = "send_sticker";
+
+    type Error = SendStickerError;
+    type Args = SendStickerArgs;
+    type Output = SendStickerOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/send_sticker").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "string",
+                        "description": "The sticker ID. On Discord, the numeric sticker ID. On Telegram, the sticker's file_id."
+                    },
+                    "fallback": {
+                        "type": "string",
+                        "description": "Emoji or short text sent instead on platforms without sticker support (e.g. \"🎉\")."
+                    }
+                },
+                "required": ["id"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let id = args.id.trim().to_string();
+        let fallback = args
+            .fallback
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+
+        let delivered_as = if supports_stickers(&self.platform) {
+            validate_sticker_id(&self.platform, &id).map_err(SendStickerError)?;
+            "sticker"
+        } else if fallback.is_some() {
+            "fallback"
+        } else {
+            return Err(SendStickerError(format!(
+                "{} doesn't support stickers; pass a fallback emoji or text to send instead",
+                self.platform
+            )));
+        };
+
+        tracing::info!(
+            platform = %self.platform,
+            sticker_id = %id,
+            delivered_as,
+            "send_sticker tool called"
+        );
+
+        self.response_tx
+            .send(OutboundResponse::Sticker { id, fallback })
+            .await
+            .map_err(|error| SendStickerError(format!("failed to send sticker: {error}")))?;
+
+        Ok(SendStickerOutput {
+            success: true,
+            delivered_as: delivered_as.to_string(),
+        })
+    }
+}
+
+/// Whether the platform's adapter sends `OutboundResponse::Sticker` natively.
+fn supports_stickers(platform: &str) -> bool {
+    matches!(platform, "discord" | "telegram")
+}
+
+/// Check a sticker ID against the platform's ID format.
+fn validate_sticker_id(platform: &str, id: &str) -> Result<(), String> {
+    match platform {
+        "discord" => {
+            let valid = id.bytes().all(|byte| byte.is_ascii_digit())
+                && id.parse::<u64>().is_ok_and(|id| id != 0);
+            if valid {
+                Ok(())
+            } else {
+                Err(format!(
+                    "'{id}' is not a Discord sticker ID; expected a numeric ID like 749054660769218631"
+                ))
+            }
+        }
+        "telegram" => {
+            let valid = !id.is_empty()
+                && id.len() <= MAX_TELEGRAM_FILE_ID_LENGTH
+                && id
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+            if valid {
+                Ok(())
+            } else {
+                Err(format!(
+                    "'{id}' is not a Telegram sticker file_id; expected the file_id of a sticker message"
+                ))
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discord_sticker_ids_must_be_numeric_snowflakes() {
+        assert!(validate_sticker_id("discord", "749054660769218631").is_ok());
+        assert!(validate_sticker_id("discord", "0").is_err());
+        assert!(validate_sticker_id("discord", "+749054660769218631").is_err());
+        assert!(validate_sticker_id("discord", "<:party:749054660769218631>").is_err());
+        assert!(validate_sticker_id("discord", "99999999999999999999999").is_err());
+    }
+
+    #[test]
+    fn telegram_sticker_ids_must_be_file_ids() {
+        assert!(validate_sticker_id("telegram", "CAACAgIAAxkBAAEBQ2Zg-example_Id").is_ok());
+        assert!(validate_sticker_id("telegram", "").is_err());
+        assert!(validate_sticker_id("telegram", "not a file id").is_err());
+        assert!(validate_sticker_id("telegram", &"A".repeat(300)).is_err());
+    }
+
+    #[tokio::test]
+    async fn unsupported_platforms_need_a_fallback() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let response_tx = RoutedSender::new(tx, crate::InboundMessage::empty());
+        let tool = SendStickerTool::new(response_tx, "slack:T012:C034");
+
+        let error = tool
+            .call(SendStickerArgs {
+                id: "party".into(),
+                fallback: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("slack doesn't support stickers"));
+
+        let output = tool
+            .call(SendStickerArgs {
+                id: "party".into(),
+                fallback: Some("🎉".into()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(output.delivered_as, "fallback");
+        let routed = rx.recv().await.unwrap();
+        assert!(matches!(
+            routed.response,
+            OutboundResponse::Sticker { fallback: Some(ref text), .. } if text == "🎉"
+        ));
+    }
+}
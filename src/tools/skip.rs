@@ -82,10 +82,12 @@ impl Tool for SkipTool {
     async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
         self.flag.store(true, Ordering::Relaxed);
 
-        // Cancel the typing indicator so it doesn't linger
+        // Cancels the typing indicator, and tells cron runs not to deliver.
         let _ = self
             .response_tx
-            .send(OutboundResponse::Status(crate::StatusUpdate::StopTyping))
+            .send(OutboundResponse::Status(crate::StatusUpdate::Skipped {
+                reason: args.reason.clone(),
+            }))
             .await;
 
         let reason = args.reason.as_deref().unwrap_or("no reason given");
@@ -0,0 +1,177 @@
+//! Conversation slot tools for channels: set and read exact key/value state
+//! scoped to the current conversation.
+
+use crate::ChannelId;
+use crate::settings::SettingsStore;
+use rig::completion::ToolDefinition;
+use rig::tool::Tool;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Error type for the slot tools.
+#[derive(Debug, thiserror::Error)]
+#[error("Slot operation failed: {0}")]
+pub struct SlotError(String);
+
+/// Tool for setting or clearing a slot in the current conversation.
+#[derive(Debug, Clone)]
+pub struct SetSlotTool {
+    settings: Arc<SettingsStore>,
+    channel_id: ChannelId,
+}
+
+impl SetSlotTool {
+    pub fn new(settings: Arc<SettingsStore>, channel_id: ChannelId) -> Self {
+        Self {
+            settings,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for set_slot tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetSlotArgs {
+    /// Slot name, e.g. `user_name` or `order_id`.
+    pub key: String,
+    /// Value to store. An empty value clears the slot.
+    pub value: String,
+}
+
+/// Output from set_slot tool.
+#[derive(Debug, Serialize)]
+pub struct SetSlotOutput {
+    pub success: bool,
+    pub key: String,
+    /// The stored value, or None when the slot was cleared.
+    pub value: Option<String>,
+}
+
+impl Tool for SetSlotTool {
+    const NAME: &'static str = "set_slot";
+
+    type Error = SlotError;
+    type Args = SetSlotArgs;
+    type Output = SetSlotOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/set_slot").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "key": {
+                        "type": "string",
+                        "description": "Slot name: letters, digits, '_', '-', or '.', e.g. \"user_name\" or \"order_id\"."
+                    },
+                    "value": {
+                        "type": "string",
+                        "description": "Value to store, replacing any previous one. Pass an empty string to clear the slot."
+                    }
+                },
+                "required": ["key", "value"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let key = args.key.trim().to_string();
+        if args.value.trim().is_empty() {
+            self.settings
+                .clear_conversation_slot(&self.channel_id, &key)
+                .map_err(|error| SlotError(error.to_string()))?;
+            tracing::info!(channel_id = %self.channel_id, slot = %key, "slot cleared");
+            return Ok(SetSlotOutput {
+                success: true,
+                key,
+                value: None,
+            });
+        }
+
+        let slot = self
+            .settings
+            .set_conversation_slot(&self.channel_id, &key, &args.value)
+            .map_err(|error| SlotError(error.to_string()))?;
+
+        tracing::info!(channel_id = %self.channel_id, slot = %key, "slot set");
+
+        Ok(SetSlotOutput {
+            success: true,
+            key,
+            value: Some(slot.value),
+        })
+    }
+}
+
+/// Tool for reading a slot in the current conversation.
+#[derive(Debug, Clone)]
+pub struct GetSlotTool {
+    settings: Arc<SettingsStore>,
+    channel_id: ChannelId,
+}
+
+impl GetSlotTool {
+    pub fn new(settings: Arc<SettingsStore>, channel_id: ChannelId) -> Self {
+        Self {
+            settings,
+            channel_id,
+        }
+    }
+}
+
+/// Arguments for get_slot tool.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetSlotArgs {
+    /// Slot name to read.
+    pub key: String,
+}
+
+/// Output from get_slot tool.
+#[derive(Debug, Serialize)]
+pub struct GetSlotOutput {
+    pub key: String,
+    /// The stored value, or None when the slot isn't set.
+    pub value: Option<String>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Tool for GetSlotTool {
+    const NAME: &'static str = "get_slot";
+
+    type Error = SlotError;
+    type Args = GetSlotArgs;
+    type Output = GetSlotOutput;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: crate::prompts::text::get("tools/get_slot").to_string(),
+            parameters: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "key": {
+                        "type": "string",
+                        "description": "Slot name to read."
+                    }
+                },
+                "required": ["key"]
+            }),
+        }
+    }
+
+    async fn call(&self, args: Self::Args) -> Result<Self::Output, Self::Error> {
+        let key = args.key.trim().to_string();
+        let slot = self
+            .settings
+            .conversation_slots_for(&self.channel_id)
+            .remove(&key);
+
+        Ok(GetSlotOutput {
+            key,
+            value: slot.as_ref().map(|slot| slot.value.clone()),
+            updated_at: slot.map(|slot| slot.updated_at),
+        })
+    }
+}
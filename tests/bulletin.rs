@@ -107,7 +107,7 @@ async fn bootstrap_deps() -> anyhow::Result<spacebot::AgentDeps> {
     );
 
     Ok(spacebot::AgentDeps {
-        agent_id,
+        agent_id: agent_id.clone(),
         memory_search,
         llm_manager,
         mcp_manager,
@@ -129,6 +129,9 @@ async fn bootstrap_deps() -> anyhow::Result<spacebot::AgentDeps> {
         process_control_registry: Arc::new(
             spacebot::agent::process_control::ProcessControlRegistry::new(),
         ),
+        process_limiter: Arc::new(spacebot::agent::process_limit::ProcessLimiter::new(
+            agent_id,
+        )),
         injection_tx: tokio::sync::mpsc::channel(1).0,
         working_memory: spacebot::memory::WorkingMemoryStore::new(
             db.sqlite.clone(),
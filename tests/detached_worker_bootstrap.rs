@@ -36,6 +36,7 @@ async fn detached_bootstrap_rolls_back_control_entry_on_database_failure() {
             created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
             updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
             completed_at TIMESTAMP,
+            dedupe_key TEXT,
             UNIQUE(agent_id, task_number)
         )",
     )